@@ -7,6 +7,9 @@
  *
  * The `command` tokens form the prefix that would be added as an execpolicy
  * `prefix_rule(..., decision="allow")`, letting the agent bypass approval for
- * commands that start with this token sequence.
+ * commands that start with this token sequence. `args` and `not_matching` hold
+ * argument clauses in policy syntax, such as `flag("--force", "-f")`, that are
+ * persisted with the rule. Without clauses the amendment is serialized as the
+ * bare `command` array.
  */
-export type ExecPolicyAmendment = Array<string>;
+export type ExecPolicyAmendment = Array<string> | { command: Array<string>, args?: Array<string>, not_matching?: Array<string>, };
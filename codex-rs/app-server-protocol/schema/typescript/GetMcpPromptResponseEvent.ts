@@ -0,0 +1,12 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Response payload for `Op::GetMcpPrompt`.
+ */
+export type GetMcpPromptResponseEvent = { server: string, name: string, 
+/**
+ * The prompt's messages joined into a single block of text, or an error.
+ */
+result: { Ok : string } | { Err : string }, };
@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Prompt } from "./Prompt";
+
+/**
+ * Response payload for `Op::ListMcpPrompts`.
+ */
+export type ListMcpPromptsResponseEvent = { 
+/**
+ * Prompts grouped by server name.
+ */
+prompts: { [key in string]?: Array<Prompt> }, };
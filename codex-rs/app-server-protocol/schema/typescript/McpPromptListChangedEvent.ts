@@ -0,0 +1,13 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Prompt } from "./Prompt";
+
+/**
+ * Sent when an MCP server emits `notifications/prompts/list_changed`.
+ */
+export type McpPromptListChangedEvent = { server: string, 
+/**
+ * The server's prompts after the change.
+ */
+prompts: Array<Prompt>, };
@@ -2,4 +2,4 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type ModelRerouteReason = "high_risk_cyber_activity";
+export type ModelRerouteReason = "high_risk_cyber_activity" | "rate_limited" | "provider_unavailable" | "context_window_exceeded";
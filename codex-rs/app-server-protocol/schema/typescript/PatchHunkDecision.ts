@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PatchHunkAction } from "./PatchHunkAction";
+
+/**
+ * The reviewer's decision on one hunk of a proposed patch.
+ */
+export type PatchHunkDecision = { 
+/**
+ * Path of the file, as keyed in the approval request's `changes`.
+ */
+path: string, 
+/**
+ * Zero-based index into [`FileChange::hunks`] for the file.
+ */
+hunk_index: number, action: PatchHunkAction, };
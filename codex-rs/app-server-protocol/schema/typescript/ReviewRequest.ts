@@ -6,4 +6,9 @@ import type { ReviewTarget } from "./ReviewTarget";
 /**
  * Review request sent to the review session.
  */
-export type ReviewRequest = { target: ReviewTarget, user_facing_hint?: string, };
+export type ReviewRequest = { target: ReviewTarget, user_facing_hint?: string, 
+/**
+ * Only review changes to files matching these git pathspecs (for example
+ * `src/api/**`). Empty means every changed file.
+ */
+paths?: Array<string>, };
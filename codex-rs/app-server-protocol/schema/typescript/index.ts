@@ -79,6 +79,7 @@ export type { GetAuthStatusResponse } from "./GetAuthStatusResponse";
 export type { GetConversationSummaryParams } from "./GetConversationSummaryParams";
 export type { GetConversationSummaryResponse } from "./GetConversationSummaryResponse";
 export type { GetHistoryEntryResponseEvent } from "./GetHistoryEntryResponseEvent";
+export type { GetMcpPromptResponseEvent } from "./GetMcpPromptResponseEvent";
 export type { GhostCommit } from "./GhostCommit";
 export type { GitDiffToRemoteParams } from "./GitDiffToRemoteParams";
 export type { GitDiffToRemoteResponse } from "./GitDiffToRemoteResponse";
@@ -95,6 +96,7 @@ export type { InputModality } from "./InputModality";
 export type { ItemCompletedEvent } from "./ItemCompletedEvent";
 export type { ItemStartedEvent } from "./ItemStartedEvent";
 export type { ListCustomPromptsResponseEvent } from "./ListCustomPromptsResponseEvent";
+export type { ListMcpPromptsResponseEvent } from "./ListMcpPromptsResponseEvent";
 export type { ListRemoteSkillsResponseEvent } from "./ListRemoteSkillsResponseEvent";
 export type { ListSkillsResponseEvent } from "./ListSkillsResponseEvent";
 export type { LocalShellAction } from "./LocalShellAction";
@@ -106,6 +108,7 @@ export type { MacOsSeatbeltProfileExtensions } from "./MacOsSeatbeltProfileExten
 export type { McpAuthStatus } from "./McpAuthStatus";
 export type { McpInvocation } from "./McpInvocation";
 export type { McpListToolsResponseEvent } from "./McpListToolsResponseEvent";
+export type { McpPromptListChangedEvent } from "./McpPromptListChangedEvent";
 export type { McpStartupCompleteEvent } from "./McpStartupCompleteEvent";
 export type { McpStartupFailure } from "./McpStartupFailure";
 export type { McpStartupStatus } from "./McpStartupStatus";
@@ -126,12 +129,16 @@ export type { ParsedCommand } from "./ParsedCommand";
 export type { PatchApplyBeginEvent } from "./PatchApplyBeginEvent";
 export type { PatchApplyEndEvent } from "./PatchApplyEndEvent";
 export type { PatchApplyStatus } from "./PatchApplyStatus";
+export type { PatchHunkAction } from "./PatchHunkAction";
+export type { PatchHunkDecision } from "./PatchHunkDecision";
 export type { PermissionProfile } from "./PermissionProfile";
 export type { Personality } from "./Personality";
 export type { PlanDeltaEvent } from "./PlanDeltaEvent";
 export type { PlanItem } from "./PlanItem";
 export type { PlanItemArg } from "./PlanItemArg";
 export type { PlanType } from "./PlanType";
+export type { Prompt } from "./Prompt";
+export type { PromptArgument } from "./PromptArgument";
 export type { PromptTraceResponseEvent } from "./PromptTraceResponseEvent";
 export type { PromptTraceScope } from "./PromptTraceScope";
 export type { PromptTraceSection } from "./PromptTraceSection";
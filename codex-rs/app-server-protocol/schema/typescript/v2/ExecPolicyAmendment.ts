@@ -2,4 +2,9 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type ExecPolicyAmendment = Array<string>;
+/**
+ * Proposed execpolicy prefix rule. Serialized as the bare `command` array, or
+ * as an object when argument clauses such as `flag("--force", "-f")` are
+ * attached.
+ */
+export type ExecPolicyAmendment = Array<string> | { command: Array<string>, args?: Array<string>, notMatching?: Array<string>, };
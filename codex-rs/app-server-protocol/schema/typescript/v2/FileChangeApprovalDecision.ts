@@ -1,5 +1,6 @@
 // GENERATED CODE! DO NOT MODIFY BY HAND!
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FileChangeHunkDecision } from "./FileChangeHunkDecision";
 
-export type FileChangeApprovalDecision = "accept" | "acceptForSession" | "decline" | "cancel";
+export type FileChangeApprovalDecision = "accept" | "acceptForSession" | { "acceptHunks": { hunks: Array<FileChangeHunkDecision>, } } | "decline" | "cancel";
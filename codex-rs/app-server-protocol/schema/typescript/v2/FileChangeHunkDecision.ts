@@ -0,0 +1,15 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FileChangeHunkAction } from "./FileChangeHunkAction";
+
+export type FileChangeHunkDecision = { 
+/**
+ * Path of the file, as reported in the item's `changes`.
+ */
+path: string, 
+/**
+ * Zero-based index of the hunk: each `@@` section of an updated file's diff, or a single
+ * hunk covering the whole file for added and deleted files.
+ */
+hunkIndex: number, action: FileChangeHunkAction, };
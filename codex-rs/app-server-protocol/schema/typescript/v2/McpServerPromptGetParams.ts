@@ -0,0 +1,17 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type McpServerPromptGetParams = { 
+/**
+ * Name of the MCP server offering the prompt.
+ */
+server: string, 
+/**
+ * Name of the prompt, as listed in `McpServerStatus.prompts`.
+ */
+name: string, 
+/**
+ * Argument values keyed by argument name.
+ */
+arguments?: { [key in string]?: string } | null, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type McpServerPromptGetResponse = { 
+/**
+ * The prompt's messages joined into a single block of text, suitable for
+ * placing in a composer.
+ */
+text: string, };
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Prompt } from "../Prompt";
+
+/**
+ * Notification emitted when an MCP server used by a thread reports that its
+ * prompts changed.
+ */
+export type McpServerPromptsChangedNotification = { threadId: string, server: string, 
+/**
+ * The server's prompts after the change.
+ */
+prompts: Array<Prompt>, };
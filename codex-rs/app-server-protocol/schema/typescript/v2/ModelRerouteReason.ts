@@ -2,4 +2,4 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type ModelRerouteReason = "highRiskCyberActivity";
+export type ModelRerouteReason = "highRiskCyberActivity" | "rateLimited" | "providerUnavailable" | "contextWindowExceeded";
@@ -0,0 +1,19 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SandboxMode } from "./SandboxMode";
+import type { ScheduleThreadMode } from "./ScheduleThreadMode";
+
+export type Schedule = { id: string, name: string, 
+/**
+ * Five-field cron expression, evaluated in the server's local time zone.
+ */
+cron: string, prompt: string, cwd: string, profile: string | null, sandbox: SandboxMode | null, threadMode: ScheduleThreadMode, 
+/**
+ * Thread the next `resume` run continues.
+ */
+threadId: string | null, enabled: boolean, 
+/**
+ * Next run time (Unix seconds).
+ */
+nextRunAt: number | null, createdAt: number, updatedAt: number, };
@@ -0,0 +1,31 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SandboxMode } from "./SandboxMode";
+import type { ScheduleThreadMode } from "./ScheduleThreadMode";
+
+export type ScheduleCreateParams = { 
+/**
+ * Display name; defaults to the start of the prompt.
+ */
+name?: string | null, 
+/**
+ * Five-field cron expression (e.g. `0 2 * * *`) or a shorthand such as `@daily`.
+ */
+cron: string, prompt: string, 
+/**
+ * Working directory for the runs; defaults to the server's working directory.
+ */
+cwd?: string | null, 
+/**
+ * Config profile to run with.
+ */
+profile?: string | null, sandbox?: SandboxMode | null, 
+/**
+ * Defaults to `new`.
+ */
+threadMode?: ScheduleThreadMode | null, 
+/**
+ * Existing thread the first `resume` run continues.
+ */
+threadId?: string | null, };
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ScheduleRunStatus } from "./ScheduleRunStatus";
+
+export type ScheduleRun = { id: number, scheduleId: string, status: ScheduleRunStatus, threadId: string | null, 
+/**
+ * Exit code of the `codex exec` process, when it exited normally.
+ */
+exitCode: number | null, error: string | null, 
+/**
+ * The cron slot this run was started for (Unix seconds).
+ */
+scheduledFor: number, startedAt: number, finishedAt: number | null, };
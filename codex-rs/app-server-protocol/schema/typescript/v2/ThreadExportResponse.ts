@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadExportFormat } from "./ThreadExportFormat";
+
+export type ThreadExportResponse = { format: ThreadExportFormat, 
+/**
+ * The rendered transcript, including the threads of spawned sub-agents.
+ */
+content: string, };
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ThreadSearchMatchRole } from "./ThreadSearchMatchRole";
+
+export type ThreadSearchMatch = { 
+/**
+ * Turn containing the matching message, when known.
+ */
+turnId: string | null, role: ThreadSearchMatchRole, 
+/**
+ * Excerpt of the message with matched terms wrapped in `[` and `]`.
+ */
+snippet: string, };
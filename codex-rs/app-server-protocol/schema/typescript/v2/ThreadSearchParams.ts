@@ -0,0 +1,20 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ThreadSearchParams = { 
+/**
+ * Free-form search text. Every whitespace-separated term must match a word
+ * or word prefix in a user or agent message.
+ */
+query: string, 
+/**
+ * Optional maximum number of threads to return; defaults to a reasonable
+ * server-side value.
+ */
+limit?: number | null, 
+/**
+ * Optional archived filter; when set to true, only archived threads are searched.
+ * If false or null, only non-archived threads are searched.
+ */
+archived?: boolean | null, };
@@ -0,0 +1,11 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Thread } from "./Thread";
+import type { ThreadSearchMatch } from "./ThreadSearchMatch";
+
+export type ThreadSearchResult = { thread: Thread, 
+/**
+ * Matching messages within the thread, most relevant first.
+ */
+matches: Array<ThreadSearchMatch>, };
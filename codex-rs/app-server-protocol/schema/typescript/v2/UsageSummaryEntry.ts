@@ -0,0 +1,23 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TokenUsageBreakdown } from "./TokenUsageBreakdown";
+
+export type UsageSummaryEntry = { 
+/**
+ * UTC day formatted as `YYYY-MM-DD`, when grouping by day.
+ */
+day: string | null, 
+/**
+ * Git origin URL, when grouping by repository and the thread has one.
+ */
+repository: string | null, 
+/**
+ * Model slug, when grouping by model and the model is known.
+ */
+model: string | null, turns: number, threads: number, usage: TokenUsageBreakdown, 
+/**
+ * Estimated cost in US dollars from the `model_pricing` config table, summed over
+ * the models in this entry that have a price. Null when none of them do.
+ */
+costUsd: number | null, };
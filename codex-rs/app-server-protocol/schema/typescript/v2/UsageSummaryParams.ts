@@ -0,0 +1,19 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { UsageGroupBy } from "./UsageGroupBy";
+
+export type UsageSummaryParams = { 
+/**
+ * Dimensions to group by, in output order. When omitted or empty, a single
+ * total entry is returned.
+ */
+groupBy?: Array<UsageGroupBy> | null, 
+/**
+ * Optional inclusive lower bound on the time usage was recorded (Unix seconds).
+ */
+since?: number | null, 
+/**
+ * Optional exclusive upper bound on the time usage was recorded (Unix seconds).
+ */
+until?: number | null, };
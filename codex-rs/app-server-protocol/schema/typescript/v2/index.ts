@@ -90,6 +90,8 @@ export type { ExternalAgentConfigMigrationItemType } from "./ExternalAgentConfig
 export type { FeedbackUploadParams } from "./FeedbackUploadParams";
 export type { FeedbackUploadResponse } from "./FeedbackUploadResponse";
 export type { FileChangeApprovalDecision } from "./FileChangeApprovalDecision";
+export type { FileChangeHunkAction } from "./FileChangeHunkAction";
+export type { FileChangeHunkDecision } from "./FileChangeHunkDecision";
 export type { FileChangeOutputDeltaNotification } from "./FileChangeOutputDeltaNotification";
 export type { FileChangeRequestApprovalParams } from "./FileChangeRequestApprovalParams";
 export type { FileChangeRequestApprovalResponse } from "./FileChangeRequestApprovalResponse";
@@ -135,6 +137,9 @@ export type { McpServerElicitationRequestResponse } from "./McpServerElicitation
 export type { McpServerOauthLoginCompletedNotification } from "./McpServerOauthLoginCompletedNotification";
 export type { McpServerOauthLoginParams } from "./McpServerOauthLoginParams";
 export type { McpServerOauthLoginResponse } from "./McpServerOauthLoginResponse";
+export type { McpServerPromptGetParams } from "./McpServerPromptGetParams";
+export type { McpServerPromptGetResponse } from "./McpServerPromptGetResponse";
+export type { McpServerPromptsChangedNotification } from "./McpServerPromptsChangedNotification";
 export type { McpServerRefreshResponse } from "./McpServerRefreshResponse";
 export type { McpServerStatus } from "./McpServerStatus";
 export type { McpToolCallError } from "./McpToolCallError";
@@ -186,6 +191,18 @@ export type { ReviewTarget } from "./ReviewTarget";
 export type { SandboxMode } from "./SandboxMode";
 export type { SandboxPolicy } from "./SandboxPolicy";
 export type { SandboxWorkspaceWrite } from "./SandboxWorkspaceWrite";
+export type { Schedule } from "./Schedule";
+export type { ScheduleCreateParams } from "./ScheduleCreateParams";
+export type { ScheduleCreateResponse } from "./ScheduleCreateResponse";
+export type { ScheduleDeleteParams } from "./ScheduleDeleteParams";
+export type { ScheduleDeleteResponse } from "./ScheduleDeleteResponse";
+export type { ScheduleListParams } from "./ScheduleListParams";
+export type { ScheduleListResponse } from "./ScheduleListResponse";
+export type { ScheduleRun } from "./ScheduleRun";
+export type { ScheduleRunStatus } from "./ScheduleRunStatus";
+export type { ScheduleRunsParams } from "./ScheduleRunsParams";
+export type { ScheduleRunsResponse } from "./ScheduleRunsResponse";
+export type { ScheduleThreadMode } from "./ScheduleThreadMode";
 export type { ServerRequestResolvedNotification } from "./ServerRequestResolvedNotification";
 export type { SessionSource } from "./SessionSource";
 export type { SkillDependencies } from "./SkillDependencies";
@@ -217,6 +234,9 @@ export type { ThreadArchivedNotification } from "./ThreadArchivedNotification";
 export type { ThreadClosedNotification } from "./ThreadClosedNotification";
 export type { ThreadCompactStartParams } from "./ThreadCompactStartParams";
 export type { ThreadCompactStartResponse } from "./ThreadCompactStartResponse";
+export type { ThreadExportFormat } from "./ThreadExportFormat";
+export type { ThreadExportParams } from "./ThreadExportParams";
+export type { ThreadExportResponse } from "./ThreadExportResponse";
 export type { ThreadForkParams } from "./ThreadForkParams";
 export type { ThreadForkResponse } from "./ThreadForkResponse";
 export type { ThreadItem } from "./ThreadItem";
@@ -240,6 +260,11 @@ export type { ThreadResumeParams } from "./ThreadResumeParams";
 export type { ThreadResumeResponse } from "./ThreadResumeResponse";
 export type { ThreadRollbackParams } from "./ThreadRollbackParams";
 export type { ThreadRollbackResponse } from "./ThreadRollbackResponse";
+export type { ThreadSearchMatch } from "./ThreadSearchMatch";
+export type { ThreadSearchMatchRole } from "./ThreadSearchMatchRole";
+export type { ThreadSearchParams } from "./ThreadSearchParams";
+export type { ThreadSearchResponse } from "./ThreadSearchResponse";
+export type { ThreadSearchResult } from "./ThreadSearchResult";
 export type { ThreadSetNameParams } from "./ThreadSetNameParams";
 export type { ThreadSetNameResponse } from "./ThreadSetNameResponse";
 export type { ThreadSortKey } from "./ThreadSortKey";
@@ -279,6 +304,10 @@ export type { TurnStartedNotification } from "./TurnStartedNotification";
 export type { TurnStatus } from "./TurnStatus";
 export type { TurnSteerParams } from "./TurnSteerParams";
 export type { TurnSteerResponse } from "./TurnSteerResponse";
+export type { UsageGroupBy } from "./UsageGroupBy";
+export type { UsageSummaryEntry } from "./UsageSummaryEntry";
+export type { UsageSummaryParams } from "./UsageSummaryParams";
+export type { UsageSummaryResponse } from "./UsageSummaryResponse";
 export type { UserInput } from "./UserInput";
 export type { WebSearchAction } from "./WebSearchAction";
 export type { WindowsSandboxSetupCompletedNotification } from "./WindowsSandboxSetupCompletedNotification";
@@ -244,6 +244,34 @@ client_request_definitions! {
         params: v2::ThreadReadParams,
         response: v2::ThreadReadResponse,
     },
+    ThreadSearch => "thread/search" {
+        params: v2::ThreadSearchParams,
+        response: v2::ThreadSearchResponse,
+    },
+    ThreadExport => "thread/export" {
+        params: v2::ThreadExportParams,
+        response: v2::ThreadExportResponse,
+    },
+    UsageSummary => "usage/summary" {
+        params: v2::UsageSummaryParams,
+        response: v2::UsageSummaryResponse,
+    },
+    ScheduleList => "schedule/list" {
+        params: v2::ScheduleListParams,
+        response: v2::ScheduleListResponse,
+    },
+    ScheduleCreate => "schedule/create" {
+        params: v2::ScheduleCreateParams,
+        response: v2::ScheduleCreateResponse,
+    },
+    ScheduleDelete => "schedule/delete" {
+        params: v2::ScheduleDeleteParams,
+        response: v2::ScheduleDeleteResponse,
+    },
+    ScheduleRuns => "schedule/runs" {
+        params: v2::ScheduleRunsParams,
+        response: v2::ScheduleRunsResponse,
+    },
     SkillsList => "skills/list" {
         params: v2::SkillsListParams,
         response: v2::SkillsListResponse,
@@ -346,6 +374,11 @@ client_request_definitions! {
         response: v2::ListMcpServerStatusResponse,
     },
 
+    McpServerPromptGet => "mcpServer/prompt/get" {
+        params: v2::McpServerPromptGetParams,
+        response: v2::McpServerPromptGetResponse,
+    },
+
     WindowsSandboxSetupStart => "windowsSandbox/setupStart" {
         params: v2::WindowsSandboxSetupStartParams,
         response: v2::WindowsSandboxSetupStartResponse,
@@ -804,6 +837,7 @@ server_notification_definitions! {
     ServerRequestResolved => "serverRequest/resolved" (v2::ServerRequestResolvedNotification),
     McpToolCallProgress => "item/mcpToolCall/progress" (v2::McpToolCallProgressNotification),
     McpServerOauthLoginCompleted => "mcpServer/oauthLogin/completed" (v2::McpServerOauthLoginCompletedNotification),
+    McpServerPromptsChanged => "mcpServer/prompts/changed" (v2::McpServerPromptsChangedNotification),
     AccountUpdated => "account/updated" (v2::AccountUpdatedNotification),
     AccountRateLimitsUpdated => "account/rateLimits/updated" (v2::AccountRateLimitsUpdatedNotification),
     AppListUpdated => "app/list/updated" (v2::AppListUpdatedNotification),
@@ -25,6 +25,7 @@ use codex_protocol::config_types::WebSearchMode;
 use codex_protocol::config_types::WebSearchToolConfig;
 use codex_protocol::items::AgentMessageContent as CoreAgentMessageContent;
 use codex_protocol::items::TurnItem as CoreTurnItem;
+use codex_protocol::mcp::Prompt as McpPrompt;
 use codex_protocol::mcp::Resource as McpResource;
 use codex_protocol::mcp::ResourceTemplate as McpResourceTemplate;
 use codex_protocol::mcp::Tool as McpTool;
@@ -51,6 +52,8 @@ use codex_protocol::protocol::ExecCommandStatus as CoreExecCommandStatus;
 use codex_protocol::protocol::ModelRerouteReason as CoreModelRerouteReason;
 use codex_protocol::protocol::NetworkAccess as CoreNetworkAccess;
 use codex_protocol::protocol::PatchApplyStatus as CorePatchApplyStatus;
+use codex_protocol::protocol::PatchHunkAction as CorePatchHunkAction;
+use codex_protocol::protocol::PatchHunkDecision as CorePatchHunkDecision;
 use codex_protocol::protocol::RateLimitSnapshot as CoreRateLimitSnapshot;
 use codex_protocol::protocol::RateLimitWindow as CoreRateLimitWindow;
 use codex_protocol::protocol::ReadOnlyAccess as CoreReadOnlyAccess;
@@ -72,6 +75,8 @@ use codex_protocol::user_input::TextElement as CoreTextElement;
 use codex_protocol::user_input::UserInput as CoreUserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use schemars::JsonSchema;
+use schemars::r#gen::SchemaGenerator;
+use schemars::schema::Schema;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
@@ -272,7 +277,10 @@ v2_enum_from_core!(
 
 v2_enum_from_core!(
     pub enum ModelRerouteReason from CoreModelRerouteReason {
-        HighRiskCyberActivity
+        HighRiskCyberActivity,
+        RateLimited,
+        ProviderUnavailable,
+        ContextWindowExceeded
     }
 );
 
@@ -784,6 +792,8 @@ impl From<CoreReviewDecision> for CommandExecutionApprovalDecision {
             } => Self::ApplyNetworkPolicyAmendment {
                 network_policy_amendment: network_policy_amendment.into(),
             },
+            // Hunk decisions only apply to file changes.
+            CoreReviewDecision::ApprovedHunks { .. } => Self::Decline,
             CoreReviewDecision::Abort => Self::Cancel,
             CoreReviewDecision::Denied => Self::Decline,
         }
@@ -896,12 +906,57 @@ pub enum FileChangeApprovalDecision {
     Accept,
     /// User approved the file changes and future changes to the same files should run without prompting.
     AcceptForSession,
+    /// User reviewed the file changes hunk by hunk. Only accepted and edited hunks are applied;
+    /// hunks without a decision are rejected.
+    AcceptHunks { hunks: Vec<FileChangeHunkDecision> },
     /// User denied the file changes. The agent will continue the turn.
     Decline,
     /// User denied the file changes. The turn will also be immediately interrupted.
     Cancel,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileChangeHunkDecision {
+    /// Path of the file, as reported in the item's `changes`.
+    pub path: String,
+    /// Zero-based index of the hunk: each `@@` section of an updated file's diff, or a single
+    /// hunk covering the whole file for added and deleted files.
+    pub hunk_index: u32,
+    pub action: FileChangeHunkAction,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(tag = "type")]
+#[ts(export_to = "v2/")]
+pub enum FileChangeHunkAction {
+    Accept,
+    Reject,
+    /// Apply `replacement` instead of the proposed hunk: the hunk's diff lines (prefixed with
+    /// ` `, `-` or `+`) for updated files, or the new contents for added files.
+    Edit {
+        replacement: String,
+    },
+}
+
+impl From<FileChangeHunkDecision> for CorePatchHunkDecision {
+    fn from(value: FileChangeHunkDecision) -> Self {
+        Self {
+            path: PathBuf::from(value.path),
+            hunk_index: value.hunk_index as usize,
+            action: match value.action {
+                FileChangeHunkAction::Accept => CorePatchHunkAction::Accept,
+                FileChangeHunkAction::Reject => CorePatchHunkAction::Reject,
+                FileChangeHunkAction::Edit { replacement } => {
+                    CorePatchHunkAction::Edit { replacement }
+                }
+            },
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1070,27 +1125,104 @@ impl From<codex_protocol::protocol::SandboxPolicy> for SandboxPolicy {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema, TS)]
-#[serde(transparent)]
-#[ts(type = "Array<string>", export_to = "v2/")]
+/// Proposed execpolicy prefix rule. Serialized as the bare `command` array, or
+/// as an object when argument clauses such as `flag("--force", "-f")` are
+/// attached.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, TS)]
+#[serde(from = "ExecPolicyAmendmentRepr", into = "ExecPolicyAmendmentRepr")]
+#[ts(
+    type = "Array<string> | { command: Array<string>, args?: Array<string>, notMatching?: Array<string>, }",
+    export_to = "v2/"
+)]
 pub struct ExecPolicyAmendment {
     pub command: Vec<String>,
+    pub args: Vec<String>,
+    pub not_matching: Vec<String>,
 }
 
 impl ExecPolicyAmendment {
     pub fn into_core(self) -> CoreExecPolicyAmendment {
-        CoreExecPolicyAmendment::new(self.command)
+        CoreExecPolicyAmendment {
+            command: self.command,
+            args: self.args,
+            not_matching: self.not_matching,
+        }
     }
 }
 
 impl From<CoreExecPolicyAmendment> for ExecPolicyAmendment {
     fn from(value: CoreExecPolicyAmendment) -> Self {
         Self {
-            command: value.command().to_vec(),
+            command: value.command,
+            args: value.args,
+            not_matching: value.not_matching,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum ExecPolicyAmendmentRepr {
+    Prefix(Vec<String>),
+    #[serde(rename_all = "camelCase")]
+    WithClauses {
+        command: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        not_matching: Vec<String>,
+    },
+}
+
+impl From<ExecPolicyAmendmentRepr> for ExecPolicyAmendment {
+    fn from(repr: ExecPolicyAmendmentRepr) -> Self {
+        match repr {
+            ExecPolicyAmendmentRepr::Prefix(command) => Self {
+                command,
+                args: Vec::new(),
+                not_matching: Vec::new(),
+            },
+            ExecPolicyAmendmentRepr::WithClauses {
+                command,
+                args,
+                not_matching,
+            } => Self {
+                command,
+                args,
+                not_matching,
+            },
+        }
+    }
+}
+
+impl From<ExecPolicyAmendment> for ExecPolicyAmendmentRepr {
+    fn from(amendment: ExecPolicyAmendment) -> Self {
+        if amendment.args.is_empty() && amendment.not_matching.is_empty() {
+            Self::Prefix(amendment.command)
+        } else {
+            Self::WithClauses {
+                command: amendment.command,
+                args: amendment.args,
+                not_matching: amendment.not_matching,
+            }
         }
     }
 }
 
+impl JsonSchema for ExecPolicyAmendment {
+    fn schema_name() -> String {
+        "ExecPolicyAmendment".to_string()
+    }
+
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        ExecPolicyAmendmentRepr::json_schema(generator)
+    }
+}
+
 v2_enum_from_core!(
     pub enum NetworkPolicyRuleAction from CoreNetworkPolicyRuleAction {
         Allow, Deny
@@ -1603,6 +1735,7 @@ pub struct McpServerStatus {
     pub tools: std::collections::HashMap<String, McpTool>,
     pub resources: Vec<McpResource>,
     pub resource_templates: Vec<McpResourceTemplate>,
+    pub prompts: Vec<McpPrompt>,
     pub auth_status: McpAuthStatus,
 }
 
@@ -1764,6 +1897,29 @@ pub struct McpServerRefreshParams {}
 #[ts(export_to = "v2/")]
 pub struct McpServerRefreshResponse {}
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptGetParams {
+    /// Name of the MCP server offering the prompt.
+    pub server: String,
+    /// Name of the prompt, as listed in `McpServerStatus.prompts`.
+    pub name: String,
+    /// Argument values keyed by argument name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional = nullable)]
+    pub arguments: Option<HashMap<String, String>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPromptGetResponse {
+    /// The prompt's messages joined into a single block of text, suitable for
+    /// placing in a composer.
+    pub text: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -2515,6 +2671,280 @@ pub struct ThreadReadResponse {
     pub thread: Thread,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchParams {
+    /// Free-form search text. Every whitespace-separated term must match a word
+    /// or word prefix in a user or agent message.
+    pub query: String,
+    /// Optional maximum number of threads to return; defaults to a reasonable
+    /// server-side value.
+    #[ts(optional = nullable)]
+    pub limit: Option<u32>,
+    /// Optional archived filter; when set to true, only archived threads are searched.
+    /// If false or null, only non-archived threads are searched.
+    #[ts(optional = nullable)]
+    pub archived: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResponse {
+    /// Matching threads, most relevant first.
+    pub data: Vec<ThreadSearchResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResult {
+    pub thread: Thread,
+    /// Matching messages within the thread, most relevant first.
+    pub matches: Vec<ThreadSearchMatch>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchMatch {
+    /// Turn containing the matching message, when known.
+    pub turn_id: Option<String>,
+    pub role: ThreadSearchMatchRole,
+    /// Excerpt of the message with matched terms wrapped in `[` and `]`.
+    pub snippet: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase", export_to = "v2/")]
+pub enum ThreadSearchMatchRole {
+    User,
+    Assistant,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadExportParams {
+    pub thread_id: String,
+    pub format: ThreadExportFormat,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase", export_to = "v2/")]
+pub enum ThreadExportFormat {
+    /// A single HTML document with inline styles and no external resources.
+    Html,
+    Markdown,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadExportResponse {
+    pub format: ThreadExportFormat,
+    /// The rendered transcript, including the threads of spawned sub-agents.
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct UsageSummaryParams {
+    /// Dimensions to group by, in output order. When omitted or empty, a single
+    /// total entry is returned.
+    #[ts(optional = nullable)]
+    pub group_by: Option<Vec<UsageGroupBy>>,
+    /// Optional inclusive lower bound on the time usage was recorded (Unix seconds).
+    #[ts(type = "number | null")]
+    #[ts(optional = nullable)]
+    pub since: Option<i64>,
+    /// Optional exclusive upper bound on the time usage was recorded (Unix seconds).
+    #[ts(type = "number | null")]
+    #[ts(optional = nullable)]
+    pub until: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase", export_to = "v2/")]
+pub enum UsageGroupBy {
+    /// UTC calendar day.
+    Day,
+    /// Git origin URL of the thread.
+    Repository,
+    Model,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct UsageSummaryResponse {
+    pub data: Vec<UsageSummaryEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct UsageSummaryEntry {
+    /// UTC day formatted as `YYYY-MM-DD`, when grouping by day.
+    pub day: Option<String>,
+    /// Git origin URL, when grouping by repository and the thread has one.
+    pub repository: Option<String>,
+    /// Model slug, when grouping by model and the model is known.
+    pub model: Option<String>,
+    #[ts(type = "number")]
+    pub turns: i64,
+    #[ts(type = "number")]
+    pub threads: i64,
+    pub usage: TokenUsageBreakdown,
+    /// Estimated cost in US dollars from the `model_pricing` config table, summed over
+    /// the models in this entry that have a price. Null when none of them do.
+    pub cost_usd: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase", export_to = "v2/")]
+pub enum ScheduleThreadMode {
+    /// Every run starts a new thread.
+    New,
+    /// Every run resumes the thread of the previous run.
+    Resume,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(rename_all = "camelCase", export_to = "v2/")]
+pub enum ScheduleRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct Schedule {
+    pub id: String,
+    pub name: String,
+    /// Five-field cron expression, evaluated in the server's local time zone.
+    pub cron: String,
+    pub prompt: String,
+    pub cwd: String,
+    pub profile: Option<String>,
+    pub sandbox: Option<SandboxMode>,
+    pub thread_mode: ScheduleThreadMode,
+    /// Thread the next `resume` run continues.
+    pub thread_id: Option<String>,
+    pub enabled: bool,
+    /// Next run time (Unix seconds).
+    #[ts(type = "number | null")]
+    pub next_run_at: Option<i64>,
+    #[ts(type = "number")]
+    pub created_at: i64,
+    #[ts(type = "number")]
+    pub updated_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ScheduleRun {
+    #[ts(type = "number")]
+    pub id: i64,
+    pub schedule_id: String,
+    pub status: ScheduleRunStatus,
+    pub thread_id: Option<String>,
+    /// Exit code of the `codex exec` process, when it exited normally.
+    pub exit_code: Option<i32>,
+    pub error: Option<String>,
+    /// The cron slot this run was started for (Unix seconds).
+    #[ts(type = "number")]
+    pub scheduled_for: i64,
+    #[ts(type = "number")]
+    pub started_at: i64,
+    #[ts(type = "number | null")]
+    pub finished_at: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ScheduleListParams {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ScheduleListResponse {
+    pub data: Vec<Schedule>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ScheduleCreateParams {
+    /// Display name; defaults to the start of the prompt.
+    #[ts(optional = nullable)]
+    pub name: Option<String>,
+    /// Five-field cron expression (e.g. `0 2 * * *`) or a shorthand such as `@daily`.
+    pub cron: String,
+    pub prompt: String,
+    /// Working directory for the runs; defaults to the server's working directory.
+    #[ts(optional = nullable)]
+    pub cwd: Option<String>,
+    /// Config profile to run with.
+    #[ts(optional = nullable)]
+    pub profile: Option<String>,
+    #[ts(optional = nullable)]
+    pub sandbox: Option<SandboxMode>,
+    /// Defaults to `new`.
+    #[ts(optional = nullable)]
+    pub thread_mode: Option<ScheduleThreadMode>,
+    /// Existing thread the first `resume` run continues.
+    #[ts(optional = nullable)]
+    pub thread_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ScheduleCreateResponse {
+    pub schedule: Schedule,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ScheduleDeleteParams {
+    pub id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ScheduleDeleteResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ScheduleRunsParams {
+    pub id: String,
+    /// Maximum number of runs to return, newest first.
+    #[ts(optional = nullable)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ScheduleRunsResponse {
+    pub data: Vec<ScheduleRun>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -3254,6 +3684,11 @@ pub struct ReviewStartParams {
     #[serde(default)]
     #[ts(optional = nullable)]
     pub delivery: Option<ReviewDelivery>,
+
+    /// Limit the review to files matching these git pathspecs (e.g. `src/api/**`).
+    #[serde(default)]
+    #[ts(optional = nullable)]
+    pub paths: Option<Vec<String>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -3289,6 +3724,17 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the changes introduced by the commits in `from..to`.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { from: String, to: String },
+
+    /// Review the changes in a unified diff file. Relative paths are resolved
+    /// against the thread's working directory.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PatchFile { path: PathBuf },
+
     /// Arbitrary instructions, equivalent to the old free-form prompt.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -4193,6 +4639,18 @@ pub struct McpToolCallProgressNotification {
     pub message: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+/// Notification emitted when an MCP server used by a thread reports that its
+/// prompts changed.
+pub struct McpServerPromptsChangedNotification {
+    pub thread_id: String,
+    pub server: String,
+    /// The server's prompts after the change.
+    pub prompts: Vec<McpPrompt>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -48,6 +48,7 @@ use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::McpServerElicitationAction;
 use codex_app_server_protocol::McpServerElicitationRequestParams;
 use codex_app_server_protocol::McpServerElicitationRequestResponse;
+use codex_app_server_protocol::McpServerPromptsChangedNotification;
 use codex_app_server_protocol::McpToolCallError;
 use codex_app_server_protocol::McpToolCallResult;
 use codex_app_server_protocol::McpToolCallStatus;
@@ -224,6 +225,20 @@ pub(crate) async fn apply_bespoke_event_handling(
                 .await;
             handle_turn_complete(conversation_id, event_turn_id, &outgoing, &thread_state).await;
         }
+        EventMsg::McpPromptListChanged(event) => {
+            if let ApiVersion::V2 = api_version {
+                let notification = McpServerPromptsChangedNotification {
+                    thread_id: conversation_id.to_string(),
+                    server: event.server,
+                    prompts: event.prompts,
+                };
+                outgoing
+                    .send_server_notification(ServerNotification::McpServerPromptsChanged(
+                        notification,
+                    ))
+                    .await;
+            }
+        }
         EventMsg::SkillsUpdateAvailable => {
             if let ApiVersion::V2 = api_version {
                 outgoing
@@ -2149,6 +2164,12 @@ fn map_file_change_approval_decision(
     match decision {
         FileChangeApprovalDecision::Accept => (ReviewDecision::Approved, None),
         FileChangeApprovalDecision::AcceptForSession => (ReviewDecision::ApprovedForSession, None),
+        FileChangeApprovalDecision::AcceptHunks { hunks } => (
+            ReviewDecision::ApprovedHunks {
+                hunks: hunks.into_iter().map(Into::into).collect(),
+            },
+            None,
+        ),
         FileChangeApprovalDecision::Decline => {
             (ReviewDecision::Denied, Some(PatchApplyStatus::Declined))
         }
@@ -2466,6 +2487,8 @@ mod tests {
     use anyhow::Result;
     use anyhow::anyhow;
     use anyhow::bail;
+    use codex_app_server_protocol::FileChangeHunkAction;
+    use codex_app_server_protocol::FileChangeHunkDecision;
     use codex_app_server_protocol::JSONRPCErrorError;
     use codex_app_server_protocol::TurnPlanStepStatus;
     use codex_protocol::mcp::CallToolResult;
@@ -2475,6 +2498,8 @@ mod tests {
     use codex_protocol::protocol::CollabResumeEndEvent;
     use codex_protocol::protocol::CreditsSnapshot;
     use codex_protocol::protocol::McpInvocation;
+    use codex_protocol::protocol::PatchHunkAction;
+    use codex_protocol::protocol::PatchHunkDecision;
     use codex_protocol::protocol::RateLimitSnapshot;
     use codex_protocol::protocol::RateLimitWindow;
     use codex_protocol::protocol::TokenUsage;
@@ -2511,6 +2536,33 @@ mod tests {
         assert_eq!(completion_status, None);
     }
 
+    #[test]
+    fn file_change_accept_hunks_maps_to_approved_hunks() {
+        let (decision, completion_status) =
+            map_file_change_approval_decision(FileChangeApprovalDecision::AcceptHunks {
+                hunks: vec![FileChangeHunkDecision {
+                    path: "/repo/src/lib.rs".to_string(),
+                    hunk_index: 1,
+                    action: FileChangeHunkAction::Edit {
+                        replacement: " a\n-b\n+c\n".to_string(),
+                    },
+                }],
+            });
+        assert_eq!(
+            decision,
+            ReviewDecision::ApprovedHunks {
+                hunks: vec![PatchHunkDecision {
+                    path: PathBuf::from("/repo/src/lib.rs"),
+                    hunk_index: 1,
+                    action: PatchHunkAction::Edit {
+                        replacement: " a\n-b\n+c\n".to_string(),
+                    },
+                }],
+            }
+        );
+        assert_eq!(completion_status, None);
+    }
+
     #[test]
     fn mcp_server_elicitation_turn_transition_error_maps_to_cancel() {
         let error = JSONRPCErrorError {
@@ -76,6 +76,8 @@ use codex_app_server_protocol::LogoutAccountResponse;
 use codex_app_server_protocol::McpServerOauthLoginCompletedNotification;
 use codex_app_server_protocol::McpServerOauthLoginParams;
 use codex_app_server_protocol::McpServerOauthLoginResponse;
+use codex_app_server_protocol::McpServerPromptGetParams;
+use codex_app_server_protocol::McpServerPromptGetResponse;
 use codex_app_server_protocol::McpServerRefreshResponse;
 use codex_app_server_protocol::McpServerStatus;
 use codex_app_server_protocol::MockExperimentalMethodParams;
@@ -97,6 +99,18 @@ use codex_app_server_protocol::ReviewStartParams;
 use codex_app_server_protocol::ReviewStartResponse;
 use codex_app_server_protocol::ReviewTarget as ApiReviewTarget;
 use codex_app_server_protocol::SandboxMode;
+use codex_app_server_protocol::Schedule as ApiSchedule;
+use codex_app_server_protocol::ScheduleCreateParams;
+use codex_app_server_protocol::ScheduleCreateResponse;
+use codex_app_server_protocol::ScheduleDeleteParams;
+use codex_app_server_protocol::ScheduleDeleteResponse;
+use codex_app_server_protocol::ScheduleListParams;
+use codex_app_server_protocol::ScheduleListResponse;
+use codex_app_server_protocol::ScheduleRun as ApiScheduleRun;
+use codex_app_server_protocol::ScheduleRunStatus as ApiScheduleRunStatus;
+use codex_app_server_protocol::ScheduleRunsParams;
+use codex_app_server_protocol::ScheduleRunsResponse;
+use codex_app_server_protocol::ScheduleThreadMode as ApiScheduleThreadMode;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequestResolvedNotification;
 use codex_app_server_protocol::SkillsConfigWriteParams;
@@ -116,6 +130,9 @@ use codex_app_server_protocol::ThreadBackgroundTerminalsCleanResponse;
 use codex_app_server_protocol::ThreadClosedNotification;
 use codex_app_server_protocol::ThreadCompactStartParams;
 use codex_app_server_protocol::ThreadCompactStartResponse;
+use codex_app_server_protocol::ThreadExportFormat;
+use codex_app_server_protocol::ThreadExportParams;
+use codex_app_server_protocol::ThreadExportResponse;
 use codex_app_server_protocol::ThreadForkParams;
 use codex_app_server_protocol::ThreadForkResponse;
 use codex_app_server_protocol::ThreadItem;
@@ -140,6 +157,11 @@ use codex_app_server_protocol::ThreadRealtimeStopResponse;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadResumeResponse;
 use codex_app_server_protocol::ThreadRollbackParams;
+use codex_app_server_protocol::ThreadSearchMatch;
+use codex_app_server_protocol::ThreadSearchMatchRole;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
+use codex_app_server_protocol::ThreadSearchResult;
 use codex_app_server_protocol::ThreadSetNameParams;
 use codex_app_server_protocol::ThreadSetNameResponse;
 use codex_app_server_protocol::ThreadSortKey;
@@ -154,6 +176,7 @@ use codex_app_server_protocol::ThreadUnarchivedNotification;
 use codex_app_server_protocol::ThreadUnsubscribeParams;
 use codex_app_server_protocol::ThreadUnsubscribeResponse;
 use codex_app_server_protocol::ThreadUnsubscribeStatus;
+use codex_app_server_protocol::TokenUsageBreakdown;
 use codex_app_server_protocol::Turn;
 use codex_app_server_protocol::TurnInterruptParams;
 use codex_app_server_protocol::TurnStartParams;
@@ -161,6 +184,10 @@ use codex_app_server_protocol::TurnStartResponse;
 use codex_app_server_protocol::TurnStatus;
 use codex_app_server_protocol::TurnSteerParams;
 use codex_app_server_protocol::TurnSteerResponse;
+use codex_app_server_protocol::UsageGroupBy;
+use codex_app_server_protocol::UsageSummaryEntry;
+use codex_app_server_protocol::UsageSummaryParams;
+use codex_app_server_protocol::UsageSummaryResponse;
 use codex_app_server_protocol::UserInput as V2UserInput;
 use codex_app_server_protocol::WindowsSandboxSetupCompletedNotification;
 use codex_app_server_protocol::WindowsSandboxSetupMode;
@@ -180,6 +207,8 @@ use codex_core::RolloutRecorder;
 use codex_core::SessionMeta;
 use codex_core::SteerInputError;
 use codex_core::ThreadConfigSnapshot;
+use codex_core::ThreadExport;
+use codex_core::ThreadExportFormat as CoreThreadExportFormat;
 use codex_core::ThreadManager;
 use codex_core::ThreadSortKey as CoreThreadSortKey;
 use codex_core::auth::AuthMode as CoreAuthMode;
@@ -209,6 +238,7 @@ use codex_core::find_thread_names_by_ids;
 use codex_core::find_thread_path_by_id_str;
 use codex_core::git_info::git_diff_to_remote;
 use codex_core::mcp::collect_mcp_snapshot;
+use codex_core::mcp::get_mcp_prompt;
 use codex_core::mcp::group_tools_by_server;
 use codex_core::models_manager::collaboration_mode_presets::CollaborationModesConfig;
 use codex_core::parse_cursor;
@@ -227,6 +257,7 @@ use codex_core::skills::remote::list_remote_skills;
 use codex_core::state_db::StateDbHandle;
 use codex_core::state_db::get_state_db;
 use codex_core::state_db::reconcile_rollout;
+use codex_core::state_db::token_usage_summary;
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_core::windows_sandbox::WindowsSandboxSetupMode as CoreWindowsSandboxSetupMode;
 use codex_core::windows_sandbox::WindowsSandboxSetupRequest;
@@ -267,6 +298,8 @@ use codex_protocol::user_input::UserInput as CoreInputItem;
 use codex_rmcp_client::perform_oauth_login_return_url;
 use codex_state::StateRuntime;
 use codex_state::ThreadMetadataBuilder;
+use codex_state::TokenUsageGroupBy;
+use codex_state::TokenUsageSummaryQuery;
 use codex_state::log_db::LogDbLayer;
 use codex_utils_json_to_toml::json_to_toml;
 use codex_utils_pty::DEFAULT_OUTPUT_BYTES_CAP;
@@ -306,6 +339,10 @@ use crate::thread_state::ThreadStateManager;
 
 const THREAD_LIST_DEFAULT_LIMIT: usize = 25;
 const THREAD_LIST_MAX_LIMIT: usize = 100;
+const THREAD_SEARCH_DEFAULT_LIMIT: usize = 20;
+const THREAD_SEARCH_MAX_LIMIT: usize = 100;
+/// Upper bound on the matches returned for each thread in a `thread/search` response.
+const THREAD_SEARCH_MAX_MATCHES_PER_THREAD: usize = 20;
 
 struct ThreadListFilters {
     model_providers: Option<Vec<String>>,
@@ -381,6 +418,7 @@ pub(crate) struct CodexMessageProcessor {
     fuzzy_search_sessions: Arc<Mutex<HashMap<String, FuzzyFileSearchSession>>>,
     feedback: CodexFeedback,
     log_db: Option<LogDbLayer>,
+    state_db: Option<Arc<StateRuntime>>,
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -417,6 +455,8 @@ pub(crate) struct CodexMessageProcessorArgs {
     pub(crate) cloud_requirements: Arc<RwLock<CloudRequirementsLoader>>,
     pub(crate) feedback: CodexFeedback,
     pub(crate) log_db: Option<LogDbLayer>,
+    /// State DB opened at startup, shared with the scheduler.
+    pub(crate) state_db: Option<Arc<StateRuntime>>,
 }
 
 impl CodexMessageProcessor {
@@ -477,6 +517,7 @@ impl CodexMessageProcessor {
             cloud_requirements,
             feedback,
             log_db,
+            state_db,
         } = args;
         Self {
             auth_manager,
@@ -495,6 +536,7 @@ impl CodexMessageProcessor {
             fuzzy_search_sessions: Arc::new(Mutex::new(HashMap::new())),
             feedback,
             log_db,
+            state_db,
         }
     }
 
@@ -551,6 +593,7 @@ impl CodexMessageProcessor {
 
     fn review_request_from_target(
         target: ApiReviewTarget,
+        paths: Option<Vec<String>>,
     ) -> Result<(ReviewRequest, String), JSONRPCErrorError> {
         fn invalid_request(message: String) -> JSONRPCErrorError {
             JSONRPCErrorError {
@@ -579,6 +622,20 @@ impl CodexMessageProcessor {
                     .filter(|t| !t.is_empty());
                 ApiReviewTarget::Commit { sha, title }
             }
+            ApiReviewTarget::CommitRange { from, to } => {
+                let from = from.trim().to_string();
+                let to = to.trim().to_string();
+                if from.is_empty() || to.is_empty() {
+                    return Err(invalid_request("from and to must not be empty".to_string()));
+                }
+                ApiReviewTarget::CommitRange { from, to }
+            }
+            ApiReviewTarget::PatchFile { path } => {
+                if path.as_os_str().is_empty() {
+                    return Err(invalid_request("path must not be empty".to_string()));
+                }
+                ApiReviewTarget::PatchFile { path }
+            }
             ApiReviewTarget::Custom { instructions } => {
                 let trimmed = instructions.trim().to_string();
                 if trimmed.is_empty() {
@@ -596,13 +653,23 @@ impl CodexMessageProcessor {
             ApiReviewTarget::UncommittedChanges => CoreReviewTarget::UncommittedChanges,
             ApiReviewTarget::BaseBranch { branch } => CoreReviewTarget::BaseBranch { branch },
             ApiReviewTarget::Commit { sha, title } => CoreReviewTarget::Commit { sha, title },
+            ApiReviewTarget::CommitRange { from, to } => CoreReviewTarget::CommitRange { from, to },
+            ApiReviewTarget::PatchFile { path } => CoreReviewTarget::PatchFile { path },
             ApiReviewTarget::Custom { instructions } => CoreReviewTarget::Custom { instructions },
         };
 
+        let paths = paths
+            .unwrap_or_default()
+            .into_iter()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty())
+            .collect();
+
         let hint = codex_core::review_prompts::user_facing_hint(&core_target);
         let review_request = ReviewRequest {
             target: core_target,
             user_facing_hint: Some(hint.clone()),
+            paths,
         };
 
         Ok((review_request, hint))
@@ -683,6 +750,34 @@ impl CodexMessageProcessor {
                 self.thread_read(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::ThreadSearch { request_id, params } => {
+                self.thread_search(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ThreadExport { request_id, params } => {
+                self.thread_export(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::UsageSummary { request_id, params } => {
+                self.usage_summary(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ScheduleList { request_id, params } => {
+                self.schedule_list(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ScheduleCreate { request_id, params } => {
+                self.schedule_create(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ScheduleDelete { request_id, params } => {
+                self.schedule_delete(to_connection_request_id(request_id), params)
+                    .await;
+            }
+            ClientRequest::ScheduleRuns { request_id, params } => {
+                self.schedule_runs(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::SkillsList { request_id, params } => {
                 self.skills_list(to_connection_request_id(request_id), params)
                     .await;
@@ -790,6 +885,10 @@ impl CodexMessageProcessor {
                 self.list_mcp_server_status(to_connection_request_id(request_id), params)
                     .await;
             }
+            ClientRequest::McpServerPromptGet { request_id, params } => {
+                self.get_mcp_server_prompt(to_connection_request_id(request_id), params)
+                    .await;
+            }
             ClientRequest::WindowsSandboxSetupStart { request_id, params } => {
                 self.windows_sandbox_setup_start(to_connection_request_id(request_id), params)
                     .await;
@@ -3043,6 +3142,388 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn thread_export(&self, request_id: ConnectionRequestId, params: ThreadExportParams) {
+        let ThreadExportParams { thread_id, format } = params;
+        let thread_uuid = match ThreadId::from_string(&thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+
+        let thread_id_str = thread_uuid.to_string();
+        let rollout_path = match find_thread_path_by_id_str(&self.config.codex_home, &thread_id_str)
+            .await
+        {
+            Ok(Some(path)) => Ok(Some(path)),
+            Ok(None) => {
+                find_archived_thread_path_by_id_str(&self.config.codex_home, &thread_id_str).await
+            }
+            Err(err) => Err(err),
+        };
+        let rollout_path = match rollout_path {
+            Ok(Some(path)) => path,
+            Ok(None) => {
+                let loaded_rollout_path = match self.thread_manager.get_thread(thread_uuid).await {
+                    Ok(thread) => thread.rollout_path(),
+                    Err(_) => None,
+                };
+                let Some(path) = loaded_rollout_path else {
+                    self.send_invalid_request_error(
+                        request_id,
+                        format!("no rollout found for thread id {thread_uuid}"),
+                    )
+                    .await;
+                    return;
+                };
+                path
+            }
+            Err(err) => {
+                self.send_invalid_request_error(
+                    request_id,
+                    format!("failed to locate thread id {thread_uuid}: {err}"),
+                )
+                .await;
+                return;
+            }
+        };
+
+        let export = match ThreadExport::load(&self.config.codex_home, &rollout_path).await {
+            Ok(export) => export,
+            Err(err) => {
+                self.send_internal_error(
+                    request_id,
+                    format!(
+                        "failed to load rollout `{}` for thread {thread_uuid}: {err}",
+                        rollout_path.display()
+                    ),
+                )
+                .await;
+                return;
+            }
+        };
+        let content = export.render(match format {
+            ThreadExportFormat::Html => CoreThreadExportFormat::Html,
+            ThreadExportFormat::Markdown => CoreThreadExportFormat::Markdown,
+        });
+        let response = ThreadExportResponse { format, content };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
+    async fn thread_search(&self, request_id: ConnectionRequestId, params: ThreadSearchParams) {
+        let ThreadSearchParams {
+            query,
+            limit,
+            archived,
+        } = params;
+        let limit = limit
+            .map(|value| value as usize)
+            .unwrap_or(THREAD_SEARCH_DEFAULT_LIMIT)
+            .clamp(1, THREAD_SEARCH_MAX_LIMIT);
+
+        let Some(state_db_ctx) = get_state_db(&self.config).await else {
+            self.send_internal_error(request_id, "sqlite state db unavailable".to_string())
+                .await;
+            return;
+        };
+        let hits = match state_db_ctx
+            .search_threads(
+                &query,
+                limit,
+                THREAD_SEARCH_MAX_MATCHES_PER_THREAD,
+                archived.unwrap_or(false),
+            )
+            .await
+        {
+            Ok(hits) => hits,
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to search threads: {err}"))
+                    .await;
+                return;
+            }
+        };
+
+        // Hits arrive best-first; keep that order for both threads and their matches.
+        let mut grouped: Vec<(ThreadId, Vec<ThreadSearchMatch>)> = Vec::new();
+        for hit in hits {
+            let role = match hit.role.as_str() {
+                "user" => ThreadSearchMatchRole::User,
+                _ => ThreadSearchMatchRole::Assistant,
+            };
+            let search_match = ThreadSearchMatch {
+                turn_id: hit.turn_id,
+                role,
+                snippet: hit.snippet,
+            };
+            if let Some((_, matches)) = grouped
+                .iter_mut()
+                .find(|(thread_id, _)| *thread_id == hit.thread_id)
+            {
+                matches.push(search_match);
+            } else {
+                grouped.push((hit.thread_id, vec![search_match]));
+            }
+        }
+
+        let thread_ids = grouped
+            .iter()
+            .map(|(thread_id, _)| *thread_id)
+            .collect::<HashSet<_>>();
+        let names = match find_thread_names_by_ids(&self.config.codex_home, &thread_ids).await {
+            Ok(names) => names,
+            Err(err) => {
+                warn!("Failed to read thread names: {err}");
+                HashMap::new()
+            }
+        };
+        let statuses = self
+            .thread_watch_manager
+            .loaded_statuses_for_threads(
+                grouped
+                    .iter()
+                    .map(|(thread_id, _)| thread_id.to_string())
+                    .collect(),
+            )
+            .await;
+
+        let mut data = Vec::with_capacity(grouped.len());
+        for (thread_id, matches) in grouped {
+            let Some(summary) =
+                read_summary_from_state_db_context_by_thread_id(Some(&state_db_ctx), thread_id)
+                    .await
+            else {
+                continue;
+            };
+            let mut thread = summary_to_thread(summary);
+            thread.name = names.get(&thread_id).cloned();
+            if let Some(status) = statuses.get(&thread.id) {
+                thread.status = status.clone();
+            }
+            data.push(ThreadSearchResult { thread, matches });
+        }
+        let response = ThreadSearchResponse { data };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
+    async fn usage_summary(&self, request_id: ConnectionRequestId, params: UsageSummaryParams) {
+        let UsageSummaryParams {
+            group_by,
+            since,
+            until,
+        } = params;
+        let query = TokenUsageSummaryQuery {
+            group_by: group_by
+                .unwrap_or_default()
+                .into_iter()
+                .map(|group_by| match group_by {
+                    UsageGroupBy::Day => TokenUsageGroupBy::Day,
+                    UsageGroupBy::Repository => TokenUsageGroupBy::Repository,
+                    UsageGroupBy::Model => TokenUsageGroupBy::Model,
+                })
+                .collect(),
+            since,
+            until,
+        };
+
+        let Some(state_db_ctx) = get_state_db(&self.config).await else {
+            self.send_internal_error(request_id, "sqlite state db unavailable".to_string())
+                .await;
+            return;
+        };
+        let rows =
+            match token_usage_summary(&state_db_ctx, &query, &self.config.model_pricing).await {
+                Ok(rows) => rows,
+                Err(err) => {
+                    self.send_internal_error(
+                        request_id,
+                        format!("failed to summarize token usage: {err}"),
+                    )
+                    .await;
+                    return;
+                }
+            };
+        let data = rows
+            .into_iter()
+            .map(|(row, cost_usd)| UsageSummaryEntry {
+                day: row.day,
+                repository: row.repository,
+                model: row.model,
+                turns: row.turns,
+                threads: row.threads,
+                usage: TokenUsageBreakdown {
+                    total_tokens: row.total_tokens,
+                    input_tokens: row.input_tokens,
+                    cached_input_tokens: row.cached_input_tokens,
+                    output_tokens: row.output_tokens,
+                    reasoning_output_tokens: row.reasoning_output_tokens,
+                },
+                cost_usd,
+            })
+            .collect();
+        let response = UsageSummaryResponse { data };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
+    /// Schedules do not depend on the thread metadata backfill, so use the
+    /// state DB opened at startup instead of going through `get_state_db`.
+    async fn schedule_state_db(
+        &self,
+        request_id: &ConnectionRequestId,
+    ) -> Option<Arc<StateRuntime>> {
+        if self.state_db.is_none() {
+            self.send_internal_error(request_id.clone(), "state db is unavailable".to_string())
+                .await;
+        }
+        self.state_db.clone()
+    }
+
+    async fn schedule_list(&self, request_id: ConnectionRequestId, _params: ScheduleListParams) {
+        let Some(state_db) = self.schedule_state_db(&request_id).await else {
+            return;
+        };
+        match state_db.list_schedules().await {
+            Ok(schedules) => {
+                let data = schedules.into_iter().map(schedule_to_api).collect();
+                self.outgoing
+                    .send_response(request_id, ScheduleListResponse { data })
+                    .await;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to list schedules: {err}"))
+                    .await;
+            }
+        }
+    }
+
+    async fn schedule_create(&self, request_id: ConnectionRequestId, params: ScheduleCreateParams) {
+        let ScheduleCreateParams {
+            name,
+            cron,
+            prompt,
+            cwd,
+            profile,
+            sandbox,
+            thread_mode,
+            thread_id,
+        } = params;
+        if prompt.trim().is_empty() {
+            self.send_invalid_request_error(request_id, "prompt must not be empty".to_string())
+                .await;
+            return;
+        }
+        if let Err(err) = codex_state::CronSchedule::parse(&cron) {
+            self.send_invalid_request_error(request_id, err.to_string())
+                .await;
+            return;
+        }
+        if let Some(thread_id) = thread_id.as_deref()
+            && let Err(err) = ThreadId::from_string(thread_id)
+        {
+            self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                .await;
+            return;
+        }
+        let thread_mode = match thread_mode {
+            Some(ApiScheduleThreadMode::New) => codex_state::ScheduleThreadMode::New,
+            Some(ApiScheduleThreadMode::Resume) => codex_state::ScheduleThreadMode::Resume,
+            None if thread_id.is_some() => codex_state::ScheduleThreadMode::Resume,
+            None => codex_state::ScheduleThreadMode::New,
+        };
+        let cwd = match cwd {
+            Some(cwd) => self.config.cwd.join(cwd),
+            None => self.config.cwd.clone(),
+        };
+        let create_params = codex_state::ScheduleCreateParams {
+            id: Uuid::now_v7().to_string(),
+            name: name.unwrap_or_else(|| codex_state::Schedule::default_name(&prompt)),
+            cron,
+            prompt,
+            cwd: cwd.to_string_lossy().into_owned(),
+            profile,
+            sandbox_mode: sandbox.map(sandbox_mode_to_cli_value),
+            thread_mode,
+            thread_id,
+        };
+
+        let Some(state_db) = self.schedule_state_db(&request_id).await else {
+            return;
+        };
+        match state_db.create_schedule(&create_params).await {
+            Ok(schedule) => {
+                let response = ScheduleCreateResponse {
+                    schedule: schedule_to_api(schedule),
+                };
+                self.outgoing.send_response(request_id, response).await;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to create schedule: {err}"))
+                    .await;
+            }
+        }
+    }
+
+    async fn schedule_delete(&self, request_id: ConnectionRequestId, params: ScheduleDeleteParams) {
+        let Some(state_db) = self.schedule_state_db(&request_id).await else {
+            return;
+        };
+        match state_db.delete_schedule(&params.id).await {
+            Ok(true) => {
+                self.outgoing
+                    .send_response(request_id, ScheduleDeleteResponse {})
+                    .await;
+            }
+            Ok(false) => {
+                self.send_invalid_request_error(
+                    request_id,
+                    format!("schedule not found: {}", params.id),
+                )
+                .await;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to delete schedule: {err}"))
+                    .await;
+            }
+        }
+    }
+
+    async fn schedule_runs(&self, request_id: ConnectionRequestId, params: ScheduleRunsParams) {
+        let ScheduleRunsParams { id, limit } = params;
+        let Some(state_db) = self.schedule_state_db(&request_id).await else {
+            return;
+        };
+        match state_db.get_schedule(&id).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                self.send_invalid_request_error(request_id, format!("schedule not found: {id}"))
+                    .await;
+                return;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to read schedule: {err}"))
+                    .await;
+                return;
+            }
+        }
+        let limit = limit.map(|limit| limit as usize);
+        match state_db.list_schedule_runs(&id, limit).await {
+            Ok(runs) => {
+                let data = runs.into_iter().map(schedule_run_to_api).collect();
+                self.outgoing
+                    .send_response(request_id, ScheduleRunsResponse { data })
+                    .await;
+            }
+            Err(err) => {
+                self.send_internal_error(
+                    request_id,
+                    format!("failed to list schedule runs: {err}"),
+                )
+                .await;
+            }
+        }
+    }
+
     pub(crate) fn thread_created_receiver(&self) -> broadcast::Receiver<ThreadId> {
         self.thread_manager.subscribe_thread_created()
     }
@@ -4416,6 +4897,44 @@ impl CodexMessageProcessor {
         });
     }
 
+    async fn get_mcp_server_prompt(
+        &self,
+        request_id: ConnectionRequestId,
+        params: McpServerPromptGetParams,
+    ) {
+        let outgoing = Arc::clone(&self.outgoing);
+        let config = match self.load_latest_config(None).await {
+            Ok(config) => config,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        tokio::spawn(async move {
+            let McpServerPromptGetParams {
+                server,
+                name,
+                arguments,
+            } = params;
+            match get_mcp_prompt(&config, &server, &name, arguments.unwrap_or_default()).await {
+                Ok(text) => {
+                    outgoing
+                        .send_response(request_id, McpServerPromptGetResponse { text })
+                        .await;
+                }
+                Err(message) => {
+                    let error = JSONRPCErrorError {
+                        code: INVALID_REQUEST_ERROR_CODE,
+                        message,
+                        data: None,
+                    };
+                    outgoing.send_error(request_id, error).await;
+                }
+            }
+        });
+    }
+
     async fn list_mcp_server_status_task(
         outgoing: Arc<OutgoingMessageSender>,
         request_id: ConnectionRequestId,
@@ -4433,6 +4952,7 @@ impl CodexMessageProcessor {
             .chain(snapshot.auth_statuses.keys().cloned())
             .chain(snapshot.resources.keys().cloned())
             .chain(snapshot.resource_templates.keys().cloned())
+            .chain(snapshot.prompts.keys().cloned())
             .collect();
         server_names.sort();
         server_names.dedup();
@@ -4479,6 +4999,7 @@ impl CodexMessageProcessor {
                     .get(name)
                     .cloned()
                     .unwrap_or_default(),
+                prompts: snapshot.prompts.get(name).cloned().unwrap_or_default(),
                 auth_status: snapshot
                     .auth_statuses
                     .get(name)
@@ -6041,6 +6562,7 @@ impl CodexMessageProcessor {
             thread_id,
             target,
             delivery,
+            paths,
         } = params;
         let (parent_thread_id, parent_thread) = match self.load_thread(&thread_id).await {
             Ok(v) => v,
@@ -6050,7 +6572,7 @@ impl CodexMessageProcessor {
             }
         };
 
-        let (review_request, display_text) = match Self::review_request_from_target(target) {
+        let (review_request, display_text) = match Self::review_request_from_target(target, paths) {
             Ok(value) => value,
             Err(err) => {
                 self.outgoing.send_error(request_id, err).await;
@@ -7144,6 +7666,67 @@ async fn sync_default_client_residency_requirement(
 ///   `typesafe_overrides` is a `ConfigOverrides` derived from the respective request object.
 ///   Because the overrides are defined explicitly in the `*Params`, this takes priority over
 ///   the more general "bag of config options" provided by `cli_overrides` and `request_overrides`.
+/// Value of the `codex exec --sandbox` flag for a sandbox mode.
+fn sandbox_mode_to_cli_value(sandbox: SandboxMode) -> String {
+    match sandbox {
+        SandboxMode::ReadOnly => "read-only",
+        SandboxMode::WorkspaceWrite => "workspace-write",
+        SandboxMode::DangerFullAccess => "danger-full-access",
+    }
+    .to_string()
+}
+
+fn sandbox_mode_from_cli_value(value: &str) -> Option<SandboxMode> {
+    match value {
+        "read-only" => Some(SandboxMode::ReadOnly),
+        "workspace-write" => Some(SandboxMode::WorkspaceWrite),
+        "danger-full-access" => Some(SandboxMode::DangerFullAccess),
+        _ => None,
+    }
+}
+
+fn schedule_to_api(schedule: codex_state::Schedule) -> ApiSchedule {
+    ApiSchedule {
+        id: schedule.id,
+        name: schedule.name,
+        cron: schedule.cron,
+        prompt: schedule.prompt,
+        cwd: schedule.cwd,
+        profile: schedule.profile,
+        sandbox: schedule
+            .sandbox_mode
+            .as_deref()
+            .and_then(sandbox_mode_from_cli_value),
+        thread_mode: match schedule.thread_mode {
+            codex_state::ScheduleThreadMode::New => ApiScheduleThreadMode::New,
+            codex_state::ScheduleThreadMode::Resume => ApiScheduleThreadMode::Resume,
+        },
+        thread_id: schedule.thread_id,
+        enabled: schedule.enabled,
+        next_run_at: schedule.next_run_at.map(|at| at.timestamp()),
+        created_at: schedule.created_at.timestamp(),
+        updated_at: schedule.updated_at.timestamp(),
+    }
+}
+
+fn schedule_run_to_api(run: codex_state::ScheduleRun) -> ApiScheduleRun {
+    ApiScheduleRun {
+        id: run.id,
+        schedule_id: run.schedule_id,
+        status: match run.status {
+            codex_state::ScheduleRunStatus::Running => ApiScheduleRunStatus::Running,
+            codex_state::ScheduleRunStatus::Succeeded => ApiScheduleRunStatus::Succeeded,
+            codex_state::ScheduleRunStatus::Failed => ApiScheduleRunStatus::Failed,
+        },
+        thread_id: run.thread_id,
+        exit_code: run.exit_code,
+        error: run.error,
+        scheduled_for: run.scheduled_for.timestamp(),
+        started_at: run.started_at.timestamp(),
+        finished_at: run.finished_at.map(|at| at.timestamp()),
+    }
+}
+
 async fn derive_config_from_params(
     cli_overrides: &[(String, TomlValue)],
     request_overrides: Option<HashMap<String, serde_json::Value>>,
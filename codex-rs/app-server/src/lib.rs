@@ -27,6 +27,7 @@ use crate::transport::ConnectionState;
 use crate::transport::OutboundConnectionState;
 use crate::transport::TransportEvent;
 use crate::transport::route_outgoing_envelope;
+use crate::transport::start_http_acceptor;
 use crate::transport::start_stdio_connection;
 use crate::transport::start_websocket_acceptor;
 use codex_app_server_protocol::ConfigLayerSource;
@@ -68,6 +69,7 @@ mod fuzzy_file_search;
 mod message_processor;
 mod models;
 mod outgoing_message;
+mod scheduler;
 mod server_request_error;
 mod thread_state;
 mod thread_status;
@@ -350,14 +352,15 @@ pub async fn run_main_with_transport(
 
     enum TransportRuntime {
         Stdio,
-        WebSocket {
+        /// A listener (WebSocket or HTTP) accepting any number of clients.
+        Acceptor {
             accept_handle: JoinHandle<()>,
             shutdown_token: CancellationToken,
         },
     }
 
     let mut stdio_handles = Vec::<JoinHandle<()>>::new();
-    let transport_runtime = match transport {
+    let transport_runtime = match &transport {
         AppServerTransport::Stdio => {
             start_stdio_connection(transport_event_tx.clone(), &mut stdio_handles).await?;
             TransportRuntime::Stdio
@@ -365,12 +368,27 @@ pub async fn run_main_with_transport(
         AppServerTransport::WebSocket { bind_address } => {
             let shutdown_token = CancellationToken::new();
             let accept_handle = start_websocket_acceptor(
+                *bind_address,
+                transport_event_tx.clone(),
+                shutdown_token.clone(),
+            )
+            .await?;
+            TransportRuntime::Acceptor {
+                accept_handle,
+                shutdown_token,
+            }
+        }
+        AppServerTransport::Http { bind_address } => {
+            let codex_home = codex_core::config::find_codex_home()?;
+            let shutdown_token = CancellationToken::new();
+            let accept_handle = start_http_acceptor(
                 bind_address,
+                &codex_home,
                 transport_event_tx.clone(),
                 shutdown_token.clone(),
             )
             .await?;
-            TransportRuntime::WebSocket {
+            TransportRuntime::Acceptor {
                 accept_handle,
                 shutdown_token,
             }
@@ -499,13 +517,12 @@ pub async fn run_main_with_transport(
 
     let feedback_layer = feedback.logger_layer();
     let feedback_metadata_layer = feedback.metadata_layer();
-    let log_db = codex_state::StateRuntime::init(
+    let state_db = codex_state::StateRuntime::init(
         config.sqlite_home.clone(),
         config.model_provider_id.clone(),
     )
-    .await
-    .ok()
-    .map(log_db::start);
+    .await;
+    let log_db = state_db.as_ref().ok().cloned().map(log_db::start);
     let log_db_layer = log_db
         .clone()
         .map(|layer| layer.with_filter(Targets::new().with_default(Level::TRACE)));
@@ -582,6 +599,19 @@ pub async fn run_main_with_transport(
         info!("outbound router task exited (channel closed)");
     });
 
+    let scheduler_shutdown_token = CancellationToken::new();
+    let scheduler_handle = match &state_db {
+        Ok(state_db) => Some(scheduler::spawn_scheduler(
+            config.codex_home.clone(),
+            Arc::clone(state_db),
+            scheduler_shutdown_token.clone(),
+        )),
+        Err(err) => {
+            warn!("scheduled runs disabled: failed to open state db: {err}");
+            None
+        }
+    };
+
     let processor_handle = tokio::spawn({
         let outgoing_message_sender = Arc::new(OutgoingMessageSender::new(outgoing_tx));
         let outbound_control_tx = outbound_control_tx;
@@ -596,13 +626,14 @@ pub async fn run_main_with_transport(
             cloud_requirements: cloud_requirements.clone(),
             feedback: feedback.clone(),
             log_db,
+            state_db: state_db.ok(),
             config_warnings,
         });
         let mut thread_created_rx = processor.thread_created_receiver();
         let mut running_turn_count_rx = processor.subscribe_running_assistant_turn_count();
         let mut connections = HashMap::<ConnectionId, ConnectionState>::new();
-        let websocket_accept_shutdown = match &transport_runtime {
-            TransportRuntime::WebSocket { shutdown_token, .. } => Some(shutdown_token.clone()),
+        let acceptor_shutdown = match &transport_runtime {
+            TransportRuntime::Acceptor { shutdown_token, .. } => Some(shutdown_token.clone()),
             TransportRuntime::Stdio => None,
         };
         async move {
@@ -617,7 +648,7 @@ pub async fn run_main_with_transport(
                     shutdown_state.update(running_turn_count, connections.len()),
                     ShutdownAction::Finish
                 ) {
-                    if let Some(shutdown_token) = &websocket_accept_shutdown {
+                    if let Some(shutdown_token) = &acceptor_shutdown {
                         shutdown_token.cancel();
                     }
                     let _ = outbound_control_tx
@@ -709,7 +740,7 @@ pub async fn run_main_with_transport(
                                             .process_request(
                                                 connection_id,
                                                 request,
-                                                transport,
+                                                &transport,
                                                 &mut connection_state.session,
                                                 &connection_state.outbound_initialized,
                                             )
@@ -802,7 +833,12 @@ pub async fn run_main_with_transport(
     let _ = processor_handle.await;
     let _ = outbound_handle.await;
 
-    if let TransportRuntime::WebSocket {
+    scheduler_shutdown_token.cancel();
+    if let Some(scheduler_handle) = scheduler_handle {
+        let _ = scheduler_handle.await;
+    }
+
+    if let TransportRuntime::Acceptor {
         accept_handle,
         shutdown_token,
     } = transport_runtime
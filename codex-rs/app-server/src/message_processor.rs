@@ -54,6 +54,7 @@ use codex_core::models_manager::collaboration_mode_presets::CollaborationModesCo
 use codex_feedback::CodexFeedback;
 use codex_protocol::ThreadId;
 use codex_protocol::protocol::SessionSource;
+use codex_state::StateRuntime;
 use codex_state::log_db::LogDbLayer;
 use futures::FutureExt;
 use tokio::sync::broadcast;
@@ -156,6 +157,7 @@ pub(crate) struct MessageProcessorArgs {
     pub(crate) cloud_requirements: CloudRequirementsLoader,
     pub(crate) feedback: CodexFeedback,
     pub(crate) log_db: Option<LogDbLayer>,
+    pub(crate) state_db: Option<Arc<StateRuntime>>,
     pub(crate) config_warnings: Vec<ConfigWarningNotification>,
 }
 
@@ -172,6 +174,7 @@ impl MessageProcessor {
             cloud_requirements,
             feedback,
             log_db,
+            state_db,
             config_warnings,
         } = args;
         let auth_manager = AuthManager::shared(
@@ -208,6 +211,7 @@ impl MessageProcessor {
             cloud_requirements: cloud_requirements.clone(),
             feedback,
             log_db,
+            state_db,
         });
         let config_api = ConfigApi::new(
             config.codex_home.clone(),
@@ -231,7 +235,7 @@ impl MessageProcessor {
         &mut self,
         connection_id: ConnectionId,
         request: JSONRPCRequest,
-        transport: AppServerTransport,
+        transport: &AppServerTransport,
         session: &mut ConnectionSessionState,
         outbound_initialized: &AtomicBool,
     ) {
@@ -0,0 +1,275 @@
+//! Background runner for the schedules stored in the state DB.
+//!
+//! Schedules are created with `schedule/create` or `codex schedule create`.
+//! Every [`POLL_INTERVAL`] the scheduler claims the schedules that are due and
+//! runs each one as a `codex exec --json` child process, recording the thread
+//! it ran in and the process exit status in `schedule_runs`.
+
+use chrono::Utc;
+use codex_state::Schedule;
+use codex_state::ScheduleRunOutcome;
+use codex_state::ScheduleRunStatus;
+use codex_state::ScheduleThreadMode;
+use codex_state::StateRuntime;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::BufReader;
+use tokio::process::Command;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+use tracing::warn;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Number of trailing stderr lines kept as the error of a failed run.
+const ERROR_TAIL_LINES: usize = 20;
+
+pub(crate) fn spawn_scheduler(
+    codex_home: PathBuf,
+    state_db: Arc<StateRuntime>,
+    shutdown_token: CancellationToken,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                _ = shutdown_token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            let schedules = match state_db.claim_due_schedules(Utc::now()).await {
+                Ok(schedules) => schedules,
+                Err(err) => {
+                    warn!("failed to claim due schedules: {err}");
+                    continue;
+                }
+            };
+            for schedule in schedules {
+                let codex_home = codex_home.clone();
+                let state_db = Arc::clone(&state_db);
+                tokio::spawn(async move {
+                    run_schedule(&codex_home, &state_db, &schedule).await;
+                });
+            }
+        }
+    })
+}
+
+async fn run_schedule(codex_home: &Path, state_db: &StateRuntime, schedule: &Schedule) {
+    let scheduled_for = schedule.next_run_at.unwrap_or_else(Utc::now);
+    let run = match state_db
+        .start_schedule_run(schedule.id.as_str(), scheduled_for)
+        .await
+    {
+        Ok(run) => run,
+        Err(err) => {
+            warn!("failed to record run of schedule {}: {err}", schedule.id);
+            return;
+        }
+    };
+    info!(schedule_id = %schedule.id, run_id = run.id, "starting scheduled run");
+    let outcome = execute_schedule(codex_home, schedule).await;
+    info!(
+        schedule_id = %schedule.id,
+        run_id = run.id,
+        status = outcome.status.as_str(),
+        "scheduled run finished"
+    );
+    if let Err(err) = state_db.finish_schedule_run(run.id, &outcome).await {
+        warn!("failed to record result of schedule run {}: {err}", run.id);
+    }
+}
+
+async fn execute_schedule(codex_home: &Path, schedule: &Schedule) -> ScheduleRunOutcome {
+    let failed = |error: String| ScheduleRunOutcome {
+        status: ScheduleRunStatus::Failed,
+        thread_id: None,
+        exit_code: None,
+        error: Some(error),
+    };
+    let mut child = match Command::new(codex_executable())
+        .args(exec_args(schedule))
+        .env("CODEX_HOME", codex_home)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) => return failed(format!("failed to start `codex exec`: {err}")),
+    };
+
+    let stderr_tail = child.stderr.take().map(|stderr| {
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            let mut tail = VecDeque::with_capacity(ERROR_TAIL_LINES);
+            while let Ok(Some(line)) = lines.next_line().await {
+                if tail.len() == ERROR_TAIL_LINES {
+                    tail.pop_front();
+                }
+                tail.push_back(line);
+            }
+            Vec::from(tail).join("\n")
+        })
+    });
+    let mut thread_id = None;
+    if let Some(stdout) = child.stdout.take() {
+        let mut lines = BufReader::new(stdout).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if thread_id.is_none() {
+                thread_id = thread_id_from_event(&line);
+            }
+        }
+    }
+
+    let status = match child.wait().await {
+        Ok(status) => status,
+        Err(err) => return failed(format!("failed to wait for `codex exec`: {err}")),
+    };
+    let stderr_tail = match stderr_tail {
+        Some(handle) => handle.await.unwrap_or_default(),
+        None => String::new(),
+    };
+    let succeeded = status.success();
+    ScheduleRunOutcome {
+        status: if succeeded {
+            ScheduleRunStatus::Succeeded
+        } else {
+            ScheduleRunStatus::Failed
+        },
+        thread_id,
+        exit_code: status.code(),
+        error: (!succeeded).then(|| {
+            if stderr_tail.trim().is_empty() {
+                format!("`codex exec` exited with {status}")
+            } else {
+                stderr_tail
+            }
+        }),
+    }
+}
+
+/// Arguments for the `codex exec` invocation of one run.
+fn exec_args(schedule: &Schedule) -> Vec<String> {
+    let mut args = vec![
+        "exec".to_string(),
+        "--json".to_string(),
+        "--cd".to_string(),
+        schedule.cwd.clone(),
+    ];
+    if let Some(profile) = &schedule.profile {
+        args.push("--profile".to_string());
+        args.push(profile.clone());
+    }
+    if let Some(sandbox_mode) = &schedule.sandbox_mode {
+        args.push("--sandbox".to_string());
+        args.push(sandbox_mode.clone());
+    }
+    if schedule.thread_mode == ScheduleThreadMode::Resume
+        && let Some(thread_id) = &schedule.thread_id
+    {
+        args.push("resume".to_string());
+        args.push(thread_id.clone());
+    }
+    // Keep prompts that start with `-` from being parsed as flags.
+    args.push("--".to_string());
+    args.push(schedule.prompt.clone());
+    args
+}
+
+/// Extract the thread id from a `thread.started` JSONL event.
+fn thread_id_from_event(line: &str) -> Option<String> {
+    let event: serde_json::Value = serde_json::from_str(line).ok()?;
+    if event.get("type")?.as_str()? != "thread.started" {
+        return None;
+    }
+    event.get("thread_id")?.as_str().map(str::to_string)
+}
+
+/// `codex exec` is a subcommand of the main `codex` binary. When the app-server
+/// runs as `codex app-server` that is the current executable; the standalone
+/// `codex-app-server` binary looks for `codex` next to itself, then on `PATH`.
+fn codex_executable() -> PathBuf {
+    let codex = PathBuf::from(format!("codex{}", std::env::consts::EXE_SUFFIX));
+    let Ok(current_exe) = std::env::current_exe() else {
+        return codex;
+    };
+    if current_exe.file_stem().and_then(|stem| stem.to_str()) == Some("codex") {
+        return current_exe;
+    }
+    current_exe
+        .parent()
+        .map(|dir| dir.join(&codex))
+        .filter(|sibling| sibling.is_file())
+        .unwrap_or(codex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn schedule(thread_mode: ScheduleThreadMode, thread_id: Option<&str>) -> Schedule {
+        let now = Utc::now();
+        Schedule {
+            id: "nightly".to_string(),
+            name: "nightly".to_string(),
+            cron: "0 2 * * *".to_string(),
+            prompt: "triage new failing tests".to_string(),
+            cwd: "/repo".to_string(),
+            profile: Some("ci".to_string()),
+            sandbox_mode: Some("workspace-write".to_string()),
+            thread_mode,
+            thread_id: thread_id.map(str::to_string),
+            enabled: true,
+            next_run_at: Some(now),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn exec_args_start_or_resume_thread() {
+        assert_eq!(
+            exec_args(&schedule(ScheduleThreadMode::New, Some("thread-1"))),
+            vec![
+                "exec",
+                "--json",
+                "--cd",
+                "/repo",
+                "--profile",
+                "ci",
+                "--sandbox",
+                "workspace-write",
+                "--",
+                "triage new failing tests",
+            ]
+        );
+        assert_eq!(
+            exec_args(&schedule(ScheduleThreadMode::Resume, Some("thread-1")))[8..],
+            ["resume", "thread-1", "--", "triage new failing tests"]
+        );
+        assert_eq!(
+            exec_args(&schedule(ScheduleThreadMode::Resume, None))[8..],
+            ["--", "triage new failing tests"]
+        );
+    }
+
+    #[test]
+    fn extracts_thread_id_from_thread_started_event() {
+        assert_eq!(
+            thread_id_from_event(r#"{"type":"thread.started","thread_id":"abc"}"#),
+            Some("abc".to_string())
+        );
+        assert_eq!(thread_id_from_event(r#"{"type":"turn.started"}"#), None);
+        assert_eq!(thread_id_from_event("not json"), None);
+    }
+}
@@ -17,6 +17,7 @@ use std::collections::HashSet;
 use std::io::ErrorKind;
 use std::io::Result as IoResult;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::RwLock;
@@ -40,6 +41,10 @@ use tracing::error;
 use tracing::info;
 use tracing::warn;
 
+mod http;
+
+pub(crate) use http::start_http_acceptor;
+
 /// Size of the bounded channels used to communicate between tasks. The value
 /// is a balance between throughput and memory usage - 128 messages should be
 /// plenty for an interactive CLI.
@@ -69,16 +74,40 @@ fn print_websocket_startup_banner(addr: SocketAddr) {
     }
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum AppServerTransport {
     Stdio,
-    WebSocket { bind_address: SocketAddr },
+    WebSocket {
+        bind_address: SocketAddr,
+    },
+    /// JSON-RPC over HTTP POST with a Server-Sent-Events notification stream.
+    Http {
+        bind_address: HttpBindAddress,
+    },
+}
+
+/// Where the HTTP transport listens. Only loopback TCP addresses and Unix
+/// domain sockets are accepted.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum HttpBindAddress {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+impl std::fmt::Display for HttpBindAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HttpBindAddress::Tcp(addr) => write!(f, "http://{addr}"),
+            HttpBindAddress::Unix(path) => write!(f, "unix://{}", path.display()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum AppServerTransportParseError {
     UnsupportedListenUrl(String),
     InvalidWebSocketListenUrl(String),
+    InvalidHttpListenUrl(String),
 }
 
 impl std::fmt::Display for AppServerTransportParseError {
@@ -86,12 +115,16 @@ impl std::fmt::Display for AppServerTransportParseError {
         match self {
             AppServerTransportParseError::UnsupportedListenUrl(listen_url) => write!(
                 f,
-                "unsupported --listen URL `{listen_url}`; expected `stdio://` or `ws://IP:PORT`"
+                "unsupported --listen URL `{listen_url}`; expected `stdio://`, `ws://IP:PORT`, `http://LOOPBACK_IP:PORT` or `unix://PATH`"
             ),
             AppServerTransportParseError::InvalidWebSocketListenUrl(listen_url) => write!(
                 f,
                 "invalid websocket --listen URL `{listen_url}`; expected `ws://IP:PORT`"
             ),
+            AppServerTransportParseError::InvalidHttpListenUrl(listen_url) => write!(
+                f,
+                "invalid http --listen URL `{listen_url}`; expected `http://LOOPBACK_IP:PORT` or `unix://PATH`"
+            ),
         }
     }
 }
@@ -113,6 +146,31 @@ impl AppServerTransport {
             return Ok(Self::WebSocket { bind_address });
         }
 
+        if let Some(socket_addr) = listen_url.strip_prefix("http://") {
+            let bind_address = socket_addr
+                .trim_end_matches('/')
+                .parse::<SocketAddr>()
+                .ok()
+                .filter(|addr| addr.ip().is_loopback())
+                .ok_or_else(|| {
+                    AppServerTransportParseError::InvalidHttpListenUrl(listen_url.to_string())
+                })?;
+            return Ok(Self::Http {
+                bind_address: HttpBindAddress::Tcp(bind_address),
+            });
+        }
+
+        if let Some(path) = listen_url.strip_prefix("unix://") {
+            if path.is_empty() {
+                return Err(AppServerTransportParseError::InvalidHttpListenUrl(
+                    listen_url.to_string(),
+                ));
+            }
+            return Ok(Self::Http {
+                bind_address: HttpBindAddress::Unix(PathBuf::from(path)),
+            });
+        }
+
         Err(AppServerTransportParseError::UnsupportedListenUrl(
             listen_url.to_string(),
         ))
@@ -711,13 +769,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn app_server_transport_parses_http_listen_urls() {
+        let transport = AppServerTransport::from_listen_url("http://127.0.0.1:1234")
+            .expect("http listen URL should parse");
+        assert_eq!(
+            transport,
+            AppServerTransport::Http {
+                bind_address: HttpBindAddress::Tcp(
+                    "127.0.0.1:1234".parse().expect("valid socket address")
+                ),
+            }
+        );
+
+        let transport = AppServerTransport::from_listen_url("unix:///tmp/codex.sock")
+            .expect("unix listen URL should parse");
+        assert_eq!(
+            transport,
+            AppServerTransport::Http {
+                bind_address: HttpBindAddress::Unix(PathBuf::from("/tmp/codex.sock")),
+            }
+        );
+    }
+
+    #[test]
+    fn app_server_transport_rejects_non_loopback_http_listen_url() {
+        let err = AppServerTransport::from_listen_url("http://0.0.0.0:1234")
+            .expect_err("non-loopback bind address should be rejected");
+        assert_eq!(
+            err.to_string(),
+            "invalid http --listen URL `http://0.0.0.0:1234`; expected `http://LOOPBACK_IP:PORT` or `unix://PATH`"
+        );
+    }
+
     #[test]
     fn app_server_transport_rejects_unsupported_listen_url() {
-        let err = AppServerTransport::from_listen_url("http://127.0.0.1:1234")
+        let err = AppServerTransport::from_listen_url("tcp://127.0.0.1:1234")
             .expect_err("unsupported scheme should fail");
         assert_eq!(
             err.to_string(),
-            "unsupported --listen URL `http://127.0.0.1:1234`; expected `stdio://` or `ws://IP:PORT`"
+            "unsupported --listen URL `tcp://127.0.0.1:1234`; expected `stdio://`, `ws://IP:PORT`, `http://LOOPBACK_IP:PORT` or `unix://PATH`"
         );
     }
 
@@ -0,0 +1,409 @@
+//! HTTP + Server-Sent-Events transport.
+//!
+//! Every `GET /events` stream is one app-server connection. Its first event
+//! (`session`) carries the session id; notifications and server requests for
+//! that connection follow as `message` events. Clients send JSON-RPC messages
+//! with `POST /rpc` and the `X-Codex-Session` header. Requests block until the
+//! server responds, and the response is returned as the HTTP response body.
+//!
+//! All endpoints require `Authorization: Bearer <token>`, where the token is
+//! stored in [`HTTP_TOKEN_FILE`] under `CODEX_HOME`.
+
+use super::CHANNEL_CAPACITY;
+use super::HttpBindAddress;
+use super::TransportEvent;
+use super::colorize;
+use super::enqueue_incoming_message;
+use super::serialize_outgoing_message;
+use crate::outgoing_message::ConnectionId;
+use crate::outgoing_message::OutgoingMessage;
+use axum::Router;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::StatusCode;
+use axum::http::header::AUTHORIZATION;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::response::sse::Event;
+use axum::response::sse::KeepAlive;
+use axum::response::sse::Sse;
+use axum::routing::get;
+use axum::routing::post;
+use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::RequestId;
+use futures::StreamExt;
+use owo_colors::Style;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::io::ErrorKind;
+use std::io::Result as IoResult;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::error;
+use tracing::info;
+use uuid::Uuid;
+
+/// File under `CODEX_HOME` holding the bearer token for the HTTP transport.
+/// It is created on first use; delete it to rotate the token.
+pub(crate) const HTTP_TOKEN_FILE: &str = "app-server-http.token";
+
+/// Header naming the `GET /events` session a `POST /rpc` message belongs to.
+const SESSION_HEADER: &str = "x-codex-session";
+
+#[derive(Clone)]
+struct HttpState {
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+    token: Arc<str>,
+    sessions: Arc<Mutex<HashMap<String, Arc<HttpSession>>>>,
+    connection_counter: Arc<AtomicU64>,
+}
+
+struct HttpSession {
+    connection_id: ConnectionId,
+    writer: mpsc::Sender<OutgoingMessage>,
+    /// `POST /rpc` requests waiting for their response, keyed by request id.
+    pending_requests: Mutex<HashMap<RequestId, oneshot::Sender<OutgoingMessage>>>,
+}
+
+impl HttpSession {
+    /// Hand responses to the `POST /rpc` call that is waiting for them.
+    /// Returns the message back if nobody is waiting for it.
+    async fn take_response(&self, message: OutgoingMessage) -> Option<OutgoingMessage> {
+        let id = match &message {
+            OutgoingMessage::Response(response) => &response.id,
+            OutgoingMessage::Error(error) => &error.id,
+            _ => return Some(message),
+        };
+        let Some(response_tx) = self.pending_requests.lock().await.remove(id) else {
+            return Some(message);
+        };
+        // The HTTP client may have gone away; the response is dropped then.
+        let _ = response_tx.send(message);
+        None
+    }
+}
+
+#[allow(clippy::print_stderr)]
+fn print_http_startup_banner(bind_address: &HttpBindAddress, token_path: &Path) {
+    let title = colorize("codex app-server (HTTP)", Style::new().bold().cyan());
+    let listening_label = colorize("listening on:", Style::new().dimmed());
+    let listen_url = colorize(&bind_address.to_string(), Style::new().green());
+    let token_label = colorize("token file:", Style::new().dimmed());
+    let note_label = colorize("note:", Style::new().dimmed());
+    eprintln!("{title}");
+    eprintln!("  {listening_label} {listen_url}");
+    eprintln!("  {token_label} {}", token_path.display());
+    eprintln!(
+        "  {note_label} open GET /events, then POST JSON-RPC messages to /rpc with the {SESSION_HEADER} header"
+    );
+}
+
+pub(crate) async fn start_http_acceptor(
+    bind_address: &HttpBindAddress,
+    codex_home: &Path,
+    transport_event_tx: mpsc::Sender<TransportEvent>,
+    shutdown_token: CancellationToken,
+) -> IoResult<JoinHandle<()>> {
+    let token_path = codex_home.join(HTTP_TOKEN_FILE);
+    let token = load_or_create_token(&token_path).await?;
+    let state = HttpState {
+        transport_event_tx,
+        token: token.into(),
+        sessions: Arc::new(Mutex::new(HashMap::new())),
+        connection_counter: Arc::new(AtomicU64::new(1)),
+    };
+    let router = Router::new()
+        .route("/rpc", post(handle_rpc))
+        .route("/events", get(handle_events))
+        .with_state(state);
+
+    match bind_address {
+        HttpBindAddress::Tcp(bind_address) => {
+            let listener = TcpListener::bind(bind_address).await?;
+            let local_addr = listener.local_addr()?;
+            print_http_startup_banner(&HttpBindAddress::Tcp(local_addr), &token_path);
+            info!("app-server http listening on http://{local_addr}");
+            let server = axum::serve(listener, router)
+                .with_graceful_shutdown(shutdown_token.cancelled_owned());
+            Ok(tokio::spawn(async move {
+                if let Err(err) = server.await {
+                    error!("http transport failed: {err}");
+                }
+            }))
+        }
+        #[cfg(unix)]
+        HttpBindAddress::Unix(path) => {
+            remove_stale_socket(path).await?;
+            let listener = tokio::net::UnixListener::bind(path)?;
+            print_http_startup_banner(bind_address, &token_path);
+            info!("app-server http listening on unix://{}", path.display());
+            let server = axum::serve(listener, router)
+                .with_graceful_shutdown(shutdown_token.cancelled_owned());
+            Ok(tokio::spawn(async move {
+                if let Err(err) = server.await {
+                    error!("http transport failed: {err}");
+                }
+            }))
+        }
+        #[cfg(not(unix))]
+        HttpBindAddress::Unix(_) => Err(std::io::Error::new(
+            ErrorKind::Unsupported,
+            "unix domain sockets are not supported on this platform",
+        )),
+    }
+}
+
+/// Remove a socket left behind by a previous run. Anything that is not a
+/// socket is left alone so that binding fails instead of clobbering it.
+#[cfg(unix)]
+async fn remove_stale_socket(path: &Path) -> IoResult<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match tokio::fs::symlink_metadata(path).await {
+        Ok(metadata) if metadata.file_type().is_socket() => tokio::fs::remove_file(path).await,
+        Ok(_) => Ok(()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+    }
+}
+
+async fn load_or_create_token(path: &Path) -> IoResult<String> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(token) if !token.trim().is_empty() => return Ok(token.trim().to_string()),
+        Ok(_) => {}
+        Err(err) if err.kind() == ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+
+    let bytes: [u8; 32] = rand::random();
+    let token = bytes
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path).await?;
+    file.write_all(format!("{token}\n").as_bytes()).await?;
+    file.flush().await?;
+    Ok(token)
+}
+
+fn is_authorized(state: &HttpState, headers: &HeaderMap) -> bool {
+    let Some(presented) = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    let expected = state.token.as_bytes();
+    let presented = presented.trim().as_bytes();
+    // Compare in constant time for tokens of the expected length.
+    presented.len() == expected.len()
+        && presented
+            .iter()
+            .zip(expected)
+            .fold(0u8, |acc, (lhs, rhs)| acc | (lhs ^ rhs))
+            == 0
+}
+
+fn unauthorized() -> Response {
+    (StatusCode::UNAUTHORIZED, "missing or invalid bearer token").into_response()
+}
+
+async fn handle_events(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+
+    let connection_id = ConnectionId(state.connection_counter.fetch_add(1, Ordering::Relaxed));
+    let session_id = Uuid::now_v7().to_string();
+    let (writer_tx, writer_rx) = mpsc::channel::<OutgoingMessage>(CHANNEL_CAPACITY);
+    let session = Arc::new(HttpSession {
+        connection_id,
+        writer: writer_tx.clone(),
+        pending_requests: Mutex::new(HashMap::new()),
+    });
+    state
+        .sessions
+        .lock()
+        .await
+        .insert(session_id.clone(), Arc::clone(&session));
+
+    let disconnect_token = CancellationToken::new();
+    if state
+        .transport_event_tx
+        .send(TransportEvent::ConnectionOpened {
+            connection_id,
+            writer: writer_tx,
+            disconnect_sender: Some(disconnect_token.clone()),
+        })
+        .await
+        .is_err()
+    {
+        state.sessions.lock().await.remove(&session_id);
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "app-server is shutting down",
+        )
+            .into_response();
+    }
+    info!(?connection_id, "http client connected");
+
+    let (event_tx, event_rx) = mpsc::channel::<Event>(CHANNEL_CAPACITY);
+    let session_event = Event::default()
+        .event("session")
+        .data(serde_json::json!({ "sessionId": session_id }).to_string());
+    tokio::spawn(run_http_session(
+        state,
+        session_id,
+        session,
+        writer_rx,
+        event_tx,
+        disconnect_token,
+    ));
+
+    let events = futures::stream::unfold(event_rx, |mut event_rx| async move {
+        event_rx.recv().await.map(|event| (event, event_rx))
+    });
+    let stream = futures::stream::once(async move { session_event })
+        .chain(events)
+        .map(Ok::<_, Infallible>);
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Forward outgoing messages for one session: responses go to the waiting
+/// `POST /rpc` call, everything else to the SSE stream. Closes the connection
+/// once the stream is dropped or the router disconnects it.
+async fn run_http_session(
+    state: HttpState,
+    session_id: String,
+    session: Arc<HttpSession>,
+    mut writer_rx: mpsc::Receiver<OutgoingMessage>,
+    event_tx: mpsc::Sender<Event>,
+    disconnect_token: CancellationToken,
+) {
+    loop {
+        let message = tokio::select! {
+            _ = disconnect_token.cancelled() => break,
+            _ = event_tx.closed() => break,
+            message = writer_rx.recv() => message,
+        };
+        let Some(message) = message else {
+            break;
+        };
+        let Some(message) = session.take_response(message).await else {
+            continue;
+        };
+        let Some(json) = serialize_outgoing_message(message) else {
+            continue;
+        };
+        tokio::select! {
+            _ = disconnect_token.cancelled() => break,
+            sent = event_tx.send(Event::default().event("message").data(json)) => {
+                if sent.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    state.sessions.lock().await.remove(&session_id);
+    // Fail any `POST /rpc` calls still waiting on this session.
+    session.pending_requests.lock().await.clear();
+    info!(connection_id = ?session.connection_id, "http client disconnected");
+    let _ = state
+        .transport_event_tx
+        .send(TransportEvent::ConnectionClosed {
+            connection_id: session.connection_id,
+        })
+        .await;
+}
+
+async fn handle_rpc(State(state): State<HttpState>, headers: HeaderMap, body: String) -> Response {
+    if !is_authorized(&state, &headers) {
+        return unauthorized();
+    }
+    let Some(session_id) = headers
+        .get(SESSION_HEADER)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!("missing {SESSION_HEADER} header; open GET /events first"),
+        )
+            .into_response();
+    };
+    let Some(session) = state.sessions.lock().await.get(session_id).cloned() else {
+        return (StatusCode::NOT_FOUND, "unknown session").into_response();
+    };
+    let message = match serde_json::from_str::<JSONRPCMessage>(&body) {
+        Ok(message) => message,
+        Err(err) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("invalid JSON-RPC message: {err}"),
+            )
+                .into_response();
+        }
+    };
+
+    let response_rx = match &message {
+        JSONRPCMessage::Request(request) => {
+            let mut pending_requests = session.pending_requests.lock().await;
+            if pending_requests.contains_key(&request.id) {
+                return (
+                    StatusCode::CONFLICT,
+                    "a request with this id is already in flight",
+                )
+                    .into_response();
+            }
+            let (response_tx, response_rx) = oneshot::channel();
+            pending_requests.insert(request.id.clone(), response_tx);
+            Some(response_rx)
+        }
+        _ => None,
+    };
+
+    if !enqueue_incoming_message(
+        &state.transport_event_tx,
+        &session.writer,
+        session.connection_id,
+        message,
+    )
+    .await
+    {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "app-server is shutting down",
+        )
+            .into_response();
+    }
+    let Some(response_rx) = response_rx else {
+        return StatusCode::ACCEPTED.into_response();
+    };
+    match response_rx.await {
+        Ok(message) => match serialize_outgoing_message(message) {
+            Some(json) => ([(CONTENT_TYPE, "application/json")], json).into_response(),
+            None => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        },
+        Err(_) => (StatusCode::SERVICE_UNAVAILABLE, "session closed").into_response(),
+    }
+}
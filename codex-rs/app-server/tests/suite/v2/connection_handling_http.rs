@@ -0,0 +1,299 @@
+use super::connection_handling_websocket::DEFAULT_READ_TIMEOUT;
+use super::connection_handling_websocket::create_config_toml;
+use super::connection_handling_websocket::reserve_local_addr;
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use app_test_support::create_mock_responses_server_sequence_unchecked;
+use codex_app_server_protocol::ClientInfo;
+use codex_app_server_protocol::InitializeParams;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::JSONRPCRequest;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use pretty_assertions::assert_eq;
+use reqwest::StatusCode;
+use serde_json::json;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::process::Stdio;
+use tempfile::TempDir;
+use tokio::io::AsyncBufReadExt;
+use tokio::process::Child;
+use tokio::process::Command;
+use tokio::time::Duration;
+use tokio::time::Instant;
+use tokio::time::sleep;
+use tokio::time::timeout;
+
+const TOKEN_FILE: &str = "app-server-http.token";
+
+#[tokio::test]
+async fn http_transport_requires_token_and_routes_per_session() -> Result<()> {
+    let server = create_mock_responses_server_sequence_unchecked(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+
+    let bind_addr = reserve_local_addr()?;
+    let mut process = spawn_http_server(codex_home.path(), bind_addr).await?;
+    let token = wait_for_token(codex_home.path()).await?;
+    let client = reqwest::Client::new();
+    let base_url = format!("http://{bind_addr}");
+
+    let mut events1 = EventStream::connect(&client, &base_url, &token).await?;
+    let unauthorized = client
+        .get(format!("{base_url}/events"))
+        .bearer_auth("not-the-token")
+        .send()
+        .await?;
+    assert_eq!(unauthorized.status(), StatusCode::UNAUTHORIZED);
+
+    let mut events2 = EventStream::connect(&client, &base_url, &token).await?;
+    let session1 = events1.read_session_id().await?;
+    let session2 = events2.read_session_id().await?;
+    assert_ne!(session1, session2);
+
+    let missing_session = client
+        .post(format!("{base_url}/rpc"))
+        .bearer_auth(&token)
+        .body(serde_json::to_string(&initialize_request(1, "missing"))?)
+        .send()
+        .await?;
+    assert_eq!(missing_session.status(), StatusCode::BAD_REQUEST);
+
+    let first_init: JSONRPCResponse = post_rpc(
+        &client,
+        &base_url,
+        &token,
+        &session1,
+        initialize_request(1, "http_client_one"),
+    )
+    .await?;
+    assert_eq!(first_init.id, RequestId::Integer(1));
+
+    let not_initialized: JSONRPCError = post_rpc(
+        &client,
+        &base_url,
+        &token,
+        &session2,
+        config_read_request(2),
+    )
+    .await?;
+    assert_eq!(not_initialized.error.message, "Not initialized");
+
+    // Same request-id on different sessions must route independently.
+    let second_init: JSONRPCResponse = post_rpc(
+        &client,
+        &base_url,
+        &token,
+        &session2,
+        initialize_request(1, "http_client_two"),
+    )
+    .await?;
+    assert_eq!(second_init.id, RequestId::Integer(1));
+
+    let config: JSONRPCResponse = post_rpc(
+        &client,
+        &base_url,
+        &token,
+        &session1,
+        config_read_request(77),
+    )
+    .await?;
+    assert!(config.result.get("config").is_some());
+
+    process
+        .kill()
+        .await
+        .context("failed to stop http app-server process")?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn http_transport_reuses_existing_token() -> Result<()> {
+    let server = create_mock_responses_server_sequence_unchecked(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "never")?;
+    std::fs::write(codex_home.path().join(TOKEN_FILE), "preset-token\n")?;
+
+    let bind_addr = reserve_local_addr()?;
+    let mut process = spawn_http_server(codex_home.path(), bind_addr).await?;
+    let client = reqwest::Client::new();
+    let base_url = format!("http://{bind_addr}");
+
+    let mut events = EventStream::connect(&client, &base_url, "preset-token").await?;
+    events.read_session_id().await?;
+    assert_eq!(
+        std::fs::read_to_string(codex_home.path().join(TOKEN_FILE))?,
+        "preset-token\n"
+    );
+
+    process
+        .kill()
+        .await
+        .context("failed to stop http app-server process")?;
+    Ok(())
+}
+
+struct EventStream {
+    response: reqwest::Response,
+    buffer: String,
+}
+
+impl EventStream {
+    /// Open `GET /events`, retrying while the server is still starting up.
+    async fn connect(client: &reqwest::Client, base_url: &str, token: &str) -> Result<Self> {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            match client
+                .get(format!("{base_url}/events"))
+                .bearer_auth(token)
+                .send()
+                .await
+            {
+                Ok(response) if response.status() == StatusCode::OK => {
+                    return Ok(Self {
+                        response,
+                        buffer: String::new(),
+                    });
+                }
+                Ok(response) => bail!("unexpected /events status {}", response.status()),
+                Err(err) => {
+                    if Instant::now() >= deadline {
+                        bail!("failed to connect to {base_url}/events: {err}");
+                    }
+                    sleep(Duration::from_millis(50)).await;
+                }
+            }
+        }
+    }
+
+    /// Read the next SSE event as `(event, data)`.
+    async fn next_event(&mut self) -> Result<(String, String)> {
+        loop {
+            if let Some(end) = self.buffer.find("\n\n") {
+                let raw_event: String = self.buffer.drain(..end + 2).collect();
+                let mut event = String::from("message");
+                let mut data = Vec::new();
+                for line in raw_event.lines() {
+                    if let Some(value) = line.strip_prefix("event:") {
+                        event = value.trim().to_string();
+                    } else if let Some(value) = line.strip_prefix("data:") {
+                        data.push(value.trim_start().to_string());
+                    }
+                }
+                if data.is_empty() {
+                    // Keep-alive comment.
+                    continue;
+                }
+                return Ok((event, data.join("\n")));
+            }
+            let chunk = timeout(DEFAULT_READ_TIMEOUT, self.response.chunk())
+                .await
+                .context("timed out waiting for SSE event")?
+                .context("failed to read SSE stream")?
+                .context("SSE stream ended")?;
+            self.buffer.push_str(std::str::from_utf8(&chunk)?);
+        }
+    }
+
+    async fn read_session_id(&mut self) -> Result<String> {
+        let (event, data) = self.next_event().await?;
+        assert_eq!(event, "session");
+        let value: serde_json::Value = serde_json::from_str(&data)?;
+        value
+            .get("sessionId")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string)
+            .context("session event should carry a sessionId")
+    }
+}
+
+async fn spawn_http_server(codex_home: &Path, bind_addr: SocketAddr) -> Result<Child> {
+    let program = codex_utils_cargo_bin::cargo_bin("codex-app-server")
+        .context("should find app-server binary")?;
+    let mut cmd = Command::new(program);
+    cmd.arg("--listen")
+        .arg(format!("http://{bind_addr}"))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .env("CODEX_HOME", codex_home)
+        .env("RUST_LOG", "debug");
+    let mut process = cmd
+        .kill_on_drop(true)
+        .spawn()
+        .context("failed to spawn http app-server process")?;
+
+    if let Some(stderr) = process.stderr.take() {
+        let mut stderr_reader = tokio::io::BufReader::new(stderr).lines();
+        tokio::spawn(async move {
+            while let Ok(Some(line)) = stderr_reader.next_line().await {
+                eprintln!("[http app-server stderr] {line}");
+            }
+        });
+    }
+
+    Ok(process)
+}
+
+async fn wait_for_token(codex_home: &Path) -> Result<String> {
+    let token_path = codex_home.join(TOKEN_FILE);
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        if let Ok(token) = std::fs::read_to_string(&token_path)
+            && !token.trim().is_empty()
+        {
+            return Ok(token.trim().to_string());
+        }
+        if Instant::now() >= deadline {
+            bail!("token file {} was not written", token_path.display());
+        }
+        sleep(Duration::from_millis(50)).await;
+    }
+}
+
+async fn post_rpc<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+    session_id: &str,
+    message: JSONRPCMessage,
+) -> Result<T> {
+    let response = client
+        .post(format!("{base_url}/rpc"))
+        .bearer_auth(token)
+        .header("X-Codex-Session", session_id)
+        .body(serde_json::to_string(&message)?)
+        .send()
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    Ok(response.json().await?)
+}
+
+fn initialize_request(id: i64, client_name: &str) -> JSONRPCMessage {
+    let params = InitializeParams {
+        client_info: ClientInfo {
+            name: client_name.to_string(),
+            title: Some("HTTP Test Client".to_string()),
+            version: "0.1.0".to_string(),
+        },
+        capabilities: None,
+    };
+    JSONRPCMessage::Request(JSONRPCRequest {
+        id: RequestId::Integer(id),
+        method: "initialize".to_string(),
+        params: serde_json::to_value(params).ok(),
+        trace: None,
+    })
+}
+
+fn config_read_request(id: i64) -> JSONRPCMessage {
+    JSONRPCMessage::Request(JSONRPCRequest {
+        id: RequestId::Integer(id),
+        method: "config/read".to_string(),
+        params: Some(json!({ "includeLayers": false })),
+        trace: None,
+    })
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use axum::Router;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::ListMcpServerStatusParams;
+use codex_app_server_protocol::ListMcpServerStatusResponse;
+use codex_app_server_protocol::McpServerPromptGetParams;
+use codex_app_server_protocol::McpServerPromptGetResponse;
+use codex_app_server_protocol::RequestId;
+use codex_protocol::mcp::Prompt as McpPrompt;
+use codex_protocol::mcp::PromptArgument as McpPromptArgument;
+use pretty_assertions::assert_eq;
+use rmcp::handler::server::ServerHandler;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
+use rmcp::model::ListPromptsResult;
+use rmcp::model::PaginatedRequestParams;
+use rmcp::model::Prompt;
+use rmcp::model::PromptArgument;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
+use rmcp::model::ServerCapabilities;
+use rmcp::model::ServerInfo;
+use rmcp::service::RequestContext;
+use rmcp::service::RoleServer;
+use rmcp::transport::StreamableHttpServerConfig;
+use rmcp::transport::StreamableHttpService;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use tempfile::TempDir;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+const INVALID_REQUEST_ERROR_CODE: i64 = -32600;
+const SERVER_NAME: &str = "docs";
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn mcp_server_status_lists_prompts_and_prompt_get_renders_them() -> Result<()> {
+    let (server_url, server_handle) = start_prompt_server().await?;
+    let codex_home = TempDir::new()?;
+    write_config_toml(codex_home.path(), &server_url)?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_list_mcp_server_status_request(ListMcpServerStatusParams {
+            cursor: None,
+            limit: None,
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ListMcpServerStatusResponse { data, .. } =
+        to_response::<ListMcpServerStatusResponse>(resp)?;
+    let status = data
+        .iter()
+        .find(|status| status.name == SERVER_NAME)
+        .expect("docs server status");
+    assert_eq!(
+        status.prompts,
+        vec![McpPrompt {
+            name: "review".to_string(),
+            title: None,
+            description: Some("Review a file for bugs.".to_string()),
+            arguments: vec![McpPromptArgument {
+                name: "path".to_string(),
+                title: None,
+                description: Some("File to review.".to_string()),
+                required: true,
+            }],
+        }]
+    );
+
+    let request_id = mcp
+        .send_mcp_server_prompt_get_request(McpServerPromptGetParams {
+            server: SERVER_NAME.to_string(),
+            name: "review".to_string(),
+            arguments: Some(HashMap::from([(
+                "path".to_string(),
+                "src/lib.rs".to_string(),
+            )])),
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let McpServerPromptGetResponse { text } = to_response::<McpServerPromptGetResponse>(resp)?;
+    assert_eq!(text, "Review src/lib.rs for bugs.");
+
+    let request_id = mcp
+        .send_mcp_server_prompt_get_request(McpServerPromptGetParams {
+            server: "missing".to_string(),
+            name: "review".to_string(),
+            arguments: None,
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(error.error.code, INVALID_REQUEST_ERROR_CODE);
+    assert!(
+        error.error.message.contains("unknown MCP server"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    server_handle.abort();
+    Ok(())
+}
+
+#[derive(Clone, Default)]
+struct PromptMcpServer;
+
+impl ServerHandler for PromptMcpServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            protocol_version: rmcp::model::ProtocolVersion::V_2025_06_18,
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_prompts()
+                .build(),
+            ..ServerInfo::default()
+        }
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, rmcp::ErrorData> {
+        Ok(ListPromptsResult {
+            prompts: vec![Prompt::new(
+                "review",
+                Some("Review a file for bugs."),
+                Some(vec![PromptArgument {
+                    name: "path".to_string(),
+                    title: None,
+                    description: Some("File to review.".to_string()),
+                    required: Some(true),
+                }]),
+            )],
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParams,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, rmcp::ErrorData> {
+        let path = request
+            .arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get("path"))
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| rmcp::ErrorData::invalid_params("missing argument: path", None))?;
+        Ok(GetPromptResult {
+            description: None,
+            messages: vec![PromptMessage::new_text(
+                PromptMessageRole::User,
+                format!("Review {path} for bugs."),
+            )],
+        })
+    }
+}
+
+async fn start_prompt_server() -> Result<(String, JoinHandle<()>)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let addr = listener.local_addr()?;
+
+    let mcp_service = StreamableHttpService::new(
+        move || Ok(PromptMcpServer),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+    let router = Router::new().nest_service("/mcp", mcp_service);
+
+    let handle = tokio::spawn(async move {
+        let _ = axum::serve(listener, router).await;
+    });
+
+    Ok((format!("http://{addr}/mcp"), handle))
+}
+
+fn write_config_toml(codex_home: &std::path::Path, server_url: &str) -> std::io::Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+mcp_oauth_credentials_store = "file"
+
+[mcp_servers.{SERVER_NAME}]
+url = "{server_url}"
+"#
+        ),
+    )
+}
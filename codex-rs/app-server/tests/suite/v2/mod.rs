@@ -6,6 +6,7 @@ mod collaboration_mode_list;
 mod command_exec;
 mod compaction;
 mod config_rpc;
+mod connection_handling_http;
 mod connection_handling_websocket;
 #[cfg(unix)]
 mod connection_handling_websocket_unix;
@@ -13,6 +14,7 @@ mod dynamic_tools;
 mod experimental_api;
 mod experimental_feature_list;
 mod initialize;
+mod mcp_prompt;
 mod mcp_server_elicitation;
 mod model_list;
 mod output_schema;
@@ -24,8 +26,10 @@ mod realtime_conversation;
 mod request_user_input;
 mod review;
 mod safety_check_downgrade;
+mod schedule;
 mod skills_list;
 mod thread_archive;
+mod thread_export;
 mod thread_fork;
 mod thread_list;
 mod thread_loaded_list;
@@ -34,6 +38,7 @@ mod thread_name_websocket;
 mod thread_read;
 mod thread_resume;
 mod thread_rollback;
+mod thread_search;
 mod thread_start;
 mod thread_status;
 mod thread_unarchive;
@@ -42,4 +47,5 @@ mod turn_interrupt;
 mod turn_start;
 mod turn_start_zsh_fork;
 mod turn_steer;
+mod usage_summary;
 mod windows_sandbox_setup;
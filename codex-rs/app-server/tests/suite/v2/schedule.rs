@@ -0,0 +1,167 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::SandboxMode;
+use codex_app_server_protocol::ScheduleCreateParams;
+use codex_app_server_protocol::ScheduleCreateResponse;
+use codex_app_server_protocol::ScheduleDeleteParams;
+use codex_app_server_protocol::ScheduleDeleteResponse;
+use codex_app_server_protocol::ScheduleListParams;
+use codex_app_server_protocol::ScheduleListResponse;
+use codex_app_server_protocol::ScheduleRunStatus;
+use codex_app_server_protocol::ScheduleRunsParams;
+use codex_app_server_protocol::ScheduleRunsResponse;
+use codex_app_server_protocol::ScheduleThreadMode;
+use codex_state::ScheduleRunOutcome;
+use codex_state::StateRuntime;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const INVALID_REQUEST_ERROR_CODE: i64 = -32600;
+
+#[tokio::test]
+async fn schedule_create_list_runs_and_delete() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_schedule_create_request(ScheduleCreateParams {
+            name: None,
+            cron: "0 2 * * *".to_string(),
+            prompt: "Triage new failing tests\nand open issues for them.".to_string(),
+            cwd: Some(workspace.path().to_string_lossy().into_owned()),
+            profile: Some("nightly".to_string()),
+            sandbox: Some(SandboxMode::WorkspaceWrite),
+            thread_mode: None,
+            thread_id: None,
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ScheduleCreateResponse { schedule } = to_response::<ScheduleCreateResponse>(resp)?;
+    assert_eq!(schedule.name, "Triage new failing tests");
+    assert_eq!(schedule.cwd, workspace.path().to_string_lossy());
+    assert_eq!(schedule.sandbox, Some(SandboxMode::WorkspaceWrite));
+    assert_eq!(schedule.thread_mode, ScheduleThreadMode::New);
+    assert!(schedule.enabled);
+    assert!(
+        schedule
+            .next_run_at
+            .is_some_and(|at| at > schedule.created_at)
+    );
+
+    let request_id = mcp
+        .send_schedule_list_request(ScheduleListParams {})
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ScheduleListResponse { data } = to_response::<ScheduleListResponse>(resp)?;
+    assert_eq!(data, vec![schedule.clone()]);
+
+    // Record a run the way the scheduler does and read it back.
+    let state_db =
+        StateRuntime::init(codex_home.path().to_path_buf(), "mock_provider".into()).await?;
+    let run = state_db
+        .start_schedule_run(&schedule.id, chrono::Utc::now())
+        .await?;
+    state_db
+        .finish_schedule_run(
+            run.id,
+            &ScheduleRunOutcome {
+                status: codex_state::ScheduleRunStatus::Failed,
+                thread_id: Some("thread-1".to_string()),
+                exit_code: Some(1),
+                error: Some("tests failed".to_string()),
+            },
+        )
+        .await?;
+
+    let request_id = mcp
+        .send_schedule_runs_request(ScheduleRunsParams {
+            id: schedule.id.clone(),
+            limit: None,
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ScheduleRunsResponse { data } = to_response::<ScheduleRunsResponse>(resp)?;
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].status, ScheduleRunStatus::Failed);
+    assert_eq!(data[0].thread_id.as_deref(), Some("thread-1"));
+    assert_eq!(data[0].exit_code, Some(1));
+    assert_eq!(data[0].error.as_deref(), Some("tests failed"));
+    assert!(data[0].finished_at.is_some());
+
+    let request_id = mcp
+        .send_schedule_delete_request(ScheduleDeleteParams {
+            id: schedule.id.clone(),
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let _: ScheduleDeleteResponse = to_response::<ScheduleDeleteResponse>(resp)?;
+
+    let request_id = mcp
+        .send_schedule_delete_request(ScheduleDeleteParams { id: schedule.id })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(error.error.code, INVALID_REQUEST_ERROR_CODE);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn schedule_create_rejects_invalid_cron() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_schedule_create_request(ScheduleCreateParams {
+            name: Some("broken".to_string()),
+            cron: "61 * * * *".to_string(),
+            prompt: "audit dependencies".to_string(),
+            cwd: None,
+            profile: None,
+            sandbox: None,
+            thread_mode: None,
+            thread_id: None,
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(error.error.code, INVALID_REQUEST_ERROR_CODE);
+    assert!(
+        error.error.message.contains("minute field"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_fake_rollout;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadExportFormat;
+use codex_app_server_protocol::ThreadExportParams;
+use codex_app_server_protocol::ThreadExportResponse;
+use codex_protocol::ThreadId;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn thread_export_renders_markdown_transcript() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let thread_id = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-05T12-00-00",
+        "2025-01-05T12:00:00Z",
+        "Summarize the incident",
+        Some("mock_provider"),
+        None,
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    let request_id = mcp
+        .send_thread_export_request(ThreadExportParams {
+            thread_id: thread_id.clone(),
+            format: ThreadExportFormat::Markdown,
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ThreadExportResponse { format, content } = to_response::<ThreadExportResponse>(resp)?;
+
+    assert_eq!(format, ThreadExportFormat::Markdown);
+    assert_eq!(
+        content,
+        format!(
+            "\
+# Codex session `{thread_id}`
+
+- Thread: {thread_id}
+- Started: 2025-01-05T12:00:00Z
+- Working directory: /
+
+## User
+
+Summarize the incident
+
+"
+        )
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn thread_export_rejects_unknown_threads() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let thread_id = ThreadId::new().to_string();
+    let request_id = mcp
+        .send_thread_export_request(ThreadExportParams {
+            thread_id: thread_id.clone(),
+            format: ThreadExportFormat::Html,
+        })
+        .await?;
+    let err: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(
+        err.error.message,
+        format!("no rollout found for thread id {thread_id}")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_fake_rollout;
+use app_test_support::rollout_path;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadSearchMatch;
+use codex_app_server_protocol::ThreadSearchMatchRole;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
+use codex_app_server_protocol::ThreadStatus;
+use codex_core::state_db::reconcile_rollout;
+use codex_state::StateRuntime;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn thread_search_returns_matching_threads_with_snippets() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+model = "mock-model"
+approval_policy = "never"
+suppress_unstable_features_warning = true
+
+[features]
+sqlite = true
+"#,
+    )?;
+    let state_db =
+        StateRuntime::init(codex_home.path().to_path_buf(), "mock_provider".into()).await?;
+    state_db.mark_backfill_complete(None).await?;
+
+    let matching = create_indexed_rollout(
+        codex_home.path(),
+        &state_db,
+        "2025-01-02T10-00-00",
+        "2025-01-02T10:00:00Z",
+        "fix the flaky websocket reconnect test",
+    )
+    .await?;
+    create_indexed_rollout(
+        codex_home.path(),
+        &state_db,
+        "2025-01-02T11-00-00",
+        "2025-01-02T11:00:00Z",
+        "write release notes",
+    )
+    .await?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    let request_id = mcp
+        .send_thread_search_request(ThreadSearchParams {
+            query: "websock flaky".to_string(),
+            limit: None,
+            archived: None,
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ThreadSearchResponse { data } = to_response::<ThreadSearchResponse>(resp)?;
+
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].thread.id, matching);
+    assert_eq!(data[0].thread.status, ThreadStatus::NotLoaded);
+    assert_eq!(
+        data[0].matches,
+        vec![ThreadSearchMatch {
+            turn_id: None,
+            role: ThreadSearchMatchRole::User,
+            snippet: "fix the [flaky] [websocket] reconnect test".to_string(),
+        }]
+    );
+
+    Ok(())
+}
+
+async fn create_indexed_rollout(
+    codex_home: &Path,
+    state_db: &StateRuntime,
+    filename_ts: &str,
+    meta_rfc3339: &str,
+    preview: &str,
+) -> Result<String> {
+    let thread_id = create_fake_rollout(
+        codex_home,
+        filename_ts,
+        meta_rfc3339,
+        preview,
+        Some("mock_provider"),
+        None,
+    )?;
+    reconcile_rollout(
+        Some(state_db),
+        rollout_path(codex_home, filename_ts, &thread_id).as_path(),
+        "mock_provider",
+        None,
+        &[],
+        None,
+        None,
+    )
+    .await;
+    Ok(thread_id)
+}
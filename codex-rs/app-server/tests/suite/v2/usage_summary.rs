@@ -0,0 +1,147 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_fake_rollout;
+use app_test_support::rollout_path;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::TokenUsageBreakdown;
+use codex_app_server_protocol::UsageGroupBy;
+use codex_app_server_protocol::UsageSummaryEntry;
+use codex_app_server_protocol::UsageSummaryParams;
+use codex_app_server_protocol::UsageSummaryResponse;
+use codex_core::state_db::reconcile_rollout;
+use codex_protocol::protocol::GitInfo;
+use codex_state::StateRuntime;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::io::Write;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn usage_summary_groups_recorded_usage_and_applies_pricing() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    std::fs::write(
+        codex_home.path().join("config.toml"),
+        r#"
+model = "mock-model"
+approval_policy = "never"
+suppress_unstable_features_warning = true
+
+[features]
+sqlite = true
+
+[model_pricing."mock-model"]
+input = 2.0
+output = 8.0
+"#,
+    )?;
+    let state_db =
+        StateRuntime::init(codex_home.path().to_path_buf(), "mock_provider".into()).await?;
+    state_db.mark_backfill_complete(None).await?;
+
+    let filename_ts = "2025-01-02T10-00-00";
+    let meta_rfc3339 = "2025-01-02T10:00:00Z";
+    let thread_id = create_fake_rollout(
+        codex_home.path(),
+        filename_ts,
+        meta_rfc3339,
+        "count my tokens",
+        Some("mock_provider"),
+        Some(GitInfo {
+            commit_hash: None,
+            branch: None,
+            repository_url: Some("https://github.com/openai/codex.git".to_string()),
+        }),
+    )?;
+    let path = rollout_path(codex_home.path(), filename_ts, &thread_id);
+    let usage = json!({
+        "input_tokens": 1000,
+        "cached_input_tokens": 200,
+        "output_tokens": 100,
+        "reasoning_output_tokens": 40,
+        "total_tokens": 1100,
+    });
+    let lines = [
+        json!({
+            "timestamp": meta_rfc3339,
+            "type": "turn_context",
+            "payload": {
+                "turn_id": "turn-1",
+                "cwd": "/",
+                "approval_policy": "never",
+                "sandbox_policy": { "type": "read-only" },
+                "model": "mock-model",
+                "summary": "auto",
+            }
+        }),
+        json!({
+            "timestamp": meta_rfc3339,
+            "type": "event_msg",
+            "payload": {
+                "type": "token_count",
+                "info": {
+                    "total_token_usage": usage,
+                    "last_token_usage": usage,
+                    "model_context_window": null,
+                },
+                "rate_limits": null,
+            }
+        }),
+    ];
+    let mut file = std::fs::OpenOptions::new().append(true).open(&path)?;
+    for line in lines {
+        writeln!(file, "{line}")?;
+    }
+    drop(file);
+    reconcile_rollout(
+        Some(&state_db),
+        path.as_path(),
+        "mock_provider",
+        None,
+        &[],
+        None,
+        None,
+    )
+    .await;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    let request_id = mcp
+        .send_usage_summary_request(UsageSummaryParams {
+            group_by: Some(vec![UsageGroupBy::Repository, UsageGroupBy::Model]),
+            since: None,
+            until: None,
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let UsageSummaryResponse { data } = to_response::<UsageSummaryResponse>(resp)?;
+
+    assert_eq!(
+        data,
+        vec![UsageSummaryEntry {
+            day: None,
+            repository: Some("https://github.com/openai/codex.git".to_string()),
+            model: Some("mock-model".to_string()),
+            turns: 1,
+            threads: 1,
+            usage: TokenUsageBreakdown {
+                total_tokens: 1100,
+                input_tokens: 1000,
+                cached_input_tokens: 200,
+                output_tokens: 100,
+                reasoning_output_tokens: 40,
+            },
+            cost_usd: Some(0.0028),
+        }]
+    );
+
+    Ok(())
+}
@@ -13,12 +13,15 @@ use crate::ApplyPatchArgs;
 use crate::ApplyPatchError;
 use crate::ApplyPatchFileChange;
 use crate::ApplyPatchFileUpdate;
+use crate::ApplyPatchOptions;
+use crate::HunkReport;
+use crate::HunkStatus;
 use crate::IoError;
 use crate::MaybeApplyPatchVerified;
 use crate::parser::Hunk;
 use crate::parser::ParseError;
 use crate::parser::parse_patch;
-use crate::unified_diff_from_chunks;
+use crate::partial_unified_diff_from_chunks;
 use std::str::Utf8Error;
 use tree_sitter::LanguageError;
 
@@ -130,6 +133,18 @@ pub fn maybe_parse_apply_patch(argv: &[String]) -> MaybeApplyPatch {
 /// cwd must be an absolute path so that we can resolve relative paths in the
 /// patch.
 pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApplyPatchVerified {
+    maybe_parse_apply_patch_verified_with_options(argv, cwd, ApplyPatchOptions::default())
+}
+
+/// Like [`maybe_parse_apply_patch_verified`], locating hunks according to
+/// `options`. Files whose hunks were all rejected are left out of the action;
+/// if that leaves nothing to apply, the rejections are returned as a
+/// [`ApplyPatchError::HunksRejected`] error.
+pub fn maybe_parse_apply_patch_verified_with_options(
+    argv: &[String],
+    cwd: &Path,
+    options: ApplyPatchOptions,
+) -> MaybeApplyPatchVerified {
     // Detect a raw patch body passed directly as the command or as the body of a shell
     // script. In these cases, report an explicit error rather than applying the patch.
     if let [body] = argv
@@ -161,6 +176,7 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                 })
                 .unwrap_or_else(|| cwd.to_path_buf());
             let mut changes = HashMap::new();
+            let mut report = HunkReport::default();
             for hunk in hunks {
                 let path = hunk.resolve_path(&effective_cwd);
                 match hunk {
@@ -184,15 +200,24 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                     Hunk::UpdateFile {
                         move_path, chunks, ..
                     } => {
-                        let ApplyPatchFileUpdate {
-                            unified_diff,
-                            content: contents,
-                        } = match unified_diff_from_chunks(&path, &chunks) {
+                        let (
+                            ApplyPatchFileUpdate {
+                                unified_diff,
+                                content: contents,
+                            },
+                            statuses,
+                        ) = match partial_unified_diff_from_chunks(&path, &chunks, 1, options) {
                             Ok(diff) => diff,
                             Err(e) => {
                                 return MaybeApplyPatchVerified::CorrectnessError(e);
                             }
                         };
+                        let all_rejected =
+                            !statuses.is_empty() && statuses.iter().all(HunkStatus::is_rejected);
+                        report.extend(&path, statuses);
+                        if all_rejected {
+                            continue;
+                        }
                         changes.insert(
                             path,
                             ApplyPatchFileChange::Update {
@@ -204,10 +229,16 @@ pub fn maybe_parse_apply_patch_verified(argv: &[String], cwd: &Path) -> MaybeApp
                     }
                 }
             }
+            if changes.is_empty() && report.has_rejections() {
+                return MaybeApplyPatchVerified::CorrectnessError(ApplyPatchError::HunksRejected(
+                    report,
+                ));
+            }
             MaybeApplyPatchVerified::Body(ApplyPatchAction {
                 changes,
                 patch,
                 cwd: effective_cwd,
+                options,
             })
         }
         MaybeApplyPatch::ShellParseError(e) => MaybeApplyPatchVerified::ShellParseError(e),
@@ -371,6 +402,7 @@ fn extract_apply_patch_from_bash(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::unified_diff_from_chunks;
     use assert_matches::assert_matches;
     use pretty_assertions::assert_eq;
     use std::fs;
@@ -760,6 +792,7 @@ PATCH"#,
                 )]),
                 patch: argv[1].clone(),
                 cwd: session_dir.path().to_path_buf(),
+                options: ApplyPatchOptions::default(),
             })
         );
     }
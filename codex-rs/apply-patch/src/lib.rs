@@ -4,6 +4,7 @@ mod seek_sequence;
 mod standalone_executable;
 
 use std::collections::HashMap;
+use std::fmt;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -14,10 +15,12 @@ pub use parser::ParseError;
 use parser::ParseError::*;
 use parser::UpdateFileChunk;
 pub use parser::parse_patch;
+use seek_sequence::MatchLevel;
 use similar::TextDiff;
 use thiserror::Error;
 
 pub use invocation::maybe_parse_apply_patch_verified;
+pub use invocation::maybe_parse_apply_patch_verified_with_options;
 pub use standalone_executable::main;
 
 use crate::invocation::ExtractHeredocError;
@@ -34,6 +37,44 @@ pub const APPLY_PATCH_TOOL_INSTRUCTIONS: &str = include_str!("../apply_patch_too
 /// dispatcher.
 pub const CODEX_CORE_APPLY_PATCH_ARG1: &str = "--codex-run-as-apply-patch";
 
+/// Environment variable read by the `apply_patch` executables to override
+/// [`ApplyPatchOptions::fuzz`].
+pub const CODEX_APPLY_PATCH_FUZZ_ENV_VAR: &str = "CODEX_APPLY_PATCH_FUZZ";
+
+/// Default number of context lines that may be ignored at each end of a hunk
+/// when it cannot be located verbatim. Fuzzy matching is opt-in.
+pub const DEFAULT_APPLY_PATCH_FUZZ: usize = 0;
+
+/// Controls how leniently hunks are matched against the file being updated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApplyPatchOptions {
+    /// Maximum number of leading/trailing context lines that may be dropped
+    /// to locate a hunk. Any value above zero also enables matching that
+    /// ignores indentation and other whitespace. `0` only accepts hunks whose
+    /// lines match up to surrounding whitespace and typographic punctuation.
+    pub fuzz: usize,
+}
+
+impl Default for ApplyPatchOptions {
+    fn default() -> Self {
+        Self {
+            fuzz: DEFAULT_APPLY_PATCH_FUZZ,
+        }
+    }
+}
+
+impl ApplyPatchOptions {
+    /// Options from [`CODEX_APPLY_PATCH_FUZZ_ENV_VAR`], falling back to the
+    /// defaults when it is unset or not a number.
+    pub fn from_env() -> Self {
+        std::env::var(CODEX_APPLY_PATCH_FUZZ_ENV_VAR)
+            .ok()
+            .and_then(|value| value.trim().parse().ok())
+            .map(|fuzz| Self { fuzz })
+            .unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Error, PartialEq)]
 pub enum ApplyPatchError {
     #[error(transparent)]
@@ -43,6 +84,9 @@ pub enum ApplyPatchError {
     /// Error that occurs while computing replacements when applying patch chunks
     #[error("{0}")]
     ComputeReplacements(String),
+    /// One or more hunks could not be located in the files they update.
+    #[error("{0}")]
+    HunksRejected(HunkReport),
     /// A raw patch body was provided without an explicit `apply_patch` invocation.
     #[error(
         "patch detected without explicit call to apply_patch. Rerun as [\"apply_patch\", \"<patch>\"]"
@@ -82,6 +126,93 @@ impl PartialEq for IoError {
     }
 }
 
+/// Outcome of applying a single `@@` hunk of an `*** Update File` section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HunkStatus {
+    /// The hunk matched where expected. `line` is the 1-based line of the
+    /// original file where the replaced region starts.
+    Applied { line: usize },
+    /// The hunk only matched earlier in the file than the previous hunk or its
+    /// `@@` context, `offset` lines away from where it was expected.
+    AppliedWithOffset { line: usize, offset: isize },
+    /// The hunk matched after ignoring whitespace and `fuzz` context lines at
+    /// each end (`fuzz` is 0 when only whitespace differed).
+    AppliedFuzzy { line: usize, fuzz: usize },
+    /// The hunk could not be located and was not applied.
+    Rejected {
+        reason: String,
+        nearest: Option<CandidateRegion>,
+    },
+}
+
+impl HunkStatus {
+    pub fn is_rejected(&self) -> bool {
+        matches!(self, HunkStatus::Rejected { .. })
+    }
+}
+
+/// Region of the original file that most resembles a rejected hunk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CandidateRegion {
+    /// 1-based line where the region starts.
+    pub line: usize,
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HunkResult {
+    pub path: PathBuf,
+    /// 1-based index of the hunk within its `*** Update File` section.
+    pub hunk: usize,
+    pub status: HunkStatus,
+}
+
+/// Per-hunk results of applying a patch.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HunkReport {
+    pub hunks: Vec<HunkResult>,
+}
+
+impl HunkReport {
+    fn extend(&mut self, path: &Path, statuses: Vec<HunkStatus>) {
+        self.hunks.extend(
+            statuses
+                .into_iter()
+                .enumerate()
+                .map(|(i, status)| HunkResult {
+                    path: path.to_path_buf(),
+                    hunk: i + 1,
+                    status,
+                }),
+        );
+    }
+
+    pub fn has_rejections(&self) -> bool {
+        self.hunks.iter().any(|result| result.status.is_rejected())
+    }
+}
+
+/// Renders the rejected hunks, which is what gets reported back to the model.
+impl fmt::Display for HunkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        for result in &self.hunks {
+            let HunkStatus::Rejected { reason, nearest } = &result.status else {
+                continue;
+            };
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            write!(f, "{reason}")?;
+            if let Some(CandidateRegion { line, lines }) = nearest {
+                write!(f, "\nNearest match at line {line}:\n{}", lines.join("\n"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Both the raw PATCH argument to `apply_patch` as well as the PATCH argument
 /// parsed into hunks.
 #[derive(Debug, PartialEq)]
@@ -135,6 +266,10 @@ pub struct ApplyPatchAction {
 
     /// The working directory that was used to resolve relative paths in the patch.
     pub cwd: PathBuf,
+
+    /// Options the patch was verified with. Applying it with the same options
+    /// reproduces `changes`.
+    pub options: ApplyPatchOptions,
 }
 
 impl ApplyPatchAction {
@@ -175,6 +310,7 @@ impl ApplyPatchAction {
                 .expect("path should have parent")
                 .to_path_buf(),
             patch,
+            options: ApplyPatchOptions::default(),
         }
     }
 }
@@ -184,6 +320,16 @@ pub fn apply_patch(
     patch: &str,
     stdout: &mut impl std::io::Write,
     stderr: &mut impl std::io::Write,
+) -> Result<(), ApplyPatchError> {
+    apply_patch_with_options(patch, stdout, stderr, ApplyPatchOptions::default())
+}
+
+/// Like [`apply_patch`], matching hunks according to `options`.
+pub fn apply_patch_with_options(
+    patch: &str,
+    stdout: &mut impl std::io::Write,
+    stderr: &mut impl std::io::Write,
+    options: ApplyPatchOptions,
 ) -> Result<(), ApplyPatchError> {
     let hunks = match parse_patch(patch) {
         Ok(source) => source.hunks,
@@ -207,7 +353,7 @@ pub fn apply_patch(
         }
     };
 
-    apply_hunks(&hunks, stdout, stderr)?;
+    apply_hunks_with_options(&hunks, stdout, stderr, options)?;
 
     Ok(())
 }
@@ -217,6 +363,17 @@ pub fn apply_hunks(
     hunks: &[Hunk],
     stdout: &mut impl std::io::Write,
     stderr: &mut impl std::io::Write,
+) -> Result<(), ApplyPatchError> {
+    apply_hunks_with_options(hunks, stdout, stderr, ApplyPatchOptions::default())
+}
+
+/// Applies hunks according to `options`. Hunks that cannot be located are
+/// skipped and reported on stderr; the rest of the patch is still applied.
+pub fn apply_hunks_with_options(
+    hunks: &[Hunk],
+    stdout: &mut impl std::io::Write,
+    stderr: &mut impl std::io::Write,
+    options: ApplyPatchOptions,
 ) -> Result<(), ApplyPatchError> {
     let _existing_paths: Vec<&Path> = hunks
         .iter()
@@ -245,9 +402,23 @@ pub fn apply_hunks(
         .collect::<Vec<&Path>>();
 
     // Delegate to a helper that applies each hunk to the filesystem.
-    match apply_hunks_to_files(hunks) {
-        Ok(affected) => {
+    match apply_hunks_to_files(hunks, options) {
+        Ok((affected, report)) if report.has_rejections() => {
+            if !affected.is_empty() {
+                writeln!(
+                    stdout,
+                    "Applied patch with rejected hunks. Updated the following files:"
+                )
+                .map_err(ApplyPatchError::from)?;
+                print_affected_paths(&affected, stdout).map_err(ApplyPatchError::from)?;
+                print_hunk_notes(&report, stdout).map_err(ApplyPatchError::from)?;
+            }
+            writeln!(stderr, "{report}").map_err(ApplyPatchError::from)?;
+            Err(ApplyPatchError::HunksRejected(report))
+        }
+        Ok((affected, report)) => {
             print_summary(&affected, stdout).map_err(ApplyPatchError::from)?;
+            print_hunk_notes(&report, stdout).map_err(ApplyPatchError::from)?;
             Ok(())
         }
         Err(err) => {
@@ -274,9 +445,19 @@ pub struct AffectedPaths {
     pub deleted: Vec<PathBuf>,
 }
 
-/// Apply the hunks to the filesystem, returning which files were added, modified, or deleted.
-/// Returns an error if the patch could not be applied.
-fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
+impl AffectedPaths {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.deleted.is_empty()
+    }
+}
+
+/// Apply the hunks to the filesystem, returning which files were added, modified, or deleted
+/// together with the per-hunk results of file updates. Files whose hunks were all rejected are
+/// left untouched. Returns an error if the patch could not be applied.
+fn apply_hunks_to_files(
+    hunks: &[Hunk],
+    options: ApplyPatchOptions,
+) -> anyhow::Result<(AffectedPaths, HunkReport)> {
     if hunks.is_empty() {
         anyhow::bail!("No files were modified.");
     }
@@ -284,6 +465,7 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
     let mut added: Vec<PathBuf> = Vec::new();
     let mut modified: Vec<PathBuf> = Vec::new();
     let mut deleted: Vec<PathBuf> = Vec::new();
+    let mut report = HunkReport::default();
     for hunk in hunks {
         match hunk {
             Hunk::AddFile { path, contents } => {
@@ -308,8 +490,16 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
                 move_path,
                 chunks,
             } => {
-                let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(path, chunks)?;
+                let AppliedPatch {
+                    new_contents,
+                    hunks,
+                    ..
+                } = derive_new_contents_from_chunks(path, chunks, options)?;
+                let all_rejected = !hunks.is_empty() && hunks.iter().all(HunkStatus::is_rejected);
+                report.extend(path, hunks);
+                if all_rejected {
+                    continue;
+                }
                 if let Some(dest) = move_path {
                     if let Some(parent) = dest.parent()
                         && !parent.as_os_str().is_empty()
@@ -331,16 +521,20 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
             }
         }
     }
-    Ok(AffectedPaths {
-        added,
-        modified,
-        deleted,
-    })
+    Ok((
+        AffectedPaths {
+            added,
+            modified,
+            deleted,
+        },
+        report,
+    ))
 }
 
 struct AppliedPatch {
     original_contents: String,
     new_contents: String,
+    hunks: Vec<HunkStatus>,
 }
 
 /// Return *only* the new file contents (joined into a single `String`) after
@@ -348,6 +542,7 @@ struct AppliedPatch {
 fn derive_new_contents_from_chunks(
     path: &Path,
     chunks: &[UpdateFileChunk],
+    options: ApplyPatchOptions,
 ) -> std::result::Result<AppliedPatch, ApplyPatchError> {
     let original_contents = match std::fs::read_to_string(path) {
         Ok(contents) => contents,
@@ -367,7 +562,7 @@ fn derive_new_contents_from_chunks(
         original_lines.pop();
     }
 
-    let replacements = compute_replacements(&original_lines, path, chunks)?;
+    let (replacements, hunks) = compute_replacements(&original_lines, path, chunks, options);
     let new_lines = apply_replacements(original_lines, &replacements);
     let mut new_lines = new_lines;
     if !new_lines.last().is_some_and(String::is_empty) {
@@ -377,37 +572,71 @@ fn derive_new_contents_from_chunks(
     Ok(AppliedPatch {
         original_contents,
         new_contents,
+        hunks,
     })
 }
 
+/// `(start_index, old_len, new_lines)` of a region of the original file.
+type Replacement = (usize, usize, Vec<String>);
+
 /// Compute a list of replacements needed to transform `original_lines` into the
 /// new lines, given the patch `chunks`. Each replacement is returned as
-/// `(start_index, old_len, new_lines)`.
+/// `(start_index, old_len, new_lines)`, together with the status of every
+/// chunk. Chunks that cannot be located are [`HunkStatus::Rejected`] and do
+/// not produce a replacement.
 fn compute_replacements(
     original_lines: &[String],
     path: &Path,
     chunks: &[UpdateFileChunk],
-) -> std::result::Result<Vec<(usize, usize, Vec<String>)>, ApplyPatchError> {
-    let mut replacements: Vec<(usize, usize, Vec<String>)> = Vec::new();
+    options: ApplyPatchOptions,
+) -> (Vec<Replacement>, Vec<HunkStatus>) {
+    let mut replacements: Vec<Replacement> = Vec::new();
+    let mut statuses: Vec<HunkStatus> = Vec::with_capacity(chunks.len());
     let mut line_index: usize = 0;
+    let hunk_label = |chunk_idx: usize| {
+        if chunks.len() > 1 {
+            format!(" (hunk {} of {})", chunk_idx + 1, chunks.len())
+        } else {
+            String::new()
+        }
+    };
+
+    for (chunk_idx, chunk) in chunks.iter().enumerate() {
+        let expected_index = line_index;
+        let mut context_missing = false;
 
-    for chunk in chunks {
         // If a chunk has a `change_context`, we use seek_sequence to find it, then
-        // adjust our `line_index` to continue from there.
+        // adjust our `line_index` to continue from there. Context that only occurs
+        // before the previous chunk is still accepted (the closest occurrence
+        // wins); the chunk is then reported as applied with an offset.
         if let Some(ctx_line) = &chunk.change_context {
-            if let Some(idx) = seek_sequence::seek_sequence(
-                original_lines,
-                std::slice::from_ref(ctx_line),
-                line_index,
-                false,
-            ) {
+            let ctx = std::slice::from_ref(ctx_line);
+            if let Some(idx) = seek_sequence::seek_sequence(original_lines, ctx, line_index, false)
+                .or_else(|| {
+                    seek_sequence::seek_sequence_before_where(
+                        original_lines,
+                        ctx,
+                        line_index,
+                        MatchLevel::Punctuation,
+                        |_| true,
+                    )
+                    .map(|(idx, _)| idx)
+                })
+            {
                 line_index = idx + 1;
+            } else if options.fuzz > 0 {
+                context_missing = true;
             } else {
-                return Err(ApplyPatchError::ComputeReplacements(format!(
-                    "Failed to find context '{}' in {}",
-                    ctx_line,
-                    path.display()
-                )));
+                statuses.push(HunkStatus::Rejected {
+                    reason: format!(
+                        "Failed to find context '{}' in {}{}",
+                        ctx_line,
+                        path.display(),
+                        hunk_label(chunk_idx)
+                    ),
+                    nearest: None,
+                });
+                continue;
             }
         }
 
@@ -420,57 +649,295 @@ fn compute_replacements(
                 original_lines.len()
             };
             replacements.push((insertion_idx, 0, chunk.new_lines.clone()));
+            statuses.push(if context_missing {
+                HunkStatus::AppliedFuzzy {
+                    line: insertion_idx + 1,
+                    fuzz: 0,
+                }
+            } else {
+                HunkStatus::Applied {
+                    line: insertion_idx + 1,
+                }
+            });
             continue;
         }
 
-        // Otherwise, try to match the existing lines in the file with the old lines
-        // from the chunk. If found, schedule that region for replacement.
-        // Attempt to locate the `old_lines` verbatim within the file.  In many
-        // real‑world diffs the last element of `old_lines` is an *empty* string
-        // representing the terminating newline of the region being replaced.
-        // This sentinel is not present in `original_lines` because we strip the
-        // trailing empty slice emitted by `split('\n')`.  If a direct search
-        // fails and the pattern ends with an empty string, retry without that
-        // final element so that modifications touching the end‑of‑file can be
-        // located reliably.
-
-        let mut pattern: &[String] = &chunk.old_lines;
-        let mut found =
-            seek_sequence::seek_sequence(original_lines, pattern, line_index, chunk.is_end_of_file);
-
-        let mut new_slice: &[String] = &chunk.new_lines;
-
-        if found.is_none() && pattern.last().is_some_and(String::is_empty) {
-            // Retry without the trailing empty line which represents the final
-            // newline in the file.
-            pattern = &pattern[..pattern.len() - 1];
-            if new_slice.last().is_some_and(String::is_empty) {
-                new_slice = &new_slice[..new_slice.len() - 1];
+        let Some(located) = locate_chunk(original_lines, chunk, line_index, &replacements, options)
+        else {
+            let reason = match (&chunk.change_context, context_missing) {
+                (Some(ctx_line), true) => format!(
+                    "Failed to find context '{}' in {}{}",
+                    ctx_line,
+                    path.display(),
+                    hunk_label(chunk_idx)
+                ),
+                _ => format!(
+                    "Failed to find expected lines in {}{}:\n{}",
+                    path.display(),
+                    hunk_label(chunk_idx),
+                    chunk.old_lines.join("\n"),
+                ),
+            };
+            let nearest =
+                seek_sequence::nearest_candidate(original_lines, &chunk.old_lines).map(|idx| {
+                    CandidateRegion {
+                        line: idx + 1,
+                        lines: original_lines
+                            [idx..(idx + chunk.old_lines.len()).min(original_lines.len())]
+                            .to_vec(),
+                    }
+                });
+            statuses.push(HunkStatus::Rejected { reason, nearest });
+            continue;
+        };
+
+        let LocatedChunk {
+            start,
+            old_len,
+            new_lines,
+            level,
+            dropped_context,
+        } = located;
+        let line = start + 1;
+        statuses.push(
+            if context_missing || dropped_context > 0 || level == MatchLevel::AllWhitespace {
+                HunkStatus::AppliedFuzzy {
+                    line,
+                    fuzz: dropped_context,
+                }
+            } else if start < expected_index {
+                HunkStatus::AppliedWithOffset {
+                    line,
+                    offset: start as isize - expected_index as isize,
+                }
+            } else {
+                HunkStatus::Applied { line }
+            },
+        );
+        replacements.push((start, old_len, new_lines));
+        line_index = start + old_len;
+    }
+
+    replacements.sort_by(|(lhs_idx, _, _), (rhs_idx, _, _)| lhs_idx.cmp(rhs_idx));
+
+    (replacements, statuses)
+}
+
+/// Where a chunk's `old_lines` (possibly minus some context) were found.
+struct LocatedChunk {
+    start: usize,
+    old_len: usize,
+    new_lines: Vec<String>,
+    level: MatchLevel,
+    dropped_context: usize,
+}
+
+/// Locate the region of `original_lines` that `chunk` replaces. Every match
+/// level and fuzz factor is tried at or after `line_index` first; only when the
+/// chunk cannot be found there is the part of the file above `line_index`
+/// searched, nearest occurrence first. Regions already claimed by earlier
+/// `replacements` are never matched again. When the chunk cannot be found
+/// as-is, up to `options.fuzz` unchanged context lines are dropped from each
+/// end of it.
+fn locate_chunk(
+    original_lines: &[String],
+    chunk: &UpdateFileChunk,
+    line_index: usize,
+    replacements: &[Replacement],
+    options: ApplyPatchOptions,
+) -> Option<LocatedChunk> {
+    locate_chunk_in(
+        original_lines,
+        chunk,
+        SearchRegion::From(line_index),
+        replacements,
+        options,
+    )
+    .or_else(|| {
+        locate_chunk_in(
+            original_lines,
+            chunk,
+            SearchRegion::Before(line_index),
+            replacements,
+            options,
+        )
+    })
+}
+
+/// Part of the file [`locate_chunk_in`] searches.
+#[derive(Clone, Copy)]
+enum SearchRegion {
+    /// At or after the given line, first match wins.
+    From(usize),
+    /// Before the given line, closest match wins.
+    Before(usize),
+}
+
+fn locate_chunk_in(
+    original_lines: &[String],
+    chunk: &UpdateFileChunk,
+    region: SearchRegion,
+    replacements: &[Replacement],
+    options: ApplyPatchOptions,
+) -> Option<LocatedChunk> {
+    let max_level = if options.fuzz > 0 {
+        MatchLevel::AllWhitespace
+    } else {
+        MatchLevel::Punctuation
+    };
+
+    // In many real‑world diffs the last element of `old_lines` is an *empty*
+    // string representing the terminating newline of the region being
+    // replaced. This sentinel is not present in `original_lines` because we
+    // strip the trailing empty slice emitted by `split('\n')`. If a direct
+    // search fails and the pattern ends with an empty string, retry without
+    // that final element so that modifications touching the end‑of‑file can be
+    // located reliably.
+    let mut variants: Vec<(&[String], &[String])> =
+        vec![(chunk.old_lines.as_slice(), chunk.new_lines.as_slice())];
+    if let Some((old_last, old_rest)) = chunk.old_lines.split_last()
+        && old_last.is_empty()
+    {
+        let new_rest = match chunk.new_lines.split_last() {
+            Some((new_last, new_rest)) if new_last.is_empty() => new_rest,
+            _ => chunk.new_lines.as_slice(),
+        };
+        variants.push((old_rest, new_rest));
+    }
+
+    let mut previous_trim: Vec<Option<(usize, usize)>> = vec![None; variants.len()];
+    for fuzz in 0..=options.fuzz {
+        for (variant_idx, (old, new)) in variants.iter().enumerate() {
+            let (leading, trailing) = common_context(old, new);
+            let trim = (leading.min(fuzz), trailing.min(fuzz));
+            let dropped_all = trim != (0, 0) && old.len() <= trim.0 + trim.1;
+            if previous_trim[variant_idx] == Some(trim) || dropped_all {
+                continue;
+            }
+            previous_trim[variant_idx] = Some(trim);
+
+            let (lead, trail) = trim;
+            let pattern = &old[lead..old.len() - trail];
+            let accept = |start: usize| {
+                let end = start + pattern.len();
+                !replacements
+                    .iter()
+                    .any(|(idx, len, _)| start < idx + len && *idx < end)
+            };
+            let found = match region {
+                SearchRegion::From(line_index) => seek_sequence::seek_sequence_where(
+                    original_lines,
+                    pattern,
+                    line_index,
+                    chunk.is_end_of_file && trail == 0,
+                    max_level,
+                    accept,
+                ),
+                SearchRegion::Before(line_index) => seek_sequence::seek_sequence_before_where(
+                    original_lines,
+                    pattern,
+                    line_index,
+                    max_level,
+                    accept,
+                ),
+            };
+            if let Some((start, level)) = found {
+                // Keep the file's own version of context lines so that a loose
+                // match does not rewrite their whitespace.
+                let mut new_lines = new[lead..new.len() - trail].to_vec();
+                let matched = &original_lines[start..start + pattern.len()];
+                let (keep_leading, keep_trailing) = common_context(pattern, &new_lines);
+                new_lines[..keep_leading].clone_from_slice(&matched[..keep_leading]);
+                let new_len = new_lines.len();
+                new_lines[new_len - keep_trailing..]
+                    .clone_from_slice(&matched[pattern.len() - keep_trailing..]);
+                if level >= MatchLevel::SurroundingWhitespace {
+                    reindent(
+                        &mut new_lines[keep_leading..new_len - keep_trailing],
+                        pattern,
+                        matched,
+                    );
+                }
+                return Some(LocatedChunk {
+                    start,
+                    old_len: pattern.len(),
+                    new_lines,
+                    level,
+                    dropped_context: lead.max(trail),
+                });
             }
+        }
+    }
+
+    None
+}
 
-            found = seek_sequence::seek_sequence(
-                original_lines,
-                pattern,
-                line_index,
-                chunk.is_end_of_file,
-            );
+/// Rewrite the indentation of the inserted `lines` in the style of the file
+/// when the hunk `pattern` was matched against `matched` despite differing
+/// indentation, so that a hunk written with spaces does not insert
+/// space-indented lines into a tab-indented file (or vice versa). A line keeps
+/// its indentation if some matched line already uses it; otherwise the
+/// indentation the hunk used for a pattern line is mapped to the one the file
+/// uses for that line.
+fn reindent(lines: &mut [String], pattern: &[String], matched: &[String]) {
+    let mut mapping: Vec<(&str, &str)> = Vec::new();
+    for (pat, line) in pattern.iter().zip(matched) {
+        if pat.trim().is_empty() || line.trim().is_empty() {
+            continue;
+        }
+        let (from, to) = (indentation(pat), indentation(line));
+        if !mapping.iter().any(|(existing, _)| *existing == from) {
+            mapping.push((from, to));
         }
+    }
 
-        if let Some(start_idx) = found {
-            replacements.push((start_idx, pattern.len(), new_slice.to_vec()));
-            line_index = start_idx + pattern.len();
-        } else {
-            return Err(ApplyPatchError::ComputeReplacements(format!(
-                "Failed to find expected lines in {}:\n{}",
-                path.display(),
-                chunk.old_lines.join("\n"),
-            )));
+    for line in lines.iter_mut() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let indent = indentation(line);
+        if matched
+            .iter()
+            .any(|file_line| indentation(file_line) == indent)
+        {
+            continue;
+        }
+        let replacement = mapping
+            .iter()
+            .find(|(from, _)| *from == indent)
+            .or_else(|| {
+                mapping
+                    .iter()
+                    .filter(|(from, _)| !from.is_empty() && indent.starts_with(from))
+                    .max_by_key(|(from, _)| from.len())
+            });
+        if let Some((from, to)) = replacement {
+            *line = format!("{to}{}", &line[from.len()..]);
         }
     }
+}
 
-    replacements.sort_by(|(lhs_idx, _, _), (rhs_idx, _, _)| lhs_idx.cmp(rhs_idx));
+fn indentation(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
 
-    Ok(replacements)
+/// Number of identical lines at the start and at the end of `old` and `new`,
+/// i.e. the context lines surrounding the change. The two never overlap.
+fn common_context(old: &[String], new: &[String]) -> (usize, usize) {
+    let leading = old
+        .iter()
+        .zip(new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let max_trailing = old.len().min(new.len()) - leading;
+    let trailing = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(max_trailing)
+        .take_while(|(old, new)| old == new)
+        .count();
+    (leading, trailing)
 }
 
 /// Apply the `(start_index, old_len, new_lines)` replacements to `original_lines`,
@@ -515,21 +982,45 @@ pub fn unified_diff_from_chunks(
     unified_diff_from_chunks_with_context(path, chunks, 1)
 }
 
+/// Fails with [`ApplyPatchError::HunksRejected`] if any chunk cannot be
+/// located.
 pub fn unified_diff_from_chunks_with_context(
     path: &Path,
     chunks: &[UpdateFileChunk],
     context: usize,
 ) -> std::result::Result<ApplyPatchFileUpdate, ApplyPatchError> {
+    let (update, hunks) =
+        partial_unified_diff_from_chunks(path, chunks, context, ApplyPatchOptions::default())?;
+    if hunks.iter().any(HunkStatus::is_rejected) {
+        let mut report = HunkReport::default();
+        report.extend(path, hunks);
+        return Err(ApplyPatchError::HunksRejected(report));
+    }
+    Ok(update)
+}
+
+/// Like [`unified_diff_from_chunks_with_context`], but leaves out rejected
+/// chunks instead of failing and returns the status of every chunk.
+pub(crate) fn partial_unified_diff_from_chunks(
+    path: &Path,
+    chunks: &[UpdateFileChunk],
+    context: usize,
+    options: ApplyPatchOptions,
+) -> std::result::Result<(ApplyPatchFileUpdate, Vec<HunkStatus>), ApplyPatchError> {
     let AppliedPatch {
         original_contents,
         new_contents,
-    } = derive_new_contents_from_chunks(path, chunks)?;
+        hunks,
+    } = derive_new_contents_from_chunks(path, chunks, options)?;
     let text_diff = TextDiff::from_lines(&original_contents, &new_contents);
     let unified_diff = text_diff.unified_diff().context_radius(context).to_string();
-    Ok(ApplyPatchFileUpdate {
-        unified_diff,
-        content: new_contents,
-    })
+    Ok((
+        ApplyPatchFileUpdate {
+            unified_diff,
+            content: new_contents,
+        },
+        hunks,
+    ))
 }
 
 /// Print the summary of changes in git-style format.
@@ -539,6 +1030,13 @@ pub fn print_summary(
     out: &mut impl std::io::Write,
 ) -> std::io::Result<()> {
     writeln!(out, "Success. Updated the following files:")?;
+    print_affected_paths(affected, out)
+}
+
+fn print_affected_paths(
+    affected: &AffectedPaths,
+    out: &mut impl std::io::Write,
+) -> std::io::Result<()> {
     for path in &affected.added {
         writeln!(out, "A {}", path.display())?;
     }
@@ -551,6 +1049,31 @@ pub fn print_summary(
     Ok(())
 }
 
+/// Note hunks that did not apply exactly where expected so that the model can
+/// double-check them.
+fn print_hunk_notes(report: &HunkReport, out: &mut impl std::io::Write) -> std::io::Result<()> {
+    for result in &report.hunks {
+        let path = result.path.display();
+        let hunk = result.hunk;
+        match result.status {
+            HunkStatus::AppliedWithOffset { line, offset } => writeln!(
+                out,
+                "Note: hunk {hunk} of {path} applied at line {line} (offset {offset} lines)."
+            )?,
+            HunkStatus::AppliedFuzzy { line, fuzz: 0 } => writeln!(
+                out,
+                "Note: hunk {hunk} of {path} applied at line {line} ignoring whitespace."
+            )?,
+            HunkStatus::AppliedFuzzy { line, fuzz } => writeln!(
+                out,
+                "Note: hunk {hunk} of {path} applied at line {line} with fuzz {fuzz}."
+            )?,
+            HunkStatus::Applied { .. } | HunkStatus::Rejected { .. } => {}
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1071,4 +1594,182 @@ g
         let result = apply_patch(&patch, &mut stdout, &mut stderr);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_rejected_hunk_is_reported_while_others_apply() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("partial.txt");
+        fs::write(&path, "alpha\nbeta\ngamma\ndelta\n").unwrap();
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+-beta
++BETA
+@@
+-gammma
+-delta
++GAMMA
++DELTA"#,
+            path.display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let result = apply_patch(&patch, &mut stdout, &mut stderr);
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "alpha\nBETA\ngamma\ndelta\n"
+        );
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            format!(
+                "Applied patch with rejected hunks. Updated the following files:\nM {}\n",
+                path.display()
+            )
+        );
+        assert_eq!(
+            String::from_utf8(stderr).unwrap(),
+            format!(
+                "Failed to find expected lines in {} (hunk 2 of 2):\ngammma\ndelta\nNearest match at line 3:\ngamma\ndelta\n",
+                path.display()
+            )
+        );
+        let Err(ApplyPatchError::HunksRejected(report)) = result else {
+            panic!("expected rejected hunks, got {result:?}");
+        };
+        let statuses: Vec<HunkStatus> = report.hunks.into_iter().map(|r| r.status).collect();
+        assert_eq!(statuses[0], HunkStatus::Applied { line: 2 });
+        assert!(statuses[1].is_rejected());
+    }
+
+    #[test]
+    fn test_out_of_order_hunk_applies_with_offset() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("offset.txt");
+        fs::write(&path, "a\nb\nc\nd\n").unwrap();
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+-d
++D
+@@
+-a
++A"#,
+            path.display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "A\nb\nc\nD\n");
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            format!(
+                "Success. Updated the following files:\nM {0}\nNote: hunk 2 of {0} applied at line 1 (offset -4 lines).\n",
+                path.display()
+            )
+        );
+        assert_eq!(String::from_utf8(stderr).unwrap(), "");
+    }
+
+    #[test]
+    fn test_fuzzy_match_after_previous_hunk_beats_earlier_duplicate() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("dup.txt");
+        fs::write(&path, "a\nx\nstale\nmid\na\nx\nfresh\n").unwrap();
+        // The second hunk matches the first block verbatim, but only the block
+        // after the first hunk once its stale context is dropped.
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+-mid
++MID
+@@
+ a
+-x
++y
+ stale"#,
+            path.display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch_with_options(
+            &patch,
+            &mut stdout,
+            &mut stderr,
+            ApplyPatchOptions { fuzz: 1 },
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "a\nx\nstale\nMID\na\ny\nfresh\n"
+        );
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            format!(
+                "Success. Updated the following files:\nM {0}\nNote: hunk 2 of {0} applied at line 6 with fuzz 1.\n",
+                path.display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_fuzz_ignores_indentation_and_stale_context() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("fuzzy.py");
+        fs::write(
+            &path,
+            "def f():\n    if x:\n        return 1\n    return 2\n",
+        )
+        .unwrap();
+        // Indentation differs from the file and the trailing context line is stale.
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+ if x:
+-  return 1
++        return 3
+ return 0"#,
+            path.display()
+        ));
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        let strict = apply_patch_with_options(
+            &patch,
+            &mut stdout,
+            &mut stderr,
+            ApplyPatchOptions { fuzz: 0 },
+        );
+        assert!(matches!(strict, Err(ApplyPatchError::HunksRejected(_))));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "def f():\n    if x:\n        return 1\n    return 2\n"
+        );
+
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch_with_options(
+            &patch,
+            &mut stdout,
+            &mut stderr,
+            ApplyPatchOptions { fuzz: 2 },
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "def f():\n    if x:\n        return 3\n    return 2\n"
+        );
+        assert_eq!(
+            String::from_utf8(stdout).unwrap(),
+            format!(
+                "Success. Updated the following files:\nM {0}\nNote: hunk 1 of {0} applied at line 3 with fuzz 1.\n",
+                path.display()
+            )
+        );
+    }
 }
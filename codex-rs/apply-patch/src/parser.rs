@@ -315,7 +315,8 @@ fn parse_one_hunk(lines: &[&str], line_number: usize) -> Result<(Hunk, usize), P
             remaining_lines = &remaining_lines[chunk_lines..]
         }
 
-        if chunks.is_empty() {
+        // A pure rename needs no chunks.
+        if chunks.is_empty() && move_path.is_none() {
             return Err(InvalidHunkError {
                 message: format!("Update file hunk for path '{path}' is empty"),
                 line_number,
@@ -478,6 +479,22 @@ fn test_parse_patch() {
             line_number: 2,
         })
     );
+    assert_eq!(
+        parse_patch_text(
+            "*** Begin Patch\n\
+             *** Update File: test.py\n\
+             *** Move to: renamed.py\n\
+             *** End Patch",
+            ParseMode::Strict
+        )
+        .unwrap()
+        .hunks,
+        vec![UpdateFile {
+            path: PathBuf::from("test.py"),
+            move_path: Some(PathBuf::from("renamed.py")),
+            chunks: Vec::new(),
+        }]
+    );
     assert_eq!(
         parse_patch_text(
             "*** Begin Patch\n\
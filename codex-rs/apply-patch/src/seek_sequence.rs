@@ -15,8 +15,66 @@ pub(crate) fn seek_sequence(
     start: usize,
     eof: bool,
 ) -> Option<usize> {
+    seek_sequence_where(lines, pattern, start, eof, MatchLevel::Punctuation, |_| {
+        true
+    })
+    .map(|(idx, _)| idx)
+}
+
+/// How leniently a line of the patch was compared with a line of the file, from strictest to
+/// most permissive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum MatchLevel {
+    Exact,
+    /// Ignore trailing whitespace.
+    TrailingWhitespace,
+    /// Ignore leading and trailing whitespace.
+    SurroundingWhitespace,
+    /// Additionally map typographic punctuation to its ASCII equivalent.
+    Punctuation,
+    /// Ignore all whitespace, including indentation and spacing inside the line.
+    AllWhitespace,
+}
+
+impl MatchLevel {
+    const ALL: [MatchLevel; 5] = [
+        MatchLevel::Exact,
+        MatchLevel::TrailingWhitespace,
+        MatchLevel::SurroundingWhitespace,
+        MatchLevel::Punctuation,
+        MatchLevel::AllWhitespace,
+    ];
+
+    fn lines_match(self, line: &str, pattern: &str) -> bool {
+        match self {
+            MatchLevel::Exact => line == pattern,
+            MatchLevel::TrailingWhitespace => line.trim_end() == pattern.trim_end(),
+            MatchLevel::SurroundingWhitespace => line.trim() == pattern.trim(),
+            MatchLevel::Punctuation => normalise(line) == normalise(pattern),
+            MatchLevel::AllWhitespace => {
+                let line = normalise(line);
+                let pattern = normalise(pattern);
+                line.chars()
+                    .filter(|c| !c.is_whitespace())
+                    .eq(pattern.chars().filter(|c| !c.is_whitespace()))
+            }
+        }
+    }
+}
+
+/// Like [`seek_sequence`], but tries every [`MatchLevel`] up to and including `max_level` and
+/// only returns start indices for which `accept` returns true. Returns the index together with
+/// the level that produced the match.
+pub(crate) fn seek_sequence_where(
+    lines: &[String],
+    pattern: &[String],
+    start: usize,
+    eof: bool,
+    max_level: MatchLevel,
+    accept: impl Fn(usize) -> bool,
+) -> Option<(usize, MatchLevel)> {
     if pattern.is_empty() {
-        return Some(start);
+        return Some((start, MatchLevel::Exact));
     }
 
     // When the pattern is longer than the available input there is no possible
@@ -31,87 +89,115 @@ pub(crate) fn seek_sequence(
     } else {
         start
     };
-    // Exact match first.
-    for i in search_start..=lines.len().saturating_sub(pattern.len()) {
-        if lines[i..i + pattern.len()] == *pattern {
-            return Some(i);
-        }
-    }
-    // Then rstrip match.
-    for i in search_start..=lines.len().saturating_sub(pattern.len()) {
-        let mut ok = true;
-        for (p_idx, pat) in pattern.iter().enumerate() {
-            if lines[i + p_idx].trim_end() != pat.trim_end() {
-                ok = false;
-                break;
+    for level in MatchLevel::ALL
+        .into_iter()
+        .filter(|level| *level <= max_level)
+    {
+        for i in search_start..=lines.len().saturating_sub(pattern.len()) {
+            let matches = lines[i..i + pattern.len()]
+                .iter()
+                .zip(pattern)
+                .all(|(line, pat)| level.lines_match(line, pat));
+            if matches && accept(i) {
+                return Some((i, level));
             }
         }
-        if ok {
-            return Some(i);
-        }
     }
-    // Finally, trim both sides to allow more lenience.
-    for i in search_start..=lines.len().saturating_sub(pattern.len()) {
-        let mut ok = true;
-        for (p_idx, pat) in pattern.iter().enumerate() {
-            if lines[i + p_idx].trim() != pat.trim() {
-                ok = false;
-                break;
+
+    None
+}
+
+/// Like [`seek_sequence_where`], but only considers matches that start before `end` and prefers
+/// the one closest to it, so a hunk that moved up the file lands on the nearest candidate rather
+/// than on the first one from the top.
+pub(crate) fn seek_sequence_before_where(
+    lines: &[String],
+    pattern: &[String],
+    end: usize,
+    max_level: MatchLevel,
+    accept: impl Fn(usize) -> bool,
+) -> Option<(usize, MatchLevel)> {
+    if pattern.is_empty() || pattern.len() > lines.len() {
+        return None;
+    }
+    let last = (lines.len() - pattern.len()).min(end.checked_sub(1)?);
+    for level in MatchLevel::ALL
+        .into_iter()
+        .filter(|level| *level <= max_level)
+    {
+        for i in (0..=last).rev() {
+            let matches = lines[i..i + pattern.len()]
+                .iter()
+                .zip(pattern)
+                .all(|(line, pat)| level.lines_match(line, pat));
+            if matches && accept(i) {
+                return Some((i, level));
             }
         }
-        if ok {
-            return Some(i);
-        }
     }
 
-    // ------------------------------------------------------------------
-    // Final, most permissive pass – attempt to match after *normalising*
-    // common Unicode punctuation to their ASCII equivalents so that diffs
-    // authored with plain ASCII characters can still be applied to source
-    // files that contain typographic dashes / quotes, etc.  This mirrors the
-    // fuzzy behaviour of `git apply` which ignores minor byte-level
-    // differences when locating context lines.
-    // ------------------------------------------------------------------
-
-    fn normalise(s: &str) -> String {
-        s.trim()
-            .chars()
-            .map(|c| match c {
-                // Various dash / hyphen code-points → ASCII '-'
-                '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}'
-                | '\u{2212}' => '-',
-                // Fancy single quotes → '\''
-                '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => '\'',
-                // Fancy double quotes → '"'
-                '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => '"',
-                // Non-breaking space and other odd spaces → normal space
-                '\u{00A0}' | '\u{2002}' | '\u{2003}' | '\u{2004}' | '\u{2005}' | '\u{2006}'
-                | '\u{2007}' | '\u{2008}' | '\u{2009}' | '\u{200A}' | '\u{202F}' | '\u{205F}'
-                | '\u{3000}' => ' ',
-                other => other,
-            })
-            .collect::<String>()
-    }
+    None
+}
 
-    for i in search_start..=lines.len().saturating_sub(pattern.len()) {
-        let mut ok = true;
-        for (p_idx, pat) in pattern.iter().enumerate() {
-            if normalise(&lines[i + p_idx]) != normalise(pat) {
-                ok = false;
-                break;
-            }
-        }
-        if ok {
-            return Some(i);
+/// Find the window of `lines` that shares the most (non-blank) lines with `pattern`, compared
+/// with [`MatchLevel::AllWhitespace`]. Used to point the model at the region it most likely
+/// meant when a hunk cannot be located. Returns `None` when no line matches at all.
+pub(crate) fn nearest_candidate(lines: &[String], pattern: &[String]) -> Option<usize> {
+    if pattern.is_empty() || lines.is_empty() {
+        return None;
+    }
+    let window = pattern.len().min(lines.len());
+    let mut best: Option<(usize, usize)> = None;
+    for i in 0..=lines.len() - window {
+        let score = lines[i..i + window]
+            .iter()
+            .zip(pattern)
+            .filter(|(line, pat)| {
+                !pat.trim().is_empty() && MatchLevel::AllWhitespace.lines_match(line, pat)
+            })
+            .count();
+        if score > 0 && best.is_none_or(|(_, best_score)| score > best_score) {
+            best = Some((i, score));
         }
     }
+    best.map(|(idx, _)| idx)
+}
 
-    None
+// ------------------------------------------------------------------
+// The permissive passes match after *normalising* common Unicode
+// punctuation to their ASCII equivalents so that diffs authored with plain
+// ASCII characters can still be applied to source files that contain
+// typographic dashes / quotes, etc.  This mirrors the fuzzy behaviour of
+// `git apply` which ignores minor byte-level differences when locating
+// context lines.
+// ------------------------------------------------------------------
+fn normalise(s: &str) -> String {
+    s.trim()
+        .chars()
+        .map(|c| match c {
+            // Various dash / hyphen code-points → ASCII '-'
+            '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}'
+            | '\u{2212}' => '-',
+            // Fancy single quotes → '\''
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => '\'',
+            // Fancy double quotes → '"'
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => '"',
+            // Non-breaking space and other odd spaces → normal space
+            '\u{00A0}' | '\u{2002}' | '\u{2003}' | '\u{2004}' | '\u{2005}' | '\u{2006}'
+            | '\u{2007}' | '\u{2008}' | '\u{2009}' | '\u{200A}' | '\u{202F}' | '\u{205F}'
+            | '\u{3000}' => ' ',
+            other => other,
+        })
+        .collect::<String>()
 }
 
 #[cfg(test)]
 mod tests {
+    use super::MatchLevel;
+    use super::nearest_candidate;
     use super::seek_sequence;
+    use super::seek_sequence_before_where;
+    use super::seek_sequence_where;
     use std::string::ToString;
 
     fn to_vec(strings: &[&str]) -> Vec<String> {
@@ -148,4 +234,55 @@ mod tests {
         // Should not panic – must return None when pattern cannot possibly fit.
         assert_eq!(seek_sequence(&lines, &pattern, 0, false), None);
     }
+
+    #[test]
+    fn test_all_whitespace_match_ignores_indentation_and_spacing() {
+        let lines = to_vec(&["fn main() {", "\t\tlet x = 1 ;", "}"]);
+        let pattern = to_vec(&["    let x=1;", "}"]);
+        assert_eq!(seek_sequence(&lines, &pattern, 0, false), None);
+        assert_eq!(
+            seek_sequence_where(
+                &lines,
+                &pattern,
+                0,
+                false,
+                MatchLevel::AllWhitespace,
+                |_| true
+            ),
+            Some((1, MatchLevel::AllWhitespace))
+        );
+    }
+
+    #[test]
+    fn test_seek_sequence_where_skips_rejected_positions() {
+        let lines = to_vec(&["a", "b", "a", "b"]);
+        let pattern = to_vec(&["a", "b"]);
+        assert_eq!(
+            seek_sequence_where(&lines, &pattern, 0, false, MatchLevel::Exact, |idx| idx
+                != 0),
+            Some((2, MatchLevel::Exact))
+        );
+    }
+
+    #[test]
+    fn test_seek_sequence_before_where_prefers_closest_earlier_match() {
+        let lines = to_vec(&["a", "b", "a", "b", "a", "b"]);
+        let pattern = to_vec(&["a", "b"]);
+        assert_eq!(
+            seek_sequence_before_where(&lines, &pattern, 4, MatchLevel::Exact, |_| true),
+            Some((2, MatchLevel::Exact))
+        );
+        assert_eq!(
+            seek_sequence_before_where(&lines, &pattern, 0, MatchLevel::Exact, |_| true),
+            None
+        );
+    }
+
+    #[test]
+    fn test_nearest_candidate_picks_window_with_most_shared_lines() {
+        let lines = to_vec(&["a", "b", "x", "c", "d"]);
+        let pattern = to_vec(&["b", "y", "c"]);
+        assert_eq!(nearest_candidate(&lines, &pattern), Some(1));
+        assert_eq!(nearest_candidate(&lines, &to_vec(&["zzz"])), None);
+    }
 }
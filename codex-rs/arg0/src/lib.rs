@@ -93,7 +93,12 @@ pub fn arg0_dispatch() -> Option<Arg0PathEntryGuard> {
             Some(patch_arg) => {
                 let mut stdout = std::io::stdout();
                 let mut stderr = std::io::stderr();
-                match codex_apply_patch::apply_patch(&patch_arg, &mut stdout, &mut stderr) {
+                match codex_apply_patch::apply_patch_with_options(
+                    &patch_arg,
+                    &mut stdout,
+                    &mut stderr,
+                    codex_apply_patch::ApplyPatchOptions::from_env(),
+                ) {
                     Ok(()) => 0,
                     Err(_) => 1,
                 }
@@ -3,6 +3,7 @@ mod pid_tracker;
 #[cfg(target_os = "macos")]
 mod seatbelt;
 
+use std::path::Path;
 use std::path::PathBuf;
 
 use codex_core::config::Config;
@@ -14,6 +15,7 @@ use codex_core::landlock::spawn_command_under_linux_sandbox;
 use codex_core::seatbelt::spawn_command_under_seatbelt;
 use codex_core::spawn::StdioPolicy;
 use codex_protocol::config_types::SandboxMode;
+use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_utils_cli::CliConfigOverrides;
 
 use crate::LandlockCommand;
@@ -256,12 +258,17 @@ async fn run_command_under_sandbox(
                 .codex_linux_sandbox_exe
                 .expect("codex-linux-sandbox executable not found");
             let use_bwrap_sandbox = config.features.enabled(Feature::UseLinuxSandboxBwrap);
+            let deny_read_paths = expand_deny_read(
+                &config.permissions.file_system_sandbox_policy,
+                sandbox_policy_cwd.as_path(),
+            )?;
             spawn_command_under_linux_sandbox(
                 codex_linux_sandbox_exe,
                 command,
                 cwd,
                 config.permissions.sandbox_policy.get(),
                 sandbox_policy_cwd.as_path(),
+                &deny_read_paths,
                 use_bwrap_sandbox,
                 stdio_policy,
                 network.as_ref(),
@@ -297,6 +304,33 @@ async fn run_command_under_sandbox(
     handle_exit_status(status);
 }
 
+/// Expand the `deny_read` patterns into the existing paths the Linux sandbox
+/// will mask, printing both so users can check them before the command runs.
+fn expand_deny_read(
+    file_system_policy: &FileSystemSandboxPolicy,
+    sandbox_policy_cwd: &Path,
+) -> anyhow::Result<Vec<PathBuf>> {
+    if file_system_policy.deny_read.is_empty() {
+        return Ok(Vec::new());
+    }
+    let deny_list = file_system_policy.read_deny_list(sandbox_policy_cwd)?;
+    eprintln!("=== deny_read ===");
+    for pattern in deny_list.patterns() {
+        eprintln!("pattern: {}", pattern.display());
+    }
+    let denied_paths =
+        deny_list.find_denied_paths(file_system_policy.effective_deny_read_scan_limit())?;
+    if denied_paths.is_empty() {
+        eprintln!("No existing paths match.");
+    } else {
+        for path in &denied_paths {
+            eprintln!("masked: {}", path.display());
+        }
+    }
+    eprintln!();
+    Ok(denied_paths)
+}
+
 pub fn create_sandbox_mode(full_auto: bool) -> SandboxMode {
     if full_auto {
         SandboxMode::WorkspaceWrite
@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_core::ThreadExport;
+use codex_core::ThreadExportFormat;
+use codex_core::config::Config;
+use codex_core::find_archived_thread_path_by_id_str;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::find_thread_path_by_name_str;
+use codex_utils_cli::CliConfigOverrides;
+use uuid::Uuid;
+
+/// Render a recorded session as a self-contained HTML or Markdown transcript.
+#[derive(Debug, clap::Parser)]
+pub struct ExportCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Thread id, thread name or path to a rollout file.
+    #[arg(value_name = "THREAD")]
+    pub thread: String,
+
+    /// Transcript format.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Html)]
+    pub format: ExportFormat,
+
+    /// Write the transcript to this file instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Html,
+    Markdown,
+}
+
+impl ExportCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let rollout_path = resolve_rollout_path(&config, &self.thread).await?;
+        let thread = ThreadExport::load(&config.codex_home, &rollout_path)
+            .await
+            .with_context(|| format!("failed to read rollout `{}`", rollout_path.display()))?;
+        let format = match self.format {
+            ExportFormat::Html => ThreadExportFormat::Html,
+            ExportFormat::Markdown => ThreadExportFormat::Markdown,
+        };
+        let transcript = thread.render(format);
+
+        match self.output {
+            Some(output) => {
+                tokio::fs::write(&output, transcript)
+                    .await
+                    .with_context(|| format!("failed to write `{}`", output.display()))?;
+                eprintln!("Wrote {}", output.display());
+            }
+            None => print!("{transcript}"),
+        }
+        Ok(())
+    }
+}
+
+pub(crate) async fn resolve_rollout_path(config: &Config, thread: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(thread);
+    if tokio::fs::metadata(&path)
+        .await
+        .is_ok_and(|metadata| metadata.is_file())
+    {
+        return Ok(path);
+    }
+
+    let found = if Uuid::parse_str(thread).is_ok() {
+        match find_thread_path_by_id_str(&config.codex_home, thread).await? {
+            Some(path) => Some(path),
+            None => find_archived_thread_path_by_id_str(&config.codex_home, thread).await?,
+        }
+    } else {
+        find_thread_path_by_name_str(&config.codex_home, thread).await?
+    };
+    found.with_context(|| format!("no recorded session found for `{thread}`"))
+}
@@ -0,0 +1,100 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_core::config::Config;
+use codex_core::external_agent_sessions::ExternalTranscriptFormat;
+use codex_core::external_agent_sessions::claude_code_transcripts;
+use codex_core::external_agent_sessions::import_external_transcript;
+use codex_utils_cli::CliConfigOverrides;
+
+/// Import transcripts recorded by other coding agents as Codex threads.
+///
+/// Without paths, imports the Claude Code sessions recorded for the current
+/// working directory.
+#[derive(Debug, clap::Parser)]
+pub struct ImportCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Transcript files to import.
+    #[arg(value_name = "FILE")]
+    pub paths: Vec<PathBuf>,
+
+    /// Transcript format. Detected from the file contents by default.
+    #[arg(long, value_enum)]
+    pub format: Option<ImportFormat>,
+
+    /// Import the Claude Code sessions of every project instead of only the
+    /// current working directory. Ignored when paths are given.
+    #[arg(long, default_value_t = false)]
+    pub all: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportFormat {
+    ClaudeCode,
+    ChatCompletions,
+}
+
+impl ImportCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let (paths, format) = if self.paths.is_empty() {
+            let cwd = (!self.all).then_some(config.cwd.as_path());
+            let paths =
+                claude_code_transcripts(cwd).context("failed to list Claude Code sessions")?;
+            if paths.is_empty() {
+                anyhow::bail!(
+                    "no Claude Code sessions found for `{}`; pass transcript paths or --all",
+                    config.cwd.display()
+                );
+            }
+            (paths, Some(ExternalTranscriptFormat::ClaudeCode))
+        } else {
+            let format = self.format.map(|format| match format {
+                ImportFormat::ClaudeCode => ExternalTranscriptFormat::ClaudeCode,
+                ImportFormat::ChatCompletions => ExternalTranscriptFormat::ChatCompletions,
+            });
+            (self.paths, format)
+        };
+
+        let mut failures = 0usize;
+        for path in paths {
+            match import_external_transcript(&config, &path, format).await {
+                Ok(imported) => {
+                    let status = if imported.already_imported {
+                        "already imported"
+                    } else {
+                        "imported"
+                    };
+                    let name = imported
+                        .name
+                        .map(|name| format!(" ({name})"))
+                        .unwrap_or_default();
+                    println!(
+                        "{status} {} -> {}{name}",
+                        path.display(),
+                        imported.thread_id
+                    );
+                }
+                Err(err) => {
+                    failures += 1;
+                    eprintln!("failed to import {}: {err}", path.display());
+                }
+            }
+        }
+        if failures > 0 {
+            anyhow::bail!("{failures} transcript(s) could not be imported");
+        }
+        eprintln!("Resume an imported thread with `codex resume <thread id>`.");
+        Ok(())
+    }
+}
@@ -38,11 +38,23 @@ use supports_color::Stream;
 mod app_cmd;
 #[cfg(target_os = "macos")]
 mod desktop_app;
+mod export_cmd;
+mod import_cmd;
 mod mcp_cmd;
+mod replay_cmd;
+mod schedule_cmd;
+mod secrets_cmd;
+mod usage_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::export_cmd::ExportCli;
+use crate::import_cmd::ImportCli;
 use crate::mcp_cmd::McpCli;
+use crate::replay_cmd::DebugReplayCommand;
+use crate::schedule_cmd::ScheduleCli;
+use crate::secrets_cmd::SecretsCli;
+use crate::usage_cmd::UsageCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -146,6 +158,21 @@ enum Subcommand {
 
     /// Inspect feature flags.
     Features(FeaturesCli),
+
+    /// Summarize recorded token usage and estimated cost by day, repository or model.
+    Usage(UsageCli),
+
+    /// Manage scheduled agent runs executed by the app-server.
+    Schedule(ScheduleCli),
+
+    /// Manage secrets passed to commands through `shell_environment_policy`.
+    Secrets(SecretsCli),
+
+    /// Export a recorded session as a self-contained HTML or Markdown transcript.
+    Export(ExportCli),
+
+    /// Import sessions recorded by other coding agents so they can be resumed.
+    Import(ImportCli),
 }
 
 #[derive(Debug, Parser)]
@@ -166,6 +193,10 @@ enum DebugSubcommand {
     /// Tooling: helps debug the app server.
     AppServer(DebugAppServerCommand),
 
+    /// Replay a recorded session offline and report divergences in tool
+    /// calls, approvals and file changes.
+    Replay(DebugReplayCommand),
+
     /// Internal: reset local memory state for a fresh start.
     #[clap(hide = true)]
     ClearMemories,
@@ -314,7 +345,7 @@ struct AppServerCommand {
     subcommand: Option<AppServerSubcommand>,
 
     /// Transport endpoint URL. Supported values: `stdio://` (default),
-    /// `ws://IP:PORT`.
+    /// `ws://IP:PORT`, `http://LOOPBACK_IP:PORT`, `unix://PATH`.
     #[arg(
         long = "listen",
         value_name = "URL",
@@ -602,6 +633,37 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
             prepend_config_flags(&mut mcp_cli.config_overrides, root_config_overrides.clone());
             mcp_cli.run().await?;
         }
+        Some(Subcommand::Usage(mut usage_cli)) => {
+            prepend_config_flags(
+                &mut usage_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            usage_cli.run().await?;
+        }
+        Some(Subcommand::Schedule(mut schedule_cli)) => {
+            prepend_config_flags(
+                &mut schedule_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            schedule_cli.run().await?;
+        }
+        Some(Subcommand::Secrets(secrets_cli)) => {
+            secrets_cli.run()?;
+        }
+        Some(Subcommand::Export(mut export_cli)) => {
+            prepend_config_flags(
+                &mut export_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            export_cli.run().await?;
+        }
+        Some(Subcommand::Import(mut import_cli)) => {
+            prepend_config_flags(
+                &mut import_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            import_cli.run().await?;
+        }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
                 let transport = app_server_cli.listen;
@@ -758,6 +820,10 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
             DebugSubcommand::AppServer(cmd) => {
                 run_debug_app_server_command(cmd).await?;
             }
+            DebugSubcommand::Replay(mut cmd) => {
+                prepend_config_flags(&mut cmd.config_overrides, root_config_overrides.clone());
+                cmd.run().await?;
+            }
             DebugSubcommand::ClearMemories => {
                 run_debug_clear_memories_command(&root_config_overrides, &interactive).await?;
             }
@@ -254,6 +254,7 @@ async fn run_add(config_overrides: &CliConfigOverrides, add_args: AddArgs) -> Re
         disabled_tools: None,
         scopes: None,
         oauth_resource: None,
+        sampling_token_budget: None,
     };
 
     servers.insert(name.clone(), new_entry);
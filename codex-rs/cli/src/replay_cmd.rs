@@ -0,0 +1,222 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_core::config::Config;
+use codex_core::replay::ApprovalDecision;
+use codex_core::replay::RecordedCommandOutcome;
+use codex_core::replay::ReplayDivergence;
+use codex_core::replay::ReplayOptions;
+use codex_core::replay::ReplayReport;
+use codex_core::replay::ReplayToolMode;
+use codex_core::replay::replay_rollout;
+use codex_utils_cli::CliConfigOverrides;
+
+use crate::export_cmd::resolve_rollout_path;
+
+/// Lines of recorded and replayed tool output shown per divergence.
+const OUTPUT_PREVIEW_LINES: usize = 6;
+
+/// Replay a recorded session offline with the current configuration and
+/// report where it diverges from the recording.
+#[derive(Debug, clap::Parser)]
+pub struct DebugReplayCommand {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Thread id, thread name or path to a rollout file.
+    #[arg(value_name = "ROLLOUT")]
+    pub rollout: String,
+
+    /// Answer tool calls with their recorded outputs (`stub`) or run them
+    /// again, approving only what ran in the recording (`execute`).
+    #[arg(long, value_enum, default_value_t = ReplayTools::Stub)]
+    pub tools: ReplayTools,
+
+    /// Model to replay with. Defaults to the recorded model.
+    #[arg(long, short = 'm')]
+    pub model: Option<String>,
+
+    /// Directory to replay in. Defaults to the recorded working directory.
+    #[arg(long = "cd", short = 'C', value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
+    /// Give up on a turn after this many seconds.
+    #[arg(long, value_name = "SECONDS", default_value_t = 600)]
+    pub turn_timeout: u64,
+
+    /// Print the report as JSON.
+    #[arg(long, default_value_t = false)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReplayTools {
+    Stub,
+    Execute,
+}
+
+impl DebugReplayCommand {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let rollout_path = resolve_rollout_path(&config, &self.rollout).await?;
+        let options = ReplayOptions {
+            tools: match self.tools {
+                ReplayTools::Stub => ReplayToolMode::Stub,
+                ReplayTools::Execute => ReplayToolMode::Execute,
+            },
+            model: self.model,
+            cwd: self.cwd,
+            turn_timeout: Duration::from_secs(self.turn_timeout),
+        };
+        let report = replay_rollout(config, &rollout_path, options)
+            .await
+            .with_context(|| format!("failed to replay `{}`", rollout_path.display()))?;
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            print_report(&report);
+        }
+        if report.has_divergences() {
+            anyhow::bail!(
+                "replay diverged from the recording in {} place(s)",
+                report.divergences.len()
+            );
+        }
+        Ok(())
+    }
+}
+
+fn print_report(report: &ReplayReport) {
+    let tools = match report.tools {
+        ReplayToolMode::Stub => "stubbed",
+        ReplayToolMode::Execute => "executed",
+    };
+    println!(
+        "Replayed {} turn(s) and {} tool call(s) ({tools}) from {}",
+        report.turns,
+        report.tool_calls,
+        report.rollout_path.display()
+    );
+
+    if !report.approvals.is_empty() {
+        println!("\nCommands exec policy would not run without asking:");
+        for approval in &report.approvals {
+            let decision = match approval.decision {
+                ApprovalDecision::Allow => "allow",
+                ApprovalDecision::Prompt => "prompt",
+                ApprovalDecision::Forbidden => "forbidden",
+            };
+            print!(
+                "  turn {}: {decision} `{}`",
+                approval.turn,
+                display_command(&approval.command)
+            );
+            match &approval.reason {
+                Some(reason) => println!(" ({reason})"),
+                None => println!(),
+            }
+        }
+    }
+
+    if report.divergences.is_empty() {
+        println!("\nNo divergences.");
+        return;
+    }
+    println!("\nDivergences:");
+    for divergence in &report.divergences {
+        let turn = divergence.turn();
+        match divergence {
+            ReplayDivergence::ToolOutput {
+                call_id,
+                tool,
+                recorded,
+                replayed,
+                ..
+            } => {
+                println!("  turn {turn}: output of {tool} ({call_id}) differs");
+                print_preview("recorded", recorded);
+                print_preview("replayed", replayed);
+            }
+            ReplayDivergence::MissingToolOutput { call_id, tool, .. } => {
+                println!("  turn {turn}: {tool} ({call_id}) returned no output");
+            }
+            ReplayDivergence::Approval {
+                command,
+                recorded,
+                replayed,
+                ..
+            } => {
+                let recorded = match recorded {
+                    RecordedCommandOutcome::Ran => "ran",
+                    RecordedCommandOutcome::Rejected => "was rejected",
+                };
+                let replayed = match replayed {
+                    ApprovalDecision::Allow => "is now allowed",
+                    ApprovalDecision::Prompt => "now needs approval",
+                    ApprovalDecision::Forbidden => "is now forbidden",
+                };
+                println!(
+                    "  turn {turn}: `{}` {recorded} but {replayed}",
+                    display_command(command)
+                );
+            }
+            ReplayDivergence::ModelRequests {
+                recorded, replayed, ..
+            } => {
+                println!("  turn {turn}: {replayed} model request(s), {recorded} recorded");
+            }
+            ReplayDivergence::FileChanges {
+                recorded, replayed, ..
+            } => {
+                println!("  turn {turn}: changed files differ");
+                println!("    recorded: {}", display_paths(recorded));
+                println!("    replayed: {}", display_paths(replayed));
+            }
+            ReplayDivergence::TurnFailed { message, .. } => {
+                println!("  turn {turn}: {message}");
+            }
+        }
+    }
+}
+
+fn print_preview(label: &str, output: &str) {
+    println!("    {label}:");
+    let lines: Vec<&str> = output.lines().collect();
+    for line in lines.iter().take(OUTPUT_PREVIEW_LINES) {
+        println!("      {line}");
+    }
+    if lines.len() > OUTPUT_PREVIEW_LINES {
+        println!(
+            "      … {} more line(s)",
+            lines.len() - OUTPUT_PREVIEW_LINES
+        );
+    }
+}
+
+fn display_command(command: &[String]) -> String {
+    match command {
+        [_, flag, script] if flag == "-lc" || flag == "-c" => script.clone(),
+        _ => command.join(" "),
+    }
+}
+
+fn display_paths(paths: &[PathBuf]) -> String {
+    if paths.is_empty() {
+        return "(none)".to_string();
+    }
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
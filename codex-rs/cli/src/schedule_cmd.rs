@@ -0,0 +1,247 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Local;
+use chrono::Utc;
+use clap::Parser;
+use codex_core::config::Config;
+use codex_state::CronSchedule;
+use codex_state::Schedule;
+use codex_state::ScheduleCreateParams;
+use codex_state::ScheduleThreadMode;
+use codex_state::StateRuntime;
+use codex_utils_cli::CliConfigOverrides;
+use codex_utils_cli::SandboxModeCliArg;
+use uuid::Uuid;
+
+const DEFAULT_RUNS_LIMIT: usize = 20;
+
+/// Manage scheduled agent runs.
+///
+/// Schedules are stored in the local state DB and executed with `codex exec`
+/// by any running `codex app-server` when they come due.
+#[derive(Debug, Parser)]
+pub struct ScheduleCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: ScheduleSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ScheduleSubcommand {
+    /// List schedules and their next run time.
+    List,
+
+    /// Create a schedule.
+    Create(CreateArgs),
+
+    /// Delete a schedule and its run history.
+    Delete(DeleteArgs),
+
+    /// Show the most recent runs of a schedule.
+    Runs(RunsArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct CreateArgs {
+    /// Cron expression (`minute hour day-of-month month day-of-week`, or
+    /// `@daily`, `@weekly`, ...) evaluated in the local time zone.
+    #[arg(long, value_name = "EXPR")]
+    pub cron: String,
+
+    /// Name shown in listings. Defaults to the first line of the prompt.
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Working directory for the runs. Defaults to the current directory.
+    #[arg(long = "cd", short = 'C', value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
+    /// Configuration profile from config.toml to run with.
+    #[arg(long = "profile", short = 'p')]
+    pub config_profile: Option<String>,
+
+    /// Sandbox policy for the runs.
+    #[arg(long = "sandbox", short = 's', value_enum)]
+    pub sandbox_mode: Option<SandboxModeCliArg>,
+
+    /// Continue the same thread on every run instead of starting a new one.
+    #[arg(long, default_value_t = false)]
+    pub resume: bool,
+
+    /// Thread the first run resumes. Implies `--resume`.
+    #[arg(long, value_name = "THREAD_ID")]
+    pub thread_id: Option<String>,
+
+    /// Prompt sent on every run.
+    #[arg(value_name = "PROMPT")]
+    pub prompt: String,
+}
+
+#[derive(Debug, Parser)]
+pub struct DeleteArgs {
+    /// Id of the schedule to delete.
+    pub id: String,
+}
+
+#[derive(Debug, Parser)]
+pub struct RunsArgs {
+    /// Id of the schedule.
+    pub id: String,
+
+    /// Maximum number of runs to show.
+    #[arg(long, default_value_t = DEFAULT_RUNS_LIMIT)]
+    pub limit: usize,
+}
+
+impl ScheduleCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let state_db =
+            StateRuntime::init(config.sqlite_home.clone(), config.model_provider_id.clone())
+                .await?;
+
+        match self.subcommand {
+            ScheduleSubcommand::List => run_list(&state_db).await,
+            ScheduleSubcommand::Create(args) => run_create(&state_db, &config, args).await,
+            ScheduleSubcommand::Delete(args) => run_delete(&state_db, args).await,
+            ScheduleSubcommand::Runs(args) => run_runs(&state_db, args).await,
+        }
+    }
+}
+
+async fn run_list(state_db: &StateRuntime) -> Result<()> {
+    let schedules = state_db.list_schedules().await?;
+    if schedules.is_empty() {
+        println!("No schedules. Create one with `codex schedule create`.");
+        return Ok(());
+    }
+    for schedule in schedules {
+        print_schedule(&schedule);
+    }
+    Ok(())
+}
+
+async fn run_create(state_db: &StateRuntime, config: &Config, args: CreateArgs) -> Result<()> {
+    let CreateArgs {
+        cron,
+        name,
+        cwd,
+        config_profile,
+        sandbox_mode,
+        resume,
+        thread_id,
+        prompt,
+    } = args;
+    if prompt.trim().is_empty() {
+        anyhow::bail!("prompt must not be empty");
+    }
+    CronSchedule::parse(&cron)?;
+    let cwd = match cwd {
+        Some(cwd) => config.cwd.join(cwd),
+        None => config.cwd.clone(),
+    };
+    let thread_mode = if resume || thread_id.is_some() {
+        ScheduleThreadMode::Resume
+    } else {
+        ScheduleThreadMode::New
+    };
+    let params = ScheduleCreateParams {
+        id: Uuid::now_v7().to_string(),
+        name: name.unwrap_or_else(|| Schedule::default_name(&prompt)),
+        cron,
+        prompt,
+        cwd: cwd.to_string_lossy().into_owned(),
+        profile: config_profile,
+        sandbox_mode: sandbox_mode.map(|sandbox_mode| {
+            match sandbox_mode {
+                SandboxModeCliArg::ReadOnly => "read-only",
+                SandboxModeCliArg::WorkspaceWrite => "workspace-write",
+                SandboxModeCliArg::DangerFullAccess => "danger-full-access",
+            }
+            .to_string()
+        }),
+        thread_mode,
+        thread_id,
+    };
+    let schedule = state_db.create_schedule(&params).await?;
+    println!("Created schedule {}.", schedule.id);
+    print_schedule(&schedule);
+    Ok(())
+}
+
+async fn run_delete(state_db: &StateRuntime, args: DeleteArgs) -> Result<()> {
+    if !state_db.delete_schedule(&args.id).await? {
+        anyhow::bail!("schedule not found: {}", args.id);
+    }
+    println!("Deleted schedule {}.", args.id);
+    Ok(())
+}
+
+async fn run_runs(state_db: &StateRuntime, args: RunsArgs) -> Result<()> {
+    if state_db.get_schedule(&args.id).await?.is_none() {
+        anyhow::bail!("schedule not found: {}", args.id);
+    }
+    let runs = state_db
+        .list_schedule_runs(&args.id, Some(args.limit))
+        .await?;
+    if runs.is_empty() {
+        println!("No runs yet.");
+        return Ok(());
+    }
+    for run in runs {
+        let exit_code = run
+            .exit_code
+            .map_or_else(|| "-".to_string(), |code| code.to_string());
+        println!(
+            "{}  {:<9}  exit {:<3}  thread {}",
+            format_local(run.started_at),
+            run.status.as_str(),
+            exit_code,
+            run.thread_id.as_deref().unwrap_or("-"),
+        );
+        if let Some(error) = run.error.as_deref().and_then(|error| error.lines().last()) {
+            println!("    {error}");
+        }
+    }
+    Ok(())
+}
+
+fn print_schedule(schedule: &Schedule) {
+    let next_run = schedule
+        .next_run_at
+        .map_or_else(|| "-".to_string(), format_local);
+    println!("{}  {}", schedule.id, schedule.name);
+    println!("    cron:     {}", schedule.cron);
+    println!("    next run: {next_run}");
+    println!("    cwd:      {}", schedule.cwd);
+    if let Some(profile) = &schedule.profile {
+        println!("    profile:  {profile}");
+    }
+    if let Some(sandbox_mode) = &schedule.sandbox_mode {
+        println!("    sandbox:  {sandbox_mode}");
+    }
+    match (schedule.thread_mode, schedule.thread_id.as_deref()) {
+        (ScheduleThreadMode::New, _) => println!("    thread:   new each run"),
+        (ScheduleThreadMode::Resume, Some(thread_id)) => {
+            println!("    thread:   resume {thread_id}");
+        }
+        (ScheduleThreadMode::Resume, None) => println!("    thread:   resume (after first run)"),
+    }
+}
+
+fn format_local(at: DateTime<Utc>) -> String {
+    at.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
@@ -0,0 +1,175 @@
+use std::io::IsTerminal;
+use std::io::Read;
+use std::io::Write;
+
+use anyhow::Context;
+use anyhow::Result;
+use clap::Parser;
+use codex_core::config::find_codex_home;
+use codex_secrets::SecretName;
+use codex_secrets::SecretScope;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+use codex_secrets::environment_id_from_cwd;
+
+/// Manage secrets that commands can receive through `shell_environment_policy`.
+///
+/// Reference a secret from config.toml as
+/// `shell_environment_policy.set.NPM_TOKEN = "secret:npm_token"`. Values never
+/// enter the model context and are redacted from command output.
+#[derive(Debug, Parser)]
+pub struct SecretsCli {
+    #[command(subcommand)]
+    pub subcommand: SecretsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SecretsSubcommand {
+    /// Store a secret. The value is read from stdin.
+    Set(NameArgs),
+
+    /// Print the value of a secret.
+    Get(NameArgs),
+
+    /// List stored secrets (names only).
+    List(ListArgs),
+
+    /// Delete a secret.
+    Delete(NameArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct NameArgs {
+    /// Name of the secret (A-Z, 0-9 and _; case-insensitive).
+    pub name: String,
+
+    #[clap(flatten)]
+    pub scope: ScopeArgs,
+}
+
+#[derive(Debug, Parser)]
+pub struct ListArgs {
+    #[clap(flatten)]
+    pub scope: ScopeArgs,
+
+    /// Only list global secrets.
+    #[arg(long, conflicts_with_all = ["environment", "repo"])]
+    pub global: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ScopeArgs {
+    /// Scope the secret to an environment instead of making it global.
+    #[arg(long = "env", value_name = "ID", conflicts_with = "repo")]
+    pub environment: Option<String>,
+
+    /// Scope the secret to the repository containing the current directory.
+    #[arg(long, default_value_t = false)]
+    pub repo: bool,
+}
+
+impl ScopeArgs {
+    /// The scope selected by the flags, if any.
+    fn scope(&self) -> Result<Option<SecretScope>> {
+        if let Some(environment) = &self.environment {
+            return SecretScope::environment(environment.as_str()).map(Some);
+        }
+        if self.repo {
+            let cwd = std::env::current_dir().context("failed to resolve current directory")?;
+            return SecretScope::environment(environment_id_from_cwd(&cwd)).map(Some);
+        }
+        Ok(None)
+    }
+}
+
+impl SecretsCli {
+    pub fn run(self) -> Result<()> {
+        let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+        let manager = SecretsManager::new(codex_home, SecretsBackendKind::Local);
+
+        match self.subcommand {
+            SecretsSubcommand::Set(args) => run_set(&manager, args),
+            SecretsSubcommand::Get(args) => run_get(&manager, args),
+            SecretsSubcommand::List(args) => run_list(&manager, args),
+            SecretsSubcommand::Delete(args) => run_delete(&manager, args),
+        }
+    }
+}
+
+fn parse_name(args: &NameArgs) -> Result<(SecretScope, SecretName)> {
+    let scope = args.scope.scope()?.unwrap_or(SecretScope::Global);
+    let name = SecretName::new(&args.name.to_ascii_uppercase())?;
+    Ok((scope, name))
+}
+
+fn run_set(manager: &SecretsManager, args: NameArgs) -> Result<()> {
+    let (scope, name) = parse_name(&args)?;
+    let mut value = String::new();
+    if std::io::stdin().is_terminal() {
+        eprint!("Value for {name}: ");
+        std::io::stderr().flush()?;
+        std::io::stdin().read_line(&mut value)?;
+    } else {
+        std::io::stdin().read_to_string(&mut value)?;
+    }
+    let value = value.trim_end_matches(['\r', '\n']);
+    if value.is_empty() {
+        anyhow::bail!("secret value must not be empty");
+    }
+    manager.set(&scope, &name, value)?;
+    println!("Stored secret {name} ({}).", format_scope(&scope));
+    Ok(())
+}
+
+fn run_get(manager: &SecretsManager, args: NameArgs) -> Result<()> {
+    let (scope, name) = parse_name(&args)?;
+    match manager.get(&scope, &name)? {
+        Some(value) => {
+            println!("{value}");
+            Ok(())
+        }
+        None => anyhow::bail!("secret {name} not found ({})", format_scope(&scope)),
+    }
+}
+
+fn run_list(manager: &SecretsManager, args: ListArgs) -> Result<()> {
+    let scope = if args.global {
+        Some(SecretScope::Global)
+    } else {
+        args.scope.scope()?
+    };
+    let mut entries = manager.list(scope.as_ref())?;
+    if entries.is_empty() {
+        println!("No secrets. Store one with `codex secrets set NAME`.");
+        return Ok(());
+    }
+    entries.sort_by(|a, b| {
+        format_scope(&a.scope)
+            .cmp(&format_scope(&b.scope))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    for entry in entries {
+        println!(
+            "{:<24}  {}",
+            entry.name.as_str(),
+            format_scope(&entry.scope)
+        );
+    }
+    Ok(())
+}
+
+fn run_delete(manager: &SecretsManager, args: NameArgs) -> Result<()> {
+    let (scope, name) = parse_name(&args)?;
+    if !manager.delete(&scope, &name)? {
+        anyhow::bail!("secret {name} not found ({})", format_scope(&scope));
+    }
+    println!("Deleted secret {name} ({}).", format_scope(&scope));
+    Ok(())
+}
+
+fn format_scope(scope: &SecretScope) -> String {
+    match scope {
+        SecretScope::Global => "global".to_string(),
+        SecretScope::Environment(environment) => format!("env {environment}"),
+    }
+}
@@ -0,0 +1,253 @@
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_app_server_protocol::TokenUsageBreakdown;
+use codex_app_server_protocol::UsageSummaryEntry;
+use codex_core::config::Config;
+use codex_core::state_db::token_usage_summary;
+use codex_state::StateRuntime;
+use codex_state::TokenUsageGroupBy;
+use codex_state::TokenUsageSummaryQuery;
+use codex_state::state_db_path;
+use codex_utils_cli::CliConfigOverrides;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Summarize the token usage recorded for local threads.
+///
+/// Costs are estimated from the `[model_pricing."<model>"]` table in `config.toml`.
+#[derive(Debug, clap::Parser)]
+pub struct UsageCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Group usage by this dimension. Repeat to group by several, in order.
+    #[arg(long = "by", value_enum, value_name = "DIMENSION")]
+    pub group_by: Vec<UsageGroupBy>,
+
+    /// Only include usage recorded in the last N days.
+    #[arg(long, value_name = "N")]
+    pub days: Option<u64>,
+
+    /// Output the summary as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum UsageGroupBy {
+    Day,
+    Repo,
+    Model,
+}
+
+impl UsageCli {
+    pub async fn run(self) -> Result<()> {
+        let overrides = self
+            .config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+
+        let state_path = state_db_path(config.sqlite_home.as_path());
+        if !tokio::fs::try_exists(&state_path).await? {
+            println!("No usage recorded yet.");
+            return Ok(());
+        }
+        let state_db =
+            StateRuntime::init(config.sqlite_home.clone(), config.model_provider_id.clone())
+                .await?;
+
+        let since = match self.days {
+            Some(days) => {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+                Some(i64::try_from(
+                    now.saturating_sub(days.saturating_mul(SECONDS_PER_DAY)),
+                )?)
+            }
+            None => None,
+        };
+        let query = TokenUsageSummaryQuery {
+            group_by: self
+                .group_by
+                .iter()
+                .map(|group_by| match group_by {
+                    UsageGroupBy::Day => TokenUsageGroupBy::Day,
+                    UsageGroupBy::Repo => TokenUsageGroupBy::Repository,
+                    UsageGroupBy::Model => TokenUsageGroupBy::Model,
+                })
+                .collect(),
+            since,
+            until: None,
+        };
+        let entries = token_usage_summary(&state_db, &query, &config.model_pricing)
+            .await?
+            .into_iter()
+            .map(|(row, cost_usd)| UsageSummaryEntry {
+                day: row.day,
+                repository: row.repository,
+                model: row.model,
+                turns: row.turns,
+                threads: row.threads,
+                usage: TokenUsageBreakdown {
+                    total_tokens: row.total_tokens,
+                    input_tokens: row.input_tokens,
+                    cached_input_tokens: row.cached_input_tokens,
+                    output_tokens: row.output_tokens,
+                    reasoning_output_tokens: row.reasoning_output_tokens,
+                },
+                cost_usd,
+            })
+            .collect::<Vec<_>>();
+
+        if self.json {
+            let output = serde_json::to_string_pretty(&entries)?;
+            println!("{output}");
+            return Ok(());
+        }
+        if entries.is_empty() {
+            println!("No usage recorded yet.");
+            return Ok(());
+        }
+        print!("{}", render_table(&self.group_by, &entries));
+        Ok(())
+    }
+}
+
+/// Render entries as a plain-text table with one column per grouping dimension.
+fn render_table(group_by: &[UsageGroupBy], entries: &[UsageSummaryEntry]) -> String {
+    let mut unique_group_by = Vec::new();
+    for dimension in group_by {
+        if !unique_group_by.contains(dimension) {
+            unique_group_by.push(*dimension);
+        }
+    }
+    let group_by = unique_group_by;
+    let mut headers = group_by
+        .iter()
+        .map(|group_by| match group_by {
+            UsageGroupBy::Day => "Day",
+            UsageGroupBy::Repo => "Repository",
+            UsageGroupBy::Model => "Model",
+        })
+        .collect::<Vec<_>>();
+    let key_columns = headers.len();
+    headers.extend([
+        "Turns",
+        "Threads",
+        "Input",
+        "Cached",
+        "Output",
+        "Reasoning",
+        "Total",
+        "Cost (USD)",
+    ]);
+
+    let rows = entries
+        .iter()
+        .map(|entry| {
+            let mut row = group_by
+                .iter()
+                .map(|group_by| {
+                    let value = match group_by {
+                        UsageGroupBy::Day => entry.day.as_deref(),
+                        UsageGroupBy::Repo => entry.repository.as_deref(),
+                        UsageGroupBy::Model => entry.model.as_deref(),
+                    };
+                    value.unwrap_or("-").to_string()
+                })
+                .collect::<Vec<_>>();
+            row.extend([
+                entry.turns.to_string(),
+                entry.threads.to_string(),
+                entry.usage.input_tokens.to_string(),
+                entry.usage.cached_input_tokens.to_string(),
+                entry.usage.output_tokens.to_string(),
+                entry.usage.reasoning_output_tokens.to_string(),
+                entry.usage.total_tokens.to_string(),
+                entry
+                    .cost_usd
+                    .map_or_else(|| "-".to_string(), |cost| format!("{cost:.2}")),
+            ]);
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = headers
+        .iter()
+        .map(|header| header.len())
+        .collect::<Vec<_>>();
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.len());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        let line = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                let width = widths[i];
+                // Grouping columns are left-aligned, numbers right-aligned.
+                if i < key_columns {
+                    format!("{cell:<width$}")
+                } else {
+                    format!("{cell:>width$}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        format!("{}\n", line.trim_end())
+    };
+    let mut output = format_row(headers);
+    for row in &rows {
+        output.push_str(&format_row(row.iter().map(String::as_str).collect()));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn render_table_aligns_group_and_number_columns() {
+        let entry =
+            |model: Option<&str>, total_tokens: i64, cost_usd: Option<f64>| UsageSummaryEntry {
+                day: None,
+                repository: None,
+                model: model.map(str::to_string),
+                turns: 3,
+                threads: 1,
+                usage: TokenUsageBreakdown {
+                    total_tokens,
+                    input_tokens: total_tokens - 10,
+                    cached_input_tokens: 0,
+                    output_tokens: 10,
+                    reasoning_output_tokens: 0,
+                },
+                cost_usd,
+            };
+        let table = render_table(
+            &[UsageGroupBy::Model],
+            &[
+                entry(Some("gpt-5"), 12_000, Some(0.126)),
+                entry(None, 110, None),
+            ],
+        );
+        assert_eq!(
+            table,
+            "\
+Model  Turns  Threads  Input  Cached  Output  Reasoning  Total  Cost (USD)
+gpt-5      3        1  11990       0      10          0  12000        0.13
+-          3        1    100       0      10          0    110           -
+"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+const BITS_PER_TRIGRAM: usize = 10;
+const HASHES: u64 = 3;
+
+/// Bloom filter over the ASCII-case-folded byte trigrams of a file.
+///
+/// It answers "might this file contain these strings?" without reading the
+/// file, at roughly ten bits per distinct trigram. False positives are
+/// weeded out when the candidate files are read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TrigramFilter {
+    words: Vec<u64>,
+}
+
+impl TrigramFilter {
+    pub(crate) fn from_text(text: &str) -> Self {
+        let trigrams: HashSet<u32> = trigrams(text).collect();
+        let bits = (trigrams.len() * BITS_PER_TRIGRAM)
+            .max(64)
+            .next_multiple_of(64);
+        let mut words = vec![0; bits / 64];
+        for trigram in trigrams {
+            for bit in bit_positions(trigram, bits) {
+                words[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        Self { words }
+    }
+
+    /// Whether the file may contain `needle`, ignoring ASCII case. Needles
+    /// shorter than three bytes always match.
+    pub(crate) fn may_contain(&self, needle: &str) -> bool {
+        let bits = self.words.len() * 64;
+        if bits == 0 {
+            return false;
+        }
+        trigrams(needle).all(|trigram| {
+            bit_positions(trigram, bits).all(|bit| self.words[bit / 64] & (1 << (bit % 64)) != 0)
+        })
+    }
+}
+
+fn trigrams(text: &str) -> impl Iterator<Item = u32> + '_ {
+    text.as_bytes().windows(3).map(|window| {
+        u32::from(window[0].to_ascii_lowercase()) << 16
+            | u32::from(window[1].to_ascii_lowercase()) << 8
+            | u32::from(window[2].to_ascii_lowercase())
+    })
+}
+
+/// Double hashing over a splitmix64 mix of the trigram.
+fn bit_positions(trigram: u32, bits: usize) -> impl Iterator<Item = usize> {
+    let mut hash = u64::from(trigram).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^= hash >> 31;
+    let first = hash & u64::from(u32::MAX);
+    let step = (hash >> 32) | 1;
+    let bits = bits as u64;
+    (0..HASHES).map(move |i| (first.wrapping_add(i.wrapping_mul(step)) % bits) as usize)
+}
+
+impl Serialize for TrigramFilter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes: Vec<u8> = self
+            .words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+        serializer.serialize_str(&BASE64.encode(bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for TrigramFilter {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        let bytes = BASE64.decode(encoded).map_err(serde::de::Error::custom)?;
+        if bytes.len() % 8 != 0 {
+            return Err(serde::de::Error::custom(
+                "trigram filter length is not a multiple of 8 bytes",
+            ));
+        }
+        let words = bytes
+            .chunks_exact(8)
+            .map(|chunk| {
+                let mut word = [0; 8];
+                word.copy_from_slice(chunk);
+                u64::from_le_bytes(word)
+            })
+            .collect();
+        Ok(Self { words })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn matches_contained_strings_ignoring_ascii_case() {
+        let filter = TrigramFilter::from_text("fn build_index(root: &Path) {}");
+        assert!(filter.may_contain("build_index"));
+        assert!(filter.may_contain("BUILD_INDEX"));
+        assert!(filter.may_contain("ro"));
+        assert!(!filter.may_contain("references_to_symbol"));
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let filter = TrigramFilter::from_text("struct CodeIndex;");
+        let json = serde_json::to_string(&filter).expect("serialize filter");
+        let decoded: TrigramFilter = serde_json::from_str(&json).expect("deserialize filter");
+        assert_eq!(decoded, filter);
+    }
+}
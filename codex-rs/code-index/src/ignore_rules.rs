@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use ignore::Match;
+use ignore::gitignore::Gitignore;
+use ignore::gitignore::GitignoreBuilder;
+
+/// Decides whether a single changed path belongs in the index, following the
+/// same rules as the full walk: hidden entries and `.gitignore`d paths are
+/// skipped. Matchers are loaded lazily and cached for one batch of updates.
+pub(crate) struct IgnoreRules<'a> {
+    root: &'a Path,
+    matchers: HashMap<PathBuf, Option<Gitignore>>,
+}
+
+impl<'a> IgnoreRules<'a> {
+    pub(crate) fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            matchers: HashMap::new(),
+        }
+    }
+
+    /// `relative` is relative to the workspace root.
+    pub(crate) fn is_excluded(&mut self, relative: &Path, is_dir: bool) -> bool {
+        if relative
+            .components()
+            .any(|component| component.as_os_str().to_string_lossy().starts_with('.'))
+        {
+            return true;
+        }
+        let path = self.root.join(relative);
+        // Deeper ignore files override shallower ones.
+        let mut dirs: Vec<PathBuf> = relative
+            .ancestors()
+            .skip(1)
+            .map(|ancestor| self.root.join(ancestor))
+            .collect();
+        dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+        for dir in dirs {
+            let Some(matcher) = self.matcher(&dir) else {
+                continue;
+            };
+            match matcher.matched_path_or_any_parents(&path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    fn matcher(&mut self, dir: &Path) -> Option<&Gitignore> {
+        let root = self.root;
+        self.matchers
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                let mut builder = GitignoreBuilder::new(dir);
+                let mut found = false;
+                let mut sources = vec![dir.join(".gitignore"), dir.join(".ignore")];
+                if dir == root {
+                    sources.push(dir.join(".git").join("info").join("exclude"));
+                }
+                for source in sources {
+                    if source.is_file() && builder.add(&source).is_none() {
+                        found = true;
+                    }
+                }
+                if !found {
+                    return None;
+                }
+                builder.build().ok()
+            })
+            .as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn nested_ignore_files_override_parent_rules() {
+        let root = tempfile::tempdir().expect("tempdir");
+        std::fs::write(root.path().join(".gitignore"), "target/\n*.log\n").expect("write");
+        std::fs::create_dir_all(root.path().join("logs")).expect("mkdir");
+        std::fs::write(root.path().join("logs/.gitignore"), "!keep.log\n").expect("write");
+
+        let mut rules = IgnoreRules::new(root.path());
+        let excluded = [
+            ("src/lib.rs", false),
+            ("target/debug/build.rs", false),
+            ("app.log", false),
+            ("logs/keep.log", false),
+            (".github/workflows/ci.yml", false),
+        ]
+        .map(|(path, is_dir)| rules.is_excluded(Path::new(path), is_dir));
+        assert_eq!(excluded, [false, true, true, false, true]);
+    }
+}
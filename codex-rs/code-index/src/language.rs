@@ -0,0 +1,110 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::warn;
+use tree_sitter::Query;
+
+/// Rust items the stock tags query does not report.
+const RUST_EXTRA_TAGS_QUERY: &str = r#"
+(const_item name: (identifier) @name) @definition.constant
+(static_item name: (identifier) @name) @definition.constant
+"#;
+
+/// Languages whose symbols are extracted with tree-sitter. Other text files
+/// are still indexed for text queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    Rust,
+    Python,
+    Go,
+    JavaScript,
+    TypeScript,
+    Tsx,
+}
+
+impl Language {
+    const ALL: [Language; 6] = [
+        Language::Rust,
+        Language::Python,
+        Language::Go,
+        Language::JavaScript,
+        Language::TypeScript,
+        Language::Tsx,
+    ];
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        match extension {
+            "rs" => Some(Self::Rust),
+            "py" | "pyi" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn grammar(self) -> tree_sitter::Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+        }
+    }
+
+    /// The tags query that captures definitions (`@definition.*`) and their
+    /// names (`@name`). Compiled once per language; `None` if the grammar
+    /// rejects it.
+    pub(crate) fn tags_query(self) -> Option<&'static Query> {
+        static QUERIES: [OnceLock<Option<Query>>; 6] = [const { OnceLock::new() }; 6];
+        let index = Self::ALL.iter().position(|language| *language == self)?;
+        QUERIES[index]
+            .get_or_init(|| {
+                let source = match self {
+                    Self::Rust => {
+                        format!("{}\n{RUST_EXTRA_TAGS_QUERY}", tree_sitter_rust::TAGS_QUERY)
+                    }
+                    Self::Python => tree_sitter_python::TAGS_QUERY.to_string(),
+                    Self::Go => tree_sitter_go::TAGS_QUERY.to_string(),
+                    Self::JavaScript => tree_sitter_javascript::TAGS_QUERY.to_string(),
+                    // The TypeScript tags only cover TypeScript-specific
+                    // syntax and are meant to be combined with JavaScript's.
+                    Self::TypeScript | Self::Tsx => format!(
+                        "{}\n{}",
+                        tree_sitter_javascript::TAGS_QUERY,
+                        tree_sitter_typescript::TAGS_QUERY
+                    ),
+                };
+                match Query::new(&self.grammar(), &source) {
+                    Ok(query) => Some(query),
+                    Err(err) => {
+                        warn!("failed to compile {self:?} tags query: {err}");
+                        None
+                    }
+                }
+            })
+            .as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_queries_compile_for_every_language() {
+        for language in Language::ALL {
+            assert!(
+                language.tags_query().is_some(),
+                "{language:?} tags query should compile"
+            );
+        }
+    }
+}
@@ -0,0 +1,614 @@
+//! Persistent code index for a workspace.
+//!
+//! Every text file gets a trigram filter so text queries only read files that
+//! can match, and files in supported languages get a symbol table extracted
+//! with tree-sitter tags queries. The index is stored as JSON in an index
+//! directory (one subdirectory per workspace root, with entries spread over
+//! shard files so saves only rewrite what changed) and kept current with
+//! [`CodeIndex::refresh`] after a restart and [`CodeIndex::update_paths`] for
+//! changes reported by a file watcher.
+
+mod filter;
+mod ignore_rules;
+mod language;
+mod search;
+mod symbols;
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+use std::fs::Metadata;
+use std::io;
+use std::num::NonZero;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use ignore::WalkBuilder;
+use serde::Deserialize;
+use serde::Serialize;
+use sha1::Digest;
+use sha1::Sha1;
+use tracing::warn;
+
+use crate::filter::TrigramFilter;
+use crate::ignore_rules::IgnoreRules;
+
+pub use language::Language;
+pub use search::SearchHit;
+pub use symbols::Symbol;
+
+/// Bump when the stored format or the extraction changes so existing indexes
+/// are rebuilt instead of misread.
+const INDEX_VERSION: u32 = 2;
+
+/// Entries are spread over this many shard files by a hash of their path, so
+/// a save only rewrites the shards holding changed files.
+const SHARD_COUNT: usize = 64;
+
+const META_FILE_NAME: &str = "meta.json";
+
+/// Larger files are usually generated or minified and are left out.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Files with a NUL byte in this prefix are treated as binary.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct FileEntry {
+    len: u64,
+    modified_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language: Option<Language>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    symbols: Vec<Symbol>,
+    /// `None` for files whose contents are not indexed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    filter: Option<TrigramFilter>,
+}
+
+impl FileEntry {
+    fn may_contain(&self, needle: &str) -> bool {
+        self.filter
+            .as_ref()
+            .is_some_and(|filter| filter.may_contain(needle))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredMeta {
+    version: u32,
+    root: PathBuf,
+}
+
+/// The index of one workspace root. Paths are relative to the root.
+pub struct CodeIndex {
+    root: PathBuf,
+    store_dir: PathBuf,
+    files: BTreeMap<PathBuf, FileEntry>,
+    /// Shards with entries added, changed or removed since the last save.
+    dirty_shards: BTreeSet<usize>,
+}
+
+/// Serialized shards taken from a [`CodeIndex`], written by
+/// [`PendingSave::write`] once the index no longer needs to be borrowed.
+pub struct PendingSave {
+    store_dir: PathBuf,
+    files: Vec<(PathBuf, Vec<u8>)>,
+}
+
+impl CodeIndex {
+    /// Loads the index of `root` from `index_dir`, building it when there is
+    /// none, and re-indexes files that changed since it was saved.
+    pub fn open(root: &Path, index_dir: &Path) -> Self {
+        let store_dir = index_dir.join(store_dir_name(root));
+        // Indexes before sharding were a single `<store dir>.json` file.
+        let _ = std::fs::remove_file(store_dir.with_extension("json"));
+        let (files, dirty_shards) = load(&store_dir, root);
+        let mut index = Self {
+            root: root.to_path_buf(),
+            store_dir,
+            files,
+            dirty_shards,
+        };
+        index.refresh();
+        index
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn symbol_count(&self) -> usize {
+        self.files.values().map(|entry| entry.symbols.len()).sum()
+    }
+
+    /// Walks the whole workspace, re-indexing files whose size or
+    /// modification time changed and dropping files that are gone. Returns
+    /// the number of files added, updated or removed.
+    pub fn refresh(&mut self) -> usize {
+        let walked = walk(&self.root);
+        let seen: HashSet<PathBuf> = walked.iter().map(|(path, _)| path.clone()).collect();
+        self.remove_where(|path| !seen.contains(path)) + self.index_changed(walked)
+    }
+
+    /// Brings the given absolute paths up to date. Paths may be files or
+    /// directories, and may no longer exist. Returns the number of files
+    /// added, updated or removed.
+    pub fn update_paths(&mut self, paths: &[PathBuf]) -> usize {
+        let root = self.root.clone();
+        let mut rules = IgnoreRules::new(&root);
+        let mut candidates = Vec::new();
+        let mut changed = 0;
+        for path in paths {
+            let Ok(relative) = path.strip_prefix(&root) else {
+                continue;
+            };
+            if relative.as_os_str().is_empty() {
+                changed += self.refresh();
+                continue;
+            }
+            let relative = relative.to_path_buf();
+            let metadata = std::fs::symlink_metadata(path).ok();
+            let is_dir = metadata.as_ref().is_some_and(Metadata::is_dir);
+            if metadata.is_none() || rules.is_excluded(&relative, is_dir) {
+                changed += self.remove_where(|indexed| indexed.starts_with(&relative));
+                continue;
+            }
+            if is_dir {
+                let walked: Vec<(PathBuf, Metadata)> = walk(path)
+                    .into_iter()
+                    .map(|(child, metadata)| (relative.join(child), metadata))
+                    .collect();
+                let seen: HashSet<PathBuf> = walked.iter().map(|(path, _)| path.clone()).collect();
+                changed += self.remove_where(|indexed| {
+                    indexed.starts_with(&relative) && !seen.contains(indexed)
+                });
+                candidates.extend(walked);
+            } else if let Some(metadata) = metadata
+                && metadata.is_file()
+            {
+                candidates.push((relative, metadata));
+            } else {
+                changed += self.remove_where(|indexed| indexed == relative);
+            }
+        }
+        changed + self.index_changed(candidates)
+    }
+
+    /// Writes the shards that changed since the index was loaded or last
+    /// saved.
+    pub fn save(&mut self) -> io::Result<()> {
+        match self.take_pending_save()? {
+            Some(pending) => pending.write(),
+            None => Ok(()),
+        }
+    }
+
+    /// Serializes the shards that changed since the index was loaded or last
+    /// saved and marks them clean. Returns `None` when nothing changed.
+    pub fn take_pending_save(&mut self) -> io::Result<Option<PendingSave>> {
+        if self.dirty_shards.is_empty() {
+            return Ok(None);
+        }
+        let mut shards: BTreeMap<usize, BTreeMap<&Path, &FileEntry>> = self
+            .dirty_shards
+            .iter()
+            .map(|shard| (*shard, BTreeMap::new()))
+            .collect();
+        for (path, entry) in &self.files {
+            if let Some(shard) = shards.get_mut(&shard_of(path)) {
+                shard.insert(path, entry);
+            }
+        }
+        let mut files = Vec::with_capacity(shards.len() + 1);
+        for (shard, entries) in shards {
+            let json = serde_json::to_vec(&entries).map_err(io::Error::other)?;
+            files.push((self.store_dir.join(shard_file_name(shard)), json));
+        }
+        // The metadata goes last so a store only becomes valid for this
+        // version once all of its rewritten shards are in place.
+        let meta = serde_json::to_vec(&StoredMeta {
+            version: INDEX_VERSION,
+            root: self.root.clone(),
+        })
+        .map_err(io::Error::other)?;
+        files.push((self.store_dir.join(META_FILE_NAME), meta));
+        self.dirty_shards.clear();
+        Ok(Some(PendingSave {
+            store_dir: self.store_dir.clone(),
+            files,
+        }))
+    }
+
+    fn remove_where(&mut self, predicate: impl Fn(&Path) -> bool) -> usize {
+        let removed: Vec<PathBuf> = self
+            .files
+            .keys()
+            .filter(|path| predicate(path))
+            .cloned()
+            .collect();
+        for path in &removed {
+            self.files.remove(path);
+            self.dirty_shards.insert(shard_of(path));
+        }
+        removed.len()
+    }
+
+    /// Indexes the files whose stamp differs from the stored entry.
+    fn index_changed(&mut self, candidates: Vec<(PathBuf, Metadata)>) -> usize {
+        let stale: Vec<(PathBuf, u64, u64)> = candidates
+            .into_iter()
+            .filter_map(|(path, metadata)| {
+                let (len, modified_ms) = stamp(&metadata);
+                let current = self
+                    .files
+                    .get(&path)
+                    .is_some_and(|entry| entry.len == len && entry.modified_ms == modified_ms);
+                (!current).then_some((path, len, modified_ms))
+            })
+            .collect();
+        if stale.is_empty() {
+            return 0;
+        }
+        let root = &self.root;
+        let indexed = parallel_map(&stale, |(path, len, modified_ms)| {
+            index_file(&root.join(path), *len, *modified_ms)
+        });
+        let changed = stale.len();
+        for ((path, _, _), entry) in stale.into_iter().zip(indexed) {
+            self.dirty_shards.insert(shard_of(&path));
+            self.files.insert(path, entry);
+        }
+        changed
+    }
+}
+
+impl PendingSave {
+    pub fn write(self) -> io::Result<()> {
+        std::fs::create_dir_all(&self.store_dir)?;
+        for (path, contents) in self.files {
+            // Another process may be saving the same workspace; rename makes
+            // the last complete write of each file win.
+            let temp_path = path.with_extension(format!("json.{}.tmp", std::process::id()));
+            std::fs::write(&temp_path, contents)?;
+            std::fs::rename(&temp_path, &path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Directories under `dir` (inclusive) that the index walks into, following
+/// the same rules as [`CodeIndex::refresh`]. `dir` is an absolute path under
+/// `root`; an excluded `dir` yields nothing. A file watcher only needs to
+/// watch these to see every change to indexed files.
+pub fn indexed_dirs(root: &Path, dir: &Path) -> Vec<PathBuf> {
+    let Ok(relative) = dir.strip_prefix(root) else {
+        return Vec::new();
+    };
+    if !relative.as_os_str().is_empty() && IgnoreRules::new(root).is_excluded(relative, true) {
+        return Vec::new();
+    }
+    WalkBuilder::new(dir)
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_dir())
+        })
+        .map(ignore::DirEntry::into_path)
+        .collect()
+}
+
+fn sha1_hex(text: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(text.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// A stable hash of the relative path, so entries keep their shard across
+/// processes and toolchains.
+fn shard_of(path: &Path) -> usize {
+    let digest = sha1_hex(&path.to_string_lossy());
+    usize::from_str_radix(digest.get(..4).unwrap_or("0"), 16).unwrap_or(0) % SHARD_COUNT
+}
+
+fn shard_file_name(shard: usize) -> String {
+    format!("shard-{shard:02}.json")
+}
+
+/// `<root dir name>-<hash of the root path>`, so indexes of different
+/// checkouts never collide and are still recognizable on disk.
+fn store_dir_name(root: &Path) -> String {
+    let digest = sha1_hex(&root.to_string_lossy());
+    let hash = digest.get(..16).unwrap_or(&digest);
+    let name: String = root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{name}-{hash}")
+}
+
+/// Reads the stored entries of `root` and the shards that must be rewritten:
+/// every shard when there is no usable store, otherwise only unreadable
+/// ones. Entries of an unreadable shard are missing, so the refresh after
+/// loading re-indexes them.
+fn load(store_dir: &Path, root: &Path) -> (BTreeMap<PathBuf, FileEntry>, BTreeSet<usize>) {
+    let meta_path = store_dir.join(META_FILE_NAME);
+    let meta = std::fs::read(&meta_path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<StoredMeta>(&bytes).ok());
+    if !meta.is_some_and(|meta| meta.version == INDEX_VERSION && meta.root == root) {
+        return (BTreeMap::new(), (0..SHARD_COUNT).collect());
+    }
+    let mut files = BTreeMap::new();
+    let mut dirty_shards = BTreeSet::new();
+    for shard in 0..SHARD_COUNT {
+        let shard_path = store_dir.join(shard_file_name(shard));
+        let bytes = match std::fs::read(&shard_path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => {
+                warn!(
+                    "ignoring unreadable code index shard {}: {err}",
+                    shard_path.display()
+                );
+                dirty_shards.insert(shard);
+                continue;
+            }
+        };
+        match serde_json::from_slice::<BTreeMap<PathBuf, FileEntry>>(&bytes) {
+            Ok(entries) => files.extend(entries),
+            Err(err) => {
+                warn!(
+                    "ignoring unreadable code index shard {}: {err}",
+                    shard_path.display()
+                );
+                dirty_shards.insert(shard);
+            }
+        }
+    }
+    (files, dirty_shards)
+}
+
+/// Files under `dir` that pass the ignore rules, relative to `dir`.
+fn walk(dir: &Path) -> Vec<(PathBuf, Metadata)> {
+    let mut files = Vec::new();
+    for entry in WalkBuilder::new(dir).build() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                warn!("code index walk error: {err}");
+                continue;
+            }
+        };
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let Ok(relative) = entry.path().strip_prefix(dir) else {
+            continue;
+        };
+        files.push((relative.to_path_buf(), metadata));
+    }
+    files
+}
+
+fn stamp(metadata: &Metadata) -> (u64, u64) {
+    let modified_ms = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| {
+            u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+        });
+    (metadata.len(), modified_ms)
+}
+
+/// Reads and indexes one file. Files that are too large, binary or not UTF-8
+/// keep an entry without a filter so they are not read again until they
+/// change.
+fn index_file(path: &Path, len: u64, modified_ms: u64) -> FileEntry {
+    let text = (len <= MAX_FILE_BYTES)
+        .then(|| std::fs::read(path).ok())
+        .flatten()
+        .filter(|bytes| !bytes.iter().take(BINARY_SNIFF_BYTES).any(|byte| *byte == 0))
+        .and_then(|bytes| String::from_utf8(bytes).ok());
+    let Some(text) = text else {
+        return FileEntry {
+            len,
+            modified_ms,
+            language: None,
+            symbols: Vec::new(),
+            filter: None,
+        };
+    };
+    let language = Language::from_path(path);
+    let symbols = language
+        .map(|language| symbols::extract_symbols(language, &text))
+        .unwrap_or_default();
+    FileEntry {
+        len,
+        modified_ms,
+        language,
+        symbols,
+        filter: Some(TrigramFilter::from_text(&text)),
+    }
+}
+
+/// Maps `items` on all available cores, preserving order.
+fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = std::thread::available_parallelism()
+        .map(NonZero::get)
+        .unwrap_or(1);
+    let chunk_size = items.len().div_ceil(threads).max(1);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(|| chunk.iter().map(&f).collect::<Vec<R>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| match handle.join() {
+                Ok(results) => results,
+                Err(panic) => std::panic::resume_unwind(panic),
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn write(root: &Path, path: &str, contents: &str) {
+        let path = root.join(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("create parent");
+        }
+        std::fs::write(path, contents).expect("write file");
+    }
+
+    fn indexed_paths(index: &CodeIndex) -> Vec<String> {
+        index
+            .files
+            .keys()
+            .map(|path| path.to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    #[test]
+    fn builds_saves_and_reloads_the_index() {
+        let workspace = tempfile::tempdir().expect("workspace");
+        let index_dir = tempfile::tempdir().expect("index dir");
+        let root = workspace.path();
+        write(root, "src/lib.rs", "pub fn parse_config() {}\n");
+        write(root, "README.md", "Configuration notes\n");
+        write(root, "assets/logo.bin", "\0\0\0");
+
+        let mut index = CodeIndex::open(root, index_dir.path());
+        assert_eq!(
+            indexed_paths(&index),
+            vec!["README.md", "assets/logo.bin", "src/lib.rs"]
+        );
+        assert_eq!(index.files[Path::new("assets/logo.bin")].filter, None);
+        assert_eq!(index.symbol_count(), 1);
+        index.save().expect("save index");
+
+        let reloaded = CodeIndex::open(root, index_dir.path());
+        assert_eq!(reloaded.files, index.files);
+        assert!(reloaded.dirty_shards.is_empty());
+    }
+
+    #[test]
+    fn save_only_rewrites_shards_with_changed_files() {
+        let workspace = tempfile::tempdir().expect("workspace");
+        let index_dir = tempfile::tempdir().expect("index dir");
+        let root = workspace.path();
+        for name in ["a", "b", "c", "d"] {
+            write(root, &format!("src/{name}.rs"), "fn f() {}\n");
+        }
+        let mut index = CodeIndex::open(root, index_dir.path());
+        let initial = index
+            .take_pending_save()
+            .expect("serialize index")
+            .expect("new index is dirty");
+        assert_eq!(initial.files.len(), SHARD_COUNT + 1);
+        initial.write().expect("write index");
+
+        write(root, "src/b.rs", "fn g() {}\nfn h() {}\n");
+        index.update_paths(&[root.join("src/b.rs")]);
+        let pending = index
+            .take_pending_save()
+            .expect("serialize index")
+            .expect("changed index is dirty");
+        let written: Vec<PathBuf> = pending.files.iter().map(|(path, _)| path.clone()).collect();
+        let store_dir = index_dir.path().join(store_dir_name(root));
+        assert_eq!(
+            written,
+            vec![
+                store_dir.join(shard_file_name(shard_of(Path::new("src/b.rs")))),
+                store_dir.join(META_FILE_NAME),
+            ]
+        );
+        pending.write().expect("write index");
+        assert!(
+            index
+                .take_pending_save()
+                .expect("serialize index")
+                .is_none()
+        );
+
+        let reloaded = CodeIndex::open(root, index_dir.path());
+        assert_eq!(reloaded.files, index.files);
+    }
+
+    #[test]
+    fn indexed_dirs_skip_ignored_and_hidden_directories() {
+        let workspace = tempfile::tempdir().expect("workspace");
+        let root = workspace.path();
+        write(root, ".gitignore", "node_modules/\n");
+        write(root, "src/lib.rs", "");
+        write(root, "node_modules/pkg/index.js", "");
+        write(root, ".cache/data", "");
+
+        let mut dirs = indexed_dirs(root, root);
+        dirs.sort();
+        assert_eq!(dirs, vec![root.to_path_buf(), root.join("src")]);
+        assert_eq!(
+            indexed_dirs(root, &root.join("node_modules/pkg")),
+            Vec::<PathBuf>::new()
+        );
+    }
+
+    #[test]
+    fn update_paths_tracks_created_changed_and_removed_files() {
+        let workspace = tempfile::tempdir().expect("workspace");
+        let index_dir = tempfile::tempdir().expect("index dir");
+        let root = workspace.path();
+        write(root, ".gitignore", "generated/\n");
+        write(root, "src/a.py", "def first():\n    pass\n");
+        write(root, "src/old/b.py", "def second():\n    pass\n");
+        let mut index = CodeIndex::open(root, index_dir.path());
+
+        write(root, "src/a.py", "def renamed():\n    pass\n");
+        write(root, "src/c.go", "package main\n\nfunc third() {}\n");
+        write(root, "generated/out.py", "def ignored():\n    pass\n");
+        std::fs::remove_dir_all(root.join("src/old")).expect("remove dir");
+        let changed = index.update_paths(&[
+            root.join("src/a.py"),
+            root.join("src/c.go"),
+            root.join("generated/out.py"),
+            root.join("src/old"),
+        ]);
+
+        assert_eq!(changed, 3);
+        assert_eq!(indexed_paths(&index), vec!["src/a.py", "src/c.go"]);
+        let names: Vec<&str> = index
+            .files
+            .values()
+            .flat_map(|entry| entry.symbols.iter().map(|symbol| symbol.name.as_str()))
+            .collect();
+        assert_eq!(names, vec!["renamed", "third"]);
+    }
+}
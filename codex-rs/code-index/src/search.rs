@@ -0,0 +1,424 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::CodeIndex;
+use crate::FileEntry;
+use crate::parallel_map;
+use crate::symbols::Symbol;
+use crate::symbols::identifier_lines;
+
+const MAX_LINE_CHARS: usize = 200;
+
+/// Text queries return at most this many lines per file so one noisy file
+/// cannot crowd out the rest.
+const MAX_HITS_PER_FILE: usize = 5;
+
+/// One line of a query result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchHit {
+    /// Relative to the index root.
+    pub path: PathBuf,
+    /// 1-based.
+    pub line: usize,
+    /// The line, trimmed and shortened.
+    pub text: String,
+    /// The symbol kind (`function`, `class`, ...) when the line defines one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+impl CodeIndex {
+    /// Definitions of `name` in files under `within` (relative to the root;
+    /// empty for everything). Exact matches come first, then matches that
+    /// ignore case, then symbols whose name contains `name`.
+    ///
+    /// Files for which `excluded` returns true (given their root-relative
+    /// path) are skipped before `limit` applies, so they never take up room
+    /// in the results. The same holds for the other queries.
+    pub fn definitions(
+        &self,
+        name: &str,
+        within: &Path,
+        excluded: impl Fn(&Path) -> bool,
+        limit: usize,
+    ) -> Vec<SearchHit> {
+        let lowered = name.to_lowercase();
+        let mut found: Vec<(u8, &Path, &Symbol)> = Vec::new();
+        for (path, entry) in self.entries_within(within, excluded) {
+            for symbol in &entry.symbols {
+                let rank = if symbol.name == name {
+                    0
+                } else {
+                    let symbol_lowered = symbol.name.to_lowercase();
+                    if symbol_lowered == lowered {
+                        1
+                    } else if symbol_lowered.contains(&lowered) {
+                        2
+                    } else {
+                        continue;
+                    }
+                };
+                found.push((rank, path, symbol));
+            }
+        }
+        found.sort_by(|(a_rank, a_path, a_symbol), (b_rank, b_path, b_symbol)| {
+            (a_rank, a_path, a_symbol.line).cmp(&(b_rank, b_path, b_symbol.line))
+        });
+        found.truncate(limit);
+
+        let mut sources: HashMap<&Path, Option<String>> = HashMap::new();
+        found
+            .into_iter()
+            .map(|(_, path, symbol)| {
+                let source = sources
+                    .entry(path)
+                    .or_insert_with(|| std::fs::read_to_string(self.root.join(path)).ok());
+                SearchHit {
+                    path: path.to_path_buf(),
+                    line: symbol.line,
+                    text: source
+                        .as_deref()
+                        .map(|source| line_text(source, symbol.line))
+                        .unwrap_or_default(),
+                    kind: Some(symbol.kind.clone()),
+                }
+            })
+            .collect()
+    }
+
+    /// Lines under `within` that use the identifier `name`, excluding its
+    /// definitions.
+    /// Files in supported languages are parsed so mentions in comments and
+    /// strings are skipped; other files match whole words.
+    pub fn references(
+        &self,
+        name: &str,
+        within: &Path,
+        excluded: impl Fn(&Path) -> bool,
+        limit: usize,
+    ) -> Vec<SearchHit> {
+        let candidates: Vec<(&Path, &FileEntry)> = self
+            .entries_within(within, excluded)
+            .filter(|(_, entry)| entry.may_contain(name))
+            .collect();
+        let per_file = parallel_map(&candidates, |(path, entry)| {
+            let Ok(source) = std::fs::read_to_string(self.root.join(path)) else {
+                return Vec::new();
+            };
+            let lines = match entry.language {
+                Some(language) => identifier_lines(language, &source, name),
+                None => word_lines(&source, name),
+            };
+            lines
+                .into_iter()
+                .filter(|line| {
+                    !entry
+                        .symbols
+                        .iter()
+                        .any(|symbol| symbol.line == *line && symbol.name == name)
+                })
+                .map(|line| SearchHit {
+                    path: path.to_path_buf(),
+                    line,
+                    text: line_text(&source, line),
+                    kind: None,
+                })
+                .collect()
+        });
+        per_file.into_iter().flatten().take(limit).collect()
+    }
+
+    /// Lines under `within` matching the whitespace-separated terms of
+    /// `query`, best first. Only files containing every term are considered.
+    /// Matching ignores case unless the query has uppercase letters. Lines
+    /// with more terms rank higher, as do lines defining a matching symbol
+    /// and files whose path matches.
+    pub fn search(
+        &self,
+        query: &str,
+        within: &Path,
+        excluded: impl Fn(&Path) -> bool,
+        limit: usize,
+    ) -> Vec<SearchHit> {
+        let case_sensitive = query.chars().any(char::is_uppercase);
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|term| {
+                if case_sensitive {
+                    term.to_string()
+                } else {
+                    term.to_lowercase()
+                }
+            })
+            .collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+        let candidates: Vec<(&Path, &FileEntry)> = self
+            .entries_within(within, excluded)
+            .filter(|(_, entry)| terms.iter().all(|term| entry.may_contain(term)))
+            .collect();
+        let per_file = parallel_map(&candidates, |(path, entry)| {
+            let Ok(source) = std::fs::read_to_string(self.root.join(path)) else {
+                return Vec::new();
+            };
+            rank_lines(path, entry, &source, &terms, case_sensitive)
+        });
+        let mut ranked: Vec<(usize, SearchHit)> = per_file.into_iter().flatten().collect();
+        ranked.sort_by(|(a_score, a), (b_score, b)| {
+            (Reverse(a_score), &a.path, a.line).cmp(&(Reverse(b_score), &b.path, b.line))
+        });
+        ranked.into_iter().take(limit).map(|(_, hit)| hit).collect()
+    }
+
+    fn entries_within<'a>(
+        &'a self,
+        within: &'a Path,
+        excluded: impl Fn(&Path) -> bool + 'a,
+    ) -> impl Iterator<Item = (&'a Path, &'a FileEntry)> + 'a {
+        self.files
+            .iter()
+            .filter(move |(path, _)| path.starts_with(within) && !excluded(path))
+            .map(|(path, entry)| (path.as_path(), entry))
+    }
+}
+
+fn rank_lines(
+    path: &Path,
+    entry: &FileEntry,
+    source: &str,
+    terms: &[String],
+    case_sensitive: bool,
+) -> Vec<(usize, SearchHit)> {
+    let fold = |text: &str| -> String {
+        if case_sensitive {
+            text.to_string()
+        } else {
+            text.to_lowercase()
+        }
+    };
+    let path_text = fold(&path.to_string_lossy());
+    let path_bonus = 5 * terms
+        .iter()
+        .filter(|term| path_text.contains(term.as_str()))
+        .count();
+
+    let mut seen_terms = vec![false; terms.len()];
+    let mut hits = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let haystack = if case_sensitive {
+            Cow::Borrowed(line)
+        } else {
+            Cow::Owned(line.to_lowercase())
+        };
+        let mut matched = 0;
+        for (term, seen) in terms.iter().zip(seen_terms.iter_mut()) {
+            if haystack.contains(term.as_str()) {
+                matched += 1;
+                *seen = true;
+            }
+        }
+        if matched == 0 {
+            continue;
+        }
+        let definition = entry.symbols.iter().find(|symbol| {
+            symbol.line == line_number
+                && terms
+                    .iter()
+                    .any(|term| fold(&symbol.name).contains(term.as_str()))
+        });
+        let mut score = 10 * matched + path_bonus;
+        if matched == terms.len() {
+            score += 10;
+        }
+        if definition.is_some() {
+            score += 20;
+        }
+        hits.push((
+            score,
+            SearchHit {
+                path: path.to_path_buf(),
+                line: line_number,
+                text: shorten(line),
+                kind: definition.map(|symbol| symbol.kind.clone()),
+            },
+        ));
+    }
+    if !seen_terms.iter().all(|seen| *seen) {
+        return Vec::new();
+    }
+    hits.sort_by_key(|(score, hit)| (Reverse(*score), hit.line));
+    hits.truncate(MAX_HITS_PER_FILE);
+    hits
+}
+
+/// 1-based lines containing `word` delimited by non-identifier characters.
+fn word_lines(source: &str, word: &str) -> Vec<usize> {
+    let is_identifier = |ch: char| ch.is_alphanumeric() || ch == '_' || ch == '$';
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            line.match_indices(word).any(|(start, _)| {
+                let before = line[..start].chars().next_back();
+                let after = line[start + word.len()..].chars().next();
+                !before.is_some_and(is_identifier) && !after.is_some_and(is_identifier)
+            })
+        })
+        .map(|(index, _)| index + 1)
+        .collect()
+}
+
+fn line_text(source: &str, line: usize) -> String {
+    source
+        .lines()
+        .nth(line.saturating_sub(1))
+        .map(shorten)
+        .unwrap_or_default()
+}
+
+fn shorten(line: &str) -> String {
+    let line = line.trim();
+    match line.char_indices().nth(MAX_LINE_CHARS) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn hit(path: &str, line: usize, text: &str, kind: Option<&str>) -> SearchHit {
+        SearchHit {
+            path: PathBuf::from(path),
+            line,
+            text: text.to_string(),
+            kind: kind.map(str::to_string),
+        }
+    }
+
+    fn workspace() -> (tempfile::TempDir, tempfile::TempDir, CodeIndex) {
+        let workspace = tempfile::tempdir().expect("workspace");
+        let index_dir = tempfile::tempdir().expect("index dir");
+        let files = [
+            (
+                "core/config.rs",
+                "pub struct ConfigLoader;\n\nimpl ConfigLoader {\n    pub fn load_config(&self) {}\n}\n",
+            ),
+            (
+                "cli/main.rs",
+                "// ConfigLoader reads config.toml\nfn main() {\n    let loader = ConfigLoader;\n    loader.load_config();\n}\n",
+            ),
+            ("docs/config.md", "Use ConfigLoader to load the config.\n"),
+        ];
+        for (path, contents) in files {
+            let path = workspace.path().join(path);
+            std::fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+            std::fs::write(path, contents).expect("write");
+        }
+        let index = CodeIndex::open(workspace.path(), index_dir.path());
+        (workspace, index_dir, index)
+    }
+
+    #[test]
+    fn definitions_rank_exact_matches_first() {
+        let (_workspace, _index_dir, index) = workspace();
+        assert_eq!(
+            index.definitions("load_config", Path::new(""), |_| false, 10),
+            vec![hit(
+                "core/config.rs",
+                4,
+                "pub fn load_config(&self) {}",
+                Some("method")
+            )]
+        );
+        assert_eq!(
+            index
+                .definitions("config", Path::new(""), |_| false, 10)
+                .into_iter()
+                .map(|hit| hit.text)
+                .collect::<Vec<_>>(),
+            vec!["pub struct ConfigLoader;", "pub fn load_config(&self) {}"]
+        );
+    }
+
+    #[test]
+    fn references_skip_definitions_and_comments() {
+        let (_workspace, _index_dir, index) = workspace();
+        assert_eq!(
+            index.references("ConfigLoader", Path::new(""), |_| false, 10),
+            vec![
+                hit("cli/main.rs", 3, "let loader = ConfigLoader;", None),
+                hit("core/config.rs", 3, "impl ConfigLoader {", None),
+                hit(
+                    "docs/config.md",
+                    1,
+                    "Use ConfigLoader to load the config.",
+                    None
+                ),
+            ]
+        );
+        assert_eq!(
+            index
+                .references("ConfigLoader", Path::new("cli"), |_| false, 10)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn excluded_files_do_not_count_toward_the_limit() {
+        let (_workspace, _index_dir, index) = workspace();
+        assert_eq!(
+            index.references(
+                "ConfigLoader",
+                Path::new(""),
+                |path| path.starts_with("cli"),
+                1
+            ),
+            vec![hit("core/config.rs", 3, "impl ConfigLoader {", None)]
+        );
+    }
+
+    #[test]
+    fn search_ranks_lines_with_all_terms_and_definitions_first() {
+        let (_workspace, _index_dir, index) = workspace();
+        let hits = index.search("load config", Path::new(""), |_| false, 4);
+        assert_eq!(
+            hits,
+            vec![
+                hit(
+                    "core/config.rs",
+                    1,
+                    "pub struct ConfigLoader;",
+                    Some("class")
+                ),
+                hit(
+                    "core/config.rs",
+                    4,
+                    "pub fn load_config(&self) {}",
+                    Some("method")
+                ),
+                hit("core/config.rs", 3, "impl ConfigLoader {", None),
+                hit(
+                    "docs/config.md",
+                    1,
+                    "Use ConfigLoader to load the config.",
+                    None
+                ),
+            ]
+        );
+        assert_eq!(
+            index.search("missing term", Path::new(""), |_| false, 3),
+            Vec::new()
+        );
+    }
+}
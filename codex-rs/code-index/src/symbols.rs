@@ -0,0 +1,195 @@
+use serde::Deserialize;
+use serde::Serialize;
+use tree_sitter::Node;
+use tree_sitter::Parser;
+use tree_sitter::QueryCursor;
+use tree_sitter::StreamingIterator;
+use tree_sitter::Tree;
+
+use crate::language::Language;
+
+/// A definition found by the language's tags query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Symbol {
+    pub name: String,
+    /// The tag kind without its `definition.` prefix, e.g. `function`,
+    /// `method`, `class` or `interface`.
+    pub kind: String,
+    /// 1-based line of the symbol's name.
+    pub line: usize,
+}
+
+fn parse(language: Language, source: &str) -> Option<Tree> {
+    let mut parser = Parser::new();
+    parser.set_language(&language.grammar()).ok()?;
+    parser.parse(source, None)
+}
+
+/// Extracts the definitions in `source`, in source order.
+pub(crate) fn extract_symbols(language: Language, source: &str) -> Vec<Symbol> {
+    let Some(query) = language.tags_query() else {
+        return Vec::new();
+    };
+    let Some(tree) = parse(language, source) else {
+        return Vec::new();
+    };
+    let capture_names = query.capture_names();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.matches(query, tree.root_node(), source.as_bytes());
+    let mut symbols = Vec::new();
+    while let Some(query_match) = matches.next() {
+        let mut name = None;
+        let mut kind = None;
+        for capture in query_match.captures {
+            let capture_name = capture_names[capture.index as usize];
+            if capture_name == "name" {
+                name = Some(capture.node);
+            } else if let Some(definition) = capture_name.strip_prefix("definition.") {
+                kind = Some(definition);
+            }
+        }
+        let (Some(name), Some(kind)) = (name, kind) else {
+            continue;
+        };
+        let Ok(text) = name.utf8_text(source.as_bytes()) else {
+            continue;
+        };
+        symbols.push(Symbol {
+            name: text.to_string(),
+            kind: kind.to_string(),
+            line: name.start_position().row + 1,
+        });
+    }
+    // A node can match several patterns (a method is also a function); keep
+    // the first, most specific, kind.
+    symbols.sort_by_key(|symbol| symbol.line);
+    symbols.dedup_by(|next, previous| next.line == previous.line && next.name == previous.name);
+    symbols
+}
+
+/// Returns the 1-based lines where `name` appears as an identifier. Comments
+/// and string contents are not identifiers, so mentions there are skipped.
+pub(crate) fn identifier_lines(language: Language, source: &str, name: &str) -> Vec<usize> {
+    let Some(tree) = parse(language, source) else {
+        return Vec::new();
+    };
+    let mut lines = Vec::new();
+    let mut cursor = tree.walk();
+    let mut visit = |node: Node| {
+        if node.child_count() == 0
+            && node.kind().ends_with("identifier")
+            && node.utf8_text(source.as_bytes()).ok() == Some(name)
+        {
+            let line = node.start_position().row + 1;
+            if lines.last() != Some(&line) {
+                lines.push(line);
+            }
+        }
+    };
+    // Pre-order walk without recursion so deeply nested files cannot
+    // overflow the stack.
+    loop {
+        visit(cursor.node());
+        if cursor.goto_first_child() || cursor.goto_next_sibling() {
+            continue;
+        }
+        loop {
+            if !cursor.goto_parent() {
+                return lines;
+            }
+            if cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn symbol(name: &str, kind: &str, line: usize) -> Symbol {
+        Symbol {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            line,
+        }
+    }
+
+    #[test]
+    fn extracts_rust_definitions() {
+        let source = "\
+const LIMIT: usize = 3;
+
+pub struct Index;
+
+trait Search {
+    fn search(&self);
+}
+
+impl Search for Index {
+    fn search(&self) {
+        helper();
+    }
+}
+
+fn helper() {}
+";
+        assert_eq!(
+            extract_symbols(Language::Rust, source),
+            vec![
+                symbol("LIMIT", "constant", 1),
+                symbol("Index", "class", 3),
+                symbol("Search", "interface", 5),
+                symbol("search", "method", 10),
+                symbol("helper", "function", 15),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_typescript_definitions() {
+        let source = "\
+export interface Store {
+  get(key: string): string;
+}
+
+export class MemoryStore implements Store {
+  get(key: string): string {
+    return key;
+  }
+}
+
+export function createStore(): Store {
+  return new MemoryStore();
+}
+";
+        assert_eq!(
+            extract_symbols(Language::TypeScript, source),
+            vec![
+                symbol("Store", "interface", 1),
+                symbol("get", "method", 2),
+                symbol("MemoryStore", "class", 5),
+                symbol("get", "method", 6),
+                symbol("createStore", "function", 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn identifier_lines_skip_comments_and_strings() {
+        let source = "\
+def load(path):
+    # load is documented here
+    return open(path)
+
+print(\"load\")
+value = load(\"config\")
+";
+        assert_eq!(
+            identifier_lines(Language::Python, source, "load"),
+            vec![1, 6]
+        );
+    }
+}
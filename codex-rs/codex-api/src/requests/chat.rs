@@ -0,0 +1,604 @@
+use crate::common::ChatCompletionsApiRequest;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use serde_json::Map;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::HashSet;
+use tracing::debug;
+
+/// Name of the single string argument used to expose freeform (`custom`)
+/// tools as Chat Completions function tools.
+pub(crate) const CUSTOM_TOOL_INPUT_ARGUMENT: &str = "input";
+
+/// Tool name used when replaying `local_shell_call` items as Chat Completions
+/// tool calls.
+const LOCAL_SHELL_TOOL_NAME: &str = "local_shell";
+
+/// Chat Completions request body plus the bookkeeping needed to translate the
+/// streamed response back into `ResponseItem`s.
+pub(crate) struct ChatRequestBody {
+    pub(crate) body: Value,
+    /// Names of freeform tools that were exposed as function tools. Calls to
+    /// these are reported as `CustomToolCall`s instead of `FunctionCall`s.
+    pub(crate) custom_tools: HashSet<String>,
+}
+
+pub(crate) fn build_chat_request_body(request: &ChatCompletionsApiRequest) -> ChatRequestBody {
+    let (tools, custom_tools) = chat_tools_from_responses_tools(&request.tools);
+
+    let mut body = Map::new();
+    body.insert("model".to_string(), Value::String(request.model.clone()));
+    body.insert(
+        "messages".to_string(),
+        Value::Array(build_chat_messages(&request.instructions, &request.input)),
+    );
+    body.insert("stream".to_string(), Value::Bool(true));
+    body.insert(
+        "stream_options".to_string(),
+        json!({ "include_usage": true }),
+    );
+    if !tools.is_empty() {
+        body.insert("tools".to_string(), Value::Array(tools));
+        body.insert("tool_choice".to_string(), Value::String("auto".to_string()));
+        body.insert(
+            "parallel_tool_calls".to_string(),
+            Value::Bool(request.parallel_tool_calls),
+        );
+    }
+    if let Some(effort) = request.reasoning_effort
+        && let Ok(effort) = serde_json::to_value(effort)
+    {
+        body.insert("reasoning_effort".to_string(), effort);
+    }
+    if let Some(max_output_tokens) = request.max_output_tokens {
+        body.insert("max_tokens".to_string(), json!(max_output_tokens));
+    }
+    if let Some(schema) = &request.output_schema {
+        body.insert(
+            "response_format".to_string(),
+            json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": "codex_output_schema",
+                    "strict": true,
+                    "schema": schema,
+                },
+            }),
+        );
+    }
+
+    ChatRequestBody {
+        body: Value::Object(body),
+        custom_tools,
+    }
+}
+
+/// Converts Responses API tool definitions into Chat Completions tools.
+///
+/// Function tools keep their schema, freeform tools become functions taking a
+/// single `input` string, and hosted tools (web search, image generation,
+/// local shell) are dropped because Chat Completions cannot express them.
+fn chat_tools_from_responses_tools(tools: &[Value]) -> (Vec<Value>, HashSet<String>) {
+    let mut chat_tools = Vec::with_capacity(tools.len());
+    let mut custom_tools = HashSet::new();
+
+    for tool in tools {
+        let Some(tool) = tool.as_object() else {
+            continue;
+        };
+        match tool.get("type").and_then(Value::as_str) {
+            Some("function") => {
+                let mut function = tool.clone();
+                function.remove("type");
+                chat_tools.push(json!({ "type": "function", "function": function }));
+            }
+            Some("custom") => {
+                let Some(name) = tool.get("name").and_then(Value::as_str) else {
+                    continue;
+                };
+                let description = tool
+                    .get("description")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                chat_tools.push(json!({
+                    "type": "function",
+                    "function": {
+                        "name": name,
+                        "description": description,
+                        "parameters": {
+                            "type": "object",
+                            "properties": {
+                                CUSTOM_TOOL_INPUT_ARGUMENT: {
+                                    "type": "string",
+                                    "description": "Raw tool input.",
+                                },
+                            },
+                            "required": [CUSTOM_TOOL_INPUT_ARGUMENT],
+                            "additionalProperties": false,
+                        },
+                    },
+                }));
+                custom_tools.insert(name.to_string());
+            }
+            other => {
+                debug!("dropping tool type {other:?} unsupported by chat completions");
+            }
+        }
+    }
+
+    (chat_tools, custom_tools)
+}
+
+/// Converts the conversation history into Chat Completions `messages`.
+pub(crate) fn build_chat_messages(instructions: &str, input: &[ResponseItem]) -> Vec<Value> {
+    let mut builder = ChatMessagesBuilder::default();
+    if !instructions.is_empty() {
+        builder.push_message(json!({ "role": "system", "content": instructions }));
+    }
+
+    for item in input {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                if role == "assistant" {
+                    let text = content
+                        .iter()
+                        .filter_map(|item| match item {
+                            ContentItem::OutputText { text } | ContentItem::InputText { text } => {
+                                Some(text.as_str())
+                            }
+                            ContentItem::InputImage { .. } => None,
+                        })
+                        .collect::<String>();
+                    let mut message = json!({ "role": "assistant", "content": text });
+                    builder.attach_pending_reasoning(&mut message);
+                    builder.push_message(message);
+                } else {
+                    let role = match role.as_str() {
+                        "developer" | "system" => "system",
+                        _ => "user",
+                    };
+                    builder.push_message(json!({
+                        "role": role,
+                        "content": user_content(content),
+                    }));
+                }
+            }
+            ResponseItem::Reasoning {
+                summary, content, ..
+            } => {
+                builder.push_reasoning(&reasoning_text(summary, content.as_deref()));
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                builder.push_tool_call(call_id, name, arguments.clone());
+            }
+            ResponseItem::CustomToolCall {
+                call_id,
+                name,
+                input,
+                ..
+            } => {
+                let arguments = json!({ CUSTOM_TOOL_INPUT_ARGUMENT: input }).to_string();
+                builder.push_tool_call(call_id, name, arguments);
+            }
+            ResponseItem::LocalShellCall {
+                id,
+                call_id,
+                action,
+                ..
+            } => {
+                let Some(call_id) = call_id.as_ref().or(id.as_ref()) else {
+                    continue;
+                };
+                let LocalShellAction::Exec(exec) = action;
+                let arguments = serde_json::to_string(exec).unwrap_or_default();
+                builder.push_tool_call(call_id, LOCAL_SHELL_TOOL_NAME, arguments);
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput { call_id, output } => {
+                builder.push_tool_output(call_id, output);
+            }
+            ResponseItem::WebSearchCall { .. }
+            | ResponseItem::ImageGenerationCall { .. }
+            | ResponseItem::GhostSnapshot { .. }
+            | ResponseItem::Compaction { .. }
+            | ResponseItem::Other => {}
+        }
+    }
+
+    builder.finish()
+}
+
+#[derive(Default)]
+struct ChatMessagesBuilder {
+    messages: Vec<Value>,
+    /// Reasoning text waiting to be attached to the next assistant message.
+    pending_reasoning: Option<String>,
+    /// Images returned by tool calls. Chat Completions only accepts images in
+    /// user messages, so they are sent right after the run of tool messages.
+    pending_images: Vec<Value>,
+}
+
+impl ChatMessagesBuilder {
+    fn push_message(&mut self, message: Value) {
+        self.flush_pending_images();
+        self.messages.push(message);
+    }
+
+    fn push_reasoning(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        match self.pending_reasoning.as_mut() {
+            Some(pending) => {
+                pending.push('\n');
+                pending.push_str(text);
+            }
+            None => self.pending_reasoning = Some(text.to_string()),
+        }
+    }
+
+    fn attach_pending_reasoning(&mut self, message: &mut Value) {
+        if let Some(reasoning) = self.pending_reasoning.take()
+            && let Some(message) = message.as_object_mut()
+        {
+            message.insert("reasoning_content".to_string(), Value::String(reasoning));
+        }
+    }
+
+    fn push_tool_call(&mut self, call_id: &str, name: &str, arguments: String) {
+        let tool_call = json!({
+            "id": call_id,
+            "type": "function",
+            "function": {
+                "name": name,
+                "arguments": arguments,
+            },
+        });
+
+        // Parallel tool calls, and tool calls that follow assistant text, belong
+        // to the same assistant message.
+        if self.pending_images.is_empty()
+            && let Some(last) = self.messages.last_mut()
+            && last.get("role").and_then(Value::as_str) == Some("assistant")
+            && let Some(last) = last.as_object_mut()
+        {
+            match last.get_mut("tool_calls").and_then(Value::as_array_mut) {
+                Some(tool_calls) => tool_calls.push(tool_call),
+                None => {
+                    last.insert("tool_calls".to_string(), Value::Array(vec![tool_call]));
+                }
+            }
+            return;
+        }
+
+        let mut message = json!({
+            "role": "assistant",
+            "content": Value::Null,
+            "tool_calls": [tool_call],
+        });
+        self.attach_pending_reasoning(&mut message);
+        self.push_message(message);
+    }
+
+    fn push_tool_output(&mut self, call_id: &str, output: &FunctionCallOutputPayload) {
+        let content = match &output.body {
+            FunctionCallOutputBody::Text(text) => text.clone(),
+            FunctionCallOutputBody::ContentItems(items) => {
+                let mut texts = Vec::new();
+                for item in items {
+                    match item {
+                        FunctionCallOutputContentItem::InputText { text } => {
+                            texts.push(text.as_str());
+                        }
+                        FunctionCallOutputContentItem::InputImage { image_url, .. } => {
+                            self.pending_images.push(image_part(image_url));
+                        }
+                    }
+                }
+                texts.join("\n")
+            }
+        };
+
+        self.messages.push(json!({
+            "role": "tool",
+            "tool_call_id": call_id,
+            "content": content,
+        }));
+    }
+
+    fn flush_pending_images(&mut self) {
+        if self.pending_images.is_empty() {
+            return;
+        }
+        let images = std::mem::take(&mut self.pending_images);
+        self.messages
+            .push(json!({ "role": "user", "content": images }));
+    }
+
+    fn finish(mut self) -> Vec<Value> {
+        self.flush_pending_images();
+        self.messages
+    }
+}
+
+/// Uses a plain string for text-only content, which every Chat Completions
+/// server accepts, and content parts when images are present.
+fn user_content(content: &[ContentItem]) -> Value {
+    let has_images = content
+        .iter()
+        .any(|item| matches!(item, ContentItem::InputImage { .. }));
+    if !has_images {
+        let texts = content
+            .iter()
+            .filter_map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    Some(text.as_str())
+                }
+                ContentItem::InputImage { .. } => None,
+            })
+            .collect::<Vec<_>>();
+        return Value::String(texts.join("\n"));
+    }
+
+    Value::Array(
+        content
+            .iter()
+            .map(|item| match item {
+                ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+                    json!({ "type": "text", "text": text })
+                }
+                ContentItem::InputImage { image_url } => image_part(image_url),
+            })
+            .collect(),
+    )
+}
+
+fn image_part(image_url: &str) -> Value {
+    json!({ "type": "image_url", "image_url": { "url": image_url } })
+}
+
+/// Prefers raw reasoning content (what chat servers stream back) and falls
+/// back to the summaries.
+fn reasoning_text(
+    summary: &[ReasoningItemReasoningSummary],
+    content: Option<&[ReasoningItemContent]>,
+) -> String {
+    if let Some(content) = content
+        && !content.is_empty()
+    {
+        return content
+            .iter()
+            .map(|item| match item {
+                ReasoningItemContent::ReasoningText { text }
+                | ReasoningItemContent::Text { text } => text.as_str(),
+            })
+            .collect();
+    }
+
+    summary
+        .iter()
+        .map(|item| match item {
+            ReasoningItemReasoningSummary::SummaryText { text } => text.as_str(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ImageDetail;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    fn function_call(call_id: &str, name: &str, arguments: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            call_id: call_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn groups_parallel_tool_calls_and_attaches_reasoning() {
+        let input = vec![
+            user_message("list files"),
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: Vec::new(),
+                content: Some(vec![ReasoningItemContent::ReasoningText {
+                    text: "need ls".to_string(),
+                }]),
+                encrypted_content: None,
+            },
+            function_call("call_a", "shell", r#"{"command":["ls"]}"#),
+            function_call("call_b", "shell", r#"{"command":["pwd"]}"#),
+            ResponseItem::FunctionCallOutput {
+                call_id: "call_a".to_string(),
+                output: FunctionCallOutputPayload::from_text("a.txt".to_string()),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call_b".to_string(),
+                output: FunctionCallOutputPayload::from_text("/tmp".to_string()),
+            },
+        ];
+
+        let messages = build_chat_messages("be terse", &input);
+
+        assert_eq!(
+            messages,
+            vec![
+                json!({ "role": "system", "content": "be terse" }),
+                json!({ "role": "user", "content": "list files" }),
+                json!({
+                    "role": "assistant",
+                    "content": null,
+                    "reasoning_content": "need ls",
+                    "tool_calls": [
+                        {
+                            "id": "call_a",
+                            "type": "function",
+                            "function": { "name": "shell", "arguments": r#"{"command":["ls"]}"# },
+                        },
+                        {
+                            "id": "call_b",
+                            "type": "function",
+                            "function": { "name": "shell", "arguments": r#"{"command":["pwd"]}"# },
+                        },
+                    ],
+                }),
+                json!({ "role": "tool", "tool_call_id": "call_a", "content": "a.txt" }),
+                json!({ "role": "tool", "tool_call_id": "call_b", "content": "/tmp" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn tool_output_images_follow_tool_messages_as_user_content() {
+        let input = vec![
+            ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call_1".to_string(),
+                name: "view".to_string(),
+                input: "shot.png".to_string(),
+            },
+            ResponseItem::CustomToolCallOutput {
+                call_id: "call_1".to_string(),
+                output: FunctionCallOutputPayload {
+                    body: FunctionCallOutputBody::ContentItems(vec![
+                        FunctionCallOutputContentItem::InputText {
+                            text: "attached".to_string(),
+                        },
+                        FunctionCallOutputContentItem::InputImage {
+                            image_url: "data:image/png;base64,AAAA".to_string(),
+                            detail: Some(ImageDetail::High),
+                        },
+                    ]),
+                    success: Some(true),
+                },
+            },
+            user_message("thanks"),
+        ];
+
+        let messages = build_chat_messages("", &input);
+
+        assert_eq!(
+            messages,
+            vec![
+                json!({
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": { "name": "view", "arguments": r#"{"input":"shot.png"}"# },
+                    }],
+                }),
+                json!({ "role": "tool", "tool_call_id": "call_1", "content": "attached" }),
+                json!({
+                    "role": "user",
+                    "content": [{
+                        "type": "image_url",
+                        "image_url": { "url": "data:image/png;base64,AAAA" },
+                    }],
+                }),
+                json!({ "role": "user", "content": "thanks" }),
+            ]
+        );
+    }
+
+    #[test]
+    fn translates_function_and_custom_tools() {
+        let request = ChatCompletionsApiRequest {
+            model: "local-model".to_string(),
+            instructions: String::new(),
+            input: Vec::new(),
+            tools: vec![
+                json!({
+                    "type": "function",
+                    "name": "shell",
+                    "description": "Runs a command",
+                    "strict": false,
+                    "parameters": { "type": "object", "properties": {} },
+                }),
+                json!({
+                    "type": "custom",
+                    "name": "apply_patch",
+                    "description": "Edits files",
+                    "format": { "type": "grammar", "syntax": "lark", "definition": "start: /.+/" },
+                }),
+                json!({ "type": "web_search" }),
+            ],
+            parallel_tool_calls: true,
+            reasoning_effort: None,
+            output_schema: None,
+            max_output_tokens: None,
+        };
+
+        let ChatRequestBody { body, custom_tools } = build_chat_request_body(&request);
+
+        assert_eq!(custom_tools, HashSet::from(["apply_patch".to_string()]));
+        assert_eq!(
+            body,
+            json!({
+                "model": "local-model",
+                "messages": [],
+                "stream": true,
+                "stream_options": { "include_usage": true },
+                "tools": [
+                    {
+                        "type": "function",
+                        "function": {
+                            "name": "shell",
+                            "description": "Runs a command",
+                            "strict": false,
+                            "parameters": { "type": "object", "properties": {} },
+                        },
+                    },
+                    {
+                        "type": "function",
+                        "function": {
+                            "name": "apply_patch",
+                            "description": "Edits files",
+                            "parameters": {
+                                "type": "object",
+                                "properties": {
+                                    "input": { "type": "string", "description": "Raw tool input." },
+                                },
+                                "required": ["input"],
+                                "additionalProperties": false,
+                            },
+                        },
+                    },
+                ],
+                "tool_choice": "auto",
+                "parallel_tool_calls": true,
+            })
+        );
+    }
+}
@@ -1,2 +1,3 @@
+pub(crate) mod chat;
 pub(crate) mod headers;
 pub mod responses;
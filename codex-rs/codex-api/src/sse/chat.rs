@@ -0,0 +1,786 @@
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::rate_limits::parse_all_rate_limits;
+use crate::requests::chat::CUSTOM_TOOL_INPUT_ARGUMENT;
+use crate::sse::responses::Error;
+use crate::sse::responses::api_error_from_stream_error;
+use crate::telemetry::SseTelemetry;
+use codex_client::ByteStream;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemContent;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+const DONE_SENTINEL: &str = "[DONE]";
+
+/// Spawns a task that translates a Chat Completions SSE stream into
+/// [`ResponseEvent`]s.
+pub fn spawn_chat_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    custom_tools: HashSet<String>,
+) -> ResponseStream {
+    let rate_limit_snapshots = parse_all_rate_limits(&stream_response.headers);
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        for snapshot in rate_limit_snapshots {
+            let _ = tx_event.send(Ok(ResponseEvent::RateLimits(snapshot))).await;
+        }
+        process_chat_sse(
+            stream_response.bytes,
+            tx_event,
+            idle_timeout,
+            telemetry,
+            custom_tools,
+        )
+        .await;
+    });
+
+    ResponseStream { rx_event }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    id: Option<String>,
+    #[serde(default)]
+    choices: Vec<ChatChoice>,
+    usage: Option<ChatUsage>,
+    error: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    delta: Option<ChatDelta>,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatDelta {
+    content: Option<String>,
+    /// vLLM, llama.cpp and DeepSeek-style servers stream reasoning here.
+    reasoning_content: Option<String>,
+    /// OpenRouter-style servers stream reasoning here.
+    reasoning: Option<String>,
+    tool_calls: Option<Vec<ChatToolCallDelta>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatToolCallDelta {
+    index: Option<usize>,
+    id: Option<String>,
+    function: Option<ChatFunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatFunctionDelta {
+    name: Option<String>,
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatUsage {
+    prompt_tokens: i64,
+    completion_tokens: i64,
+    total_tokens: i64,
+    prompt_tokens_details: Option<ChatPromptTokensDetails>,
+    completion_tokens_details: Option<ChatCompletionTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatPromptTokensDetails {
+    cached_tokens: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionTokensDetails {
+    reasoning_tokens: Option<i64>,
+}
+
+impl From<ChatUsage> for TokenUsage {
+    fn from(val: ChatUsage) -> Self {
+        TokenUsage {
+            input_tokens: val.prompt_tokens,
+            cached_input_tokens: val
+                .prompt_tokens_details
+                .and_then(|d| d.cached_tokens)
+                .unwrap_or(0),
+            output_tokens: val.completion_tokens,
+            reasoning_output_tokens: val
+                .completion_tokens_details
+                .and_then(|d| d.reasoning_tokens)
+                .unwrap_or(0),
+            total_tokens: val.total_tokens,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PendingToolCall {
+    id: Option<String>,
+    name: String,
+    arguments: String,
+}
+
+/// Accumulates streamed chat deltas into complete `ResponseItem`s.
+///
+/// Chat Completions streams a single assistant message whose reasoning, text
+/// and tool calls are interleaved as deltas. They are surfaced as separate
+/// items in the order reasoning, message, tool calls so that the rest of the
+/// pipeline can treat them exactly like Responses API output.
+#[derive(Debug, Default)]
+struct ChatStreamState {
+    custom_tools: HashSet<String>,
+    response_id: Option<String>,
+    created: bool,
+    reasoning: Option<String>,
+    message: Option<String>,
+    message_started: bool,
+    tool_calls: BTreeMap<usize, PendingToolCall>,
+    finish_reason: Option<String>,
+    token_usage: Option<TokenUsage>,
+}
+
+impl ChatStreamState {
+    fn new(custom_tools: HashSet<String>) -> Self {
+        Self {
+            custom_tools,
+            ..Default::default()
+        }
+    }
+
+    fn process_chunk(
+        &mut self,
+        chunk: ChatCompletionChunk,
+    ) -> Result<Vec<ResponseEvent>, ApiError> {
+        if let Some(error) = chunk.error {
+            return Err(chat_stream_error(error));
+        }
+
+        let mut events = Vec::new();
+        if !self.created {
+            self.created = true;
+            events.push(ResponseEvent::Created);
+        }
+        if self.response_id.is_none() {
+            self.response_id = chunk.id;
+        }
+        if let Some(usage) = chunk.usage {
+            self.token_usage = Some(usage.into());
+        }
+
+        // Servers only ever produce a single choice because we never ask for `n > 1`.
+        let Some(choice) = chunk.choices.into_iter().next() else {
+            return Ok(events);
+        };
+
+        if let Some(delta) = choice.delta {
+            if let Some(reasoning) = delta.reasoning_content.or(delta.reasoning)
+                && !reasoning.is_empty()
+            {
+                self.push_reasoning_delta(reasoning, &mut events);
+            }
+            if let Some(content) = delta.content
+                && !content.is_empty()
+            {
+                self.push_content_delta(content, &mut events);
+            }
+            for tool_call in delta.tool_calls.unwrap_or_default() {
+                self.push_tool_call_delta(tool_call, &mut events);
+            }
+        }
+
+        if let Some(finish_reason) = choice.finish_reason {
+            self.finish_reason = Some(finish_reason);
+        }
+
+        Ok(events)
+    }
+
+    fn push_reasoning_delta(&mut self, delta: String, events: &mut Vec<ResponseEvent>) {
+        if self.message_started || !self.tool_calls.is_empty() {
+            // Reasoning that arrives after the answer started cannot be
+            // represented as a separate item without reordering the output.
+            trace!("dropping late chat reasoning delta");
+            return;
+        }
+
+        match self.reasoning.as_mut() {
+            Some(reasoning) => reasoning.push_str(&delta),
+            None => {
+                events.push(ResponseEvent::OutputItemAdded(
+                    reasoning_item(String::new()),
+                ));
+                self.reasoning = Some(delta.clone());
+            }
+        }
+        events.push(ResponseEvent::ReasoningContentDelta {
+            delta,
+            content_index: 0,
+        });
+    }
+
+    fn push_content_delta(&mut self, delta: String, events: &mut Vec<ResponseEvent>) {
+        self.close_reasoning(events);
+        match self.message.as_mut() {
+            Some(message) => message.push_str(&delta),
+            None => {
+                events.push(ResponseEvent::OutputItemAdded(message_item(String::new())));
+                self.message = Some(delta.clone());
+                self.message_started = true;
+            }
+        }
+        events.push(ResponseEvent::OutputTextDelta(delta));
+    }
+
+    fn push_tool_call_delta(&mut self, delta: ChatToolCallDelta, events: &mut Vec<ResponseEvent>) {
+        self.close_reasoning(events);
+        let index = match delta.index {
+            Some(index) => index,
+            // Some servers omit `index`. Such deltas continue the last call
+            // unless they carry the id of a new one.
+            None => match self.tool_calls.last_key_value() {
+                Some((&last, call))
+                    if delta.id.as_deref().is_some_and(|id| {
+                        !id.is_empty() && call.id.as_deref().is_some_and(|open| open != id)
+                    }) =>
+                {
+                    last + 1
+                }
+                Some((&last, _)) => last,
+                None => 0,
+            },
+        };
+        let pending = self.tool_calls.entry(index).or_default();
+        if let Some(id) = delta.id
+            && !id.is_empty()
+        {
+            pending.id = Some(id);
+        }
+        if let Some(function) = delta.function {
+            if let Some(name) = function.name {
+                pending.name.push_str(&name);
+            }
+            if let Some(arguments) = function.arguments {
+                pending.arguments.push_str(&arguments);
+            }
+        }
+    }
+
+    fn close_reasoning(&mut self, events: &mut Vec<ResponseEvent>) {
+        if let Some(reasoning) = self.reasoning.take() {
+            events.push(ResponseEvent::OutputItemDone(reasoning_item(reasoning)));
+        }
+    }
+
+    /// Emits the remaining items followed by `Completed`.
+    fn complete(mut self) -> Result<Vec<ResponseEvent>, ApiError> {
+        if let Some(reason @ ("length" | "content_filter")) = self.finish_reason.as_deref() {
+            return Err(ApiError::Stream(format!(
+                "Incomplete response returned, reason: {reason}"
+            )));
+        }
+
+        let mut events = Vec::new();
+        self.close_reasoning(&mut events);
+        if let Some(message) = self.message.take() {
+            events.push(ResponseEvent::OutputItemDone(message_item(message)));
+        }
+
+        let response_id = self.response_id.unwrap_or_default();
+        for (index, tool_call) in std::mem::take(&mut self.tool_calls) {
+            if tool_call.name.is_empty() {
+                debug!("dropping chat tool call {index} without a name");
+                continue;
+            }
+            let call_id = tool_call
+                .id
+                .unwrap_or_else(|| format!("call_{response_id}_{index}"));
+            let item = if self.custom_tools.contains(&tool_call.name) {
+                ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id,
+                    input: custom_tool_input(tool_call.arguments),
+                    name: tool_call.name,
+                }
+            } else {
+                ResponseItem::FunctionCall {
+                    id: None,
+                    name: tool_call.name,
+                    arguments: tool_call.arguments,
+                    call_id,
+                }
+            };
+            events.push(ResponseEvent::OutputItemDone(item));
+        }
+
+        events.push(ResponseEvent::Completed {
+            response_id,
+            token_usage: self.token_usage,
+        });
+        Ok(events)
+    }
+}
+
+fn reasoning_item(text: String) -> ResponseItem {
+    ResponseItem::Reasoning {
+        id: String::new(),
+        summary: Vec::new(),
+        content: Some(if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![ReasoningItemContent::ReasoningText { text }]
+        }),
+        encrypted_content: None,
+    }
+}
+
+fn message_item(text: String) -> ResponseItem {
+    ResponseItem::Message {
+        id: None,
+        role: "assistant".to_string(),
+        content: vec![ContentItem::OutputText { text }],
+        end_turn: None,
+        phase: None,
+    }
+}
+
+/// Unwraps the `input` argument used to expose freeform tools as functions.
+/// Falls back to the raw arguments when the model did not produce the wrapper.
+fn custom_tool_input(arguments: String) -> String {
+    match serde_json::from_str::<Value>(&arguments) {
+        Ok(Value::Object(mut object)) => match object.remove(CUSTOM_TOOL_INPUT_ARGUMENT) {
+            Some(Value::String(input)) => input,
+            _ => arguments,
+        },
+        _ => arguments,
+    }
+}
+
+fn chat_stream_error(error: Value) -> ApiError {
+    match serde_json::from_value::<Error>(error.clone()) {
+        Ok(error) => api_error_from_stream_error(error),
+        Err(_) => ApiError::Stream(format!("chat completions stream error: {error}")),
+    }
+}
+
+pub async fn process_chat_sse(
+    stream: ByteStream,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<Arc<dyn SseTelemetry>>,
+    custom_tools: HashSet<String>,
+) {
+    let mut stream = stream.eventsource();
+    let mut state = ChatStreamState::new(custom_tools);
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                debug!("SSE Error: {e:#}");
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                // Some servers close the stream right after the final chunk
+                // without sending `[DONE]`.
+                if state.finish_reason.is_some() {
+                    send_completion(state, &tx_event).await;
+                } else {
+                    let _ = tx_event
+                        .send(Err(ApiError::Stream(
+                            "stream closed before chat completion finished".into(),
+                        )))
+                        .await;
+                }
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", &sse.data);
+
+        let data = sse.data.trim();
+        if data == DONE_SENTINEL {
+            send_completion(state, &tx_event).await;
+            return;
+        }
+
+        let chunk: ChatCompletionChunk = match serde_json::from_str(data) {
+            Ok(chunk) => chunk,
+            Err(e) => {
+                debug!("Failed to parse chat SSE event: {e}, data: {data}");
+                continue;
+            }
+        };
+
+        match state.process_chunk(chunk) {
+            Ok(events) => {
+                for event in events {
+                    if tx_event.send(Ok(event)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let _ = tx_event.send(Err(error)).await;
+                return;
+            }
+        }
+    }
+}
+
+async fn send_completion(
+    state: ChatStreamState,
+    tx_event: &mpsc::Sender<Result<ResponseEvent, ApiError>>,
+) {
+    match state.complete() {
+        Ok(events) => {
+            for event in events {
+                if tx_event.send(Ok(event)).await.is_err() {
+                    return;
+                }
+            }
+        }
+        Err(error) => {
+            let _ = tx_event.send(Err(error)).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use codex_client::TransportError;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio_util::io::ReaderStream;
+
+    async fn run_chat_sse(chunks: Vec<Value>, done: bool) -> Vec<Result<ResponseEvent, ApiError>> {
+        let mut body = String::new();
+        for chunk in chunks {
+            body.push_str(&format!("data: {chunk}\n\n"));
+        }
+        if done {
+            body.push_str("data: [DONE]\n\n");
+        }
+
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(64);
+        let stream = ReaderStream::new(std::io::Cursor::new(body))
+            .map_err(|err| TransportError::Network(err.to_string()));
+        let custom_tools = HashSet::from(["apply_patch".to_string()]);
+        tokio::spawn(process_chat_sse(
+            Box::pin(stream),
+            tx,
+            Duration::from_millis(1000),
+            None,
+            custom_tools,
+        ));
+
+        let mut out = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            out.push(ev);
+        }
+        out
+    }
+
+    fn delta_chunk(delta: Value) -> Value {
+        json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "choices": [{ "index": 0, "delta": delta, "finish_reason": null }],
+        })
+    }
+
+    fn finish_chunk(reason: &str) -> Value {
+        json!({
+            "id": "chatcmpl-1",
+            "object": "chat.completion.chunk",
+            "choices": [{ "index": 0, "delta": {}, "finish_reason": reason }],
+        })
+    }
+
+    #[tokio::test]
+    async fn streams_reasoning_then_text_with_usage() {
+        let events = run_chat_sse(
+            vec![
+                delta_chunk(json!({ "role": "assistant", "reasoning_content": "Think" })),
+                delta_chunk(json!({ "reasoning_content": "ing" })),
+                delta_chunk(json!({ "content": "Hel" })),
+                delta_chunk(json!({ "content": "lo" })),
+                finish_chunk("stop"),
+                json!({
+                    "id": "chatcmpl-1",
+                    "choices": [],
+                    "usage": {
+                        "prompt_tokens": 10,
+                        "completion_tokens": 5,
+                        "total_tokens": 15,
+                        "prompt_tokens_details": { "cached_tokens": 4 },
+                        "completion_tokens_details": { "reasoning_tokens": 2 },
+                    },
+                }),
+            ],
+            true,
+        )
+        .await;
+        let events = events
+            .into_iter()
+            .map(|event| event.expect("unexpected stream error"))
+            .collect::<Vec<_>>();
+
+        assert_matches!(events[0], ResponseEvent::Created);
+        assert_matches!(
+            &events[1],
+            ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { .. })
+        );
+        assert_matches!(
+            &events[2],
+            ResponseEvent::ReasoningContentDelta { delta, content_index: 0 } if delta == "Think"
+        );
+        assert_matches!(
+            &events[3],
+            ResponseEvent::ReasoningContentDelta { delta, .. } if delta == "ing"
+        );
+        match &events[4] {
+            ResponseEvent::OutputItemDone(ResponseItem::Reasoning { content, .. }) => {
+                assert_eq!(
+                    content,
+                    &Some(vec![ReasoningItemContent::ReasoningText {
+                        text: "Thinking".to_string(),
+                    }])
+                );
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        assert_matches!(
+            &events[5],
+            ResponseEvent::OutputItemAdded(ResponseItem::Message { role, .. }) if role == "assistant"
+        );
+        assert_matches!(&events[6], ResponseEvent::OutputTextDelta(delta) if delta == "Hel");
+        assert_matches!(&events[7], ResponseEvent::OutputTextDelta(delta) if delta == "lo");
+        match &events[8] {
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. }) => {
+                assert_eq!(
+                    content,
+                    &vec![ContentItem::OutputText {
+                        text: "Hello".to_string(),
+                    }]
+                );
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        match &events[9] {
+            ResponseEvent::Completed {
+                response_id,
+                token_usage,
+            } => {
+                assert_eq!(response_id, "chatcmpl-1");
+                assert_eq!(
+                    token_usage,
+                    &Some(TokenUsage {
+                        input_tokens: 10,
+                        cached_input_tokens: 4,
+                        output_tokens: 5,
+                        reasoning_output_tokens: 2,
+                        total_tokens: 15,
+                    })
+                );
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        assert_eq!(events.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn accumulates_streamed_tool_call_deltas() {
+        let events = run_chat_sse(
+            vec![
+                delta_chunk(json!({
+                    "tool_calls": [{
+                        "index": 0,
+                        "id": "call_shell",
+                        "type": "function",
+                        "function": { "name": "shell", "arguments": "" },
+                    }],
+                })),
+                delta_chunk(json!({
+                    "tool_calls": [{ "index": 0, "function": { "arguments": "{\"command\":" } }],
+                })),
+                delta_chunk(json!({
+                    "tool_calls": [
+                        { "index": 0, "function": { "arguments": "[\"ls\"]}" } },
+                        {
+                            "index": 1,
+                            "id": "call_patch",
+                            "type": "function",
+                            "function": {
+                                "name": "apply_patch",
+                                "arguments": "{\"input\":\"*** Begin Patch\"}",
+                            },
+                        },
+                    ],
+                })),
+                finish_chunk("tool_calls"),
+            ],
+            false,
+        )
+        .await;
+        let events = events
+            .into_iter()
+            .map(|event| event.expect("unexpected stream error"))
+            .filter(|event| matches!(event, ResponseEvent::OutputItemDone(_)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            }) => {
+                assert_eq!(name, "shell");
+                assert_eq!(arguments, r#"{"command":["ls"]}"#);
+                assert_eq!(call_id, "call_shell");
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+        match &events[1] {
+            ResponseEvent::OutputItemDone(ResponseItem::CustomToolCall {
+                name,
+                input,
+                call_id,
+                ..
+            }) => {
+                assert_eq!(name, "apply_patch");
+                assert_eq!(input, "*** Begin Patch");
+                assert_eq!(call_id, "call_patch");
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn tool_call_deltas_without_index_continue_the_open_call() {
+        let events = run_chat_sse(
+            vec![
+                delta_chunk(json!({
+                    "tool_calls": [{
+                        "id": "call_a",
+                        "type": "function",
+                        "function": { "name": "shell", "arguments": "{\"command\":" },
+                    }],
+                })),
+                delta_chunk(json!({
+                    "tool_calls": [{ "function": { "arguments": "[\"ls\"]}" } }],
+                })),
+                delta_chunk(json!({
+                    "tool_calls": [{
+                        "id": "call_b",
+                        "type": "function",
+                        "function": { "name": "shell", "arguments": "{\"command\":[\"pwd\"]}" },
+                    }],
+                })),
+                finish_chunk("tool_calls"),
+            ],
+            false,
+        )
+        .await;
+        let calls = events
+            .into_iter()
+            .filter_map(|event| match event.expect("unexpected stream error") {
+                ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+                    arguments,
+                    call_id,
+                    ..
+                }) => Some((call_id, arguments)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            calls,
+            vec![
+                ("call_a".to_string(), r#"{"command":["ls"]}"#.to_string()),
+                ("call_b".to_string(), r#"{"command":["pwd"]}"#.to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_context_window_error_chunk() {
+        let events = run_chat_sse(
+            vec![json!({
+                "error": {
+                    "message": "too long",
+                    "type": "invalid_request_error",
+                    "code": "context_length_exceeded",
+                },
+            })],
+            false,
+        )
+        .await;
+
+        assert_eq!(events.len(), 1);
+        assert_matches!(&events[0], Err(ApiError::ContextWindowExceeded));
+    }
+
+    #[tokio::test]
+    async fn errors_when_stream_closes_before_finish() {
+        let events = run_chat_sse(vec![delta_chunk(json!({ "content": "partial" }))], false).await;
+
+        assert_matches!(
+            events.last(),
+            Some(Err(ApiError::Stream(message)))
+                if message == "stream closed before chat completion finished"
+        );
+    }
+
+    #[tokio::test]
+    async fn length_finish_reason_is_incomplete() {
+        let events = run_chat_sse(
+            vec![
+                delta_chunk(json!({ "content": "partial" })),
+                finish_chunk("length"),
+            ],
+            true,
+        )
+        .await;
+
+        assert_matches!(
+            events.last(),
+            Some(Err(ApiError::Stream(message)))
+                if message == "Incomplete response returned, reason: length"
+        );
+    }
+}
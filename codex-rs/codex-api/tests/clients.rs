@@ -6,6 +6,9 @@ use anyhow::Result;
 use async_trait::async_trait;
 use bytes::Bytes;
 use codex_api::AuthProvider;
+use codex_api::ChatClient;
+use codex_api::ChatCompletionsApiRequest;
+use codex_api::ChatOptions;
 use codex_api::Provider;
 use codex_api::ResponsesApiRequest;
 use codex_api::ResponsesClient;
@@ -211,6 +214,55 @@ async fn responses_client_uses_responses_path() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn chat_client_uses_chat_completions_path_and_messages() -> Result<()> {
+    let state = RecordingState::default();
+    let transport = RecordingTransport::new(state.clone());
+    let client = ChatClient::new(transport, provider("vllm"), NoAuth);
+
+    let request = ChatCompletionsApiRequest {
+        model: "qwen".into(),
+        instructions: "Say hi".into(),
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".into(),
+            content: vec![ContentItem::InputText { text: "hi".into() }],
+            end_turn: None,
+            phase: None,
+        }],
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        reasoning_effort: None,
+        output_schema: None,
+        max_output_tokens: None,
+    };
+    let _stream = client
+        .stream_request(
+            request,
+            ChatOptions {
+                conversation_id: Some("sess_123".into()),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let requests = state.take_stream_requests();
+    assert_path_ends_with(&requests, "/chat/completions");
+    let req = &requests[0];
+    assert_eq!(
+        req.headers.get("session_id").and_then(|v| v.to_str().ok()),
+        Some("sess_123")
+    );
+    assert_eq!(
+        req.body.as_ref().and_then(|body| body.get("messages")),
+        Some(&serde_json::json!([
+            { "role": "system", "content": "Say hi" },
+            { "role": "user", "content": "hi" },
+        ]))
+    );
+    Ok(())
+}
+
 #[tokio::test]
 async fn streaming_client_adds_auth_headers() -> Result<()> {
     let state = RecordingState::default();
@@ -268,6 +320,7 @@ async fn streaming_client_retries_on_transport_error() -> Result<()> {
         service_tier: None,
         prompt_cache_key: None,
         text: None,
+        max_output_tokens: None,
     };
     let client = ResponsesClient::new(transport.clone(), provider, NoAuth);
 
@@ -310,6 +363,7 @@ async fn azure_default_store_attaches_ids_and_headers() -> Result<()> {
         service_tier: None,
         prompt_cache_key: None,
         text: None,
+        max_output_tokens: None,
     };
 
     let mut extra_headers = HeaderMap::new();
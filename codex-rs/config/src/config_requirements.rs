@@ -1234,6 +1234,7 @@ mod tests {
                     decision: Decision::Forbidden,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 }],
             }
         );
@@ -172,6 +172,8 @@ impl RequirementsExecPolicyToml {
                         first: Arc::from(head.as_str()),
                         rest: rest.clone(),
                     },
+                    args: Vec::new(),
+                    not_matching: Vec::new(),
                     decision,
                     justification: justification.clone(),
                 });
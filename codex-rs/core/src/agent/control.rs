@@ -3,6 +3,7 @@ use crate::agent::guards::Guards;
 use crate::agent::role::DEFAULT_ROLE_NAME;
 use crate::agent::role::resolve_role_config;
 use crate::agent::status::is_final;
+use crate::agent::worktree::AgentWorktrees;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::find_thread_path_by_id_str;
@@ -25,6 +26,9 @@ use codex_protocol::user_input::UserInput;
 use std::sync::Arc;
 use std::sync::Weak;
 use tokio::sync::watch;
+use tracing::Instrument;
+use tracing::Span;
+use tracing::info_span;
 
 const AGENT_NAMES: &str = include_str!("agent_names.txt");
 const FORKED_SPAWN_AGENT_OUTPUT_MESSAGE: &str = "You are the newly spawned agent. The prior conversation history was forked from your parent agent. Treat the next user message as your new task, and use the forked history only as background context.";
@@ -72,6 +76,7 @@ pub(crate) struct AgentControl {
     /// `ThreadManagerState -> CodexThread -> Session -> SessionServices -> ThreadManagerState`.
     manager: Weak<ThreadManagerState>,
     state: Arc<Guards>,
+    worktrees: Arc<AgentWorktrees>,
 }
 
 impl AgentControl {
@@ -300,6 +305,10 @@ impl AgentControl {
         items: Vec<UserInput>,
     ) -> CodexResult<String> {
         let state = self.upgrade()?;
+        // The sub-agent's turn gets its own trace, linked to the span that
+        // sent the input, so long-running agents don't stretch the parent turn.
+        let input_span = info_span!(parent: None, "agent_input", agent.id = %agent_id);
+        input_span.follows_from(Span::current());
         let result = state
             .send_op(
                 agent_id,
@@ -308,6 +317,7 @@ impl AgentControl {
                     final_output_json_schema: None,
                 },
             )
+            .instrument(input_span)
             .await;
         if matches!(result, Err(CodexErr::InternalAgentDied)) {
             let _ = state.remove_thread(&agent_id).await;
@@ -322,6 +332,11 @@ impl AgentControl {
         state.send_op(agent_id, Op::Interrupt).await
     }
 
+    /// Git worktrees of the agents spawned with worktree isolation in this user session.
+    pub(crate) fn worktrees(&self) -> &AgentWorktrees {
+        &self.worktrees
+    }
+
     /// Submit a shutdown request to an existing agent thread.
     pub(crate) async fn shutdown_agent(&self, agent_id: ThreadId) -> CodexResult<String> {
         let state = self.upgrade()?;
@@ -1414,6 +1429,7 @@ mod tests {
                 description: Some("Research role".to_string()),
                 config_file: None,
                 nickname_candidates: Some(vec!["Atlas".to_string()]),
+                isolation: None,
             },
         );
         let (parent_thread_id, _parent_thread) = harness.start_thread().await;
@@ -2,6 +2,7 @@ pub(crate) mod control;
 mod guards;
 pub(crate) mod role;
 pub(crate) mod status;
+pub(crate) mod worktree;
 
 pub(crate) use codex_protocol::protocol::AgentStatus;
 pub(crate) use control::AgentControl;
@@ -6,6 +6,7 @@
 //! explicitly takes ownership of model selection. It does not decide when to spawn a sub-agent or
 //! which role to use; the multi-agent tool handler owns that orchestration.
 
+use crate::config::AgentIsolation;
 use crate::config::AgentRoleConfig;
 use crate::config::Config;
 use crate::config::ConfigOverrides;
@@ -180,10 +181,15 @@ Available roles:
     }
 
     fn format_role(name: &str, declaration: &AgentRoleConfig) -> String {
+        let isolation = if declaration.isolation == Some(AgentIsolation::Worktree) {
+            " (runs in its own git worktree)"
+        } else {
+            ""
+        };
         if let Some(description) = &declaration.description {
-            format!("{name}: {{\n{description}\n}}")
+            format!("{name}{isolation}: {{\n{description}\n}}")
         } else {
-            format!("{name}: no description")
+            format!("{name}{isolation}: no description")
         }
     }
 }
@@ -201,6 +207,7 @@ mod built_in {
                         description: Some("Default agent.".to_string()),
                         config_file: None,
                         nickname_candidates: None,
+                        isolation: None,
                     }
                 ),
                 (
@@ -215,6 +222,7 @@ Rules:
 - Reuse existing explorers for related questions."#.to_string()),
                         config_file: Some("explorer.toml".to_string().parse().unwrap_or_default()),
                         nickname_candidates: None,
+                        isolation: None,
                     }
                 ),
                 (
@@ -230,6 +238,7 @@ Rules:
 - Always tell workers they are **not alone in the codebase**, and they should not revert the edits made by others, and they should adjust their implementation to accommodate the changes made by others. This is important because there may be multiple workers making changes in parallel, and they need to be aware of each other's work to avoid conflicts and ensure a cohesive final product."#.to_string()),
                         config_file: None,
                         nickname_candidates: None,
+                        isolation: None,
                     }
                 ),
                 // Awaiter is temp removed
@@ -361,6 +370,7 @@ mod tests {
                 description: None,
                 config_file: Some(PathBuf::from("/path/does/not/exist.toml")),
                 nickname_candidates: None,
+                isolation: None,
             },
         );
 
@@ -381,6 +391,7 @@ mod tests {
                 description: None,
                 config_file: Some(role_path),
                 nickname_candidates: None,
+                isolation: None,
             },
         );
 
@@ -412,6 +423,7 @@ mod tests {
                 description: None,
                 config_file: Some(role_path),
                 nickname_candidates: None,
+                isolation: None,
             },
         );
 
@@ -466,6 +478,7 @@ model_provider = "test-provider"
                 description: None,
                 config_file: Some(role_path),
                 nickname_candidates: None,
+                isolation: None,
             },
         );
 
@@ -523,6 +536,7 @@ model_provider = "role-provider"
                 description: None,
                 config_file: Some(role_path),
                 nickname_candidates: None,
+                isolation: None,
             },
         );
 
@@ -581,6 +595,7 @@ model_provider = "base-provider"
                 description: None,
                 config_file: Some(role_path),
                 nickname_candidates: None,
+                isolation: None,
             },
         );
 
@@ -643,6 +658,7 @@ model_reasoning_effort = "high"
                 description: None,
                 config_file: Some(role_path),
                 nickname_candidates: None,
+                isolation: None,
             },
         );
 
@@ -685,6 +701,7 @@ writable_roots = ["./sandbox-root"]
                 description: None,
                 config_file: Some(role_path),
                 nickname_candidates: None,
+                isolation: None,
             },
         );
 
@@ -739,6 +756,7 @@ writable_roots = ["./sandbox-root"]
                 description: None,
                 config_file: Some(role_path),
                 nickname_candidates: None,
+                isolation: None,
             },
         );
 
@@ -780,6 +798,7 @@ enabled = false
                 description: None,
                 config_file: Some(role_path),
                 nickname_candidates: None,
+                isolation: None,
             },
         );
 
@@ -808,6 +827,7 @@ enabled = false
                     description: Some("user override".to_string()),
                     config_file: None,
                     nickname_candidates: None,
+                    isolation: None,
                 },
             ),
             ("researcher".to_string(), AgentRoleConfig::default()),
@@ -829,6 +849,7 @@ enabled = false
                 description: Some("first".to_string()),
                 config_file: None,
                 nickname_candidates: None,
+                isolation: None,
             },
         )]);
 
@@ -0,0 +1,366 @@
+//! Git worktree isolation for spawned sub-agents.
+//!
+//! An agent spawned with `isolation = "worktree"` runs in a dedicated linked worktree under
+//! `$CODEX_HOME/worktrees`, on a `codex/agent-*` branch created from the parent's `HEAD`, so
+//! parallel agents editing the same repository cannot clobber each other or the parent checkout.
+//! The parent reviews the agent's diff when it finishes and decides, when closing the agent,
+//! whether to merge the changes into its own checkout, keep them on the branch, or discard them.
+//! Worktrees still open when the session ends are kept on their branch and removed from disk.
+
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+use codex_git::ApplyGitRequest;
+use codex_git::GitToolingError;
+use codex_git::Worktree;
+use codex_git::apply_git_patch;
+use codex_git::create_worktree;
+use codex_protocol::ThreadId;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::warn;
+use uuid::Uuid;
+
+const WORKTREES_DIR: &str = "worktrees";
+const BRANCH_PREFIX: &str = "codex/agent-";
+const COMMIT_MESSAGE: &str = "Changes from Codex sub-agent";
+/// Diffs returned to the parent model are truncated to this many bytes.
+const MAX_DIFF_BYTES: usize = 32 * 1024;
+
+/// What to do with an isolated agent's worktree when the agent is closed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WorktreeAction {
+    /// Commit the changes to the agent's branch and remove the worktree.
+    #[default]
+    Keep,
+    /// Apply the changes to the parent's checkout, then delete the worktree and branch.
+    Merge,
+    /// Delete the worktree and branch without applying anything.
+    Discard,
+}
+
+/// Location of an isolated agent's worktree, reported when the agent is spawned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct AgentWorktreeInfo {
+    pub(crate) path: PathBuf,
+    pub(crate) branch: String,
+}
+
+/// Snapshot of an isolated agent's changes, reported once the agent has finished.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct AgentWorktreeReview {
+    pub(crate) branch: String,
+    /// Diff against the commit the agent started from, relative to the repository root.
+    pub(crate) diff: String,
+}
+
+/// Result of closing an isolated agent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct AgentWorktreeOutcome {
+    /// Branch holding the agent's changes, when they were kept.
+    pub(crate) branch: Option<String>,
+    /// Whether the changes were applied to the parent's checkout.
+    pub(crate) merged: bool,
+    pub(crate) diff: String,
+    /// Why a requested merge did not happen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) error: Option<String>,
+}
+
+/// Worktrees of the isolated agents in one user session, keyed by agent thread.
+#[derive(Default)]
+pub(crate) struct AgentWorktrees {
+    worktrees: Mutex<HashMap<ThreadId, Worktree>>,
+}
+
+impl AgentWorktrees {
+    pub(crate) fn insert(&self, thread_id: ThreadId, worktree: Worktree) {
+        if let Ok(mut worktrees) = self.worktrees.lock() {
+            worktrees.insert(thread_id, worktree);
+        }
+    }
+
+    fn get(&self, thread_id: ThreadId) -> Option<Worktree> {
+        self.worktrees
+            .lock()
+            .ok()
+            .and_then(|worktrees| worktrees.get(&thread_id).cloned())
+    }
+
+    fn take(&self, thread_id: ThreadId) -> Option<Worktree> {
+        self.worktrees
+            .lock()
+            .ok()
+            .and_then(|mut worktrees| worktrees.remove(&thread_id))
+    }
+
+    /// Returns the current diff of the agent's worktree, if it has one.
+    pub(crate) async fn review(&self, thread_id: ThreadId) -> Option<AgentWorktreeReview> {
+        let worktree = self.get(thread_id)?;
+        let branch = worktree.branch.clone();
+        let diff = match tokio::task::spawn_blocking(move || {
+            worktree.stage_all()?;
+            worktree.diff()
+        })
+        .await
+        {
+            Ok(Ok(diff)) => diff,
+            Ok(Err(err)) => format!("failed to compute diff: {err}"),
+            Err(err) => format!("failed to compute diff: {err}"),
+        };
+        Some(AgentWorktreeReview {
+            branch,
+            diff: truncate_diff(&diff),
+        })
+    }
+
+    /// Applies `action` to the agent's worktree and removes it from disk. Returns `None` when
+    /// the agent was not isolated.
+    pub(crate) async fn finish(
+        &self,
+        thread_id: ThreadId,
+        action: WorktreeAction,
+    ) -> Option<Result<AgentWorktreeOutcome, String>> {
+        let worktree = self.take(thread_id)?;
+        let result = tokio::task::spawn_blocking(move || finish_worktree(&worktree, action))
+            .await
+            .map_err(|err| format!("worktree cleanup panicked: {err}"))
+            .and_then(|result| result.map_err(|err| format!("worktree cleanup failed: {err}")));
+        Some(result)
+    }
+
+    /// Keeps the changes of every worktree that is still open on its branch and removes the
+    /// worktrees from disk. Called when the user session shuts down.
+    pub(crate) async fn cleanup(&self) {
+        let worktrees: Vec<(ThreadId, Worktree)> = self
+            .worktrees
+            .lock()
+            .map(|mut worktrees| worktrees.drain().collect())
+            .unwrap_or_default();
+        for (thread_id, worktree) in worktrees {
+            let result = tokio::task::spawn_blocking(move || {
+                finish_worktree(&worktree, WorktreeAction::Keep)
+            })
+            .await;
+            match result {
+                Ok(Ok(_)) => {}
+                Ok(Err(err)) => warn!("failed to clean up worktree of agent {thread_id}: {err}"),
+                Err(err) => warn!("worktree cleanup of agent {thread_id} panicked: {err}"),
+            }
+        }
+    }
+}
+
+/// Creates a worktree for a new agent from the repository containing `cwd`.
+pub(crate) async fn create_agent_worktree(
+    codex_home: &Path,
+    cwd: &Path,
+) -> Result<Worktree, String> {
+    let id = Uuid::new_v4().simple().to_string();
+    let id = &id[..12];
+    let path = codex_home.join(WORKTREES_DIR).join(format!("agent-{id}"));
+    let branch = format!("{BRANCH_PREFIX}{id}");
+    let cwd = cwd.to_path_buf();
+    tokio::task::spawn_blocking(move || create_worktree(&cwd, &path, &branch))
+        .await
+        .map_err(|err| format!("failed to create worktree: {err}"))?
+        .map_err(|err| match err {
+            GitToolingError::NotAGitRepository { .. } => {
+                "worktree isolation requires the current directory to be in a git repository"
+                    .to_string()
+            }
+            err => format!("failed to create worktree: {err}"),
+        })
+}
+
+/// Removes a worktree that was created for an agent that then failed to start.
+pub(crate) async fn discard_agent_worktree(worktree: Worktree) {
+    let result = tokio::task::spawn_blocking(move || worktree.remove(true)).await;
+    if let Ok(Err(err)) = result {
+        warn!("failed to remove unused agent worktree: {err}");
+    }
+}
+
+pub(crate) fn worktree_info(worktree: &Worktree) -> AgentWorktreeInfo {
+    AgentWorktreeInfo {
+        path: worktree.cwd(),
+        branch: worktree.branch.clone(),
+    }
+}
+
+fn finish_worktree(
+    worktree: &Worktree,
+    action: WorktreeAction,
+) -> Result<AgentWorktreeOutcome, GitToolingError> {
+    worktree.stage_all()?;
+    let diff = worktree.diff()?;
+    let mut outcome = AgentWorktreeOutcome {
+        branch: None,
+        merged: false,
+        diff: truncate_diff(&diff),
+        error: None,
+    };
+    let keep = match action {
+        WorktreeAction::Keep => true,
+        WorktreeAction::Discard => false,
+        WorktreeAction::Merge if diff.is_empty() => false,
+        WorktreeAction::Merge => {
+            let mut request = ApplyGitRequest {
+                cwd: worktree.repo_root.clone(),
+                diff,
+                revert: false,
+                preflight: true,
+            };
+            // Check first so a conflicting merge never leaves conflict markers behind.
+            let mut result = apply_git_patch(&request)?;
+            if result.exit_code == 0 {
+                request.preflight = false;
+                result = apply_git_patch(&request)?;
+            }
+            outcome.merged = result.exit_code == 0;
+            if !outcome.merged {
+                outcome.error = Some(format!(
+                    "changes did not apply cleanly and were kept on the branch: {}",
+                    result.stderr.trim()
+                ));
+            }
+            !outcome.merged
+        }
+    };
+    if keep {
+        worktree.commit_all(COMMIT_MESSAGE)?;
+        if worktree.has_commits()? {
+            outcome.branch = Some(worktree.branch.clone());
+        }
+    }
+    worktree.remove(outcome.branch.is_none())?;
+    Ok(outcome)
+}
+
+fn truncate_diff(diff: &str) -> String {
+    truncate_text(diff, TruncationPolicy::Bytes(MAX_DIFF_BYTES))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+
+    fn git(repo: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .current_dir(repo)
+            .args(args)
+            .output()
+            .expect("git command");
+        assert!(output.status.success(), "git command failed: {args:?}");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn init_repo(repo: &Path) {
+        std::fs::create_dir_all(repo).expect("create repo");
+        git(repo, &["init", "--initial-branch=main"]);
+        git(repo, &["config", "user.name", "Tester"]);
+        git(repo, &["config", "user.email", "test@example.com"]);
+        std::fs::write(repo.join("lib.rs"), "fn a() {}\n").expect("write file");
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-m", "init"]);
+    }
+
+    #[tokio::test]
+    async fn merge_applies_changes_to_parent_checkout() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let repo = temp.path().join("repo");
+        init_repo(&repo);
+        let worktree = create_agent_worktree(&temp.path().join("home"), &repo)
+            .await
+            .expect("create worktree");
+        assert!(worktree.branch.starts_with(BRANCH_PREFIX));
+        std::fs::write(worktree.cwd().join("lib.rs"), "fn b() {}\n").expect("write file");
+
+        let thread_id = ThreadId::new();
+        let worktrees = AgentWorktrees::default();
+        worktrees.insert(thread_id, worktree.clone());
+        let review = worktrees.review(thread_id).await.expect("review");
+        assert!(review.diff.contains("+fn b() {}"), "{}", review.diff);
+
+        let outcome = worktrees
+            .finish(thread_id, WorktreeAction::Merge)
+            .await
+            .expect("isolated agent")
+            .expect("finish");
+        assert!(outcome.merged);
+        assert_eq!(outcome.branch, None);
+        assert_eq!(
+            std::fs::read_to_string(repo.join("lib.rs")).expect("read file"),
+            "fn b() {}\n"
+        );
+        assert!(!worktree.path.exists());
+        assert_eq!(git(&repo, &["branch", "--list", &worktree.branch]), "");
+        assert!(
+            worktrees
+                .finish(thread_id, WorktreeAction::Merge)
+                .await
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn conflicting_merge_keeps_changes_on_branch() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let repo = temp.path().join("repo");
+        init_repo(&repo);
+        let worktree = create_agent_worktree(&temp.path().join("home"), &repo)
+            .await
+            .expect("create worktree");
+        std::fs::write(worktree.cwd().join("lib.rs"), "fn b() {}\n").expect("write file");
+        std::fs::write(repo.join("lib.rs"), "fn c() {}\n").expect("write file");
+
+        let outcome = finish_worktree(&worktree, WorktreeAction::Merge).expect("finish");
+        assert!(!outcome.merged);
+        assert!(outcome.error.is_some());
+        assert_eq!(outcome.branch.as_deref(), Some(worktree.branch.as_str()));
+        assert_eq!(
+            std::fs::read_to_string(repo.join("lib.rs")).expect("read file"),
+            "fn c() {}\n"
+        );
+        assert_eq!(
+            git(&repo, &["show", &format!("{}:lib.rs", worktree.branch)]),
+            "fn b() {}"
+        );
+    }
+
+    #[tokio::test]
+    async fn cleanup_keeps_open_worktrees_on_their_branch() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let repo = temp.path().join("repo");
+        init_repo(&repo);
+        let worktree = create_agent_worktree(&temp.path().join("home"), &repo)
+            .await
+            .expect("create worktree");
+        std::fs::write(worktree.cwd().join("lib.rs"), "fn b() {}\n").expect("write file");
+        let worktrees = AgentWorktrees::default();
+        worktrees.insert(ThreadId::new(), worktree.clone());
+
+        worktrees.cleanup().await;
+
+        assert!(!worktree.path.exists());
+        assert_eq!(
+            git(&repo, &["show", &format!("{}:lib.rs", worktree.branch)]),
+            "fn b() {}"
+        );
+    }
+
+    #[tokio::test]
+    async fn create_fails_outside_git_repository() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let err = create_agent_worktree(&temp.path().join("home"), temp.path())
+            .await
+            .expect_err("not a repository");
+        assert!(err.contains("git repository"), "{err}");
+    }
+}
@@ -1,12 +1,15 @@
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 use crate::protocol::FileChange;
+use crate::protocol::PatchHunkAction;
+use crate::protocol::PatchHunkDecision;
 use crate::safety::SafetyCheck;
 use crate::safety::assess_patch_safety;
 use crate::tools::sandboxing::ExecApprovalRequirement;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub(crate) enum InternalApplyPatchInvocation {
@@ -101,6 +104,174 @@ pub(crate) fn convert_apply_patch_to_protocol(
     result
 }
 
+/// The part of a patch the user kept while reviewing it hunk by hunk.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PatchHunkSelection {
+    /// `apply_patch` input with only the accepted and edited hunks, or `None`
+    /// when every hunk was rejected.
+    pub(crate) patch: Option<String>,
+    /// Which hunks were applied, reported back to the model with the tool
+    /// output.
+    pub(crate) report: String,
+    /// The kept hunks of each file, reported to clients once the patch has
+    /// been applied.
+    pub(crate) changes: HashMap<PathBuf, FileChange>,
+}
+
+/// Rebuilds the patch behind `changes` from the hunks the user accepted or
+/// edited. Hunks without a decision are rejected.
+pub(crate) fn select_patch_hunks(
+    cwd: &Path,
+    changes: &HashMap<PathBuf, FileChange>,
+    decisions: &[PatchHunkDecision],
+) -> PatchHunkSelection {
+    let mut paths: Vec<&PathBuf> = changes.keys().collect();
+    paths.sort();
+
+    let mut patch = String::from("*** Begin Patch\n");
+    let mut applied_any = false;
+    let mut selected_changes = HashMap::new();
+    let mut report = String::from("The user reviewed this patch hunk by hunk:");
+    for path in paths {
+        let change = &changes[path];
+        let hunks = change.hunks();
+        let actions: Vec<&PatchHunkAction> = (0..hunks.len())
+            .map(|index| {
+                decisions
+                    .iter()
+                    .rev()
+                    .find(|decision| &decision.path == path && decision.hunk_index == index)
+                    .map_or(&PatchHunkAction::Reject, |decision| &decision.action)
+            })
+            .collect();
+
+        let display_path = path.strip_prefix(cwd).unwrap_or(path).display();
+        let outcomes: Vec<&str> = actions
+            .iter()
+            .map(|action| match action {
+                PatchHunkAction::Accept => "applied",
+                PatchHunkAction::Reject => "rejected",
+                PatchHunkAction::Edit { .. } => match change {
+                    FileChange::Delete { .. } => "applied",
+                    _ => "applied with edits by the user",
+                },
+            })
+            .collect();
+        if let [outcome] = outcomes.as_slice() {
+            report.push_str(&format!("\n- {display_path}: {outcome}"));
+        } else {
+            let outcomes = outcomes
+                .iter()
+                .enumerate()
+                .map(|(index, outcome)| format!("hunk {} {outcome}", index + 1))
+                .collect::<Vec<_>>()
+                .join(", ");
+            report.push_str(&format!("\n- {display_path}: {outcomes}"));
+        }
+
+        let selected = match change {
+            FileChange::Add { content } => match actions[0] {
+                PatchHunkAction::Accept => Some((
+                    add_file_section(path, content),
+                    FileChange::Add {
+                        content: content.clone(),
+                    },
+                )),
+                PatchHunkAction::Edit { replacement } => Some((
+                    add_file_section(path, replacement),
+                    FileChange::Add {
+                        content: replacement.clone(),
+                    },
+                )),
+                PatchHunkAction::Reject => None,
+            },
+            FileChange::Delete { .. } => match actions[0] {
+                PatchHunkAction::Accept | PatchHunkAction::Edit { .. } => Some((
+                    format!("*** Delete File: {}\n", path.display()),
+                    change.clone(),
+                )),
+                PatchHunkAction::Reject => None,
+            },
+            FileChange::Update { move_path, .. } => {
+                let kept: Vec<&str> = hunks
+                    .iter()
+                    .zip(&actions)
+                    .filter_map(|(hunk, action)| match action {
+                        PatchHunkAction::Accept => Some(hunk.as_str()),
+                        PatchHunkAction::Edit { replacement } => Some(replacement.as_str()),
+                        PatchHunkAction::Reject => None,
+                    })
+                    .collect();
+                let chunks: String = kept.iter().filter_map(|hunk| update_chunk(hunk)).collect();
+                // A rename has no diff lines of its own, so it is kept along
+                // with any kept hunk, including the empty hunk of a pure rename.
+                let keeps_move = move_path.is_some() && !kept.is_empty();
+                (!chunks.is_empty() || keeps_move).then(|| {
+                    let mut section = format!("*** Update File: {}\n", path.display());
+                    if let Some(move_path) = move_path {
+                        section.push_str(&format!("*** Move to: {}\n", move_path.display()));
+                    }
+                    section.push_str(&chunks);
+                    let mut unified_diff = String::new();
+                    for hunk in &kept {
+                        unified_diff.push_str(hunk);
+                        if !hunk.is_empty() && !hunk.ends_with('\n') {
+                            unified_diff.push('\n');
+                        }
+                    }
+                    (
+                        section,
+                        FileChange::Update {
+                            unified_diff,
+                            move_path: move_path.clone(),
+                        },
+                    )
+                })
+            }
+        };
+        if let Some((section, selected_change)) = selected {
+            patch.push_str(&section);
+            selected_changes.insert(path.clone(), selected_change);
+            applied_any = true;
+        }
+    }
+    patch.push_str("*** End Patch\n");
+
+    PatchHunkSelection {
+        patch: applied_any.then_some(patch),
+        report,
+        changes: selected_changes,
+    }
+}
+
+fn add_file_section(path: &Path, content: &str) -> String {
+    let mut section = format!("*** Add File: {}\n", path.display());
+    for line in content.lines() {
+        section.push('+');
+        section.push_str(line);
+        section.push('\n');
+    }
+    section
+}
+
+/// Turns the lines of a unified diff hunk into an `apply_patch` chunk. Returns
+/// `None` when the hunk has no diff lines, e.g. for a pure rename.
+fn update_chunk(hunk: &str) -> Option<String> {
+    let mut chunk = String::from("@@\n");
+    let mut has_lines = false;
+    for line in hunk.lines() {
+        // `@@` headers carry line numbers `apply_patch` does not use, and
+        // `\ No newline at end of file` markers are not diff lines.
+        if line.starts_with("@@") || line.starts_with('\\') {
+            continue;
+        }
+        chunk.push_str(line);
+        chunk.push('\n');
+        has_lines = true;
+    }
+    has_lines.then_some(chunk)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,4 +295,113 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn select_patch_hunks_keeps_accepted_and_edited_hunks() {
+        let cwd = PathBuf::from("/repo");
+        let changes = HashMap::from([
+            (
+                cwd.join("a.txt"),
+                FileChange::Update {
+                    unified_diff: "@@ -1,2 +1,2 @@\n a\n-b\n+B\n@@ -8,2 +8,2 @@\n y\n-z\n+Z\n@@ -20,1 +20,1 @@\n-q\n+Q\n"
+                        .to_string(),
+                    move_path: None,
+                },
+            ),
+            (
+                cwd.join("new.txt"),
+                FileChange::Add {
+                    content: "one\ntwo\n".to_string(),
+                },
+            ),
+        ]);
+        let decisions = vec![
+            PatchHunkDecision {
+                path: cwd.join("a.txt"),
+                hunk_index: 0,
+                action: PatchHunkAction::Accept,
+            },
+            PatchHunkDecision {
+                path: cwd.join("a.txt"),
+                hunk_index: 1,
+                action: PatchHunkAction::Edit {
+                    replacement: "@@ -8,2 +8,2 @@\n y\n-z\n+zz\n".to_string(),
+                },
+            },
+        ];
+
+        let selection = select_patch_hunks(&cwd, &changes, &decisions);
+
+        assert_eq!(
+            selection,
+            PatchHunkSelection {
+                patch: Some(
+                    "*** Begin Patch\n*** Update File: /repo/a.txt\n@@\n a\n-b\n+B\n@@\n y\n-z\n+zz\n*** End Patch\n"
+                        .to_string()
+                ),
+                report: "The user reviewed this patch hunk by hunk:\n- a.txt: hunk 1 applied, hunk 2 applied with edits by the user, hunk 3 rejected\n- new.txt: rejected"
+                    .to_string(),
+                changes: HashMap::from([(
+                    cwd.join("a.txt"),
+                    FileChange::Update {
+                        unified_diff: "@@ -1,2 +1,2 @@\n a\n-b\n+B\n@@ -8,2 +8,2 @@\n y\n-z\n+zz\n"
+                            .to_string(),
+                        move_path: None,
+                    },
+                )]),
+            }
+        );
+    }
+
+    #[test]
+    fn select_patch_hunks_keeps_pure_rename() {
+        let cwd = PathBuf::from("/repo");
+        let changes = HashMap::from([(
+            cwd.join("old.txt"),
+            FileChange::Update {
+                unified_diff: String::new(),
+                move_path: Some(cwd.join("new.txt")),
+            },
+        )]);
+        let decisions = vec![PatchHunkDecision {
+            path: cwd.join("old.txt"),
+            hunk_index: 0,
+            action: PatchHunkAction::Accept,
+        }];
+
+        let selection = select_patch_hunks(&cwd, &changes, &decisions);
+
+        assert_eq!(
+            selection.patch.as_deref(),
+            Some(
+                "*** Begin Patch\n*** Update File: /repo/old.txt\n*** Move to: /repo/new.txt\n*** End Patch\n"
+            )
+        );
+        assert_eq!(selection.changes, changes);
+    }
+
+    #[test]
+    fn select_patch_hunks_without_accepted_hunks_has_no_patch() {
+        let cwd = PathBuf::from("/repo");
+        let changes = HashMap::from([(
+            cwd.join("gone.txt"),
+            FileChange::Delete {
+                content: "bye\n".to_string(),
+            },
+        )]);
+        let decisions = vec![PatchHunkDecision {
+            path: cwd.join("gone.txt"),
+            hunk_index: 0,
+            action: PatchHunkAction::Reject,
+        }];
+
+        let selection = select_patch_hunks(&cwd, &changes, &decisions);
+
+        assert_eq!(selection.patch, None);
+        assert_eq!(selection.changes, HashMap::new());
+        assert_eq!(
+            selection.report,
+            "The user reviewed this patch hunk by hunk:\n- gone.txt: rejected"
+        );
+    }
 }
@@ -34,6 +34,9 @@ use crate::api_bridge::CoreAuthProvider;
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use crate::auth::UnauthorizedRecovery;
+use codex_api::ChatClient as ApiChatClient;
+use codex_api::ChatCompletionsApiRequest;
+use codex_api::ChatOptions as ApiChatOptions;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
 use codex_api::MemoriesClient as ApiMemoriesClient;
@@ -241,6 +244,25 @@ impl ModelClient {
         }
     }
 
+    /// Returns a client with the same session settings that talks to `provider`.
+    ///
+    /// Transport fallback and cached WebSocket state are not shared with `self`, since they
+    /// describe the previous provider's connection.
+    pub fn with_provider(&self, provider: ModelProviderInfo) -> Self {
+        let state = &self.state;
+        Self::new(
+            state.auth_manager.clone(),
+            state.conversation_id,
+            provider,
+            state.session_source.clone(),
+            state.model_verbosity,
+            state.responses_websockets_enabled_by_feature,
+            state.enable_request_compression,
+            state.include_timing_metrics,
+            state.beta_features_header.clone(),
+        )
+    }
+
     /// Creates a fresh turn-scoped streaming session.
     ///
     /// This constructor does not perform network I/O itself; the session opens a websocket lazily
@@ -383,7 +405,8 @@ impl ModelClient {
     /// If websockets are only enabled via model preference (no explicit feature flag), prefer the
     /// current v2 behavior.
     pub fn responses_websocket_enabled(&self, model_info: &ModelInfo) -> bool {
-        if !self.state.provider.supports_websockets
+        if self.state.provider.wire_api != WireApi::Responses
+            || !self.state.provider.supports_websockets
             || self.state.disable_websockets.load(Ordering::Relaxed)
         {
             return false;
@@ -551,6 +574,7 @@ impl ModelClientSession {
             },
             prompt_cache_key,
             text,
+            max_output_tokens: prompt.max_output_tokens,
         };
         Ok(request)
     }
@@ -812,6 +836,83 @@ impl ModelClientSession {
         }
     }
 
+    fn build_chat_completions_request(
+        &self,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        effort: Option<ReasoningEffortConfig>,
+    ) -> Result<ChatCompletionsApiRequest> {
+        let reasoning_effort = if model_info.supports_reasoning_summaries {
+            effort.or(model_info.default_reasoning_level)
+        } else {
+            None
+        };
+        Ok(ChatCompletionsApiRequest {
+            model: model_info.slug.clone(),
+            instructions: prompt.base_instructions.text.clone(),
+            input: prompt.get_formatted_input(),
+            tools: create_tools_json_for_responses_api(&prompt.tools)?,
+            parallel_tool_calls: prompt.parallel_tool_calls,
+            reasoning_effort,
+            output_schema: prompt.output_schema.clone(),
+            max_output_tokens: prompt.max_output_tokens,
+        })
+    }
+
+    /// Streams a turn via an OpenAI-compatible Chat Completions API.
+    ///
+    /// The chat stream is translated into the same `ResponseEvent`s as the
+    /// Responses API, so turn handling does not depend on the wire protocol.
+    async fn stream_chat_completions_api(
+        &self,
+        prompt: &Prompt,
+        model_info: &ModelInfo,
+        session_telemetry: &SessionTelemetry,
+        effort: Option<ReasoningEffortConfig>,
+        turn_metadata_header: Option<&str>,
+    ) -> Result<ResponseStream> {
+        let auth_manager = self.client.state.auth_manager.clone();
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(super::auth::AuthManager::unauthorized_recovery);
+        loop {
+            let client_setup = self.client.current_client_setup().await?;
+            let transport = ReqwestTransport::new(build_reqwest_client());
+            let (request_telemetry, sse_telemetry) =
+                Self::build_streaming_telemetry(session_telemetry);
+            let turn_metadata_header = parse_turn_metadata_header(turn_metadata_header);
+            let options = ApiChatOptions {
+                conversation_id: Some(self.client.state.conversation_id.to_string()),
+                session_source: Some(self.client.state.session_source.clone()),
+                extra_headers: build_responses_headers(
+                    self.client.state.beta_features_header.as_deref(),
+                    None,
+                    turn_metadata_header.as_ref(),
+                ),
+            };
+
+            let request = self.build_chat_completions_request(prompt, model_info, effort)?;
+            let client =
+                ApiChatClient::new(transport, client_setup.api_provider, client_setup.api_auth)
+                    .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
+            let stream_result = client.stream_request(request, options).await;
+
+            match stream_result {
+                Ok(stream) => {
+                    let (stream, _) = map_response_stream(stream, session_telemetry.clone());
+                    return Ok(stream);
+                }
+                Err(ApiError::Transport(
+                    unauthorized_transport @ TransportError::Http { status, .. },
+                )) if status == StatusCode::UNAUTHORIZED => {
+                    handle_unauthorized(unauthorized_transport, &mut auth_recovery).await?;
+                    continue;
+                }
+                Err(err) => return Err(map_api_error(err)),
+            }
+        }
+    }
+
     /// Streams a turn via the Responses API over WebSocket transport.
     #[allow(clippy::too_many_arguments)]
     async fn stream_responses_websocket(
@@ -972,7 +1073,8 @@ impl ModelClientSession {
     /// The caller is responsible for passing per-turn settings explicitly (model selection,
     /// reasoning settings, telemetry context, and turn metadata). This method will prefer the
     /// Responses WebSocket transport when enabled and healthy, and will fall back to the HTTP
-    /// Responses API transport otherwise.
+    /// Responses API transport otherwise. Providers configured with `wire_api = "chat"` always
+    /// use the Chat Completions API.
     pub async fn stream(
         &mut self,
         prompt: &Prompt,
@@ -1018,6 +1120,16 @@ impl ModelClientSession {
                 )
                 .await
             }
+            WireApi::Chat => {
+                self.stream_chat_completions_api(
+                    prompt,
+                    model_info,
+                    session_telemetry,
+                    effort,
+                    turn_metadata_header,
+                )
+                .await
+            }
         }
     }
 
@@ -42,6 +42,9 @@ pub struct Prompt {
 
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
+
+    /// Optional cap on the number of tokens the model may generate.
+    pub max_output_tokens: Option<u64>,
 }
 
 impl Prompt {
@@ -343,6 +346,7 @@ mod tests {
                 verbosity: Some(OpenAiVerbosity::Low),
                 format: None,
             }),
+            max_output_tokens: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -382,6 +386,7 @@ mod tests {
             prompt_cache_key: None,
             service_tier: None,
             text: Some(text_controls),
+            max_output_tokens: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -419,6 +424,7 @@ mod tests {
             prompt_cache_key: None,
             service_tier: None,
             text: None,
+            max_output_tokens: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -441,6 +447,7 @@ mod tests {
             prompt_cache_key: None,
             service_tier: Some(ServiceTier::Flex.to_string()),
             text: None,
+            max_output_tokens: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -0,0 +1,258 @@
+//! Shared, persistent code indexes for the `code_search` tool.
+//!
+//! Each workspace root gets one [`CodeIndex`], stored under
+//! `CODEX_HOME/code_index` so later sessions only re-index what changed. The
+//! index is built on the first search in a workspace; after that the
+//! directories it indexes are watched and changed paths are applied before
+//! the next search.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::Weak;
+
+use codex_code_index::CodeIndex;
+use codex_code_index::PendingSave;
+use codex_code_index::indexed_dirs;
+use tokio::runtime::Handle;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::file_watcher::FileWatcher;
+use crate::file_watcher::FileWatcherEvent;
+use crate::file_watcher::WorkspaceWatchRegistration;
+use crate::git_info::get_git_repo_root;
+
+const CODE_INDEX_SUBDIR: &str = "code_index";
+
+pub(crate) struct CodeIndexManager {
+    index_dir: PathBuf,
+    file_watcher: Arc<FileWatcher>,
+    workspaces: Mutex<HashMap<PathBuf, Arc<Workspace>>>,
+}
+
+struct Workspace {
+    /// `None` until the first search opens the index.
+    index: Mutex<Option<CodeIndex>>,
+    /// Paths reported by the watcher since the last search.
+    pending: Mutex<HashSet<PathBuf>>,
+    /// Taken before the index lock is released and held while changed
+    /// shards are written, so writes land in the order they were taken.
+    save: Mutex<()>,
+    _watch: WorkspaceWatchRegistration,
+}
+
+impl CodeIndexManager {
+    pub(crate) fn new(codex_home: &Path, file_watcher: Arc<FileWatcher>) -> Arc<Self> {
+        let manager = Arc::new(Self {
+            index_dir: codex_home.join(CODE_INDEX_SUBDIR),
+            file_watcher,
+            workspaces: Mutex::new(HashMap::new()),
+        });
+        manager.start_listener();
+        manager
+    }
+
+    /// The workspace indexed for searches under `path`: the enclosing git
+    /// repository. Outside of a repository there is nothing bounding how much
+    /// would be indexed (e.g. a search from `$HOME`), so there is no workspace.
+    pub(crate) fn workspace_root(path: &Path) -> Option<PathBuf> {
+        let dir = if path.is_dir() {
+            path
+        } else {
+            path.parent().unwrap_or(path)
+        };
+        get_git_repo_root(dir)
+    }
+
+    /// Runs `query` against the up-to-date index of `root` on a blocking
+    /// thread, building the index first if this is the first search there.
+    pub(crate) async fn query<T, F>(&self, root: PathBuf, query: F) -> Result<T, String>
+    where
+        T: Send + 'static,
+        F: FnOnce(&CodeIndex) -> T + Send + 'static,
+    {
+        let workspace = self.workspace(&root);
+        let index_dir = self.index_dir.clone();
+        let file_watcher = Arc::clone(&self.file_watcher);
+        tokio::task::spawn_blocking(move || {
+            let mut slot = workspace
+                .index
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            let pending: Vec<PathBuf> = workspace
+                .pending
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .drain()
+                .collect();
+            // Directories are watched before their files are indexed so edits
+            // made in the meantime are applied on the next search. Only the
+            // directories the index walks into are watched, leaving ignored
+            // trees such as `node_modules` or `target` alone.
+            let index = match slot.as_mut() {
+                Some(index) => {
+                    let new_dirs = pending
+                        .iter()
+                        .filter(|path| path.is_dir())
+                        .flat_map(|dir| indexed_dirs(&root, dir))
+                        .collect();
+                    file_watcher.watch_workspace_dirs(&root, new_dirs);
+                    index.update_paths(&pending);
+                    index
+                }
+                None => {
+                    file_watcher.watch_workspace_dirs(&root, indexed_dirs(&root, &root));
+                    slot.insert(CodeIndex::open(&root, &index_dir))
+                }
+            };
+            let result = query(index);
+            let pending_save = index.take_pending_save();
+            let _save = workspace
+                .save
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            drop(slot);
+            if let Err(err) =
+                pending_save.and_then(|pending| pending.map_or(Ok(()), PendingSave::write))
+            {
+                warn!("failed to save code index for {}: {err}", root.display());
+            }
+            result
+        })
+        .await
+        .map_err(|err| format!("code search failed: {err}"))
+    }
+
+    fn workspace(&self, root: &Path) -> Arc<Workspace> {
+        let mut workspaces = self
+            .workspaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        Arc::clone(workspaces.entry(root.to_path_buf()).or_insert_with(|| {
+            Arc::new(Workspace {
+                index: Mutex::new(None),
+                pending: Mutex::new(HashSet::new()),
+                save: Mutex::new(()),
+                _watch: self.file_watcher.register_workspace(root.to_path_buf()),
+            })
+        }))
+    }
+
+    fn mark_changed(&self, paths: &[PathBuf]) {
+        let workspaces = self
+            .workspaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for (root, workspace) in workspaces.iter() {
+            let changed = paths.iter().filter(|path| path.starts_with(root));
+            workspace
+                .pending
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .extend(changed.cloned());
+        }
+    }
+
+    fn start_listener(self: &Arc<Self>) {
+        let Ok(handle) = Handle::try_current() else {
+            warn!("code index listener skipped: no Tokio runtime available");
+            return;
+        };
+        let mut rx = self.file_watcher.subscribe();
+        let manager: Weak<Self> = Arc::downgrade(self);
+        handle.spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(FileWatcherEvent::WorkspaceChanged { paths }) => {
+                        let Some(manager) = manager.upgrade() else {
+                            break;
+                        };
+                        manager.mark_changed(&paths);
+                    }
+                    Ok(FileWatcherEvent::SkillsChanged { .. }) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                    Err(broadcast::error::RecvError::Lagged(_)) => {
+                        // Changes were dropped; rebuild from disk on the next
+                        // search rather than miss them.
+                        let Some(manager) = manager.upgrade() else {
+                            break;
+                        };
+                        manager.mark_all_changed();
+                    }
+                }
+            }
+        });
+    }
+
+    fn mark_all_changed(&self) {
+        let roots: Vec<PathBuf> = self
+            .workspaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect();
+        self.mark_changed(&roots);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn workspace_root_requires_a_git_repository() {
+        let dir = tempfile::tempdir().expect("dir");
+        let nested = dir.path().join("src");
+        std::fs::create_dir(&nested).expect("mkdir");
+        assert_eq!(CodeIndexManager::workspace_root(&nested), None);
+
+        std::fs::create_dir(dir.path().join(".git")).expect("mkdir");
+        assert_eq!(
+            CodeIndexManager::workspace_root(&nested.join("lib.rs")),
+            Some(dir.path().to_path_buf())
+        );
+    }
+
+    #[tokio::test]
+    async fn applies_marked_changes_before_the_next_query() {
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let workspace = tempfile::tempdir().expect("workspace");
+        let root = workspace.path().to_path_buf();
+        std::fs::write(root.join("lib.rs"), "fn old_name() {}\n").expect("write");
+        let manager = CodeIndexManager::new(codex_home.path(), Arc::new(FileWatcher::noop()));
+
+        let definitions = |name: &'static str| {
+            move |index: &CodeIndex| index.definitions(name, Path::new(""), |_| false, 10).len()
+        };
+        assert_eq!(
+            manager
+                .query(root.clone(), definitions("old_name"))
+                .await
+                .expect("query"),
+            1
+        );
+
+        std::fs::write(root.join("lib.rs"), "fn new_name() {}\nfn helper() {}\n").expect("write");
+        manager.mark_changed(&[root.join("lib.rs")]);
+        assert_eq!(
+            manager
+                .query(root.clone(), definitions("new_name"))
+                .await
+                .expect("query"),
+            1
+        );
+        assert!(
+            std::fs::read_dir(codex_home.path().join(CODE_INDEX_SUBDIR))
+                .expect("index dir")
+                .next()
+                .is_some()
+        );
+    }
+}
@@ -22,6 +22,7 @@ use crate::compact;
 use crate::compact::InitialContextInjection;
 use crate::compact::run_inline_auto_compact_task;
 use crate::compact::should_use_remote_compact_task;
+use crate::compact_prune::run_inline_pruning_compact_task;
 use crate::compact_remote::run_inline_remote_auto_compact_task;
 use crate::config::ManagedFeatures;
 use crate::connectors;
@@ -59,10 +60,17 @@ use chrono::Local;
 use chrono::Utc;
 use codex_app_server_protocol::McpServerElicitationRequest;
 use codex_app_server_protocol::McpServerElicitationRequestParams;
+use codex_hooks::HookCompactTrigger;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterAgent;
+use codex_hooks::HookEventBeforeCompact;
+use codex_hooks::HookEventName;
+use codex_hooks::HookEventSessionEnd;
+use codex_hooks::HookEventSessionStart;
+use codex_hooks::HookEventUserPromptSubmit;
 use codex_hooks::HookPayload;
 use codex_hooks::HookResult;
+use codex_hooks::HookSessionStartSource;
 use codex_hooks::Hooks;
 use codex_hooks::HooksConfig;
 use codex_network_proxy::NetworkProxy;
@@ -134,6 +142,7 @@ use tokio::sync::watch;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use tracing::Instrument;
+use tracing::Span;
 use tracing::debug;
 use tracing::debug_span;
 use tracing::error;
@@ -159,7 +168,9 @@ use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
 use crate::config::StartedNetworkProxy;
 use crate::config::resolve_web_search_mode_for_turn;
+use crate::config::types::CompactionStrategy;
 use crate::config::types::McpServerConfig;
+use crate::config::types::ModelFallback;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
 use crate::context_manager::TotalTokenUsageBreakdown;
@@ -168,6 +179,7 @@ use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 #[cfg(test)]
 use crate::exec::StreamOutput;
+use crate::exec_env::ExecSecrets;
 use codex_config::CONFIG_TOML_FILE;
 
 mod rollout_reconstruction;
@@ -193,6 +205,7 @@ pub(crate) struct PreviousTurnSettings {
     pub(crate) realtime_active: Option<bool>,
 }
 
+use crate::code_index::CodeIndexManager;
 use crate::exec_policy::ExecPolicyUpdateError;
 use crate::feedback_tags;
 use crate::file_watcher::FileWatcher;
@@ -203,6 +216,7 @@ use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::McpManager;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp::maybe_prompt_and_install_mcp_dependencies;
+use crate::mcp::sampling::McpSampler;
 use crate::mcp::with_codex_apps_mcp;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::mcp_connection_manager::codex_apps_tools_cache_key;
@@ -216,6 +230,7 @@ use crate::mentions::collect_explicit_app_ids;
 use crate::mentions::collect_explicit_plugin_mentions;
 use crate::mentions::collect_tool_mentions_from_messages;
 use crate::network_policy_decision::execpolicy_network_rule_amendment;
+use crate::notification_sinks::NotificationSinks;
 use crate::plugins::PluginsManager;
 use crate::plugins::build_plugin_injections;
 use crate::project_doc::get_user_instructions;
@@ -256,6 +271,7 @@ use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::protocol::TurnDiffEvent;
 use crate::protocol::WarningEvent;
+use crate::replay::RecordedToolOutputs;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
@@ -359,6 +375,8 @@ impl Codex {
         plugins_manager: Arc<PluginsManager>,
         mcp_manager: Arc<McpManager>,
         file_watcher: Arc<FileWatcher>,
+        code_index: Arc<CodeIndexManager>,
+        replay_tool_outputs: Option<Arc<RecordedToolOutputs>>,
         conversation_history: InitialHistory,
         session_source: SessionSource,
         agent_control: AgentControl,
@@ -557,6 +575,8 @@ impl Codex {
             plugins_manager,
             mcp_manager.clone(),
             file_watcher,
+            code_index,
+            replay_tool_outputs,
             agent_control,
         )
         .instrument(session_init_span)
@@ -737,6 +757,9 @@ pub(crate) struct TurnContext {
     pub(crate) turn_metadata_state: Arc<TurnMetadataState>,
     pub(crate) turn_skills: TurnSkillsContext,
     pub(crate) turn_timing_state: Arc<TurnTimingState>,
+    /// Client for `provider` once the turn has moved to a `model_fallbacks` entry; `None`
+    /// while the turn samples through the session's client.
+    pub(crate) fallback_model_client: Option<ModelClient>,
 }
 
 #[derive(Debug, Clone)]
@@ -845,9 +868,41 @@ impl TurnContext {
             turn_metadata_state: self.turn_metadata_state.clone(),
             turn_skills: self.turn_skills.clone(),
             turn_timing_state: Arc::clone(&self.turn_timing_state),
+            fallback_model_client: self.fallback_model_client.clone(),
         }
     }
 
+    /// Returns a copy of this turn context that samples `fallback.model`
+    /// through `fallback.provider` for the rest of the turn.
+    pub(crate) async fn with_model_fallback(
+        &self,
+        fallback: &ModelFallback,
+        models_manager: &ModelsManager,
+        session_client: &ModelClient,
+    ) -> Self {
+        let mut turn_context = self
+            .with_model(fallback.model.clone(), models_manager)
+            .await;
+        let mut config = (*turn_context.config).clone();
+        config.model_provider_id = fallback.provider_id.clone();
+        config.model_provider = fallback.provider.clone();
+        turn_context.config = Arc::new(config);
+        turn_context.provider = fallback.provider.clone();
+        turn_context.fallback_model_client =
+            Some(session_client.with_provider(fallback.provider.clone()));
+        turn_context
+    }
+
+    /// Returns the client this turn samples through: the fallback provider's
+    /// client after a switch to a `model_fallbacks` entry, otherwise
+    /// `session_client`. Compaction must use it too so that it does not send
+    /// the fallback model to the original provider.
+    pub(crate) fn model_client<'a>(&'a self, session_client: &'a ModelClient) -> &'a ModelClient {
+        self.fallback_model_client
+            .as_ref()
+            .unwrap_or(session_client)
+    }
+
     pub(crate) fn resolve_path(&self, path: Option<String>) -> PathBuf {
         path.as_ref()
             .map(PathBuf::from)
@@ -1004,7 +1059,11 @@ impl SessionConfiguration {
         if let Some(sandbox_policy) = updates.sandbox_policy.clone() {
             next_configuration.sandbox_policy.set(sandbox_policy)?;
             next_configuration.file_system_sandbox_policy =
-                FileSystemSandboxPolicy::from(next_configuration.sandbox_policy.get());
+                FileSystemSandboxPolicy::from(next_configuration.sandbox_policy.get())
+                    .with_deny_read(self.file_system_sandbox_policy.deny_read.clone())
+                    .with_deny_read_scan_limit(
+                        self.file_system_sandbox_policy.deny_read_scan_limit,
+                    );
             next_configuration.network_sandbox_policy =
                 NetworkSandboxPolicy::from(next_configuration.sandbox_policy.get());
         }
@@ -1142,6 +1201,7 @@ impl Session {
                         };
                         sess.send_event_raw(event).await;
                     }
+                    Ok(FileWatcherEvent::WorkspaceChanged { .. }) => {}
                     Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
                 }
@@ -1237,6 +1297,7 @@ impl Session {
             turn_metadata_state,
             turn_skills: TurnSkillsContext::new(skills_outcome),
             turn_timing_state: Arc::new(TurnTimingState::default()),
+            fallback_model_client: None,
         }
     }
 
@@ -1255,6 +1316,8 @@ impl Session {
         plugins_manager: Arc<PluginsManager>,
         mcp_manager: Arc<McpManager>,
         file_watcher: Arc<FileWatcher>,
+        code_index: Arc<CodeIndexManager>,
+        replay_tool_outputs: Option<Arc<RecordedToolOutputs>>,
         agent_control: AgentControl,
     ) -> anyhow::Result<Arc<Self>> {
         debug!(
@@ -1592,7 +1655,14 @@ impl Session {
             ),
             hooks: Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
+                command_hooks: config.hooks.clone(),
             }),
+            notification_sinks: NotificationSinks::new(
+                config.notification_sinks.clone(),
+                conversation_id,
+                &session_configuration.cwd,
+                session_configuration.session_source.clone(),
+            ),
             rollout: Mutex::new(rollout_recorder),
             user_shell: Arc::new(default_shell),
             shell_snapshot_tx,
@@ -1607,10 +1677,13 @@ impl Session {
             plugins_manager: Arc::clone(&plugins_manager),
             mcp_manager: Arc::clone(&mcp_manager),
             file_watcher,
+            code_index,
+            replay_tool_outputs,
             agent_control,
             network_proxy,
             network_approval: Arc::clone(&network_approval),
             state_db: state_db_ctx.clone(),
+            exec_secrets: ExecSecrets::new(config.codex_home.clone()),
             model_client: ModelClient::new(
                 Some(Arc::clone(&auth_manager)),
                 conversation_id,
@@ -1705,6 +1778,7 @@ impl Session {
             config.codex_home.clone(),
             codex_apps_tools_cache_key(auth),
             tool_plugin_provenance,
+            Some(Arc::new(McpSampler::new(Arc::downgrade(&sess)))),
         )
         .await;
         {
@@ -1740,8 +1814,23 @@ impl Session {
         sess.schedule_startup_prewarm(session_configuration.base_instructions.clone())
             .await;
 
+        let session_start_source = match &initial_history {
+            InitialHistory::New => HookSessionStartSource::Startup,
+            InitialHistory::Resumed(_) => HookSessionStartSource::Resume,
+            InitialHistory::Forked(_) => HookSessionStartSource::Fork,
+        };
         // record_initial_history can emit events. We record only after the SessionConfiguredEvent is emitted.
         sess.record_initial_history(initial_history).await;
+        if sess.hooks().has_hooks_for(HookEventName::SessionStart) {
+            let turn_context = sess.new_default_turn().await;
+            let hook_event = HookEvent::SessionStart {
+                event: HookEventSessionStart {
+                    source: session_start_source,
+                    model: turn_context.model_info.slug.clone(),
+                },
+            };
+            sess.run_hooks(&turn_context, hook_event).await;
+        }
 
         memories::start_memories_startup_task(
             &sess,
@@ -2163,7 +2252,7 @@ impl Session {
     ) -> ConstraintResult<Arc<TurnContext>> {
         let (
             session_configuration,
-            sandbox_policy_changed,
+            sandbox_state_changed,
             previous_cwd,
             codex_home,
             session_source,
@@ -2172,14 +2261,15 @@ impl Session {
             match state.session_configuration.clone().apply(&updates) {
                 Ok(next) => {
                     let previous_cwd = state.session_configuration.cwd.clone();
-                    let sandbox_policy_changed =
-                        state.session_configuration.sandbox_policy != next.sandbox_policy;
+                    let sandbox_state_changed = state.session_configuration.sandbox_policy
+                        != next.sandbox_policy
+                        || state.session_configuration.cwd != next.cwd;
                     let codex_home = next.codex_home.clone();
                     let session_source = next.session_source.clone();
                     state.session_configuration = next.clone();
                     (
                         next,
-                        sandbox_policy_changed,
+                        sandbox_state_changed,
                         previous_cwd,
                         codex_home,
                         session_source,
@@ -2212,7 +2302,7 @@ impl Session {
                 sub_id,
                 session_configuration,
                 updates.final_output_json_schema,
-                sandbox_policy_changed,
+                sandbox_state_changed,
             )
             .await)
     }
@@ -2222,7 +2312,7 @@ impl Session {
         sub_id: String,
         session_configuration: SessionConfiguration,
         final_output_json_schema: Option<Option<Value>>,
-        sandbox_policy_changed: bool,
+        sandbox_state_changed: bool,
     ) -> Arc<TurnContext> {
         let per_turn_config = Self::build_per_turn_config(&session_configuration);
         self.services
@@ -2231,7 +2321,7 @@ impl Session {
             .await
             .set_approval_policy(&session_configuration.approval_policy);
 
-        if sandbox_policy_changed {
+        if sandbox_state_changed {
             let sandbox_state = SandboxState {
                 sandbox_policy: per_turn_config.permissions.sandbox_policy.get().clone(),
                 codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
@@ -2520,6 +2610,9 @@ impl Session {
         // Persist the event into rollout (recorder filters as needed)
         let rollout_items = vec![RolloutItem::EventMsg(event.msg.clone())];
         self.persist_rollout_items(&rollout_items).await;
+        self.services
+            .notification_sinks
+            .notify(&event, &self.services.exec_secrets);
         if let Err(e) = self.tx_event.send(event).await {
             debug!("dropping event because channel is closed: {e}");
         }
@@ -2538,6 +2631,9 @@ impl Session {
         self.persist_rollout_items(&[RolloutItem::EventMsg(event.msg.clone())])
             .await;
         self.flush_rollout().await;
+        self.services
+            .notification_sinks
+            .notify(&event, &self.services.exec_secrets);
         if let Err(e) = self.tx_event.send(event).await {
             debug!("dropping event because channel is closed: {e}");
         }
@@ -4012,6 +4108,77 @@ impl Session {
         &self.services.hooks
     }
 
+    /// Runs the hooks registered for `hook_event` and returns a message explaining why the
+    /// event was blocked when a hook denied it or failed with an abort.
+    pub(crate) async fn run_hooks(
+        &self,
+        turn_context: &TurnContext,
+        hook_event: HookEvent,
+    ) -> Option<String> {
+        let event_name = hook_event.name();
+        if !self.hooks().has_hooks_for(event_name) {
+            return None;
+        }
+        let hook_outcomes = self
+            .hooks()
+            .dispatch(HookPayload {
+                session_id: self.conversation_id,
+                cwd: turn_context.cwd.clone(),
+                client: turn_context.app_server_client_name.clone(),
+                triggered_at: chrono::Utc::now(),
+                hook_event,
+            })
+            .await;
+
+        for hook_outcome in hook_outcomes {
+            let hook_name = hook_outcome.hook_name;
+            match hook_outcome.result {
+                HookResult::Success | HookResult::RewriteToolInput(_) => {}
+                HookResult::FailedContinue(error) => {
+                    warn!(
+                        turn_id = %turn_context.sub_id,
+                        hook_name = %hook_name,
+                        error = %error,
+                        "{event_name} hook failed; continuing"
+                    );
+                }
+                HookResult::Deny { reason } => {
+                    return Some(format!("{event_name} hook '{hook_name}' denied: {reason}"));
+                }
+                HookResult::FailedAbort(error) => {
+                    warn!(
+                        turn_id = %turn_context.sub_id,
+                        hook_name = %hook_name,
+                        error = %error,
+                        "{event_name} hook failed; aborting operation"
+                    );
+                    return Some(format!(
+                        "{event_name} hook '{hook_name}' failed and aborted operation: {error}"
+                    ));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Compaction cannot be vetoed, so `before_compact` hooks are purely observational.
+    async fn run_before_compact_hooks(
+        &self,
+        turn_context: &TurnContext,
+        trigger: HookCompactTrigger,
+    ) {
+        let hook_event = HookEvent::BeforeCompact {
+            event: HookEventBeforeCompact {
+                turn_id: turn_context.sub_id.clone(),
+                trigger,
+            },
+        };
+        if let Some(message) = self.run_hooks(turn_context, hook_event).await {
+            warn!("{message}; compacting anyway");
+        }
+    }
+
     pub(crate) fn user_shell(&self) -> Arc<shell::Shell> {
         Arc::clone(&self.services.user_shell)
     }
@@ -4046,6 +4213,8 @@ impl Session {
             guard.cancel();
             *guard = CancellationToken::new();
         }
+        // Keep the sampler so token budgets span refreshes.
+        let sampler = self.services.mcp_connection_manager.read().await.sampler();
         let (refreshed_manager, cancel_token) = McpConnectionManager::new(
             &mcp_servers,
             store_mode,
@@ -4056,6 +4225,7 @@ impl Session {
             config.codex_home.clone(),
             codex_apps_tools_cache_key(auth.as_ref()),
             tool_plugin_provenance,
+            sampler,
         )
         .await;
         {
@@ -4260,6 +4430,18 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                     handlers::list_mcp_tools(&sess, &config, sub.id.clone()).await;
                     false
                 }
+                Op::ListMcpPrompts => {
+                    handlers::list_mcp_prompts(&sess, sub.id.clone()).await;
+                    false
+                }
+                Op::GetMcpPrompt {
+                    server,
+                    name,
+                    arguments,
+                } => {
+                    handlers::get_mcp_prompt(&sess, sub.id.clone(), server, name, arguments).await;
+                    false
+                }
                 Op::RefreshMcpServers { config } => {
                     handlers::refresh_mcp_servers(&sess, config).await;
                     false
@@ -4364,13 +4546,18 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
     debug!("Agent loop exited");
 }
 
+/// Each submission starts its own trace unless the submitter supplied one, so
+/// a turn and everything it does (model requests, tool calls, approvals) form
+/// one trace instead of hanging off the long-lived session loop span. The
+/// session span is kept reachable through a span link.
 fn submission_dispatch_span(sub: &Submission) -> tracing::Span {
     let dispatch_span = match &sub.op {
         Op::RealtimeConversationAudio(_) => {
-            debug_span!("submission_dispatch", submission.id = sub.id.as_str())
+            debug_span!(parent: None, "submission_dispatch", submission.id = sub.id.as_str())
         }
-        _ => info_span!("submission_dispatch", submission.id = sub.id.as_str()),
+        _ => info_span!(parent: None, "submission_dispatch", submission.id = sub.id.as_str()),
     };
+    dispatch_span.follows_from(Span::current());
     if let Some(trace) = sub.trace.as_ref()
         && !set_parent_from_w3c_trace_context(&dispatch_span, trace)
     {
@@ -4393,6 +4580,8 @@ mod handlers {
 
     use crate::mcp::auth::compute_auth_statuses;
     use crate::mcp::collect_mcp_snapshot_from_manager;
+    use crate::mcp::convert_mcp_prompts;
+    use crate::mcp::render_mcp_prompt;
     use crate::review_prompts::resolve_review_request;
     use crate::rollout::RolloutRecorder;
     use crate::rollout::session_index;
@@ -4406,7 +4595,9 @@ mod handlers {
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::GetMcpPromptResponseEvent;
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
+    use codex_protocol::protocol::ListMcpPromptsResponseEvent;
     use codex_protocol::protocol::ListRemoteSkillsResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
     use codex_protocol::protocol::McpServerRefreshConfig;
@@ -4436,6 +4627,7 @@ mod handlers {
     use codex_rmcp_client::ElicitationAction;
     use codex_rmcp_client::ElicitationResponse;
     use serde_json::Value;
+    use std::collections::HashMap;
     use std::path::PathBuf;
     use std::sync::Arc;
     use tracing::info;
@@ -4528,6 +4720,30 @@ mod handlers {
             .await;
         current_context.session_telemetry.user_prompt(&items);
 
+        let hook_event = HookEvent::UserPromptSubmit {
+            event: HookEventUserPromptSubmit {
+                turn_id: current_context.sub_id.clone(),
+                input_messages: items
+                    .iter()
+                    .filter_map(|item| match item {
+                        UserInput::Text { text, .. } => Some(text.clone()),
+                        _ => None,
+                    })
+                    .collect(),
+            },
+        };
+        if let Some(message) = sess.run_hooks(&current_context, hook_event).await {
+            sess.send_event(
+                &current_context,
+                EventMsg::Error(ErrorEvent {
+                    message,
+                    codex_error_info: None,
+                }),
+            )
+            .await;
+            return;
+        }
+
         // Attempt to inject input into current task.
         if let Err(SteerInputError::NoActiveTurn(items)) = sess.steer_input(items, None).await {
             sess.refresh_mcp_servers_if_requested(&current_context)
@@ -4750,6 +4966,51 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    pub async fn list_mcp_prompts(sess: &Session, sub_id: String) {
+        let prompts = sess
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .list_all_prompts()
+            .await
+            .into_iter()
+            .map(|(server, prompts)| (server, convert_mcp_prompts(prompts)))
+            .collect();
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ListMcpPromptsResponse(ListMcpPromptsResponseEvent { prompts }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn get_mcp_prompt(
+        sess: &Session,
+        sub_id: String,
+        server: String,
+        name: String,
+        arguments: HashMap<String, String>,
+    ) {
+        let result = sess
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .get_prompt(&server, &name, arguments)
+            .await
+            .map(render_mcp_prompt)
+            .map_err(|err| format!("{err:#}"));
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::GetMcpPromptResponse(GetMcpPromptResponseEvent {
+                server,
+                name,
+                result,
+            }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
     pub async fn list_custom_prompts(sess: &Session, sub_id: String) {
         let custom_prompts: Vec<CustomPrompt> =
             if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
@@ -4906,6 +5167,8 @@ mod handlers {
 
     pub async fn compact(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.run_before_compact_hooks(&turn_context, HookCompactTrigger::Manual)
+            .await;
 
         sess.spawn_task(
             Arc::clone(&turn_context),
@@ -5163,6 +5426,18 @@ mod handlers {
             .unified_exec_manager
             .terminate_all_processes()
             .await;
+        // Sub-agents share the worktrees of the user session, so only the root session cleans
+        // them up.
+        let is_subagent = {
+            let state = sess.state.lock().await;
+            matches!(
+                state.session_configuration.session_source,
+                SessionSource::SubAgent(_)
+            )
+        };
+        if !is_subagent {
+            sess.services.agent_control.worktrees().cleanup().await;
+        }
         info!("Shutting down Codex instance");
         let history = sess.clone_history().await;
         let turn_count = history
@@ -5175,6 +5450,15 @@ mod handlers {
             i64::try_from(turn_count).unwrap_or(0),
             &[],
         );
+        if sess.hooks().has_hooks_for(HookEventName::SessionEnd) {
+            let turn_context = sess.new_default_turn().await;
+            let hook_event = HookEvent::SessionEnd {
+                event: HookEventSessionEnd {
+                    turn_count: u64::try_from(turn_count).unwrap_or(u64::MAX),
+                },
+            };
+            sess.run_hooks(&turn_context, hook_event).await;
+        }
 
         // Gracefully flush and shutdown rollout recorder on session end so tests
         // that inspect the rollout file do not race with the background writer.
@@ -5355,6 +5639,7 @@ async fn spawn_review_thread(
         turn_metadata_state,
         turn_skills: TurnSkillsContext::new(parent_turn_context.turn_skills.outcome.clone()),
         turn_timing_state: Arc::new(TurnTimingState::default()),
+        fallback_model_client: None,
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -5374,6 +5659,7 @@ async fn spawn_review_thread(
     let review_request = ReviewRequest {
         target: resolved.target,
         user_facing_hint: Some(resolved.user_facing_hint),
+        paths: resolved.paths,
     };
     sess.send_event(&tc, EventMsg::EnteredReviewMode(review_request))
         .await;
@@ -5449,7 +5735,7 @@ fn errors_to_info(errors: &[SkillError]) -> Vec<SkillErrorInfo> {
 ///
 pub(crate) async fn run_turn(
     sess: Arc<Session>,
-    turn_context: Arc<TurnContext>,
+    mut turn_context: Arc<TurnContext>,
     input: Vec<UserInput>,
     prewarmed_client_session: Option<ModelClientSession>,
     cancellation_token: CancellationToken,
@@ -5459,7 +5745,10 @@ pub(crate) async fn run_turn(
     }
 
     let model_info = turn_context.model_info.clone();
-    let auto_compact_limit = model_info.auto_compact_token_limit().unwrap_or(i64::MAX);
+    let mut auto_compact_limit = model_info.auto_compact_token_limit().unwrap_or(i64::MAX);
+    // Each turn starts on the configured model and walks `model_fallbacks`
+    // at most once.
+    let mut model_fallbacks = turn_context.config.model_fallbacks.clone().into_iter();
 
     let event = EventMsg::TurnStarted(TurnStartedEvent {
         turn_id: turn_context.sub_id.clone(),
@@ -5479,7 +5768,8 @@ pub(crate) async fn run_turn(
         return None;
     }
 
-    let skills_outcome = Some(turn_context.turn_skills.outcome.as_ref());
+    let turn_skills_outcome = Arc::clone(&turn_context.turn_skills.outcome);
+    let skills_outcome = Some(turn_skills_outcome.as_ref());
 
     sess.record_context_updates_and_set_reference_context_item(turn_context.as_ref())
         .await;
@@ -5775,7 +6065,7 @@ pub(crate) async fn run_turn(
                     for hook_outcome in hook_outcomes {
                         let hook_name = hook_outcome.hook_name;
                         match hook_outcome.result {
-                            HookResult::Success => {}
+                            HookResult::Success | HookResult::RewriteToolInput(_) => {}
                             HookResult::FailedContinue(error) => {
                                 warn!(
                                     turn_id = %turn_context.sub_id,
@@ -5784,6 +6074,13 @@ pub(crate) async fn run_turn(
                                     "after_agent hook failed; continuing"
                                 );
                             }
+                            HookResult::Deny { reason } => {
+                                if abort_message.is_none() {
+                                    abort_message = Some(format!(
+                                        "after_agent hook '{hook_name}' denied turn completion: {reason}"
+                                    ));
+                                }
+                            }
                             HookResult::FailedAbort(error) => {
                                 let message = format!(
                                     "after_agent hook '{hook_name}' failed and aborted turn completion: {error}"
@@ -5836,6 +6133,24 @@ pub(crate) async fn run_turn(
                 break;
             }
             Err(e) => {
+                if let Some(reason) = e.model_fallback_reason()
+                    && let Some(fallback) = model_fallbacks.next()
+                {
+                    info!(
+                        "Turn error: {e:#}; continuing on fallback model {}",
+                        fallback.model
+                    );
+                    turn_context =
+                        switch_to_model_fallback(&sess, &turn_context, &fallback, reason, &e).await;
+                    auto_compact_limit = turn_context
+                        .model_info
+                        .auto_compact_token_limit()
+                        .unwrap_or(i64::MAX);
+                    client_session = turn_context
+                        .model_client(&sess.services.model_client)
+                        .new_session();
+                    continue;
+                }
                 info!("Turn error: {e:#}");
                 let event = EventMsg::Error(e.to_error_event(None));
                 sess.send_event(&turn_context, event).await;
@@ -5848,6 +6163,55 @@ pub(crate) async fn run_turn(
     last_agent_message
 }
 
+/// Moves the rest of the turn onto `fallback`, telling clients why the model
+/// changed and recording the new model in the rollout.
+async fn switch_to_model_fallback(
+    sess: &Session,
+    turn_context: &TurnContext,
+    fallback: &ModelFallback,
+    reason: ModelRerouteReason,
+    err: &CodexErr,
+) -> Arc<TurnContext> {
+    let fallback_context = Arc::new(
+        turn_context
+            .with_model_fallback(
+                fallback,
+                &sess.services.models_manager,
+                &sess.services.model_client,
+            )
+            .await,
+    );
+    let from_model = turn_context.model_info.slug.clone();
+    let to_model = fallback_context.model_info.slug.clone();
+    sess.send_event(
+        &fallback_context,
+        EventMsg::ModelReroute(ModelRerouteEvent {
+            from_model: from_model.clone(),
+            to_model: to_model.clone(),
+            reason,
+        }),
+    )
+    .await;
+    sess.send_event(
+        &fallback_context,
+        EventMsg::Warning(WarningEvent {
+            message: format!(
+                "{from_model} failed ({err}); continuing this turn on {to_model} via {}.",
+                fallback.provider_id
+            ),
+        }),
+    )
+    .await;
+    sess.record_context_updates_and_set_reference_context_item(fallback_context.as_ref())
+        .await;
+    sess.set_previous_turn_settings(Some(PreviousTurnSettings {
+        model: to_model,
+        realtime_active: Some(fallback_context.realtime_active),
+    }))
+    .await;
+    fallback_context
+}
+
 async fn run_pre_sampling_compact(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
@@ -5921,6 +6285,25 @@ async fn run_auto_compact(
     turn_context: &Arc<TurnContext>,
     initial_context_injection: InitialContextInjection,
 ) -> CodexResult<()> {
+    sess.run_before_compact_hooks(turn_context, HookCompactTrigger::Auto)
+        .await;
+    let strategy = turn_context.config.compaction_strategy;
+    if strategy != CompactionStrategy::Summary {
+        let target_tokens = turn_context
+            .model_info
+            .auto_compact_token_limit()
+            .or_else(|| turn_context.model_context_window())
+            .map_or(0, |limit| limit / 2);
+        if run_inline_pruning_compact_task(sess, turn_context, strategy, target_tokens).await? {
+            return Ok(());
+        }
+        sess.notify_background_event(
+            turn_context.as_ref(),
+            "Pruning old tool outputs did not free enough context; falling back to a summary."
+                .to_string(),
+        )
+        .await;
+    }
     if should_use_remote_compact_task(&turn_context.provider) {
         run_inline_remote_auto_compact_task(
             Arc::clone(sess),
@@ -6108,6 +6491,7 @@ fn build_prompt(
         base_instructions,
         personality: turn_context.personality,
         output_schema: turn_context.final_output_json_schema.clone(),
+        max_output_tokens: None,
     }
 }
 #[allow(clippy::too_many_arguments)]
@@ -6557,6 +6941,9 @@ fn realtime_text_for_event(msg: &EventMsg) -> Option<String> {
         | EventMsg::GetHistoryEntryResponse(_)
         | EventMsg::McpListToolsResponse(_)
         | EventMsg::ListCustomPromptsResponse(_)
+        | EventMsg::ListMcpPromptsResponse(_)
+        | EventMsg::McpPromptListChanged(_)
+        | EventMsg::GetMcpPromptResponse(_)
         | EventMsg::PromptTraceResponse(_)
         | EventMsg::ListSkillsResponse(_)
         | EventMsg::ListRemoteSkillsResponse(_)
@@ -6868,8 +7255,15 @@ async fn drain_in_flight(
 #[instrument(level = "trace",
     skip_all,
     fields(
+        otel.name = "model_request",
         turn_id = %turn_context.sub_id,
-        model = %turn_context.model_info.slug
+        model = %turn_context.model_info.slug,
+        provider = %turn_context.provider.name,
+        input_tokens = field::Empty,
+        cached_input_tokens = field::Empty,
+        output_tokens = field::Empty,
+        reasoning_output_tokens = field::Empty,
+        total_tokens = field::Empty
     )
 )]
 async fn try_run_sampling_request(
@@ -7089,6 +7483,14 @@ async fn try_run_sampling_request(
                     &mut assistant_message_stream_parsers,
                 )
                 .await;
+                if let Some(usage) = token_usage.as_ref() {
+                    let span = Span::current();
+                    span.record("input_tokens", usage.input_tokens);
+                    span.record("cached_input_tokens", usage.cached_input_tokens);
+                    span.record("output_tokens", usage.output_tokens);
+                    span.record("reasoning_output_tokens", usage.reasoning_output_tokens);
+                    span.record("total_tokens", usage.total_tokens);
+                }
                 sess.update_token_usage_info(&turn_context, token_usage.as_ref())
                     .await;
                 should_emit_turn_diff = true;
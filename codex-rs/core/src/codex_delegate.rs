@@ -59,6 +59,8 @@ pub(crate) async fn run_codex_thread_interactive(
         Arc::clone(&parent_session.services.plugins_manager),
         Arc::clone(&parent_session.services.mcp_manager),
         Arc::clone(&parent_session.services.file_watcher),
+        Arc::clone(&parent_session.services.code_index),
+        parent_session.services.replay_tool_outputs.clone(),
         initial_history.unwrap_or(InitialHistory::New),
         SessionSource::SubAgent(subagent_source),
         parent_session.services.agent_control.clone(),
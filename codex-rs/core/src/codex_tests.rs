@@ -1965,6 +1965,7 @@ async fn session_new_fails_when_zsh_fork_enabled_without_zsh_path() {
         plugins_manager,
         mcp_manager,
         Arc::new(FileWatcher::noop()),
+        CodeIndexManager::new(&config.codex_home, Arc::new(FileWatcher::noop())),
         AgentControl::default(),
     )
     .await;
@@ -2056,6 +2057,7 @@ pub(crate) async fn make_session_and_context() -> (Session, TurnContext) {
     let network_approval = Arc::new(NetworkApprovalService::default());
 
     let file_watcher = Arc::new(FileWatcher::noop());
+    let code_index = CodeIndexManager::new(&config.codex_home, Arc::clone(&file_watcher));
     let services = SessionServices {
         mcp_connection_manager: Arc::new(RwLock::new(
             McpConnectionManager::new_mcp_connection_manager_for_tests(
@@ -2074,7 +2076,14 @@ pub(crate) async fn make_session_and_context() -> (Session, TurnContext) {
         ),
         hooks: Hooks::new(HooksConfig {
             legacy_notify_argv: config.notify.clone(),
+            command_hooks: config.hooks.clone(),
         }),
+        notification_sinks: NotificationSinks::new(
+            config.notification_sinks.clone(),
+            conversation_id,
+            &session_configuration.cwd,
+            session_configuration.session_source.clone(),
+        ),
         rollout: Mutex::new(None),
         user_shell: Arc::new(default_user_shell()),
         shell_snapshot_tx: watch::channel(None).0,
@@ -2089,10 +2098,13 @@ pub(crate) async fn make_session_and_context() -> (Session, TurnContext) {
         plugins_manager,
         mcp_manager,
         file_watcher,
+        code_index,
+        replay_tool_outputs: None,
         agent_control,
         network_proxy: None,
         network_approval: Arc::clone(&network_approval),
         state_db: None,
+        exec_secrets: ExecSecrets::new(config.codex_home.clone()),
         model_client: ModelClient::new(
             Some(auth_manager.clone()),
             conversation_id,
@@ -2256,6 +2268,36 @@ fn submission_dispatch_span_prefers_submission_trace_context() {
     );
 }
 
+#[test]
+fn submission_dispatch_span_starts_new_trace_without_submission_trace() {
+    init_test_tracing();
+
+    let ambient_parent = W3cTraceContext {
+        traceparent: Some("00-00000000000000000000000000000077-0000000000000088-01".into()),
+        tracestate: None,
+    };
+    let ambient_span = info_span!("ambient");
+    assert!(set_parent_from_w3c_trace_context(
+        &ambient_span,
+        &ambient_parent
+    ));
+
+    let dispatch_span = ambient_span.in_scope(|| {
+        submission_dispatch_span(&Submission {
+            id: "sub-1".into(),
+            op: Op::Interrupt,
+            trace: None,
+        })
+    });
+
+    let trace_id = dispatch_span.context().span().span_context().trace_id();
+    assert_ne!(trace_id, TraceId::INVALID);
+    assert_ne!(
+        trace_id,
+        TraceId::from_hex("00000000000000000000000000000077").expect("trace id")
+    );
+}
+
 #[test]
 fn submission_dispatch_span_uses_debug_for_realtime_audio() {
     init_test_tracing();
@@ -2463,6 +2505,7 @@ pub(crate) async fn make_session_and_context_with_dynamic_tools_and_rx(
     let network_approval = Arc::new(NetworkApprovalService::default());
 
     let file_watcher = Arc::new(FileWatcher::noop());
+    let code_index = CodeIndexManager::new(&config.codex_home, Arc::clone(&file_watcher));
     let services = SessionServices {
         mcp_connection_manager: Arc::new(RwLock::new(
             McpConnectionManager::new_mcp_connection_manager_for_tests(
@@ -2481,7 +2524,14 @@ pub(crate) async fn make_session_and_context_with_dynamic_tools_and_rx(
         ),
         hooks: Hooks::new(HooksConfig {
             legacy_notify_argv: config.notify.clone(),
+            command_hooks: config.hooks.clone(),
         }),
+        notification_sinks: NotificationSinks::new(
+            config.notification_sinks.clone(),
+            conversation_id,
+            &session_configuration.cwd,
+            session_configuration.session_source.clone(),
+        ),
         rollout: Mutex::new(None),
         user_shell: Arc::new(default_user_shell()),
         shell_snapshot_tx: watch::channel(None).0,
@@ -2496,10 +2546,13 @@ pub(crate) async fn make_session_and_context_with_dynamic_tools_and_rx(
         plugins_manager,
         mcp_manager,
         file_watcher,
+        code_index,
+        replay_tool_outputs: None,
         agent_control,
         network_proxy: None,
         network_approval: Arc::clone(&network_approval),
         state_db: None,
+        exec_secrets: ExecSecrets::new(config.codex_home.clone()),
         model_client: ModelClient::new(
             Some(Arc::clone(&auth_manager)),
             conversation_id,
@@ -221,6 +221,7 @@ async fn guardian_subagent_does_not_inherit_parent_exec_policy_rules() {
                 decision: Decision::Forbidden,
                 resolved_program: None,
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         }
     );
@@ -239,6 +240,7 @@ async fn guardian_subagent_does_not_inherit_parent_exec_policy_rules() {
     ));
     let mcp_manager = Arc::new(McpManager::new(Arc::clone(&plugins_manager)));
     let file_watcher = Arc::new(FileWatcher::noop());
+    let code_index = CodeIndexManager::new(&config.codex_home, Arc::clone(&file_watcher));
 
     let CodexSpawnOk { codex, .. } = Codex::spawn(
         config,
@@ -248,6 +250,8 @@ async fn guardian_subagent_does_not_inherit_parent_exec_policy_rules() {
         plugins_manager,
         mcp_manager,
         file_watcher,
+        code_index,
+        None,
         InitialHistory::New,
         SessionSource::SubAgent(SubAgentSource::Other(GUARDIAN_SUBAGENT_NAME.to_string())),
         AgentControl::default(),
@@ -0,0 +1,447 @@
+//! Compaction strategies that shrink history by dropping old tool output
+//! instead of replacing the whole conversation with a summary.
+//!
+//! `prune` is deterministic and makes no model call: the content of the
+//! oldest tool outputs is replaced with a short marker until the history fits
+//! the target. Messages, edits and the most recent tool outputs are kept
+//! verbatim. `hybrid` prunes the same outputs but first asks the model to
+//! summarize them, and keeps that summary in place of the newest pruned one.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use codex_protocol::items::ContextCompactionItem;
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ResponseItem;
+use futures::StreamExt;
+
+use crate::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::CompactionStrategy;
+use crate::context_manager::estimate_response_item_model_visible_bytes;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::protocol::CompactedItem;
+use crate::protocol::EventMsg;
+use crate::protocol::TurnStartedEvent;
+use crate::protocol::WarningEvent;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::approx_tokens_from_byte_count_i64;
+use crate::truncate::truncate_text;
+
+/// Replaces the content of a pruned tool output.
+pub(crate) const PRUNED_TOOL_OUTPUT: &str = "[output pruned during compaction]";
+const TOOL_OUTPUTS_SUMMARY_PROMPT: &str =
+    include_str!("../templates/compact/tool_outputs_prompt.md");
+const TOOL_OUTPUTS_SUMMARY_PREFIX: &str = "Summary of earlier tool outputs removed from history:";
+
+/// Number of most recent tool outputs that are never pruned.
+const KEEP_RECENT_TOOL_OUTPUTS: usize = 4;
+/// Tools whose calls record edits; their outputs are small and always kept.
+const EDIT_TOOL_NAMES: &[&str] = &["apply_patch"];
+/// Budget for the tool output transcript sent to the model by `hybrid`.
+const TOOL_OUTPUTS_SUMMARY_MAX_TOKENS: usize = 60_000;
+/// Per-output budget inside the `hybrid` transcript.
+const TOOL_OUTPUT_TRANSCRIPT_MAX_TOKENS: usize = 2_000;
+
+/// History after pruning, with the indices of the outputs that were pruned
+/// (oldest first) and the new token estimate.
+#[derive(Debug)]
+pub(crate) struct PrunedHistory {
+    pub(crate) items: Vec<ResponseItem>,
+    pub(crate) pruned: Vec<usize>,
+    pub(crate) estimated_tokens: i64,
+}
+
+impl PrunedHistory {
+    fn fits(&self, target_tokens: Option<i64>) -> bool {
+        target_tokens.is_none_or(|target| self.estimated_tokens <= target)
+    }
+}
+
+/// Runs a `prune` or `hybrid` compaction as a standalone task (`/compact`).
+/// Every eligible tool output is pruned.
+pub(crate) async fn run_pruning_compact_task(
+    sess: Arc<Session>,
+    turn_context: Arc<TurnContext>,
+    strategy: CompactionStrategy,
+) -> CodexResult<()> {
+    let start_event = EventMsg::TurnStarted(TurnStartedEvent {
+        turn_id: turn_context.sub_id.clone(),
+        model_context_window: turn_context.model_context_window(),
+        collaboration_mode_kind: turn_context.collaboration_mode.mode,
+    });
+    sess.send_event(&turn_context, start_event).await;
+    run_pruning_compact_task_inner(&sess, &turn_context, strategy, None).await?;
+    Ok(())
+}
+
+/// Runs a `prune` or `hybrid` compaction during a turn, pruning the oldest
+/// tool outputs until the history fits `target_tokens`.
+///
+/// Returns `false` without touching the history when pruning every eligible
+/// output would still not fit, so the caller can fall back to a summary.
+pub(crate) async fn run_inline_pruning_compact_task(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    strategy: CompactionStrategy,
+    target_tokens: i64,
+) -> CodexResult<bool> {
+    run_pruning_compact_task_inner(sess, turn_context, strategy, Some(target_tokens)).await
+}
+
+async fn run_pruning_compact_task_inner(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    strategy: CompactionStrategy,
+    target_tokens: Option<i64>,
+) -> CodexResult<bool> {
+    let history = sess.clone_history().await;
+    let estimated_tokens = history
+        .estimate_token_count_with_base_instructions(&sess.get_base_instructions().await)
+        .unwrap_or_default();
+    let mut pruned = prune_tool_outputs(
+        history.raw_items().to_vec(),
+        estimated_tokens,
+        target_tokens,
+    );
+    if !pruned.fits(target_tokens) {
+        return Ok(false);
+    }
+
+    let compaction_item = TurnItem::ContextCompaction(ContextCompactionItem::new());
+    sess.emit_turn_item_started(turn_context, &compaction_item)
+        .await;
+
+    if strategy == CompactionStrategy::Hybrid
+        && let Some(&newest) = pruned.pruned.last()
+    {
+        let transcript = tool_outputs_transcript(history.raw_items(), &pruned.pruned);
+        match summarize_tool_outputs(sess, turn_context, transcript).await {
+            Ok(summary) => {
+                if let Some(item) = pruned.items.get_mut(newest) {
+                    set_tool_output_text(
+                        item,
+                        format!("{PRUNED_TOOL_OUTPUT}\n\n{TOOL_OUTPUTS_SUMMARY_PREFIX}\n{summary}"),
+                    );
+                }
+            }
+            Err(CodexErr::Interrupted) => return Err(CodexErr::Interrupted),
+            Err(err) => {
+                sess.send_event(
+                    turn_context,
+                    EventMsg::Warning(WarningEvent {
+                        message: format!(
+                            "Could not summarize pruned tool outputs; they were dropped instead: {err}"
+                        ),
+                    }),
+                )
+                .await;
+            }
+        }
+    }
+
+    let pruned_count = pruned.pruned.len();
+    let message = format!("Pruned {pruned_count} tool output(s) during compaction.");
+    let compacted_item = CompactedItem {
+        message: message.clone(),
+        replacement_history: Some(pruned.items.clone()),
+    };
+    let reference_context_item = sess.reference_context_item().await;
+    sess.replace_compacted_history(pruned.items, reference_context_item, compacted_item)
+        .await;
+    sess.recompute_token_usage(turn_context).await;
+    sess.emit_turn_item_completed(turn_context, compaction_item)
+        .await;
+    sess.notify_background_event(turn_context.as_ref(), message)
+        .await;
+    Ok(true)
+}
+
+/// Prunes tool outputs oldest first until `estimated_tokens` fits
+/// `target_tokens`, or prunes every eligible output when there is no target.
+pub(crate) fn prune_tool_outputs(
+    mut items: Vec<ResponseItem>,
+    mut estimated_tokens: i64,
+    target_tokens: Option<i64>,
+) -> PrunedHistory {
+    let mut pruned = Vec::new();
+    for index in prunable_tool_outputs(&items) {
+        if target_tokens.is_some_and(|target| estimated_tokens <= target) {
+            break;
+        }
+        let Some(item) = items.get_mut(index) else {
+            continue;
+        };
+        let before = estimate_item_tokens(item);
+        set_tool_output_text(item, PRUNED_TOOL_OUTPUT.to_string());
+        estimated_tokens = estimated_tokens.saturating_sub(before - estimate_item_tokens(item));
+        pruned.push(index);
+    }
+    PrunedHistory {
+        items,
+        pruned,
+        estimated_tokens,
+    }
+}
+
+/// Indices of tool outputs that may be pruned, oldest first: outputs of edit
+/// tools, outputs that are already pruned and the most recent outputs are
+/// excluded.
+fn prunable_tool_outputs(items: &[ResponseItem]) -> Vec<usize> {
+    let edit_call_ids: HashSet<&str> = items
+        .iter()
+        .filter_map(|item| match item {
+            ResponseItem::FunctionCall { name, call_id, .. }
+            | ResponseItem::CustomToolCall { name, call_id, .. }
+                if EDIT_TOOL_NAMES.contains(&name.as_str()) =>
+            {
+                Some(call_id.as_str())
+            }
+            _ => None,
+        })
+        .collect();
+    let outputs = items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| match item {
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput { call_id, output }
+                if !edit_call_ids.contains(call_id.as_str()) =>
+            {
+                Some((index, output))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let eligible = outputs.len().saturating_sub(KEEP_RECENT_TOOL_OUTPUTS);
+    outputs
+        .into_iter()
+        .take(eligible)
+        .filter(|(_, output)| !output.text_content().is_some_and(is_pruned_output))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+fn is_pruned_output(text: &str) -> bool {
+    text.starts_with(PRUNED_TOOL_OUTPUT)
+}
+
+fn set_tool_output_text(item: &mut ResponseItem, text: String) {
+    if let ResponseItem::FunctionCallOutput { output, .. }
+    | ResponseItem::CustomToolCallOutput { output, .. } = item
+    {
+        *output = FunctionCallOutputPayload {
+            success: output.success,
+            ..FunctionCallOutputPayload::from_text(text)
+        };
+    }
+}
+
+fn estimate_item_tokens(item: &ResponseItem) -> i64 {
+    approx_tokens_from_byte_count_i64(estimate_response_item_model_visible_bytes(item))
+}
+
+/// Renders the calls and outputs at `indices` for the `hybrid` summary request.
+fn tool_outputs_transcript(items: &[ResponseItem], indices: &[usize]) -> String {
+    let sections = indices
+        .iter()
+        .filter_map(|&index| {
+            let (call_id, output) = match items.get(index)? {
+                ResponseItem::FunctionCallOutput { call_id, output }
+                | ResponseItem::CustomToolCallOutput { call_id, output } => (call_id, output),
+                _ => return None,
+            };
+            let call = items.iter().find_map(|item| match item {
+                ResponseItem::FunctionCall {
+                    name,
+                    arguments,
+                    call_id: id,
+                    ..
+                } if id == call_id => Some((name.as_str(), arguments.as_str())),
+                ResponseItem::CustomToolCall {
+                    name,
+                    input,
+                    call_id: id,
+                    ..
+                } if id == call_id => Some((name.as_str(), input.as_str())),
+                _ => None,
+            });
+            let (name, arguments) = call.unwrap_or(("tool", ""));
+            let output = output.body.to_text().unwrap_or_default();
+            Some(format!(
+                "## {name}\nInput: {}\nOutput:\n{}",
+                truncate_text(arguments, TruncationPolicy::Tokens(200)),
+                truncate_text(
+                    &output,
+                    TruncationPolicy::Tokens(TOOL_OUTPUT_TRANSCRIPT_MAX_TOKENS)
+                ),
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    truncate_text(
+        &sections,
+        TruncationPolicy::Tokens(TOOL_OUTPUTS_SUMMARY_MAX_TOKENS),
+    )
+}
+
+async fn summarize_tool_outputs(
+    sess: &Session,
+    turn_context: &TurnContext,
+    transcript: String,
+) -> CodexResult<String> {
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!("{TOOL_OUTPUTS_SUMMARY_PROMPT}\n{transcript}"),
+            }],
+            end_turn: None,
+            phase: None,
+        }],
+        base_instructions: sess.get_base_instructions().await,
+        personality: turn_context.personality,
+        ..Default::default()
+    };
+    let turn_metadata_header = turn_context.turn_metadata_state.current_header_value();
+    let mut client_session = turn_context
+        .model_client(&sess.services.model_client)
+        .new_session();
+    let mut stream = client_session
+        .stream(
+            &prompt,
+            &turn_context.model_info,
+            &turn_context.session_telemetry,
+            turn_context.reasoning_effort,
+            turn_context.reasoning_summary,
+            turn_context.config.service_tier,
+            turn_metadata_header.as_deref(),
+        )
+        .await?;
+
+    let mut summary = String::new();
+    while let Some(event) = stream.next().await.transpose()? {
+        match event {
+            ResponseEvent::OutputTextDelta(delta) => summary.push_str(&delta),
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. })
+                if summary.is_empty() =>
+            {
+                if let Some(text) = crate::compact::content_items_to_text(&content) {
+                    summary.push_str(&text);
+                }
+            }
+            ResponseEvent::Completed { .. } => break,
+            _ => {}
+        }
+    }
+    let summary = summary.trim();
+    if summary.is_empty() {
+        return Err(CodexErr::Stream(
+            "model returned an empty tool output summary".into(),
+            None,
+        ));
+    }
+    Ok(summary.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn call(name: &str, call_id: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: "{}".to_string(),
+            call_id: call_id.to_string(),
+        }
+    }
+
+    fn output(call_id: &str, text: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload::from_text(text.to_string()),
+        }
+    }
+
+    fn output_text(item: &ResponseItem) -> &str {
+        match item {
+            ResponseItem::FunctionCallOutput { output, .. } => {
+                output.text_content().unwrap_or_default()
+            }
+            other => panic!("expected a tool output, got {other:?}"),
+        }
+    }
+
+    /// An edit, then `count` shell calls with large outputs.
+    fn history(count: usize) -> Vec<ResponseItem> {
+        let mut items = vec![
+            call("apply_patch", "edit"),
+            output(
+                "edit",
+                "Success. Updated the following files:\nM src/lib.rs",
+            ),
+        ];
+        for i in 0..count {
+            let call_id = format!("shell-{i}");
+            items.push(call("shell", &call_id));
+            items.push(output(&call_id, &"x".repeat(4_000)));
+        }
+        items
+    }
+
+    #[test]
+    fn prunes_oldest_outputs_and_keeps_edits_and_recent_outputs() {
+        let pruned = prune_tool_outputs(history(6), 100_000, None);
+
+        assert_eq!(pruned.pruned, vec![3, 5]);
+        assert!(output_text(&pruned.items[1]).starts_with("Success."));
+        assert_eq!(output_text(&pruned.items[3]), PRUNED_TOOL_OUTPUT);
+        assert_eq!(output_text(&pruned.items[5]), PRUNED_TOOL_OUTPUT);
+        for index in [7, 9, 11, 13] {
+            assert_eq!(output_text(&pruned.items[index]).len(), 4_000);
+        }
+        assert!(pruned.estimated_tokens < 100_000 - 1_900);
+    }
+
+    #[test]
+    fn stops_pruning_once_target_is_reached() {
+        let pruned = prune_tool_outputs(history(8), 10_000, Some(9_500));
+
+        assert_eq!(pruned.pruned, vec![3]);
+        assert!(pruned.fits(Some(9_500)));
+    }
+
+    #[test]
+    fn reports_when_target_is_unreachable() {
+        let pruned = prune_tool_outputs(history(5), 10_000, Some(1_000));
+
+        assert_eq!(pruned.pruned, vec![3]);
+        assert!(!pruned.fits(Some(1_000)));
+    }
+
+    #[test]
+    fn already_pruned_outputs_are_skipped() {
+        let first = prune_tool_outputs(history(6), 100_000, None);
+        let second = prune_tool_outputs(first.items, 100_000, None);
+
+        assert_eq!(second.pruned, Vec::<usize>::new());
+    }
+
+    #[test]
+    fn transcript_pairs_outputs_with_their_calls() {
+        let items = vec![call("shell", "a"), output("a", "cargo test: 2 failed")];
+
+        assert_eq!(
+            tool_outputs_transcript(&items, &[1]),
+            "## shell\nInput: {}\nOutput:\ncargo test: 2 failed"
+        );
+    }
+}
@@ -99,11 +99,11 @@ async fn run_remote_compact_task_inner_impl(
         base_instructions,
         personality: turn_context.personality,
         output_schema: None,
+        max_output_tokens: None,
     };
 
-    let mut new_history = sess
-        .services
-        .model_client
+    let mut new_history = turn_context
+        .model_client(&sess.services.model_client)
         .compact_conversation_history(
             &prompt,
             &turn_context.model_info,
@@ -1,18 +1,23 @@
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
 use crate::config::edit::apply_blocking;
+use crate::config::types::CompactionStrategy;
 use crate::config::types::FeedbackConfigToml;
 use crate::config::types::HistoryPersistence;
 use crate::config::types::McpServerTransportConfig;
 use crate::config::types::MemoriesConfig;
 use crate::config::types::MemoriesToml;
 use crate::config::types::ModelAvailabilityNuxConfig;
+use crate::config::types::ModelPricing;
 use crate::config::types::NotificationMethod;
+use crate::config::types::NotificationSinkEvent;
+use crate::config::types::NotificationSinkTarget;
 use crate::config::types::Notifications;
 use crate::config_loader::RequirementSource;
 use crate::features::Feature;
 use assert_matches::assert_matches;
 use codex_config::CONFIG_TOML_FILE;
+use codex_hooks::HookEventName;
 use codex_protocol::permissions::FileSystemAccessMode;
 use codex_protocol::permissions::FileSystemPath;
 use codex_protocol::permissions::FileSystemSandboxEntry;
@@ -47,6 +52,7 @@ fn stdio_mcp(command: &str) -> McpServerConfig {
         disabled_tools: None,
         scopes: None,
         oauth_resource: None,
+        sampling_token_budget: None,
     }
 }
 
@@ -67,6 +73,7 @@ fn http_mcp(url: &str) -> McpServerConfig {
         disabled_tools: None,
         scopes: None,
         oauth_resource: None,
+        sampling_token_budget: None,
     }
 }
 
@@ -155,6 +162,137 @@ consolidation_model = "gpt-5"
     );
 }
 
+#[test]
+fn config_toml_deserializes_command_hooks() {
+    let toml = r#"
+[[hooks.before_tool_use]]
+command = ["/usr/local/bin/guard", "--strict"]
+timeout_ms = 5000
+
+[[hooks.session_end]]
+name = "archive"
+command = ["archive-session"]
+"#;
+    let cfg = toml::from_str::<ConfigToml>(toml).expect("TOML deserialization should succeed");
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect("load config from hooks settings");
+
+    assert_eq!(
+        config.hooks,
+        vec![
+            CommandHookConfig {
+                name: "guard".to_string(),
+                event: HookEventName::BeforeToolUse,
+                argv: vec!["/usr/local/bin/guard".to_string(), "--strict".to_string()],
+                timeout: Duration::from_millis(5000),
+                fail_closed: true,
+            },
+            CommandHookConfig {
+                name: "archive".to_string(),
+                event: HookEventName::SessionEnd,
+                argv: vec!["archive-session".to_string()],
+                timeout: codex_hooks::DEFAULT_COMMAND_HOOK_TIMEOUT,
+                fail_closed: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn config_toml_deserializes_notification_sinks() {
+    let socket = test_absolute_path("/tmp/codex-notify.sock");
+    let toml = format!(
+        r#"
+[[notification_sinks]]
+type = "webhook"
+url = "https://chat.example.com/hook"
+headers = {{ Authorization = "Bearer token" }}
+body = '{{"text": "{{{{message}}}}"}}'
+events = ["approval_requested", "turn_failed"]
+
+[[notification_sinks]]
+type = "command"
+command = ["notify-send", "Codex"]
+
+[[notification_sinks]]
+type = "unix_socket"
+path = '{}'
+"#,
+        socket.display()
+    );
+    let cfg = toml::from_str::<ConfigToml>(&toml).expect("TOML deserialization should succeed");
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect("load config from notification sink settings");
+
+    assert_eq!(
+        config.notification_sinks,
+        vec![
+            NotificationSinkToml {
+                events: Some(vec![
+                    NotificationSinkEvent::ApprovalRequested,
+                    NotificationSinkEvent::TurnFailed,
+                ]),
+                target: NotificationSinkTarget::Webhook {
+                    url: "https://chat.example.com/hook".to_string(),
+                    headers: HashMap::from([(
+                        "Authorization".to_string(),
+                        "Bearer token".to_string(),
+                    )]),
+                    body: Some(r#"{"text": "{{message}}"}"#.to_string()),
+                },
+            },
+            NotificationSinkToml {
+                events: None,
+                target: NotificationSinkTarget::Command {
+                    command: vec!["notify-send".to_string(), "Codex".to_string()],
+                },
+            },
+            NotificationSinkToml {
+                events: None,
+                target: NotificationSinkTarget::UnixSocket { path: socket },
+            },
+        ]
+    );
+    assert!(!config.notification_sinks[0].accepts(NotificationSinkEvent::TurnCompleted));
+    assert!(config.notification_sinks[1].accepts(NotificationSinkEvent::TurnCompleted));
+}
+
+#[test]
+fn config_toml_deserializes_model_pricing() {
+    let toml = r#"
+[model_pricing."gpt-5"]
+input = 1.25
+cached_input = 0.125
+output = 10.0
+"#;
+    let cfg = toml::from_str::<ConfigToml>(toml).expect("TOML deserialization should succeed");
+    let config = Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides::default(),
+        tempdir().expect("tempdir").path().to_path_buf(),
+    )
+    .expect("load config from pricing settings");
+
+    let pricing = config.model_pricing["gpt-5"];
+    assert_eq!(
+        pricing,
+        ModelPricing {
+            input: 1.25,
+            cached_input: Some(0.125),
+            output: 10.0,
+        }
+    );
+    assert_eq!(pricing.cost_usd(2_000_000, 1_000_000, 100_000), 2.375);
+}
+
 #[test]
 fn config_toml_deserializes_model_availability_nux() {
     let toml = r#"
@@ -1549,6 +1687,7 @@ async fn replace_mcp_servers_round_trips_entries() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         },
     );
 
@@ -1705,6 +1844,7 @@ async fn replace_mcp_servers_serializes_env_sorted() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         },
     )]);
 
@@ -1777,6 +1917,7 @@ async fn replace_mcp_servers_serializes_env_vars() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         },
     )]);
 
@@ -1829,6 +1970,7 @@ async fn replace_mcp_servers_serializes_cwd() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         },
     )]);
 
@@ -1879,6 +2021,7 @@ async fn replace_mcp_servers_streamable_http_serializes_bearer_token() -> anyhow
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         },
     )]);
 
@@ -1945,6 +2088,7 @@ async fn replace_mcp_servers_streamable_http_serializes_custom_headers() -> anyh
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         },
     )]);
     apply_blocking(
@@ -2023,6 +2167,7 @@ async fn replace_mcp_servers_streamable_http_removes_optional_sections() -> anyh
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         },
     )]);
 
@@ -2054,6 +2199,7 @@ async fn replace_mcp_servers_streamable_http_removes_optional_sections() -> anyh
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         },
     );
     apply_blocking(
@@ -2120,6 +2266,7 @@ async fn replace_mcp_servers_streamable_http_isolates_headers_between_servers()
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
         ),
         (
@@ -2141,6 +2288,7 @@ async fn replace_mcp_servers_streamable_http_isolates_headers_between_servers()
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
         ),
     ]);
@@ -2225,6 +2373,7 @@ async fn replace_mcp_servers_serializes_disabled_flag() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         },
     )]);
 
@@ -2271,6 +2420,7 @@ async fn replace_mcp_servers_serializes_required_flag() -> anyhow::Result<()> {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         },
     )]);
 
@@ -2317,6 +2467,7 @@ async fn replace_mcp_servers_serializes_tool_filters() -> anyhow::Result<()> {
             disabled_tools: Some(vec!["blocked".to_string()]),
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         },
     )]);
 
@@ -2367,6 +2518,7 @@ async fn replace_mcp_servers_streamable_http_serializes_oauth_resource() -> anyh
             disabled_tools: None,
             scopes: None,
             oauth_resource: Some("https://resource.example.com".to_string()),
+            sampling_token_budget: None,
         },
     )]);
 
@@ -2561,6 +2713,133 @@ fn cli_override_sets_compact_prompt() -> std::io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn profile_compaction_strategy_overrides_top_level() -> std::io::Result<()> {
+    let codex_home = TempDir::new()?;
+    let cfg: ConfigToml = toml::from_str(
+        r#"
+compaction_strategy = "hybrid"
+profile = "ci"
+
+[profiles.ci]
+compaction_strategy = "prune"
+"#,
+    )
+    .expect("TOML deserialization should succeed");
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg.clone(),
+        ConfigOverrides::default(),
+        codex_home.path().to_path_buf(),
+    )?;
+    assert_eq!(config.compaction_strategy, CompactionStrategy::Prune);
+
+    let config = Config::load_from_base_config_with_overrides(
+        ConfigToml {
+            profile: None,
+            ..cfg
+        },
+        ConfigOverrides::default(),
+        codex_home.path().to_path_buf(),
+    )?;
+    assert_eq!(config.compaction_strategy, CompactionStrategy::Hybrid);
+
+    Ok(())
+}
+
+#[test]
+fn model_fallbacks_resolve_providers_and_reject_unknown_ones() -> std::io::Result<()> {
+    let codex_home = TempDir::new()?;
+    let cfg: ConfigToml = toml::from_str(
+        r#"
+model_provider = "openai"
+model_fallbacks = [
+  { model = "gpt-5.1-codex-mini" },
+  { model = "qwen3-coder", provider = "ollama" },
+]
+"#,
+    )
+    .expect("TOML deserialization should succeed");
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg.clone(),
+        ConfigOverrides::default(),
+        codex_home.path().to_path_buf(),
+    )?;
+    let resolved: Vec<(&str, &str)> = config
+        .model_fallbacks
+        .iter()
+        .map(|fallback| (fallback.model.as_str(), fallback.provider_id.as_str()))
+        .collect();
+    assert_eq!(
+        resolved,
+        vec![("gpt-5.1-codex-mini", "openai"), ("qwen3-coder", "ollama")]
+    );
+    assert_eq!(
+        config.model_fallbacks[1].provider,
+        built_in_model_providers()["ollama"]
+    );
+
+    let err = Config::load_from_base_config_with_overrides(
+        ConfigToml {
+            model_fallbacks: Some(vec![ModelFallbackToml {
+                model: "gpt-5".to_string(),
+                provider: Some("missing".to_string()),
+            }]),
+            ..cfg
+        },
+        ConfigOverrides::default(),
+        codex_home.path().to_path_buf(),
+    )
+    .expect_err("unknown fallback provider should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+
+    Ok(())
+}
+
+#[test]
+fn deny_read_patterns_survive_sandbox_mode_and_reject_invalid_globs() -> std::io::Result<()> {
+    let codex_home = TempDir::new()?;
+    let cfg: ConfigToml = toml::from_str(
+        r#"
+sandbox_mode = "workspace-write"
+deny_read = ["**/.env", "~/.ssh"]
+deny_read_scan_limit = 500
+"#,
+    )
+    .expect("TOML deserialization should succeed");
+
+    let config = Config::load_from_base_config_with_overrides(
+        cfg.clone(),
+        ConfigOverrides::default(),
+        codex_home.path().to_path_buf(),
+    )?;
+    assert_eq!(
+        config.permissions.file_system_sandbox_policy.deny_read,
+        vec!["**/.env".to_string(), "~/.ssh".to_string()]
+    );
+    assert_eq!(
+        config
+            .permissions
+            .file_system_sandbox_policy
+            .effective_deny_read_scan_limit(),
+        500
+    );
+
+    let err = Config::load_from_base_config_with_overrides(
+        ConfigToml {
+            deny_read: Some(vec!["secrets/[".to_string()]),
+            ..cfg
+        },
+        ConfigOverrides::default(),
+        codex_home.path().to_path_buf(),
+    )
+    .expect_err("invalid glob should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+    Ok(())
+}
+
 #[test]
 fn loads_compact_prompt_from_file() -> std::io::Result<()> {
     let codex_home = TempDir::new()?;
@@ -2609,6 +2888,7 @@ fn load_config_rejects_missing_agent_role_config_file() -> std::io::Result<()> {
                     description: Some("Research role".to_string()),
                     config_file: Some(AbsolutePathBuf::from_absolute_path(missing_path)?),
                     nickname_candidates: None,
+                    isolation: None,
                 },
             )]),
         }),
@@ -2691,6 +2971,7 @@ fn load_config_normalizes_agent_role_nickname_candidates() -> std::io::Result<()
                         "  Hypatia  ".to_string(),
                         "Noether".to_string(),
                     ]),
+                    isolation: None,
                 },
             )]),
         }),
@@ -2729,6 +3010,7 @@ fn load_config_rejects_empty_agent_role_nickname_candidates() -> std::io::Result
                     description: Some("Research role".to_string()),
                     config_file: None,
                     nickname_candidates: Some(Vec::new()),
+                    isolation: None,
                 },
             )]),
         }),
@@ -2764,6 +3046,7 @@ fn load_config_rejects_duplicate_agent_role_nickname_candidates() -> std::io::Re
                     description: Some("Research role".to_string()),
                     config_file: None,
                     nickname_candidates: Some(vec!["Hypatia".to_string(), " Hypatia ".to_string()]),
+                    isolation: None,
                 },
             )]),
         }),
@@ -2799,6 +3082,7 @@ fn load_config_rejects_unsafe_agent_role_nickname_candidates() -> std::io::Resul
                     description: Some("Research role".to_string()),
                     config_file: None,
                     nickname_candidates: Some(vec!["Agent <One>".to_string()]),
+                    isolation: None,
                 },
             )]),
         }),
@@ -3002,9 +3286,11 @@ fn test_precedence_fixture_with_o3_profile() -> std::io::Result<()> {
             review_model: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            compaction_strategy: CompactionStrategy::Summary,
             service_tier: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_fallbacks: Vec::new(),
             permissions: Permissions {
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
@@ -3021,6 +3307,9 @@ fn test_precedence_fixture_with_o3_profile() -> std::io::Result<()> {
             enforce_residency: Constrained::allow_any(None),
             user_instructions: None,
             notify: None,
+            hooks: Vec::new(),
+            notification_sinks: Vec::new(),
+            model_pricing: HashMap::new(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -3031,6 +3320,7 @@ fn test_precedence_fixture_with_o3_profile() -> std::io::Result<()> {
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            apply_patch_fuzz: DEFAULT_APPLY_PATCH_FUZZ,
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
             agent_roles: BTreeMap::new(),
@@ -3137,9 +3427,11 @@ fn test_precedence_fixture_with_gpt3_profile() -> std::io::Result<()> {
         review_model: None,
         model_context_window: None,
         model_auto_compact_token_limit: None,
+        compaction_strategy: CompactionStrategy::Summary,
         service_tier: None,
         model_provider_id: "openai-custom".to_string(),
         model_provider: fixture.openai_custom_provider.clone(),
+        model_fallbacks: Vec::new(),
         permissions: Permissions {
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
@@ -3156,6 +3448,9 @@ fn test_precedence_fixture_with_gpt3_profile() -> std::io::Result<()> {
         enforce_residency: Constrained::allow_any(None),
         user_instructions: None,
         notify: None,
+        hooks: Vec::new(),
+        notification_sinks: Vec::new(),
+        model_pricing: HashMap::new(),
         cwd: fixture.cwd(),
         cli_auth_credentials_store_mode: Default::default(),
         mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -3166,6 +3461,7 @@ fn test_precedence_fixture_with_gpt3_profile() -> std::io::Result<()> {
         project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
         project_doc_fallback_filenames: Vec::new(),
         tool_output_token_limit: None,
+        apply_patch_fuzz: DEFAULT_APPLY_PATCH_FUZZ,
         agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
@@ -3270,9 +3566,11 @@ fn test_precedence_fixture_with_zdr_profile() -> std::io::Result<()> {
         review_model: None,
         model_context_window: None,
         model_auto_compact_token_limit: None,
+        compaction_strategy: CompactionStrategy::Summary,
         service_tier: None,
         model_provider_id: "openai".to_string(),
         model_provider: fixture.openai_provider.clone(),
+        model_fallbacks: Vec::new(),
         permissions: Permissions {
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
@@ -3289,6 +3587,9 @@ fn test_precedence_fixture_with_zdr_profile() -> std::io::Result<()> {
         enforce_residency: Constrained::allow_any(None),
         user_instructions: None,
         notify: None,
+        hooks: Vec::new(),
+        notification_sinks: Vec::new(),
+        model_pricing: HashMap::new(),
         cwd: fixture.cwd(),
         cli_auth_credentials_store_mode: Default::default(),
         mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -3299,6 +3600,7 @@ fn test_precedence_fixture_with_zdr_profile() -> std::io::Result<()> {
         project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
         project_doc_fallback_filenames: Vec::new(),
         tool_output_token_limit: None,
+        apply_patch_fuzz: DEFAULT_APPLY_PATCH_FUZZ,
         agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
@@ -3389,9 +3691,11 @@ fn test_precedence_fixture_with_gpt5_profile() -> std::io::Result<()> {
         review_model: None,
         model_context_window: None,
         model_auto_compact_token_limit: None,
+        compaction_strategy: CompactionStrategy::Summary,
         service_tier: None,
         model_provider_id: "openai".to_string(),
         model_provider: fixture.openai_provider.clone(),
+        model_fallbacks: Vec::new(),
         permissions: Permissions {
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
@@ -3408,6 +3712,9 @@ fn test_precedence_fixture_with_gpt5_profile() -> std::io::Result<()> {
         enforce_residency: Constrained::allow_any(None),
         user_instructions: None,
         notify: None,
+        hooks: Vec::new(),
+        notification_sinks: Vec::new(),
+        model_pricing: HashMap::new(),
         cwd: fixture.cwd(),
         cli_auth_credentials_store_mode: Default::default(),
         mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -3418,6 +3725,7 @@ fn test_precedence_fixture_with_gpt5_profile() -> std::io::Result<()> {
         project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
         project_doc_fallback_filenames: Vec::new(),
         tool_output_token_limit: None,
+        apply_patch_fuzz: DEFAULT_APPLY_PATCH_FUZZ,
         agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
         agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
         agent_roles: BTreeMap::new(),
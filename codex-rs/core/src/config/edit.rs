@@ -225,6 +225,9 @@ mod document_helpers {
         {
             entry["oauth_resource"] = value(resource.clone());
         }
+        if let Some(budget) = config.sampling_token_budget {
+            entry["sampling_token_budget"] = value(i64::try_from(budget).unwrap_or(i64::MAX));
+        }
 
         entry
     }
@@ -1530,6 +1533,7 @@ gpt-5 = "gpt-5.1"
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
         );
 
@@ -1555,6 +1559,7 @@ gpt-5 = "gpt-5.1"
                 disabled_tools: Some(vec!["forbidden".to_string()]),
                 scopes: None,
                 oauth_resource: Some("https://resource.example.com".to_string()),
+                sampling_token_budget: Some(20_000),
             },
         );
 
@@ -1574,6 +1579,7 @@ enabled = false
 startup_timeout_sec = 5.0
 disabled_tools = [\"forbidden\"]
 oauth_resource = \"https://resource.example.com\"
+sampling_token_budget = 20000
 
 [mcp_servers.http.http_headers]
 Z-Header = \"z\"
@@ -1624,6 +1630,7 @@ foo = { command = "cmd" }
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
         );
 
@@ -1671,6 +1678,7 @@ foo = { command = "cmd" } # keep me
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
         );
 
@@ -1717,6 +1725,7 @@ foo = { command = "cmd", args = ["--flag"] } # keep me
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
         );
 
@@ -1764,6 +1773,7 @@ foo = { command = "cmd" }
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
         );
 
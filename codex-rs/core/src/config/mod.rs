@@ -2,16 +2,22 @@ use crate::auth::AuthCredentialsStoreMode;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
 use crate::config::types::AppsConfigToml;
+use crate::config::types::CompactionStrategy;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::HooksToml;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
 use crate::config::types::MemoriesConfig;
 use crate::config::types::MemoriesToml;
 use crate::config::types::ModelAvailabilityNuxConfig;
+use crate::config::types::ModelFallback;
+use crate::config::types::ModelFallbackToml;
+use crate::config::types::ModelPricing;
 use crate::config::types::Notice;
 use crate::config::types::NotificationMethod;
+use crate::config::types::NotificationSinkToml;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
@@ -60,6 +66,8 @@ use crate::windows_sandbox::WindowsSandboxLevelExt;
 use crate::windows_sandbox::resolve_windows_sandbox_mode;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
+use codex_apply_patch::DEFAULT_APPLY_PATCH_FUZZ;
+use codex_hooks::CommandHookConfig;
 use codex_protocol::config_types::AltScreenMode;
 use codex_protocol::config_types::ForcedLoginMethod;
 use codex_protocol::config_types::Personality;
@@ -77,6 +85,7 @@ use codex_protocol::openai_models::ModelsResponse;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::permissions::FileSystemSandboxPolicy;
 use codex_protocol::permissions::NetworkSandboxPolicy;
+use codex_protocol::permissions::ReadDenyList;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
@@ -218,12 +227,19 @@ pub struct Config {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// How conversation history is compacted.
+    pub compaction_strategy: CompactionStrategy,
+
     /// Key into the model_providers map that specifies which provider to use.
     pub model_provider_id: String,
 
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Ordered models (and providers) a turn continues on when the current
+    /// model is rate limited, persistently failing or out of context window.
+    pub model_fallbacks: Vec<ModelFallback>,
+
     /// Optionally specify the personality of the model
     pub personality: Option<Personality>,
 
@@ -285,6 +301,18 @@ pub struct Config {
     /// If unset the feature is disabled.
     pub notify: Option<Vec<String>>,
 
+    /// Command hooks declared in the `[hooks]` table, run on session lifecycle
+    /// and tool-use events.
+    pub hooks: Vec<CommandHookConfig>,
+
+    /// Webhooks, commands and Unix sockets notified of selected session events
+    /// (`[[notification_sinks]]`).
+    pub notification_sinks: Vec<NotificationSinkToml>,
+
+    /// Per-model prices from the `[model_pricing]` table, keyed by model slug,
+    /// used to estimate costs in usage reports.
+    pub model_pricing: HashMap<String, ModelPricing>,
+
     /// TUI notifications preference. When set, the TUI will send terminal notifications on
     /// approvals and turn completions when not focused.
     pub tui_notifications: Notifications,
@@ -367,6 +395,10 @@ pub struct Config {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Number of context lines `apply_patch` may ignore at each end of a hunk it
+    /// cannot locate verbatim. `0` also disables whitespace-insensitive matching.
+    pub apply_patch_fuzz: usize,
+
     /// Maximum number of agent threads that can be open concurrently.
     pub agent_max_threads: Option<usize>,
     /// Maximum runtime in seconds for agent job workers before they are failed.
@@ -1039,12 +1071,21 @@ pub struct ConfigToml {
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
 
+    /// Ordered `{ model, provider }` entries to continue a turn on when the
+    /// current model is rate limited, persistently failing or out of context
+    /// window.
+    pub model_fallbacks: Option<Vec<ModelFallbackToml>>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// How conversation history is compacted: `summary` (default), `prune`
+    /// (no model call) or `hybrid`.
+    pub compaction_strategy: Option<CompactionStrategy>,
+
     /// Default approval policy for executing commands.
     pub approval_policy: Option<AskForApproval>,
 
@@ -1067,6 +1108,15 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Glob patterns for paths the agent may not read, even inside a
+    /// readable or writable root (for example `**/.env` or `~/.ssh`).
+    /// Relative patterns are resolved against the session cwd.
+    pub deny_read: Option<Vec<String>>,
+
+    /// Maximum number of directory entries visited while expanding
+    /// `deny_read` globs for the Linux sandbox. Defaults to 100000.
+    pub deny_read_scan_limit: Option<usize>,
+
     /// Default named permissions profile to apply from the `[permissions]`
     /// table.
     pub default_permissions: Option<String>,
@@ -1079,6 +1129,20 @@ pub struct ConfigToml {
     #[serde(default)]
     pub notify: Option<Vec<String>>,
 
+    /// Command hooks run on session lifecycle and tool-use events.
+    #[serde(default)]
+    pub hooks: Option<HooksToml>,
+
+    /// Webhooks, commands and Unix sockets notified when Codex needs approval, a turn
+    /// completes or fails, a sub-agent finishes or a usage limit is reached.
+    #[serde(default)]
+    pub notification_sinks: Option<Vec<NotificationSinkToml>>,
+
+    /// Per-model prices in US dollars per million tokens, used by `codex usage`
+    /// and `usage/summary` to estimate costs.
+    #[serde(default)]
+    pub model_pricing: HashMap<String, ModelPricing>,
+
     /// System instructions.
     pub instructions: Option<String>,
 
@@ -1152,6 +1216,11 @@ pub struct ConfigToml {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Number of context lines `apply_patch` may ignore at each end of a hunk it
+    /// cannot locate verbatim. `0` also disables whitespace-insensitive matching.
+    /// Default: `0`.
+    pub apply_patch_fuzz: Option<usize>,
+
     /// Maximum poll window for background terminal output (`write_stdin`), in milliseconds.
     /// Default: `300000` (5 minutes).
     pub background_terminal_max_timeout: Option<u64>,
@@ -1415,6 +1484,7 @@ pub struct AgentsToml {
     /// description = "Research-focused role."
     /// config_file = "./agents/researcher.toml"
     /// nickname_candidates = ["Herodotus", "Ibn Battuta"]
+    /// isolation = "worktree"
     /// ```
     #[serde(default, flatten)]
     pub roles: BTreeMap<String, AgentRoleToml>,
@@ -1428,6 +1498,8 @@ pub struct AgentRoleConfig {
     pub config_file: Option<PathBuf>,
     /// Candidate nicknames for agents spawned with this role.
     pub nickname_candidates: Option<Vec<String>>,
+    /// Workspace isolation for agents spawned with this role.
+    pub isolation: Option<AgentIsolation>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, JsonSchema)]
@@ -1442,6 +1514,21 @@ pub struct AgentRoleToml {
 
     /// Candidate nicknames for agents spawned with this role.
     pub nickname_candidates: Option<Vec<String>>,
+
+    /// Workspace isolation for agents spawned with this role. `worktree` runs each agent in its
+    /// own git worktree and branch instead of the parent's working directory.
+    pub isolation: Option<AgentIsolation>,
+}
+
+/// Where a spawned agent makes its changes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AgentIsolation {
+    /// Work directly in the parent's working directory.
+    #[default]
+    None,
+    /// Work in a dedicated git worktree created from the parent's `HEAD`.
+    Worktree,
 }
 
 impl From<ToolsToml> for Tools {
@@ -2062,6 +2149,28 @@ impl Config {
                 std::io::Error::new(std::io::ErrorKind::NotFound, message)
             })?
             .clone();
+        let model_fallbacks = config_profile
+            .model_fallbacks
+            .or(cfg.model_fallbacks)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|fallback| {
+                let provider_id = fallback
+                    .provider
+                    .unwrap_or_else(|| model_provider_id.clone());
+                let provider = model_providers.get(&provider_id).cloned().ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("Model provider `{provider_id}` in `model_fallbacks` not found"),
+                    )
+                })?;
+                Ok(ModelFallback {
+                    model: fallback.model,
+                    provider_id,
+                    provider,
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
 
         let shell_environment_policy = cfg.shell_environment_policy.into();
         let allow_login_shell = cfg.allow_login_shell.unwrap_or(true);
@@ -2111,6 +2220,7 @@ impl Config {
                                 description: role.description.clone(),
                                 config_file,
                                 nickname_candidates,
+                                isolation: role.isolation,
                             },
                         ))
                     })
@@ -2326,12 +2436,18 @@ impl Config {
             network.enabled().then_some(network)
         };
         let effective_sandbox_policy = constrained_sandbox_policy.value.get().clone();
+        // Compile the patterns once so an invalid glob fails config loading
+        // instead of every later file read.
+        let deny_read = cfg.deny_read.clone().unwrap_or_default();
+        ReadDenyList::new(&deny_read, &resolved_cwd)?;
         let effective_file_system_sandbox_policy =
             if effective_sandbox_policy == original_sandbox_policy {
                 file_system_sandbox_policy
             } else {
                 FileSystemSandboxPolicy::from(&effective_sandbox_policy)
-            };
+            }
+            .with_deny_read(deny_read)
+            .with_deny_read_scan_limit(cfg.deny_read_scan_limit);
         let effective_network_sandbox_policy =
             if effective_sandbox_policy == original_sandbox_policy {
                 network_sandbox_policy
@@ -2345,8 +2461,13 @@ impl Config {
             review_model,
             model_context_window: cfg.model_context_window,
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
+            compaction_strategy: config_profile
+                .compaction_strategy
+                .or(cfg.compaction_strategy)
+                .unwrap_or_default(),
             model_provider_id,
             model_provider,
+            model_fallbacks,
             cwd: resolved_cwd,
             startup_warnings,
             permissions: Permissions {
@@ -2362,6 +2483,13 @@ impl Config {
             },
             enforce_residency: enforce_residency.value,
             notify: cfg.notify,
+            hooks: cfg
+                .hooks
+                .as_ref()
+                .map(HooksToml::command_hooks)
+                .unwrap_or_default(),
+            notification_sinks: cfg.notification_sinks.unwrap_or_default(),
+            model_pricing: cfg.model_pricing,
             user_instructions,
             base_instructions,
             personality,
@@ -2393,6 +2521,7 @@ impl Config {
                 })
                 .collect(),
             tool_output_token_limit: cfg.tool_output_token_limit,
+            apply_patch_fuzz: cfg.apply_patch_fuzz.unwrap_or(DEFAULT_APPLY_PATCH_FUZZ),
             agent_max_threads,
             agent_max_depth,
             agent_roles,
@@ -4,15 +4,21 @@
 // definitions that do not contain business logic.
 
 use crate::config_loader::RequirementSource;
+use crate::model_provider_info::ModelProviderInfo;
+use codex_hooks::CommandHookConfig;
+use codex_hooks::DEFAULT_COMMAND_HOOK_TIMEOUT;
+use codex_hooks::HookEventName;
 pub use codex_protocol::config_types::AltScreenMode;
 pub use codex_protocol::config_types::ModeKind;
 pub use codex_protocol::config_types::Personality;
 pub use codex_protocol::config_types::ServiceTier;
 pub use codex_protocol::config_types::WebSearchMode;
+use codex_secrets::SecretName;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
+use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 use wildmatch::WildMatchPattern;
@@ -104,6 +110,11 @@ pub struct McpServerConfig {
     /// Optional OAuth resource parameter to include during MCP login (RFC 8707).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub oauth_resource: Option<String>,
+
+    /// Maximum number of tokens this server may consume through
+    /// `sampling/createMessage` per session. `0` disables sampling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling_token_budget: Option<u64>,
 }
 
 // Raw MCP config shape used for deserialization and JSON Schema generation.
@@ -143,6 +154,8 @@ pub(crate) struct RawMcpServerConfig {
     #[serde(default)]
     pub required: Option<bool>,
     #[serde(default)]
+    pub sampling_token_budget: Option<u64>,
+    #[serde(default)]
     pub enabled_tools: Option<Vec<String>>,
     #[serde(default)]
     pub disabled_tools: Option<Vec<String>>,
@@ -174,6 +187,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
         let disabled_tools = raw.disabled_tools.clone();
         let scopes = raw.scopes.clone();
         let oauth_resource = raw.oauth_resource.clone();
+        let sampling_token_budget = raw.sampling_token_budget;
 
         fn throw_if_set<E, T>(transport: &str, field: &str, value: Option<&T>) -> Result<(), E>
         where
@@ -232,6 +246,7 @@ impl<'de> Deserialize<'de> for McpServerConfig {
             disabled_tools,
             scopes,
             oauth_resource,
+            sampling_token_budget,
         })
     }
 }
@@ -351,6 +366,50 @@ pub enum HistoryPersistence {
     None,
 }
 
+/// How conversation history is shrunk when it nears the context window.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CompactionStrategy {
+    /// Replace the history with a model-written summary (or the provider's
+    /// remote compaction endpoint when available).
+    #[default]
+    Summary,
+    /// Drop the content of the oldest tool outputs first, keeping messages,
+    /// edits and recent outputs. Makes no model call.
+    Prune,
+    /// Like `prune`, but replace the dropped tool outputs with a model-written
+    /// summary of them.
+    Hybrid,
+}
+
+impl CompactionStrategy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Summary => "summary",
+            Self::Prune => "prune",
+            Self::Hybrid => "hybrid",
+        }
+    }
+}
+
+/// One entry of `model_fallbacks` as written in `config.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelFallbackToml {
+    /// Model to continue the turn on.
+    pub model: String,
+    /// Key in the `model_providers` map. Defaults to the active provider.
+    pub provider: Option<String>,
+}
+
+/// A resolved `model_fallbacks` entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelFallback {
+    pub model: String,
+    pub provider_id: String,
+    pub provider: ModelProviderInfo,
+}
+
 // ===== Analytics configuration =====
 
 /// Analytics settings loaded from config.toml. Fields are optional so we can apply defaults.
@@ -675,6 +734,187 @@ impl fmt::Display for NotificationMethod {
     }
 }
 
+/// A single command hook declared in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HookCommandToml {
+    /// Name reported in logs and denial messages. Defaults to the program name.
+    pub name: Option<String>,
+    /// Program and arguments to run. The JSON hook payload is written to stdin and an optional
+    /// JSON decision (`{"decision": "allow" | "deny" | "rewrite", ...}`) is read from stdout.
+    pub command: Vec<String>,
+    /// Maximum time to wait for the command before treating the hook as failed. Defaults to 60s.
+    pub timeout_ms: Option<u64>,
+    /// Whether a failing `before_tool_use` or `user_prompt_submit` hook (timeout, crash or
+    /// unparseable output) denies the operation. Defaults to true; set to false to log and ignore
+    /// the failure instead.
+    pub fail_closed: Option<bool>,
+}
+
+/// Lifecycle hooks loaded from the `[hooks]` table in config.toml, keyed by event.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HooksToml {
+    /// Run when a session starts, resumes or forks.
+    #[serde(default)]
+    pub session_start: Vec<HookCommandToml>,
+    /// Run before a user prompt starts a turn. May deny the prompt.
+    #[serde(default)]
+    pub user_prompt_submit: Vec<HookCommandToml>,
+    /// Run before each tool call. May deny the call or rewrite its arguments.
+    #[serde(default)]
+    pub before_tool_use: Vec<HookCommandToml>,
+    /// Run after each tool call.
+    #[serde(default)]
+    pub after_tool_use: Vec<HookCommandToml>,
+    /// Run before the conversation history is compacted.
+    #[serde(default)]
+    pub before_compact: Vec<HookCommandToml>,
+    /// Run when the agent finishes a turn.
+    #[serde(default)]
+    pub after_agent: Vec<HookCommandToml>,
+    /// Run when the session shuts down.
+    #[serde(default)]
+    pub session_end: Vec<HookCommandToml>,
+}
+
+impl HooksToml {
+    pub fn command_hooks(&self) -> Vec<CommandHookConfig> {
+        [
+            (HookEventName::SessionStart, &self.session_start),
+            (HookEventName::UserPromptSubmit, &self.user_prompt_submit),
+            (HookEventName::BeforeToolUse, &self.before_tool_use),
+            (HookEventName::AfterToolUse, &self.after_tool_use),
+            (HookEventName::BeforeCompact, &self.before_compact),
+            (HookEventName::AfterAgent, &self.after_agent),
+            (HookEventName::SessionEnd, &self.session_end),
+        ]
+        .into_iter()
+        .flat_map(|(event, hooks)| {
+            hooks.iter().map(move |hook| CommandHookConfig {
+                name: hook
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| default_hook_name(&hook.command, event)),
+                event,
+                argv: hook.command.clone(),
+                timeout: hook
+                    .timeout_ms
+                    .map_or(DEFAULT_COMMAND_HOOK_TIMEOUT, Duration::from_millis),
+                fail_closed: hook.fail_closed.unwrap_or(true),
+            })
+        })
+        .collect()
+    }
+}
+
+fn default_hook_name(command: &[String], event: HookEventName) -> String {
+    match command.first() {
+        Some(program) => Path::new(program).file_name().map_or_else(
+            || program.clone(),
+            |name| name.to_string_lossy().into_owned(),
+        ),
+        None => event.to_string(),
+    }
+}
+
+/// Session events that can trigger a notification sink.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSinkEvent {
+    /// Codex is waiting for the user to approve a command, edit or tool call, or to answer a
+    /// question.
+    ApprovalRequested,
+    /// A turn finished.
+    TurnCompleted,
+    /// A turn ended with an error other than an exhausted usage limit.
+    TurnFailed,
+    /// A spawned sub-agent finished its turn.
+    SubAgentFinished,
+    /// A turn ended because the usage limit or quota was exhausted.
+    RateLimitReached,
+}
+
+impl NotificationSinkEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NotificationSinkEvent::ApprovalRequested => "approval_requested",
+            NotificationSinkEvent::TurnCompleted => "turn_completed",
+            NotificationSinkEvent::TurnFailed => "turn_failed",
+            NotificationSinkEvent::SubAgentFinished => "sub_agent_finished",
+            NotificationSinkEvent::RateLimitReached => "rate_limit_reached",
+        }
+    }
+}
+
+/// Where a notification sink delivers events.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NotificationSinkTarget {
+    /// POST a JSON body to `url`.
+    Webhook {
+        url: String,
+        /// Extra HTTP headers, for example `Authorization`.
+        #[serde(default)]
+        headers: HashMap<String, String>,
+        /// JSON body template. `{{event}}`, `{{message}}`, `{{thread_id}}`, `{{turn_id}}`,
+        /// `{{cwd}}` and `{{timestamp}}` are replaced with JSON-escaped values, so place them
+        /// inside string literals. Defaults to the full notification payload.
+        #[serde(default)]
+        body: Option<String>,
+    },
+    /// Run a command with the notification payload as JSON on stdin.
+    Command { command: Vec<String> },
+    /// Write the notification payload as one line of JSON to a Unix domain socket.
+    UnixSocket { path: AbsolutePathBuf },
+}
+
+/// A notification sink from `[[notification_sinks]]` in config.toml.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
+pub struct NotificationSinkToml {
+    /// Events delivered to this sink. Defaults to all events.
+    #[serde(default)]
+    pub events: Option<Vec<NotificationSinkEvent>>,
+    #[serde(flatten)]
+    pub target: NotificationSinkTarget,
+}
+
+impl NotificationSinkToml {
+    pub fn accepts(&self, event: NotificationSinkEvent) -> bool {
+        self.events
+            .as_ref()
+            .is_none_or(|events| events.contains(&event))
+    }
+}
+
+/// Prices for one model from the `[model_pricing."<model>"]` table in config.toml, in US
+/// dollars per million tokens. Used to estimate costs in usage reports.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelPricing {
+    /// Price of uncached input tokens.
+    #[serde(default)]
+    pub input: f64,
+    /// Price of cached input tokens. Defaults to the input price.
+    pub cached_input: Option<f64>,
+    /// Price of output tokens, including reasoning tokens.
+    #[serde(default)]
+    pub output: f64,
+}
+
+impl ModelPricing {
+    /// Estimated cost in US dollars. `input_tokens` includes `cached_input_tokens`.
+    pub fn cost_usd(&self, input_tokens: i64, cached_input_tokens: i64, output_tokens: i64) -> f64 {
+        let cached_input_tokens = cached_input_tokens.clamp(0, input_tokens.max(0));
+        let uncached_input_tokens = input_tokens.max(0) - cached_input_tokens;
+        let cached_input = self.cached_input.unwrap_or(self.input);
+        (uncached_input_tokens as f64 * self.input
+            + cached_input_tokens as f64 * cached_input
+            + output_tokens.max(0) as f64 * self.output)
+            / 1_000_000.0
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
 pub struct ModelAvailabilityNuxConfig {
@@ -840,12 +1080,19 @@ pub struct ShellEnvironmentPolicyToml {
     /// List of regular expressions.
     pub exclude: Option<Vec<String>>,
 
+    /// Values of the form `secret:<name>` are resolved from the secrets stored
+    /// with `codex secrets set` when a command listed in `secret_commands` runs.
     pub r#set: Option<HashMap<String, String>>,
 
     /// List of regular expressions.
     pub include_only: Option<Vec<String>>,
 
     pub experimental_use_profile: Option<bool>,
+
+    /// Programs (e.g. `npm`) that receive the secret-backed variables from `set`.
+    /// Bare names only match programs run by that name; a program run by path
+    /// (`./npm`) must be listed as an absolute path to the same file.
+    pub secret_commands: Option<Vec<String>>,
 }
 
 pub type EnvironmentVariablePattern = WildMatchPattern<'*', '?'>;
@@ -857,6 +1104,9 @@ pub type EnvironmentVariablePattern = WildMatchPattern<'*', '?'>;
 /// 3. If `exclude` is not empty, filter the map using the provided patterns.
 /// 4. Insert any entries from `r#set` into the map.
 /// 5. If non-empty, filter the map using the `include_only` patterns.
+///
+/// Secret-backed variables are not part of this derivation: they are added
+/// afterwards, and only for commands that run one of `secret_commands`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ShellEnvironmentPolicy {
     /// Starting point when building the environment.
@@ -877,6 +1127,12 @@ pub struct ShellEnvironmentPolicy {
 
     /// If true, the shell profile will be used to run the command.
     pub use_profile: bool,
+
+    /// Variables whose value is a stored secret, keyed by variable name.
+    pub secrets: HashMap<String, SecretName>,
+
+    /// Programs allowed to receive the variables in `secrets`.
+    pub secret_commands: Vec<String>,
 }
 
 impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
@@ -890,7 +1146,21 @@ impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
             .into_iter()
             .map(|s| EnvironmentVariablePattern::new_case_insensitive(&s))
             .collect();
-        let r#set = toml.r#set.unwrap_or_default();
+        let mut r#set = HashMap::new();
+        let mut secrets = HashMap::new();
+        for (key, value) in toml.r#set.unwrap_or_default() {
+            match SecretName::from_reference(&value) {
+                Some(Ok(name)) => {
+                    secrets.insert(key, name);
+                }
+                Some(Err(err)) => {
+                    tracing::warn!("ignoring shell_environment_policy.set.{key}: {err}");
+                }
+                None => {
+                    r#set.insert(key, value);
+                }
+            }
+        }
         let include_only = toml
             .include_only
             .unwrap_or_default()
@@ -898,6 +1168,7 @@ impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
             .map(|s| EnvironmentVariablePattern::new_case_insensitive(&s))
             .collect();
         let use_profile = toml.experimental_use_profile.unwrap_or(false);
+        let secret_commands = toml.secret_commands.unwrap_or_default();
 
         Self {
             inherit,
@@ -906,6 +1177,8 @@ impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
             r#set,
             include_only,
             use_profile,
+            secrets,
+            secret_commands,
         }
     }
 }
@@ -919,6 +1192,8 @@ impl Default for ShellEnvironmentPolicy {
             r#set: HashMap::new(),
             include_only: Vec::new(),
             use_profile: false,
+            secrets: HashMap::new(),
+            secret_commands: Vec::new(),
         }
     }
 }
@@ -1155,6 +1430,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn deserialize_server_config_with_sampling_token_budget() {
+        let cfg: McpServerConfig = toml::from_str(
+            r#"
+            command = "indexer"
+            sampling_token_budget = 20000
+        "#,
+        )
+        .expect("should deserialize config with sampling_token_budget");
+
+        assert_eq!(cfg.sampling_token_budget, Some(20_000));
+    }
+
     #[test]
     fn deserialize_server_config_with_tool_filters() {
         let cfg: McpServerConfig = toml::from_str(
@@ -1240,4 +1528,29 @@ mod tests {
             "unexpected error: {err}"
         );
     }
+
+    #[test]
+    fn shell_environment_policy_separates_secret_references() {
+        let toml: ShellEnvironmentPolicyToml = toml::from_str(
+            r#"
+            set = { NODE_ENV = "test", NPM_TOKEN = "secret:npm_token", BAD = "secret:not-valid" }
+            secret_commands = ["npm"]
+        "#,
+        )
+        .expect("should deserialize shell environment policy");
+
+        let policy = ShellEnvironmentPolicy::from(toml);
+        assert_eq!(
+            policy.r#set,
+            HashMap::from([("NODE_ENV".to_string(), "test".to_string())])
+        );
+        assert_eq!(
+            policy.secrets,
+            HashMap::from([(
+                "NPM_TOKEN".to_string(),
+                SecretName::new("NPM_TOKEN").expect("secret name")
+            )])
+        );
+        assert_eq!(policy.secret_commands, vec!["npm".to_string()]);
+    }
 }
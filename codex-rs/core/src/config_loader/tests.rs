@@ -1528,6 +1528,7 @@ prefix_rules = [
                     decision: Decision::Forbidden,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 }],
             }
         );
@@ -1554,6 +1555,7 @@ prefix_rules = [
                     decision: Decision::Prompt,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 }],
             }
         );
@@ -1566,6 +1568,7 @@ prefix_rules = [
                     decision: Decision::Prompt,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 }],
             }
         );
@@ -1650,6 +1653,7 @@ prefix_rules = []
                     decision: Decision::Forbidden,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 }],
             }
         );
@@ -1689,6 +1693,7 @@ prefix_rules = []
                     decision: Decision::Forbidden,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 }],
             }
         );
@@ -1704,6 +1709,7 @@ prefix_rules = []
                     decision: Decision::Prompt,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 }],
             }
         );
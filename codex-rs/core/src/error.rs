@@ -12,6 +12,7 @@ use codex_async_utils::CancelErr;
 use codex_protocol::ThreadId;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::ModelRerouteReason;
 use codex_protocol::protocol::RateLimitSnapshot;
 use reqwest::StatusCode;
 use serde_json;
@@ -58,6 +59,11 @@ pub enum SandboxErr {
     /// Error from linux landlock
     #[error("Landlock was not able to fully enforce all sandbox rules")]
     LandlockRestrict,
+
+    /// `deny_read` patterns could not be expanded into the paths to mask, so
+    /// the command was not started
+    #[error("cannot enforce deny_read, so the command was not run: {0}")]
+    DenyReadExpansion(String),
 }
 
 #[derive(Error, Debug)]
@@ -616,6 +622,35 @@ impl CodexErr {
         };
         http_status_code.as_ref().map(StatusCode::as_u16)
     }
+
+    /// Returns why a turn should continue on the next `model_fallbacks`
+    /// entry, or `None` when this error is not one a different model could
+    /// fix. Transient errors only reach this point once retries are spent.
+    pub fn model_fallback_reason(&self) -> Option<ModelRerouteReason> {
+        match self {
+            CodexErr::ContextWindowExceeded => Some(ModelRerouteReason::ContextWindowExceeded),
+            CodexErr::UsageLimitReached(_) | CodexErr::QuotaExceeded => {
+                Some(ModelRerouteReason::RateLimited)
+            }
+            CodexErr::RetryLimit(RetryLimitReachedError { status, .. })
+            | CodexErr::UnexpectedStatus(UnexpectedResponseError { status, .. }) => {
+                if *status == StatusCode::TOO_MANY_REQUESTS {
+                    Some(ModelRerouteReason::RateLimited)
+                } else if status.is_server_error() {
+                    Some(ModelRerouteReason::ProviderUnavailable)
+                } else {
+                    None
+                }
+            }
+            CodexErr::ServerOverloaded
+            | CodexErr::InternalServerError
+            | CodexErr::ConnectionFailed(_)
+            | CodexErr::ResponseStreamFailed(_)
+            | CodexErr::Stream(..)
+            | CodexErr::Timeout => Some(ModelRerouteReason::ProviderUnavailable),
+            _ => None,
+        }
+    }
 }
 
 pub fn get_error_message_ui(e: &CodexErr) -> String {
@@ -728,6 +763,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn model_fallback_reason_covers_quota_outages_and_context_overflow() {
+        let retry_limit = |status| {
+            CodexErr::RetryLimit(RetryLimitReachedError {
+                status,
+                request_id: None,
+            })
+        };
+        assert_eq!(
+            CodexErr::QuotaExceeded.model_fallback_reason(),
+            Some(ModelRerouteReason::RateLimited)
+        );
+        assert_eq!(
+            retry_limit(StatusCode::TOO_MANY_REQUESTS).model_fallback_reason(),
+            Some(ModelRerouteReason::RateLimited)
+        );
+        assert_eq!(
+            retry_limit(StatusCode::BAD_GATEWAY).model_fallback_reason(),
+            Some(ModelRerouteReason::ProviderUnavailable)
+        );
+        assert_eq!(
+            CodexErr::ServerOverloaded.model_fallback_reason(),
+            Some(ModelRerouteReason::ProviderUnavailable)
+        );
+        assert_eq!(
+            CodexErr::ContextWindowExceeded.model_fallback_reason(),
+            Some(ModelRerouteReason::ContextWindowExceeded)
+        );
+        assert_eq!(
+            retry_limit(StatusCode::BAD_REQUEST).model_fallback_reason(),
+            None
+        );
+        assert_eq!(
+            CodexErr::InvalidRequest("bad".to_string()).model_fallback_reason(),
+            None
+        );
+        assert_eq!(CodexErr::TurnAborted.model_fallback_reason(), None);
+    }
+
     #[test]
     fn sandbox_denied_uses_aggregated_output_when_stderr_empty() {
         let output = ExecToolCallOutput {
@@ -567,6 +567,7 @@ fn finalize_exec_result(
 
 pub(crate) mod errors {
     use super::CodexErr;
+    use super::SandboxErr;
     use crate::sandboxing::SandboxTransformError;
 
     impl From<SandboxTransformError> for CodexErr {
@@ -579,6 +580,9 @@ pub(crate) mod errors {
                 SandboxTransformError::SeatbeltUnavailable => CodexErr::UnsupportedOperation(
                     "seatbelt sandbox is only available on macOS".to_string(),
                 ),
+                SandboxTransformError::DenyReadExpansion(message) => {
+                    CodexErr::Sandbox(SandboxErr::DenyReadExpansion(message))
+                }
             }
         }
     }
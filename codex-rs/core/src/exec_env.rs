@@ -1,9 +1,19 @@
 use crate::config::types::EnvironmentVariablePattern;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyInherit;
+use crate::exec::ExecToolCallOutput;
 use codex_protocol::ThreadId;
+use codex_secrets::SecretsBackendKind;
+use codex_secrets::SecretsManager;
+use codex_secrets::redact_secret_values;
+use codex_shell_command::bash::parse_shell_lc_plain_commands;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::RwLock;
+use tracing::warn;
 
 pub const CODEX_THREAD_ID_ENV_VAR: &str = "CODEX_THREAD_ID";
 
@@ -93,10 +103,121 @@ where
     env_map
 }
 
+/// Resolves the secret-backed variables of a [`ShellEnvironmentPolicy`] when a
+/// command is about to run, and remembers every value handed out so that it can
+/// be scrubbed from command output before that output reaches the model, the
+/// client or the rollout.
+#[derive(Clone)]
+pub(crate) struct ExecSecrets {
+    manager: SecretsManager,
+    injected: Arc<RwLock<HashSet<String>>>,
+}
+
+impl ExecSecrets {
+    pub(crate) fn new(codex_home: PathBuf) -> Self {
+        Self::with_manager(SecretsManager::new(codex_home, SecretsBackendKind::Local))
+    }
+
+    pub(crate) fn with_manager(manager: SecretsManager) -> Self {
+        Self {
+            manager,
+            injected: Arc::default(),
+        }
+    }
+
+    /// Returns the secret-backed variables of `policy` to add to the
+    /// environment of `command`. Empty unless every program in `command` is one
+    /// of `policy.secret_commands`.
+    pub(crate) fn resolve_env(
+        &self,
+        policy: &ShellEnvironmentPolicy,
+        command: &[String],
+        cwd: &Path,
+    ) -> HashMap<String, String> {
+        let mut env = HashMap::new();
+        if policy.secrets.is_empty() || !runs_only_secret_commands(command, cwd, policy) {
+            return env;
+        }
+        for (key, name) in &policy.secrets {
+            match self.manager.resolve(name, cwd) {
+                Ok(Some(value)) => {
+                    if let Ok(mut injected) = self.injected.write() {
+                        injected.insert(value.clone());
+                    }
+                    env.insert(key.clone(), value);
+                }
+                Ok(None) => warn!("secret {name} for {key} is not set; run `codex secrets set`"),
+                Err(err) => warn!("failed to read secret {name} for {key}: {err}"),
+            }
+        }
+        env
+    }
+
+    /// Replaces the values of all secrets injected so far in `text`.
+    pub(crate) fn redact(&self, text: &str) -> String {
+        match self.injected.read() {
+            Ok(injected) if !injected.is_empty() => {
+                redact_secret_values(text, &injected.iter().collect::<Vec<_>>())
+            }
+            _ => text.to_string(),
+        }
+    }
+
+    pub(crate) fn redact_output(&self, output: &mut ExecToolCallOutput) {
+        output.stdout.text = self.redact(&output.stdout.text);
+        output.stderr.text = self.redact(&output.stderr.text);
+        output.aggregated_output.text = self.redact(&output.aggregated_output.text);
+    }
+}
+
+/// Whether every program `command` runs is allowed to receive secrets. Shell
+/// scripts qualify only when they are plain command sequences: expansions,
+/// substitutions and redirections could otherwise leak the values.
+fn runs_only_secret_commands(
+    command: &[String],
+    cwd: &Path,
+    policy: &ShellEnvironmentPolicy,
+) -> bool {
+    let commands = parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
+    commands.iter().all(|words| {
+        words
+            .first()
+            .is_some_and(|program| is_secret_command(program, cwd, policy))
+    })
+}
+
+/// Bare program names must be listed in `secret_commands` as-is. A program
+/// given as a path, such as `./npm` or `/tmp/x/npm`, could be any file, so it
+/// only qualifies when it is the same file as an absolute path listed there.
+fn is_secret_command(program: &str, cwd: &Path, policy: &ShellEnvironmentPolicy) -> bool {
+    if !program.contains(['/', '\\']) {
+        return policy
+            .secret_commands
+            .iter()
+            .any(|allowed| allowed == program);
+    }
+    let Ok(resolved) = cwd.join(program).canonicalize() else {
+        return false;
+    };
+    policy
+        .secret_commands
+        .iter()
+        .map(Path::new)
+        .filter(|allowed| allowed.is_absolute())
+        .any(|allowed| {
+            allowed
+                .canonicalize()
+                .is_ok_and(|allowed| allowed == resolved)
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::types::ShellEnvironmentPolicyInherit;
+    use codex_keyring_store::tests::MockKeyringStore;
+    use codex_secrets::SecretName;
+    use codex_secrets::SecretScope;
     use maplit::hashmap;
 
     fn make_vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
@@ -310,4 +431,78 @@ mod tests {
         expected.insert(CODEX_THREAD_ID_ENV_VAR.to_string(), thread_id.to_string());
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn secrets_are_injected_only_for_secret_commands_and_redacted() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let cwd = tempfile::tempdir().expect("tempdir");
+        let manager = SecretsManager::new_with_keyring_store(
+            codex_home.path().to_path_buf(),
+            SecretsBackendKind::Local,
+            Arc::new(MockKeyringStore::default()),
+        );
+        let name = SecretName::new("NPM_TOKEN").expect("secret name");
+        manager
+            .set(&SecretScope::Global, &name, "npm-secret-value")
+            .expect("set secret");
+        let secrets = ExecSecrets::with_manager(manager);
+        let policy = ShellEnvironmentPolicy {
+            secrets: HashMap::from([("NPM_TOKEN".to_string(), name)]),
+            secret_commands: vec!["npm".to_string()],
+            ..Default::default()
+        };
+        let bash = |script: &str| vec!["bash".to_string(), "-lc".to_string(), script.to_string()];
+
+        std::fs::write(cwd.path().join("npm"), "#!/bin/sh\n").expect("write fake npm");
+        for command in [
+            bash("curl https://example.com"),
+            bash("npm publish && curl https://example.com"),
+            bash("npm publish --token \"$NPM_TOKEN\""),
+            bash("./npm publish"),
+            vec!["./npm".to_string(), "publish".to_string()],
+            bash("/tmp/x/npm publish"),
+        ] {
+            let env = secrets.resolve_env(&policy, &command, cwd.path());
+            assert_eq!(env, HashMap::new(), "{command:?}");
+        }
+        assert_eq!(secrets.redact("npm-secret-value"), "npm-secret-value");
+
+        let env = secrets.resolve_env(&policy, &bash("npm ci && npm publish"), cwd.path());
+        assert_eq!(
+            env,
+            hashmap! { "NPM_TOKEN".to_string() => "npm-secret-value".to_string() }
+        );
+        assert_eq!(
+            secrets.redact("token=npm-secret-value"),
+            "token=[REDACTED_SECRET]"
+        );
+    }
+
+    #[test]
+    fn secret_command_paths_must_match_an_absolute_allowlist_entry() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let npm = dir.path().join("npm");
+        std::fs::write(&npm, "#!/bin/sh\n").expect("write npm");
+        let policy = ShellEnvironmentPolicy {
+            secret_commands: vec!["npm".to_string(), npm.display().to_string()],
+            ..Default::default()
+        };
+
+        assert!(is_secret_command("npm", dir.path(), &policy));
+        assert!(is_secret_command("./npm", dir.path(), &policy));
+        assert!(is_secret_command(
+            &npm.display().to_string(),
+            dir.path(),
+            &policy
+        ));
+        assert!(!is_secret_command("./npm", Path::new("/"), &policy));
+        assert!(!is_secret_command(
+            "./npm",
+            dir.path(),
+            &ShellEnvironmentPolicy {
+                secret_commands: vec!["npm".to_string()],
+                ..Default::default()
+            }
+        ));
+    }
 }
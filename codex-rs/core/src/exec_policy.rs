@@ -10,6 +10,7 @@ use crate::config_loader::ConfigLayerStackOrdering;
 use crate::is_dangerous_command::command_might_be_dangerous;
 use crate::is_safe_command::is_known_safe_command;
 use codex_execpolicy::AmendError;
+use codex_execpolicy::ArgMatcher;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error as ExecPolicyRuleError;
 use codex_execpolicy::Evaluation;
@@ -17,12 +18,14 @@ use codex_execpolicy::MatchOptions;
 use codex_execpolicy::NetworkRuleProtocol;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
+use codex_execpolicy::PrefixRuleAmendment;
 use codex_execpolicy::RuleMatch;
-use codex_execpolicy::blocking_append_allow_prefix_rule;
 use codex_execpolicy::blocking_append_network_rule;
+use codex_execpolicy::blocking_append_prefix_rule;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use thiserror::Error;
 use tokio::fs;
 use tokio::task::spawn_blocking;
@@ -155,6 +158,9 @@ pub enum ExecPolicyUpdateError {
     #[error("failed to update rules file {path}: {source}")]
     AppendRule { path: PathBuf, source: AmendError },
 
+    #[error("invalid argument clause in rules update: {source}")]
+    InvalidClause { source: ExecPolicyRuleError },
+
     #[error("failed to join blocking rules update task: {source}")]
     JoinBlockingTask { source: tokio::task::JoinError },
 
@@ -175,6 +181,30 @@ pub(crate) struct ExecApprovalRequest<'a> {
     pub(crate) sandbox_policy: &'a SandboxPolicy,
     pub(crate) sandbox_permissions: SandboxPermissions,
     pub(crate) prefix_rule: Option<Vec<String>>,
+    /// Working directory of the command. Workspace path matchers in rules resolve relative paths
+    /// against it.
+    pub(crate) cwd: Option<&'a Path>,
+    /// The turn's configured working directory. Together with the sandbox's writable roots it
+    /// forms the workspace for workspace path matchers. The command's own working directory is
+    /// chosen by the model, so it never widens the workspace.
+    pub(crate) workspace_root: Option<&'a Path>,
+}
+
+/// Directories that `workspace_path()` and `outside_workspace()` rule matchers treat as the
+/// workspace: the turn's working directory and the extra writable roots of a workspace-write
+/// sandbox.
+pub(crate) fn exec_policy_workspace_roots(
+    turn_cwd: &Path,
+    sandbox_policy: &SandboxPolicy,
+) -> Vec<AbsolutePathBuf> {
+    let mut roots: Vec<AbsolutePathBuf> = AbsolutePathBuf::try_from(turn_cwd)
+        .ok()
+        .into_iter()
+        .collect();
+    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = sandbox_policy {
+        roots.extend(writable_roots.iter().cloned());
+    }
+    roots
 }
 
 impl ExecPolicyManager {
@@ -206,6 +236,8 @@ impl ExecPolicyManager {
             sandbox_policy,
             sandbox_permissions,
             prefix_rule,
+            cwd,
+            workspace_root,
         } = req;
         let exec_policy = self.current();
         let (commands, used_complex_parsing) = commands_for_exec_policy(command);
@@ -224,6 +256,10 @@ impl ExecPolicyManager {
         };
         let match_options = MatchOptions {
             resolve_host_executables: true,
+            cwd: cwd.and_then(|cwd| AbsolutePathBuf::try_from(cwd).ok()),
+            workspace_roots: workspace_root
+                .map(|root| exec_policy_workspace_roots(root, sandbox_policy))
+                .unwrap_or_default(),
         };
         let evaluation = exec_policy.check_multiple_with_options(
             commands.iter(),
@@ -286,11 +322,22 @@ impl ExecPolicyManager {
         amendment: &ExecPolicyAmendment,
     ) -> Result<(), ExecPolicyUpdateError> {
         let policy_path = default_policy_path(codex_home);
-        let prefix = amendment.command.clone();
+        let parse_clauses = |clauses: &[String]| {
+            clauses
+                .iter()
+                .map(|clause| ArgMatcher::parse(clause))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|source| ExecPolicyUpdateError::InvalidClause { source })
+        };
+        let rule = PrefixRuleAmendment {
+            args: parse_clauses(&amendment.args)?,
+            not_matching: parse_clauses(&amendment.not_matching)?,
+            ..PrefixRuleAmendment::allow(amendment.command.clone())
+        };
         spawn_blocking({
             let policy_path = policy_path.clone();
-            let prefix = prefix.clone();
-            move || blocking_append_allow_prefix_rule(&policy_path, &prefix)
+            let rule = rule.clone();
+            move || blocking_append_prefix_rule(&policy_path, &rule)
         })
         .await
         .map_err(|source| ExecPolicyUpdateError::JoinBlockingTask { source })?
@@ -300,7 +347,7 @@ impl ExecPolicyManager {
         })?;
 
         let mut updated_policy = self.current().as_ref().clone();
-        updated_policy.add_prefix_rule(&prefix, Decision::Allow)?;
+        updated_policy.add_prefix_rule_amendment(&rule)?;
         self.policy.store(Arc::new(updated_policy));
         Ok(())
     }
@@ -981,6 +1028,7 @@ mod tests {
                     decision: Decision::Forbidden,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 }],
             },
             policy.check_multiple(command.iter(), &|_| Decision::Allow)
@@ -1192,6 +1240,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
                     decision: Decision::Forbidden,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 }],
             },
             policy.check_multiple([vec!["rm".to_string()]].iter(), &|_| Decision::Allow)
@@ -1204,6 +1253,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
                     decision: Decision::Prompt,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 }],
             },
             policy.check_multiple([vec!["ls".to_string()]].iter(), &|_| Decision::Allow)
@@ -1236,6 +1286,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1286,6 +1338,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1313,6 +1367,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1341,6 +1397,8 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: Some(requested_prefix.clone()),
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1380,6 +1438,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1409,6 +1469,8 @@ prefix_rule(
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1445,6 +1507,8 @@ prefix_rule(pattern=["git"], decision="allow")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1487,6 +1551,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1515,6 +1581,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: Some(vec!["cargo".to_string(), "install".to_string()]),
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1548,6 +1616,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1595,6 +1665,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1631,6 +1703,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1665,6 +1739,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1688,6 +1764,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1712,6 +1790,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1740,6 +1820,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1768,6 +1850,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: Some(vec!["cargo".to_string(), "install".to_string()]),
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1799,6 +1883,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::RequireEscalated,
                 prefix_rule: Some(vec!["cargo".to_string(), "install".to_string()]),
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1837,6 +1923,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                     sandbox_policy: &SandboxPolicy::DangerFullAccess,
                     sandbox_permissions: SandboxPermissions::UseDefault,
                     prefix_rule: None,
+                    cwd: None,
+                    workspace_root: None,
                 })
                 .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -1881,6 +1969,52 @@ prefix_rule(pattern=["git"], decision="prompt")
         );
     }
 
+    #[tokio::test]
+    async fn approved_amendment_persists_argument_clauses() {
+        let codex_home = tempdir().expect("create temp dir");
+        let manager = ExecPolicyManager::default();
+        let amendment = ExecPolicyAmendment {
+            not_matching: vec![r#"flag("--force", "-f")"#.to_string()],
+            ..ExecPolicyAmendment::new(vec!["git".to_string(), "push".to_string()])
+        };
+
+        manager
+            .append_amendment_and_update(codex_home.path(), &amendment)
+            .await
+            .expect("update policy");
+
+        let contents = fs::read_to_string(default_policy_path(codex_home.path()))
+            .expect("policy file should have been created");
+        assert_eq!(
+            contents,
+            r#"prefix_rule(pattern=["git", "push"], decision="allow", not_matching=[flag("--force", "-f")])
+"#
+        );
+        let updated_policy = manager.current();
+        let decision = |command: &[&str]| {
+            let command = command.iter().map(ToString::to_string).collect::<Vec<_>>();
+            updated_policy
+                .check(&command, &|_| Decision::Prompt)
+                .decision
+        };
+        assert_eq!(decision(&["git", "push", "origin"]), Decision::Allow);
+        assert_eq!(
+            decision(&["git", "push", "-qf", "origin"]),
+            Decision::Prompt
+        );
+
+        let invalid = ExecPolicyAmendment {
+            args: vec!["flag(\"force\")".to_string()],
+            ..ExecPolicyAmendment::new(vec!["git".to_string()])
+        };
+        assert!(matches!(
+            manager
+                .append_amendment_and_update(codex_home.path(), &invalid)
+                .await,
+            Err(ExecPolicyUpdateError::InvalidClause { .. })
+        ));
+    }
+
     #[tokio::test]
     async fn append_execpolicy_amendment_rejects_empty_prefix() {
         let codex_home = tempdir().expect("create temp dir");
@@ -1911,6 +2045,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1941,6 +2077,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -1968,6 +2106,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -2006,6 +2146,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: SandboxPermissions::UseDefault,
                     prefix_rule: None,
+                    cwd: None,
+                    workspace_root: None,
                 })
                 .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -2029,6 +2171,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -2059,6 +2203,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -2182,6 +2328,7 @@ prefix_rule(pattern=["git"], decision="prompt")
             decision: Decision::Prompt,
             resolved_program: None,
             justification: None,
+            matched_clauses: Vec::new(),
         }];
         assert_eq!(
             None,
@@ -2196,6 +2343,7 @@ prefix_rule(pattern=["git"], decision="prompt")
             decision: Decision::Allow,
             resolved_program: None,
             justification: None,
+            matched_clauses: Vec::new(),
         }];
         assert_eq!(
             None,
@@ -2210,6 +2358,7 @@ prefix_rule(pattern=["git"], decision="prompt")
             decision: Decision::Forbidden,
             resolved_program: None,
             justification: None,
+            matched_clauses: Vec::new(),
         }];
         assert_eq!(
             None,
@@ -2232,6 +2381,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                 sandbox_policy: &SandboxPolicy::DangerFullAccess,
                 sandbox_permissions: SandboxPermissions::UseDefault,
                 prefix_rule: None,
+                cwd: None,
+                workspace_root: None,
             })
             .await;
 
@@ -2298,6 +2449,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    cwd: None,
+                    workspace_root: None,
                 })
                 .await,
             "{pwsh_approval_reason}"
@@ -2321,6 +2474,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    cwd: None,
+                    workspace_root: None,
                 })
                 .await,
             r#"On all platforms, a forbidden command should require approval
@@ -2340,6 +2495,8 @@ prefix_rule(pattern=["git"], decision="prompt")
                     sandbox_policy: &SandboxPolicy::new_read_only_policy(),
                     sandbox_permissions: permissions,
                     prefix_rule: None,
+                    cwd: None,
+                    workspace_root: None,
                 })
                 .await,
             r#"On all platforms, a forbidden command should require approval
@@ -345,7 +345,7 @@ impl ExternalAgentConfigService {
     }
 }
 
-fn default_claude_home() -> PathBuf {
+pub(crate) fn default_claude_home() -> PathBuf {
     if let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
         return PathBuf::from(home).join(".claude");
     }
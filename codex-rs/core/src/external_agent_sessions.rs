@@ -0,0 +1,868 @@
+//! Import transcripts recorded by other coding agents as Codex threads.
+//!
+//! Each importer turns a JSONL transcript into rollout items: messages become
+//! `Message` items together with the user/agent events that thread listings
+//! and memories read, shell commands become `LocalShellCall` items and other
+//! tool calls `FunctionCall` items. The rollout is written under `sessions/`
+//! and registered in the state DB so it can be resumed like any other thread.
+//!
+//! Thread ids are derived from the source session, so importing the same
+//! transcript twice finds the earlier import instead of creating a copy.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_protocol::ThreadId;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::LocalShellExecAction;
+use codex_protocol::models::LocalShellStatus;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AgentMessageEvent;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::GitInfo;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionMeta;
+use codex_protocol::protocol::SessionMetaLine;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::UserMessageEvent;
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use crate::config::Config;
+use crate::external_agent_config::default_claude_home;
+use crate::rollout::SESSIONS_SUBDIR;
+use crate::rollout::append_thread_name;
+use crate::rollout::find_thread_path_by_id_str;
+use crate::state_db;
+
+/// Originator recorded in the session meta of imported threads.
+const IMPORT_ORIGINATOR: &str = "codex_import";
+
+/// Tool names that other agents use for running a shell command. Calls to
+/// these tools are imported as `LocalShellCall` items.
+const SHELL_TOOL_NAMES: &[&str] = &[
+    "bash",
+    "shell",
+    "run_shell_command",
+    "run_terminal_cmd",
+    "execute_command",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExternalTranscriptFormat {
+    /// Claude Code session logs from `~/.claude/projects/<project>/<session>.jsonl`.
+    ClaudeCode,
+    /// One OpenAI Chat Completions message object per line, as written by
+    /// many agents and evaluation harnesses.
+    ChatCompletions,
+}
+
+impl ExternalTranscriptFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ClaudeCode => "claude-code",
+            Self::ChatCompletions => "chat-completions",
+        }
+    }
+
+    /// Guess the format from the first JSON object in the transcript.
+    fn detect(contents: &str) -> Option<Self> {
+        let first = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .find_map(|line| serde_json::from_str::<JsonValue>(line).ok())?;
+        if first.get("role").is_some() {
+            Some(Self::ChatCompletions)
+        } else if first.get("sessionId").is_some()
+            || first.get("message").is_some()
+            || first.get("type").and_then(JsonValue::as_str) == Some("summary")
+        {
+            Some(Self::ClaudeCode)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedThread {
+    pub thread_id: ThreadId,
+    pub rollout_path: PathBuf,
+    /// Title recorded by the source agent, also stored as the thread name.
+    pub name: Option<String>,
+    /// The transcript had already been imported; nothing was written.
+    pub already_imported: bool,
+}
+
+/// Claude Code transcripts for the project rooted at `cwd`, or for every
+/// project when `cwd` is `None`.
+pub fn claude_code_transcripts(cwd: Option<&Path>) -> io::Result<Vec<PathBuf>> {
+    let projects = default_claude_home().join("projects");
+    let project_dirs = match cwd {
+        Some(cwd) => vec![projects.join(claude_code_project_dir_name(cwd))],
+        None => match std::fs::read_dir(&projects) {
+            Ok(entries) => entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        },
+    };
+
+    let mut transcripts = Vec::new();
+    for dir in project_dirs {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "jsonl") && path.is_file() {
+                transcripts.push(path);
+            }
+        }
+    }
+    transcripts.sort();
+    Ok(transcripts)
+}
+
+/// Claude Code names project directories after the project path with every
+/// character other than ASCII letters and digits replaced by `-`.
+fn claude_code_project_dir_name(cwd: &Path) -> String {
+    cwd.to_string_lossy()
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '-' })
+        .collect()
+}
+
+/// Import the transcript at `source` as a Codex thread. The format is
+/// detected from the file when `format` is `None`.
+pub async fn import_external_transcript(
+    config: &Config,
+    source: &Path,
+    format: Option<ExternalTranscriptFormat>,
+) -> io::Result<ImportedThread> {
+    let contents = tokio::fs::read_to_string(source).await?;
+    let format = match format.or_else(|| ExternalTranscriptFormat::detect(&contents)) {
+        Some(format) => format,
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unrecognized transcript format: {}", source.display()),
+            ));
+        }
+    };
+    let source_path = std::fs::canonicalize(source)?;
+    let transcript = match format {
+        ExternalTranscriptFormat::ClaudeCode => parse_claude_code(&contents),
+        ExternalTranscriptFormat::ChatCompletions => parse_chat_completions(&contents),
+    };
+    if transcript.entries.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("no messages found in {}", source.display()),
+        ));
+    }
+
+    let source_id = transcript
+        .session_id
+        .clone()
+        .unwrap_or_else(|| source_path.display().to_string());
+    let thread_id = imported_thread_id(format, &source_id)?;
+    let name = transcript.title.clone();
+    if let Some(rollout_path) =
+        find_thread_path_by_id_str(&config.codex_home, &thread_id.to_string()).await?
+    {
+        return Ok(ImportedThread {
+            thread_id,
+            rollout_path,
+            name,
+            already_imported: true,
+        });
+    }
+
+    let started_at = transcript.started_at.unwrap_or_else(Utc::now);
+    let lines = rollout_lines(thread_id, &transcript, &config.cwd, started_at);
+    let rollout_path = imported_rollout_path(&config.codex_home, thread_id, started_at);
+    if let Some(parent) = rollout_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let mut jsonl = String::new();
+    for line in &lines {
+        jsonl.push_str(&serde_json::to_string(line).map_err(io::Error::other)?);
+        jsonl.push('\n');
+    }
+    tokio::fs::write(&rollout_path, jsonl).await?;
+
+    if let Some(name) = name.as_deref() {
+        append_thread_name(&config.codex_home, thread_id, name).await?;
+    }
+    if let Some(state_db) = state_db::get_state_db(config).await {
+        state_db::reconcile_rollout(
+            Some(state_db.as_ref()),
+            &rollout_path,
+            config.model_provider_id.as_str(),
+            None,
+            &[],
+            None,
+            None,
+        )
+        .await;
+    }
+
+    Ok(ImportedThread {
+        thread_id,
+        rollout_path,
+        name,
+        already_imported: false,
+    })
+}
+
+fn imported_thread_id(format: ExternalTranscriptFormat, source_id: &str) -> io::Result<ThreadId> {
+    let uuid = Uuid::new_v5(
+        &Uuid::NAMESPACE_URL,
+        format!("codex-import:{}:{source_id}", format.as_str()).as_bytes(),
+    );
+    ThreadId::from_string(&uuid.to_string()).map_err(io::Error::other)
+}
+
+fn imported_rollout_path(
+    codex_home: &Path,
+    thread_id: ThreadId,
+    started_at: DateTime<Utc>,
+) -> PathBuf {
+    codex_home
+        .join(SESSIONS_SUBDIR)
+        .join(started_at.format("%Y").to_string())
+        .join(started_at.format("%m").to_string())
+        .join(started_at.format("%d").to_string())
+        .join(format!(
+            "rollout-{}-{thread_id}.jsonl",
+            started_at.format("%Y-%m-%dT%H-%M-%S")
+        ))
+}
+
+#[derive(Debug, Default)]
+struct Transcript {
+    session_id: Option<String>,
+    cwd: Option<PathBuf>,
+    git_branch: Option<String>,
+    title: Option<String>,
+    started_at: Option<DateTime<Utc>>,
+    entries: Vec<TranscriptEntry>,
+}
+
+#[derive(Debug)]
+struct TranscriptEntry {
+    timestamp: Option<DateTime<Utc>>,
+    item: TranscriptItem,
+}
+
+#[derive(Debug, PartialEq)]
+enum TranscriptItem {
+    User(String),
+    Assistant(String),
+    ToolCall {
+        call_id: String,
+        name: String,
+        input: JsonValue,
+    },
+    ToolResult {
+        call_id: String,
+        output: String,
+        is_error: bool,
+    },
+}
+
+impl Transcript {
+    fn push(&mut self, timestamp: Option<DateTime<Utc>>, item: TranscriptItem) {
+        if self.started_at.is_none() {
+            self.started_at = timestamp;
+        }
+        self.entries.push(TranscriptEntry { timestamp, item });
+    }
+}
+
+fn parse_claude_code(contents: &str) -> Transcript {
+    let mut transcript = Transcript::default();
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        let Ok(record) = serde_json::from_str::<JsonValue>(line) else {
+            continue;
+        };
+        let record_type = record.get("type").and_then(JsonValue::as_str);
+        if record_type == Some("summary") {
+            if let Some(summary) = record.get("summary").and_then(JsonValue::as_str) {
+                transcript.title = Some(summary.to_string());
+            }
+            continue;
+        }
+        // Sidechains are Task sub-agent conversations and meta records are
+        // notices Claude Code injects; neither is part of the main thread.
+        let is_flag_set = |key: &str| record.get(key).and_then(JsonValue::as_bool) == Some(true);
+        if is_flag_set("isSidechain") || is_flag_set("isMeta") {
+            continue;
+        }
+        if transcript.session_id.is_none() {
+            transcript.session_id = string_field(&record, "sessionId");
+        }
+        if transcript.cwd.is_none() {
+            transcript.cwd = string_field(&record, "cwd").map(PathBuf::from);
+        }
+        if transcript.git_branch.is_none() {
+            transcript.git_branch = string_field(&record, "gitBranch");
+        }
+        let timestamp = parse_timestamp(record.get("timestamp"));
+        let Some(content) = record
+            .get("message")
+            .and_then(|message| message.get("content"))
+        else {
+            continue;
+        };
+
+        match record_type {
+            Some("user") => match content {
+                JsonValue::String(text) => {
+                    transcript.push(timestamp, TranscriptItem::User(text.clone()));
+                }
+                JsonValue::Array(blocks) => {
+                    let mut text = String::new();
+                    for block in blocks {
+                        match block.get("type").and_then(JsonValue::as_str) {
+                            Some("text") => {
+                                if let Some(block_text) = string_field(block, "text") {
+                                    push_paragraph(&mut text, &block_text);
+                                }
+                            }
+                            Some("image") => push_paragraph(&mut text, "[image]"),
+                            Some("tool_result") => {
+                                let Some(call_id) = string_field(block, "tool_use_id") else {
+                                    continue;
+                                };
+                                transcript.push(
+                                    timestamp,
+                                    TranscriptItem::ToolResult {
+                                        call_id,
+                                        output: content_text(block.get("content")),
+                                        is_error: block
+                                            .get("is_error")
+                                            .and_then(JsonValue::as_bool)
+                                            == Some(true),
+                                    },
+                                );
+                            }
+                            _ => {}
+                        }
+                    }
+                    if !text.is_empty() {
+                        transcript.push(timestamp, TranscriptItem::User(text));
+                    }
+                }
+                _ => {}
+            },
+            Some("assistant") => {
+                let JsonValue::Array(blocks) = content else {
+                    if let Some(text) = content.as_str() {
+                        transcript.push(timestamp, TranscriptItem::Assistant(text.to_string()));
+                    }
+                    continue;
+                };
+                for block in blocks {
+                    match block.get("type").and_then(JsonValue::as_str) {
+                        Some("text") => {
+                            if let Some(text) = string_field(block, "text")
+                                && !text.is_empty()
+                            {
+                                transcript.push(timestamp, TranscriptItem::Assistant(text));
+                            }
+                        }
+                        Some("tool_use") => {
+                            let (Some(call_id), Some(name)) =
+                                (string_field(block, "id"), string_field(block, "name"))
+                            else {
+                                continue;
+                            };
+                            transcript.push(
+                                timestamp,
+                                TranscriptItem::ToolCall {
+                                    call_id,
+                                    name,
+                                    input: block.get("input").cloned().unwrap_or_default(),
+                                },
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    transcript
+}
+
+fn parse_chat_completions(contents: &str) -> Transcript {
+    let mut transcript = Transcript::default();
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        let Ok(message) = serde_json::from_str::<JsonValue>(line) else {
+            continue;
+        };
+        let timestamp = parse_timestamp(message.get("timestamp"));
+        let text = content_text(message.get("content"));
+        match message.get("role").and_then(JsonValue::as_str) {
+            Some("user") => {
+                if !text.is_empty() {
+                    transcript.push(timestamp, TranscriptItem::User(text));
+                }
+            }
+            Some("assistant") => {
+                if !text.is_empty() {
+                    transcript.push(timestamp, TranscriptItem::Assistant(text));
+                }
+                let tool_calls = message
+                    .get("tool_calls")
+                    .and_then(JsonValue::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                for tool_call in tool_calls {
+                    let Some(function) = tool_call.get("function") else {
+                        continue;
+                    };
+                    let (Some(call_id), Some(name)) = (
+                        string_field(tool_call, "id"),
+                        string_field(function, "name"),
+                    ) else {
+                        continue;
+                    };
+                    // Arguments are a JSON-encoded string on the wire.
+                    let input = match function.get("arguments") {
+                        Some(JsonValue::String(arguments)) => serde_json::from_str(arguments)
+                            .unwrap_or_else(|_| JsonValue::String(arguments.clone())),
+                        Some(arguments) => arguments.clone(),
+                        None => JsonValue::Null,
+                    };
+                    transcript.push(
+                        timestamp,
+                        TranscriptItem::ToolCall {
+                            call_id,
+                            name,
+                            input,
+                        },
+                    );
+                }
+            }
+            Some("tool") => {
+                if let Some(call_id) = string_field(&message, "tool_call_id") {
+                    transcript.push(
+                        timestamp,
+                        TranscriptItem::ToolResult {
+                            call_id,
+                            output: text,
+                            is_error: false,
+                        },
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    transcript
+}
+
+fn rollout_lines(
+    thread_id: ThreadId,
+    transcript: &Transcript,
+    fallback_cwd: &Path,
+    started_at: DateTime<Utc>,
+) -> Vec<RolloutLine> {
+    let started_at_str = format_timestamp(started_at);
+    let session_meta = SessionMetaLine {
+        meta: SessionMeta {
+            id: thread_id,
+            timestamp: started_at_str.clone(),
+            cwd: transcript
+                .cwd
+                .clone()
+                .unwrap_or_else(|| fallback_cwd.to_path_buf()),
+            originator: IMPORT_ORIGINATOR.to_string(),
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            source: SessionSource::Cli,
+            ..SessionMeta::default()
+        },
+        git: transcript.git_branch.as_ref().map(|branch| GitInfo {
+            commit_hash: None,
+            branch: Some(branch.clone()),
+            repository_url: None,
+        }),
+    };
+    let mut lines = vec![RolloutLine {
+        timestamp: started_at_str,
+        item: RolloutItem::SessionMeta(session_meta),
+    }];
+
+    let mut last_timestamp = started_at;
+    for entry in &transcript.entries {
+        last_timestamp = entry.timestamp.unwrap_or(last_timestamp);
+        let timestamp = format_timestamp(last_timestamp);
+        for item in rollout_items(&entry.item) {
+            lines.push(RolloutLine {
+                timestamp: timestamp.clone(),
+                item,
+            });
+        }
+    }
+    lines
+}
+
+fn rollout_items(item: &TranscriptItem) -> Vec<RolloutItem> {
+    match item {
+        TranscriptItem::User(text) => vec![
+            RolloutItem::ResponseItem(ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText { text: text.clone() }],
+                end_turn: None,
+                phase: None,
+            }),
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: text.clone(),
+                images: None,
+                local_images: Vec::new(),
+                text_elements: Vec::new(),
+            })),
+        ],
+        TranscriptItem::Assistant(text) => vec![
+            RolloutItem::ResponseItem(ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText { text: text.clone() }],
+                end_turn: None,
+                phase: None,
+            }),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: text.clone(),
+                phase: None,
+            })),
+        ],
+        TranscriptItem::ToolCall {
+            call_id,
+            name,
+            input,
+        } => {
+            let item = match shell_command(name, input) {
+                Some(command) => ResponseItem::LocalShellCall {
+                    id: None,
+                    call_id: Some(call_id.clone()),
+                    status: LocalShellStatus::Completed,
+                    action: LocalShellAction::Exec(LocalShellExecAction {
+                        command,
+                        timeout_ms: None,
+                        working_directory: string_field(input, "cwd")
+                            .or_else(|| string_field(input, "workdir")),
+                        env: None,
+                        user: None,
+                    }),
+                },
+                None => ResponseItem::FunctionCall {
+                    id: None,
+                    name: name.clone(),
+                    arguments: input.to_string(),
+                    call_id: call_id.clone(),
+                },
+            };
+            vec![RolloutItem::ResponseItem(item)]
+        }
+        TranscriptItem::ToolResult {
+            call_id,
+            output,
+            is_error,
+        } => vec![RolloutItem::ResponseItem(
+            ResponseItem::FunctionCallOutput {
+                call_id: call_id.clone(),
+                output: FunctionCallOutputPayload {
+                    body: FunctionCallOutputBody::Text(output.clone()),
+                    success: Some(!is_error),
+                },
+            },
+        )],
+    }
+}
+
+/// The argv of a call to one of [`SHELL_TOOL_NAMES`], wrapping string
+/// commands in `bash -lc`.
+fn shell_command(name: &str, input: &JsonValue) -> Option<Vec<String>> {
+    if !SHELL_TOOL_NAMES.contains(&name.to_ascii_lowercase().as_str()) {
+        return None;
+    }
+    match input.get("command")? {
+        JsonValue::String(script) => {
+            Some(vec!["bash".to_string(), "-lc".to_string(), script.clone()])
+        }
+        JsonValue::Array(argv) => argv
+            .iter()
+            .map(|arg| arg.as_str().map(str::to_string))
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Text of a message or tool result `content`, which is either a string or a
+/// list of typed blocks.
+fn content_text(content: Option<&JsonValue>) -> String {
+    match content {
+        Some(JsonValue::String(text)) => text.clone(),
+        Some(JsonValue::Array(blocks)) => {
+            let mut text = String::new();
+            for block in blocks {
+                match block.get("type").and_then(JsonValue::as_str) {
+                    Some("text") => {
+                        if let Some(block_text) = string_field(block, "text") {
+                            push_paragraph(&mut text, &block_text);
+                        }
+                    }
+                    Some("image") | Some("image_url") => push_paragraph(&mut text, "[image]"),
+                    _ => {}
+                }
+            }
+            text
+        }
+        _ => String::new(),
+    }
+}
+
+fn push_paragraph(text: &mut String, paragraph: &str) {
+    if !text.is_empty() {
+        text.push_str("\n\n");
+    }
+    text.push_str(paragraph);
+}
+
+fn string_field(value: &JsonValue, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(JsonValue::as_str)
+        .map(str::to_string)
+}
+
+fn parse_timestamp(value: Option<&JsonValue>) -> Option<DateTime<Utc>> {
+    let timestamp = value?.as_str()?;
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn jsonl(lines: &[JsonValue]) -> String {
+        lines
+            .iter()
+            .map(JsonValue::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn detects_transcript_formats() {
+        let claude = jsonl(&[json!({"type": "summary", "summary": "Fix CI"})]);
+        let chat = jsonl(&[json!({"role": "system", "content": "You are helpful."})]);
+
+        assert_eq!(
+            ExternalTranscriptFormat::detect(&claude),
+            Some(ExternalTranscriptFormat::ClaudeCode)
+        );
+        assert_eq!(
+            ExternalTranscriptFormat::detect(&chat),
+            Some(ExternalTranscriptFormat::ChatCompletions)
+        );
+        assert_eq!(ExternalTranscriptFormat::detect("not json"), None);
+    }
+
+    #[test]
+    fn parses_claude_code_messages_and_tool_calls() {
+        let contents = jsonl(&[
+            json!({"type": "summary", "summary": "Fix the failing build"}),
+            json!({
+                "type": "user", "sessionId": "abc", "cwd": "/repo", "gitBranch": "main",
+                "timestamp": "2025-06-01T10:00:00.000Z", "isMeta": true,
+                "message": {"role": "user", "content": "Caveat: injected notice"}
+            }),
+            json!({
+                "type": "user", "sessionId": "abc", "cwd": "/repo", "gitBranch": "main",
+                "timestamp": "2025-06-01T10:00:01.000Z",
+                "message": {"role": "user", "content": "Why does the build fail?"}
+            }),
+            json!({
+                "type": "assistant", "sessionId": "abc", "timestamp": "2025-06-01T10:00:02.000Z",
+                "message": {"role": "assistant", "content": [
+                    {"type": "thinking", "thinking": "hmm"},
+                    {"type": "text", "text": "Let me run it."},
+                    {"type": "tool_use", "id": "toolu_1", "name": "Bash", "input": {"command": "cargo build"}}
+                ]}
+            }),
+            json!({
+                "type": "user", "sessionId": "abc", "timestamp": "2025-06-01T10:00:03.000Z",
+                "message": {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "is_error": true,
+                     "content": [{"type": "text", "text": "error: missing semicolon"}]}
+                ]}
+            }),
+            json!({
+                "type": "assistant", "sessionId": "abc", "isSidechain": true,
+                "message": {"role": "assistant", "content": [{"type": "text", "text": "sub-agent"}]}
+            }),
+        ]);
+
+        let transcript = parse_claude_code(&contents);
+
+        assert_eq!(transcript.session_id.as_deref(), Some("abc"));
+        assert_eq!(transcript.cwd, Some(PathBuf::from("/repo")));
+        assert_eq!(transcript.git_branch.as_deref(), Some("main"));
+        assert_eq!(transcript.title.as_deref(), Some("Fix the failing build"));
+        assert_eq!(
+            transcript.started_at.map(format_timestamp).as_deref(),
+            Some("2025-06-01T10:00:01.000Z")
+        );
+        assert_eq!(
+            transcript
+                .entries
+                .into_iter()
+                .map(|entry| entry.item)
+                .collect::<Vec<_>>(),
+            vec![
+                TranscriptItem::User("Why does the build fail?".to_string()),
+                TranscriptItem::Assistant("Let me run it.".to_string()),
+                TranscriptItem::ToolCall {
+                    call_id: "toolu_1".to_string(),
+                    name: "Bash".to_string(),
+                    input: json!({"command": "cargo build"}),
+                },
+                TranscriptItem::ToolResult {
+                    call_id: "toolu_1".to_string(),
+                    output: "error: missing semicolon".to_string(),
+                    is_error: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_chat_completions_tool_calls() {
+        let contents = jsonl(&[
+            json!({"role": "system", "content": "You are a coding agent."}),
+            json!({"role": "user", "content": [{"type": "text", "text": "List files"}]}),
+            json!({"role": "assistant", "content": null, "tool_calls": [
+                {"id": "call_1", "type": "function",
+                 "function": {"name": "read_file", "arguments": "{\"path\":\"README.md\"}"}}
+            ]}),
+            json!({"role": "tool", "tool_call_id": "call_1", "content": "# Readme"}),
+        ]);
+
+        let items = parse_chat_completions(&contents)
+            .entries
+            .into_iter()
+            .map(|entry| entry.item)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            items,
+            vec![
+                TranscriptItem::User("List files".to_string()),
+                TranscriptItem::ToolCall {
+                    call_id: "call_1".to_string(),
+                    name: "read_file".to_string(),
+                    input: json!({"path": "README.md"}),
+                },
+                TranscriptItem::ToolResult {
+                    call_id: "call_1".to_string(),
+                    output: "# Readme".to_string(),
+                    is_error: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn shell_tools_become_local_shell_calls() {
+        let items = rollout_items(&TranscriptItem::ToolCall {
+            call_id: "toolu_1".to_string(),
+            name: "Bash".to_string(),
+            input: json!({"command": "cargo test"}),
+        });
+        let [RolloutItem::ResponseItem(item)] = items.as_slice() else {
+            panic!("expected a single response item, got {items:?}");
+        };
+        assert_eq!(
+            item,
+            &ResponseItem::LocalShellCall {
+                id: None,
+                call_id: Some("toolu_1".to_string()),
+                status: LocalShellStatus::Completed,
+                action: LocalShellAction::Exec(LocalShellExecAction {
+                    command: vec![
+                        "bash".to_string(),
+                        "-lc".to_string(),
+                        "cargo test".to_string()
+                    ],
+                    timeout_ms: None,
+                    working_directory: None,
+                    env: None,
+                    user: None,
+                }),
+            }
+        );
+
+        let items = rollout_items(&TranscriptItem::ToolCall {
+            call_id: "toolu_2".to_string(),
+            name: "Read".to_string(),
+            input: json!({"file_path": "src/lib.rs"}),
+        });
+        let [RolloutItem::ResponseItem(item)] = items.as_slice() else {
+            panic!("expected a single response item, got {items:?}");
+        };
+        assert_eq!(
+            item,
+            &ResponseItem::FunctionCall {
+                id: None,
+                name: "Read".to_string(),
+                arguments: r#"{"file_path":"src/lib.rs"}"#.to_string(),
+                call_id: "toolu_2".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn imported_thread_ids_are_stable_per_source() {
+        let first = imported_thread_id(ExternalTranscriptFormat::ClaudeCode, "abc").unwrap();
+        let again = imported_thread_id(ExternalTranscriptFormat::ClaudeCode, "abc").unwrap();
+        let other = imported_thread_id(ExternalTranscriptFormat::ChatCompletions, "abc").unwrap();
+
+        assert_eq!(first, again);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn claude_code_project_dirs_replace_separators() {
+        assert_eq!(
+            claude_code_project_dir_name(Path::new("/Users/me/src/my.app")),
+            "-Users-me-src-my-app"
+        );
+    }
+}
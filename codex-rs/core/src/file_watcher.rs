@@ -1,5 +1,6 @@
-//! Watches skill roots for changes and broadcasts coarse-grained
-//! `FileWatcherEvent`s that higher-level components react to on the next turn.
+//! Watches skill roots and indexed workspaces for changes and broadcasts
+//! coarse-grained `FileWatcherEvent`s that higher-level components react to on
+//! the next turn.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -27,11 +28,35 @@ use crate::skills::SkillsManager;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FileWatcherEvent {
-    SkillsChanged { paths: Vec<PathBuf> },
+    SkillsChanged {
+        paths: Vec<PathBuf>,
+    },
+    /// Files changed under a workspace registered with
+    /// [`FileWatcher::register_workspace`].
+    WorkspaceChanged {
+        paths: Vec<PathBuf>,
+    },
 }
 
 struct WatchState {
     skills_root_ref_counts: HashMap<PathBuf, usize>,
+    workspace_root_ref_counts: HashMap<PathBuf, usize>,
+    /// Directories watched (non-recursively) for each registered workspace.
+    workspace_dirs: HashMap<PathBuf, HashSet<PathBuf>>,
+}
+
+impl WatchState {
+    fn new() -> Self {
+        Self {
+            skills_root_ref_counts: HashMap::new(),
+            workspace_root_ref_counts: HashMap::new(),
+            workspace_dirs: HashMap::new(),
+        }
+    }
+
+    fn is_workspace_dir(&self, path: &Path) -> bool {
+        self.workspace_dirs.values().any(|dirs| dirs.contains(path))
+    }
 }
 
 struct FileWatcherInner {
@@ -41,17 +66,27 @@ struct FileWatcherInner {
 
 const WATCHER_THROTTLE_INTERVAL: Duration = Duration::from_secs(10);
 
+/// Workspace changes feed the code index, which should catch up with edits
+/// quickly.
+const WORKSPACE_THROTTLE_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Coalesces bursts of paths and emits at most once per interval.
 struct ThrottledPaths {
     pending: HashSet<PathBuf>,
     next_allowed_at: Instant,
+    interval: Duration,
 }
 
 impl ThrottledPaths {
     fn new(now: Instant) -> Self {
+        Self::with_interval(now, WATCHER_THROTTLE_INTERVAL)
+    }
+
+    fn with_interval(now: Instant, interval: Duration) -> Self {
         Self {
             pending: HashSet::new(),
             next_allowed_at: now,
+            interval,
         }
     }
 
@@ -80,7 +115,7 @@ impl ThrottledPaths {
     fn take_with_next_allowed(&mut self, now: Instant) -> Vec<PathBuf> {
         let mut paths: Vec<PathBuf> = self.pending.drain().collect();
         paths.sort_unstable_by(|a, b| a.as_os_str().cmp(b.as_os_str()));
-        self.next_allowed_at = now + WATCHER_THROTTLE_INTERVAL;
+        self.next_allowed_at = now + self.interval;
         paths
     }
 }
@@ -104,6 +139,20 @@ impl Drop for WatchRegistration {
     }
 }
 
+/// Keeps a workspace root watched until dropped.
+pub(crate) struct WorkspaceWatchRegistration {
+    file_watcher: std::sync::Weak<FileWatcher>,
+    root: PathBuf,
+}
+
+impl Drop for WorkspaceWatchRegistration {
+    fn drop(&mut self) {
+        if let Some(file_watcher) = self.file_watcher.upgrade() {
+            file_watcher.unregister_workspace_root(&self.root);
+        }
+    }
+}
+
 impl FileWatcher {
     pub(crate) fn new(_codex_home: PathBuf) -> notify::Result<Self> {
         let (raw_tx, raw_rx) = mpsc::unbounded_channel();
@@ -116,9 +165,7 @@ impl FileWatcher {
             watched_paths: HashMap::new(),
         };
         let (tx, _) = broadcast::channel(128);
-        let state = Arc::new(RwLock::new(WatchState {
-            skills_root_ref_counts: HashMap::new(),
-        }));
+        let state = Arc::new(RwLock::new(WatchState::new()));
         let file_watcher = Self {
             inner: Some(Mutex::new(inner)),
             state: Arc::clone(&state),
@@ -132,9 +179,7 @@ impl FileWatcher {
         let (tx, _) = broadcast::channel(1);
         Self {
             inner: None,
-            state: Arc::new(RwLock::new(WatchState {
-                skills_root_ref_counts: HashMap::new(),
-            })),
+            state: Arc::new(RwLock::new(WatchState::new())),
             tx,
         }
     }
@@ -165,6 +210,64 @@ impl FileWatcher {
         }
     }
 
+    /// Reports changes below `root` as [`FileWatcherEvent::WorkspaceChanged`]
+    /// until the registration is dropped. Nothing is watched until the
+    /// directories to watch are added with [`Self::watch_workspace_dirs`].
+    pub(crate) fn register_workspace(
+        self: &Arc<Self>,
+        root: PathBuf,
+    ) -> WorkspaceWatchRegistration {
+        {
+            let mut state = self
+                .state
+                .write()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            *state
+                .workspace_root_ref_counts
+                .entry(root.clone())
+                .or_insert(0) += 1;
+            state.workspace_dirs.entry(root.clone()).or_default();
+        }
+        WorkspaceWatchRegistration {
+            file_watcher: Arc::downgrade(self),
+            root,
+        }
+    }
+
+    /// Watches `dirs` of the registered workspace `root`, each without
+    /// recursion, so directories the caller leaves out (ignored build output,
+    /// dependencies, ...) are never watched. Directories that were deleted
+    /// and re-created are watched again.
+    pub(crate) fn watch_workspace_dirs(&self, root: &Path, dirs: Vec<PathBuf>) {
+        let mut state = self
+            .state
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Some(watched_dirs) = state.workspace_dirs.get_mut(root) else {
+            return;
+        };
+        let Some(inner) = &self.inner else {
+            watched_dirs.extend(dirs);
+            return;
+        };
+        let mut guard = inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        for dir in dirs {
+            // A recursive watch (a skills root) already covers the directory.
+            if guard.watched_paths.get(&dir) != Some(&RecursiveMode::Recursive) {
+                if let Err(err) = guard.watcher.watch(&dir, RecursiveMode::NonRecursive) {
+                    warn!("failed to watch {}: {err}", dir.display());
+                    continue;
+                }
+                guard
+                    .watched_paths
+                    .insert(dir.clone(), RecursiveMode::NonRecursive);
+            }
+            watched_dirs.insert(dir);
+        }
+    }
+
     // Bridge `notify`'s callback-based events into the Tokio runtime and
     // broadcast coarse-grained change signals to subscribers.
     fn spawn_event_loop(
@@ -177,10 +280,15 @@ impl FileWatcher {
             handle.spawn(async move {
                 let now = Instant::now();
                 let mut skills = ThrottledPaths::new(now);
+                let mut workspace = ThrottledPaths::with_interval(now, WORKSPACE_THROTTLE_INTERVAL);
 
                 loop {
                     let now = Instant::now();
-                    let next_deadline = skills.next_deadline(now);
+                    let next_deadline =
+                        match (skills.next_deadline(now), workspace.next_deadline(now)) {
+                            (Some(a), Some(b)) => Some(a.min(b)),
+                            (a, b) => a.or(b),
+                        };
                     let timer_deadline = next_deadline
                         .unwrap_or_else(|| now + Duration::from_secs(60 * 60 * 24 * 365));
                     let timer = sleep_until(timer_deadline);
@@ -191,12 +299,19 @@ impl FileWatcher {
                             match res {
                                 Some(Ok(event)) => {
                                     let skills_paths = classify_event(&event, &state);
+                                    let workspace_paths =
+                                        classify_workspace_event(&event, &state);
                                     let now = Instant::now();
                                     skills.add(skills_paths);
+                                    workspace.add(workspace_paths);
 
                                     if let Some(paths) = skills.take_ready(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = workspace.take_ready(now) {
+                                        let _ =
+                                            tx.send(FileWatcherEvent::WorkspaceChanged { paths });
+                                    }
                                 }
                                 Some(Err(err)) => {
                                     warn!("file watcher error: {err}");
@@ -208,6 +323,10 @@ impl FileWatcher {
                                     if let Some(paths) = skills.take_pending(now) {
                                         let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                                     }
+                                    if let Some(paths) = workspace.take_pending(now) {
+                                        let _ =
+                                            tx.send(FileWatcherEvent::WorkspaceChanged { paths });
+                                    }
                                     break;
                                 }
                             }
@@ -217,6 +336,9 @@ impl FileWatcher {
                             if let Some(paths) = skills.take_ready(now) {
                                 let _ = tx.send(FileWatcherEvent::SkillsChanged { paths });
                             }
+                            if let Some(paths) = workspace.take_ready(now) {
+                                let _ = tx.send(FileWatcherEvent::WorkspaceChanged { paths });
+                            }
                         }
                     }
                 }
@@ -255,7 +377,7 @@ impl FileWatcher {
                     *count -= 1;
                 } else {
                     state.skills_root_ref_counts.remove(root);
-                    should_unwatch = true;
+                    should_unwatch = !state.is_workspace_dir(root);
                 }
             }
 
@@ -284,6 +406,39 @@ impl FileWatcher {
         }
     }
 
+    fn unregister_workspace_root(&self, root: &Path) {
+        let mut state = self
+            .state
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let Some(count) = state.workspace_root_ref_counts.get_mut(root) else {
+            return;
+        };
+        if *count > 1 {
+            *count -= 1;
+            return;
+        }
+        state.workspace_root_ref_counts.remove(root);
+        let dirs = state.workspace_dirs.remove(root).unwrap_or_default();
+        let Some(inner) = &self.inner else {
+            return;
+        };
+        let mut guard = inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        for dir in dirs {
+            // Keep watches still needed by a skills root or another workspace.
+            if state.skills_root_ref_counts.contains_key(&dir) || state.is_workspace_dir(&dir) {
+                continue;
+            }
+            if guard.watched_paths.remove(&dir).is_some()
+                && let Err(err) = guard.watcher.unwatch(&dir)
+            {
+                warn!("failed to unwatch {}: {err}", dir.display());
+            }
+        }
+    }
+
     fn watch_path(&self, path: PathBuf, mode: RecursiveMode) {
         let Some(inner) = &self.inner else {
             return;
@@ -349,6 +504,34 @@ fn is_skills_path(path: &Path, roots: &HashSet<PathBuf>) -> bool {
     roots.iter().any(|root| path.starts_with(root))
 }
 
+/// Paths of a mutating event under a registered workspace root. Changes inside
+/// `.git` are dropped; they never affect indexed files.
+fn classify_workspace_event(event: &Event, state: &RwLock<WatchState>) -> Vec<PathBuf> {
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return Vec::new();
+    }
+    let state = state
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    event
+        .paths
+        .iter()
+        .filter(|path| {
+            state
+                .workspace_root_ref_counts
+                .keys()
+                .any(|root| path.starts_with(root))
+                && !path
+                    .components()
+                    .any(|component| component.as_os_str() == ".git")
+        })
+        .cloned()
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -413,6 +596,8 @@ mod tests {
         let root = path("/tmp/skills");
         let state = RwLock::new(WatchState {
             skills_root_ref_counts: HashMap::from([(root.clone(), 1)]),
+            workspace_root_ref_counts: HashMap::new(),
+            workspace_dirs: HashMap::new(),
         });
         let event = notify_event(
             EventKind::Create(CreateKind::Any),
@@ -432,6 +617,8 @@ mod tests {
         let root_b = path("/tmp/workspace/.codex/skills");
         let state = RwLock::new(WatchState {
             skills_root_ref_counts: HashMap::from([(root_a.clone(), 1), (root_b.clone(), 1)]),
+            workspace_root_ref_counts: HashMap::new(),
+            workspace_dirs: HashMap::new(),
         });
         let event = notify_event(
             EventKind::Modify(ModifyKind::Any),
@@ -454,6 +641,8 @@ mod tests {
         let root = path("/tmp/skills");
         let state = RwLock::new(WatchState {
             skills_root_ref_counts: HashMap::from([(root.clone(), 1)]),
+            workspace_root_ref_counts: HashMap::new(),
+            workspace_dirs: HashMap::new(),
         });
         let path = root.join("demo/SKILL.md");
 
@@ -498,6 +687,90 @@ mod tests {
         assert_eq!(state.skills_root_ref_counts.len(), 0);
     }
 
+    #[test]
+    fn classify_workspace_event_filters_to_workspace_roots() {
+        let root = path("/tmp/workspace");
+        let state = RwLock::new(WatchState {
+            skills_root_ref_counts: HashMap::new(),
+            workspace_root_ref_counts: HashMap::from([(root.clone(), 1)]),
+            workspace_dirs: HashMap::new(),
+        });
+        let event = notify_event(
+            EventKind::Modify(ModifyKind::Any),
+            vec![
+                root.join("src/lib.rs"),
+                root.join(".git/index"),
+                path("/tmp/workspace-other/src/lib.rs"),
+            ],
+        );
+
+        assert_eq!(
+            classify_workspace_event(&event, &state),
+            vec![root.join("src/lib.rs")]
+        );
+        assert_eq!(classify_event(&event, &state), Vec::<PathBuf>::new());
+    }
+
+    #[test]
+    fn workspace_registration_drop_unregisters_root() {
+        let watcher = Arc::new(FileWatcher::noop());
+        let root = path("/tmp/workspace");
+        let first = watcher.register_workspace(root.clone());
+        let second = watcher.register_workspace(root.clone());
+
+        drop(first);
+        assert_eq!(
+            watcher
+                .state
+                .read()
+                .expect("state lock")
+                .workspace_root_ref_counts
+                .get(&root),
+            Some(&1)
+        );
+
+        drop(second);
+        let state = watcher.state.read().expect("state lock");
+        assert_eq!(state.workspace_root_ref_counts.len(), 0);
+        assert_eq!(state.workspace_dirs.len(), 0);
+    }
+
+    #[test]
+    fn workspace_dirs_are_watched_non_recursively_until_unregistered() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let root = temp_dir.path().to_path_buf();
+        let src = root.join("src");
+        std::fs::create_dir_all(root.join("node_modules/pkg")).expect("create node_modules");
+        std::fs::create_dir(&src).expect("create src");
+
+        let watcher = Arc::new(FileWatcher::new(root.clone()).expect("watcher"));
+        watcher.watch_workspace_dirs(&root, vec![root.clone()]);
+        let registration = watcher.register_workspace(root.clone());
+        watcher.watch_workspace_dirs(&root, vec![root.clone(), src.clone()]);
+
+        let watched_paths = |watcher: &FileWatcher| {
+            let inner = watcher.inner.as_ref().expect("watcher inner");
+            let inner = inner.lock().expect("inner lock");
+            let mut watched: Vec<(PathBuf, RecursiveMode)> = inner
+                .watched_paths
+                .iter()
+                .map(|(path, mode)| (path.clone(), *mode))
+                .collect();
+            watched.sort_by(|(a, _), (b, _)| a.cmp(b));
+            watched
+        };
+        assert_eq!(
+            watched_paths(&watcher),
+            vec![
+                (root.clone(), RecursiveMode::NonRecursive),
+                (src, RecursiveMode::NonRecursive),
+            ]
+        );
+
+        drop(registration);
+        assert_eq!(watched_paths(&watcher), Vec::new());
+    }
+
     #[test]
     fn unregister_holds_state_lock_until_unwatch_finishes() {
         let temp_dir = tempfile::tempdir().expect("temp dir");
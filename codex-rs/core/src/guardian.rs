@@ -25,6 +25,9 @@ use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value;
 use tokio_util::sync::CancellationToken;
+use tracing::Span;
+use tracing::field;
+use tracing::instrument;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
@@ -161,6 +164,12 @@ impl GuardianTranscriptEntryKind {
 ///
 /// This function always fails closed: any timeout, subagent failure, or parse
 /// failure is treated as a high-risk denial.
+#[instrument(
+    name = "guardian_review",
+    level = "info",
+    skip_all,
+    fields(risk_score = field::Empty, risk_level = field::Empty, decision = field::Empty)
+)]
 async fn run_guardian_review(
     session: Arc<Session>,
     turn: Arc<TurnContext>,
@@ -213,6 +222,10 @@ async fn run_guardian_review(
 
     let approved = assessment.risk_score < GUARDIAN_APPROVAL_RISK_THRESHOLD;
     let verdict = if approved { "approved" } else { "denied" };
+    let span = Span::current();
+    span.record("risk_score", assessment.risk_score);
+    span.record("risk_level", assessment.risk_level.as_str());
+    span.record("decision", verdict);
     // Emit a concise warning so the parent turn has an auditable summary of the
     // guardian decision without needing the full subagent transcript.
     let warning = format!(
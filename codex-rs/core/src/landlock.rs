@@ -24,6 +24,7 @@ pub async fn spawn_command_under_linux_sandbox<P>(
     command_cwd: PathBuf,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    deny_read_paths: &[PathBuf],
     use_bwrap_sandbox: bool,
     stdio_policy: StdioPolicy,
     network: Option<&NetworkProxy>,
@@ -36,6 +37,7 @@ where
         command,
         sandbox_policy,
         sandbox_policy_cwd,
+        deny_read_paths,
         use_bwrap_sandbox,
         allow_network_for_proxy(false),
     );
@@ -64,10 +66,13 @@ pub(crate) fn allow_network_for_proxy(enforce_managed_network: bool) -> bool {
 ///
 /// The helper performs the actual sandboxing (bubblewrap + seccomp) after
 /// parsing these arguments. See `docs/linux_sandbox.md` for the Linux semantics.
+/// `deny_read_paths` are the already expanded `deny_read` matches; see
+/// [`crate::sandboxing::deny_read`].
 pub(crate) fn create_linux_sandbox_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    deny_read_paths: &[PathBuf],
     use_bwrap_sandbox: bool,
     allow_network_for_proxy: bool,
 ) -> Vec<String> {
@@ -87,6 +92,10 @@ pub(crate) fn create_linux_sandbox_command_args(
         "--sandbox-policy".to_string(),
         sandbox_policy_json,
     ];
+    for path in deny_read_paths {
+        linux_cmd.push("--deny-read-path".to_string());
+        linux_cmd.push(path.to_string_lossy().into_owned());
+    }
     if use_bwrap_sandbox {
         linux_cmd.push("--use-bwrap-sandbox".to_string());
     }
@@ -116,13 +125,14 @@ mod tests {
         let policy = SandboxPolicy::new_read_only_policy();
 
         let with_bwrap =
-            create_linux_sandbox_command_args(command.clone(), &policy, cwd, true, false);
+            create_linux_sandbox_command_args(command.clone(), &policy, cwd, &[], true, false);
         assert_eq!(
             with_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             true
         );
 
-        let without_bwrap = create_linux_sandbox_command_args(command, &policy, cwd, false, false);
+        let without_bwrap =
+            create_linux_sandbox_command_args(command, &policy, cwd, &[], false, false);
         assert_eq!(
             without_bwrap.contains(&"--use-bwrap-sandbox".to_string()),
             false
@@ -135,13 +145,38 @@ mod tests {
         let cwd = Path::new("/tmp");
         let policy = SandboxPolicy::new_read_only_policy();
 
-        let args = create_linux_sandbox_command_args(command, &policy, cwd, true, true);
+        let args = create_linux_sandbox_command_args(command, &policy, cwd, &[], true, true);
         assert_eq!(
             args.contains(&"--allow-network-for-proxy".to_string()),
             true
         );
     }
 
+    #[test]
+    fn deny_read_paths_are_forwarded_before_the_command() {
+        let command = vec!["/bin/cat".to_string(), ".env".to_string()];
+        let cwd = Path::new("/tmp");
+        let policy = SandboxPolicy::new_read_only_policy();
+        let deny_read_paths = vec![PathBuf::from("/tmp/.env"), PathBuf::from("/home/me/.ssh")];
+
+        let args =
+            create_linux_sandbox_command_args(command, &policy, cwd, &deny_read_paths, true, false);
+        let separator = args
+            .iter()
+            .position(|arg| arg == "--")
+            .expect("command separator");
+        assert_eq!(
+            args[4..8],
+            [
+                "--deny-read-path",
+                "/tmp/.env",
+                "--deny-read-path",
+                "/home/me/.ssh"
+            ]
+        );
+        assert_eq!(args[separator + 1..], ["/bin/cat", ".env"]);
+    }
+
     #[test]
     fn proxy_network_requires_managed_requirements() {
         assert_eq!(allow_network_for_proxy(false), false);
@@ -12,11 +12,13 @@ mod apps;
 pub mod auth;
 mod client;
 mod client_common;
+mod code_index;
 pub mod codex;
 mod realtime_context;
 mod realtime_conversation;
 pub use codex::SteerInputError;
 mod codex_thread;
+mod compact_prune;
 mod compact_remote;
 pub use codex_thread::CodexThread;
 pub use codex_thread::ThreadConfigSnapshot;
@@ -37,6 +39,7 @@ pub mod exec;
 pub mod exec_env;
 mod exec_policy;
 pub mod external_agent_config;
+pub mod external_agent_sessions;
 pub mod features;
 mod file_watcher;
 mod flags;
@@ -58,6 +61,7 @@ mod memories;
 mod mentions;
 mod message_history;
 mod model_provider_info;
+mod notification_sinks;
 pub mod path_utils;
 pub mod personality_migration;
 pub mod plugins;
@@ -83,6 +87,7 @@ pub use model_provider_info::WireApi;
 pub use model_provider_info::built_in_model_providers;
 pub use model_provider_info::create_oss_provider_with_base_url;
 mod event_mapping;
+pub mod replay;
 pub mod review_format;
 pub mod review_prompts;
 mod seatbelt_permissions;
@@ -122,6 +127,8 @@ pub use rollout::RolloutRecorderParams;
 pub use rollout::SESSIONS_SUBDIR;
 pub use rollout::SessionMeta;
 pub use rollout::append_thread_name;
+pub use rollout::export::ThreadExport;
+pub use rollout::export::ThreadExportFormat;
 pub use rollout::find_archived_thread_path_by_id_str;
 #[deprecated(note = "use find_thread_path_by_id_str")]
 pub use rollout::find_conversation_path_by_id_str;
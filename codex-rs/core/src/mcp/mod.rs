@@ -1,4 +1,5 @@
 pub mod auth;
+pub(crate) mod sampling;
 mod skill_dependencies;
 pub(crate) use skill_dependencies::maybe_prompt_and_install_mcp_dependencies;
 
@@ -9,12 +10,17 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_channel::unbounded;
+use codex_protocol::mcp::Prompt;
 use codex_protocol::mcp::Resource;
 use codex_protocol::mcp::ResourceTemplate;
 use codex_protocol::mcp::Tool;
 use codex_protocol::protocol::McpListToolsResponseEvent;
 use codex_protocol::protocol::SandboxPolicy;
+use rmcp::model::GetPromptResult;
+use rmcp::model::PromptMessageContent;
+use rmcp::model::ResourceContents;
 use serde_json::Value;
+use tokio_util::sync::CancellationToken;
 
 use crate::AuthManager;
 use crate::CodexAuth;
@@ -201,6 +207,7 @@ fn codex_apps_mcp_server_config(config: &Config, auth: Option<&CodexAuth>) -> Mc
         disabled_tools: None,
         scopes: None,
         oauth_resource: None,
+        sampling_token_budget: None,
     }
 }
 
@@ -289,6 +296,7 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
             tools: HashMap::new(),
             resources: HashMap::new(),
             resource_templates: HashMap::new(),
+            prompts: HashMap::new(),
             auth_statuses: HashMap::new(),
         };
     }
@@ -296,10 +304,81 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
     let auth_status_entries =
         compute_auth_statuses(mcp_servers.iter(), config.mcp_oauth_credentials_store_mode).await;
 
+    let (mcp_connection_manager, cancel_token) = start_standalone_mcp_connection_manager(
+        config,
+        &mcp_servers,
+        auth_status_entries.clone(),
+        auth.as_ref(),
+        tool_plugin_provenance,
+    )
+    .await;
+
+    let snapshot =
+        collect_mcp_snapshot_from_manager(&mcp_connection_manager, auth_status_entries).await;
+
+    cancel_token.cancel();
+
+    snapshot
+}
+
+/// Starts only `server` and renders one of its prompts, for callers that have
+/// no running session. Returns the prompt text, or an error message.
+pub async fn get_mcp_prompt(
+    config: &Config,
+    server: &str,
+    name: &str,
+    arguments: HashMap<String, String>,
+) -> Result<String, String> {
+    let auth_manager = AuthManager::shared(
+        config.codex_home.clone(),
+        false,
+        config.cli_auth_credentials_store_mode,
+    );
+    let auth = auth_manager.auth().await;
+    let mcp_manager = McpManager::new(Arc::new(PluginsManager::new(config.codex_home.clone())));
+    let mcp_servers: HashMap<String, McpServerConfig> = mcp_manager
+        .effective_servers(config, auth.as_ref())
+        .into_iter()
+        .filter(|(server_name, cfg)| server_name == server && cfg.enabled)
+        .collect();
+    if mcp_servers.is_empty() {
+        return Err(format!("unknown MCP server '{server}'"));
+    }
+    let tool_plugin_provenance = mcp_manager.tool_plugin_provenance(config);
+    let auth_status_entries =
+        compute_auth_statuses(mcp_servers.iter(), config.mcp_oauth_credentials_store_mode).await;
+
+    let (mcp_connection_manager, cancel_token) = start_standalone_mcp_connection_manager(
+        config,
+        &mcp_servers,
+        auth_status_entries,
+        auth.as_ref(),
+        tool_plugin_provenance,
+    )
+    .await;
+
+    let result = mcp_connection_manager
+        .get_prompt(server, name, arguments)
+        .await
+        .map(render_mcp_prompt)
+        .map_err(|err| format!("{err:#}"));
+
+    cancel_token.cancel();
+
+    result
+}
+
+async fn start_standalone_mcp_connection_manager(
+    config: &Config,
+    mcp_servers: &HashMap<String, McpServerConfig>,
+    auth_status_entries: HashMap<String, crate::mcp::auth::McpAuthStatusEntry>,
+    auth: Option<&CodexAuth>,
+    tool_plugin_provenance: ToolPluginProvenance,
+) -> (McpConnectionManager, CancellationToken) {
     let (tx_event, rx_event) = unbounded();
     drop(rx_event);
 
-    // Use ReadOnly sandbox policy for MCP snapshot collection (safest default)
+    // Use ReadOnly sandbox policy for standalone MCP access (safest default)
     let sandbox_state = SandboxState {
         sandbox_policy: SandboxPolicy::new_read_only_policy(),
         codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
@@ -307,25 +386,19 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
         use_linux_sandbox_bwrap: config.features.enabled(Feature::UseLinuxSandboxBwrap),
     };
 
-    let (mcp_connection_manager, cancel_token) = McpConnectionManager::new(
-        &mcp_servers,
+    McpConnectionManager::new(
+        mcp_servers,
         config.mcp_oauth_credentials_store_mode,
-        auth_status_entries.clone(),
+        auth_status_entries,
         &config.permissions.approval_policy,
         tx_event,
         sandbox_state,
         config.codex_home.clone(),
-        codex_apps_tools_cache_key(auth.as_ref()),
+        codex_apps_tools_cache_key(auth),
         tool_plugin_provenance,
+        None,
     )
-    .await;
-
-    let snapshot =
-        collect_mcp_snapshot_from_manager(&mcp_connection_manager, auth_status_entries).await;
-
-    cancel_token.cancel();
-
-    snapshot
+    .await
 }
 
 pub fn split_qualified_tool_name(qualified_name: &str) -> Option<(String, String)> {
@@ -361,10 +434,11 @@ pub(crate) async fn collect_mcp_snapshot_from_manager(
     mcp_connection_manager: &McpConnectionManager,
     auth_status_entries: HashMap<String, crate::mcp::auth::McpAuthStatusEntry>,
 ) -> McpListToolsResponseEvent {
-    let (tools, resources, resource_templates) = tokio::join!(
+    let (tools, resources, resource_templates, prompts) = tokio::join!(
         mcp_connection_manager.list_all_tools(),
         mcp_connection_manager.list_all_resources(),
         mcp_connection_manager.list_all_resource_templates(),
+        mcp_connection_manager.list_all_prompts(),
     );
 
     let auth_statuses = auth_status_entries
@@ -460,14 +534,62 @@ pub(crate) async fn collect_mcp_snapshot_from_manager(
         })
         .collect();
 
+    let prompts = prompts
+        .into_iter()
+        .map(|(name, prompts)| (name, convert_mcp_prompts(prompts)))
+        .collect();
+
     McpListToolsResponseEvent {
         tools,
         resources,
         resource_templates,
+        prompts,
         auth_statuses,
     }
 }
 
+pub(crate) fn convert_mcp_prompts(prompts: Vec<rmcp::model::Prompt>) -> Vec<Prompt> {
+    prompts
+        .into_iter()
+        .filter_map(|prompt| {
+            let name = prompt.name.clone();
+            match serde_json::to_value(prompt) {
+                Ok(value) => match Prompt::from_mcp_value(value) {
+                    Ok(prompt) => Some(prompt),
+                    Err(err) => {
+                        tracing::warn!("Failed to convert MCP prompt '{name}': {err}");
+                        None
+                    }
+                },
+                Err(err) => {
+                    tracing::warn!("Failed to serialize MCP prompt '{name}': {err}");
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Joins the text of a rendered MCP prompt's messages so it can be placed in
+/// the composer. Embedded text resources are inlined; images and resource
+/// links are skipped.
+pub(crate) fn render_mcp_prompt(result: GetPromptResult) -> String {
+    result
+        .messages
+        .into_iter()
+        .filter_map(|message| match message.content {
+            PromptMessageContent::Text { text } => Some(text),
+            PromptMessageContent::Resource { resource } => match resource.raw.resource {
+                ResourceContents::TextResourceContents { text, .. } => Some(text),
+                ResourceContents::BlobResourceContents { .. } => None,
+            },
+            PromptMessageContent::Image { .. } | PromptMessageContent::ResourceLink { .. } => None,
+        })
+        .filter(|text| !text.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -783,6 +905,7 @@ mod tests {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
         );
         config
@@ -809,4 +932,40 @@ mod tests {
             other => panic!("expected streamable http transport, got {other:?}"),
         }
     }
+
+    #[test]
+    fn render_mcp_prompt_joins_text_messages() {
+        use rmcp::model::AnnotateAble;
+        use rmcp::model::PromptMessage;
+        use rmcp::model::PromptMessageRole;
+        use rmcp::model::RawEmbeddedResource;
+
+        let result = GetPromptResult {
+            description: None,
+            messages: vec![
+                PromptMessage::new_text(PromptMessageRole::User, "Review src/lib.rs."),
+                PromptMessage {
+                    role: PromptMessageRole::User,
+                    content: PromptMessageContent::Resource {
+                        resource: RawEmbeddedResource {
+                            meta: None,
+                            resource: ResourceContents::TextResourceContents {
+                                uri: "file:///guide.md".to_string(),
+                                mime_type: None,
+                                text: "Style guide".to_string(),
+                                meta: None,
+                            },
+                        }
+                        .no_annotation(),
+                    },
+                },
+                PromptMessage::new_text(PromptMessageRole::Assistant, "  "),
+            ],
+        };
+
+        assert_eq!(
+            render_mcp_prompt(result),
+            "Review src/lib.rs.\n\nStyle guide"
+        );
+    }
 }
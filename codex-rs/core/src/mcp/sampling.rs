@@ -0,0 +1,293 @@
+//! Answers `sampling/createMessage` requests from MCP servers with the
+//! session's model.
+//!
+//! Requests are approved by the user before they reach the model (see
+//! `McpConnectionManager`), and each server has a per-session token budget
+//! configured with `sampling_token_budget`.
+
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::sync::Weak;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_api::ResponseEvent;
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use futures::StreamExt;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::CreateMessageResult;
+use rmcp::model::Role;
+use rmcp::model::SamplingMessage;
+use rmcp::model::SamplingMessageContent;
+
+use crate::Prompt;
+use crate::codex::Session;
+
+/// Tokens a server may consume through sampling per session when its config
+/// does not set `sampling_token_budget`.
+pub(crate) const DEFAULT_SAMPLING_TOKEN_BUDGET: u64 = 50_000;
+
+/// Runs sampling requests for one session and tracks per-server token usage.
+pub(crate) struct McpSampler {
+    session: Weak<Session>,
+    usage: StdMutex<HashMap<String, u64>>,
+}
+
+impl McpSampler {
+    pub(crate) fn new(session: Weak<Session>) -> Self {
+        Self {
+            session,
+            usage: StdMutex::new(HashMap::new()),
+        }
+    }
+
+    /// Tokens `server_name` may still consume out of `budget`.
+    pub(crate) fn remaining_budget(&self, server_name: &str, budget: u64) -> u64 {
+        let used = self
+            .usage
+            .lock()
+            .ok()
+            .and_then(|usage| usage.get(server_name).copied())
+            .unwrap_or_default();
+        budget.saturating_sub(used)
+    }
+
+    fn record_usage(&self, server_name: &str, tokens: u64) {
+        if let Ok(mut usage) = self.usage.lock() {
+            *usage.entry(server_name.to_string()).or_default() += tokens;
+        }
+    }
+
+    /// Sends the request to the session's current model and returns its reply.
+    /// The reply is capped at the server's `max_tokens` or at what is left of
+    /// its `budget`, whichever is smaller. Model preferences from the server
+    /// are ignored.
+    pub(crate) async fn create_message(
+        &self,
+        server_name: &str,
+        budget: u64,
+        params: CreateMessageRequestParams,
+    ) -> Result<CreateMessageResult> {
+        let session = self
+            .session
+            .upgrade()
+            .ok_or_else(|| anyhow!("session is no longer running"))?;
+        let max_output_tokens =
+            sampling_max_output_tokens(&params, self.remaining_budget(server_name, budget));
+        let prompt = Prompt {
+            max_output_tokens: Some(max_output_tokens),
+            ..sampling_prompt(&params)?
+        };
+        let turn_context = session.new_default_turn().await;
+        let turn_metadata_header = turn_context.turn_metadata_state.current_header_value();
+
+        let mut client_session = session.services.model_client.new_session();
+        let mut stream = client_session
+            .stream(
+                &prompt,
+                &turn_context.model_info,
+                &turn_context.session_telemetry,
+                turn_context.reasoning_effort,
+                turn_context.reasoning_summary,
+                turn_context.config.service_tier,
+                turn_metadata_header.as_deref(),
+            )
+            .await?;
+
+        let mut text = String::new();
+        while let Some(event) = stream.next().await.transpose()? {
+            match event {
+                ResponseEvent::OutputTextDelta(delta) => text.push_str(&delta),
+                ResponseEvent::OutputItemDone(item) => {
+                    if text.is_empty()
+                        && let ResponseItem::Message { content, .. } = item
+                        && let Some(item_text) = crate::compact::content_items_to_text(&content)
+                    {
+                        text.push_str(&item_text);
+                    }
+                }
+                ResponseEvent::Completed { token_usage, .. } => {
+                    if let Some(token_usage) = token_usage {
+                        self.record_usage(
+                            server_name,
+                            u64::try_from(token_usage.total_tokens).unwrap_or_default(),
+                        );
+                    }
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(CreateMessageResult {
+            model: turn_context.model_info.slug.clone(),
+            stop_reason: Some(CreateMessageResult::STOP_REASON_END_TURN.to_string()),
+            message: SamplingMessage::assistant_text(text),
+        })
+    }
+}
+
+/// The output-token cap for a sampling request: the server's `max_tokens`,
+/// limited to what is left of its budget.
+fn sampling_max_output_tokens(params: &CreateMessageRequestParams, remaining_budget: u64) -> u64 {
+    u64::from(params.max_tokens).min(remaining_budget)
+}
+
+/// Converts a sampling request into a tool-less prompt. Only text and image
+/// content is supported.
+fn sampling_prompt(params: &CreateMessageRequestParams) -> Result<Prompt> {
+    let mut input = Vec::with_capacity(params.messages.len());
+    for message in &params.messages {
+        let (role, content) = match message.role {
+            Role::User => ("user", sampling_user_content(message)?),
+            Role::Assistant => ("assistant", sampling_assistant_content(message)?),
+        };
+        input.push(ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content,
+            end_turn: None,
+            phase: None,
+        });
+    }
+    Ok(Prompt {
+        input,
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions: BaseInstructions {
+            text: params.system_prompt.clone().unwrap_or_default(),
+        },
+        personality: None,
+        output_schema: None,
+        max_output_tokens: None,
+    })
+}
+
+fn sampling_user_content(message: &SamplingMessage) -> Result<Vec<ContentItem>> {
+    message
+        .content
+        .iter()
+        .map(|content| match content {
+            SamplingMessageContent::Text(text) => Ok(ContentItem::InputText {
+                text: text.text.clone(),
+            }),
+            SamplingMessageContent::Image(image) => Ok(ContentItem::InputImage {
+                image_url: format!("data:{};base64,{}", image.mime_type, image.data),
+            }),
+            _ => Err(anyhow!("only text and image sampling content is supported")),
+        })
+        .collect()
+}
+
+fn sampling_assistant_content(message: &SamplingMessage) -> Result<Vec<ContentItem>> {
+    message
+        .content
+        .iter()
+        .map(|content| match content.as_text() {
+            Some(text) => Ok(ContentItem::OutputText {
+                text: text.text.clone(),
+            }),
+            None => Err(anyhow!(
+                "only text is supported in assistant sampling messages"
+            )),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rmcp::model::RawImageContent;
+
+    fn params(messages: Vec<SamplingMessage>) -> CreateMessageRequestParams {
+        CreateMessageRequestParams {
+            meta: None,
+            task: None,
+            messages,
+            model_preferences: None,
+            system_prompt: Some("Summarize files.".to_string()),
+            include_context: None,
+            temperature: None,
+            max_tokens: 128,
+            stop_sequences: None,
+            metadata: None,
+            tools: None,
+            tool_choice: None,
+        }
+    }
+
+    #[test]
+    fn sampling_prompt_maps_roles_and_content() {
+        let image = SamplingMessage::new(
+            Role::User,
+            SamplingMessageContent::Image(RawImageContent {
+                data: "AAAA".to_string(),
+                mime_type: "image/png".to_string(),
+                meta: None,
+            }),
+        );
+        let prompt = sampling_prompt(&params(vec![
+            SamplingMessage::user_text("What is in lib.rs?"),
+            SamplingMessage::assistant_text("A parser."),
+            image,
+        ]))
+        .expect("prompt");
+
+        assert_eq!(prompt.base_instructions.text, "Summarize files.");
+        assert!(prompt.tools.is_empty());
+        assert_eq!(
+            prompt.input,
+            vec![
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputText {
+                        text: "What is in lib.rs?".to_string(),
+                    }],
+                    end_turn: None,
+                    phase: None,
+                },
+                ResponseItem::Message {
+                    id: None,
+                    role: "assistant".to_string(),
+                    content: vec![ContentItem::OutputText {
+                        text: "A parser.".to_string(),
+                    }],
+                    end_turn: None,
+                    phase: None,
+                },
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputImage {
+                        image_url: "data:image/png;base64,AAAA".to_string(),
+                    }],
+                    end_turn: None,
+                    phase: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn remaining_budget_subtracts_recorded_usage() {
+        let sampler = McpSampler::new(Weak::new());
+        sampler.record_usage("indexer", 300);
+        sampler.record_usage("indexer", 200);
+
+        assert_eq!(sampler.remaining_budget("indexer", 1_000), 500);
+        assert_eq!(sampler.remaining_budget("indexer", 400), 0);
+        assert_eq!(sampler.remaining_budget("docs", 1_000), 1_000);
+    }
+
+    #[test]
+    fn output_tokens_are_capped_by_the_remaining_budget() {
+        let params = params(vec![SamplingMessage::user_text("hi")]);
+
+        assert_eq!(sampling_max_output_tokens(&params, 1_000), 128);
+        assert_eq!(sampling_max_output_tokens(&params, 50), 50);
+    }
+}
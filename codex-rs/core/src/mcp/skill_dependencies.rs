@@ -394,6 +394,7 @@ fn mcp_dependency_to_server_config(
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         });
     }
 
@@ -419,6 +420,7 @@ fn mcp_dependency_to_server_config(
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         });
     }
 
@@ -476,6 +478,7 @@ mod tests {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
         )]);
 
@@ -525,6 +528,7 @@ mod tests {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
         )]);
 
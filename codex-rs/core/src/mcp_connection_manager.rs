@@ -22,6 +22,9 @@ use std::time::Instant;
 use crate::mcp::CODEX_APPS_MCP_SERVER_NAME;
 use crate::mcp::ToolPluginProvenance;
 use crate::mcp::auth::McpAuthStatusEntry;
+use crate::mcp::convert_mcp_prompts;
+use crate::mcp::sampling::DEFAULT_SAMPLING_TOKEN_BUDGET;
+use crate::mcp::sampling::McpSampler;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
@@ -29,6 +32,7 @@ use async_channel::Sender;
 use codex_async_utils::CancelErr;
 use codex_async_utils::OrCancelExt;
 use codex_config::Constrained;
+use codex_otel::current_span_w3c_trace_context;
 use codex_protocol::approvals::ElicitationRequest;
 use codex_protocol::approvals::ElicitationRequestEvent;
 use codex_protocol::mcp::CallToolResult;
@@ -36,6 +40,7 @@ use codex_protocol::mcp::RequestId as ProtocolRequestId;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::McpPromptListChangedEvent;
 use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::McpStartupFailure;
 use codex_protocol::protocol::McpStartupStatus;
@@ -45,25 +50,34 @@ use codex_rmcp_client::ElicitationResponse;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_rmcp_client::RmcpClient;
 use codex_rmcp_client::SendElicitation;
+use codex_rmcp_client::SendSampling;
 use futures::future::BoxFuture;
 use futures::future::FutureExt;
 use futures::future::Shared;
 use rmcp::model::ClientCapabilities;
 use rmcp::model::CreateElicitationRequestParams;
+use rmcp::model::CreateMessageRequestParams;
 use rmcp::model::ElicitationAction;
 use rmcp::model::ElicitationCapability;
 use rmcp::model::FormElicitationCapability;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::Implementation;
 use rmcp::model::InitializeRequestParams;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::PaginatedRequestParams;
+use rmcp::model::Prompt;
 use rmcp::model::ProtocolVersion;
 use rmcp::model::ReadResourceRequestParams;
 use rmcp::model::ReadResourceResult;
 use rmcp::model::RequestId;
 use rmcp::model::Resource;
 use rmcp::model::ResourceTemplate;
+use rmcp::model::Root;
+use rmcp::model::RootsCapabilities;
+use rmcp::model::SamplingCapability;
+use rmcp::model::SamplingMessageContent;
 use rmcp::model::Tool;
 
 use serde::Deserialize;
@@ -102,6 +116,11 @@ const MCP_TOOLS_LIST_DURATION_METRIC: &str = "codex.mcp.tools.list.duration_ms";
 const MCP_TOOLS_FETCH_UNCACHED_DURATION_METRIC: &str = "codex.mcp.tools.fetch_uncached.duration_ms";
 const MCP_TOOLS_CACHE_WRITE_DURATION_METRIC: &str = "codex.mcp.tools.cache_write.duration_ms";
 
+const MCP_SAMPLING_APPROVAL_KIND_KEY: &str = "codex_approval_kind";
+const MCP_SAMPLING_APPROVAL_KIND: &str = "mcp_sampling";
+/// Sampling requests shown for approval are truncated to this many characters.
+const MCP_SAMPLING_PREVIEW_CHARS: usize = 500;
+
 /// The Responses API requires tool names to match `^[a-zA-Z0-9_-]+$`.
 /// MCP server/tool names are user-controlled, so sanitize the fully-qualified
 /// name we expose to the model by replacing any disallowed character with `_`.
@@ -328,37 +347,159 @@ impl ElicitationRequestManager {
                         elicitation_id,
                     },
                 };
-                let (tx, rx) = oneshot::channel();
+                request_elicitation(&elicitation_requests, &tx_event, server_name, id, request)
+                    .await
+            }
+            .boxed()
+        })
+    }
+
+    /// Answers `sampling/createMessage` with `sampler` after the user approves
+    /// the request and while the server has budget left.
+    fn make_sampling_sender(
+        &self,
+        server_name: String,
+        tx_event: Sender<Event>,
+        sampler: Arc<McpSampler>,
+        budget: u64,
+    ) -> SendSampling {
+        let elicitation_requests = self.requests.clone();
+        let approval_policy = self.approval_policy.clone();
+        Box::new(move |id, params| {
+            let elicitation_requests = elicitation_requests.clone();
+            let tx_event = tx_event.clone();
+            let server_name = server_name.clone();
+            let approval_policy = approval_policy.clone();
+            let sampler = Arc::clone(&sampler);
+            async move {
+                if approval_policy
+                    .lock()
+                    .is_ok_and(|policy| elicitation_is_rejected_by_policy(*policy))
                 {
-                    let mut lock = elicitation_requests.lock().await;
-                    lock.insert((server_name.clone(), id.clone()), tx);
+                    return Err(anyhow!(
+                        "sampling requests are rejected by the approval policy"
+                    ));
+                }
+                let remaining = sampler.remaining_budget(&server_name, budget);
+                if remaining == 0 {
+                    return Err(anyhow!(
+                        "sampling token budget of {budget} tokens is exhausted for this session"
+                    ));
                 }
-                let _ = tx_event
-                    .send(Event {
-                        id: "mcp_elicitation_request".to_string(),
-                        msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
-                            turn_id: None,
-                            server_name,
-                            id: match id.clone() {
-                                rmcp::model::NumberOrString::String(value) => {
-                                    ProtocolRequestId::String(value.to_string())
-                                }
-                                rmcp::model::NumberOrString::Number(value) => {
-                                    ProtocolRequestId::Integer(value)
-                                }
-                            },
-                            request,
-                        }),
-                    })
-                    .await;
-                rx.await
-                    .context("elicitation request channel closed unexpectedly")
+
+                let request = ElicitationRequest::Form {
+                    meta: Some(serde_json::json!({
+                        MCP_SAMPLING_APPROVAL_KIND_KEY: MCP_SAMPLING_APPROVAL_KIND,
+                    })),
+                    message: sampling_approval_message(&server_name, &params, remaining),
+                    requested_schema: serde_json::json!({
+                        "type": "object",
+                        "properties": {},
+                    }),
+                };
+                let response = request_elicitation(
+                    &elicitation_requests,
+                    &tx_event,
+                    server_name.clone(),
+                    id,
+                    request,
+                )
+                .await?;
+                if response.action != ElicitationAction::Accept {
+                    return Err(anyhow!("user declined the sampling request"));
+                }
+                sampler.create_message(&server_name, budget, params).await
             }
             .boxed()
         })
     }
 }
 
+/// Emits an elicitation request to the UI and waits for the user's response.
+async fn request_elicitation(
+    elicitation_requests: &Mutex<ResponderMap>,
+    tx_event: &Sender<Event>,
+    server_name: String,
+    id: RequestId,
+    request: ElicitationRequest,
+) -> Result<ElicitationResponse> {
+    let (tx, rx) = oneshot::channel();
+    {
+        let mut lock = elicitation_requests.lock().await;
+        lock.insert((server_name.clone(), id.clone()), tx);
+    }
+    let _ = tx_event
+        .send(Event {
+            id: "mcp_elicitation_request".to_string(),
+            msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
+                turn_id: None,
+                server_name,
+                id: match id {
+                    rmcp::model::NumberOrString::String(value) => {
+                        ProtocolRequestId::String(value.to_string())
+                    }
+                    rmcp::model::NumberOrString::Number(value) => ProtocolRequestId::Integer(value),
+                },
+                request,
+            }),
+        })
+        .await;
+    rx.await
+        .context("elicitation request channel closed unexpectedly")
+}
+
+fn sampling_approval_message(
+    server_name: &str,
+    params: &CreateMessageRequestParams,
+    remaining_budget: u64,
+) -> String {
+    let mut preview = params
+        .messages
+        .iter()
+        .flat_map(|message| message.content.iter())
+        .filter_map(SamplingMessageContent::as_text)
+        .map(|text| text.text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if preview.chars().count() > MCP_SAMPLING_PREVIEW_CHARS {
+        preview = preview.chars().take(MCP_SAMPLING_PREVIEW_CHARS).collect();
+        preview.push('…');
+    }
+    format!(
+        "MCP server `{server_name}` wants to query the model ({remaining_budget} tokens left in its budget):\n\n{preview}"
+    )
+}
+
+/// Roots reported to MCP servers for `roots/list`: the session cwd followed by
+/// any additional writable roots.
+fn roots_from_sandbox_state(sandbox_state: &SandboxState) -> Vec<Root> {
+    let mut paths = vec![sandbox_state.sandbox_cwd.clone()];
+    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &sandbox_state.sandbox_policy {
+        paths.extend(
+            writable_roots
+                .iter()
+                .map(|root| root.as_path().to_path_buf()),
+        );
+    }
+    let mut roots: Vec<Root> = Vec::new();
+    for path in paths {
+        let Ok(uri) = Url::from_directory_path(&path) else {
+            continue;
+        };
+        let uri = uri.to_string();
+        if roots.iter().any(|root| root.uri == uri) {
+            continue;
+        }
+        roots.push(Root {
+            uri,
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+        });
+    }
+    roots
+}
+
 #[derive(Clone)]
 struct ManagedClient {
     client: Arc<RmcpClient>,
@@ -366,6 +507,7 @@ struct ManagedClient {
     tool_filter: ToolFilter,
     tool_timeout: Option<Duration>,
     server_supports_sandbox_state_capability: bool,
+    server_supports_prompts: bool,
     codex_apps_tools_cache_context: Option<CodexAppsToolsCacheContext>,
 }
 
@@ -397,6 +539,9 @@ impl ManagedClient {
 
     /// Returns once the server has ack'd the sandbox state update.
     async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
+        self.client
+            .set_roots(roots_from_sandbox_state(sandbox_state))
+            .await?;
         if !self.server_supports_sandbox_state_capability {
             return Ok(());
         }
@@ -431,6 +576,8 @@ impl AsyncManagedClient {
         cancel_token: CancellationToken,
         tx_event: Sender<Event>,
         elicitation_requests: ElicitationRequestManager,
+        sampler: Option<Arc<McpSampler>>,
+        roots: Vec<Root>,
         codex_apps_tools_cache_context: Option<CodexAppsToolsCacheContext>,
         tool_plugin_provenance: Arc<ToolPluginProvenance>,
     ) -> Self {
@@ -462,6 +609,11 @@ impl AsyncManagedClient {
                         tool_filter: startup_tool_filter,
                         tx_event,
                         elicitation_requests,
+                        sampler,
+                        sampling_token_budget: config
+                            .sampling_token_budget
+                            .unwrap_or(DEFAULT_SAMPLING_TOKEN_BUDGET),
+                        roots,
                         codex_apps_tools_cache_context,
                     },
                 )
@@ -591,6 +743,7 @@ pub(crate) struct McpConnectionManager {
     clients: HashMap<String, AsyncManagedClient>,
     server_origins: HashMap<String, String>,
     elicitation_requests: ElicitationRequestManager,
+    sampler: Option<Arc<McpSampler>>,
 }
 
 impl McpConnectionManager {
@@ -599,6 +752,7 @@ impl McpConnectionManager {
             clients: HashMap::new(),
             server_origins: HashMap::new(),
             elicitation_requests: ElicitationRequestManager::new(approval_policy.value()),
+            sampler: None,
         }
     }
 
@@ -617,6 +771,11 @@ impl McpConnectionManager {
         self.server_origins.get(server_name).map(String::as_str)
     }
 
+    /// Sampler answering `sampling/createMessage`, when sampling is enabled.
+    pub(crate) fn sampler(&self) -> Option<Arc<McpSampler>> {
+        self.sampler.clone()
+    }
+
     pub fn set_approval_policy(&self, approval_policy: &Constrained<AskForApproval>) {
         if let Ok(mut policy) = self.elicitation_requests.approval_policy.lock() {
             *policy = approval_policy.value();
@@ -634,6 +793,7 @@ impl McpConnectionManager {
         codex_home: PathBuf,
         codex_apps_tools_cache_key: CodexAppsToolsCacheKey,
         tool_plugin_provenance: ToolPluginProvenance,
+        sampler: Option<Arc<McpSampler>>,
     ) -> (Self, CancellationToken) {
         let cancel_token = CancellationToken::new();
         let mut clients = HashMap::new();
@@ -641,6 +801,7 @@ impl McpConnectionManager {
         let mut join_set = JoinSet::new();
         let elicitation_requests = ElicitationRequestManager::new(approval_policy.value());
         let tool_plugin_provenance = Arc::new(tool_plugin_provenance);
+        let roots = roots_from_sandbox_state(&initial_sandbox_state);
         let mcp_servers = mcp_servers.clone();
         for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
             if let Some(origin) = transport_origin(&cfg.transport) {
@@ -670,6 +831,8 @@ impl McpConnectionManager {
                 cancel_token.clone(),
                 tx_event.clone(),
                 elicitation_requests.clone(),
+                sampler.clone(),
+                roots.clone(),
                 codex_apps_tools_cache_context,
                 Arc::clone(&tool_plugin_provenance),
             );
@@ -721,6 +884,7 @@ impl McpConnectionManager {
             clients,
             server_origins,
             elicitation_requests: elicitation_requests.clone(),
+            sampler,
         };
         tokio::spawn(async move {
             let outcomes = join_set.join_all().await;
@@ -996,6 +1160,75 @@ impl McpConnectionManager {
         aggregated
     }
 
+    /// Returns the prompts of every server that supports them, keyed by server
+    /// name.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        let mut join_set = JoinSet::new();
+
+        for (server_name, async_managed_client) in &self.clients {
+            let server_name = server_name.clone();
+            let Ok(managed_client) = async_managed_client.client().await else {
+                continue;
+            };
+            if !managed_client.server_supports_prompts {
+                continue;
+            }
+            let timeout = managed_client.tool_timeout;
+            let client = managed_client.client.clone();
+
+            join_set.spawn(async move {
+                let prompts = list_prompts_for_client(&client, timeout).await;
+                (server_name, prompts)
+            });
+        }
+
+        let mut aggregated: HashMap<String, Vec<Prompt>> = HashMap::new();
+
+        while let Some(join_res) = join_set.join_next().await {
+            match join_res {
+                Ok((server_name, Ok(prompts))) => {
+                    aggregated.insert(server_name, prompts);
+                }
+                Ok((server_name, Err(err))) => {
+                    warn!("Failed to list prompts for MCP server '{server_name}': {err:#}");
+                }
+                Err(err) => {
+                    warn!("Task panic when listing prompts for MCP server: {err:#}");
+                }
+            }
+        }
+
+        aggregated
+    }
+
+    /// Render a prompt from the specified server.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<GetPromptResult> {
+        let managed = self.client_by_name(server).await?;
+        let timeout = managed.tool_timeout;
+        let arguments = (!arguments.is_empty()).then(|| {
+            arguments
+                .into_iter()
+                .map(|(key, value)| (key, serde_json::Value::String(value)))
+                .collect()
+        });
+        let params = GetPromptRequestParams {
+            meta: None,
+            name: name.to_string(),
+            arguments,
+        };
+
+        managed
+            .client
+            .get_prompt(params, timeout)
+            .await
+            .with_context(|| format!("prompts/get failed for `{server}` ({name})"))
+    }
+
     /// Invoke the tool indicated by the (server, tool) pair.
     pub async fn call_tool(
         &self,
@@ -1012,7 +1245,12 @@ impl McpConnectionManager {
 
         let result: rmcp::model::CallToolResult = client
             .client
-            .call_tool(tool.to_string(), arguments, client.tool_timeout)
+            .call_tool_with_trace(
+                tool.to_string(),
+                arguments,
+                client.tool_timeout,
+                current_span_w3c_trace_context(),
+            )
             .await
             .with_context(|| format!("tool call failed for `{server}/{tool}`"))?;
 
@@ -1316,16 +1554,33 @@ async fn start_server_task(
         tool_filter,
         tx_event,
         elicitation_requests,
+        sampler,
+        sampling_token_budget,
+        roots,
         codex_apps_tools_cache_context,
     } = params;
     let elicitation = elicitation_capability_for_server(&server_name);
+    let send_sampling = sampler
+        .filter(|_| sampling_token_budget > 0)
+        .map(|sampler| {
+            elicitation_requests.make_sampling_sender(
+                server_name.clone(),
+                tx_event.clone(),
+                sampler,
+                sampling_token_budget,
+            )
+        });
     let params = InitializeRequestParams {
         meta: None,
         capabilities: ClientCapabilities {
             experimental: None,
             extensions: None,
-            roots: None,
-            sampling: None,
+            roots: Some(RootsCapabilities {
+                list_changed: Some(true),
+            }),
+            sampling: send_sampling
+                .as_ref()
+                .map(|_| SamplingCapability::default()),
             elicitation,
             tasks: None,
         },
@@ -1340,10 +1595,14 @@ async fn start_server_task(
         protocol_version: ProtocolVersion::V_2025_06_18,
     };
 
-    let send_elicitation = elicitation_requests.make_sender(server_name.clone(), tx_event);
+    let send_elicitation = elicitation_requests.make_sender(server_name.clone(), tx_event.clone());
 
+    client
+        .set_roots(roots)
+        .await
+        .map_err(StartupOutcomeError::from)?;
     let initialize_result = client
-        .initialize(params, startup_timeout, send_elicitation)
+        .initialize(params, startup_timeout, send_elicitation, send_sampling)
         .await
         .map_err(StartupOutcomeError::from)?;
 
@@ -1377,6 +1636,10 @@ async fn start_server_task(
         .as_ref()
         .and_then(|exp| exp.get(MCP_SANDBOX_STATE_CAPABILITY))
         .is_some();
+    let server_supports_prompts = initialize_result.capabilities.prompts.is_some();
+    if server_supports_prompts {
+        spawn_prompt_list_watcher(server_name, &client, tx_event, Some(tool_timeout));
+    }
 
     let managed = ManagedClient {
         client: Arc::clone(&client),
@@ -1384,18 +1647,87 @@ async fn start_server_task(
         tool_timeout: Some(tool_timeout),
         tool_filter,
         server_supports_sandbox_state_capability,
+        server_supports_prompts,
         codex_apps_tools_cache_context,
     };
 
     Ok(managed)
 }
 
+/// Re-lists the server's prompts whenever it sends
+/// `notifications/prompts/list_changed` and forwards them as
+/// [`EventMsg::McpPromptListChanged`]. Exits once the client is dropped.
+fn spawn_prompt_list_watcher(
+    server_name: String,
+    client: &Arc<RmcpClient>,
+    tx_event: Sender<Event>,
+    timeout: Option<Duration>,
+) {
+    let mut changed = client.subscribe_prompt_list_changed();
+    let client = Arc::downgrade(client);
+    tokio::spawn(async move {
+        while changed.changed().await.is_ok() {
+            let Some(client) = client.upgrade() else {
+                break;
+            };
+            let prompts = match list_prompts_for_client(&client, timeout).await {
+                Ok(prompts) => prompts,
+                Err(err) => {
+                    warn!("Failed to refresh prompts for MCP server '{server_name}': {err:#}");
+                    continue;
+                }
+            };
+            drop(client);
+            let event = Event {
+                id: INITIAL_SUBMIT_ID.to_owned(),
+                msg: EventMsg::McpPromptListChanged(McpPromptListChangedEvent {
+                    server: server_name.clone(),
+                    prompts: convert_mcp_prompts(prompts),
+                }),
+            };
+            if tx_event.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+}
+
+async fn list_prompts_for_client(
+    client: &RmcpClient,
+    timeout: Option<Duration>,
+) -> Result<Vec<Prompt>> {
+    let mut collected: Vec<Prompt> = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let params = cursor.as_ref().map(|next| PaginatedRequestParams {
+            meta: None,
+            cursor: Some(next.clone()),
+        });
+        let response = client.list_prompts(params, timeout).await?;
+        collected.extend(response.prompts);
+
+        match response.next_cursor {
+            Some(next) => {
+                if cursor.as_ref() == Some(&next) {
+                    return Err(anyhow!("prompts/list returned duplicate cursor"));
+                }
+                cursor = Some(next);
+            }
+            None => return Ok(collected),
+        }
+    }
+}
+
 struct StartServerTaskParams {
     startup_timeout: Option<Duration>, // TODO: cancel_token should handle this.
     tool_timeout: Duration,
     tool_filter: ToolFilter,
     tx_event: Sender<Event>,
     elicitation_requests: ElicitationRequestManager,
+    sampler: Option<Arc<McpSampler>>,
+    sampling_token_budget: u64,
+    roots: Vec<Root>,
     codex_apps_tools_cache_context: Option<CodexAppsToolsCacheContext>,
 }
 
@@ -1670,7 +2002,10 @@ mod mcp_init_error_display_tests {}
 mod tests {
     use super::*;
     use codex_protocol::protocol::McpAuthStatus;
+    use codex_protocol::protocol::ReadOnlyAccess;
     use codex_protocol::protocol::RejectConfig;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
     use rmcp::model::JsonObject;
     use std::collections::HashSet;
     use std::sync::Arc;
@@ -1756,6 +2091,47 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn roots_include_cwd_and_writable_roots_once() {
+        let temp = tempdir().expect("tempdir");
+        let cwd = temp.path().join("repo");
+        let extra = temp.path().join("docs");
+        let writable_roots = vec![
+            AbsolutePathBuf::from_absolute_path(&extra).expect("absolute path"),
+            AbsolutePathBuf::from_absolute_path(&cwd).expect("absolute path"),
+        ];
+        let sandbox_state = SandboxState {
+            sandbox_policy: SandboxPolicy::WorkspaceWrite {
+                writable_roots,
+                read_only_access: ReadOnlyAccess::FullAccess,
+                network_access: false,
+                exclude_tmpdir_env_var: false,
+                exclude_slash_tmp: false,
+            },
+            codex_linux_sandbox_exe: None,
+            sandbox_cwd: cwd.clone(),
+            use_linux_sandbox_bwrap: false,
+        };
+
+        assert_eq!(
+            roots_from_sandbox_state(&sandbox_state),
+            vec![
+                Root {
+                    uri: Url::from_directory_path(&cwd)
+                        .expect("file url")
+                        .to_string(),
+                    name: Some("repo".to_string()),
+                },
+                Root {
+                    uri: Url::from_directory_path(&extra)
+                        .expect("file url")
+                        .to_string(),
+                    name: Some("docs".to_string()),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_qualify_tools_short_non_duplicated_names() {
         let tools = vec![
@@ -2206,6 +2582,7 @@ mod tests {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
@@ -2254,6 +2631,7 @@ mod tests {
                 disabled_tools: None,
                 scopes: None,
                 oauth_resource: None,
+                sampling_token_budget: None,
             },
             auth_status: McpAuthStatus::Unsupported,
         };
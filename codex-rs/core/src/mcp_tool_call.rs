@@ -6,6 +6,7 @@ use codex_app_server_protocol::McpElicitationObjectType;
 use codex_app_server_protocol::McpElicitationSchema;
 use codex_app_server_protocol::McpServerElicitationRequest;
 use codex_app_server_protocol::McpServerElicitationRequestParams;
+use tracing::Instrument;
 use tracing::error;
 
 use crate::analytics_client::AppInvocation;
@@ -25,6 +26,7 @@ use crate::protocol::McpInvocation;
 use crate::protocol::McpToolCallBeginEvent;
 use crate::protocol::McpToolCallEndEvent;
 use crate::state_db;
+use crate::tools::sandboxing::approval_wait_span;
 use codex_protocol::mcp::CallToolResult;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::FunctionCallOutputPayload;
@@ -432,6 +434,7 @@ async fn maybe_request_mcp_tool_approval(
         return Some(McpToolApprovalDecision::Accept);
     }
 
+    let wait_span = approval_wait_span(turn_context, &invocation.tool, call_id);
     if routes_approval_to_guardian(turn_context) {
         let decision = review_approval_request(
             sess,
@@ -439,6 +442,7 @@ async fn maybe_request_mcp_tool_approval(
             build_guardian_mcp_tool_review_request(invocation, metadata),
             None,
         )
+        .instrument(wait_span)
         .await;
         return Some(mcp_tool_approval_decision_from_guardian(decision));
     }
@@ -472,6 +476,7 @@ async fn maybe_request_mcp_tool_approval(
         );
         let decision = parse_mcp_tool_approval_elicitation_response(
             sess.request_mcp_server_elicitation(turn_context.as_ref(), request_id, params)
+                .instrument(wait_span)
                 .await,
             &question_id,
         );
@@ -489,6 +494,7 @@ async fn maybe_request_mcp_tool_approval(
     };
     let response = sess
         .request_user_input(turn_context.as_ref(), call_id.to_string(), args)
+        .instrument(wait_span)
         .await;
     let decision = normalize_approval_decision_for_mode(
         parse_mcp_tool_approval_response(response, &question_id),
@@ -596,7 +602,10 @@ fn mcp_tool_approval_decision_from_guardian(decision: ReviewDecision) -> McpTool
         | ReviewDecision::ApprovedExecpolicyAmendment { .. }
         | ReviewDecision::ApprovedForSession
         | ReviewDecision::NetworkPolicyAmendment { .. } => McpToolApprovalDecision::Accept,
-        ReviewDecision::Denied | ReviewDecision::Abort => McpToolApprovalDecision::Decline,
+        // Hunk decisions only apply to file changes.
+        ReviewDecision::ApprovedHunks { .. } | ReviewDecision::Denied | ReviewDecision::Abort => {
+            McpToolApprovalDecision::Decline
+        }
     }
 }
 
@@ -340,6 +340,7 @@ mod job {
             },
             personality: None,
             output_schema: Some(output_schema()),
+            max_output_tokens: None,
         };
 
         let mut client_session = session.services.model_client.new_session();
@@ -0,0 +1,367 @@
+//! Delivers selected session events to the `[[notification_sinks]]` declared in
+//! config.toml. Unlike the TUI's terminal notifications, sinks work without an
+//! attached terminal, so long sessions on remote machines can ping a chat or a
+//! local daemon when Codex needs attention.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_hooks::command_from_argv;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::SubAgentSource;
+use codex_utils_string::take_bytes_at_char_boundary;
+use reqwest::header::CONTENT_TYPE;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+use crate::config::types::NotificationSinkEvent;
+use crate::config::types::NotificationSinkTarget;
+use crate::config::types::NotificationSinkToml;
+use crate::default_client::build_reqwest_client;
+use crate::exec_env::ExecSecrets;
+
+/// How long a single delivery may take before it is abandoned.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest assistant message, command or error text copied into a notification.
+const MAX_MESSAGE_BYTES: usize = 500;
+
+/// Payload sent to every sink. Webhook body templates can reference each field
+/// as `{{field}}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct SinkNotification {
+    event: NotificationSinkEvent,
+    thread_id: String,
+    turn_id: String,
+    cwd: String,
+    message: String,
+    timestamp: String,
+}
+
+pub(crate) struct NotificationSinks {
+    sinks: Arc<[NotificationSinkToml]>,
+    http_client: Option<reqwest::Client>,
+    thread_id: ThreadId,
+    cwd: String,
+    session_source: SessionSource,
+    /// Turns that reported a turn-ending error. Their `TurnComplete` still
+    /// follows, but must not be announced as a completed turn.
+    failed_turns: Mutex<HashSet<String>>,
+}
+
+impl NotificationSinks {
+    pub(crate) fn new(
+        sinks: Vec<NotificationSinkToml>,
+        thread_id: ThreadId,
+        cwd: &Path,
+        session_source: SessionSource,
+    ) -> Self {
+        let http_client = sinks
+            .iter()
+            .any(|sink| matches!(sink.target, NotificationSinkTarget::Webhook { .. }))
+            .then(build_reqwest_client);
+        Self {
+            sinks: sinks.into(),
+            http_client,
+            thread_id,
+            cwd: cwd.display().to_string(),
+            session_source,
+            failed_turns: Mutex::default(),
+        }
+    }
+
+    /// Sends `event` to the sinks subscribed to it. Delivery runs in the
+    /// background and failures are only logged, so a slow or unreachable sink
+    /// never holds up the session. Secrets injected into commands are
+    /// redacted from approval messages.
+    pub(crate) fn notify(&self, event: &Event, exec_secrets: &ExecSecrets) {
+        if self.sinks.is_empty() {
+            return;
+        }
+        let Some((kind, message)) = self.notification_for(event, |text| exec_secrets.redact(text))
+        else {
+            return;
+        };
+        let notification = SinkNotification {
+            event: kind,
+            thread_id: self.thread_id.to_string(),
+            turn_id: event.id.clone(),
+            cwd: self.cwd.clone(),
+            message,
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        };
+        for (index, sink) in self.sinks.iter().enumerate() {
+            if !sink.accepts(kind) {
+                continue;
+            }
+            let sinks = Arc::clone(&self.sinks);
+            let http_client = self.http_client.clone();
+            let notification = notification.clone();
+            tokio::spawn(async move {
+                let target = &sinks[index].target;
+                let delivery = deliver(target, http_client.as_ref(), &notification);
+                let result = match tokio::time::timeout(DELIVERY_TIMEOUT, delivery).await {
+                    Ok(result) => result,
+                    Err(_) => Err(anyhow::anyhow!(
+                        "timed out after {}s",
+                        DELIVERY_TIMEOUT.as_secs()
+                    )),
+                };
+                if let Err(err) = result {
+                    warn!(
+                        "failed to deliver {} notification to {}: {err:#}",
+                        notification.event.as_str(),
+                        describe_target(target)
+                    );
+                }
+            });
+        }
+    }
+
+    /// Like [`notification_for_event`], but drops the `TurnCompleted`
+    /// notification of a turn that already notified `TurnFailed` or
+    /// `RateLimitReached`.
+    fn notification_for(
+        &self,
+        event: &Event,
+        redact: impl Fn(&str) -> String,
+    ) -> Option<(NotificationSinkEvent, String)> {
+        let (kind, message) = notification_for_event(&event.msg, &self.session_source, redact)?;
+        let Ok(mut failed_turns) = self.failed_turns.lock() else {
+            return Some((kind, message));
+        };
+        match kind {
+            NotificationSinkEvent::TurnFailed | NotificationSinkEvent::RateLimitReached => {
+                failed_turns.insert(event.id.clone());
+            }
+            NotificationSinkEvent::TurnCompleted | NotificationSinkEvent::SubAgentFinished => {
+                if failed_turns.remove(&event.id) && kind == NotificationSinkEvent::TurnCompleted {
+                    return None;
+                }
+            }
+            NotificationSinkEvent::ApprovalRequested => {}
+        }
+        Some((kind, message))
+    }
+}
+
+/// Maps a session event to the notification it triggers, if any.
+///
+/// Sub-agents other than those spawned with `spawn_agent` (reviews, guardian
+/// assessments, compaction) never notify: their parent session reports the
+/// outcome instead. `redact` scrubs secrets from commands before they are
+/// truncated.
+fn notification_for_event(
+    msg: &EventMsg,
+    session_source: &SessionSource,
+    redact: impl Fn(&str) -> String,
+) -> Option<(NotificationSinkEvent, String)> {
+    let spawned_agent = match session_source {
+        SessionSource::SubAgent(SubAgentSource::ThreadSpawn { agent_nickname, .. }) => {
+            Some(agent_nickname.as_deref().unwrap_or("sub-agent"))
+        }
+        SessionSource::SubAgent(_) => return None,
+        _ => None,
+    };
+    match msg {
+        EventMsg::ExecApprovalRequest(event) => {
+            let command = redact(&event.command.join(" "));
+            Some((
+                NotificationSinkEvent::ApprovalRequested,
+                format!(
+                    "Approval requested: {}",
+                    take_bytes_at_char_boundary(&command, MAX_MESSAGE_BYTES)
+                ),
+            ))
+        }
+        EventMsg::ApplyPatchApprovalRequest(event) => Some((
+            NotificationSinkEvent::ApprovalRequested,
+            match event.changes.len() {
+                1 => "Codex wants to edit 1 file".to_string(),
+                count => format!("Codex wants to edit {count} files"),
+            },
+        )),
+        EventMsg::ElicitationRequest(event) => Some((
+            NotificationSinkEvent::ApprovalRequested,
+            format!("Approval requested by {}", event.server_name),
+        )),
+        EventMsg::RequestUserInput(_) => Some((
+            NotificationSinkEvent::ApprovalRequested,
+            "Codex is waiting for your answer".to_string(),
+        )),
+        EventMsg::TurnComplete(event) => {
+            let response = event
+                .last_agent_message
+                .as_deref()
+                .map(str::trim)
+                .filter(|message| !message.is_empty())
+                .map(|message| take_bytes_at_char_boundary(message, MAX_MESSAGE_BYTES));
+            Some(match spawned_agent {
+                Some(agent) => (
+                    NotificationSinkEvent::SubAgentFinished,
+                    match response {
+                        Some(response) => format!("{agent} finished: {response}"),
+                        None => format!("{agent} finished"),
+                    },
+                ),
+                None => (
+                    NotificationSinkEvent::TurnCompleted,
+                    response.unwrap_or("Agent turn complete").to_string(),
+                ),
+            })
+        }
+        EventMsg::Error(event) => {
+            let kind = match event.codex_error_info.as_ref()? {
+                CodexErrorInfo::UsageLimitExceeded => NotificationSinkEvent::RateLimitReached,
+                info if info.affects_turn_status() => NotificationSinkEvent::TurnFailed,
+                _ => return None,
+            };
+            Some((
+                kind,
+                take_bytes_at_char_boundary(&event.message, MAX_MESSAGE_BYTES).to_string(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+async fn deliver(
+    target: &NotificationSinkTarget,
+    http_client: Option<&reqwest::Client>,
+    notification: &SinkNotification,
+) -> Result<()> {
+    match target {
+        NotificationSinkTarget::Webhook { url, headers, body } => {
+            let client = http_client.context("webhook client was not initialized")?;
+            let body = match body {
+                Some(template) => render_body_template(template, notification),
+                None => serde_json::to_string(notification)?,
+            };
+            let mut request = client
+                .post(url)
+                .header(CONTENT_TYPE, "application/json")
+                .body(body);
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            request.send().await?.error_for_status()?;
+            Ok(())
+        }
+        NotificationSinkTarget::Command { command } => {
+            let mut command = command_from_argv(command).context("command is empty")?;
+            let mut child = command
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .kill_on_drop(true)
+                .spawn()?;
+            if let Some(mut stdin) = child.stdin.take() {
+                // The command is free to ignore its input, so a closed pipe is not an error.
+                let _ = stdin.write_all(&serde_json::to_vec(notification)?).await;
+            }
+            let status = child.wait().await?;
+            anyhow::ensure!(status.success(), "command exited with {status}");
+            Ok(())
+        }
+        NotificationSinkTarget::UnixSocket { path } => {
+            write_to_unix_socket(path.as_path(), notification).await
+        }
+    }
+}
+
+#[cfg(unix)]
+async fn write_to_unix_socket(path: &Path, notification: &SinkNotification) -> Result<()> {
+    let mut line = serde_json::to_vec(notification)?;
+    line.push(b'\n');
+    let mut stream = tokio::net::UnixStream::connect(path).await?;
+    stream.write_all(&line).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn write_to_unix_socket(_path: &Path, _notification: &SinkNotification) -> Result<()> {
+    anyhow::bail!("Unix socket sinks are not supported on this platform")
+}
+
+/// Replaces the `{{field}}` placeholders in a webhook body template. Values are
+/// JSON-escaped without surrounding quotes, so placeholders belong inside
+/// string literals. The template is scanned once, so placeholders that appear
+/// inside substituted values are left as they are.
+fn render_body_template(template: &str, notification: &SinkNotification) -> String {
+    let field = |name: &str| match name {
+        "event" => Some(notification.event.as_str()),
+        "thread_id" => Some(notification.thread_id.as_str()),
+        "turn_id" => Some(notification.turn_id.as_str()),
+        "cwd" => Some(notification.cwd.as_str()),
+        "message" => Some(notification.message.as_str()),
+        "timestamp" => Some(notification.timestamp.as_str()),
+        _ => None,
+    };
+    let mut body = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        body.push_str(&rest[..start]);
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        match field(&after_open[..end]) {
+            Some(value) => {
+                body.push_str(&json_escape(value));
+                rest = &after_open[end + 2..];
+            }
+            None => {
+                body.push_str("{{");
+                rest = after_open;
+            }
+        }
+    }
+    body.push_str(rest);
+    body
+}
+
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::Value::from(value).to_string();
+    quoted
+        .strip_prefix('"')
+        .and_then(|quoted| quoted.strip_suffix('"'))
+        .unwrap_or(&quoted)
+        .to_string()
+}
+
+fn describe_target(target: &NotificationSinkTarget) -> String {
+    match target {
+        // Webhook URLs often embed a token, so only the host is logged.
+        NotificationSinkTarget::Webhook { url, .. } => {
+            let host = reqwest::Url::parse(url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string));
+            format!("webhook {}", host.as_deref().unwrap_or("<invalid url>"))
+        }
+        NotificationSinkTarget::Command { command } => {
+            format!("command {}", command.first().map_or("", String::as_str))
+        }
+        NotificationSinkTarget::UnixSocket { path } => {
+            format!("socket {}", path.display())
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "notification_sinks_tests.rs"]
+mod tests;
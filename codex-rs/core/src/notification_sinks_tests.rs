@@ -0,0 +1,253 @@
+use super::*;
+use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::ExecApprovalRequestEvent;
+use codex_protocol::protocol::TurnCompleteEvent;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+fn turn_complete(last_agent_message: Option<&str>) -> EventMsg {
+    EventMsg::TurnComplete(TurnCompleteEvent {
+        turn_id: "turn-1".to_string(),
+        last_agent_message: last_agent_message.map(str::to_string),
+    })
+}
+
+fn error(codex_error_info: Option<CodexErrorInfo>) -> EventMsg {
+    EventMsg::Error(ErrorEvent {
+        message: "stream disconnected".to_string(),
+        codex_error_info,
+    })
+}
+
+fn exec_approval(command: &[&str]) -> EventMsg {
+    EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+        call_id: "call-1".to_string(),
+        approval_id: None,
+        turn_id: "turn-1".to_string(),
+        command: command.iter().map(ToString::to_string).collect(),
+        cwd: "/work/repo".into(),
+        reason: None,
+        network_approval_context: None,
+        proposed_execpolicy_amendment: None,
+        proposed_network_policy_amendments: None,
+        additional_permissions: None,
+        available_decisions: None,
+        parsed_cmd: Vec::new(),
+    })
+}
+
+fn notification() -> SinkNotification {
+    SinkNotification {
+        event: NotificationSinkEvent::TurnCompleted,
+        thread_id: "thread-1".to_string(),
+        turn_id: "turn-1".to_string(),
+        cwd: "/work/repo".to_string(),
+        message: "Done: \"tests\" pass\nall green".to_string(),
+        timestamp: "2026-01-01T00:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn turn_complete_notifies_turn_completed_or_sub_agent_finished() {
+    assert_eq!(
+        notification_for_event(
+            &turn_complete(Some("  All done. ")),
+            &SessionSource::Cli,
+            str::to_string
+        ),
+        Some((
+            NotificationSinkEvent::TurnCompleted,
+            "All done.".to_string()
+        ))
+    );
+    assert_eq!(
+        notification_for_event(&turn_complete(None), &SessionSource::Cli, str::to_string),
+        Some((
+            NotificationSinkEvent::TurnCompleted,
+            "Agent turn complete".to_string()
+        ))
+    );
+
+    let spawned = SessionSource::SubAgent(SubAgentSource::ThreadSpawn {
+        parent_thread_id: ThreadId::new(),
+        depth: 1,
+        agent_nickname: Some("Ada".to_string()),
+        agent_role: None,
+    });
+    assert_eq!(
+        notification_for_event(&turn_complete(Some("Fixed it")), &spawned, str::to_string),
+        Some((
+            NotificationSinkEvent::SubAgentFinished,
+            "Ada finished: Fixed it".to_string()
+        ))
+    );
+    assert_eq!(
+        notification_for_event(
+            &turn_complete(Some("Looks safe")),
+            &SessionSource::SubAgent(SubAgentSource::Review),
+            str::to_string
+        ),
+        None
+    );
+}
+
+#[test]
+fn errors_notify_rate_limit_or_turn_failure() {
+    assert_eq!(
+        notification_for_event(
+            &error(Some(CodexErrorInfo::UsageLimitExceeded)),
+            &SessionSource::Cli,
+            str::to_string
+        )
+        .map(|(kind, _)| kind),
+        Some(NotificationSinkEvent::RateLimitReached)
+    );
+    assert_eq!(
+        notification_for_event(
+            &error(Some(CodexErrorInfo::ResponseStreamDisconnected {
+                http_status_code: None
+            })),
+            &SessionSource::Cli,
+            str::to_string
+        ),
+        Some((
+            NotificationSinkEvent::TurnFailed,
+            "stream disconnected".to_string()
+        ))
+    );
+    assert_eq!(
+        notification_for_event(
+            &error(Some(CodexErrorInfo::ThreadRollbackFailed)),
+            &SessionSource::Cli,
+            str::to_string
+        ),
+        None
+    );
+    assert_eq!(
+        notification_for_event(&error(None), &SessionSource::Cli, str::to_string),
+        None
+    );
+}
+
+#[test]
+fn failed_turn_does_not_also_notify_turn_completed() {
+    let sinks = NotificationSinks::new(
+        Vec::new(),
+        ThreadId::new(),
+        Path::new("/work/repo"),
+        SessionSource::Cli,
+    );
+    let event = |id: &str, msg: EventMsg| Event {
+        id: id.to_string(),
+        msg,
+    };
+    let failure = Some(CodexErrorInfo::ResponseStreamDisconnected {
+        http_status_code: None,
+    });
+
+    assert_eq!(
+        sinks
+            .notification_for(&event("turn-1", error(failure)), str::to_string)
+            .map(|(kind, _)| kind),
+        Some(NotificationSinkEvent::TurnFailed)
+    );
+    assert_eq!(
+        sinks.notification_for(&event("turn-1", turn_complete(None)), str::to_string),
+        None
+    );
+    assert_eq!(
+        sinks
+            .notification_for(&event("turn-2", turn_complete(None)), str::to_string)
+            .map(|(kind, _)| kind),
+        Some(NotificationSinkEvent::TurnCompleted)
+    );
+}
+
+#[test]
+fn exec_approval_command_is_redacted_and_truncated() {
+    let redact = |text: &str| text.replace("hunter2", "[REDACTED]");
+    assert_eq!(
+        notification_for_event(
+            &exec_approval(&["curl", "-H", "token: hunter2", "example.com"]),
+            &SessionSource::Cli,
+            redact
+        ),
+        Some((
+            NotificationSinkEvent::ApprovalRequested,
+            "Approval requested: curl -H token: [REDACTED] example.com".to_string()
+        ))
+    );
+
+    let long_script = "x".repeat(MAX_MESSAGE_BYTES * 2);
+    let (_, message) = notification_for_event(
+        &exec_approval(&["bash", "-lc", &long_script]),
+        &SessionSource::Cli,
+        redact,
+    )
+    .expect("exec approvals notify");
+    assert_eq!(
+        message.len(),
+        "Approval requested: ".len() + MAX_MESSAGE_BYTES
+    );
+}
+
+#[test]
+fn body_template_does_not_expand_placeholders_in_values() {
+    let mut notification = notification();
+    notification.message = "literal {{event}} and {{thread_id}}".to_string();
+
+    assert_eq!(
+        render_body_template(
+            r#"{"text": "{{message}}", "id": "{{thread_id}}"}"#,
+            &notification
+        ),
+        r#"{"text": "literal {{event}} and {{thread_id}}", "id": "thread-1"}"#
+    );
+}
+
+#[test]
+fn body_template_substitutes_json_escaped_fields() {
+    let template =
+        r#"{"text": "[{{event}}] {{message}}", "thread": "{{thread_id}}", "unknown": "{{nope}}"}"#;
+
+    let body: Value = serde_json::from_str(&render_body_template(template, &notification()))
+        .expect("rendered body should be valid JSON");
+
+    assert_eq!(
+        body,
+        json!({
+            "text": "[turn_completed] Done: \"tests\" pass\nall green",
+            "thread": "thread-1",
+            "unknown": "{{nope}}",
+        })
+    );
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn unix_socket_sink_writes_one_json_line() -> anyhow::Result<()> {
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use tokio::io::AsyncReadExt;
+
+    let dir = tempfile::tempdir()?;
+    let path = AbsolutePathBuf::from_absolute_path(dir.path().join("notify.sock"))?;
+    let listener = tokio::net::UnixListener::bind(path.as_path())?;
+    let target = NotificationSinkTarget::UnixSocket { path };
+
+    let (delivered, received) = tokio::join!(deliver(&target, None, &notification()), async {
+        let (mut stream, _) = listener.accept().await?;
+        let mut received = String::new();
+        stream.read_to_string(&mut received).await?;
+        anyhow::Ok(received)
+    });
+    delivered?;
+    let received = received?;
+
+    assert!(received.ends_with('\n'));
+    assert_eq!(
+        serde_json::from_str::<Value>(&received)?,
+        serde_json::to_value(notification())?
+    );
+    Ok(())
+}
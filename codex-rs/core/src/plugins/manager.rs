@@ -976,6 +976,7 @@ mod tests {
                         disabled_tools: None,
                         scopes: None,
                         oauth_resource: None,
+                        sampling_token_budget: None,
                     },
                 )]),
                 apps: vec![AppConnectorId("connector_example".to_string())],
@@ -1103,6 +1104,7 @@ mod tests {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_budget: None,
                 },
             )])
         );
@@ -1206,6 +1208,7 @@ mod tests {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_budget: None,
                 },
             )])
         );
@@ -1353,6 +1356,7 @@ mod tests {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         };
         let plugin = |config_name: &str, dir_name: &str, manifest_name: &str| LoadedPlugin {
             config_name: config_name.to_string(),
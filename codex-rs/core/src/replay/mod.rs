@@ -0,0 +1,669 @@
+//! Deterministic offline replay of a recorded session.
+//!
+//! The user messages of a rollout are submitted to a fresh thread whose model
+//! provider is a local server streaming the recorded model responses back in
+//! order, so the session takes the same path without network access. Tool
+//! calls are either answered with their recorded outputs or executed again,
+//! with approval requests answered the way they were in the recording.
+//! The replay runs with the current configuration, which makes it a
+//! regression test for exec policy rules, hooks and other config changes:
+//! anything that makes the replay differ from the recording is reported as a
+//! [`ReplayDivergence`].
+
+mod script;
+mod server;
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::SandboxPermissions;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SessionSource;
+use serde::Serialize;
+
+use crate::AuthManager;
+use crate::CodexThread;
+use crate::ThreadManager;
+use crate::WireApi;
+use crate::config::Config;
+use crate::create_oss_provider_with_base_url;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::exec_policy::ExecApprovalRequest;
+use crate::exec_policy::ExecPolicyManager;
+use crate::exec_policy::format_exec_policy_error_with_source;
+use crate::git_info::get_git_repo_root;
+use crate::models_manager::collaboration_mode_presets::CollaborationModesConfig;
+use crate::rollout::RolloutRecorder;
+use crate::tools::sandboxing::ExecApprovalRequirement;
+use script::RecordedToolCall;
+use script::RecordedToolCallKind;
+pub(crate) use script::RecordedToolOutputs;
+use script::ReplayScript;
+use server::ReplayServer;
+
+const REPLAY_PROVIDER_ID: &str = "replay";
+
+/// How long to wait for a turn to settle after it was interrupted.
+const INTERRUPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayToolMode {
+    /// Answer tool calls with their recorded outputs. Nothing is executed.
+    Stub,
+    /// Execute tool calls again. Approval requests get the recorded decision:
+    /// commands and patches that ran are approved, everything else is denied.
+    Execute,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+    pub tools: ReplayToolMode,
+    /// Model to replay with. Defaults to the recorded model, which keeps the
+    /// recorded tool names available.
+    pub model: Option<String>,
+    /// Working directory to replay in. Defaults to the recorded one when it
+    /// still exists.
+    pub cwd: Option<PathBuf>,
+    /// Upper bound on the duration of a single turn.
+    pub turn_timeout: Duration,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayReport {
+    pub rollout_path: PathBuf,
+    pub tools: ReplayToolMode,
+    pub turns: usize,
+    pub tool_calls: usize,
+    /// Recorded commands that exec policy would not run without asking.
+    pub approvals: Vec<ReplayApproval>,
+    pub divergences: Vec<ReplayDivergence>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayApproval {
+    /// 1-based turn number.
+    pub turn: usize,
+    pub call_id: String,
+    pub command: Vec<String>,
+    pub decision: ApprovalDecision,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    Allow,
+    Prompt,
+    Forbidden,
+}
+
+/// What happened to a command in the recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordedCommandOutcome {
+    Ran,
+    Rejected,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReplayDivergence {
+    /// A tool call returned a different output than recorded. Timing
+    /// details are ignored.
+    ToolOutput {
+        turn: usize,
+        call_id: String,
+        tool: String,
+        recorded: String,
+        replayed: String,
+    },
+    /// A recorded tool call never returned an output during the replay.
+    MissingToolOutput {
+        turn: usize,
+        call_id: String,
+        tool: String,
+    },
+    /// Exec policy now decides differently about a recorded command.
+    Approval {
+        turn: usize,
+        call_id: String,
+        command: Vec<String>,
+        recorded: RecordedCommandOutcome,
+        replayed: ApprovalDecision,
+    },
+    /// Codex asked the model for a different number of responses than were
+    /// recorded, for example because a hook blocked a prompt.
+    ModelRequests {
+        turn: usize,
+        recorded: usize,
+        replayed: usize,
+    },
+    /// The files changed by `apply_patch` differ from the recorded patches.
+    FileChanges {
+        turn: usize,
+        recorded: Vec<PathBuf>,
+        replayed: Vec<PathBuf>,
+    },
+    /// The turn failed, was interrupted or needed input the replay cannot
+    /// provide.
+    TurnFailed { turn: usize, message: String },
+}
+
+impl ReplayReport {
+    pub fn has_divergences(&self) -> bool {
+        !self.divergences.is_empty()
+    }
+}
+
+/// Replay the rollout at `rollout_path` with `config` and report where the
+/// replay diverged from the recording.
+pub async fn replay_rollout(
+    mut config: Config,
+    rollout_path: &Path,
+    options: ReplayOptions,
+) -> CodexResult<ReplayReport> {
+    let (items, _, _) = RolloutRecorder::load_rollout_items(rollout_path).await?;
+    let script = ReplayScript::from_rollout_items(&items);
+    if script.turns.is_empty() {
+        return Err(CodexErr::InvalidRequest(format!(
+            "no user turns recorded in {}",
+            rollout_path.display()
+        )));
+    }
+
+    let server = ReplayServer::start().await?;
+    let mut provider = create_oss_provider_with_base_url(&server.base_url(), WireApi::Responses);
+    provider.name = REPLAY_PROVIDER_ID.to_string();
+    provider.request_max_retries = Some(0);
+    provider.stream_max_retries = Some(0);
+    config.model_provider_id = REPLAY_PROVIDER_ID.to_string();
+    config.model_provider = provider;
+    config.ephemeral = true;
+    if let Some(model) = options.model.clone().or_else(|| script.model.clone()) {
+        config.model = Some(model);
+    }
+    if let Some(cwd) = options
+        .cwd
+        .clone()
+        .or_else(|| script.cwd.clone().filter(|cwd| cwd.is_dir()))
+    {
+        config.cwd = cwd;
+    }
+
+    let mut report = ReplayReport {
+        rollout_path: rollout_path.to_path_buf(),
+        tools: options.tools,
+        turns: script.turns.len(),
+        tool_calls: script.tool_calls.len(),
+        approvals: Vec::new(),
+        divergences: Vec::new(),
+    };
+    check_approvals(&config, &script, &mut report).await?;
+
+    let auth_manager = AuthManager::shared(
+        config.codex_home.clone(),
+        false,
+        config.cli_auth_credentials_store_mode,
+    );
+    let replay_tool_outputs =
+        (options.tools == ReplayToolMode::Stub).then(|| Arc::new(script.tool_outputs.clone()));
+    let thread_manager = ThreadManager::with_replay_tool_outputs(
+        config.codex_home.clone(),
+        auth_manager,
+        SessionSource::Exec,
+        config.model_catalog.clone(),
+        CollaborationModesConfig {
+            default_mode_request_user_input: false,
+        },
+        replay_tool_outputs,
+    );
+    let cwd = config.cwd.clone();
+    let new_thread = thread_manager.start_thread(config).await?;
+    let thread = new_thread.thread;
+
+    for (index, turn) in script.turns.iter().enumerate() {
+        let turn_number = index + 1;
+        server.begin_turn(turn.responses.clone());
+        thread
+            .submit(Op::UserInput {
+                items: turn.input.clone(),
+                final_output_json_schema: None,
+            })
+            .await?;
+        let outcome = run_turn(&thread, &script, options.turn_timeout).await?;
+        let requests = server.end_turn();
+
+        if let Some(message) = outcome.failure {
+            report.divergences.push(ReplayDivergence::TurnFailed {
+                turn: turn_number,
+                message,
+            });
+        }
+        if requests.unused_responses > 0 || requests.extra_requests > 0 {
+            let recorded = turn.responses.len();
+            report.divergences.push(ReplayDivergence::ModelRequests {
+                turn: turn_number,
+                recorded,
+                replayed: recorded - requests.unused_responses + requests.extra_requests,
+            });
+        }
+        if options.tools == ReplayToolMode::Execute {
+            let recorded = recorded_patch_files(&script.tool_calls, index, &cwd);
+            let replayed = outcome
+                .unified_diff
+                .as_deref()
+                .map(|diff| diff_files(diff, &cwd))
+                .unwrap_or_default();
+            if recorded != replayed {
+                report.divergences.push(ReplayDivergence::FileChanges {
+                    turn: turn_number,
+                    recorded: recorded.into_iter().collect(),
+                    replayed: replayed.into_iter().collect(),
+                });
+            }
+        }
+    }
+
+    thread.submit(Op::Shutdown).await?;
+    let _ = tokio::time::timeout(INTERRUPT_TIMEOUT, async {
+        while let Ok(event) = thread.next_event().await {
+            if matches!(event.msg, EventMsg::ShutdownComplete) {
+                break;
+            }
+        }
+    })
+    .await;
+
+    compare_tool_outputs(&script, &server.tool_outputs(), &mut report);
+    Ok(report)
+}
+
+#[derive(Debug, Default)]
+struct TurnOutcome {
+    unified_diff: Option<String>,
+    failure: Option<String>,
+}
+
+async fn run_turn(
+    thread: &CodexThread,
+    script: &ReplayScript,
+    turn_timeout: Duration,
+) -> CodexResult<TurnOutcome> {
+    let mut outcome = TurnOutcome::default();
+    let deadline = tokio::time::Instant::now() + turn_timeout;
+    loop {
+        let event = match tokio::time::timeout_at(deadline, thread.next_event()).await {
+            Ok(event) => event?,
+            Err(_) => {
+                outcome.failure = Some(format!(
+                    "turn did not finish within {}s",
+                    turn_timeout.as_secs()
+                ));
+                interrupt(thread).await?;
+                return Ok(outcome);
+            }
+        };
+        match event.msg {
+            EventMsg::TurnComplete(_) => return Ok(outcome),
+            EventMsg::TurnAborted(event) => {
+                outcome.failure.get_or_insert_with(|| {
+                    format!("turn aborted: {:?}", event.reason).to_lowercase()
+                });
+                return Ok(outcome);
+            }
+            EventMsg::Error(event) => {
+                outcome.failure = Some(event.message);
+            }
+            EventMsg::TurnDiff(event) => {
+                outcome.unified_diff = Some(event.unified_diff);
+            }
+            EventMsg::ExecApprovalRequest(event) => {
+                let decision = recorded_decision(script, &event.call_id);
+                thread
+                    .submit(Op::ExecApproval {
+                        id: event.effective_approval_id(),
+                        turn_id: Some(event.turn_id),
+                        decision,
+                    })
+                    .await?;
+            }
+            EventMsg::ApplyPatchApprovalRequest(event) => {
+                let decision = recorded_decision(script, &event.call_id);
+                thread
+                    .submit(Op::PatchApproval {
+                        id: event.call_id,
+                        decision,
+                    })
+                    .await?;
+            }
+            EventMsg::RequestUserInput(_) | EventMsg::ElicitationRequest(_) => {
+                outcome.failure = Some("turn requested input from the user".to_string());
+                interrupt(thread).await?;
+                return Ok(outcome);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Interrupt the running turn and wait for it to wind down.
+async fn interrupt(thread: &CodexThread) -> CodexResult<()> {
+    thread.submit(Op::Interrupt).await?;
+    let _ = tokio::time::timeout(INTERRUPT_TIMEOUT, async {
+        while let Ok(event) = thread.next_event().await {
+            if matches!(
+                event.msg,
+                EventMsg::TurnAborted(_) | EventMsg::TurnComplete(_)
+            ) {
+                break;
+            }
+        }
+    })
+    .await;
+    Ok(())
+}
+
+/// Evaluate every recorded command against the current exec policy.
+async fn check_approvals(
+    config: &Config,
+    script: &ReplayScript,
+    report: &mut ReplayReport,
+) -> CodexResult<()> {
+    let exec_policy = ExecPolicyManager::load(&config.config_layer_stack)
+        .await
+        .map_err(|err| CodexErr::Fatal(format_exec_policy_error_with_source(&err)))?;
+    let approval_policy = config.permissions.approval_policy.value();
+    let sandbox_policy = config.permissions.sandbox_policy.get();
+    for call in &script.tool_calls {
+        let RecordedToolCallKind::Command(command) = &call.kind else {
+            continue;
+        };
+        let requirement = exec_policy
+            .create_exec_approval_requirement_for_command(ExecApprovalRequest {
+                command,
+                approval_policy,
+                sandbox_policy,
+                sandbox_permissions: SandboxPermissions::UseDefault,
+                prefix_rule: None,
+                cwd: Some(&config.cwd),
+                workspace_root: Some(&config.cwd),
+            })
+            .await;
+        let (decision, reason) = match requirement {
+            ExecApprovalRequirement::Skip { .. } => (ApprovalDecision::Allow, None),
+            ExecApprovalRequirement::NeedsApproval { reason, .. } => {
+                (ApprovalDecision::Prompt, reason)
+            }
+            ExecApprovalRequirement::Forbidden { reason } => {
+                (ApprovalDecision::Forbidden, Some(reason))
+            }
+        };
+        if decision != ApprovalDecision::Allow {
+            report.approvals.push(ReplayApproval {
+                turn: call.turn + 1,
+                call_id: call.call_id.clone(),
+                command: command.clone(),
+                decision,
+                reason,
+            });
+        }
+
+        let Some(recorded) = recorded_outcome(script, &call.call_id) else {
+            continue;
+        };
+        let diverged = match recorded {
+            RecordedCommandOutcome::Ran => decision == ApprovalDecision::Forbidden,
+            RecordedCommandOutcome::Rejected => decision == ApprovalDecision::Allow,
+        };
+        if diverged {
+            report.divergences.push(ReplayDivergence::Approval {
+                turn: call.turn + 1,
+                call_id: call.call_id.clone(),
+                command: command.clone(),
+                recorded,
+                replayed: decision,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Whether the command or patch of `call_id` ran in the recording, from its
+/// end event when one was persisted and from its tool output otherwise.
+fn recorded_outcome(script: &ReplayScript, call_id: &str) -> Option<RecordedCommandOutcome> {
+    script.command_outcomes.get(call_id).copied().or_else(|| {
+        script
+            .tool_outputs
+            .payload(call_id)
+            .map(recorded_command_outcome)
+    })
+}
+
+/// The answer to an approval request during replay. Only calls that are known
+/// to have run in the recording are approved, so nothing the user declined
+/// (or never got to decide on) is executed.
+fn recorded_decision(script: &ReplayScript, call_id: &str) -> ReviewDecision {
+    match recorded_outcome(script, call_id) {
+        Some(RecordedCommandOutcome::Ran) => ReviewDecision::Approved,
+        Some(RecordedCommandOutcome::Rejected) | None => ReviewDecision::Denied,
+    }
+}
+
+fn recorded_command_outcome(output: &FunctionCallOutputPayload) -> RecordedCommandOutcome {
+    let text = output.body.to_text().unwrap_or_default();
+    // Mirrors the messages Codex returns to the model for declined and
+    // policy-forbidden commands and patches.
+    if text.starts_with("exec command rejected")
+        || text.starts_with("patch rejected")
+        || text.starts_with("rejected by user")
+        || (text.starts_with('`') && text.contains("` rejected: "))
+    {
+        RecordedCommandOutcome::Rejected
+    } else {
+        RecordedCommandOutcome::Ran
+    }
+}
+
+fn compare_tool_outputs(
+    script: &ReplayScript,
+    replayed: &std::collections::HashMap<String, FunctionCallOutputPayload>,
+    report: &mut ReplayReport,
+) {
+    for call in &script.tool_calls {
+        let Some(recorded) = script.tool_outputs.payload(&call.call_id) else {
+            continue;
+        };
+        let Some(replayed) = replayed.get(&call.call_id) else {
+            report
+                .divergences
+                .push(ReplayDivergence::MissingToolOutput {
+                    turn: call.turn + 1,
+                    call_id: call.call_id.clone(),
+                    tool: call.name.clone(),
+                });
+            continue;
+        };
+        let recorded = normalize_tool_output(&recorded.body.to_text().unwrap_or_default());
+        let replayed = normalize_tool_output(&replayed.body.to_text().unwrap_or_default());
+        if recorded != replayed {
+            report.divergences.push(ReplayDivergence::ToolOutput {
+                turn: call.turn + 1,
+                call_id: call.call_id.clone(),
+                tool: call.name.clone(),
+                recorded,
+                replayed,
+            });
+        }
+    }
+    report.divergences.sort_by_key(ReplayDivergence::turn);
+}
+
+impl ReplayDivergence {
+    pub fn turn(&self) -> usize {
+        match self {
+            Self::ToolOutput { turn, .. }
+            | Self::MissingToolOutput { turn, .. }
+            | Self::Approval { turn, .. }
+            | Self::ModelRequests { turn, .. }
+            | Self::FileChanges { turn, .. }
+            | Self::TurnFailed { turn, .. } => *turn,
+        }
+    }
+}
+
+/// Drop the parts of a tool output that change on every run: wall time and
+/// chunk ids of command output, in both the freeform and the JSON format.
+fn normalize_tool_output(output: &str) -> String {
+    if let Ok(mut value) = serde_json::from_str::<serde_json::Value>(output)
+        && let Some(metadata) = value
+            .get_mut("metadata")
+            .and_then(serde_json::Value::as_object_mut)
+    {
+        metadata.remove("duration_seconds");
+        return value.to_string();
+    }
+    output
+        .lines()
+        .filter(|line| !line.starts_with("Wall time: ") && !line.starts_with("Chunk ID: "))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Files touched by the patches recorded in the turn at `turn_index`.
+fn recorded_patch_files(
+    tool_calls: &[RecordedToolCall],
+    turn_index: usize,
+    cwd: &Path,
+) -> BTreeSet<PathBuf> {
+    let mut files = BTreeSet::new();
+    for call in tool_calls.iter().filter(|call| call.turn == turn_index) {
+        let RecordedToolCallKind::Patch(patch) = &call.kind else {
+            continue;
+        };
+        let Ok(args) = codex_apply_patch::parse_patch(patch) else {
+            continue;
+        };
+        for hunk in args.hunks {
+            match hunk {
+                codex_apply_patch::Hunk::AddFile { path, .. }
+                | codex_apply_patch::Hunk::DeleteFile { path } => {
+                    files.insert(cwd.join(path));
+                }
+                codex_apply_patch::Hunk::UpdateFile {
+                    path, move_path, ..
+                } => {
+                    files.insert(cwd.join(move_path.unwrap_or(path)));
+                }
+            }
+        }
+    }
+    files
+}
+
+/// Files named in a turn diff. Paths in turn diffs are relative to the git
+/// root of the file, or absolute outside of a repository.
+fn diff_files(unified_diff: &str, cwd: &Path) -> BTreeSet<PathBuf> {
+    let root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+    unified_diff
+        .lines()
+        .filter_map(|line| line.strip_prefix("diff --git "))
+        .filter_map(|paths| paths.rsplit_once(" b/").map(|(_, right)| right))
+        .map(|path| root.join(path))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn normalizes_timing_in_tool_outputs() {
+        assert_eq!(
+            normalize_tool_output("Exit code: 0\nWall time: 1.2 seconds\nOutput:\nok"),
+            "Exit code: 0\nOutput:\nok"
+        );
+        let normalized = normalize_tool_output(
+            r#"{"output":"ok","metadata":{"exit_code":0,"duration_seconds":0.4}}"#,
+        );
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&normalized).unwrap(),
+            serde_json::json!({"output": "ok", "metadata": {"exit_code": 0}})
+        );
+    }
+
+    #[test]
+    fn classifies_recorded_rejections() {
+        let outcome = |text: &str| {
+            recorded_command_outcome(&FunctionCallOutputPayload::from_text(text.into()))
+        };
+
+        assert_eq!(
+            outcome("exec command rejected by user"),
+            RecordedCommandOutcome::Rejected
+        );
+        assert_eq!(
+            outcome("`rm -rf /` rejected: blocked by policy"),
+            RecordedCommandOutcome::Rejected
+        );
+        assert_eq!(
+            outcome("Exit code: 0\nOutput:\n"),
+            RecordedCommandOutcome::Ran
+        );
+    }
+
+    #[test]
+    fn approvals_replay_the_recorded_decision() {
+        use codex_protocol::protocol::RolloutItem;
+        use codex_protocol::protocol::UserMessageEvent;
+
+        let output = |call_id: &str, text: &str| {
+            RolloutItem::ResponseItem(codex_protocol::models::ResponseItem::FunctionCallOutput {
+                call_id: call_id.to_string(),
+                output: FunctionCallOutputPayload::from_text(text.to_string()),
+            })
+        };
+        let script = ReplayScript::from_rollout_items(&[
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: "clean up".to_string(),
+                images: None,
+                local_images: Vec::new(),
+                text_elements: Vec::new(),
+            })),
+            output("denied", "exec command rejected by user"),
+            output("patch-denied", "patch rejected by user"),
+            output("ran", "Exit code: 0\nOutput:\n"),
+        ]);
+
+        assert_eq!(recorded_decision(&script, "ran"), ReviewDecision::Approved);
+        assert_eq!(recorded_decision(&script, "denied"), ReviewDecision::Denied);
+        assert_eq!(
+            recorded_decision(&script, "patch-denied"),
+            ReviewDecision::Denied
+        );
+        assert_eq!(
+            recorded_decision(&script, "not-recorded"),
+            ReviewDecision::Denied
+        );
+    }
+
+    #[test]
+    fn diff_files_resolve_against_cwd_outside_git() {
+        let cwd = Path::new("/nonexistent-replay-dir");
+        let diff = "diff --git a/src/lib.rs b/src/lib.rs\nindex 1..2\n--- a/src/lib.rs\n+++ b/src/lib.rs\n";
+
+        assert_eq!(
+            diff_files(diff, cwd),
+            BTreeSet::from([cwd.join("src/lib.rs")])
+        );
+    }
+}
@@ -0,0 +1,418 @@
+//! Split a recorded rollout into the inputs, model responses and tool outputs
+//! needed to re-drive it.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseInputItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::ShellCommandToolCallParams;
+use codex_protocol::models::ShellToolCallParams;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ExecCommandStatus;
+use codex_protocol::protocol::PatchApplyStatus;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::user_input::UserInput;
+use serde_json::Value as JsonValue;
+
+use super::RecordedCommandOutcome;
+
+/// Tool outputs recorded in a rollout, keyed by call id. When a replay hands
+/// them to the sessions it starts, tool calls are answered from here instead
+/// of being executed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct RecordedToolOutputs {
+    outputs: HashMap<String, ResponseInputItem>,
+}
+
+impl RecordedToolOutputs {
+    pub(crate) fn get(&self, call_id: &str) -> Option<&ResponseInputItem> {
+        self.outputs.get(call_id)
+    }
+
+    pub(crate) fn payload(&self, call_id: &str) -> Option<&FunctionCallOutputPayload> {
+        match self.outputs.get(call_id)? {
+            ResponseInputItem::FunctionCallOutput { output, .. }
+            | ResponseInputItem::CustomToolCallOutput { output, .. } => Some(output),
+            ResponseInputItem::Message { .. } | ResponseInputItem::McpToolCallOutput { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ReplayScript {
+    /// Model of the first recorded turn.
+    pub(crate) model: Option<String>,
+    pub(crate) cwd: Option<PathBuf>,
+    pub(crate) turns: Vec<ReplayTurn>,
+    /// Recorded tool calls in the order the model made them.
+    pub(crate) tool_calls: Vec<RecordedToolCall>,
+    pub(crate) tool_outputs: RecordedToolOutputs,
+    /// Whether recorded commands and patches ran or were declined, keyed by
+    /// call id. Only rollouts with extended event persistence record this.
+    pub(crate) command_outcomes: HashMap<String, RecordedCommandOutcome>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct ReplayTurn {
+    pub(crate) input: Vec<UserInput>,
+    /// Model responses of the turn, one per request Codex made. Every
+    /// response but the last one ends in tool calls.
+    pub(crate) responses: Vec<Vec<ResponseItem>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RecordedToolCall {
+    pub(crate) turn: usize,
+    pub(crate) call_id: String,
+    pub(crate) name: String,
+    pub(crate) kind: RecordedToolCallKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum RecordedToolCallKind {
+    /// A shell command, as the argv exec policy rules are matched against.
+    Command(Vec<String>),
+    /// An `apply_patch` call with the raw patch.
+    Patch(String),
+    Other,
+}
+
+impl ReplayScript {
+    pub(crate) fn from_rollout_items(items: &[RolloutItem]) -> Self {
+        let mut script = Self::default();
+        let mut response = Vec::new();
+        for item in items {
+            match item {
+                RolloutItem::SessionMeta(meta) => {
+                    script.cwd.get_or_insert_with(|| meta.meta.cwd.clone());
+                }
+                RolloutItem::TurnContext(context) => {
+                    script.model.get_or_insert_with(|| context.model.clone());
+                    script.cwd = Some(context.cwd.clone());
+                }
+                RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+                    script.flush_response(&mut response);
+                    let mut input: Vec<UserInput> = event
+                        .images
+                        .iter()
+                        .flatten()
+                        .map(|image_url| UserInput::Image {
+                            image_url: image_url.clone(),
+                        })
+                        .collect();
+                    input.extend(
+                        event
+                            .local_images
+                            .iter()
+                            .map(|path| UserInput::LocalImage { path: path.clone() }),
+                    );
+                    input.push(UserInput::Text {
+                        text: event.message.clone(),
+                        text_elements: event.text_elements.clone(),
+                    });
+                    script.turns.push(ReplayTurn {
+                        input,
+                        responses: Vec::new(),
+                    });
+                }
+                RolloutItem::ResponseItem(item) => {
+                    // Items recorded before the first user message (for
+                    // example injected instructions) are not model output.
+                    if script.turns.is_empty() {
+                        continue;
+                    }
+                    match item {
+                        ResponseItem::Message { role, .. } if role == "assistant" => {
+                            response.push(item.clone());
+                        }
+                        ResponseItem::Reasoning { .. }
+                        | ResponseItem::WebSearchCall { .. }
+                        | ResponseItem::ImageGenerationCall { .. } => response.push(item.clone()),
+                        ResponseItem::FunctionCall { .. }
+                        | ResponseItem::LocalShellCall { .. }
+                        | ResponseItem::CustomToolCall { .. } => {
+                            if let Some(call) = recorded_tool_call(script.turns.len() - 1, item) {
+                                script.tool_calls.push(call);
+                            }
+                            response.push(item.clone());
+                        }
+                        ResponseItem::FunctionCallOutput { call_id, output } => {
+                            script.flush_response(&mut response);
+                            script.tool_outputs.outputs.insert(
+                                call_id.clone(),
+                                ResponseInputItem::FunctionCallOutput {
+                                    call_id: call_id.clone(),
+                                    output: output.clone(),
+                                },
+                            );
+                        }
+                        ResponseItem::CustomToolCallOutput { call_id, output } => {
+                            script.flush_response(&mut response);
+                            script.tool_outputs.outputs.insert(
+                                call_id.clone(),
+                                ResponseInputItem::CustomToolCallOutput {
+                                    call_id: call_id.clone(),
+                                    output: output.clone(),
+                                },
+                            );
+                        }
+                        ResponseItem::Message { .. }
+                        | ResponseItem::GhostSnapshot { .. }
+                        | ResponseItem::Compaction { .. }
+                        | ResponseItem::Other => {}
+                    }
+                }
+                RolloutItem::EventMsg(EventMsg::ExecCommandEnd(event)) => {
+                    let outcome = match event.status {
+                        ExecCommandStatus::Declined => RecordedCommandOutcome::Rejected,
+                        ExecCommandStatus::Completed | ExecCommandStatus::Failed => {
+                            RecordedCommandOutcome::Ran
+                        }
+                    };
+                    script
+                        .command_outcomes
+                        .insert(event.call_id.clone(), outcome);
+                }
+                RolloutItem::EventMsg(EventMsg::PatchApplyEnd(event)) => {
+                    let outcome = match event.status {
+                        PatchApplyStatus::Declined => RecordedCommandOutcome::Rejected,
+                        PatchApplyStatus::Completed | PatchApplyStatus::Failed => {
+                            RecordedCommandOutcome::Ran
+                        }
+                    };
+                    script
+                        .command_outcomes
+                        .insert(event.call_id.clone(), outcome);
+                }
+                RolloutItem::EventMsg(_) | RolloutItem::Compacted(_) => {}
+            }
+        }
+        script.flush_response(&mut response);
+        script
+    }
+
+    fn flush_response(&mut self, response: &mut Vec<ResponseItem>) {
+        if response.is_empty() {
+            return;
+        }
+        if let Some(turn) = self.turns.last_mut() {
+            turn.responses.push(std::mem::take(response));
+        }
+    }
+}
+
+fn recorded_tool_call(turn: usize, item: &ResponseItem) -> Option<RecordedToolCall> {
+    let (call_id, name, kind) = match item {
+        ResponseItem::LocalShellCall {
+            id,
+            call_id,
+            action: LocalShellAction::Exec(exec),
+            ..
+        } => (
+            call_id.clone().or_else(|| id.clone())?,
+            "local_shell".to_string(),
+            RecordedToolCallKind::Command(exec.command.clone()),
+        ),
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } => (
+            call_id.clone(),
+            name.clone(),
+            function_call_kind(name, arguments),
+        ),
+        ResponseItem::CustomToolCall {
+            call_id,
+            name,
+            input,
+            ..
+        } => {
+            let kind = if name == "apply_patch" {
+                RecordedToolCallKind::Patch(input.clone())
+            } else {
+                RecordedToolCallKind::Other
+            };
+            (call_id.clone(), name.clone(), kind)
+        }
+        _ => return None,
+    };
+    Some(RecordedToolCall {
+        turn,
+        call_id,
+        name,
+        kind,
+    })
+}
+
+fn function_call_kind(name: &str, arguments: &str) -> RecordedToolCallKind {
+    match name {
+        "shell" | "container.exec" => serde_json::from_str::<ShellToolCallParams>(arguments)
+            .map(|params| RecordedToolCallKind::Command(params.command))
+            .unwrap_or(RecordedToolCallKind::Other),
+        "shell_command" => serde_json::from_str::<ShellCommandToolCallParams>(arguments)
+            .map(|params| RecordedToolCallKind::Command(bash_lc(params.command)))
+            .unwrap_or(RecordedToolCallKind::Other),
+        "exec_command" => serde_json::from_str::<JsonValue>(arguments)
+            .ok()
+            .and_then(|args| args.get("cmd")?.as_str().map(str::to_string))
+            .map(|cmd| RecordedToolCallKind::Command(bash_lc(cmd)))
+            .unwrap_or(RecordedToolCallKind::Other),
+        "apply_patch" => serde_json::from_str::<JsonValue>(arguments)
+            .ok()
+            .and_then(|args| args.get("input")?.as_str().map(str::to_string))
+            .map(RecordedToolCallKind::Patch)
+            .unwrap_or(RecordedToolCallKind::Other),
+        _ => RecordedToolCallKind::Other,
+    }
+}
+
+fn bash_lc(script: String) -> Vec<String> {
+    vec!["bash".to_string(), "-lc".to_string(), script]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::protocol::SessionMeta;
+    use codex_protocol::protocol::SessionMetaLine;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    fn user_message(text: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: text.to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        }))
+    }
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![ContentItem::OutputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    fn function_call(name: &str, arguments: &str, call_id: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            call_id: call_id.to_string(),
+        }
+    }
+
+    fn output(call_id: &str, text: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload::from_text(text.to_string()),
+        }
+    }
+
+    #[test]
+    fn splits_turns_into_model_responses() {
+        let items = vec![
+            RolloutItem::SessionMeta(SessionMetaLine {
+                meta: SessionMeta {
+                    id: ThreadId::new(),
+                    cwd: PathBuf::from("/repo"),
+                    ..SessionMeta::default()
+                },
+                git: None,
+            }),
+            RolloutItem::ResponseItem(message("developer", "instructions")),
+            user_message("run the tests"),
+            RolloutItem::ResponseItem(message("user", "run the tests")),
+            RolloutItem::ResponseItem(message("assistant", "Running them.")),
+            RolloutItem::ResponseItem(function_call(
+                "shell_command",
+                r#"{"command":"cargo test"}"#,
+                "call-1",
+            )),
+            RolloutItem::ResponseItem(output("call-1", "ok")),
+            RolloutItem::ResponseItem(message("assistant", "All green.")),
+            user_message("thanks"),
+            RolloutItem::ResponseItem(message("assistant", "Anytime.")),
+        ];
+
+        let script = ReplayScript::from_rollout_items(&items);
+
+        assert_eq!(script.cwd, Some(PathBuf::from("/repo")));
+        assert_eq!(
+            script.turns,
+            vec![
+                ReplayTurn {
+                    input: vec![UserInput::Text {
+                        text: "run the tests".to_string(),
+                        text_elements: Vec::new(),
+                    }],
+                    responses: vec![
+                        vec![
+                            message("assistant", "Running them."),
+                            function_call("shell_command", r#"{"command":"cargo test"}"#, "call-1"),
+                        ],
+                        vec![message("assistant", "All green.")],
+                    ],
+                },
+                ReplayTurn {
+                    input: vec![UserInput::Text {
+                        text: "thanks".to_string(),
+                        text_elements: Vec::new(),
+                    }],
+                    responses: vec![vec![message("assistant", "Anytime.")]],
+                },
+            ]
+        );
+        assert_eq!(
+            script.tool_calls,
+            vec![RecordedToolCall {
+                turn: 0,
+                call_id: "call-1".to_string(),
+                name: "shell_command".to_string(),
+                kind: RecordedToolCallKind::Command(vec![
+                    "bash".to_string(),
+                    "-lc".to_string(),
+                    "cargo test".to_string(),
+                ]),
+            }]
+        );
+        assert_eq!(
+            script.tool_outputs.get("call-1"),
+            Some(&ResponseInputItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload::from_text("ok".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn recognizes_patches() {
+        let patch = "*** Begin Patch\n*** Add File: a.txt\n+hi\n*** End Patch";
+        let call = recorded_tool_call(
+            0,
+            &function_call(
+                "apply_patch",
+                &serde_json::json!({ "input": patch }).to_string(),
+                "call-2",
+            ),
+        );
+
+        assert_eq!(
+            call.map(|call| call.kind),
+            Some(RecordedToolCallKind::Patch(patch.to_string()))
+        );
+    }
+}
@@ -0,0 +1,329 @@
+//! A local stand-in for the Responses API that streams recorded model
+//! responses back in order.
+//!
+//! Only the parts of HTTP/1.1 that Codex's client uses are supported: one
+//! `POST .../responses` request per connection with a `Content-Length` body,
+//! answered with a server-sent event stream and `Connection: close`.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ResponseItem;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+
+pub(crate) struct ReplayServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<ServerState>>,
+    accept_task: JoinHandle<()>,
+}
+
+#[derive(Debug, Default)]
+struct ServerState {
+    /// Responses left to serve in the current turn.
+    responses: VecDeque<Vec<ResponseItem>>,
+    /// Requests in the current turn that found no recorded response left.
+    extra_requests: usize,
+    /// Total number of requests served, used for response ids.
+    served: usize,
+    /// Tool outputs Codex sent back to the model, keyed by call id.
+    tool_outputs: HashMap<String, FunctionCallOutputPayload>,
+}
+
+/// How the requests of a turn lined up with the recorded responses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TurnRequests {
+    /// Recorded responses that were never requested.
+    pub(crate) unused_responses: usize,
+    /// Requests made after every recorded response had been served.
+    pub(crate) extra_requests: usize,
+}
+
+impl ReplayServer {
+    pub(crate) async fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(ServerState::default()));
+        let accept_state = Arc::clone(&state);
+        let accept_task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = Arc::clone(&accept_state);
+                tokio::spawn(async move {
+                    if let Err(err) = handle_connection(stream, state).await {
+                        tracing::debug!("replay server connection failed: {err}");
+                    }
+                });
+            }
+        });
+        Ok(Self {
+            addr,
+            state,
+            accept_task,
+        })
+    }
+
+    /// Base URL for a Responses API provider served by this server.
+    pub(crate) fn base_url(&self) -> String {
+        format!("http://{}/v1", self.addr)
+    }
+
+    /// Queue the recorded responses of the next turn.
+    pub(crate) fn begin_turn(&self, responses: Vec<Vec<ResponseItem>>) {
+        let mut state = lock(&self.state);
+        state.responses = responses.into();
+        state.extra_requests = 0;
+    }
+
+    pub(crate) fn end_turn(&self) -> TurnRequests {
+        let mut state = lock(&self.state);
+        let unused_responses = state.responses.len();
+        state.responses.clear();
+        TurnRequests {
+            unused_responses,
+            extra_requests: std::mem::take(&mut state.extra_requests),
+        }
+    }
+
+    /// Tool outputs Codex sent to the model so far, keyed by call id.
+    pub(crate) fn tool_outputs(&self) -> HashMap<String, FunctionCallOutputPayload> {
+        lock(&self.state).tool_outputs.clone()
+    }
+}
+
+impl Drop for ReplayServer {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+    }
+}
+
+fn lock(state: &Mutex<ServerState>) -> std::sync::MutexGuard<'_, ServerState> {
+    state
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+impl ServerState {
+    fn respond(&mut self, body: &[u8]) -> String {
+        if let Ok(request) = serde_json::from_slice::<JsonValue>(body) {
+            for item in request
+                .get("input")
+                .and_then(JsonValue::as_array)
+                .into_iter()
+                .flatten()
+            {
+                if let Ok(
+                    ResponseItem::FunctionCallOutput { call_id, output }
+                    | ResponseItem::CustomToolCallOutput { call_id, output },
+                ) = serde_json::from_value::<ResponseItem>(item.clone())
+                {
+                    self.tool_outputs.insert(call_id, output);
+                }
+            }
+        }
+
+        let items = match self.responses.pop_front() {
+            Some(items) => items,
+            None => {
+                self.extra_requests += 1;
+                Vec::new()
+            }
+        };
+        self.served += 1;
+        let response_id = format!("resp_replay_{}", self.served);
+        let mut events = vec![json!({
+            "type": "response.created",
+            "response": { "id": response_id },
+        })];
+        for item in items {
+            if let Ok(item) = serde_json::to_value(item) {
+                events.push(json!({ "type": "response.output_item.done", "item": item }));
+            }
+        }
+        events.push(json!({
+            "type": "response.completed",
+            "response": {
+                "id": response_id,
+                "usage": {
+                    "input_tokens": 0,
+                    "input_tokens_details": null,
+                    "output_tokens": 0,
+                    "output_tokens_details": null,
+                    "total_tokens": 0,
+                },
+            },
+        }));
+
+        let mut sse = String::new();
+        for event in events {
+            let kind = event
+                .get("type")
+                .and_then(JsonValue::as_str)
+                .unwrap_or_default();
+            sse.push_str(&format!("event: {kind}\ndata: {event}\n\n"));
+        }
+        sse
+    }
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    state: Arc<Mutex<ServerState>>,
+) -> io::Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..read]);
+        if let Some(pos) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            break pos;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).into_owned();
+    let mut lines = head.lines();
+    let request_line = lines.next().unwrap_or_default();
+    let content_length = lines
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = buf[header_end + 4..].to_vec();
+    while body.len() < content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+    let response = if method == "POST" && path.ends_with("/responses") {
+        let sse = lock(&state).respond(&body);
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\ncache-control: no-cache\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{sse}",
+            sse.len()
+        )
+    } else {
+        "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n".to_string()
+    };
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use pretty_assertions::assert_eq;
+
+    async fn post(server: &ReplayServer, body: &str) -> String {
+        let mut stream = TcpStream::connect(server.addr).await.unwrap();
+        let request = format!(
+            "POST /v1/responses HTTP/1.1\r\nhost: localhost\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    fn data_events(response: &str) -> Vec<JsonValue> {
+        response
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .map(|data| serde_json::from_str(data).unwrap())
+            .collect()
+    }
+
+    fn completed_event(id: &str) -> JsonValue {
+        json!({
+            "type": "response.completed",
+            "response": {
+                "id": id,
+                "usage": {
+                    "input_tokens": 0,
+                    "input_tokens_details": null,
+                    "output_tokens": 0,
+                    "output_tokens_details": null,
+                    "total_tokens": 0,
+                },
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn serves_recorded_responses_and_captures_tool_outputs() {
+        let server = ReplayServer::start().await.unwrap();
+        server.begin_turn(vec![vec![ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText {
+                text: "done".to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }]]);
+
+        let first = post(
+            &server,
+            r#"{"input":[{"type":"function_call_output","call_id":"call-1","output":"ok"}]}"#,
+        )
+        .await;
+        let second = post(&server, r#"{"input":[]}"#).await;
+
+        assert!(first.starts_with("HTTP/1.1 200 OK"));
+        assert_eq!(
+            data_events(&first),
+            vec![
+                json!({"type": "response.created", "response": {"id": "resp_replay_1"}}),
+                json!({
+                    "type": "response.output_item.done",
+                    "item": {
+                        "type": "message",
+                        "role": "assistant",
+                        "content": [{"type": "output_text", "text": "done"}],
+                    },
+                }),
+                completed_event("resp_replay_1"),
+            ]
+        );
+        assert_eq!(
+            data_events(&second),
+            vec![
+                json!({"type": "response.created", "response": {"id": "resp_replay_2"}}),
+                completed_event("resp_replay_2"),
+            ]
+        );
+        assert_eq!(
+            server.end_turn(),
+            TurnRequests {
+                unused_responses: 0,
+                extra_requests: 1,
+            }
+        );
+        assert_eq!(
+            server.tool_outputs(),
+            HashMap::from([(
+                "call-1".to_string(),
+                FunctionCallOutputPayload::from_text("ok".to_string())
+            )])
+        );
+    }
+}
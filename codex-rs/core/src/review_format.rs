@@ -1,3 +1,9 @@
+use std::path::Path;
+
+use serde_json::Value;
+use serde_json::json;
+use url::Url;
+
 use crate::protocol::ReviewFinding;
 use crate::protocol::ReviewOutputEvent;
 
@@ -80,3 +86,366 @@ pub fn render_review_output_text(output: &ReviewOutputEvent) -> String {
         sections.join("\n\n")
     }
 }
+
+/// Version of the JSON document produced by [`render_review_output_json`].
+/// Bump when fields are renamed or removed.
+pub const REVIEW_JSON_VERSION: u32 = 1;
+
+/// Lowest priority value a finding can carry (P0 is the most severe).
+const LOWEST_PRIORITY: i32 = 3;
+
+/// Exit code offset used by [`review_exit_code`]; a P0 finding exits with 10.
+const REVIEW_EXIT_CODE_BASE: i32 = 10;
+
+/// Render review findings as a stable JSON document.
+///
+/// File paths under `root` are reported relative to it so the output can be
+/// consumed on other machines; other paths are reported as-is.
+pub fn render_review_output_json(output: &ReviewOutputEvent, root: &Path) -> Value {
+    let findings = output
+        .findings
+        .iter()
+        .map(|finding| {
+            json!({
+                "title": finding.title,
+                "body": finding.body,
+                "priority": clamp_priority(finding.priority),
+                "confidence_score": finding.confidence_score,
+                "file": display_path(&finding.code_location.absolute_file_path, root),
+                "line_range": {
+                    "start": finding.code_location.line_range.start,
+                    "end": finding.code_location.line_range.end,
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "version": REVIEW_JSON_VERSION,
+        "overall_correctness": output.overall_correctness,
+        "overall_explanation": output.overall_explanation,
+        "overall_confidence_score": output.overall_confidence_score,
+        "findings": findings,
+    })
+}
+
+/// Render review findings as a SARIF 2.1.0 log with one rule per priority.
+///
+/// P0 and P1 findings are reported at level `error`, P2 at `warning` and P3
+/// at `note`. Locations under `root` are relative to `%SRCROOT%`.
+pub fn render_review_output_sarif(output: &ReviewOutputEvent, root: &Path) -> Value {
+    let rules = (0..=LOWEST_PRIORITY)
+        .map(|priority| {
+            json!({
+                "id": sarif_rule_id(priority),
+                "name": format!("ReviewFindingP{priority}"),
+                "shortDescription": { "text": format!("P{priority} review finding") },
+                "defaultConfiguration": { "level": sarif_level(priority) },
+            })
+        })
+        .collect::<Vec<_>>();
+    let results = output
+        .findings
+        .iter()
+        .map(|finding| {
+            let priority = clamp_priority(finding.priority);
+            let start = finding.code_location.line_range.start.max(1);
+            let end = finding.code_location.line_range.end.max(start);
+            json!({
+                "ruleId": sarif_rule_id(priority),
+                "ruleIndex": priority,
+                "level": sarif_level(priority),
+                "message": { "text": finding_message(finding) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": sarif_artifact_location(
+                            &finding.code_location.absolute_file_path,
+                            root,
+                        ),
+                        "region": { "startLine": start, "endLine": end },
+                    },
+                }],
+                "properties": {
+                    "priority": priority,
+                    "confidence": finding.confidence_score,
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "codex",
+                    "informationUri": "https://github.com/openai/codex",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "originalUriBaseIds": {
+                "%SRCROOT%": { "uri": file_uri_for_dir(root) },
+            },
+            "results": results,
+            "properties": {
+                "overall_correctness": output.overall_correctness,
+                "overall_explanation": output.overall_explanation,
+                "overall_confidence_score": output.overall_confidence_score,
+            },
+        }],
+    })
+}
+
+/// Render review findings as a Markdown report, most severe findings first.
+pub fn render_review_output_markdown(output: &ReviewOutputEvent, root: &Path) -> String {
+    let mut lines = vec!["# Code review".to_string(), String::new()];
+    let explanation = output.overall_explanation.trim();
+    if !explanation.is_empty() {
+        lines.push(explanation.to_string());
+        lines.push(String::new());
+    }
+    let correctness = output.overall_correctness.trim();
+    if !correctness.is_empty() {
+        lines.push(format!(
+            "**Overall:** {correctness} (confidence {:.2})",
+            output.overall_confidence_score
+        ));
+        lines.push(String::new());
+    }
+
+    if output.findings.is_empty() {
+        lines.push("No findings.".to_string());
+        return lines.join("\n");
+    }
+
+    let mut findings = output.findings.iter().collect::<Vec<_>>();
+    findings.sort_by_key(|finding| clamp_priority(finding.priority));
+    lines.push("## Findings".to_string());
+    for finding in findings {
+        let location = &finding.code_location;
+        lines.push(String::new());
+        lines.push(format!(
+            "### [P{}] {}",
+            clamp_priority(finding.priority),
+            finding.title.trim()
+        ));
+        lines.push(String::new());
+        lines.push(format!(
+            "`{}:{}-{}` · confidence {:.2}",
+            display_path(&location.absolute_file_path, root),
+            location.line_range.start,
+            location.line_range.end,
+            finding.confidence_score
+        ));
+        let body = finding.body.trim();
+        if !body.is_empty() {
+            lines.push(String::new());
+            lines.push(body.to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+/// Exit code reflecting the most severe finding: 0 when there are no
+/// findings, otherwise 10 for P0, 11 for P1, 12 for P2 and 13 for P3.
+pub fn review_exit_code(findings: &[ReviewFinding]) -> i32 {
+    findings
+        .iter()
+        .map(|finding| clamp_priority(finding.priority))
+        .min()
+        .map_or(0, |priority| REVIEW_EXIT_CODE_BASE + priority)
+}
+
+fn clamp_priority(priority: i32) -> i32 {
+    priority.clamp(0, LOWEST_PRIORITY)
+}
+
+fn sarif_rule_id(priority: i32) -> String {
+    format!("codex-review/p{priority}")
+}
+
+fn sarif_level(priority: i32) -> &'static str {
+    match priority {
+        0 | 1 => "error",
+        2 => "warning",
+        _ => "note",
+    }
+}
+
+fn finding_message(finding: &ReviewFinding) -> String {
+    let title = finding.title.trim();
+    let body = finding.body.trim();
+    if body.is_empty() {
+        title.to_string()
+    } else {
+        format!("{title}\n\n{body}")
+    }
+}
+
+fn display_path(path: &Path, root: &Path) -> String {
+    match path.strip_prefix(root) {
+        Ok(relative) => relative_uri(relative),
+        Err(_) => path.display().to_string(),
+    }
+}
+
+fn sarif_artifact_location(path: &Path, root: &Path) -> Value {
+    if path.starts_with(root)
+        && let Ok(base) = Url::from_directory_path(root)
+        && let Ok(file) = Url::from_file_path(path)
+        && let Some(relative) = base.make_relative(&file)
+    {
+        return json!({ "uri": relative, "uriBaseId": "%SRCROOT%" });
+    }
+    json!({ "uri": file_uri(path) })
+}
+
+fn relative_uri(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Percent-encoded `file://` URI for an absolute path. Relative paths, which
+/// have no file URI, fall back to their `/`-joined components.
+fn file_uri(path: &Path) -> String {
+    Url::from_file_path(path)
+        .map(String::from)
+        .unwrap_or_else(|()| relative_uri(path))
+}
+
+fn file_uri_for_dir(path: &Path) -> String {
+    Url::from_directory_path(path)
+        .map(String::from)
+        .unwrap_or_else(|()| format!("{}/", relative_uri(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ReviewCodeLocation;
+    use crate::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(title: &str, priority: i32, path: &str, start: u32, end: u32) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_string(),
+            body: format!("Details for {title}."),
+            confidence_score: 0.5,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from(path),
+                line_range: ReviewLineRange { start, end },
+            },
+        }
+    }
+
+    fn output() -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings: vec![
+                finding("Unchecked index", 2, "/repo/src/api/handler.rs", 10, 12),
+                finding("Leaked token", 0, "/elsewhere/secret.rs", 0, 0),
+            ],
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "Two issues.".to_string(),
+            overall_confidence_score: 0.8,
+        }
+    }
+
+    #[test]
+    fn json_output_uses_paths_relative_to_root() {
+        let value = render_review_output_json(&output(), Path::new("/repo"));
+        assert_eq!(value["version"], json!(REVIEW_JSON_VERSION));
+        assert_eq!(
+            value["findings"][0],
+            json!({
+                "title": "Unchecked index",
+                "body": "Details for Unchecked index.",
+                "priority": 2,
+                "confidence_score": 0.5,
+                "file": "src/api/handler.rs",
+                "line_range": { "start": 10, "end": 12 },
+            })
+        );
+        assert_eq!(value["findings"][1]["file"], json!("/elsewhere/secret.rs"));
+    }
+
+    #[test]
+    fn sarif_output_maps_priority_to_level_and_clamps_lines() {
+        let value = render_review_output_sarif(&output(), Path::new("/repo"));
+        assert_eq!(value["version"], json!("2.1.0"));
+        let run = &value["runs"][0];
+        assert_eq!(
+            run["originalUriBaseIds"]["%SRCROOT%"]["uri"],
+            json!("file:///repo/")
+        );
+        assert_eq!(
+            run["results"][0]["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "src/api/handler.rs", "uriBaseId": "%SRCROOT%" },
+                "region": { "startLine": 10, "endLine": 12 },
+            })
+        );
+        assert_eq!(run["results"][0]["level"], json!("warning"));
+        assert_eq!(run["results"][1]["ruleId"], json!("codex-review/p0"));
+        assert_eq!(run["results"][1]["level"], json!("error"));
+        assert_eq!(
+            run["results"][1]["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "file:///elsewhere/secret.rs" },
+                "region": { "startLine": 1, "endLine": 1 },
+            })
+        );
+    }
+
+    #[test]
+    fn sarif_uris_are_percent_encoded() {
+        let output = ReviewOutputEvent {
+            findings: vec![
+                finding("Inside", 1, "/my repo/src/a #1.rs", 1, 1),
+                finding("Outside", 1, "/tmp/100%/b.rs", 1, 1),
+            ],
+            ..output()
+        };
+        let value = render_review_output_sarif(&output, Path::new("/my repo"));
+        let run = &value["runs"][0];
+        assert_eq!(
+            run["originalUriBaseIds"]["%SRCROOT%"]["uri"],
+            json!("file:///my%20repo/")
+        );
+        assert_eq!(
+            run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"],
+            json!({ "uri": "src/a%20%231.rs", "uriBaseId": "%SRCROOT%" })
+        );
+        assert_eq!(
+            run["results"][1]["locations"][0]["physicalLocation"]["artifactLocation"],
+            json!({ "uri": "file:///tmp/100%25/b.rs" })
+        );
+    }
+
+    #[test]
+    fn markdown_output_lists_most_severe_first() {
+        let markdown = render_review_output_markdown(&output(), Path::new("/repo"));
+        let leaked = markdown.find("### [P0] Leaked token").expect("P0 heading");
+        let unchecked = markdown
+            .find("### [P2] Unchecked index")
+            .expect("P2 heading");
+        assert!(leaked < unchecked, "{markdown}");
+        assert!(
+            markdown.contains("`src/api/handler.rs:10-12`"),
+            "{markdown}"
+        );
+    }
+
+    #[test]
+    fn exit_code_reflects_highest_priority_finding() {
+        assert_eq!(review_exit_code(&[]), 0);
+        assert_eq!(review_exit_code(&output().findings), 10);
+        assert_eq!(
+            review_exit_code(&[finding("Nit", 7, "/repo/a.rs", 1, 1)]),
+            13
+        );
+    }
+}
@@ -8,6 +8,7 @@ pub struct ResolvedReviewRequest {
     pub target: ReviewTarget,
     pub prompt: String,
     pub user_facing_hint: String,
+    pub paths: Vec<String>,
 }
 
 const UNCOMMITTED_PROMPT: &str = "Review the current code changes (staged, unstaged, and untracked files) and provide prioritized findings.";
@@ -19,12 +20,37 @@ const COMMIT_PROMPT_WITH_TITLE: &str = "Review the code changes introduced by co
 const COMMIT_PROMPT: &str =
     "Review the code changes introduced by commit {sha}. Provide prioritized, actionable findings.";
 
+const COMMIT_RANGE_PROMPT: &str = "Review the code changes introduced by the commits in {from}..{to}. Run `git log {from}..{to}` to list the commits and `git diff {from}...{to}` to inspect their combined changes. Provide prioritized, actionable findings.";
+
+const PATCH_FILE_PROMPT: &str = "Review the code changes in the unified diff at {path}. Read the patch file, and use the repository for surrounding context; the patch may not be applied to the working tree. Provide prioritized, actionable findings.";
+
+const PATHS_PROMPT: &str = "Only review changes to files matching these paths: {paths}. Pass them as git pathspecs when inspecting changes (for example `git diff <args> -- {pathspecs}`) and ignore changes to other files.";
+
 pub fn resolve_review_request(
     request: ReviewRequest,
     cwd: &Path,
 ) -> anyhow::Result<ResolvedReviewRequest> {
-    let target = request.target;
-    let prompt = review_prompt(&target, cwd)?;
+    let target = match request.target {
+        ReviewTarget::PatchFile { path } => {
+            let path = cwd.join(path);
+            if !path.is_file() {
+                anyhow::bail!("Patch file not found: {}", path.display());
+            }
+            ReviewTarget::PatchFile { path }
+        }
+        target => target,
+    };
+    let paths: Vec<String> = request
+        .paths
+        .iter()
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .collect();
+    let mut prompt = review_prompt(&target, cwd)?;
+    if !paths.is_empty() {
+        prompt.push_str("\n\n");
+        prompt.push_str(&paths_prompt(&paths));
+    }
     let user_facing_hint = request
         .user_facing_hint
         .unwrap_or_else(|| user_facing_hint(&target));
@@ -33,9 +59,26 @@ pub fn resolve_review_request(
         target,
         prompt,
         user_facing_hint,
+        paths,
     })
 }
 
+fn paths_prompt(paths: &[String]) -> String {
+    let listed = paths
+        .iter()
+        .map(|path| format!("`{path}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let pathspecs = paths
+        .iter()
+        .map(|path| format!("':(glob){path}'"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    PATHS_PROMPT
+        .replace("{paths}", &listed)
+        .replace("{pathspecs}", &pathspecs)
+}
+
 pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String> {
     match target {
         ReviewTarget::UncommittedChanges => Ok(UNCOMMITTED_PROMPT.to_string()),
@@ -57,6 +100,17 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String
                 Ok(COMMIT_PROMPT.replace("{sha}", sha))
             }
         }
+        ReviewTarget::CommitRange { from, to } => {
+            if from.trim().is_empty() || to.trim().is_empty() {
+                anyhow::bail!("Commit range must name both ends");
+            }
+            Ok(COMMIT_RANGE_PROMPT
+                .replace("{from}", from)
+                .replace("{to}", to))
+        }
+        ReviewTarget::PatchFile { path } => {
+            Ok(PATCH_FILE_PROMPT.replace("{path}", &path.display().to_string()))
+        }
         ReviewTarget::Custom { instructions } => {
             let prompt = instructions.trim();
             if prompt.is_empty() {
@@ -79,6 +133,14 @@ pub fn user_facing_hint(target: &ReviewTarget) -> String {
                 format!("commit {short_sha}")
             }
         }
+        ReviewTarget::CommitRange { from, to } => format!("commits {from}..{to}"),
+        ReviewTarget::PatchFile { path } => {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string());
+            format!("patch {name}")
+        }
         ReviewTarget::Custom { instructions } => instructions.trim().to_string(),
     }
 }
@@ -88,6 +150,67 @@ impl From<ResolvedReviewRequest> for ReviewRequest {
         ReviewRequest {
             target: resolved.target,
             user_facing_hint: Some(resolved.user_facing_hint),
+            paths: resolved.paths,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn commit_range_with_paths_limits_review() {
+        let cwd = tempfile::tempdir().expect("tempdir");
+        let resolved = resolve_review_request(
+            ReviewRequest {
+                target: ReviewTarget::CommitRange {
+                    from: "main".to_string(),
+                    to: "HEAD".to_string(),
+                },
+                user_facing_hint: None,
+                paths: vec!["src/api/**".to_string(), "  ".to_string()],
+            },
+            cwd.path(),
+        )
+        .expect("resolve");
+
+        assert_eq!(resolved.user_facing_hint, "commits main..HEAD");
+        assert_eq!(resolved.paths, vec!["src/api/**".to_string()]);
+        assert_eq!(
+            resolved.prompt,
+            format!(
+                "{}\n\n{}",
+                COMMIT_RANGE_PROMPT
+                    .replace("{from}", "main")
+                    .replace("{to}", "HEAD"),
+                "Only review changes to files matching these paths: `src/api/**`. Pass them as git pathspecs when inspecting changes (for example `git diff <args> -- ':(glob)src/api/**'`) and ignore changes to other files."
+            )
+        );
+    }
+
+    #[test]
+    fn patch_file_is_resolved_against_cwd() {
+        let cwd = tempfile::tempdir().expect("tempdir");
+        std::fs::write(cwd.path().join("change.diff"), "--- a/x\n+++ b/x\n").expect("write");
+        let request = |path: &str| ReviewRequest {
+            target: ReviewTarget::PatchFile { path: path.into() },
+            user_facing_hint: None,
+            paths: Vec::new(),
+        };
+
+        let resolved = resolve_review_request(request("change.diff"), cwd.path()).expect("resolve");
+        assert_eq!(
+            resolved.target,
+            ReviewTarget::PatchFile {
+                path: cwd.path().join("change.diff"),
+            }
+        );
+        assert_eq!(resolved.user_facing_hint, "patch change.diff");
+
+        let err = resolve_review_request(request("missing.diff"), cwd.path())
+            .expect_err("missing patch file");
+        assert!(err.to_string().contains("Patch file not found"), "{err}");
+    }
+}
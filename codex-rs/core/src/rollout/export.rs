@@ -0,0 +1,1065 @@
+//! Render a recorded rollout as a self-contained HTML or Markdown transcript.
+//!
+//! The transcript covers user and assistant messages, command executions with
+//! their output, `apply_patch` diffs, plans, sub-agent threads and the final
+//! token usage. Reasoning, context injected by Codex and other bookkeeping
+//! recorded in the rollout are left out.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use codex_protocol::items::AgentMessageContent;
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::ShellCommandToolCallParams;
+use codex_protocol::models::ShellToolCallParams;
+use codex_protocol::plan_tool::PlanItemArg;
+use codex_protocol::plan_tool::StepStatus;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::TokenUsage;
+use codex_shell_command::parse_command::extract_shell_command;
+use codex_shell_command::parse_command::shlex_join;
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::event_mapping::parse_turn_item;
+use crate::rollout::list::find_thread_path_by_id_str;
+use crate::rollout::recorder::RolloutRecorder;
+
+/// Sub-agents spawned deeper than this are listed but their threads are not
+/// included.
+const MAX_SUB_AGENT_DEPTH: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadExportFormat {
+    Html,
+    Markdown,
+}
+
+/// A rollout loaded for export, together with the rollouts of the sub-agents
+/// it spawned.
+#[derive(Debug, Clone, Default)]
+pub struct ThreadExport {
+    pub items: Vec<RolloutItem>,
+    /// Sub-agent threads keyed by the agent id returned from `spawn_agent`.
+    pub sub_agents: HashMap<String, ThreadExport>,
+}
+
+impl ThreadExport {
+    /// Load the rollout at `rollout_path` and the rollouts of the sub-agents
+    /// it spawned that can still be found under `codex_home`.
+    pub async fn load(codex_home: &Path, rollout_path: &Path) -> io::Result<Self> {
+        Self::load_at_depth(codex_home.to_path_buf(), rollout_path.to_path_buf(), 0).await
+    }
+
+    fn load_at_depth(
+        codex_home: PathBuf,
+        rollout_path: PathBuf,
+        depth: usize,
+    ) -> Pin<Box<dyn Future<Output = io::Result<Self>> + Send>> {
+        Box::pin(async move {
+            let (items, _, _) = RolloutRecorder::load_rollout_items(&rollout_path).await?;
+            let mut sub_agents = HashMap::new();
+            if depth < MAX_SUB_AGENT_DEPTH {
+                for agent_id in spawned_agent_ids(&items) {
+                    let path = match find_thread_path_by_id_str(&codex_home, &agent_id).await {
+                        Ok(Some(path)) => path,
+                        Ok(None) => continue,
+                        Err(err) => {
+                            warn!("failed to locate rollout for sub-agent {agent_id}: {err}");
+                            continue;
+                        }
+                    };
+                    match Self::load_at_depth(codex_home.clone(), path, depth + 1).await {
+                        Ok(thread) => {
+                            sub_agents.insert(agent_id, thread);
+                        }
+                        Err(err) => {
+                            warn!("failed to load rollout for sub-agent {agent_id}: {err}");
+                        }
+                    }
+                }
+            }
+            Ok(Self { items, sub_agents })
+        })
+    }
+
+    pub fn render(&self, format: ThreadExportFormat) -> String {
+        let transcript = Transcript::from_thread(self);
+        match format {
+            ThreadExportFormat::Html => render_html(&transcript),
+            ThreadExportFormat::Markdown => render_markdown(&transcript),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Transcript {
+    thread_id: Option<String>,
+    started_at: Option<String>,
+    cwd: Option<String>,
+    model: Option<String>,
+    git_branch: Option<String>,
+    agent_name: Option<String>,
+    entries: Vec<Entry>,
+    token_usage: Option<TokenUsage>,
+}
+
+#[derive(Debug)]
+enum Entry {
+    User(String),
+    Assistant(String),
+    Command {
+        command: String,
+        output: Option<String>,
+    },
+    Patch {
+        patch: String,
+        output: Option<String>,
+    },
+    Plan {
+        explanation: Option<String>,
+        steps: Vec<PlanItemArg>,
+    },
+    ProposedPlan(String),
+    ToolCall {
+        name: String,
+        input: String,
+        output: Option<String>,
+    },
+    SubAgent {
+        prompt: Option<String>,
+        agent_id: Option<String>,
+        output: Option<String>,
+        thread: Option<Box<Transcript>>,
+    },
+    Compacted,
+}
+
+#[derive(Deserialize)]
+struct ApplyPatchArgs {
+    input: String,
+}
+
+#[derive(Deserialize)]
+struct ExecCommandArgs {
+    cmd: String,
+}
+
+#[derive(Deserialize)]
+struct SpawnAgentArgs {
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SpawnAgentResult {
+    agent_id: String,
+}
+
+impl Transcript {
+    fn from_thread(thread: &ThreadExport) -> Self {
+        let mut transcript = Self::default();
+        // Tool outputs arrive after their calls; remember where each call
+        // landed so the output can be attached to it.
+        let mut calls: HashMap<String, usize> = HashMap::new();
+        for item in &thread.items {
+            match item {
+                RolloutItem::SessionMeta(meta_line) => {
+                    if transcript.thread_id.is_none() {
+                        let meta = &meta_line.meta;
+                        transcript.thread_id = Some(meta.id.to_string());
+                        transcript.started_at = Some(meta.timestamp.clone());
+                        transcript.cwd = Some(meta.cwd.display().to_string());
+                        transcript.agent_name = meta
+                            .agent_nickname
+                            .clone()
+                            .or_else(|| meta.agent_role.clone());
+                        transcript.git_branch =
+                            meta_line.git.as_ref().and_then(|git| git.branch.clone());
+                    }
+                }
+                RolloutItem::TurnContext(turn_context) => {
+                    transcript.model = Some(turn_context.model.clone());
+                }
+                RolloutItem::Compacted(_) => transcript.entries.push(Entry::Compacted),
+                RolloutItem::EventMsg(EventMsg::TokenCount(event)) => {
+                    if let Some(info) = &event.info {
+                        transcript.token_usage = Some(info.total_token_usage.clone());
+                    }
+                }
+                RolloutItem::EventMsg(EventMsg::ItemCompleted(event)) => {
+                    if let TurnItem::Plan(plan) = &event.item {
+                        transcript
+                            .entries
+                            .push(Entry::ProposedPlan(plan.text.clone()));
+                    }
+                }
+                RolloutItem::EventMsg(_) => {}
+                RolloutItem::ResponseItem(item) => {
+                    transcript.push_response_item(item, &mut calls);
+                }
+            }
+        }
+
+        for entry in &mut transcript.entries {
+            if let Entry::SubAgent {
+                agent_id: Some(agent_id),
+                thread: nested,
+                ..
+            } = entry
+            {
+                *nested = thread_for_agent(&thread.sub_agents, agent_id);
+            }
+        }
+        transcript
+    }
+
+    fn push_response_item(&mut self, item: &ResponseItem, calls: &mut HashMap<String, usize>) {
+        match item {
+            ResponseItem::Message { .. } => match parse_turn_item(item) {
+                Some(TurnItem::UserMessage(message)) => {
+                    let mut text = message.message();
+                    let images = message.image_urls().len() + message.local_image_paths().len();
+                    if images > 0 {
+                        if !text.is_empty() {
+                            text.push_str("\n\n");
+                        }
+                        let _ = write!(text, "[{images} image(s) attached]");
+                    }
+                    if !text.is_empty() {
+                        self.entries.push(Entry::User(text));
+                    }
+                }
+                Some(TurnItem::AgentMessage(message)) => {
+                    let text = message
+                        .content
+                        .iter()
+                        .map(|content| match content {
+                            AgentMessageContent::Text { text } => text.as_str(),
+                        })
+                        .collect::<String>();
+                    if !text.is_empty() {
+                        self.entries.push(Entry::Assistant(text));
+                    }
+                }
+                _ => {}
+            },
+            ResponseItem::LocalShellCall {
+                call_id, action, ..
+            } => {
+                let LocalShellAction::Exec(exec) = action;
+                let entry = Entry::Command {
+                    command: display_command(&exec.command),
+                    output: None,
+                };
+                self.push_call(call_id.as_deref(), entry, calls);
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                let entry = function_call_entry(name, arguments);
+                self.push_call(Some(call_id), entry, calls);
+            }
+            ResponseItem::CustomToolCall {
+                name,
+                input,
+                call_id,
+                ..
+            } => {
+                let entry = if name == "apply_patch" {
+                    Entry::Patch {
+                        patch: input.clone(),
+                        output: None,
+                    }
+                } else {
+                    Entry::ToolCall {
+                        name: name.clone(),
+                        input: input.clone(),
+                        output: None,
+                    }
+                };
+                self.push_call(Some(call_id), entry, calls);
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput { call_id, output } => {
+                if let Some(index) = calls.get(call_id) {
+                    attach_output(&mut self.entries[*index], output);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn push_call(
+        &mut self,
+        call_id: Option<&str>,
+        entry: Entry,
+        calls: &mut HashMap<String, usize>,
+    ) {
+        if let Some(call_id) = call_id {
+            calls.insert(call_id.to_string(), self.entries.len());
+        }
+        self.entries.push(entry);
+    }
+}
+
+fn thread_for_agent(
+    sub_agents: &HashMap<String, ThreadExport>,
+    agent_id: &str,
+) -> Option<Box<Transcript>> {
+    sub_agents
+        .get(agent_id)
+        .map(|thread| Box::new(Transcript::from_thread(thread)))
+}
+
+fn function_call_entry(name: &str, arguments: &str) -> Entry {
+    let command = match name {
+        "shell" | "container.exec" => serde_json::from_str::<ShellToolCallParams>(arguments)
+            .ok()
+            .map(|params| display_command(&params.command)),
+        "shell_command" => serde_json::from_str::<ShellCommandToolCallParams>(arguments)
+            .ok()
+            .map(|params| params.command),
+        "exec_command" => serde_json::from_str::<ExecCommandArgs>(arguments)
+            .ok()
+            .map(|args| args.cmd),
+        _ => None,
+    };
+    if let Some(command) = command {
+        return Entry::Command {
+            command,
+            output: None,
+        };
+    }
+
+    match name {
+        "apply_patch" => {
+            if let Ok(args) = serde_json::from_str::<ApplyPatchArgs>(arguments) {
+                return Entry::Patch {
+                    patch: args.input,
+                    output: None,
+                };
+            }
+        }
+        "update_plan" => {
+            if let Ok(args) = serde_json::from_str::<UpdatePlanArgs>(arguments) {
+                return Entry::Plan {
+                    explanation: args.explanation,
+                    steps: args.plan,
+                };
+            }
+        }
+        "spawn_agent" => {
+            let prompt = serde_json::from_str::<SpawnAgentArgs>(arguments)
+                .ok()
+                .and_then(|args| args.message);
+            return Entry::SubAgent {
+                prompt,
+                agent_id: None,
+                output: None,
+                thread: None,
+            };
+        }
+        _ => {}
+    }
+    Entry::ToolCall {
+        name: name.to_string(),
+        input: arguments.to_string(),
+        output: None,
+    }
+}
+
+fn attach_output(entry: &mut Entry, payload: &FunctionCallOutputPayload) {
+    let text = payload.body.to_text();
+    match entry {
+        Entry::SubAgent {
+            agent_id, output, ..
+        } => {
+            *agent_id = text
+                .as_deref()
+                .and_then(|text| serde_json::from_str::<SpawnAgentResult>(text).ok())
+                .map(|result| result.agent_id);
+            if agent_id.is_none() {
+                *output = text;
+            }
+        }
+        Entry::Command { output, .. }
+        | Entry::Patch { output, .. }
+        | Entry::ToolCall { output, .. } => *output = text,
+        Entry::User(_)
+        | Entry::Assistant(_)
+        | Entry::Plan { .. }
+        | Entry::ProposedPlan(_)
+        | Entry::Compacted => {}
+    }
+}
+
+/// Agent ids returned by the `spawn_agent` calls recorded in `items`.
+fn spawned_agent_ids(items: &[RolloutItem]) -> Vec<String> {
+    let mut spawn_calls = Vec::new();
+    let mut agent_ids = Vec::new();
+    for item in items {
+        match item {
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall { name, call_id, .. })
+                if name == "spawn_agent" =>
+            {
+                spawn_calls.push(call_id.as_str());
+            }
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput { call_id, output })
+                if spawn_calls.contains(&call_id.as_str()) =>
+            {
+                if let Some(result) = output
+                    .body
+                    .to_text()
+                    .and_then(|text| serde_json::from_str::<SpawnAgentResult>(&text).ok())
+                    && !agent_ids.contains(&result.agent_id)
+                {
+                    agent_ids.push(result.agent_id);
+                }
+            }
+            _ => {}
+        }
+    }
+    agent_ids
+}
+
+fn display_command(command: &[String]) -> String {
+    match extract_shell_command(command) {
+        Some((_, script)) => script.to_string(),
+        None => shlex_join(command),
+    }
+}
+
+fn step_marker(status: &StepStatus) -> &'static str {
+    match status {
+        StepStatus::Pending => "[ ]",
+        StepStatus::InProgress => "[~]",
+        StepStatus::Completed => "[x]",
+    }
+}
+
+fn token_usage_rows(usage: &TokenUsage) -> [(&'static str, i64); 5] {
+    [
+        ("Input", usage.input_tokens),
+        ("Cached input", usage.cached_input_tokens),
+        ("Output", usage.output_tokens),
+        ("Reasoning", usage.reasoning_output_tokens),
+        ("Total", usage.total_tokens),
+    ]
+}
+
+fn header_rows(transcript: &Transcript) -> Vec<(&'static str, &str)> {
+    [
+        ("Thread", transcript.thread_id.as_deref()),
+        ("Agent", transcript.agent_name.as_deref()),
+        ("Started", transcript.started_at.as_deref()),
+        ("Working directory", transcript.cwd.as_deref()),
+        ("Git branch", transcript.git_branch.as_deref()),
+        ("Model", transcript.model.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(label, value)| value.map(|value| (label, value)))
+    .collect()
+}
+
+const HTML_STYLE: &str = "\
+body { font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; margin: 0; background: #f6f8fa; color: #1f2328; }
+main { max-width: 960px; margin: 0 auto; padding: 24px; }
+dl { display: grid; grid-template-columns: max-content 1fr; gap: 4px 16px; }
+dt { font-weight: 600; }
+dd { margin: 0; }
+.entry { background: #fff; border: 1px solid #d0d7de; border-radius: 6px; margin: 12px 0; padding: 12px 16px; }
+.role { font-size: 12px; font-weight: 600; text-transform: uppercase; color: #59636e; margin-bottom: 6px; }
+.user { border-left: 4px solid #0969da; }
+.assistant { border-left: 4px solid #1a7f37; }
+.text { white-space: pre-wrap; overflow-wrap: anywhere; }
+summary { cursor: pointer; }
+pre { background: #f6f8fa; border-radius: 6px; padding: 8px 12px; overflow-x: auto; margin: 8px 0 0; }
+.add { color: #1a7f37; }
+.del { color: #cf222e; }
+.hunk { color: #8250df; }
+.plan ul { list-style: none; padding-left: 0; margin: 0; }
+.completed { text-decoration: line-through; color: #59636e; }
+.note { color: #59636e; font-style: italic; }
+table { border-collapse: collapse; }
+td { padding: 2px 16px 2px 0; }
+td.number { text-align: right; font-variant-numeric: tabular-nums; }
+@media (prefers-color-scheme: dark) {
+  body { background: #0d1117; color: #e6edf3; }
+  .entry { background: #161b22; border-color: #30363d; }
+  pre { background: #0d1117; }
+  .role, .note, .completed { color: #9198a1; }
+}
+";
+
+fn render_html(transcript: &Transcript) -> String {
+    let title = match &transcript.thread_id {
+        Some(thread_id) => format!("Codex session {thread_id}"),
+        None => "Codex session".to_string(),
+    };
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    let _ = writeln!(out, "<title>{}</title>", html_escape(&title));
+    let _ = writeln!(out, "<style>\n{HTML_STYLE}</style>");
+    out.push_str("</head>\n<body>\n<main>\n");
+    let _ = writeln!(out, "<h1>{}</h1>", html_escape(&title));
+    render_html_thread(&mut out, transcript);
+    out.push_str("</main>\n</body>\n</html>\n");
+    out
+}
+
+fn render_html_thread(out: &mut String, transcript: &Transcript) {
+    let rows = header_rows(transcript);
+    if !rows.is_empty() {
+        out.push_str("<dl>\n");
+        for (label, value) in rows {
+            let _ = writeln!(out, "<dt>{label}</dt><dd>{}</dd>", html_escape(value));
+        }
+        out.push_str("</dl>\n");
+    }
+
+    for entry in &transcript.entries {
+        match entry {
+            Entry::User(text) => render_html_message(out, "user", "User", text),
+            Entry::Assistant(text) => render_html_message(out, "assistant", "Assistant", text),
+            Entry::Command { command, output } => {
+                out.push_str("<details class=\"entry command\">\n");
+                let _ = writeln!(
+                    out,
+                    "<summary><code>$ {}</code></summary>",
+                    html_escape(command)
+                );
+                render_html_output(out, output.as_deref());
+                out.push_str("</details>\n");
+            }
+            Entry::Patch { patch, output } => {
+                out.push_str("<details class=\"entry patch\" open>\n<summary>Patch</summary>\n");
+                out.push_str("<pre class=\"diff\">");
+                for line in patch.lines() {
+                    match diff_line_class(line) {
+                        Some(class) => {
+                            let _ = writeln!(
+                                out,
+                                "<span class=\"{class}\">{}</span>",
+                                html_escape(line)
+                            );
+                        }
+                        None => {
+                            let _ = writeln!(out, "{}", html_escape(line));
+                        }
+                    }
+                }
+                out.push_str("</pre>\n");
+                render_html_output(out, output.as_deref());
+                out.push_str("</details>\n");
+            }
+            Entry::Plan { explanation, steps } => {
+                out.push_str("<section class=\"entry plan\">\n<div class=\"role\">Plan</div>\n");
+                if let Some(explanation) = explanation {
+                    let _ = writeln!(
+                        out,
+                        "<div class=\"text\">{}</div>",
+                        html_escape(explanation)
+                    );
+                }
+                out.push_str("<ul>\n");
+                for step in steps {
+                    let class = match step.status {
+                        StepStatus::Completed => " class=\"completed\"",
+                        StepStatus::Pending | StepStatus::InProgress => "",
+                    };
+                    let _ = writeln!(
+                        out,
+                        "<li{class}>{} {}</li>",
+                        step_marker(&step.status),
+                        html_escape(&step.step)
+                    );
+                }
+                out.push_str("</ul>\n</section>\n");
+            }
+            Entry::ProposedPlan(text) => render_html_message(out, "plan", "Proposed plan", text),
+            Entry::ToolCall {
+                name,
+                input,
+                output,
+            } => {
+                out.push_str("<details class=\"entry tool\">\n");
+                let _ = writeln!(
+                    out,
+                    "<summary>Tool call <code>{}</code></summary>",
+                    html_escape(name)
+                );
+                let _ = writeln!(out, "<pre>{}</pre>", html_escape(input));
+                render_html_output(out, output.as_deref());
+                out.push_str("</details>\n");
+            }
+            Entry::SubAgent {
+                prompt,
+                agent_id,
+                output,
+                thread,
+            } => {
+                out.push_str("<details class=\"entry sub-agent\">\n");
+                let label = match agent_id {
+                    Some(agent_id) => format!("Sub-agent {agent_id}"),
+                    None => "Sub-agent".to_string(),
+                };
+                let _ = writeln!(out, "<summary>{}</summary>", html_escape(&label));
+                if let Some(prompt) = prompt {
+                    let _ = writeln!(out, "<div class=\"text\">{}</div>", html_escape(prompt));
+                }
+                match thread {
+                    Some(thread) => render_html_thread(out, thread),
+                    None => render_html_output(out, output.as_deref()),
+                }
+                out.push_str("</details>\n");
+            }
+            Entry::Compacted => {
+                out.push_str("<p class=\"note\">Earlier context was compacted.</p>\n");
+            }
+        }
+    }
+
+    if let Some(usage) = &transcript.token_usage {
+        out.push_str("<section class=\"entry usage\">\n<div class=\"role\">Token usage</div>\n");
+        out.push_str("<table>\n");
+        for (label, value) in token_usage_rows(usage) {
+            let _ = writeln!(
+                out,
+                "<tr><td>{label}</td><td class=\"number\">{value}</td></tr>"
+            );
+        }
+        out.push_str("</table>\n</section>\n");
+    }
+}
+
+fn render_html_message(out: &mut String, class: &str, role: &str, text: &str) {
+    let _ = writeln!(
+        out,
+        "<section class=\"entry {class}\">\n<div class=\"role\">{role}</div>\n<div class=\"text\">{}</div>\n</section>",
+        html_escape(text)
+    );
+}
+
+fn render_html_output(out: &mut String, output: Option<&str>) {
+    if let Some(output) = output
+        && !output.is_empty()
+    {
+        let _ = writeln!(out, "<pre>{}</pre>", html_escape(output));
+    }
+}
+
+fn diff_line_class(line: &str) -> Option<&'static str> {
+    if line.starts_with("***") || line.starts_with("@@") {
+        Some("hunk")
+    } else if line.starts_with('+') {
+        Some("add")
+    } else if line.starts_with('-') {
+        Some("del")
+    } else {
+        None
+    }
+}
+
+fn html_escape(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for ch in input.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn render_markdown(transcript: &Transcript) -> String {
+    let mut out = String::new();
+    match &transcript.thread_id {
+        Some(thread_id) => {
+            let _ = writeln!(out, "# Codex session `{thread_id}`\n");
+        }
+        None => out.push_str("# Codex session\n\n"),
+    }
+    render_markdown_thread(&mut out, transcript, 2);
+    out
+}
+
+fn render_markdown_thread(out: &mut String, transcript: &Transcript, level: usize) {
+    let heading = "#".repeat(level.min(6));
+    let rows = header_rows(transcript);
+    if !rows.is_empty() {
+        for (label, value) in rows {
+            let _ = writeln!(out, "- {label}: {value}");
+        }
+        out.push('\n');
+    }
+
+    for entry in &transcript.entries {
+        match entry {
+            Entry::User(text) => {
+                let _ = writeln!(out, "{heading} User\n\n{text}\n");
+            }
+            Entry::Assistant(text) => {
+                let _ = writeln!(out, "{heading} Assistant\n\n{text}\n");
+            }
+            Entry::Command { command, output } => {
+                let summary = first_line(command);
+                let _ = writeln!(
+                    out,
+                    "<details>\n<summary>Command: <code>{}</code></summary>\n",
+                    html_escape(summary)
+                );
+                push_code_block(out, "sh", command);
+                if let Some(output) = output.as_deref()
+                    && !output.is_empty()
+                {
+                    push_code_block(out, "text", output);
+                }
+                out.push_str("</details>\n\n");
+            }
+            Entry::Patch { patch, output } => {
+                let _ = writeln!(out, "{heading} Patch\n");
+                push_code_block(out, "diff", patch);
+                if let Some(output) = output.as_deref()
+                    && !output.is_empty()
+                {
+                    push_code_block(out, "text", output);
+                }
+            }
+            Entry::Plan { explanation, steps } => {
+                let _ = writeln!(out, "{heading} Plan\n");
+                if let Some(explanation) = explanation {
+                    let _ = writeln!(out, "{explanation}\n");
+                }
+                for step in steps {
+                    let _ = writeln!(out, "- {} {}", step_marker(&step.status), step.step);
+                }
+                out.push('\n');
+            }
+            Entry::ProposedPlan(text) => {
+                let _ = writeln!(out, "{heading} Proposed plan\n\n{text}\n");
+            }
+            Entry::ToolCall {
+                name,
+                input,
+                output,
+            } => {
+                let _ = writeln!(
+                    out,
+                    "<details>\n<summary>Tool call: <code>{}</code></summary>\n",
+                    html_escape(name)
+                );
+                push_code_block(out, "json", input);
+                if let Some(output) = output.as_deref()
+                    && !output.is_empty()
+                {
+                    push_code_block(out, "text", output);
+                }
+                out.push_str("</details>\n\n");
+            }
+            Entry::SubAgent {
+                prompt,
+                agent_id,
+                output,
+                thread,
+            } => {
+                match agent_id {
+                    Some(agent_id) => {
+                        let _ = writeln!(out, "{heading} Sub-agent `{agent_id}`\n");
+                    }
+                    None => {
+                        let _ = writeln!(out, "{heading} Sub-agent\n");
+                    }
+                }
+                if let Some(prompt) = prompt {
+                    for line in prompt.lines() {
+                        let _ = writeln!(out, "> {line}");
+                    }
+                    out.push('\n');
+                }
+                match thread {
+                    Some(thread) => {
+                        out.push_str("<details>\n<summary>Sub-agent thread</summary>\n\n");
+                        render_markdown_thread(out, thread, level + 1);
+                        out.push_str("</details>\n\n");
+                    }
+                    None => {
+                        if let Some(output) = output.as_deref()
+                            && !output.is_empty()
+                        {
+                            push_code_block(out, "text", output);
+                        }
+                    }
+                }
+            }
+            Entry::Compacted => out.push_str("_Earlier context was compacted._\n\n"),
+        }
+    }
+
+    if let Some(usage) = &transcript.token_usage {
+        let _ = writeln!(out, "{heading} Token usage\n");
+        out.push_str("| Tokens | Count |\n| --- | ---: |\n");
+        for (label, value) in token_usage_rows(usage) {
+            let _ = writeln!(out, "| {label} | {value} |");
+        }
+        out.push('\n');
+    }
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default()
+}
+
+/// Write `text` as a fenced code block whose fence is longer than any run of
+/// backticks inside it.
+fn push_code_block(out: &mut String, language: &str, text: &str) {
+    let mut longest = 0;
+    let mut current = 0;
+    for ch in text.chars() {
+        if ch == '`' {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    let fence = "`".repeat((longest + 1).max(3));
+    let _ = writeln!(
+        out,
+        "{fence}{language}\n{}\n{fence}\n",
+        text.trim_end_matches('\n')
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+    use codex_protocol::models::ContentItem;
+    use codex_protocol::models::FunctionCallOutputBody;
+    use codex_protocol::protocol::SessionMeta;
+    use codex_protocol::protocol::SessionMetaLine;
+    use codex_protocol::protocol::TokenCountEvent;
+    use codex_protocol::protocol::TokenUsageInfo;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: &str, text: &str) -> RolloutItem {
+        let content = if role == "assistant" {
+            ContentItem::OutputText {
+                text: text.to_string(),
+            }
+        } else {
+            ContentItem::InputText {
+                text: text.to_string(),
+            }
+        };
+        RolloutItem::ResponseItem(ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![content],
+            end_turn: None,
+            phase: None,
+        })
+    }
+
+    fn function_call(name: &str, arguments: &str, call_id: &str) -> RolloutItem {
+        RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            call_id: call_id.to_string(),
+        })
+    }
+
+    fn function_call_output(call_id: &str, text: &str) -> RolloutItem {
+        RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload {
+                body: FunctionCallOutputBody::Text(text.to_string()),
+                success: Some(true),
+            },
+        })
+    }
+
+    fn token_count(total_tokens: i64) -> RolloutItem {
+        let usage = TokenUsage {
+            input_tokens: total_tokens - 10,
+            cached_input_tokens: 0,
+            output_tokens: 10,
+            reasoning_output_tokens: 0,
+            total_tokens,
+        };
+        RolloutItem::EventMsg(EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: usage.clone(),
+                last_token_usage: usage,
+                model_context_window: None,
+            }),
+            rate_limits: None,
+        }))
+    }
+
+    fn session_meta(thread_id: ThreadId) -> RolloutItem {
+        RolloutItem::SessionMeta(SessionMetaLine {
+            meta: SessionMeta {
+                id: thread_id,
+                timestamp: "2025-01-02T03:04:05Z".to_string(),
+                cwd: PathBuf::from("/repo"),
+                ..SessionMeta::default()
+            },
+            git: None,
+        })
+    }
+
+    #[test]
+    fn markdown_export_renders_messages_commands_patches_and_usage() {
+        let thread_id = ThreadId::new();
+        let thread = ThreadExport {
+            items: vec![
+                session_meta(thread_id),
+                message("user", "<environment_context>\n</environment_context>"),
+                message("user", "Fix the build"),
+                function_call(
+                    "shell_command",
+                    r#"{"command":"cargo build","workdir":null}"#,
+                    "call-1",
+                ),
+                function_call_output("call-1", "error[E0425]: cannot find value `x`"),
+                RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                    id: None,
+                    status: None,
+                    call_id: "call-2".to_string(),
+                    name: "apply_patch".to_string(),
+                    input: "*** Begin Patch\n*** Update File: src/lib.rs\n-x\n+1\n*** End Patch"
+                        .to_string(),
+                }),
+                function_call(
+                    "update_plan",
+                    r#"{"plan":[{"step":"Fix build","status":"completed"},{"step":"Run tests","status":"pending"}]}"#,
+                    "call-3",
+                ),
+                message("assistant", "Fixed."),
+                token_count(1200),
+            ],
+            sub_agents: HashMap::new(),
+        };
+
+        let expected = format!(
+            "\
+# Codex session `{thread_id}`
+
+- Thread: {thread_id}
+- Started: 2025-01-02T03:04:05Z
+- Working directory: /repo
+
+## User
+
+Fix the build
+
+<details>
+<summary>Command: <code>cargo build</code></summary>
+
+```sh
+cargo build
+```
+
+```text
+error[E0425]: cannot find value `x`
+```
+
+</details>
+
+## Patch
+
+```diff
+*** Begin Patch
+*** Update File: src/lib.rs
+-x
++1
+*** End Patch
+```
+
+## Plan
+
+- [x] Fix build
+- [ ] Run tests
+
+## Assistant
+
+Fixed.
+
+## Token usage
+
+| Tokens | Count |
+| --- | ---: |
+| Input | 1190 |
+| Cached input | 0 |
+| Output | 10 |
+| Reasoning | 0 |
+| Total | 1200 |
+
+"
+        );
+        assert_eq!(thread.render(ThreadExportFormat::Markdown), expected);
+    }
+
+    #[test]
+    fn html_export_escapes_content_and_nests_sub_agent_threads() {
+        let agent_id = ThreadId::new().to_string();
+        let sub_agent = ThreadExport {
+            items: vec![
+                message("user", "Look at <main>"),
+                message("assistant", "Done & dusted"),
+            ],
+            sub_agents: HashMap::new(),
+        };
+        let thread = ThreadExport {
+            items: vec![
+                message("user", "Delegate <this>"),
+                function_call("spawn_agent", r#"{"message":"Look at <main>"}"#, "call-1"),
+                function_call_output(
+                    "call-1",
+                    &format!(r#"{{"agent_id":"{agent_id}","nickname":null}}"#),
+                ),
+            ],
+            sub_agents: HashMap::from([(agent_id.clone(), sub_agent)]),
+        };
+
+        let html = thread.render(ThreadExportFormat::Html);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<div class=\"text\">Delegate &lt;this&gt;</div>"));
+        assert!(html.contains(&format!("<summary>Sub-agent {agent_id}</summary>")));
+        assert!(html.contains("<div class=\"text\">Done &amp; dusted</div>"));
+        assert!(!html.contains("Look at <main>"));
+    }
+
+    #[test]
+    fn spawned_agent_ids_come_from_spawn_agent_outputs() {
+        let items = vec![
+            function_call("spawn_agent", r#"{"message":"a"}"#, "call-1"),
+            function_call_output("call-1", r#"{"agent_id":"agent-1","nickname":null}"#),
+            function_call("wait", r#"{"ids":["agent-1"]}"#, "call-2"),
+            function_call_output("call-2", r#"{"agent_id":"not-a-spawn"}"#),
+        ];
+
+        assert_eq!(spawned_agent_ids(&items), vec!["agent-1".to_string()]);
+    }
+}
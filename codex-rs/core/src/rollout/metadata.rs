@@ -96,8 +96,27 @@ pub(crate) async fn extract_metadata_from_rollout(
     rollout_path: &Path,
     default_provider: &str,
 ) -> anyhow::Result<ExtractionOutcome> {
-    let (items, _thread_id, parse_errors) =
-        RolloutRecorder::load_rollout_items(rollout_path).await?;
+    extract_metadata_and_items_from_rollout(rollout_path, default_provider)
+        .await
+        .map(|(outcome, _items, _written_at)| outcome)
+}
+
+/// Like [`extract_metadata_from_rollout`], but also returns the parsed rollout items along with
+/// the time each of them was written, when its rollout line has a readable timestamp.
+pub(crate) async fn extract_metadata_and_items_from_rollout(
+    rollout_path: &Path,
+    default_provider: &str,
+) -> anyhow::Result<(
+    ExtractionOutcome,
+    Vec<RolloutItem>,
+    Vec<Option<DateTime<Utc>>>,
+)> {
+    let (lines, _thread_id, parse_errors) =
+        RolloutRecorder::load_rollout_lines(rollout_path).await?;
+    let (written_at, items): (Vec<_>, Vec<_>) = lines
+        .into_iter()
+        .map(|line| (parse_timestamp_to_utc(&line.timestamp), line.item))
+        .unzip();
     if items.is_empty() {
         return Err(anyhow::anyhow!(
             "empty session file: {}",
@@ -117,7 +136,7 @@ pub(crate) async fn extract_metadata_from_rollout(
     if let Some(updated_at) = file_modified_time_utc(rollout_path).await {
         metadata.updated_at = updated_at;
     }
-    Ok(ExtractionOutcome {
+    let outcome = ExtractionOutcome {
         metadata,
         memory_mode: items.iter().rev().find_map(|item| match item {
             RolloutItem::SessionMeta(meta_line) => meta_line.meta.memory_mode.clone(),
@@ -127,7 +146,8 @@ pub(crate) async fn extract_metadata_from_rollout(
             | RolloutItem::EventMsg(_) => None,
         }),
         parse_errors,
-    })
+    };
+    Ok((outcome, items, written_at))
 }
 
 pub(crate) async fn backfill_sessions(runtime: &codex_state::StateRuntime, config: &Config) {
@@ -226,10 +246,13 @@ pub(crate) async fn backfill_sessions(runtime: &codex_state::StateRuntime, confi
     for batch in rollout_paths.chunks(BACKFILL_BATCH_SIZE) {
         for rollout in batch {
             stats.scanned = stats.scanned.saturating_add(1);
-            match extract_metadata_from_rollout(&rollout.path, config.model_provider_id.as_str())
-                .await
+            match extract_metadata_and_items_from_rollout(
+                &rollout.path,
+                config.model_provider_id.as_str(),
+            )
+            .await
             {
-                Ok(outcome) => {
+                Ok((outcome, items, written_at)) => {
                     if outcome.parse_errors > 0
                         && let Some(ref metric_client) = metric_client
                     {
@@ -288,6 +311,21 @@ pub(crate) async fn backfill_sessions(runtime: &codex_state::StateRuntime, confi
                                 rollout.path.display()
                             );
                         }
+                        if let Err(err) = runtime
+                            .record_token_usage_at(&metadata, &items, &written_at)
+                            .await
+                        {
+                            warn!(
+                                "failed to record token usage {}: {err}",
+                                rollout.path.display()
+                            );
+                        }
+                        if let Err(err) = runtime.index_thread_messages(metadata.id, &items).await {
+                            warn!(
+                                "failed to index messages for search {}: {err}",
+                                rollout.path.display()
+                            );
+                        }
                     }
                 }
                 Err(err) => {
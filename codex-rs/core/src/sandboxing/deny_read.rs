@@ -0,0 +1,132 @@
+/*
+Module: sandboxing::deny_read
+
+Expands `deny_read` patterns into the existing paths the Linux sandbox masks.
+Walking the filesystem for every command would make large workspaces slow, so
+each expansion is computed once per process for a given set of patterns, cwd
+and scan limit, and reused by every later command with the same inputs.
+*/
+
+use super::SandboxTransformError;
+use codex_protocol::permissions::FileSystemSandboxPolicy;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ExpansionKey {
+    patterns: Vec<String>,
+    cwd: PathBuf,
+    scan_limit: usize,
+}
+
+/// Failures are cached too, so a pattern that is too broad to expand fails
+/// fast instead of re-walking the tree on every command.
+type Expansion = Result<Arc<[PathBuf]>, String>;
+
+static EXPANSIONS: LazyLock<Mutex<HashMap<ExpansionKey, Expansion>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Returns the existing paths matched by the policy's `deny_read` patterns,
+/// resolved against `cwd`.
+///
+/// The result is a snapshot taken the first time these patterns are expanded
+/// for `cwd`: paths created afterwards are not included.
+pub(crate) fn denied_paths(
+    file_system_policy: &FileSystemSandboxPolicy,
+    cwd: &Path,
+) -> Result<Arc<[PathBuf]>, SandboxTransformError> {
+    if file_system_policy.deny_read.is_empty() {
+        return Ok(Arc::from([]));
+    }
+    let key = ExpansionKey {
+        patterns: file_system_policy.deny_read.clone(),
+        cwd: cwd.to_path_buf(),
+        scan_limit: file_system_policy.effective_deny_read_scan_limit(),
+    };
+    // Holding the lock while expanding keeps concurrent commands from walking
+    // the same tree twice.
+    let mut expansions = EXPANSIONS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let expansion = expansions
+        .entry(key)
+        .or_insert_with_key(|key| expand(file_system_policy, key));
+    expansion
+        .clone()
+        .map_err(SandboxTransformError::DenyReadExpansion)
+}
+
+fn expand(file_system_policy: &FileSystemSandboxPolicy, key: &ExpansionKey) -> Expansion {
+    let paths = file_system_policy
+        .read_deny_list(&key.cwd)
+        .and_then(|deny_list| deny_list.find_denied_paths(key.scan_limit))
+        .map_err(|err| err.to_string())?;
+    // The helper receives paths as UTF-8 arguments; masking a lossily
+    // converted path would leave the real one readable.
+    if let Some(path) = paths.iter().find(|path| path.to_str().is_none()) {
+        return Err(format!(
+            "deny_read matched `{}`, which is not valid UTF-8 and cannot be masked",
+            path.display()
+        ));
+    }
+    Ok(paths.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn expansion_is_reused_for_the_same_cwd() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let root = temp_dir.path();
+        std::fs::write(root.join(".env"), "TOKEN=1").expect("write .env");
+        let policy = FileSystemSandboxPolicy::default().with_deny_read(vec!["**/.env".to_string()]);
+
+        let first = denied_paths(&policy, root).expect("expand deny_read");
+        assert_eq!(first.as_ref(), [root.join(".env")]);
+
+        std::fs::create_dir_all(root.join("service")).expect("create service dir");
+        std::fs::write(root.join("service/.env"), "TOKEN=2").expect("write nested .env");
+        let second = denied_paths(&policy, root).expect("expand deny_read");
+        assert!(Arc::ptr_eq(&first, &second));
+
+        let service = root.join("service");
+        assert_eq!(
+            denied_paths(&policy, &service)
+                .expect("expand deny_read")
+                .as_ref(),
+            [service.join(".env")]
+        );
+    }
+
+    #[test]
+    fn scan_limit_overflow_is_a_transform_error() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let root = temp_dir.path();
+        for name in ["a", "b", "c"] {
+            std::fs::create_dir_all(root.join(name)).expect("create dir");
+        }
+        let policy = FileSystemSandboxPolicy::default()
+            .with_deny_read(vec!["**/.env".to_string()])
+            .with_deny_read_scan_limit(Some(2));
+
+        let err = denied_paths(&policy, root).expect_err("scan should exceed the limit");
+        assert!(
+            matches!(err, SandboxTransformError::DenyReadExpansion(ref message) if message.contains("deny_read_scan_limit")),
+            "{err}"
+        );
+        assert_eq!(
+            denied_paths(&policy.with_deny_read_scan_limit(Some(10)), root)
+                .expect("expand deny_read")
+                .len(),
+            0
+        );
+    }
+}
@@ -6,6 +6,7 @@ sandbox placement and transformation of portable CommandSpec into a
 ready‑to‑spawn environment.
 */
 
+pub(crate) mod deny_read;
 pub(crate) mod macos_permissions;
 
 use crate::exec::ExecExpiration;
@@ -110,6 +111,8 @@ pub(crate) enum SandboxTransformError {
     #[cfg(not(target_os = "macos"))]
     #[error("seatbelt sandbox is only available on macOS")]
     SeatbeltUnavailable,
+    #[error("failed to expand deny_read patterns: {0}")]
+    DenyReadExpansion(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -515,10 +518,15 @@ impl SandboxManager {
                 let exe = codex_linux_sandbox_exe
                     .ok_or(SandboxTransformError::MissingLinuxSandboxExecutable)?;
                 let allow_proxy_network = allow_network_for_proxy(enforce_managed_network);
+                // Refuse to run the command rather than start it with denied
+                // files left unmasked.
+                let deny_read_paths =
+                    deny_read::denied_paths(&effective_file_system_policy, sandbox_policy_cwd)?;
                 let mut args = create_linux_sandbox_command_args(
                     command.clone(),
                     &effective_policy,
                     sandbox_policy_cwd,
+                    &deny_read_paths,
                     use_linux_sandbox_bwrap,
                     allow_proxy_network,
                 );
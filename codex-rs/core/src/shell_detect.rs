@@ -10,6 +10,9 @@ pub(crate) fn detect_shell_type(shell_path: &PathBuf) -> Option<ShellType> {
         Some("bash") => Some(ShellType::Bash),
         Some("pwsh") => Some(ShellType::PowerShell),
         Some("powershell") => Some(ShellType::PowerShell),
+        Some("fish") => Some(ShellType::Fish),
+        Some("nu") => Some(ShellType::Nushell),
+        Some("nushell") => Some(ShellType::Nushell),
         _ => {
             let shell_name = shell_path.file_stem();
             if let Some(shell_name) = shell_name {
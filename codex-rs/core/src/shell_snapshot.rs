@@ -33,6 +33,8 @@ const SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(10);
 const SNAPSHOT_RETENTION: Duration = Duration::from_secs(60 * 60 * 24 * 3); // 3 days retention.
 const SNAPSHOT_DIR: &str = "shell_snapshots";
 const EXCLUDED_EXPORT_VARS: &[&str] = &["PWD", "OLDPWD"];
+/// Automatic variables that Nushell refuses to reassign from a sourced file.
+const NUSHELL_EXCLUDED_EXPORT_VARS: &[&str] = &["FILE_PWD", "CURRENT_FILE"];
 
 impl ShellSnapshot {
     pub fn start_snapshotting(
@@ -118,6 +120,8 @@ impl ShellSnapshot {
         // File to store the snapshot
         let extension = match shell.shell_type {
             ShellType::PowerShell => "ps1",
+            ShellType::Fish => "fish",
+            ShellType::Nushell => "nu",
             _ => "sh",
         };
         let path = codex_home
@@ -227,6 +231,8 @@ async fn capture_snapshot(shell: &Shell, cwd: &Path) -> Result<String> {
         ShellType::Bash => run_shell_script(shell, &bash_snapshot_script(), cwd).await,
         ShellType::Sh => run_shell_script(shell, &sh_snapshot_script(), cwd).await,
         ShellType::PowerShell => run_shell_script(shell, powershell_snapshot_script(), cwd).await,
+        ShellType::Fish => run_shell_script(shell, &fish_snapshot_script(), cwd).await,
+        ShellType::Nushell => run_shell_script(shell, &nushell_snapshot_script(), cwd).await,
         ShellType::Cmd => bail!("Shell snapshotting is not yet supported for {shell_type:?}"),
     }
 }
@@ -242,7 +248,11 @@ fn strip_snapshot_preamble(snapshot: &str) -> Result<String> {
 
 async fn validate_snapshot(shell: &Shell, snapshot_path: &Path, cwd: &Path) -> Result<()> {
     let snapshot_path_display = snapshot_path.display();
-    let script = format!("set -e; . \"{snapshot_path_display}\"");
+    let script = match shell.shell_type {
+        ShellType::Fish => format!("source \"{snapshot_path_display}\""),
+        ShellType::Nushell => format!("source `{snapshot_path_display}`"),
+        _ => format!("set -e; . \"{snapshot_path_display}\""),
+    };
     run_script_with_timeout(shell, &script, SNAPSHOT_TIMEOUT, false, cwd)
         .await
         .map(|_| ())
@@ -448,6 +458,58 @@ fi
     script.replace("EXCLUDED_EXPORTS", &excluded)
 }
 
+fn fish_snapshot_script() -> String {
+    let excluded = EXCLUDED_EXPORT_VARS.join(" ");
+    let script = r##"echo '# Snapshot file'
+echo '# Functions'
+for name in (functions --names)
+    functions -- $name
+end
+echo ''
+set -l excluded EXCLUDED_EXPORTS
+set -l export_names
+for name in (set --names --export)
+    if contains -- $name $excluded
+        continue
+    end
+    if not string match -qr '^[A-Za-z_][A-Za-z0-9_]*$' -- $name
+        continue
+    end
+    set -a export_names $name
+end
+echo "# exports "(count $export_names)
+for name in $export_names
+    set -l value (string escape -- $$name | string join ' ')
+    echo "set -gx $name $value"
+end
+"##;
+    script.replace("EXCLUDED_EXPORTS", &excluded)
+}
+
+fn nushell_snapshot_script() -> String {
+    let excluded = EXCLUDED_EXPORT_VARS
+        .iter()
+        .chain(NUSHELL_EXCLUDED_EXPORT_VARS)
+        .map(|name| format!("'{name}'"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let script = r##"print '# Snapshot file'
+let aliases = (scope aliases)
+print $"# aliases ($aliases | length)"
+$aliases | each {|alias| print $"alias ($alias.name) = ($alias.expansion)" } | ignore
+print ''
+let excluded = [EXCLUDED_EXPORTS]
+let exports = ($env | transpose name value | where {|row| (
+    ($row.name not-in $excluded)
+    and ($row.name =~ '^[A-Za-z_][A-Za-z0-9_]*$')
+    and (($row.value | describe) in ['string', 'list<string>'])
+)})
+print $"# exports ($exports | length)"
+$exports | each {|row| print $"$env.($row.name) = ($row.value | to nuon)" } | ignore
+"##;
+    script.replace("EXCLUDED_EXPORTS", &excluded)
+}
+
 fn powershell_snapshot_script() -> &'static str {
     r##"$ErrorActionPreference = 'Stop'
 Write-Output '# Snapshot file'
@@ -709,6 +771,71 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn fish_snapshot_filters_invalid_exports() -> Result<()> {
+        let Ok(fish) = which::which("fish") else {
+            return Ok(());
+        };
+        let output = Command::new(&fish)
+            .arg("--no-config")
+            .arg("-c")
+            .arg(fish_snapshot_script())
+            .env("VALID_NAME", "it's ok")
+            .env("BAD-NAME", "broken")
+            .output()?;
+
+        assert!(output.status.success());
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("set -gx VALID_NAME"));
+        assert!(!stdout.contains("BAD-NAME"));
+
+        let dir = tempdir()?;
+        let snapshot_path = dir.path().join("snapshot.fish");
+        std::fs::write(&snapshot_path, stdout.as_bytes())?;
+
+        let validate = Command::new(&fish)
+            .arg("--no-config")
+            .arg("-c")
+            .arg("source $argv[1]; and printf '%s' $VALID_NAME")
+            .arg(&snapshot_path)
+            .env_remove("VALID_NAME")
+            .output()?;
+
+        assert!(
+            validate.status.success(),
+            "snapshot validation failed: {}",
+            String::from_utf8_lossy(&validate.stderr)
+        );
+        assert_eq!(String::from_utf8_lossy(&validate.stdout), "it's ok");
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn nushell_snapshot_filters_invalid_exports() -> Result<()> {
+        let Ok(nu) = which::which("nu") else {
+            return Ok(());
+        };
+        let output = Command::new(&nu)
+            .arg("-c")
+            .arg(nushell_snapshot_script())
+            .env("VALID_NAME", "it's ok")
+            .env("BAD-NAME", "broken")
+            .output()?;
+
+        assert!(output.status.success());
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("$env.VALID_NAME = "));
+        assert!(!stdout.contains("BAD-NAME"));
+        assert!(!stdout.contains("$env.FILE_PWD"));
+
+        Ok(())
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn try_new_creates_and_deletes_snapshot_file() -> Result<()> {
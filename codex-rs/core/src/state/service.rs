@@ -6,13 +6,17 @@ use crate::RolloutRecorder;
 use crate::agent::AgentControl;
 use crate::analytics_client::AnalyticsEventsClient;
 use crate::client::ModelClient;
+use crate::code_index::CodeIndexManager;
 use crate::config::StartedNetworkProxy;
+use crate::exec_env::ExecSecrets;
 use crate::exec_policy::ExecPolicyManager;
 use crate::file_watcher::FileWatcher;
 use crate::mcp::McpManager;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
+use crate::notification_sinks::NotificationSinks;
 use crate::plugins::PluginsManager;
+use crate::replay::RecordedToolOutputs;
 use crate::skills::SkillsManager;
 use crate::state_db::StateDbHandle;
 use crate::tools::network_approval::NetworkApprovalService;
@@ -38,6 +42,7 @@ pub(crate) struct SessionServices {
     pub(crate) main_execve_wrapper_exe: Option<PathBuf>,
     pub(crate) analytics_events_client: AnalyticsEventsClient,
     pub(crate) hooks: Hooks,
+    pub(crate) notification_sinks: NotificationSinks,
     pub(crate) rollout: Mutex<Option<RolloutRecorder>>,
     pub(crate) user_shell: Arc<crate::shell::Shell>,
     pub(crate) shell_snapshot_tx: watch::Sender<Option<Arc<crate::shell_snapshot::ShellSnapshot>>>,
@@ -53,10 +58,16 @@ pub(crate) struct SessionServices {
     pub(crate) plugins_manager: Arc<PluginsManager>,
     pub(crate) mcp_manager: Arc<McpManager>,
     pub(crate) file_watcher: Arc<FileWatcher>,
+    pub(crate) code_index: Arc<CodeIndexManager>,
+    /// Recorded tool outputs returned instead of executing tool calls. Only
+    /// set for sessions started by `codex debug replay`.
+    pub(crate) replay_tool_outputs: Option<Arc<RecordedToolOutputs>>,
     pub(crate) agent_control: AgentControl,
     pub(crate) network_proxy: Option<StartedNetworkProxy>,
     pub(crate) network_approval: Arc<NetworkApprovalService>,
     pub(crate) state_db: Option<StateDbHandle>,
+    /// Secrets handed to commands through `shell_environment_policy`.
+    pub(crate) exec_secrets: ExecSecrets,
     /// Session-scoped model client shared across turns.
     pub(crate) model_client: ModelClient,
 }
@@ -1,4 +1,5 @@
 use crate::config::Config;
+use crate::config::types::ModelPricing;
 use crate::path_utils::normalize_for_path_comparison;
 use crate::rollout::list::Cursor;
 use crate::rollout::list::ThreadSortKey;
@@ -13,7 +14,11 @@ use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::SessionSource;
 pub use codex_state::LogEntry;
 use codex_state::ThreadMetadataBuilder;
+use codex_state::TokenUsageGroupBy;
+use codex_state::TokenUsageSummary;
+use codex_state::TokenUsageSummaryQuery;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -268,6 +273,126 @@ pub async fn list_threads_db(
     }
 }
 
+/// Search indexed thread messages in SQLite, grouped per thread and best-first.
+///
+/// At most `max_threads` threads with up to `max_matches_per_thread` hits each are fetched.
+/// Threads whose source or model provider fall outside the given filters are then dropped, as
+/// are threads whose rollout file no longer exists.
+pub async fn search_threads_db(
+    context: Option<&codex_state::StateRuntime>,
+    query: &str,
+    max_threads: usize,
+    max_matches_per_thread: usize,
+    allowed_sources: &[SessionSource],
+    model_providers: Option<&[String]>,
+) -> Option<
+    Vec<(
+        codex_state::ThreadMetadata,
+        Vec<codex_state::ThreadSearchHit>,
+    )>,
+> {
+    let ctx = context?;
+    let hits = match ctx
+        .search_threads(query, max_threads, max_matches_per_thread, false)
+        .await
+    {
+        Ok(hits) => hits,
+        Err(err) => {
+            warn!("state db search_threads failed: {err}");
+            return None;
+        }
+    };
+    let allowed_sources: Vec<String> = allowed_sources
+        .iter()
+        .map(|value| match serde_json::to_value(value) {
+            Ok(Value::String(s)) => s,
+            Ok(other) => other.to_string(),
+            Err(_) => String::new(),
+        })
+        .collect();
+
+    let mut grouped: Vec<(ThreadId, Vec<codex_state::ThreadSearchHit>)> = Vec::new();
+    for hit in hits {
+        match grouped
+            .iter_mut()
+            .find(|(thread_id, _)| *thread_id == hit.thread_id)
+        {
+            Some((_, thread_hits)) => thread_hits.push(hit),
+            None => grouped.push((hit.thread_id, vec![hit])),
+        }
+    }
+    let mut results = Vec::with_capacity(grouped.len());
+    for (thread_id, thread_hits) in grouped {
+        let Ok(Some(metadata)) = ctx.get_thread(thread_id).await else {
+            continue;
+        };
+        if !allowed_sources.is_empty() && !allowed_sources.contains(&metadata.source) {
+            continue;
+        }
+        if let Some(model_providers) = model_providers
+            && !model_providers.is_empty()
+            && !model_providers.contains(&metadata.model_provider)
+        {
+            continue;
+        }
+        if !tokio::fs::try_exists(&metadata.rollout_path)
+            .await
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        results.push((metadata, thread_hits));
+    }
+    Some(results)
+}
+
+/// Aggregate the token usage ledger and estimate the cost of each group.
+///
+/// Costs are computed per model and summed into each group, so they are available even
+/// when usage is not grouped by model. Models without a configured price do not
+/// contribute, and a group's cost is `None` when none of its models are priced.
+pub async fn token_usage_summary(
+    runtime: &codex_state::StateRuntime,
+    query: &TokenUsageSummaryQuery,
+    pricing: &HashMap<String, ModelPricing>,
+) -> anyhow::Result<Vec<(TokenUsageSummary, Option<f64>)>> {
+    let summary = runtime.token_usage_summary(query).await?;
+    let grouped_by_model = query.group_by.contains(&TokenUsageGroupBy::Model);
+    let by_model = if pricing.is_empty() {
+        Vec::new()
+    } else {
+        let mut by_model_query = query.clone();
+        if !grouped_by_model {
+            by_model_query.group_by.push(TokenUsageGroupBy::Model);
+        }
+        runtime.token_usage_summary(&by_model_query).await?
+    };
+    Ok(summary
+        .into_iter()
+        .map(|row| {
+            let cost_usd = by_model
+                .iter()
+                .filter(|model_row| {
+                    model_row.day == row.day
+                        && model_row.repository == row.repository
+                        && (!grouped_by_model || model_row.model == row.model)
+                })
+                .filter_map(|model_row| {
+                    let price = pricing.get(model_row.model.as_deref()?)?;
+                    Some(price.cost_usd(
+                        model_row.input_tokens,
+                        model_row.cached_input_tokens,
+                        model_row.output_tokens,
+                    ))
+                })
+                .fold(None, |total: Option<f64>, cost| {
+                    Some(total.unwrap_or_default() + cost)
+                });
+            (row, cost_usd)
+        })
+        .collect())
+}
+
 /// Look up the rollout path for a thread id using SQLite.
 pub async fn find_rollout_path_by_id(
     context: Option<&codex_state::StateRuntime>,
@@ -355,9 +480,11 @@ pub async fn reconcile_rollout(
         .await;
         return;
     }
-    let outcome =
-        match metadata::extract_metadata_from_rollout(rollout_path, default_provider).await {
-            Ok(outcome) => outcome,
+    let (outcome, items, written_at) =
+        match metadata::extract_metadata_and_items_from_rollout(rollout_path, default_provider)
+            .await
+        {
+            Ok(extracted) => extracted,
             Err(err) => {
                 warn!(
                     "state db reconcile_rollout extraction failed {}: {err}",
@@ -412,6 +539,21 @@ pub async fn reconcile_rollout(
             rollout_path.display()
         );
     }
+    if let Err(err) = ctx
+        .record_token_usage_at(&metadata, &items, &written_at)
+        .await
+    {
+        warn!(
+            "state db reconcile_rollout token usage failed {}: {err}",
+            rollout_path.display()
+        );
+    }
+    if let Err(err) = ctx.index_thread_messages(metadata.id, &items).await {
+        warn!(
+            "state db reconcile_rollout search index failed {}: {err}",
+            rollout_path.display()
+        );
+    }
 }
 
 /// Repair a thread's rollout path after filesystem fallback succeeds.
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use super::SessionTask;
 use super::SessionTaskContext;
 use crate::codex::TurnContext;
+use crate::config::types::CompactionStrategy;
 use crate::state::TaskKind;
 use async_trait::async_trait;
 use codex_protocol::user_input::UserInput;
@@ -29,7 +30,15 @@ impl SessionTask for CompactTask {
         _cancellation_token: CancellationToken,
     ) -> Option<String> {
         let session = session.clone_session();
-        let _ = if crate::compact::should_use_remote_compact_task(&ctx.provider) {
+        let strategy = ctx.config.compaction_strategy;
+        let _ = if strategy != CompactionStrategy::Summary {
+            let _ = session.services.session_telemetry.counter(
+                "codex.task.compact",
+                1,
+                &[("type", strategy.as_str())],
+            );
+            crate::compact_prune::run_pruning_compact_task(session.clone(), ctx, strategy).await
+        } else if crate::compact::should_use_remote_compact_task(&ctx.provider) {
             let _ = session.services.session_telemetry.counter(
                 "codex.task.compact",
                 1,
@@ -2,6 +2,7 @@ use crate::AuthManager;
 use crate::CodexAuth;
 use crate::ModelProviderInfo;
 use crate::agent::AgentControl;
+use crate::code_index::CodeIndexManager;
 use crate::codex::Codex;
 use crate::codex::CodexSpawnOk;
 use crate::codex::INITIAL_SUBMIT_ID;
@@ -19,6 +20,7 @@ use crate::prompt_hooks::ensure_codex_home_docs;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::SessionConfiguredEvent;
+use crate::replay::RecordedToolOutputs;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::truncation;
 use crate::shell_snapshot::ShellSnapshot;
@@ -100,6 +102,7 @@ fn build_file_watcher(codex_home: PathBuf, skills_manager: Arc<SkillsManager>) -
                     Ok(FileWatcherEvent::SkillsChanged { .. }) => {
                         skills_manager.clear_cache();
                     }
+                    Ok(FileWatcherEvent::WorkspaceChanged { .. }) => {}
                     Err(broadcast::error::RecvError::Closed) => break,
                     Err(broadcast::error::RecvError::Lagged(_)) => continue,
                 }
@@ -139,6 +142,10 @@ pub(crate) struct ThreadManagerState {
     plugins_manager: Arc<PluginsManager>,
     mcp_manager: Arc<McpManager>,
     file_watcher: Arc<FileWatcher>,
+    code_index: Arc<CodeIndexManager>,
+    /// Handed to every session so `codex debug replay` can answer tool calls
+    /// with recorded outputs.
+    replay_tool_outputs: Option<Arc<RecordedToolOutputs>>,
     session_source: SessionSource,
     // Captures submitted ops for testing purpose when test mode is enabled.
     ops_log: Option<SharedCapturedOps>,
@@ -151,6 +158,26 @@ impl ThreadManager {
         session_source: SessionSource,
         model_catalog: Option<ModelsResponse>,
         collaboration_modes_config: CollaborationModesConfig,
+    ) -> Self {
+        Self::with_replay_tool_outputs(
+            codex_home,
+            auth_manager,
+            session_source,
+            model_catalog,
+            collaboration_modes_config,
+            None,
+        )
+    }
+
+    /// Like [`Self::new`], but threads answer tool calls from
+    /// `replay_tool_outputs` instead of running them when it is set.
+    pub(crate) fn with_replay_tool_outputs(
+        codex_home: PathBuf,
+        auth_manager: Arc<AuthManager>,
+        session_source: SessionSource,
+        model_catalog: Option<ModelsResponse>,
+        collaboration_modes_config: CollaborationModesConfig,
+        replay_tool_outputs: Option<Arc<RecordedToolOutputs>>,
     ) -> Self {
         ensure_codex_home_docs(&codex_home);
         let (thread_created_tx, _) = broadcast::channel(THREAD_CREATED_CHANNEL_CAPACITY);
@@ -161,6 +188,7 @@ impl ThreadManager {
             Arc::clone(&plugins_manager),
         ));
         let file_watcher = build_file_watcher(codex_home.clone(), Arc::clone(&skills_manager));
+        let code_index = CodeIndexManager::new(&codex_home, Arc::clone(&file_watcher));
         Self {
             state: Arc::new(ThreadManagerState {
                 threads: Arc::new(RwLock::new(HashMap::new())),
@@ -175,6 +203,8 @@ impl ThreadManager {
                 plugins_manager,
                 mcp_manager,
                 file_watcher,
+                code_index,
+                replay_tool_outputs,
                 auth_manager,
                 session_source,
                 ops_log: should_use_test_thread_manager_behavior()
@@ -221,6 +251,7 @@ impl ThreadManager {
             Arc::clone(&plugins_manager),
         ));
         let file_watcher = build_file_watcher(codex_home.clone(), Arc::clone(&skills_manager));
+        let code_index = CodeIndexManager::new(&codex_home, Arc::clone(&file_watcher));
         Self {
             state: Arc::new(ThreadManagerState {
                 threads: Arc::new(RwLock::new(HashMap::new())),
@@ -234,6 +265,8 @@ impl ThreadManager {
                 plugins_manager,
                 mcp_manager,
                 file_watcher,
+                code_index,
+                replay_tool_outputs: None,
                 auth_manager,
                 session_source: SessionSource::Exec,
                 ops_log: should_use_test_thread_manager_behavior()
@@ -607,6 +640,8 @@ impl ThreadManagerState {
             Arc::clone(&self.plugins_manager),
             Arc::clone(&self.mcp_manager),
             Arc::clone(&self.file_watcher),
+            Arc::clone(&self.code_index),
+            self.replay_tool_outputs.clone(),
             initial_history,
             session_source,
             agent_control,
@@ -59,6 +59,16 @@ impl ToolPayload {
             ToolPayload::Mcp { raw_arguments, .. } => Cow::Borrowed(raw_arguments),
         }
     }
+
+    /// Short label for the payload variant, used to tag tool call spans.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ToolPayload::Function { .. } => "function",
+            ToolPayload::Custom { .. } => "custom",
+            ToolPayload::LocalShell { .. } => "local_shell",
+            ToolPayload::Mcp { .. } => "mcp",
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -301,8 +301,10 @@ impl ToolEmitter {
         ctx: ToolEventCtx<'_>,
         out: Result<ExecToolCallOutput, ToolError>,
     ) -> Result<String, FunctionCallError> {
+        let exec_secrets = &ctx.session.services.exec_secrets;
         let (event, result) = match out {
-            Ok(output) => {
+            Ok(mut output) => {
+                exec_secrets.redact_output(&mut output);
                 let content = self.format_exec_output_for_model(&output, ctx);
                 let exit_code = output.exit_code;
                 let event = ToolEventStage::Success(output);
@@ -313,8 +315,9 @@ impl ToolEmitter {
                 };
                 (event, result)
             }
-            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
-            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output, .. }))) => {
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { mut output })))
+            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { mut output, .. }))) => {
+                exec_secrets.redact_output(&mut output);
                 let response = self.format_exec_output_for_model(&output, ctx);
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
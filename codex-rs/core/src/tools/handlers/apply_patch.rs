@@ -30,6 +30,7 @@ use crate::tools::spec::JsonSchema;
 use async_trait::async_trait;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::ApplyPatchOptions;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::sync::Arc;
 
@@ -61,6 +62,28 @@ fn to_abs_path(cwd: &Path, path: &Path) -> Option<AbsolutePathBuf> {
     AbsolutePathBuf::resolve_path_against_base(path, cwd).ok()
 }
 
+/// Tells the model which hunks were applied when the user reviewed the patch
+/// hunk by hunk.
+fn with_hunk_review_report(content: String, runtime: &ApplyPatchRuntime) -> String {
+    match runtime.hunk_review_report() {
+        Some(report) => format!("{content}\n\n{report}"),
+        None => content,
+    }
+}
+
+/// Reports only the hunks the user kept when the patch was reviewed hunk by
+/// hunk, rather than the patch the model proposed.
+fn emitter_for_selected_hunks(
+    emitter: ToolEmitter,
+    runtime: &ApplyPatchRuntime,
+    auto_approved: bool,
+) -> ToolEmitter {
+    match runtime.selected_changes() {
+        Some(changes) => ToolEmitter::apply_patch(changes.clone(), auto_approved),
+        None => emitter,
+    }
+}
+
 #[async_trait]
 impl ToolHandler for ApplyPatchHandler {
     fn kind(&self) -> ToolKind {
@@ -106,7 +129,12 @@ impl ToolHandler for ApplyPatchHandler {
         // Avoid building temporary ExecParams/command vectors; derive directly from inputs.
         let cwd = turn.cwd.clone();
         let command = vec!["apply_patch".to_string(), patch_input.clone()];
-        match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &cwd) {
+        let options = ApplyPatchOptions {
+            fuzz: turn.config.apply_patch_fuzz,
+        };
+        match codex_apply_patch::maybe_parse_apply_patch_verified_with_options(
+            &command, &cwd, options,
+        ) {
             codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
                 match apply_patch::apply_patch(turn.as_ref(), changes).await {
                     InternalApplyPatchInvocation::Output(item) => {
@@ -162,7 +190,10 @@ impl ToolHandler for ApplyPatchHandler {
                             &call_id,
                             Some(&tracker),
                         );
+                        let emitter =
+                            emitter_for_selected_hunks(emitter, &runtime, apply.auto_approved);
                         let content = emitter.finish(event_ctx, out).await?;
+                        let content = with_hunk_review_report(content, &runtime);
                         Ok(ToolOutput::Function {
                             body: FunctionCallOutputBody::Text(content),
                             success: Some(true),
@@ -201,7 +232,10 @@ pub(crate) async fn intercept_apply_patch(
     call_id: &str,
     tool_name: &str,
 ) -> Result<Option<ToolOutput>, FunctionCallError> {
-    match codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd) {
+    let options = ApplyPatchOptions {
+        fuzz: turn.config.apply_patch_fuzz,
+    };
+    match codex_apply_patch::maybe_parse_apply_patch_verified_with_options(command, cwd, options) {
         codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
             session
                 .record_model_warning(
@@ -264,7 +298,10 @@ pub(crate) async fn intercept_apply_patch(
                         call_id,
                         tracker.as_ref().copied(),
                     );
+                    let emitter =
+                        emitter_for_selected_hunks(emitter, &runtime, apply.auto_approved);
                     let content = emitter.finish(event_ctx, out).await?;
+                    let content = with_hunk_review_report(content, &runtime);
                     Ok(Some(ToolOutput::Function {
                         body: FunctionCallOutputBody::Text(content),
                         success: Some(true),
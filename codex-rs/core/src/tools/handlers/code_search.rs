@@ -0,0 +1,189 @@
+use codex_protocol::models::FunctionCallOutputBody;
+use std::path::Path;
+
+use async_trait::async_trait;
+use codex_code_index::SearchHit;
+use serde::Deserialize;
+
+use crate::code_index::CodeIndexManager;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::is_read_denied;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::handlers::read_denied_error;
+use crate::tools::handlers::read_deny_list;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct CodeSearchHandler;
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CodeSearchMode {
+    Definition,
+    References,
+    #[default]
+    Text,
+}
+
+#[derive(Deserialize)]
+struct CodeSearchArgs {
+    query: String,
+    #[serde(default)]
+    mode: CodeSearchMode,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for CodeSearchHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            payload,
+            session,
+            turn,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "code_search handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: CodeSearchArgs = parse_arguments(&arguments)?;
+
+        let query = args.query.trim().to_string();
+        if query.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "query must not be empty".to_string(),
+            ));
+        }
+        if args.mode != CodeSearchMode::Text && query.split_whitespace().count() > 1 {
+            return Err(FunctionCallError::RespondToModel(
+                "definition and references queries take a single identifier".to_string(),
+            ));
+        }
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+
+        let limit = args.limit.min(MAX_LIMIT);
+        let search_path = turn.resolve_path(args.path);
+        if tokio::fs::metadata(&search_path).await.is_err() {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "unable to access `{}`",
+                search_path.display()
+            )));
+        }
+
+        let deny_list = read_deny_list(&turn)?;
+        if is_read_denied(&deny_list, &search_path).await {
+            return Err(read_denied_error(&search_path));
+        }
+
+        let Some(root) = CodeIndexManager::workspace_root(&search_path) else {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "`{}` is not inside a git repository; code_search only indexes repositories",
+                search_path.display()
+            )));
+        };
+        let within = search_path
+            .strip_prefix(&root)
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        let mode = args.mode;
+        let hits = session
+            .services
+            .code_index
+            .query(root.clone(), move |index| {
+                // Files hidden by `deny_read` are skipped before they are read
+                // and never count toward the limit.
+                let denied = |path: &Path| deny_list.is_denied(&index.root().join(path));
+                match mode {
+                    CodeSearchMode::Definition => index.definitions(&query, &within, denied, limit),
+                    CodeSearchMode::References => index.references(&query, &within, denied, limit),
+                    CodeSearchMode::Text => index.search(&query, &within, denied, limit),
+                }
+            })
+            .await
+            .map_err(FunctionCallError::RespondToModel)?;
+
+        let lines: Vec<String> = hits
+            .iter()
+            .map(|hit| format_hit(hit, &root, &turn.cwd))
+            .collect();
+
+        if lines.is_empty() {
+            Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::Text("No matches found.".to_string()),
+                success: Some(false),
+            })
+        } else {
+            Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::Text(lines.join("\n")),
+                success: Some(true),
+            })
+        }
+    }
+}
+
+/// Formats a hit as `path:line: text`, with the symbol kind in brackets for
+/// definitions. Paths are relative to `cwd` when they are under it.
+fn format_hit(hit: &SearchHit, root: &Path, cwd: &Path) -> String {
+    let path = root.join(&hit.path);
+    let display = path.strip_prefix(cwd).unwrap_or(&path).display();
+    match &hit.kind {
+        Some(kind) => format!("{display}:{}: [{kind}] {}", hit.line, hit.text),
+        None => format!("{display}:{}: {}", hit.line, hit.text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn formats_hits_relative_to_cwd() {
+        let hit = SearchHit {
+            path: PathBuf::from("core/src/lib.rs"),
+            line: 12,
+            text: "pub fn load_config() {}".to_string(),
+            kind: Some("function".to_string()),
+        };
+        assert_eq!(
+            format_hit(&hit, Path::new("/repo"), Path::new("/repo/core")),
+            "src/lib.rs:12: [function] pub fn load_config() {}"
+        );
+        assert_eq!(
+            format_hit(
+                &SearchHit { kind: None, ..hit },
+                Path::new("/repo"),
+                Path::new("/elsewhere")
+            ),
+            "/repo/core/src/lib.rs:12: pub fn load_config() {}"
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::path::Path;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use codex_protocol::permissions::ReadDenyList;
 use serde::Deserialize;
 use tokio::process::Command;
 use tokio::time::timeout;
@@ -11,7 +12,10 @@ use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::is_read_denied;
 use crate::tools::handlers::parse_arguments;
+use crate::tools::handlers::read_denied_error;
+use crate::tools::handlers::read_deny_list;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 
@@ -74,6 +78,11 @@ impl ToolHandler for GrepFilesHandler {
 
         verify_path_exists(&search_path).await?;
 
+        let deny_list = read_deny_list(&turn)?;
+        if is_read_denied(&deny_list, &search_path).await {
+            return Err(read_denied_error(&search_path));
+        }
+
         let include = args.include.as_deref().map(str::trim).and_then(|val| {
             if val.is_empty() {
                 None
@@ -82,8 +91,15 @@ impl ToolHandler for GrepFilesHandler {
             }
         });
 
-        let search_results =
-            run_rg_search(pattern, include.as_deref(), &search_path, limit, &turn.cwd).await?;
+        let search_results = run_rg_search(
+            pattern,
+            include.as_deref(),
+            &search_path,
+            limit,
+            &turn.cwd,
+            &deny_list,
+        )
+        .await?;
 
         if search_results.is_empty() {
             Ok(ToolOutput::Function {
@@ -112,6 +128,7 @@ async fn run_rg_search(
     search_path: &Path,
     limit: usize,
     cwd: &Path,
+    deny_list: &ReadDenyList,
 ) -> Result<Vec<String>, FunctionCallError> {
     let mut command = Command::new("rg");
     command
@@ -140,7 +157,7 @@ async fn run_rg_search(
         })?;
 
     match output.status.code() {
-        Some(0) => Ok(parse_results(&output.stdout, limit)),
+        Some(0) => Ok(parse_results(&output.stdout, limit, deny_list)),
         Some(1) => Ok(Vec::new()),
         _ => {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -151,14 +168,15 @@ async fn run_rg_search(
     }
 }
 
-fn parse_results(stdout: &[u8], limit: usize) -> Vec<String> {
+/// Parses rg's file list, dropping files hidden by `deny_read`.
+fn parse_results(stdout: &[u8], limit: usize, deny_list: &ReadDenyList) -> Vec<String> {
     let mut results = Vec::new();
     for line in stdout.split(|byte| *byte == b'\n') {
         if line.is_empty() {
             continue;
         }
         if let Ok(text) = std::str::from_utf8(line) {
-            if text.is_empty() {
+            if text.is_empty() || deny_list.is_denied(Path::new(text)) {
                 continue;
             }
             results.push(text.to_string());
@@ -179,7 +197,7 @@ mod tests {
     #[test]
     fn parses_basic_results() {
         let stdout = b"/tmp/file_a.rs\n/tmp/file_b.rs\n";
-        let parsed = parse_results(stdout, 10);
+        let parsed = parse_results(stdout, 10, &ReadDenyList::default());
         assert_eq!(
             parsed,
             vec!["/tmp/file_a.rs".to_string(), "/tmp/file_b.rs".to_string()]
@@ -189,7 +207,7 @@ mod tests {
     #[test]
     fn parse_truncates_after_limit() {
         let stdout = b"/tmp/file_a.rs\n/tmp/file_b.rs\n/tmp/file_c.rs\n";
-        let parsed = parse_results(stdout, 2);
+        let parsed = parse_results(stdout, 2, &ReadDenyList::default());
         assert_eq!(
             parsed,
             vec!["/tmp/file_a.rs".to_string(), "/tmp/file_b.rs".to_string()]
@@ -207,7 +225,7 @@ mod tests {
         std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
         std::fs::write(dir.join("other.txt"), "omega").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 10, dir).await?;
+        let results = run_rg_search("alpha", None, dir, 10, dir, &ReadDenyList::default()).await?;
         assert_eq!(results.len(), 2);
         assert!(results.iter().any(|path| path.ends_with("match_one.txt")));
         assert!(results.iter().any(|path| path.ends_with("match_two.txt")));
@@ -224,7 +242,15 @@ mod tests {
         std::fs::write(dir.join("match_one.rs"), "alpha beta gamma").unwrap();
         std::fs::write(dir.join("match_two.txt"), "alpha delta").unwrap();
 
-        let results = run_rg_search("alpha", Some("*.rs"), dir, 10, dir).await?;
+        let results = run_rg_search(
+            "alpha",
+            Some("*.rs"),
+            dir,
+            10,
+            dir,
+            &ReadDenyList::default(),
+        )
+        .await?;
         assert_eq!(results.len(), 1);
         assert!(results.iter().all(|path| path.ends_with("match_one.rs")));
         Ok(())
@@ -241,7 +267,7 @@ mod tests {
         std::fs::write(dir.join("two.txt"), "alpha two").unwrap();
         std::fs::write(dir.join("three.txt"), "alpha three").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 2, dir).await?;
+        let results = run_rg_search("alpha", None, dir, 2, dir, &ReadDenyList::default()).await?;
         assert_eq!(results.len(), 2);
         Ok(())
     }
@@ -255,11 +281,20 @@ mod tests {
         let dir = temp.path();
         std::fs::write(dir.join("one.txt"), "omega").unwrap();
 
-        let results = run_rg_search("alpha", None, dir, 5, dir).await?;
+        let results = run_rg_search("alpha", None, dir, 5, dir, &ReadDenyList::default()).await?;
         assert!(results.is_empty());
         Ok(())
     }
 
+    #[test]
+    fn parse_results_skips_denied_files() {
+        let stdout = b"/repo/.env\n/repo/src/main.rs\n/repo/api/.env\n";
+        let deny_list = ReadDenyList::new(&["**/.env".to_string()], Path::new("/repo"))
+            .expect("compile deny list");
+        let parsed = parse_results(stdout, 10, &deny_list);
+        assert_eq!(parsed, vec!["/repo/src/main.rs".to_string()]);
+    }
+
     fn rg_available() -> bool {
         StdCommand::new("rg")
             .arg("--version")
@@ -6,6 +6,7 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use async_trait::async_trait;
+use codex_protocol::permissions::ReadDenyList;
 use codex_utils_string::take_bytes_at_char_boundary;
 use serde::Deserialize;
 use tokio::fs;
@@ -14,7 +15,10 @@ use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::is_read_denied;
 use crate::tools::handlers::parse_arguments;
+use crate::tools::handlers::read_denied_error;
+use crate::tools::handlers::read_deny_list;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 
@@ -53,7 +57,7 @@ impl ToolHandler for ListDirHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation { payload, turn, .. } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -98,7 +102,12 @@ impl ToolHandler for ListDirHandler {
             ));
         }
 
-        let entries = list_dir_slice(&path, offset, limit, depth).await?;
+        let deny_list = read_deny_list(&turn)?;
+        if is_read_denied(&deny_list, &path).await {
+            return Err(read_denied_error(&path));
+        }
+
+        let entries = list_dir_slice(&path, offset, limit, depth, &deny_list).await?;
         let mut output = Vec::with_capacity(entries.len() + 1);
         output.push(format!("Absolute path: {}", path.display()));
         output.extend(entries);
@@ -114,9 +123,10 @@ async fn list_dir_slice(
     offset: usize,
     limit: usize,
     depth: usize,
+    deny_list: &ReadDenyList,
 ) -> Result<Vec<String>, FunctionCallError> {
     let mut entries = Vec::new();
-    collect_entries(path, Path::new(""), depth, &mut entries).await?;
+    collect_entries(path, Path::new(""), depth, deny_list, &mut entries).await?;
 
     if entries.is_empty() {
         return Ok(Vec::new());
@@ -152,6 +162,7 @@ async fn collect_entries(
     dir_path: &Path,
     relative_prefix: &Path,
     depth: usize,
+    deny_list: &ReadDenyList,
     entries: &mut Vec<DirEntry>,
 ) -> Result<(), FunctionCallError> {
     let mut queue = VecDeque::new();
@@ -167,6 +178,10 @@ async fn collect_entries(
         while let Some(entry) = read_dir.next_entry().await.map_err(|err| {
             FunctionCallError::RespondToModel(format!("failed to read directory: {err}"))
         })? {
+            // Entries hidden by `deny_read` are neither listed nor descended into.
+            if deny_list.is_denied(&entry.path()) {
+                continue;
+            }
             let file_type = entry.file_type().await.map_err(|err| {
                 FunctionCallError::RespondToModel(format!("failed to inspect entry: {err}"))
             })?;
@@ -306,7 +321,7 @@ mod tests {
             symlink(dir_path.join("entry.txt"), &link_path).expect("create symlink");
         }
 
-        let entries = list_dir_slice(dir_path, 1, 20, 3)
+        let entries = list_dir_slice(dir_path, 1, 20, 3, &ReadDenyList::default())
             .await
             .expect("list directory");
 
@@ -340,7 +355,7 @@ mod tests {
             .await
             .expect("create sub dir");
 
-        let err = list_dir_slice(dir_path, 10, 1, 2)
+        let err = list_dir_slice(dir_path, 10, 1, 2, &ReadDenyList::default())
             .await
             .expect_err("offset exceeds entries");
         assert_eq!(
@@ -367,7 +382,7 @@ mod tests {
             .await
             .expect("write deeper");
 
-        let entries_depth_one = list_dir_slice(dir_path, 1, 10, 1)
+        let entries_depth_one = list_dir_slice(dir_path, 1, 10, 1, &ReadDenyList::default())
             .await
             .expect("list depth 1");
         assert_eq!(
@@ -375,7 +390,7 @@ mod tests {
             vec!["nested/".to_string(), "root.txt".to_string(),]
         );
 
-        let entries_depth_two = list_dir_slice(dir_path, 1, 20, 2)
+        let entries_depth_two = list_dir_slice(dir_path, 1, 20, 2, &ReadDenyList::default())
             .await
             .expect("list depth 2");
         assert_eq!(
@@ -388,7 +403,7 @@ mod tests {
             ]
         );
 
-        let entries_depth_three = list_dir_slice(dir_path, 1, 30, 3)
+        let entries_depth_three = list_dir_slice(dir_path, 1, 30, 3, &ReadDenyList::default())
             .await
             .expect("list depth 3");
         assert_eq!(
@@ -420,7 +435,7 @@ mod tests {
             .await
             .expect("write b child");
 
-        let first_page = list_dir_slice(dir_path, 1, 2, 2)
+        let first_page = list_dir_slice(dir_path, 1, 2, 2, &ReadDenyList::default())
             .await
             .expect("list page one");
         assert_eq!(
@@ -432,7 +447,7 @@ mod tests {
             ]
         );
 
-        let second_page = list_dir_slice(dir_path, 3, 2, 2)
+        let second_page = list_dir_slice(dir_path, 3, 2, 2, &ReadDenyList::default())
             .await
             .expect("list page two");
         assert_eq!(
@@ -455,7 +470,7 @@ mod tests {
             .await
             .expect("write gamma");
 
-        let entries = list_dir_slice(dir_path, 2, usize::MAX, 1)
+        let entries = list_dir_slice(dir_path, 2, usize::MAX, 1, &ReadDenyList::default())
             .await
             .expect("list without overflow");
         assert_eq!(
@@ -476,7 +491,7 @@ mod tests {
                 .expect("write file");
         }
 
-        let entries = list_dir_slice(dir_path, 1, 25, 1)
+        let entries = list_dir_slice(dir_path, 1, 25, 1, &ReadDenyList::default())
             .await
             .expect("list directory");
         assert_eq!(entries.len(), 26);
@@ -498,7 +513,8 @@ mod tests {
         tokio::fs::write(nested.join("child.txt"), b"child").await?;
         tokio::fs::write(deeper.join("grandchild.txt"), b"deep").await?;
 
-        let entries_depth_three = list_dir_slice(dir_path, 1, 3, 3).await?;
+        let entries_depth_three =
+            list_dir_slice(dir_path, 1, 3, 3, &ReadDenyList::default()).await?;
         assert_eq!(
             entries_depth_three,
             vec![
@@ -511,4 +527,22 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn skips_entries_hidden_by_deny_read() -> anyhow::Result<()> {
+        let temp = tempdir()?;
+        let dir_path = temp.path();
+        let secrets = dir_path.join("secrets");
+        tokio::fs::create_dir(&secrets).await?;
+        tokio::fs::write(secrets.join("key.pem"), b"key").await?;
+        tokio::fs::write(dir_path.join(".env"), b"TOKEN=1").await?;
+        tokio::fs::write(dir_path.join("main.rs"), b"fn main() {}").await?;
+
+        let deny_list =
+            ReadDenyList::new(&["**/.env".to_string(), "secrets".to_string()], dir_path)?;
+        let entries = list_dir_slice(dir_path, 1, 10, 2, &deny_list).await?;
+        assert_eq!(entries, vec!["main.rs".to_string()]);
+
+        Ok(())
+    }
 }
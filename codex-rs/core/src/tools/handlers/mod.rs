@@ -1,6 +1,7 @@
 pub(crate) mod agent_jobs;
 pub mod apply_patch;
 mod artifacts;
+mod code_search;
 mod dynamic;
 mod grep_files;
 mod js_repl;
@@ -24,12 +25,15 @@ use serde_json::Value;
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::normalize_additional_permissions;
 pub use apply_patch::ApplyPatchHandler;
 pub use artifacts::ArtifactsHandler;
+pub use code_search::CodeSearchHandler;
 use codex_protocol::models::PermissionProfile;
+use codex_protocol::permissions::ReadDenyList;
 use codex_protocol::protocol::AskForApproval;
 pub use dynamic::DynamicToolHandler;
 pub use grep_files::GrepFilesHandler;
@@ -61,6 +65,34 @@ where
     })
 }
 
+/// Compiles the turn's `deny_read` patterns for the file-reading tools.
+fn read_deny_list(turn: &TurnContext) -> Result<ReadDenyList, FunctionCallError> {
+    turn.file_system_sandbox_policy
+        .read_deny_list(&turn.cwd)
+        .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))
+}
+
+/// Checks `path` and, when it resolves, its canonical form so a symlink cannot
+/// be used to reach a denied file.
+async fn is_read_denied(deny_list: &ReadDenyList, path: &Path) -> bool {
+    if deny_list.is_empty() {
+        return false;
+    }
+    if deny_list.is_denied(path) {
+        return true;
+    }
+    tokio::fs::canonicalize(path)
+        .await
+        .is_ok_and(|canonical| deny_list.is_denied(&canonical))
+}
+
+fn read_denied_error(path: &Path) -> FunctionCallError {
+    FunctionCallError::RespondToModel(format!(
+        "reading `{}` is blocked by the `deny_read` sandbox setting",
+        path.display()
+    ))
+}
+
 fn parse_arguments_with_base_path<T>(
     arguments: &str,
     base_path: &Path,
@@ -7,6 +7,7 @@
 
 use crate::agent::AgentStatus;
 use crate::agent::exceeds_thread_spawn_depth_limit;
+use crate::agent::worktree::WorktreeAction;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::config::Config;
@@ -53,6 +54,8 @@ pub(crate) const MAX_WAIT_TIMEOUT_MS: i64 = 3600 * 1000;
 #[derive(Debug, Deserialize)]
 struct CloseAgentArgs {
     id: String,
+    #[serde(default)]
+    worktree: WorktreeAction,
 }
 
 #[async_trait]
@@ -102,6 +105,12 @@ mod spawn {
     use crate::agent::control::SpawnAgentOptions;
     use crate::agent::role::DEFAULT_ROLE_NAME;
     use crate::agent::role::apply_role_to_config;
+    use crate::agent::role::resolve_role_config;
+    use crate::agent::worktree::AgentWorktreeInfo;
+    use crate::agent::worktree::create_agent_worktree;
+    use crate::agent::worktree::discard_agent_worktree;
+    use crate::agent::worktree::worktree_info;
+    use crate::config::AgentIsolation;
 
     use crate::agent::exceeds_thread_spawn_depth_limit;
     use crate::agent::next_thread_spawn_depth;
@@ -114,12 +123,15 @@ mod spawn {
         agent_type: Option<String>,
         #[serde(default)]
         fork_context: bool,
+        isolation: Option<AgentIsolation>,
     }
 
     #[derive(Debug, Serialize)]
     struct SpawnAgentResult {
         agent_id: String,
         nickname: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        worktree: Option<AgentWorktreeInfo>,
     }
 
     pub async fn handle(
@@ -162,6 +174,22 @@ mod spawn {
             .map_err(FunctionCallError::RespondToModel)?;
         apply_spawn_agent_runtime_overrides(&mut config, turn.as_ref())?;
         apply_spawn_agent_overrides(&mut config, child_depth);
+        let isolation = args.isolation.unwrap_or_else(|| {
+            resolve_role_config(&config, role_name.unwrap_or(DEFAULT_ROLE_NAME))
+                .and_then(|role| role.isolation)
+                .unwrap_or_default()
+        });
+        let worktree = match isolation {
+            AgentIsolation::None => None,
+            AgentIsolation::Worktree => {
+                let worktree = create_agent_worktree(&config.codex_home, &config.cwd)
+                    .await
+                    .map_err(FunctionCallError::RespondToModel)?;
+                config.cwd = worktree.cwd();
+                Some(worktree)
+            }
+        };
+        let worktree_info = worktree.as_ref().map(worktree_info);
 
         let result = session
             .services
@@ -180,6 +208,16 @@ mod spawn {
             )
             .await
             .map_err(collab_spawn_error);
+        if let Some(worktree) = worktree {
+            match &result {
+                Ok(thread_id) => session
+                    .services
+                    .agent_control
+                    .worktrees()
+                    .insert(*thread_id, worktree),
+                Err(_) => discard_agent_worktree(worktree).await,
+            }
+        }
         let (new_thread_id, status) = match &result {
             Ok(thread_id) => (
                 Some(*thread_id),
@@ -220,6 +258,7 @@ mod spawn {
         let content = serde_json::to_string(&SpawnAgentResult {
             agent_id: new_thread_id.to_string(),
             nickname,
+            worktree: worktree_info,
         })
         .map_err(|err| {
             FunctionCallError::Fatal(format!("failed to serialize spawn_agent result: {err}"))
@@ -467,6 +506,7 @@ mod resume_agent {
 pub(crate) mod wait {
     use super::*;
     use crate::agent::status::is_final;
+    use crate::agent::worktree::AgentWorktreeReview;
     use futures::FutureExt;
     use futures::StreamExt;
     use futures::stream::FuturesUnordered;
@@ -488,6 +528,9 @@ pub(crate) mod wait {
     pub(crate) struct WaitResult {
         pub(crate) status: HashMap<ThreadId, AgentStatus>,
         pub(crate) timed_out: bool,
+        /// Changes made by finished agents that run in their own git worktree.
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        pub(crate) worktrees: HashMap<ThreadId, AgentWorktreeReview>,
     }
 
     pub async fn handle(
@@ -622,9 +665,16 @@ pub(crate) mod wait {
         // Convert payload.
         let statuses_map = statuses.clone().into_iter().collect::<HashMap<_, _>>();
         let agent_statuses = build_wait_agent_statuses(&statuses_map, &receiver_agents);
+        let mut worktrees = HashMap::new();
+        for id in statuses_map.keys() {
+            if let Some(review) = session.services.agent_control.worktrees().review(*id).await {
+                worktrees.insert(*id, review);
+            }
+        }
         let result = WaitResult {
             status: statuses_map.clone(),
             timed_out: statuses.is_empty(),
+            worktrees,
         };
 
         // Final event emission.
@@ -676,11 +726,14 @@ pub(crate) mod wait {
 
 pub mod close_agent {
     use super::*;
+    use crate::agent::worktree::AgentWorktreeOutcome;
     use std::sync::Arc;
 
     #[derive(Debug, Deserialize, Serialize)]
     pub(super) struct CloseAgentResult {
         pub(super) status: AgentStatus,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub(super) worktree: Option<AgentWorktreeOutcome>,
     }
 
     pub async fn handle(
@@ -760,10 +813,22 @@ pub mod close_agent {
             )
             .await;
         result?;
+        let worktree = match session
+            .services
+            .agent_control
+            .worktrees()
+            .finish(agent_id, args.worktree)
+            .await
+        {
+            Some(Ok(outcome)) => Some(outcome),
+            Some(Err(err)) => return Err(FunctionCallError::RespondToModel(err)),
+            None => None,
+        };
 
-        let content = serde_json::to_string(&CloseAgentResult { status }).map_err(|err| {
-            FunctionCallError::Fatal(format!("failed to serialize close_agent result: {err}"))
-        })?;
+        let content =
+            serde_json::to_string(&CloseAgentResult { status, worktree }).map_err(|err| {
+                FunctionCallError::Fatal(format!("failed to serialize close_agent result: {err}"))
+            })?;
 
         Ok(ToolOutput::Function {
             body: FunctionCallOutputBody::Text(content),
@@ -1842,7 +1907,8 @@ mod tests {
                     (id_a, AgentStatus::NotFound),
                     (id_b, AgentStatus::NotFound),
                 ]),
-                timed_out: false
+                timed_out: false,
+                worktrees: HashMap::new(),
             }
         );
         assert_eq!(success, None);
@@ -1883,7 +1949,8 @@ mod tests {
             result,
             wait::WaitResult {
                 status: HashMap::new(),
-                timed_out: true
+                timed_out: true,
+                worktrees: HashMap::new(),
             }
         );
         assert_eq!(success, None);
@@ -1980,7 +2047,8 @@ mod tests {
             result,
             wait::WaitResult {
                 status: HashMap::from([(agent_id, AgentStatus::Shutdown)]),
-                timed_out: false
+                timed_out: false,
+                worktrees: HashMap::new(),
             }
         );
         assert_eq!(success, None);
@@ -10,7 +10,10 @@ use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::is_read_denied;
 use crate::tools::handlers::parse_arguments;
+use crate::tools::handlers::read_denied_error;
+use crate::tools::handlers::read_deny_list;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 
@@ -99,7 +102,7 @@ impl ToolHandler for ReadFileHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation { payload, turn, .. } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -139,6 +142,11 @@ impl ToolHandler for ReadFileHandler {
             ));
         }
 
+        let deny_list = read_deny_list(&turn)?;
+        if is_read_denied(&deny_list, &path).await {
+            return Err(read_denied_error(&path));
+        }
+
         let collected = match mode {
             ReadMode::Slice => slice::read(&path, offset, limit).await?,
             ReadMode::Indentation => {
@@ -330,6 +330,13 @@ impl ShellHandler {
                 explicit_env_overrides.insert(key.clone(), value.clone());
             }
         }
+        let secret_env = session.services.exec_secrets.resolve_env(
+            &turn.shell_environment_policy,
+            &exec_params.command,
+            &exec_params.cwd,
+        );
+        exec_params.env.extend(secret_env.clone());
+        explicit_env_overrides.extend(secret_env);
 
         let request_permission_enabled = session.features().enabled(Feature::RequestPermissions);
         let normalized_additional_permissions = normalize_and_validate_additional_permissions(
@@ -389,6 +396,8 @@ impl ShellHandler {
                 sandbox_policy: turn.sandbox_policy.get(),
                 sandbox_permissions: exec_params.sandbox_permissions,
                 prefix_rule,
+                cwd: Some(exec_params.cwd.as_path()),
+                workspace_root: Some(turn.cwd.as_path()),
             })
             .await;
 
@@ -273,7 +273,10 @@ impl ToolHandler for UnifiedExecHandler {
             }
         };
 
-        let content = format_response(&response);
+        let content = session
+            .services
+            .exec_secrets
+            .redact(&format_response(&response));
 
         Ok(ToolOutput::Function {
             body: FunctionCallOutputBody::Text(content),
@@ -5,6 +5,7 @@ use crate::guardian::review_approval_request;
 use crate::guardian::routes_approval_to_guardian;
 use crate::network_policy_decision::denied_network_policy_message;
 use crate::tools::sandboxing::ToolError;
+use crate::tools::sandboxing::approval_wait_span;
 use codex_network_proxy::BlockedRequest;
 use codex_network_proxy::BlockedRequestObserver;
 use codex_network_proxy::NetworkDecision;
@@ -28,6 +29,7 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 use tokio::sync::Notify;
 use tokio::sync::RwLock;
+use tracing::Instrument;
 use tracing::warn;
 use uuid::Uuid;
 
@@ -340,6 +342,8 @@ impl NetworkApprovalService {
             host: request.host.clone(),
             protocol,
         };
+        let approval_id = Self::approval_id_for_key(&key);
+        let wait_span = approval_wait_span(&turn_context, "network_access", &approval_id);
         let approval_decision = if routes_approval_to_guardian(&turn_context) {
             review_approval_request(
                 &session,
@@ -355,9 +359,9 @@ impl NetworkApprovalService {
                 },
                 Some(policy_denial_message.clone()),
             )
+            .instrument(wait_span)
             .await
         } else {
-            let approval_id = Self::approval_id_for_key(&key);
             let prompt_command = vec!["network-access".to_string(), target.clone()];
             let available_decisions = None;
             session
@@ -373,6 +377,7 @@ impl NetworkApprovalService {
                     None,
                     available_decisions,
                 )
+                .instrument(wait_span)
                 .await
         };
 
@@ -451,7 +456,9 @@ impl NetworkApprovalService {
                     PendingApprovalDecision::Deny
                 }
             },
-            ReviewDecision::Denied | ReviewDecision::Abort => {
+            ReviewDecision::ApprovedHunks { .. }
+            | ReviewDecision::Denied
+            | ReviewDecision::Abort => {
                 if routes_approval_to_guardian(&turn_context) {
                     self.record_outcome_for_single_active_call(
                         NetworkApprovalOutcome::DeniedByPolicy(
@@ -26,11 +26,13 @@ use crate::tools::sandboxing::SandboxOverride;
 use crate::tools::sandboxing::ToolCtx;
 use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
+use crate::tools::sandboxing::approval_wait_span;
 use crate::tools::sandboxing::default_exec_approval_requirement;
 use codex_otel::ToolDecisionSource;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::NetworkPolicyRuleAction;
 use codex_protocol::protocol::ReviewDecision;
+use tracing::Instrument;
 
 pub(crate) struct ToolOrchestrator {
     sandbox: SandboxManager,
@@ -137,12 +139,19 @@ impl ToolOrchestrator {
                     retry_reason: reason,
                     network_approval_context: None,
                 };
-                let decision = tool.start_approval_async(req, approval_ctx).await;
+                let decision = tool
+                    .start_approval_async(req, approval_ctx)
+                    .instrument(approval_wait_span(turn_ctx, otel_tn, otel_ci))
+                    .await;
 
                 otel.tool_decision(otel_tn, otel_ci, &decision, otel_user.clone());
 
                 match decision {
-                    ReviewDecision::Denied | ReviewDecision::Abort => {
+                    // Runtimes that support hunk-by-hunk review resolve it to
+                    // `Approved` themselves.
+                    ReviewDecision::ApprovedHunks { .. }
+                    | ReviewDecision::Denied
+                    | ReviewDecision::Abort => {
                         let reason = if routes_approval_to_guardian(turn_ctx) {
                             GUARDIAN_REJECTION_MESSAGE.to_string()
                         } else {
@@ -283,11 +292,16 @@ impl ToolOrchestrator {
                         network_approval_context: network_approval_context.clone(),
                     };
 
-                    let decision = tool.start_approval_async(req, approval_ctx).await;
+                    let decision = tool
+                        .start_approval_async(req, approval_ctx)
+                        .instrument(approval_wait_span(turn_ctx, otel_tn, otel_ci))
+                        .await;
                     otel.tool_decision(otel_tn, otel_ci, &decision, otel_user);
 
                     match decision {
-                        ReviewDecision::Denied | ReviewDecision::Abort => {
+                        ReviewDecision::ApprovedHunks { .. }
+                        | ReviewDecision::Denied
+                        | ReviewDecision::Abort => {
                             let reason = if routes_approval_to_guardian(turn_ctx) {
                                 GUARDIAN_REJECTION_MESSAGE.to_string()
                             } else {
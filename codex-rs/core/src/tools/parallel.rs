@@ -66,6 +66,7 @@ impl ToolCallRuntime {
             otel.name = call.tool_name.as_str(),
             tool_name = call.tool_name.as_str(),
             call_id = call.call_id.as_str(),
+            tool_kind = call.payload.kind(),
             aborted = false,
         );
 
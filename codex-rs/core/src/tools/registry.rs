@@ -15,12 +15,14 @@ use crate::tools::context::ToolPayload;
 use async_trait::async_trait;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterToolUse;
+use codex_hooks::HookEventBeforeToolUse;
+use codex_hooks::HookEventName;
 use codex_hooks::HookPayload;
 use codex_hooks::HookResult;
 use codex_hooks::HookToolInput;
 use codex_hooks::HookToolInputLocalShell;
-use codex_hooks::HookToolKind;
 use codex_protocol::models::ResponseInputItem;
+use codex_protocol::models::ShellToolCallParams;
 use codex_utils_readiness::Readiness;
 use tracing::warn;
 
@@ -78,7 +80,7 @@ impl ToolRegistry {
 
     pub async fn dispatch(
         &self,
-        invocation: ToolInvocation,
+        mut invocation: ToolInvocation,
     ) -> Result<ResponseInputItem, FunctionCallError> {
         let tool_name = invocation.tool_name.clone();
         let call_id_owned = invocation.call_id.clone();
@@ -162,6 +164,35 @@ impl ToolRegistry {
             return Err(FunctionCallError::Fatal(message));
         }
 
+        match dispatch_before_tool_use_hook(&invocation).await {
+            Ok(Some(payload)) => invocation.payload = payload,
+            Ok(None) => {}
+            Err(err) => {
+                otel.tool_result_with_tags(
+                    tool_name.as_ref(),
+                    &call_id_owned,
+                    log_payload.as_ref(),
+                    Duration::ZERO,
+                    false,
+                    &err.to_string(),
+                    &metric_tags,
+                    mcp_server_ref,
+                    mcp_server_origin_ref,
+                );
+                return Err(err);
+            }
+        }
+
+        // `codex debug replay` answers tool calls with the outputs recorded in
+        // the replayed session instead of running them.
+        if let Some(recorded) = invocation.session.services.replay_tool_outputs.as_ref() {
+            return recorded.get(&call_id_owned).cloned().ok_or_else(|| {
+                FunctionCallError::RespondToModel(format!(
+                    "no recorded output for tool call {call_id_owned}"
+                ))
+            });
+        }
+
         let is_mutating = handler.is_mutating(&invocation).await;
         let output_cell = tokio::sync::Mutex::new(None);
         let invocation_for_tool = invocation.clone();
@@ -357,13 +388,119 @@ impl From<&ToolPayload> for HookToolInput {
     }
 }
 
-fn hook_tool_kind(tool_input: &HookToolInput) -> HookToolKind {
-    match tool_input {
-        HookToolInput::Function { .. } => HookToolKind::Function,
-        HookToolInput::Custom { .. } => HookToolKind::Custom,
-        HookToolInput::LocalShell { .. } => HookToolKind::LocalShell,
-        HookToolInput::Mcp { .. } => HookToolKind::Mcp,
+/// Applies a `before_tool_use` rewrite to the original payload. Returns `None` when the hook
+/// answered with an input of a different kind than the tool call it was given.
+fn rewritten_tool_payload(
+    original: &ToolPayload,
+    tool_input: HookToolInput,
+) -> Option<ToolPayload> {
+    match (original, tool_input) {
+        (ToolPayload::Function { .. }, HookToolInput::Function { arguments }) => {
+            Some(ToolPayload::Function { arguments })
+        }
+        (ToolPayload::Custom { .. }, HookToolInput::Custom { input }) => {
+            Some(ToolPayload::Custom { input })
+        }
+        (ToolPayload::LocalShell { params }, HookToolInput::LocalShell { params: rewritten }) => {
+            Some(ToolPayload::LocalShell {
+                params: ShellToolCallParams {
+                    command: rewritten.command,
+                    workdir: rewritten.workdir,
+                    timeout_ms: rewritten.timeout_ms,
+                    sandbox_permissions: rewritten.sandbox_permissions,
+                    prefix_rule: rewritten.prefix_rule,
+                    additional_permissions: params.additional_permissions.clone(),
+                    justification: rewritten.justification,
+                },
+            })
+        }
+        // Only the arguments of an MCP call can be rewritten; it keeps targeting the same tool.
+        (ToolPayload::Mcp { server, tool, .. }, HookToolInput::Mcp { arguments, .. }) => {
+            Some(ToolPayload::Mcp {
+                server: server.clone(),
+                tool: tool.clone(),
+                raw_arguments: arguments,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Runs the `before_tool_use` hooks. Returns the rewritten payload when a hook replaced the tool
+/// input, or an error when a hook denied the call (reported to the model) or aborted it.
+async fn dispatch_before_tool_use_hook(
+    invocation: &ToolInvocation,
+) -> Result<Option<ToolPayload>, FunctionCallError> {
+    let session = invocation.session.as_ref();
+    let turn = invocation.turn.as_ref();
+    if !session.hooks().has_hooks_for(HookEventName::BeforeToolUse) {
+        return Ok(None);
+    }
+    let tool_input = HookToolInput::from(&invocation.payload);
+    let hook_outcomes = session
+        .hooks()
+        .dispatch(HookPayload {
+            session_id: session.conversation_id,
+            cwd: turn.cwd.clone(),
+            client: turn.app_server_client_name.clone(),
+            triggered_at: chrono::Utc::now(),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: turn.sub_id.clone(),
+                    call_id: invocation.call_id.clone(),
+                    tool_name: invocation.tool_name.clone(),
+                    tool_kind: tool_input.kind(),
+                    tool_input,
+                },
+            },
+        })
+        .await;
+
+    let mut rewritten_payload = None;
+    for hook_outcome in hook_outcomes {
+        let hook_name = hook_outcome.hook_name;
+        match hook_outcome.result {
+            HookResult::Success => {}
+            HookResult::RewriteToolInput(tool_input) => {
+                let payload = rewritten_payload.as_ref().unwrap_or(&invocation.payload);
+                let Some(payload) = rewritten_tool_payload(payload, tool_input) else {
+                    return Err(FunctionCallError::Fatal(format!(
+                        "before_tool_use hook '{hook_name}' rewrote {} to an incompatible tool input",
+                        invocation.tool_name
+                    )));
+                };
+                rewritten_payload = Some(payload);
+            }
+            HookResult::Deny { reason } => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "tool call denied by hook '{hook_name}': {reason}"
+                )));
+            }
+            HookResult::FailedContinue(error) => {
+                warn!(
+                    call_id = %invocation.call_id,
+                    tool_name = %invocation.tool_name,
+                    hook_name = %hook_name,
+                    error = %error,
+                    "before_tool_use hook failed; continuing"
+                );
+            }
+            HookResult::FailedAbort(error) => {
+                warn!(
+                    call_id = %invocation.call_id,
+                    tool_name = %invocation.tool_name,
+                    hook_name = %hook_name,
+                    error = %error,
+                    "before_tool_use hook failed; aborting operation"
+                );
+                return Err(FunctionCallError::Fatal(format!(
+                    "before_tool_use hook '{hook_name}' failed and aborted operation: {error}"
+                )));
+            }
+        }
     }
+
+    Ok(rewritten_payload)
 }
 
 struct AfterToolUseHookDispatch<'a> {
@@ -394,7 +531,7 @@ async fn dispatch_after_tool_use_hook(
                     turn_id: turn.sub_id.clone(),
                     call_id: invocation.call_id.clone(),
                     tool_name: invocation.tool_name.clone(),
-                    tool_kind: hook_tool_kind(&tool_input),
+                    tool_kind: tool_input.kind(),
                     tool_input,
                     executed: dispatch.executed,
                     success: dispatch.success,
@@ -416,7 +553,8 @@ async fn dispatch_after_tool_use_hook(
     for hook_outcome in hook_outcomes {
         let hook_name = hook_outcome.hook_name;
         match hook_outcome.result {
-            HookResult::Success => {}
+            // The tool already ran, so there is nothing left to deny or rewrite.
+            HookResult::Success | HookResult::Deny { .. } | HookResult::RewriteToolInput(_) => {}
             HookResult::FailedContinue(error) => {
                 warn!(
                     call_id = %invocation.call_id,
@@ -3,7 +3,10 @@
 //! Assumes `apply_patch` verification/approval happened upstream. Reuses that
 //! decision to avoid re-prompting, builds the self-invocation command for
 //! `codex --codex-run-as-apply-patch`, and runs under the current
-//! `SandboxAttempt` with a minimal environment.
+//! `SandboxAttempt` with a minimal environment. When the user reviewed the
+//! patch hunk by hunk, only the hunks they kept are applied.
+use crate::apply_patch::PatchHunkSelection;
+use crate::apply_patch::select_patch_hunks;
 use crate::exec::ExecToolCallOutput;
 use crate::guardian::GuardianReviewRequest;
 use crate::guardian::review_approval_request;
@@ -22,6 +25,7 @@ use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::with_cached_approval;
 use codex_apply_patch::ApplyPatchAction;
+use codex_apply_patch::CODEX_APPLY_PATCH_FUZZ_ENV_VAR;
 use codex_apply_patch::CODEX_CORE_APPLY_PATCH_ARG1;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::FileChange;
@@ -43,11 +47,28 @@ pub struct ApplyPatchRequest {
 }
 
 #[derive(Default)]
-pub struct ApplyPatchRuntime;
+pub struct ApplyPatchRuntime {
+    /// Set when the user approved only some hunks of the patch.
+    hunk_selection: Option<PatchHunkSelection>,
+}
 
 impl ApplyPatchRuntime {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Summary of a hunk-by-hunk review of the patch, for the model.
+    pub(crate) fn hunk_review_report(&self) -> Option<&str> {
+        self.hunk_selection
+            .as_ref()
+            .map(|selection| selection.report.as_str())
+    }
+
+    /// The changes the user kept in a hunk-by-hunk review of the patch.
+    pub(crate) fn selected_changes(&self) -> Option<&HashMap<PathBuf, FileChange>> {
+        self.hunk_selection
+            .as_ref()
+            .map(|selection| &selection.changes)
     }
 
     fn build_guardian_review_request(req: &ApplyPatchRequest) -> GuardianReviewRequest {
@@ -64,6 +85,7 @@ impl ApplyPatchRuntime {
 
     fn build_command_spec(
         req: &ApplyPatchRequest,
+        patch: &str,
         _codex_home: &std::path::Path,
     ) -> Result<CommandSpec, ToolError> {
         let exe = if let Some(path) = &req.codex_exe {
@@ -83,14 +105,15 @@ impl ApplyPatchRuntime {
         let program = exe.to_string_lossy().to_string();
         Ok(CommandSpec {
             program,
-            args: vec![
-                CODEX_CORE_APPLY_PATCH_ARG1.to_string(),
-                req.action.patch.clone(),
-            ],
+            args: vec![CODEX_CORE_APPLY_PATCH_ARG1.to_string(), patch.to_string()],
             cwd: req.action.cwd.clone(),
             expiration: req.timeout_ms.into(),
             // Run apply_patch with a minimal environment for determinism and to avoid leaks.
-            env: HashMap::new(),
+            // The fuzz factor is passed along so hunks land where they did during verification.
+            env: HashMap::from([(
+                CODEX_APPLY_PATCH_FUZZ_ENV_VAR.to_string(),
+                req.action.options.fuzz.to_string(),
+            )]),
             sandbox_permissions: SandboxPermissions::UseDefault,
             additional_permissions: None,
             justification: None,
@@ -133,30 +156,43 @@ impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
         let retry_reason = ctx.retry_reason.clone();
         let approval_keys = self.approval_keys(req);
         let changes = req.changes.clone();
+        let hunk_selection = &mut self.hunk_selection;
         Box::pin(async move {
             if routes_approval_to_guardian(turn) {
                 let request = ApplyPatchRuntime::build_guardian_review_request(req);
                 return review_approval_request(session, turn, request, retry_reason).await;
             }
-            if let Some(reason) = retry_reason {
+            let decision = if let Some(reason) = retry_reason {
                 let rx_approve = session
                     .request_patch_approval(turn, call_id, changes.clone(), Some(reason), None)
                     .await;
-                return rx_approve.await.unwrap_or_default();
-            }
+                rx_approve.await.unwrap_or_default()
+            } else {
+                with_cached_approval(
+                    &session.services,
+                    "apply_patch",
+                    approval_keys,
+                    || async move {
+                        let rx_approve = session
+                            .request_patch_approval(turn, call_id, changes, None, None)
+                            .await;
+                        rx_approve.await.unwrap_or_default()
+                    },
+                )
+                .await
+            };
 
-            with_cached_approval(
-                &session.services,
-                "apply_patch",
-                approval_keys,
-                || async move {
-                    let rx_approve = session
-                        .request_patch_approval(turn, call_id, changes, None, None)
-                        .await;
-                    rx_approve.await.unwrap_or_default()
-                },
-            )
-            .await
+            let ReviewDecision::ApprovedHunks { hunks } = &decision else {
+                return decision;
+            };
+            let selection = select_patch_hunks(&req.action.cwd, &req.changes, hunks);
+            let decision = if selection.patch.is_some() {
+                ReviewDecision::Approved
+            } else {
+                ReviewDecision::Denied
+            };
+            *hunk_selection = Some(selection);
+            decision
         })
     }
 
@@ -189,7 +225,12 @@ impl ToolRuntime<ApplyPatchRequest, ExecToolCallOutput> for ApplyPatchRuntime {
         attempt: &SandboxAttempt<'_>,
         ctx: &ToolCtx,
     ) -> Result<ExecToolCallOutput, ToolError> {
-        let spec = Self::build_command_spec(req, &ctx.turn.config.codex_home)?;
+        let patch = self
+            .hunk_selection
+            .as_ref()
+            .and_then(|selection| selection.patch.as_deref())
+            .unwrap_or(&req.action.patch);
+        let spec = Self::build_command_spec(req, patch, &ctx.turn.config.codex_home)?;
         let env = attempt
             .env_for(spec, None)
             .map_err(|err| ToolError::Codex(err.into()))?;
@@ -9,6 +9,7 @@ use crate::path_utils;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxPermissions;
 use crate::shell::Shell;
+use crate::shell::ShellType;
 use crate::skills::SkillMetadata;
 use crate::tools::sandboxing::ToolError;
 use codex_protocol::models::PermissionProfile;
@@ -53,18 +54,20 @@ pub(crate) fn build_command_spec(
     })
 }
 
-/// POSIX-only helper: for commands produced by `Shell::derive_exec_args`
-/// for Bash/Zsh/sh of the form `[shell_path, "-lc", "<script>"]`, and
-/// when a snapshot is configured on the session shell, rewrite the argv
-/// to a single non-login shell that sources the snapshot before running
-/// the original script:
+/// Unix-only helper: for commands produced by `Shell::derive_exec_args`
+/// for Bash/Zsh/sh/fish of the form `[shell_path, "-lc", "<script>"]` (or
+/// Nushell's `[shell_path, "-l", "-c", "<script>"]`), and when a snapshot is
+/// configured on the session shell, rewrite the argv to a single non-login
+/// session shell that sources the snapshot before running the original
+/// script:
 ///
 ///   shell -lc "<script>"
 ///   => user_shell -c ". SNAPSHOT (best effort); exec shell -c <script>"
 ///
-/// This wrapper script uses POSIX constructs (`if`, `.`, `exec`) so it can
-/// be run by Bash/Zsh/sh. On non-matching commands, or when command cwd does
-/// not match the snapshot cwd, this is a no-op.
+/// The wrapper script is written in the session shell's own syntax: POSIX
+/// constructs (`if`, `.`, `exec`) for Bash/Zsh/sh, and `source`/`exec` for
+/// fish and Nushell. On non-matching commands, or when command cwd does not
+/// match the snapshot cwd, this is a no-op.
 pub(crate) fn maybe_wrap_shell_lc_with_snapshot(
     command: &[String],
     session_shell: &Shell,
@@ -94,26 +97,60 @@ pub(crate) fn maybe_wrap_shell_lc_with_snapshot(
         return command.to_vec();
     }
 
-    if command.len() < 3 {
-        return command.to_vec();
-    }
-
-    let flag = command[1].as_str();
-    if flag != "-lc" {
-        return command.to_vec();
-    }
+    let script_index = match command {
+        [_, flag, _, ..] if flag == "-lc" => 2,
+        [_, login, flag, _, ..] if login == "-l" && flag == "-c" => 3,
+        _ => return command.to_vec(),
+    };
 
     let snapshot_path = snapshot.path.to_string_lossy();
     let shell_path = session_shell.shell_path.to_string_lossy();
-    let original_shell = shell_single_quote(&command[0]);
-    let original_script = shell_single_quote(&command[2]);
-    let snapshot_path = shell_single_quote(snapshot_path.as_ref());
-    let trailing_args = command[3..]
+    let original_shell = &command[0];
+    let original_script = &command[script_index];
+    let trailing_args = &command[script_index + 1..];
+    let rewritten_script = match session_shell.shell_type {
+        ShellType::Fish => fish_snapshot_wrapper(
+            snapshot_path.as_ref(),
+            original_shell,
+            original_script,
+            trailing_args,
+            explicit_env_overrides,
+        ),
+        ShellType::Nushell => nushell_snapshot_wrapper(
+            snapshot_path.as_ref(),
+            original_shell,
+            original_script,
+            trailing_args,
+            explicit_env_overrides,
+        ),
+        _ => posix_snapshot_wrapper(
+            snapshot_path.as_ref(),
+            original_shell,
+            original_script,
+            trailing_args,
+            explicit_env_overrides,
+        ),
+    };
+
+    vec![shell_path.to_string(), "-c".to_string(), rewritten_script]
+}
+
+fn posix_snapshot_wrapper(
+    snapshot_path: &str,
+    original_shell: &str,
+    original_script: &str,
+    trailing_args: &[String],
+    explicit_env_overrides: &HashMap<String, String>,
+) -> String {
+    let original_shell = shell_single_quote(original_shell);
+    let original_script = shell_single_quote(original_script);
+    let snapshot_path = shell_single_quote(snapshot_path);
+    let trailing_args = trailing_args
         .iter()
         .map(|arg| format!(" '{}'", shell_single_quote(arg)))
         .collect::<String>();
     let (override_captures, override_exports) = build_override_exports(explicit_env_overrides);
-    let rewritten_script = if override_exports.is_empty() {
+    if override_exports.is_empty() {
         format!(
             "if . '{snapshot_path}' >/dev/null 2>&1; then :; fi\n\nexec '{original_shell}' -c '{original_script}'{trailing_args}"
         )
@@ -121,17 +158,120 @@ pub(crate) fn maybe_wrap_shell_lc_with_snapshot(
         format!(
             "{override_captures}\n\nif . '{snapshot_path}' >/dev/null 2>&1; then :; fi\n\n{override_exports}\n\nexec '{original_shell}' -c '{original_script}'{trailing_args}"
         )
-    };
+    }
+}
 
-    vec![shell_path.to_string(), "-c".to_string(), rewritten_script]
+fn fish_snapshot_wrapper(
+    snapshot_path: &str,
+    original_shell: &str,
+    original_script: &str,
+    trailing_args: &[String],
+    explicit_env_overrides: &HashMap<String, String>,
+) -> String {
+    let original_shell = fish_single_quote(original_shell);
+    let original_script = fish_single_quote(original_script);
+    let snapshot_path = fish_single_quote(snapshot_path);
+    let trailing_args = trailing_args
+        .iter()
+        .map(|arg| format!(" '{}'", fish_single_quote(arg)))
+        .collect::<String>();
+    let keys = sorted_override_keys(explicit_env_overrides);
+    let mut sections = Vec::new();
+    if !keys.is_empty() {
+        sections.push(
+            keys.iter()
+                .enumerate()
+                .map(|(idx, key)| {
+                    format!(
+                        "set -l __codex_snapshot_override_set_{idx} (set -q {key}; and echo x)\nset -l __codex_snapshot_override_{idx} ${key}"
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+    }
+    sections.push(format!("source '{snapshot_path}' >/dev/null 2>&1"));
+    if !keys.is_empty() {
+        sections.push(
+            keys.iter()
+                .enumerate()
+                .map(|(idx, key)| {
+                    format!(
+                        "if test -n \"$__codex_snapshot_override_set_{idx}\"; set -gx {key} $__codex_snapshot_override_{idx}; else; set -e {key}; end"
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+    }
+    sections.push(format!(
+        "exec '{original_shell}' -c '{original_script}'{trailing_args}"
+    ));
+    sections.join("\n\n")
 }
 
-fn build_override_exports(explicit_env_overrides: &HashMap<String, String>) -> (String, String) {
+fn nushell_snapshot_wrapper(
+    snapshot_path: &str,
+    original_shell: &str,
+    original_script: &str,
+    trailing_args: &[String],
+    explicit_env_overrides: &HashMap<String, String>,
+) -> String {
+    let original_shell = nushell_raw_string(original_shell);
+    let original_script = nushell_raw_string(original_script);
+    let snapshot_path = nushell_raw_string(snapshot_path);
+    let trailing_args = trailing_args
+        .iter()
+        .map(|arg| format!(" {}", nushell_raw_string(arg)))
+        .collect::<String>();
+    let keys = sorted_override_keys(explicit_env_overrides);
+    let mut sections = Vec::new();
+    if !keys.is_empty() {
+        sections.push(
+            keys.iter()
+                .enumerate()
+                .map(|(idx, key)| {
+                    format!(
+                        "let __codex_snapshot_override_set_{idx} = ('{key}' in $env)\nlet __codex_snapshot_override_{idx} = $env.{key}?"
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+    }
+    // Nushell resolves `source` at parse time, so a broken snapshot cannot be
+    // skipped the way the POSIX wrapper does; snapshots are validated before use.
+    sections.push(format!("source {snapshot_path}"));
+    if !keys.is_empty() {
+        sections.push(
+            keys.iter()
+                .enumerate()
+                .map(|(idx, key)| {
+                    format!(
+                        "if $__codex_snapshot_override_set_{idx} {{ $env.{key} = $__codex_snapshot_override_{idx} }} else {{ hide-env -i {key} }}"
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+    }
+    sections.push(format!(
+        "exec {original_shell} -c {original_script}{trailing_args}"
+    ));
+    sections.join("\n\n")
+}
+
+fn sorted_override_keys(explicit_env_overrides: &HashMap<String, String>) -> Vec<&String> {
     let mut keys = explicit_env_overrides
         .keys()
         .filter(|key| is_valid_shell_variable_name(key))
         .collect::<Vec<_>>();
     keys.sort_unstable();
+    keys
+}
+
+fn build_override_exports(explicit_env_overrides: &HashMap<String, String>) -> (String, String) {
+    let keys = sorted_override_keys(explicit_env_overrides);
 
     if keys.is_empty() {
         return (String::new(), String::new());
@@ -176,10 +316,24 @@ fn shell_single_quote(input: &str) -> String {
     input.replace('\'', r#"'"'"'"#)
 }
 
+/// fish single-quoted strings only recognize `\\` and `\'` as escapes.
+fn fish_single_quote(input: &str) -> String {
+    input.replace('\\', r"\\").replace('\'', r"\'")
+}
+
+/// Nushell raw strings (`r#'...'#`) take their contents verbatim, so pick
+/// enough `#`s that the closing delimiter cannot appear in the input.
+fn nushell_raw_string(input: &str) -> String {
+    let mut hashes = String::from("#");
+    while input.contains(&format!("'{hashes}")) {
+        hashes.push('#');
+    }
+    format!("r{hashes}'{input}'{hashes}")
+}
+
 #[cfg(all(test, unix))]
 mod tests {
     use super::*;
-    use crate::shell::ShellType;
     use crate::shell_snapshot::ShellSnapshot;
     use pretty_assertions::assert_eq;
     use std::path::PathBuf;
@@ -322,6 +476,95 @@ mod tests {
         assert!(rewritten[2].contains("exec '/bin/bash' -c 'echo hello'"));
     }
 
+    #[test]
+    fn maybe_wrap_shell_lc_with_snapshot_uses_fish_bootstrap_shell() {
+        let dir = tempdir().expect("create temp dir");
+        let snapshot_path = dir.path().join("snapshot.fish");
+        std::fs::write(&snapshot_path, "# Snapshot file\n").expect("write snapshot");
+        let session_shell = shell_with_snapshot(
+            ShellType::Fish,
+            "/usr/bin/fish",
+            snapshot_path,
+            dir.path().to_path_buf(),
+        );
+        let command = vec![
+            "/usr/bin/fish".to_string(),
+            "-lc".to_string(),
+            r"echo 'it\'s'".to_string(),
+        ];
+
+        let rewritten = maybe_wrap_shell_lc_with_snapshot(
+            &command,
+            &session_shell,
+            dir.path(),
+            &HashMap::new(),
+        );
+
+        assert_eq!(rewritten[0], "/usr/bin/fish");
+        assert_eq!(rewritten[1], "-c");
+        assert!(rewritten[2].contains("source '"));
+        assert!(!rewritten[2].contains("if . '"));
+        assert!(rewritten[2].contains(r"exec '/usr/bin/fish' -c 'echo \'it\\\'s\''"));
+    }
+
+    #[test]
+    fn maybe_wrap_shell_lc_with_snapshot_uses_nushell_bootstrap_shell() {
+        let dir = tempdir().expect("create temp dir");
+        let snapshot_path = dir.path().join("snapshot.nu");
+        std::fs::write(&snapshot_path, "# Snapshot file\n").expect("write snapshot");
+        let session_shell = shell_with_snapshot(
+            ShellType::Nushell,
+            "/usr/bin/nu",
+            snapshot_path,
+            dir.path().to_path_buf(),
+        );
+        let command = vec![
+            "/usr/bin/nu".to_string(),
+            "-l".to_string(),
+            "-c".to_string(),
+            "print 'hello'#".to_string(),
+        ];
+
+        let rewritten = maybe_wrap_shell_lc_with_snapshot(
+            &command,
+            &session_shell,
+            dir.path(),
+            &HashMap::new(),
+        );
+
+        assert_eq!(rewritten[0], "/usr/bin/nu");
+        assert_eq!(rewritten[1], "-c");
+        assert!(rewritten[2].contains("source r#'"));
+        assert!(rewritten[2].contains("exec r#'/usr/bin/nu'# -c r##'print 'hello'#'##"));
+    }
+
+    #[test]
+    fn maybe_wrap_shell_lc_with_snapshot_skips_non_login_nushell() {
+        let dir = tempdir().expect("create temp dir");
+        let snapshot_path = dir.path().join("snapshot.nu");
+        std::fs::write(&snapshot_path, "# Snapshot file\n").expect("write snapshot");
+        let session_shell = shell_with_snapshot(
+            ShellType::Nushell,
+            "/usr/bin/nu",
+            snapshot_path,
+            dir.path().to_path_buf(),
+        );
+        let command = vec![
+            "/usr/bin/nu".to_string(),
+            "-c".to_string(),
+            "print hello".to_string(),
+        ];
+
+        let rewritten = maybe_wrap_shell_lc_with_snapshot(
+            &command,
+            &session_shell,
+            dir.path(),
+            &HashMap::new(),
+        );
+
+        assert_eq!(rewritten, command);
+    }
+
     #[test]
     fn maybe_wrap_shell_lc_with_snapshot_preserves_trailing_args() {
         let dir = tempdir().expect("create temp dir");
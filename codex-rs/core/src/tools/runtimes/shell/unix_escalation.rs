@@ -5,6 +5,7 @@ use crate::exec::ExecExpiration;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::is_likely_sandbox_denied;
+use crate::exec_policy::exec_policy_workspace_roots;
 use crate::exec_policy::prompt_is_rejected_by_policy;
 use crate::features::Feature;
 use crate::guardian::GuardianReviewRequest;
@@ -20,6 +21,7 @@ use crate::tools::sandboxing::SandboxAttempt;
 use crate::tools::sandboxing::SandboxablePreference;
 use crate::tools::sandboxing::ToolCtx;
 use crate::tools::sandboxing::ToolError;
+use crate::tools::sandboxing::approval_wait_span;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Evaluation;
 use codex_execpolicy::MatchOptions;
@@ -56,6 +58,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 use uuid::Uuid;
 
 pub(crate) struct PreparedUnifiedExecZshFork {
@@ -382,6 +385,7 @@ impl CoreShellActionProvider {
         let call_id = self.call_id.clone();
         let approval_id = Some(Uuid::new_v4().to_string());
         let tool_name = self.tool_name;
+        let wait_span = approval_wait_span(&turn, tool_name, &call_id);
         Ok(stopwatch
             .pause_for(async move {
                 if routes_approval_to_guardian(&turn) {
@@ -425,6 +429,7 @@ impl CoreShellActionProvider {
                     )
                     .await
             })
+            .instrument(wait_span)
             .await)
     }
 
@@ -539,7 +544,7 @@ impl CoreShellActionProvider {
                                 EscalationDecision::deny(Some("User denied execution".to_string()))
                             }
                         },
-                        ReviewDecision::Denied => {
+                        ReviewDecision::ApprovedHunks { .. } | ReviewDecision::Denied => {
                             EscalationDecision::deny(Some("User denied execution".to_string()))
                         }
                         ReviewDecision::Abort => {
@@ -650,6 +655,8 @@ impl EscalationPolicy for CoreShellActionProvider {
                 &policy,
                 program,
                 argv,
+                workdir,
+                exec_policy_workspace_roots(&self.turn.cwd, &self.sandbox_policy),
                 self.approval_policy,
                 &self.sandbox_policy,
                 self.sandbox_permissions,
@@ -702,6 +709,8 @@ fn evaluate_intercepted_exec_policy(
     policy: &Policy,
     program: &AbsolutePathBuf,
     argv: &[String],
+    workdir: &AbsolutePathBuf,
+    workspace_roots: Vec<AbsolutePathBuf>,
     approval_policy: AskForApproval,
     sandbox_policy: &SandboxPolicy,
     sandbox_permissions: SandboxPermissions,
@@ -739,6 +748,8 @@ fn evaluate_intercepted_exec_policy(
         &fallback,
         &MatchOptions {
             resolve_host_executables: true,
+            cwd: Some(workdir.clone()),
+            workspace_roots,
         },
     )
 }
@@ -343,6 +343,8 @@ fn evaluate_intercepted_exec_policy_uses_wrapper_command_when_shell_wrapper_pars
             "-lc".to_string(),
             "npm publish".to_string(),
         ],
+        &AbsolutePathBuf::try_from(host_absolute_path(&["workspace"])).unwrap(),
+        Vec::new(),
         AskForApproval::OnRequest,
         &SandboxPolicy::new_read_only_policy(),
         SandboxPermissions::UseDefault,
@@ -391,6 +393,8 @@ fn evaluate_intercepted_exec_policy_matches_inner_shell_commands_when_enabled()
             "-lc".to_string(),
             "npm publish".to_string(),
         ],
+        &AbsolutePathBuf::try_from(host_absolute_path(&["workspace"])).unwrap(),
+        Vec::new(),
         AskForApproval::OnRequest,
         &SandboxPolicy::new_read_only_policy(),
         SandboxPermissions::UseDefault,
@@ -406,6 +410,7 @@ fn evaluate_intercepted_exec_policy_matches_inner_shell_commands_when_enabled()
                 decision: Decision::Prompt,
                 resolved_program: None,
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         }
     );
@@ -430,6 +435,8 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
         &policy,
         &program,
         &["git".to_string(), "status".to_string()],
+        &AbsolutePathBuf::try_from(host_absolute_path(&["workspace"])).unwrap(),
+        Vec::new(),
         AskForApproval::OnRequest,
         &SandboxPolicy::new_read_only_policy(),
         SandboxPermissions::UseDefault,
@@ -445,6 +452,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
                 decision: Decision::Prompt,
                 resolved_program: Some(program),
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         }
     );
@@ -474,6 +482,8 @@ host_executable(name = "git", paths = ["{allowed_git_literal}"])
         &policy,
         &program,
         &["git".to_string(), "status".to_string()],
+        &AbsolutePathBuf::try_from(host_absolute_path(&["workspace"])).unwrap(),
+        Vec::new(),
         AskForApproval::OnRequest,
         &SandboxPolicy::new_read_only_policy(),
         SandboxPermissions::UseDefault,
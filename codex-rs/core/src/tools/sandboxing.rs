@@ -7,6 +7,7 @@
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::error::CodexErr;
+use crate::guardian::routes_approval_to_guardian;
 use crate::protocol::SandboxPolicy;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxManager;
@@ -118,6 +119,21 @@ pub(crate) struct ApprovalCtx<'a> {
     pub network_approval_context: Option<NetworkApprovalContext>,
 }
 
+/// Span covering the time a tool call waits for an approval decision, so a
+/// slow reviewer shows up separately from the tool's own execution in traces.
+pub(crate) fn approval_wait_span(
+    turn: &TurnContext,
+    tool_name: &str,
+    call_id: &str,
+) -> tracing::Span {
+    let reviewer = if routes_approval_to_guardian(turn) {
+        "guardian"
+    } else {
+        "user"
+    };
+    tracing::info_span!("approval_wait", tool_name, call_id, reviewer)
+}
+
 // Specifies what tool orchestrator should do with a given tool call.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) enum ExecApprovalRequirement {
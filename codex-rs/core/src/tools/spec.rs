@@ -64,6 +64,7 @@ pub(crate) struct ToolsConfig {
     pub image_gen_tool: bool,
     pub agent_roles: BTreeMap<String, AgentRoleConfig>,
     pub search_tool: bool,
+    pub code_search: bool,
     pub request_permission_enabled: bool,
     pub js_repl_enabled: bool,
     pub js_repl_tools_only: bool,
@@ -100,6 +101,7 @@ impl ToolsConfig {
         let include_default_mode_request_user_input =
             include_request_user_input && features.enabled(Feature::DefaultModeRequestUserInput);
         let include_search_tool = features.enabled(Feature::Apps);
+        let include_code_search = features.enabled(Feature::CodeSearch);
         let include_artifact_tools =
             features.enabled(Feature::Artifact) && codex_artifacts::can_manage_artifact_runtime();
         let include_image_gen_tool =
@@ -165,6 +167,7 @@ impl ToolsConfig {
             image_gen_tool: include_image_gen_tool,
             agent_roles: BTreeMap::new(),
             search_tool: include_search_tool,
+            code_search: include_code_search,
             request_permission_enabled,
             js_repl_enabled: include_js_repl,
             js_repl_tools_only: include_js_repl_tools_only,
@@ -747,6 +750,15 @@ fn create_spawn_agent_tool(config: &ToolsConfig) -> ToolSpec {
                 ),
             },
         ),
+        (
+            "isolation".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Either `none` or `worktree`. With `worktree`, the agent works in its own git worktree and branch created from the current HEAD, so its edits cannot collide with yours or other agents'. `wait` reports the agent's diff once it finishes, and `close_agent` merges, keeps or discards it. Defaults to the role's setting, or `none`."
+                        .to_string(),
+                ),
+            },
+        ),
     ]);
 
     ToolSpec::Function(ResponsesApiTool {
@@ -1108,6 +1120,15 @@ fn create_close_agent_tool() -> ToolSpec {
             description: Some("Agent id to close (from spawn_agent).".to_string()),
         },
     );
+    properties.insert(
+        "worktree".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "For agents spawned with worktree isolation: `merge` applies the agent's changes to your working tree, `keep` (default) commits them to the agent's branch for later review, and `discard` drops them. The worktree itself is always removed."
+                    .to_string(),
+            ),
+        },
+    );
 
     ToolSpec::Function(ResponsesApiTool {
         name: "close_agent".to_string(),
@@ -1189,6 +1210,64 @@ fn create_test_sync_tool() -> ToolSpec {
     })
 }
 
+fn create_code_search_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Identifier for `definition` and `references`; whitespace-separated terms \
+                     for `text`."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "mode".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "`definition` finds where a symbol is defined, `references` finds where an \
+                     identifier is used (skipping comments and strings), and `text` (the \
+                     default) ranks lines containing the terms."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Directory or file to search within. Defaults to the session's working \
+                     directory."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of lines to return (defaults to 50).".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "code_search".to_string(),
+        description: "Searches an index of the git repository that is kept up to date as \
+                      files change. Prefer it over shell searches in large repositories. Returns \
+                      `path:line: text` lines, tagging definitions with their kind."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_grep_files_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -1809,6 +1888,7 @@ pub(crate) fn build_specs(
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
     use crate::tools::handlers::ArtifactsHandler;
+    use crate::tools::handlers::CodeSearchHandler;
     use crate::tools::handlers::DynamicToolHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::JsReplHandler;
@@ -1931,6 +2011,12 @@ pub(crate) fn build_specs(
         builder.register_handler("apply_patch", apply_patch_handler);
     }
 
+    if config.code_search {
+        let code_search_handler = Arc::new(CodeSearchHandler);
+        builder.push_spec_with_parallel_support(create_code_search_tool(), true);
+        builder.register_handler("code_search", code_search_handler);
+    }
+
     if config
         .experimental_supported_tools
         .contains(&"grep_files".to_string())
@@ -3049,6 +3135,33 @@ mod tests {
         assert!(tools.iter().any(|tool| tool_name(&tool.spec) == "list_dir"));
     }
 
+    #[test]
+    fn code_search_tool_requires_feature() {
+        let config = test_config();
+        let model_info =
+            ModelsManager::construct_model_info_offline_for_tests("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let build = |features: &Features| {
+            let tools_config = ToolsConfig::new(&ToolsConfigParams {
+                model_info: &model_info,
+                features,
+                web_search_mode: Some(WebSearchMode::Cached),
+                session_source: SessionSource::Cli,
+            });
+            build_specs(&tools_config, None, None, &[]).build().0
+        };
+
+        assert!(
+            !build(&features)
+                .iter()
+                .any(|tool| tool_name(&tool.spec) == "code_search")
+        );
+
+        features.enable(Feature::CodeSearch);
+        let tools = build(&features);
+        assert!(find_tool(&tools, "code_search").supports_parallel_tool_calls);
+    }
+
     #[test]
     fn test_build_specs_mcp_tools_converted() {
         let config = test_config();
@@ -188,6 +188,7 @@ pub(crate) async fn emit_exec_end_for_unified_exec(
     duration: Duration,
 ) {
     let aggregated_output = resolve_aggregated_output(&transcript, fallback_output).await;
+    let aggregated_output = session_ref.services.exec_secrets.redact(&aggregated_output);
     let output = ExecToolCallOutput {
         exit_code,
         stdout: StreamOutput::new(aggregated_output.clone()),
@@ -568,10 +568,18 @@ impl UnifiedExecProcessManager {
         cwd: PathBuf,
         context: &UnifiedExecContext,
     ) -> Result<(UnifiedExecProcess, Option<DeferredNetworkApproval>), UnifiedExecError> {
-        let env = apply_unified_exec_env(create_env(
+        let mut env = apply_unified_exec_env(create_env(
             &context.turn.shell_environment_policy,
             Some(context.session.conversation_id),
         ));
+        let secret_env = context.session.services.exec_secrets.resolve_env(
+            &context.turn.shell_environment_policy,
+            &request.command,
+            &cwd,
+        );
+        env.extend(secret_env.clone());
+        let mut explicit_env_overrides = context.turn.shell_environment_policy.r#set.clone();
+        explicit_env_overrides.extend(secret_env);
         let mut orchestrator = ToolOrchestrator::new();
         let mut runtime =
             UnifiedExecRuntime::new(self, context.turn.tools_config.unified_exec_backend);
@@ -585,13 +593,15 @@ impl UnifiedExecProcessManager {
                 sandbox_policy: context.turn.sandbox_policy.get(),
                 sandbox_permissions: request.sandbox_permissions,
                 prefix_rule: request.prefix_rule.clone(),
+                cwd: Some(cwd.as_path()),
+                workspace_root: Some(context.turn.cwd.as_path()),
             })
             .await;
         let req = UnifiedExecToolRequest {
             command: request.command.clone(),
             cwd,
             env,
-            explicit_env_overrides: context.turn.shell_environment_policy.r#set.clone(),
+            explicit_env_overrides,
             network: request.network.clone(),
             tty: request.tty,
             sandbox_permissions: request.sandbox_permissions,
@@ -879,6 +879,65 @@ pub fn ev_apply_patch_shell_command_call_via_heredoc(call_id: &str, patch: &str)
     ev_function_call(call_id, "shell_command", &arguments)
 }
 
+/// Build a Chat Completions SSE stream body from a list of chunks, terminated
+/// by the `[DONE]` sentinel.
+pub fn chat_sse(chunks: Vec<Value>) -> String {
+    use std::fmt::Write as _;
+    let mut out = String::new();
+    for chunk in chunks {
+        write!(&mut out, "data: {chunk}\n\n").unwrap();
+    }
+    out.push_str("data: [DONE]\n\n");
+    out
+}
+
+fn chat_chunk(delta: Value, finish_reason: Option<&str>) -> Value {
+    serde_json::json!({
+        "id": "chatcmpl-test",
+        "object": "chat.completion.chunk",
+        "choices": [{ "index": 0, "delta": delta, "finish_reason": finish_reason }],
+    })
+}
+
+pub fn ev_chat_text_delta(text: &str) -> Value {
+    chat_chunk(serde_json::json!({ "content": text }), None)
+}
+
+pub fn ev_chat_reasoning_delta(text: &str) -> Value {
+    chat_chunk(serde_json::json!({ "reasoning_content": text }), None)
+}
+
+pub fn ev_chat_tool_call(index: usize, call_id: &str, name: &str, arguments: &str) -> Value {
+    chat_chunk(
+        serde_json::json!({
+            "tool_calls": [{
+                "index": index,
+                "id": call_id,
+                "type": "function",
+                "function": { "name": name, "arguments": arguments },
+            }],
+        }),
+        None,
+    )
+}
+
+pub fn ev_chat_finish(reason: &str) -> Value {
+    chat_chunk(serde_json::json!({}), Some(reason))
+}
+
+pub fn ev_chat_usage(prompt_tokens: i64, completion_tokens: i64) -> Value {
+    serde_json::json!({
+        "id": "chatcmpl-test",
+        "object": "chat.completion.chunk",
+        "choices": [],
+        "usage": {
+            "prompt_tokens": prompt_tokens,
+            "completion_tokens": completion_tokens,
+            "total_tokens": prompt_tokens + completion_tokens,
+        },
+    })
+}
+
 pub fn sse_failed(id: &str, code: &str, message: &str) -> String {
     sse(vec![serde_json::json!({
         "type": "response.failed",
@@ -1430,6 +1489,46 @@ pub async fn mount_sse_sequence(server: &MockServer, bodies: Vec<String>) -> Res
     response_mock
 }
 
+/// Mounts a sequence of SSE response bodies and serves them in order for each
+/// POST to `/v1/chat/completions`. Asserts the exact number of expected calls.
+pub async fn mount_chat_sse_sequence(server: &MockServer, bodies: Vec<String>) -> ResponseMock {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    struct SeqResponder {
+        num_calls: AtomicUsize,
+        responses: Vec<String>,
+    }
+
+    impl Respond for SeqResponder {
+        fn respond(&self, _: &wiremock::Request) -> ResponseTemplate {
+            let call_num = self.num_calls.fetch_add(1, Ordering::SeqCst);
+            match self.responses.get(call_num) {
+                Some(body) => sse_response(body.clone()),
+                None => panic!("no chat response for {call_num}"),
+            }
+        }
+    }
+
+    let num_calls = bodies.len();
+    let responder = SeqResponder {
+        num_calls: AtomicUsize::new(0),
+        responses: bodies,
+    };
+
+    let response_mock = ResponseMock::new();
+    Mock::given(method("POST"))
+        .and(path_regex(".*/chat/completions$"))
+        .and(response_mock.clone())
+        .respond_with(responder)
+        .up_to_n_times(num_calls as u64)
+        .expect(num_calls as u64)
+        .mount(server)
+        .await;
+
+    response_mock
+}
+
 /// Mounts a sequence of responses for each POST to `/v1/responses`.
 /// Panics if more requests are received than responses provided.
 pub async fn mount_response_sequence(
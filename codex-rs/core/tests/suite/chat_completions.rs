@@ -0,0 +1,129 @@
+//! End-to-end coverage for providers configured with `wire_api = "chat"`.
+
+use codex_core::WireApi;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::chat_sse;
+use core_test_support::responses::ev_chat_finish;
+use core_test_support::responses::ev_chat_reasoning_delta;
+use core_test_support::responses::ev_chat_text_delta;
+use core_test_support::responses::ev_chat_tool_call;
+use core_test_support::responses::ev_chat_usage;
+use core_test_support::responses::mount_chat_sse_sequence;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn chat_wire_api_round_trips_tool_calls() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+
+    let call_id = "call_plan";
+    let plan_args = json!({
+        "plan": [{"step": "Inspect workspace", "status": "in_progress"}],
+    })
+    .to_string();
+    let chat_mock = mount_chat_sse_sequence(
+        &server,
+        vec![
+            chat_sse(vec![
+                ev_chat_reasoning_delta("Planning first."),
+                ev_chat_tool_call(0, call_id, "update_plan", &plan_args),
+                ev_chat_finish("tool_calls"),
+                ev_chat_usage(10, 3),
+            ]),
+            chat_sse(vec![
+                ev_chat_text_delta("plan "),
+                ev_chat_text_delta("acknowledged"),
+                ev_chat_finish("stop"),
+            ]),
+        ],
+    )
+    .await;
+
+    let mut builder = test_codex().with_config(|config| {
+        config.model_provider.wire_api = WireApi::Chat;
+    });
+    let TestCodex {
+        codex,
+        cwd,
+        session_configured,
+        ..
+    } = builder.build(&server).await?;
+
+    codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "please update the plan".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+            cwd: cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::DangerFullAccess,
+            model: session_configured.model.clone(),
+            effort: None,
+            summary: None,
+            service_tier: None,
+            collaboration_mode: None,
+            personality: None,
+        })
+        .await?;
+
+    let mut last_agent_message = None;
+    wait_for_event(&codex, |event| match event {
+        EventMsg::TurnComplete(complete) => {
+            last_agent_message = complete.last_agent_message.clone();
+            true
+        }
+        _ => false,
+    })
+    .await;
+    assert_eq!(last_agent_message.as_deref(), Some("plan acknowledged"));
+
+    let requests = chat_mock.requests();
+    assert_eq!(requests.len(), 2);
+
+    let first = requests[0].body_json();
+    assert_eq!(first["stream"], json!(true));
+    assert!(
+        first["tools"].as_array().is_some_and(|tools| tools
+            .iter()
+            .any(|tool| tool["function"]["name"] == "update_plan")),
+        "expected update_plan in chat tools: {first}"
+    );
+
+    let second = requests[1].body_json();
+    let messages = second["messages"].as_array().cloned().unwrap_or_default();
+    let assistant = messages
+        .iter()
+        .find(|message| message.get("tool_calls").is_some())
+        .unwrap_or_else(|| panic!("assistant tool call message missing: {second}"));
+    assert_eq!(assistant["reasoning_content"], json!("Planning first."));
+    assert_eq!(
+        assistant["tool_calls"],
+        json!([{
+            "id": call_id,
+            "type": "function",
+            "function": { "name": "update_plan", "arguments": plan_args },
+        }])
+    );
+    let tool_output = messages
+        .iter()
+        .find(|message| message.get("role").and_then(Value::as_str) == Some("tool"))
+        .unwrap_or_else(|| panic!("tool message missing: {second}"));
+    assert_eq!(tool_output["tool_call_id"], json!(call_id));
+    assert_eq!(tool_output["content"], json!("Plan updated"));
+
+    Ok(())
+}
@@ -5,6 +5,7 @@ use codex_core::built_in_model_providers;
 use codex_core::compact::SUMMARIZATION_PROMPT;
 use codex_core::compact::SUMMARY_PREFIX;
 use codex_core::config::Config;
+use codex_core::config::types::CompactionStrategy;
 use codex_protocol::items::TurnItem;
 use codex_protocol::openai_models::ModelInfo;
 use codex_protocol::openai_models::ModelsResponse;
@@ -66,6 +67,7 @@ const DUMMY_CALL_ID: &str = "call-multi-auto";
 const FUNCTION_CALL_LIMIT_MSG: &str = "function call limit push";
 const POST_AUTO_USER_MSG: &str = "post auto follow-up";
 const PRETURN_CONTEXT_DIFF_CWD: &str = "/tmp/PRETURN_CONTEXT_DIFF_CWD";
+const PRUNED_TOOL_OUTPUT_MARKER: &str = "[output pruned during compaction]";
 
 pub(super) const COMPACT_WARNING_MESSAGE: &str = "Heads up: Long threads and multiple compactions can cause the model to be less accurate. Start a new thread when possible to keep threads small and targeted.";
 
@@ -626,6 +628,91 @@ async fn manual_compact_emits_context_compaction_items() {
     assert!(legacy_event);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn manual_compact_with_prune_strategy_skips_model_and_prunes_old_outputs() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+
+    let call_ids = [
+        "prune-0", "prune-1", "prune-2", "prune-3", "prune-4", "prune-5",
+    ];
+    let mut tool_calls = call_ids
+        .iter()
+        .map(|call_id| ev_function_call(call_id, DUMMY_FUNCTION_NAME, "{}"))
+        .collect::<Vec<_>>();
+    tool_calls.push(ev_completed("r1"));
+    let request_log = mount_sse_sequence(
+        &server,
+        vec![
+            sse(tool_calls),
+            sse(vec![
+                ev_assistant_message("m1", FIRST_REPLY),
+                ev_completed("r2"),
+            ]),
+            sse(vec![
+                ev_assistant_message("m2", FINAL_REPLY),
+                ev_completed("r3"),
+            ]),
+        ],
+    )
+    .await;
+
+    let model_provider = non_openai_model_provider(&server);
+    let mut builder = test_codex().with_config(move |config| {
+        config.model_provider = model_provider;
+        config.compaction_strategy = CompactionStrategy::Prune;
+    });
+    let codex = builder.build(&server).await.unwrap().codex;
+
+    let user_input = |text: &str| Op::UserInput {
+        items: vec![UserInput::Text {
+            text: text.into(),
+            text_elements: Vec::new(),
+        }],
+        final_output_json_schema: None,
+    };
+
+    codex.submit(user_input("run the tools")).await.unwrap();
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    codex.submit(Op::Compact).await.unwrap();
+    wait_for_event(&codex, |event| {
+        matches!(
+            event,
+            EventMsg::ItemCompleted(ItemCompletedEvent {
+                item: TurnItem::ContextCompaction(_),
+                ..
+            })
+        )
+    })
+    .await;
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    codex.submit(user_input(THIRD_USER_MSG)).await.unwrap();
+    wait_for_event(&codex, |event| matches!(event, EventMsg::TurnComplete(_))).await;
+
+    let requests = request_log.requests();
+    assert_eq!(
+        requests.len(),
+        3,
+        "prune compaction must not call the model"
+    );
+    let last_request = requests.last().expect("follow-up request");
+    for call_id in &call_ids[..2] {
+        assert_eq!(
+            last_request.function_call_output_text(call_id).as_deref(),
+            Some(PRUNED_TOOL_OUTPUT_MARKER),
+        );
+    }
+    for call_id in &call_ids[2..] {
+        assert_ne!(
+            last_request.function_call_output_text(call_id).as_deref(),
+            Some(PRUNED_TOOL_OUTPUT_MARKER),
+        );
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn multiple_auto_compact_per_task_runs_after_token_limit_hit() {
     skip_if_no_network!();
@@ -0,0 +1,141 @@
+#![cfg(not(target_os = "windows"))]
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_hooks::CommandHookConfig;
+use codex_hooks::HookEventName;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn write_hook_script(dir: &Path, body: &str) -> Result<String> {
+    let script = dir.join("hook.sh");
+    std::fs::write(&script, format!("#!/bin/sh\ncat > /dev/null\n{body}\n"))?;
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+    Ok(script.to_string_lossy().into_owned())
+}
+
+fn before_tool_use_hook(script: String) -> CommandHookConfig {
+    CommandHookConfig {
+        name: "guard".to_string(),
+        event: HookEventName::BeforeToolUse,
+        argv: vec![script],
+        timeout: Duration::from_secs(10),
+        fail_closed: true,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn before_tool_use_deny_reason_is_returned_to_model() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let hook_dir = TempDir::new()?;
+    let script = write_hook_script(
+        hook_dir.path(),
+        r#"echo '{"decision":"deny","reason":"no shell today"}'"#,
+    )?;
+    let mut builder = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(move |config| config.hooks = vec![before_tool_use_hook(script)]);
+    let fixture = builder.build(&server).await?;
+
+    let call_id = "denied-shell";
+    let args = json!({
+        "command": ["/bin/echo", "should not run"],
+        "timeout_ms": 1_000,
+    });
+    let mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(call_id, "shell", &serde_json::to_string(&args)?),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    fixture.submit_turn("run echo").await?;
+
+    let output_text = mock
+        .function_call_output_text(call_id)
+        .context("denied tool output present")?;
+    assert_eq!(
+        output_text,
+        "tool call denied by hook 'guard': no shell today"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn before_tool_use_rewrite_changes_executed_command() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let hook_dir = TempDir::new()?;
+    let script = write_hook_script(
+        hook_dir.path(),
+        r#"echo '{"decision":"rewrite","tool_input":{"input_type":"function","arguments":"{\"command\":[\"/bin/echo\",\"rewritten by hook\"],\"timeout_ms\":1000}"}}'"#,
+    )?;
+    let mut builder = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(move |config| config.hooks = vec![before_tool_use_hook(script)]);
+    let fixture = builder.build(&server).await?;
+
+    let call_id = "rewritten-shell";
+    let args = json!({
+        "command": ["/bin/echo", "original command"],
+        "timeout_ms": 1_000,
+    });
+    let mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(call_id, "shell", &serde_json::to_string(&args)?),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    fixture.submit_turn("run echo").await?;
+
+    let output_text = mock
+        .function_call_output_text(call_id)
+        .context("shell output present")?;
+    assert!(
+        output_text.contains("rewritten by hook"),
+        "expected rewritten command output: {output_text}"
+    );
+    assert!(
+        !output_text.contains("original command"),
+        "original command should not run: {output_text}"
+    );
+
+    Ok(())
+}
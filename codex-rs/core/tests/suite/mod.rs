@@ -62,6 +62,7 @@ mod apply_patch_cli;
 #[cfg(not(target_os = "windows"))]
 mod approvals;
 mod auth_refresh;
+mod chat_completions;
 mod cli_stream;
 mod client;
 mod client_websockets;
@@ -76,6 +77,7 @@ mod exec_policy;
 mod fork_thread;
 mod grep_files;
 mod hierarchical_agents;
+mod hooks;
 mod image_rollout;
 mod items;
 mod js_repl;
@@ -84,12 +86,14 @@ mod list_dir;
 mod live_cli;
 mod live_reload;
 mod memories;
+mod model_fallbacks;
 mod model_info_overrides;
 mod model_overrides;
 mod model_switching;
 mod model_visible_layout;
 mod models_cache_ttl;
 mod models_etag_responses;
+mod notification_sinks;
 mod otel;
 mod pending_input;
 mod permissions_messages;
@@ -101,6 +105,7 @@ mod quota_exceeded;
 mod read_file;
 mod realtime_conversation;
 mod remote_models;
+mod replay;
 mod request_compression;
 #[cfg(not(target_os = "windows"))]
 mod request_permissions;
@@ -0,0 +1,189 @@
+use anyhow::Result;
+use codex_core::built_in_model_providers;
+use codex_core::config::types::ModelFallback;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ModelRerouteReason;
+use codex_protocol::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_completed_with_tokens;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::sse_failed;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+
+const PRIMARY_MODEL: &str = "gpt-5.1";
+const FALLBACK_MODEL: &str = "gpt-5.1-codex-mini";
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn context_window_overflow_continues_turn_on_fallback_model() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let responses = mount_sse_sequence(
+        &server,
+        vec![
+            sse_failed(
+                "resp-1",
+                "context_length_exceeded",
+                "Your input exceeds the context window of this model.",
+            ),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| {
+            config.model = Some(PRIMARY_MODEL.to_string());
+            config.model_fallbacks = vec![ModelFallback {
+                model: FALLBACK_MODEL.to_string(),
+                provider_id: config.model_provider_id.clone(),
+                provider: config.model_provider.clone(),
+            }];
+        })
+        .build(&server)
+        .await?;
+
+    test.submit_turn("hello").await?;
+
+    let requests = responses.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].body_json()["model"], PRIMARY_MODEL);
+    assert_eq!(requests[1].body_json()["model"], FALLBACK_MODEL);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn quota_exhaustion_emits_reroute_and_errors_once_fallbacks_run_out() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let quota_failure = || {
+        sse_failed(
+            "resp-quota",
+            "insufficient_quota",
+            "You exceeded your current quota, please check your plan and billing details.",
+        )
+    };
+    let responses = mount_sse_sequence(&server, vec![quota_failure(), quota_failure()]).await;
+
+    let test = test_codex()
+        .with_config(|config| {
+            config.model = Some(PRIMARY_MODEL.to_string());
+            config.model_fallbacks = vec![ModelFallback {
+                model: FALLBACK_MODEL.to_string(),
+                provider_id: config.model_provider_id.clone(),
+                provider: config.model_provider.clone(),
+            }];
+        })
+        .build(&server)
+        .await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "quota?".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let mut reroutes = Vec::new();
+    let mut error_events = 0;
+    loop {
+        match wait_for_event(&test.codex, |_| true).await {
+            EventMsg::ModelReroute(reroute) => reroutes.push(reroute),
+            EventMsg::Error(_) => error_events += 1,
+            EventMsg::TurnComplete(_) => break,
+            _ => {}
+        }
+    }
+
+    assert_eq!(reroutes.len(), 1);
+    assert_eq!(reroutes[0].from_model, PRIMARY_MODEL);
+    assert_eq!(reroutes[0].to_model, FALLBACK_MODEL);
+    assert_eq!(reroutes[0].reason, ModelRerouteReason::RateLimited);
+    assert_eq!(error_events, 1);
+    assert_eq!(responses.requests().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn compaction_after_fallback_uses_fallback_provider() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let primary_server = start_mock_server().await;
+    let fallback_server = start_mock_server().await;
+    let primary_responses = mount_sse_sequence(
+        &primary_server,
+        vec![sse_failed(
+            "resp-1",
+            "context_length_exceeded",
+            "Your input exceeds the context window of this model.",
+        )],
+    )
+    .await;
+    // The fallback's first response needs a follow-up and exceeds the auto-compact limit, so
+    // the turn compacts mid-way before continuing.
+    let fallback_responses = mount_sse_sequence(
+        &fallback_server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_function_call("call-1", "test_tool", "{}"),
+                ev_completed_with_tokens("resp-2", 500_000),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "summary"),
+                ev_completed_with_tokens("resp-3", 100),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-2", "done"),
+                ev_completed_with_tokens("resp-4", 100),
+            ]),
+        ],
+    )
+    .await;
+
+    let mut fallback_provider = built_in_model_providers()["openai"].clone();
+    fallback_provider.name = "Fallback (test)".into();
+    fallback_provider.base_url = Some(format!("{}/v1", fallback_server.uri()));
+    let test = test_codex()
+        .with_config(move |config| {
+            config.model = Some(PRIMARY_MODEL.to_string());
+            config.model_auto_compact_token_limit = Some(200_000);
+            config.model_fallbacks = vec![ModelFallback {
+                model: FALLBACK_MODEL.to_string(),
+                provider_id: "fallback".to_string(),
+                provider: fallback_provider,
+            }];
+        })
+        .build(&primary_server)
+        .await?;
+
+    test.submit_turn("hello").await?;
+
+    assert_eq!(primary_responses.requests().len(), 1);
+    let fallback_requests = fallback_responses.requests();
+    assert_eq!(fallback_requests.len(), 3);
+    for request in fallback_requests {
+        assert_eq!(request.body_json()["model"], FALLBACK_MODEL);
+    }
+
+    Ok(())
+}
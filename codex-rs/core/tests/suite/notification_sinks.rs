@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_core::config::types::NotificationSinkEvent;
+use codex_core::config::types::NotificationSinkTarget;
+use codex_core::config::types::NotificationSinkToml;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+const WEBHOOK_PATH: &str = "/notify";
+
+async fn wait_for_webhook_body(server: &MockServer) -> Result<Value> {
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    loop {
+        let requests = server.received_requests().await.unwrap_or_default();
+        if let Some(request) = requests
+            .iter()
+            .find(|request| request.url.path() == WEBHOOK_PATH)
+        {
+            return Ok(serde_json::from_slice(&request.body)?);
+        }
+        anyhow::ensure!(
+            tokio::time::Instant::now() < deadline,
+            "webhook was not called"
+        );
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn webhook_sink_receives_templated_turn_completed_notification() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "All \"tests\" pass"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(path(WEBHOOK_PATH))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&server)
+        .await;
+
+    let url = format!("{}{WEBHOOK_PATH}", server.uri());
+    let test = test_codex()
+        .with_config(move |config| {
+            config.notification_sinks = vec![
+                NotificationSinkToml {
+                    events: Some(vec![NotificationSinkEvent::ApprovalRequested]),
+                    target: NotificationSinkTarget::Webhook {
+                        url: url.clone(),
+                        headers: Default::default(),
+                        body: Some(r#"{"text": "ignored"}"#.to_string()),
+                    },
+                },
+                NotificationSinkToml {
+                    events: Some(vec![NotificationSinkEvent::TurnCompleted]),
+                    target: NotificationSinkTarget::Webhook {
+                        url,
+                        headers: Default::default(),
+                        body: Some(r#"{"text": "[{{event}}] {{message}}"}"#.to_string()),
+                    },
+                },
+            ];
+        })
+        .build(&server)
+        .await?;
+
+    test.submit_turn("run the tests").await?;
+
+    let body = wait_for_webhook_body(&server).await?;
+    assert_eq!(
+        body,
+        json!({ "text": "[turn_completed] All \"tests\" pass" })
+    );
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let webhook_calls = server
+        .received_requests()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|request| request.url.path() == WEBHOOK_PATH)
+        .count();
+    assert_eq!(webhook_calls, 1);
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use codex_core::config::Constrained;
+use codex_core::replay::ReplayOptions;
+use codex_core::replay::ReplayToolMode;
+use codex_core::replay::replay_rollout;
+use codex_protocol::ThreadId;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::SessionMeta;
+use codex_protocol::protocol::SessionMetaLine;
+use codex_protocol::protocol::UserMessageEvent;
+use core_test_support::load_default_config_for_test;
+use core_test_support::skip_if_no_network;
+use serde_json::json;
+use tempfile::TempDir;
+
+const TEST_TIMESTAMP: &str = "2025-01-01T00:00:00.000Z";
+
+fn shell_command_call(call_id: &str, command: &str) -> RolloutItem {
+    RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+        id: None,
+        name: "shell_command".to_string(),
+        arguments: json!({ "command": command }).to_string(),
+        call_id: call_id.to_string(),
+    })
+}
+
+fn output(call_id: &str, text: &str) -> RolloutItem {
+    RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+        call_id: call_id.to_string(),
+        output: FunctionCallOutputPayload::from_text(text.to_string()),
+    })
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn replay_does_not_execute_commands_the_user_denied() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let codex_home = TempDir::new()?;
+    let workdir = TempDir::new()?;
+    let items = vec![
+        RolloutItem::SessionMeta(SessionMetaLine {
+            meta: SessionMeta {
+                id: ThreadId::new(),
+                cwd: workdir.path().to_path_buf(),
+                ..SessionMeta::default()
+            },
+            git: None,
+        }),
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: "create the markers".to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        })),
+        shell_command_call("call-denied", "touch denied.txt"),
+        shell_command_call("call-approved", "touch approved.txt"),
+        output("call-denied", "exec command rejected by user"),
+        output("call-approved", "Exit code: 0\nOutput:\n"),
+        RolloutItem::ResponseItem(ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText {
+                text: "Created one of them.".to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }),
+    ];
+    let rollout_path = codex_home.path().join("rollout.jsonl");
+    let mut rollout = String::new();
+    for item in items {
+        rollout.push_str(&serde_json::to_string(&RolloutLine {
+            timestamp: TEST_TIMESTAMP.to_string(),
+            item,
+        })?);
+        rollout.push('\n');
+    }
+    std::fs::write(&rollout_path, rollout)?;
+
+    let mut config = load_default_config_for_test(&codex_home).await;
+    config.permissions.approval_policy = Constrained::allow_any(AskForApproval::UnlessTrusted);
+    config.permissions.sandbox_policy = Constrained::allow_any(SandboxPolicy::DangerFullAccess);
+
+    replay_rollout(
+        config,
+        &rollout_path,
+        ReplayOptions {
+            tools: ReplayToolMode::Execute,
+            model: Some("gpt-5.1".to_string()),
+            cwd: Some(workdir.path().to_path_buf()),
+            turn_timeout: Duration::from_secs(30),
+        },
+    )
+    .await?;
+
+    assert!(workdir.path().join("approved.txt").exists());
+    assert!(!workdir.path().join("denied.txt").exists());
+
+    Ok(())
+}
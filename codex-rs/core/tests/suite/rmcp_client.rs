@@ -105,6 +105,7 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_budget: None,
                 },
             );
             config
@@ -249,6 +250,7 @@ async fn stdio_image_responses_round_trip() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_budget: None,
                 },
             );
             config
@@ -471,6 +473,7 @@ async fn stdio_image_responses_are_sanitized_for_text_only_model() -> anyhow::Re
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_budget: None,
                 },
             );
             config
@@ -591,6 +594,7 @@ async fn stdio_server_propagates_whitelisted_env_vars() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_budget: None,
                 },
             );
             config
@@ -752,6 +756,7 @@ async fn streamable_http_tool_call_round_trip() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_budget: None,
                 },
             );
             config
@@ -973,6 +978,7 @@ async fn streamable_http_with_oauth_round_trip_impl() -> anyhow::Result<()> {
                     disabled_tools: None,
                     scopes: None,
                     oauth_resource: None,
+                    sampling_token_budget: None,
                 },
             );
             config
@@ -220,7 +220,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "uncommitted",
         default_value_t = false,
-        conflicts_with_all = ["base", "commit", "prompt"]
+        conflicts_with_all = ["base", "commit", "range", "patch", "prompt"]
     )]
     pub uncommitted: bool,
 
@@ -228,7 +228,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "base",
         value_name = "BRANCH",
-        conflicts_with_all = ["uncommitted", "commit", "prompt"]
+        conflicts_with_all = ["uncommitted", "commit", "range", "patch", "prompt"]
     )]
     pub base: Option<String>,
 
@@ -236,7 +236,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "commit",
         value_name = "SHA",
-        conflicts_with_all = ["uncommitted", "base", "prompt"]
+        conflicts_with_all = ["uncommitted", "base", "range", "patch", "prompt"]
     )]
     pub commit: Option<String>,
 
@@ -244,11 +244,52 @@ pub struct ReviewArgs {
     #[arg(long = "title", value_name = "TITLE", requires = "commit")]
     pub commit_title: Option<String>,
 
+    /// Review the changes introduced by the commits in a range, e.g. `main..HEAD`.
+    #[arg(
+        long = "range",
+        value_name = "FROM..TO",
+        conflicts_with_all = ["uncommitted", "base", "commit", "patch", "prompt"]
+    )]
+    pub range: Option<String>,
+
+    /// Review the changes in a unified diff file.
+    #[arg(
+        long = "patch",
+        value_name = "FILE",
+        conflicts_with_all = ["uncommitted", "base", "commit", "range", "prompt"]
+    )]
+    pub patch: Option<PathBuf>,
+
+    /// Only review changes to files matching this git pathspec (e.g. `src/api/**`).
+    /// May be repeated.
+    #[arg(long = "path", value_name = "PATHSPEC")]
+    pub paths: Vec<String>,
+
+    /// Print the review findings to stdout in this format. Structured formats
+    /// exit with a code reflecting the most severe finding (10 for P0 through
+    /// 13 for P3, 0 when there are none).
+    #[arg(long = "format", value_enum, default_value_t = ReviewOutputFormat::Text)]
+    pub format: ReviewOutputFormat,
+
     /// Custom review instructions. If `-` is used, read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewOutputFormat {
+    /// The reviewer's prose summary.
+    #[default]
+    Text,
+    /// Stable JSON document with the structured findings.
+    Json,
+    /// SARIF 2.1.0 log for code-scanning tools.
+    Sarif,
+    /// Markdown report, most severe findings first.
+    Markdown,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "kebab-case")]
 pub enum Color {
@@ -859,6 +859,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
+            | EventMsg::ListMcpPromptsResponse(_)
+            | EventMsg::McpPromptListChanged(_)
+            | EventMsg::GetMcpPromptResponse(_)
             | EventMsg::PromptTraceResponse(_)
             | EventMsg::ListSkillsResponse(_)
             | EventMsg::ListRemoteSkillsResponse(_)
@@ -941,6 +944,9 @@ impl EventProcessorWithHumanOutput {
                 | EventMsg::GetHistoryEntryResponse(_)
                 | EventMsg::McpListToolsResponse(_)
                 | EventMsg::ListCustomPromptsResponse(_)
+                | EventMsg::ListMcpPromptsResponse(_)
+                | EventMsg::McpPromptListChanged(_)
+                | EventMsg::GetMcpPromptResponse(_)
                 | EventMsg::PromptTraceResponse(_)
                 | EventMsg::ListSkillsResponse(_)
                 | EventMsg::ListRemoteSkillsResponse(_)
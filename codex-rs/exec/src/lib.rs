@@ -13,6 +13,7 @@ pub mod exec_events;
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
+pub use cli::ReviewOutputFormat;
 use codex_arg0::Arg0DispatchPaths;
 use codex_cloud_requirements::cloud_requirements_loader;
 use codex_core::AuthManager;
@@ -34,6 +35,11 @@ use codex_core::format_exec_policy_error_with_source;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::models_manager::collaboration_mode_presets::CollaborationModesConfig;
 use codex_core::models_manager::manager::RefreshStrategy;
+use codex_core::review_format::render_review_output_json;
+use codex_core::review_format::render_review_output_markdown;
+use codex_core::review_format::render_review_output_sarif;
+use codex_core::review_format::render_review_output_text;
+use codex_core::review_format::review_exit_code;
 use codex_otel::set_parent_from_context;
 use codex_otel::traceparent_context_from_env;
 use codex_protocol::approvals::ElicitationAction;
@@ -42,6 +48,7 @@ use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::ReviewOutputEvent;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::ReviewTarget;
 use codex_protocol::protocol::SessionSource;
@@ -56,6 +63,7 @@ use serde_json::Value;
 use std::collections::HashSet;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use supports_color::Stream;
@@ -420,6 +428,14 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
         stderr_with_ansi,
     } = args;
 
+    let review_format = match &command {
+        Some(ExecCommand::Review(review_cli)) => review_cli.format,
+        _ => ReviewOutputFormat::Text,
+    };
+    if json_mode && review_format != ReviewOutputFormat::Text {
+        anyhow::bail!("--format cannot be combined with --json");
+    }
+
     let mut event_processor: Box<dyn EventProcessor> = match json_mode {
         true => Box::new(EventProcessorWithJsonOutput::new(last_message_file.clone())),
         _ => Box::new(EventProcessorWithHumanOutput::create_with_ansi(
@@ -638,7 +654,7 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
             let task_id = thread
                 .submit(Op::UserTurn {
                     items,
-                    cwd: default_cwd,
+                    cwd: default_cwd.clone(),
                     approval_policy: default_approval_policy,
                     sandbox_policy: default_sandbox_policy.clone(),
                     model: default_model,
@@ -666,6 +682,7 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
     let mut shutdown_requested = false;
+    let mut review_output = None;
     while let Some(envelope) = rx.recv().await {
         let ThreadEventEnvelope {
             thread_id,
@@ -679,6 +696,11 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
         if matches!(event.msg, EventMsg::Error(_)) {
             error_seen = true;
         }
+        if thread_id == primary_thread_id
+            && let EventMsg::ExitedReviewMode(ev) = &event.msg
+        {
+            review_output = ev.review_output.clone();
+        }
         if shutdown_requested
             && !matches!(&event.msg, EventMsg::ShutdownComplete | EventMsg::Error(_))
         {
@@ -729,11 +751,45 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
             CodexStatus::Shutdown => continue,
         }
     }
-    event_processor.print_final_output();
+    if review_format == ReviewOutputFormat::Text {
+        event_processor.print_final_output();
+    }
     if error_seen {
         std::process::exit(1);
     }
+    if review_format != ReviewOutputFormat::Text {
+        let Some(review_output) = review_output else {
+            eprintln!("Review finished without structured findings.");
+            std::process::exit(1);
+        };
+        let root = get_git_repo_root(&default_cwd).unwrap_or_else(|| default_cwd.clone());
+        print_review_output(&review_output, review_format, &root)?;
+        let exit_code = review_exit_code(&review_output.findings);
+        if exit_code != 0 {
+            std::process::exit(exit_code);
+        }
+    }
+
+    Ok(())
+}
 
+#[allow(clippy::print_stdout)]
+fn print_review_output(
+    output: &ReviewOutputEvent,
+    format: ReviewOutputFormat,
+    root: &Path,
+) -> anyhow::Result<()> {
+    let rendered = match format {
+        ReviewOutputFormat::Text => render_review_output_text(output),
+        ReviewOutputFormat::Json => {
+            serde_json::to_string_pretty(&render_review_output_json(output, root))?
+        }
+        ReviewOutputFormat::Sarif => {
+            serde_json::to_string_pretty(&render_review_output_sarif(output, root))?
+        }
+        ReviewOutputFormat::Markdown => render_review_output_markdown(output, root),
+    };
+    println!("{rendered}");
     Ok(())
 }
 
@@ -991,6 +1047,19 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
             sha,
             title: args.commit_title,
         }
+    } else if let Some(range) = args.range {
+        let Some((from, to)) = range.split_once("..") else {
+            anyhow::bail!("--range must have the form FROM..TO");
+        };
+        if from.is_empty() || to.is_empty() || to.starts_with('.') {
+            anyhow::bail!("--range must have the form FROM..TO");
+        }
+        ReviewTarget::CommitRange {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    } else if let Some(path) = args.patch {
+        ReviewTarget::PatchFile { path }
     } else if let Some(prompt_arg) = args.prompt {
         let prompt = resolve_prompt(Some(prompt_arg)).trim().to_string();
         if prompt.is_empty() {
@@ -1001,13 +1070,14 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
         }
     } else {
         anyhow::bail!(
-            "Specify --uncommitted, --base, --commit, or provide custom review instructions"
+            "Specify --uncommitted, --base, --commit, --range, --patch, or provide custom review instructions"
         );
     };
 
     Ok(ReviewRequest {
         target,
         user_facing_hint: None,
+        paths: args.paths,
     })
 }
 
@@ -1059,6 +1129,10 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            patch: None,
+            paths: Vec::new(),
+            format: ReviewOutputFormat::Text,
             prompt: None,
         })
         .expect("builds uncommitted review request");
@@ -1066,6 +1140,7 @@ mod tests {
         let expected = ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: None,
+            paths: Vec::new(),
         };
 
         assert_eq!(request, expected);
@@ -1078,6 +1153,10 @@ mod tests {
             base: None,
             commit: Some("123456789".to_string()),
             commit_title: Some("Add review command".to_string()),
+            range: None,
+            patch: None,
+            paths: Vec::new(),
+            format: ReviewOutputFormat::Text,
             prompt: None,
         })
         .expect("builds commit review request");
@@ -1088,6 +1167,7 @@ mod tests {
                 title: Some("Add review command".to_string()),
             },
             user_facing_hint: None,
+            paths: Vec::new(),
         };
 
         assert_eq!(request, expected);
@@ -1100,6 +1180,10 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            patch: None,
+            paths: Vec::new(),
+            format: ReviewOutputFormat::Text,
             prompt: Some("  custom review instructions  ".to_string()),
         })
         .expect("builds custom review request");
@@ -1109,11 +1193,53 @@ mod tests {
                 instructions: "custom review instructions".to_string(),
             },
             user_facing_hint: None,
+            paths: Vec::new(),
         };
 
         assert_eq!(request, expected);
     }
 
+    #[test]
+    fn builds_commit_range_review_request_with_paths() {
+        let request = build_review_request(ReviewArgs {
+            uncommitted: false,
+            base: None,
+            commit: None,
+            commit_title: None,
+            range: Some("main..feature".to_string()),
+            patch: None,
+            paths: vec!["src/api/**".to_string()],
+            format: ReviewOutputFormat::Text,
+            prompt: None,
+        })
+        .expect("builds commit range review request");
+
+        let expected = ReviewRequest {
+            target: ReviewTarget::CommitRange {
+                from: "main".to_string(),
+                to: "feature".to_string(),
+            },
+            user_facing_hint: None,
+            paths: vec!["src/api/**".to_string()],
+        };
+
+        assert_eq!(request, expected);
+
+        let err = build_review_request(ReviewArgs {
+            uncommitted: false,
+            base: None,
+            commit: None,
+            commit_title: None,
+            range: Some("main".to_string()),
+            patch: None,
+            paths: Vec::new(),
+            format: ReviewOutputFormat::Text,
+            prompt: None,
+        })
+        .expect_err("range without ..");
+        assert!(err.to_string().contains("FROM..TO"), "{err}");
+    }
+
     #[test]
     fn decode_prompt_bytes_strips_utf8_bom() {
         let input = [0xEF, 0xBB, 0xBF, b'h', b'i', b'\n'];
@@ -7,6 +7,7 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use crate::decision::Decision;
+use crate::rule::ArgMatcher;
 use crate::rule::NetworkRuleProtocol;
 use crate::rule::normalize_network_rule_host;
 use serde_json;
@@ -16,6 +17,8 @@ use thiserror::Error;
 pub enum AmendError {
     #[error("prefix rule requires at least one token")]
     EmptyPrefix,
+    #[error("invalid prefix rule: {0}")]
+    InvalidPrefixRule(String),
     #[error("invalid network rule: {0}")]
     InvalidNetworkRule(String),
     #[error("policy path has no parent: {path}")]
@@ -61,26 +64,92 @@ pub enum AmendError {
     },
 }
 
+/// A `prefix_rule` to persist, optionally constrained by argument clauses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrefixRuleAmendment {
+    pub prefix: Vec<String>,
+    pub decision: Decision,
+    pub args: Vec<ArgMatcher>,
+    pub not_matching: Vec<ArgMatcher>,
+    pub justification: Option<String>,
+}
+
+impl PrefixRuleAmendment {
+    pub fn allow(prefix: Vec<String>) -> Self {
+        Self {
+            prefix,
+            decision: Decision::Allow,
+            args: Vec::new(),
+            not_matching: Vec::new(),
+            justification: None,
+        }
+    }
+}
+
 /// Note this thread uses advisory file locking and performs blocking I/O, so it should be used with
 /// [`tokio::task::spawn_blocking`] when called from an async context.
 pub fn blocking_append_allow_prefix_rule(
     policy_path: &Path,
     prefix: &[String],
 ) -> Result<(), AmendError> {
-    if prefix.is_empty() {
+    blocking_append_prefix_rule(policy_path, &PrefixRuleAmendment::allow(prefix.to_vec()))
+}
+
+/// Note this function uses advisory file locking and performs blocking I/O, so it should be used
+/// with [`tokio::task::spawn_blocking`] when called from an async context.
+pub fn blocking_append_prefix_rule(
+    policy_path: &Path,
+    amendment: &PrefixRuleAmendment,
+) -> Result<(), AmendError> {
+    if amendment.prefix.is_empty() {
         return Err(AmendError::EmptyPrefix);
     }
+    if let Some(raw) = amendment.justification.as_deref()
+        && raw.trim().is_empty()
+    {
+        return Err(AmendError::InvalidPrefixRule(
+            "justification cannot be empty".to_string(),
+        ));
+    }
 
-    let tokens = prefix
+    let tokens = amendment
+        .prefix
         .iter()
         .map(serde_json::to_string)
         .collect::<Result<Vec<_>, _>>()
         .map_err(|source| AmendError::SerializePrefix { source })?;
-    let pattern = format!("[{}]", tokens.join(", "));
-    let rule = format!(r#"prefix_rule(pattern={pattern}, decision="allow")"#);
+    let decision = match amendment.decision {
+        Decision::Allow => "allow",
+        Decision::Prompt => "prompt",
+        Decision::Forbidden => "forbidden",
+    };
+    let mut args = vec![
+        format!("pattern=[{}]", tokens.join(", ")),
+        format!(r#"decision="{decision}""#),
+    ];
+    if !amendment.args.is_empty() {
+        args.push(format!("args={}", render_matchers(&amendment.args)));
+    }
+    if !amendment.not_matching.is_empty() {
+        args.push(format!(
+            "not_matching={}",
+            render_matchers(&amendment.not_matching)
+        ));
+    }
+    if let Some(justification) = amendment.justification.as_deref() {
+        let justification = serde_json::to_string(justification)
+            .map_err(|source| AmendError::SerializePrefix { source })?;
+        args.push(format!("justification={justification}"));
+    }
+    let rule = format!("prefix_rule({})", args.join(", "));
     append_rule_line(policy_path, &rule)
 }
 
+fn render_matchers(matchers: &[ArgMatcher]) -> String {
+    let matchers = matchers.iter().map(ToString::to_string).collect::<Vec<_>>();
+    format!("[{}]", matchers.join(", "))
+}
+
 /// Note this function uses advisory file locking and performs blocking I/O, so it should be used
 /// with [`tokio::task::spawn_blocking`] when called from an async context.
 pub fn blocking_append_network_rule(
@@ -271,6 +340,38 @@ prefix_rule(pattern=["echo", "Hello, world!"], decision="allow")
         );
     }
 
+    #[test]
+    fn appends_prefix_rule_with_argument_clauses() {
+        let tmp = tempdir().expect("create temp dir");
+        let policy_path = tmp.path().join("rules").join("default.rules");
+
+        blocking_append_prefix_rule(
+            &policy_path,
+            &PrefixRuleAmendment {
+                not_matching: vec![
+                    ArgMatcher::flag(vec!["--force".to_string(), "-f".to_string()], None)
+                        .expect("valid flag"),
+                    ArgMatcher::OutsideWorkspace,
+                ],
+                justification: Some("pushing is fine, force-pushing is not".to_string()),
+                ..PrefixRuleAmendment::allow(vec!["git".to_string(), "push".to_string()])
+            },
+        )
+        .expect("append rule");
+
+        let contents = std::fs::read_to_string(&policy_path).expect("read policy");
+        assert_eq!(
+            contents,
+            r#"prefix_rule(pattern=["git", "push"], decision="allow", not_matching=[flag("--force", "-f"), outside_workspace()], justification="pushing is fine, force-pushing is not")
+"#
+        );
+
+        let mut parser = crate::PolicyParser::new();
+        parser
+            .parse("default.rules", &contents)
+            .expect("persisted rule should parse");
+    }
+
     #[test]
     fn appends_network_rule() {
         let tmp = tempdir().expect("create temp dir");
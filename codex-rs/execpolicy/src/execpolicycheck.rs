@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use anyhow::Context;
 use anyhow::Result;
 use clap::Parser;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use serde::Serialize;
 
 use crate::Decision;
@@ -28,6 +29,17 @@ pub struct ExecPolicyCheckCommand {
     #[arg(long)]
     pub resolve_host_executables: bool,
 
+    /// Working directory of the command, used by `workspace_path()` and
+    /// `outside_workspace()` matchers. Defaults to the current directory.
+    #[arg(long, value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
+    /// Directory that counts as the workspace for `workspace_path()` and
+    /// `outside_workspace()` matchers. May be repeated. Defaults to the
+    /// working directory.
+    #[arg(long = "workspace-root", value_name = "DIR")]
+    pub workspace_roots: Vec<PathBuf>,
+
     /// Command tokens to check against the policy.
     #[arg(
         value_name = "COMMAND",
@@ -42,11 +54,27 @@ impl ExecPolicyCheckCommand {
     /// Load the policies for this command, evaluate the command, and render JSON output.
     pub fn run(&self) -> Result<()> {
         let policy = load_policies(&self.rules)?;
+        let cwd = match &self.cwd {
+            Some(cwd) => AbsolutePathBuf::from_absolute_path(cwd),
+            None => AbsolutePathBuf::current_dir(),
+        }
+        .context("failed to resolve working directory")?;
+        let workspace_roots = if self.workspace_roots.is_empty() {
+            vec![cwd.clone()]
+        } else {
+            self.workspace_roots
+                .iter()
+                .map(|root| AbsolutePathBuf::resolve_path_against_base(root, cwd.as_path()))
+                .collect::<std::io::Result<Vec<_>>>()
+                .context("failed to resolve workspace root")?
+        };
         let matched_rules = policy.matches_for_command_with_options(
             &self.command,
             None,
             &MatchOptions {
                 resolve_host_executables: self.resolve_host_executables,
+                cwd: Some(cwd),
+                workspace_roots,
             },
         );
 
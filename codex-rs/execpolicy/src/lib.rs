@@ -8,8 +8,10 @@ pub mod policy;
 pub mod rule;
 
 pub use amend::AmendError;
+pub use amend::PrefixRuleAmendment;
 pub use amend::blocking_append_allow_prefix_rule;
 pub use amend::blocking_append_network_rule;
+pub use amend::blocking_append_prefix_rule;
 pub use decision::Decision;
 pub use error::Error;
 pub use error::ErrorLocation;
@@ -21,6 +23,9 @@ pub use parser::PolicyParser;
 pub use policy::Evaluation;
 pub use policy::MatchOptions;
 pub use policy::Policy;
+pub use rule::ArgMatcher;
+pub use rule::BundleMatching;
+pub use rule::ClauseMatch;
 pub use rule::NetworkRuleProtocol;
 pub use rule::Rule;
 pub use rule::RuleMatch;
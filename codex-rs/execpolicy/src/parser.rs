@@ -9,10 +9,14 @@ use starlark::eval::Evaluator;
 use starlark::starlark_module;
 use starlark::syntax::AstModule;
 use starlark::syntax::Dialect;
+use starlark::values::Heap;
 use starlark::values::Value;
 use starlark::values::list::ListRef;
 use starlark::values::list::UnpackList;
 use starlark::values::none::NoneType;
+use starlark::values::structs::AllocStruct;
+use starlark::values::structs::StructRef;
+use starlark::values::tuple::UnpackTuple;
 use std::cell::RefCell;
 use std::cell::RefMut;
 use std::collections::HashMap;
@@ -27,6 +31,7 @@ use crate::error::TextPosition;
 use crate::error::TextRange;
 use crate::executable_name::executable_lookup_key;
 use crate::executable_name::executable_path_lookup_key;
+use crate::rule::ArgMatcher;
 use crate::rule::NetworkRule;
 use crate::rule::NetworkRuleProtocol;
 use crate::rule::PatternToken;
@@ -184,6 +189,8 @@ fn parse_pattern<'v>(pattern: UnpackList<Value<'v>>) -> Result<Vec<PatternToken>
 fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
     if let Some(s) = value.unpack_str() {
         Ok(PatternToken::Single(s.to_string()))
+    } else if StructRef::from_value(value).is_some() {
+        parse_arg_matcher(value).map(PatternToken::Matcher)
     } else if let Some(list) = ListRef::from_value(value) {
         let tokens: Vec<String> = list
             .content()
@@ -210,12 +217,98 @@ fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
         }
     } else {
         Err(Error::InvalidPattern(format!(
-            "pattern element must be a string or list of strings (got {})",
+            "pattern element must be a string, list of strings or argument matcher (got {})",
             value.get_type()
         )))
     }
 }
 
+/// Argument matcher builtins return a struct naming the matcher kind and its string arguments,
+/// which is decoded again by [`parse_arg_matcher`].
+const MATCHER_KIND_FIELD: &str = "matcher";
+const MATCHER_VALUES_FIELD: &str = "values";
+/// Only set for `flag()`.
+const MATCHER_BUNDLES_WITH_FIELD: &str = "bundles_with";
+
+fn alloc_arg_matcher<'v>(heap: &'v Heap, matcher: &ArgMatcher) -> Value<'v> {
+    let (kind, values, bundles_with) = match matcher {
+        ArgMatcher::Flag {
+            names,
+            bundles_with,
+        } => ("flag", names.clone(), Some(bundles_with.as_str())),
+        ArgMatcher::Regex(regex) => ("regex", vec![regex.as_str().to_string()], None),
+        ArgMatcher::Glob(glob) => ("glob", vec![glob.as_str().to_string()], None),
+        ArgMatcher::WorkspacePath => ("workspace_path", Vec::new(), None),
+        ArgMatcher::OutsideWorkspace => ("outside_workspace", Vec::new(), None),
+    };
+    let mut fields = vec![
+        (MATCHER_KIND_FIELD, heap.alloc(kind)),
+        (MATCHER_VALUES_FIELD, heap.alloc(values)),
+    ];
+    if let Some(bundles_with) = bundles_with {
+        fields.push((MATCHER_BUNDLES_WITH_FIELD, heap.alloc(bundles_with)));
+    }
+    heap.alloc(AllocStruct(fields))
+}
+
+fn parse_arg_matcher<'v>(value: Value<'v>) -> Result<ArgMatcher> {
+    let invalid = || {
+        Error::InvalidPattern(format!(
+            "expected an argument matcher such as flag() or regex() (got {value})"
+        ))
+    };
+    let fields = StructRef::from_value(value).ok_or_else(invalid)?;
+    let mut kind = None;
+    let mut values = None;
+    let mut bundles_with = None;
+    for (name, field) in fields.iter() {
+        match name.as_str() {
+            MATCHER_KIND_FIELD => kind = field.unpack_str(),
+            MATCHER_BUNDLES_WITH_FIELD => {
+                bundles_with = Some(field.unpack_str().ok_or_else(invalid)?.to_string());
+            }
+            MATCHER_VALUES_FIELD => {
+                values = ListRef::from_value(field).and_then(|list| {
+                    list.content()
+                        .iter()
+                        .map(|value| value.unpack_str().map(str::to_string))
+                        .collect::<Option<Vec<_>>>()
+                });
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    let (Some(kind), Some(values)) = (kind, values) else {
+        return Err(invalid());
+    };
+
+    match (kind, values.as_slice()) {
+        ("flag", _) => ArgMatcher::flag(values, bundles_with),
+        ("regex", [source]) => ArgMatcher::regex(source),
+        ("glob", [source]) => ArgMatcher::glob(source),
+        ("workspace_path", []) => Ok(ArgMatcher::WorkspacePath),
+        ("outside_workspace", []) => Ok(ArgMatcher::OutsideWorkspace),
+        _ => Err(invalid()),
+    }
+}
+
+/// Evaluates a single argument clause written in policy syntax, such as `flag("--force", "-f")`.
+pub(crate) fn parse_arg_matcher_source(source: &str) -> Result<ArgMatcher> {
+    let ast = AstModule::parse("clause", source.to_string(), &Dialect::Extended)
+        .map_err(Error::Starlark)?;
+    let globals = GlobalsBuilder::standard().with(policy_builtins).build();
+    let module = Module::new();
+    let builder = RefCell::new(PolicyBuilder::new());
+    let mut eval = Evaluator::new(&module);
+    eval.extra = Some(&builder);
+    let value = eval.eval_module(ast, &globals).map_err(Error::Starlark)?;
+    parse_arg_matcher(value)
+}
+
+fn parse_arg_matchers<'v>(matchers: UnpackList<Value<'v>>) -> Result<Vec<ArgMatcher>> {
+    matchers.items.into_iter().map(parse_arg_matcher).collect()
+}
+
 fn parse_examples<'v>(examples: UnpackList<Value<'v>>) -> Result<Vec<Vec<String>>> {
     examples.items.into_iter().map(parse_example).collect()
 }
@@ -346,12 +439,15 @@ fn policy_builder<'v, 'a>(eval: &Evaluator<'v, 'a, '_>) -> RefMut<'a, PolicyBuil
 
 #[starlark_module]
 fn policy_builtins(builder: &mut GlobalsBuilder) {
+    #[allow(clippy::too_many_arguments)]
     fn prefix_rule<'v>(
         pattern: UnpackList<Value<'v>>,
         decision: Option<&'v str>,
         r#match: Option<UnpackList<Value<'v>>>,
         not_match: Option<UnpackList<Value<'v>>>,
         justification: Option<&'v str>,
+        args: Option<UnpackList<Value<'v>>>,
+        not_matching: Option<UnpackList<Value<'v>>>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<NoneType> {
         let decision = match decision {
@@ -368,6 +464,14 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         };
 
         let pattern_tokens = parse_pattern(pattern)?;
+        let args = args
+            .map(parse_arg_matchers)
+            .transpose()?
+            .unwrap_or_default();
+        let not_matching = not_matching
+            .map(parse_arg_matchers)
+            .transpose()?
+            .unwrap_or_default();
 
         let matches: Vec<Vec<String>> =
             r#match.map(parse_examples).transpose()?.unwrap_or_default();
@@ -384,6 +488,12 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         let (first_token, remaining_tokens) = pattern_tokens
             .split_first()
             .ok_or_else(|| Error::InvalidPattern("pattern cannot be empty".to_string()))?;
+        if matches!(first_token, PatternToken::Matcher(_)) {
+            return Err(Error::InvalidPattern(
+                "the first pattern element must name the program".to_string(),
+            )
+            .into());
+        }
 
         let rest: Arc<[PatternToken]> = remaining_tokens.to_vec().into();
 
@@ -396,6 +506,8 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
                         first: Arc::from(head.as_str()),
                         rest: rest.clone(),
                     },
+                    args: args.clone(),
+                    not_matching: not_matching.clone(),
                     decision,
                     justification: justification.clone(),
                 }) as RuleRef
@@ -407,6 +519,37 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         Ok(NoneType)
     }
 
+    fn flag<'v>(
+        #[starlark(args)] names: UnpackTuple<&'v str>,
+        #[starlark(require = named)] bundles_with: Option<&'v str>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<Value<'v>> {
+        let matcher = ArgMatcher::flag(
+            names.items.into_iter().map(str::to_string).collect(),
+            bundles_with.map(str::to_string),
+        )?;
+        Ok(alloc_arg_matcher(eval.heap(), &matcher))
+    }
+
+    fn regex<'v>(pattern: &'v str, eval: &mut Evaluator<'v, '_, '_>) -> anyhow::Result<Value<'v>> {
+        Ok(alloc_arg_matcher(eval.heap(), &ArgMatcher::regex(pattern)?))
+    }
+
+    fn glob<'v>(pattern: &'v str, eval: &mut Evaluator<'v, '_, '_>) -> anyhow::Result<Value<'v>> {
+        Ok(alloc_arg_matcher(eval.heap(), &ArgMatcher::glob(pattern)?))
+    }
+
+    fn workspace_path<'v>(eval: &mut Evaluator<'v, '_, '_>) -> anyhow::Result<Value<'v>> {
+        Ok(alloc_arg_matcher(eval.heap(), &ArgMatcher::WorkspacePath))
+    }
+
+    fn outside_workspace<'v>(eval: &mut Evaluator<'v, '_, '_>) -> anyhow::Result<Value<'v>> {
+        Ok(alloc_arg_matcher(
+            eval.heap(),
+            &ArgMatcher::OutsideWorkspace,
+        ))
+    }
+
     fn network_rule<'v>(
         host: &'v str,
         protocol: &'v str,
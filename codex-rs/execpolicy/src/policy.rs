@@ -1,3 +1,4 @@
+use crate::amend::PrefixRuleAmendment;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
@@ -22,6 +23,13 @@ type HeuristicsFallback<'a> = Option<&'a dyn Fn(&[String]) -> Decision>;
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct MatchOptions {
     pub resolve_host_executables: bool,
+    /// Working directory of the command. `workspace_path()` and `outside_workspace()` resolve
+    /// relative path arguments against it.
+    pub cwd: Option<AbsolutePathBuf>,
+    /// Directories that count as the workspace for `workspace_path()` and `outside_workspace()`.
+    /// These come from the session configuration rather than from the command, so a command
+    /// cannot widen its workspace by choosing its own working directory.
+    pub workspace_roots: Vec<AbsolutePathBuf>,
 }
 
 #[derive(Clone, Debug)]
@@ -72,7 +80,11 @@ impl Policy {
                 let Some(prefix_rule) = rule.as_any().downcast_ref::<PrefixRule>() else {
                     continue;
                 };
-                if prefix_rule.decision != Decision::Allow {
+                // Rules constrained by argument clauses do not allow a plain prefix.
+                if prefix_rule.decision != Decision::Allow
+                    || !prefix_rule.args.is_empty()
+                    || !prefix_rule.not_matching.is_empty()
+                {
                     continue;
                 }
 
@@ -89,7 +101,16 @@ impl Policy {
     }
 
     pub fn add_prefix_rule(&mut self, prefix: &[String], decision: Decision) -> Result<()> {
-        let (first_token, rest) = prefix
+        self.add_prefix_rule_amendment(&PrefixRuleAmendment {
+            decision,
+            ..PrefixRuleAmendment::allow(prefix.to_vec())
+        })
+    }
+
+    /// Adds the rule that [`crate::blocking_append_prefix_rule`] persists for `amendment`.
+    pub fn add_prefix_rule_amendment(&mut self, amendment: &PrefixRuleAmendment) -> Result<()> {
+        let (first_token, rest) = amendment
+            .prefix
             .split_first()
             .ok_or_else(|| Error::InvalidPattern("prefix cannot be empty".to_string()))?;
 
@@ -102,8 +123,10 @@ impl Policy {
                     .collect::<Vec<_>>()
                     .into(),
             },
-            decision,
-            justification: None,
+            args: amendment.args.clone(),
+            not_matching: amendment.not_matching.clone(),
+            decision: amendment.decision,
+            justification: amendment.justification.clone(),
         });
 
         self.rules_by_program.insert(first_token.clone(), rule);
@@ -272,12 +295,12 @@ impl Policy {
         options: &MatchOptions,
     ) -> Vec<RuleMatch> {
         let matched_rules = self
-            .match_exact_rules(cmd)
+            .match_exact_rules(cmd, options)
             .filter(|matched_rules| !matched_rules.is_empty())
             .or_else(|| {
                 options
                     .resolve_host_executables
-                    .then(|| self.match_host_executable_rules(cmd))
+                    .then(|| self.match_host_executable_rules(cmd, options))
                     .filter(|matched_rules| !matched_rules.is_empty())
             })
             .unwrap_or_default();
@@ -294,17 +317,26 @@ impl Policy {
         }
     }
 
-    fn match_exact_rules(&self, cmd: &[String]) -> Option<Vec<RuleMatch>> {
+    fn match_exact_rules(&self, cmd: &[String], options: &MatchOptions) -> Option<Vec<RuleMatch>> {
         let first = cmd.first()?;
         Some(
             self.rules_by_program
                 .get_vec(first)
-                .map(|rules| rules.iter().filter_map(|rule| rule.matches(cmd)).collect())
+                .map(|rules| {
+                    rules
+                        .iter()
+                        .filter_map(|rule| rule.matches(cmd, options))
+                        .collect()
+                })
                 .unwrap_or_default(),
         )
     }
 
-    fn match_host_executable_rules(&self, cmd: &[String]) -> Vec<RuleMatch> {
+    fn match_host_executable_rules(
+        &self,
+        cmd: &[String],
+        options: &MatchOptions,
+    ) -> Vec<RuleMatch> {
         let Some(first) = cmd.first() else {
             return Vec::new();
         };
@@ -328,7 +360,7 @@ impl Policy {
             .collect::<Vec<_>>();
         rules
             .iter()
-            .filter_map(|rule| rule.matches(&basename_command))
+            .filter_map(|rule| rule.matches(&basename_command, options))
             .map(|rule_match| rule_match.with_resolved_program(&program))
             .collect()
     }
@@ -343,6 +375,7 @@ fn render_pattern_token(token: &PatternToken) -> String {
     match token {
         PatternToken::Single(value) => value.clone(),
         PatternToken::Alts(alternatives) => format!("[{}]", alternatives.join("|")),
+        PatternToken::Matcher(matcher) => matcher.to_string(),
     }
 }
 
@@ -4,36 +4,316 @@ use crate::error::Result;
 use crate::policy::MatchOptions;
 use crate::policy::Policy;
 use codex_utils_absolute_path::AbsolutePathBuf;
+use globset::Glob;
+use globset::GlobMatcher;
+use regex_lite::Regex;
 use serde::Deserialize;
 use serde::Serialize;
 use shlex::try_join;
 use std::any::Any;
+use std::fmt;
 use std::fmt::Debug;
 use std::sync::Arc;
 
-/// Matches a single command token, either a fixed string or one of several allowed alternatives.
+/// Matches a single command token: a fixed string, one of several allowed alternatives, or an
+/// [`ArgMatcher`] such as `regex(...)`.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PatternToken {
     Single(String),
     Alts(Vec<String>),
+    Matcher(ArgMatcher),
 }
 
 impl PatternToken {
-    fn matches(&self, token: &str) -> bool {
+    fn matches(&self, token: &str, options: &MatchOptions, bundles: BundleMatching) -> bool {
         match self {
             Self::Single(expected) => expected == token,
             Self::Alts(alternatives) => alternatives.iter().any(|alt| alt == token),
+            Self::Matcher(matcher) => matcher.matches(token, options, bundles),
         }
     }
 
+    /// Literal alternatives for this token. Empty for [`PatternToken::Matcher`].
     pub fn alternatives(&self) -> &[String] {
         match self {
             Self::Single(expected) => std::slice::from_ref(expected),
             Self::Alts(alternatives) => alternatives,
+            Self::Matcher(_) => &[],
         }
     }
 }
 
+/// How `flag()` matches single-letter short flags bundled with other letters.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BundleMatching {
+    /// Only bundles made of listed short flags and the boolean flags in `bundles_with` match.
+    /// Used where a match relaxes the decision, so a flag with an attached value such as
+    /// `-ofile` is not mistaken for `-f`.
+    KnownFlags,
+    /// Any bundle containing a listed short flag matches. Used where a match restricts the
+    /// decision, so bundling an unlisted letter cannot slip a command past the clause.
+    AnyLetters,
+}
+
+/// Matches individual argv tokens. Built by the `flag()`, `regex()`, `glob()`,
+/// `workspace_path()` and `outside_workspace()` policy builtins.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ArgMatcher {
+    /// Any of the listed flags. Long flags also match `--flag=value`. Single-letter short flags
+    /// also match a bundle such as `-fu`; see [`BundleMatching`] for which bundles qualify.
+    Flag {
+        names: Vec<String>,
+        bundles_with: String,
+    },
+    /// A regular expression that must match the whole token.
+    Regex(TokenRegex),
+    /// A glob that must match the whole token.
+    Glob(TokenGlob),
+    /// A path argument that resolves inside one of the workspace roots.
+    WorkspacePath,
+    /// A path argument that resolves outside every workspace root.
+    OutsideWorkspace,
+}
+
+impl ArgMatcher {
+    pub fn flag(names: Vec<String>, bundles_with: Option<String>) -> Result<Self> {
+        if names.is_empty() {
+            return Err(Error::InvalidPattern(
+                "flag() requires at least one flag name".to_string(),
+            ));
+        }
+        if let Some(name) = names
+            .iter()
+            .find(|name| !name.starts_with('-') || name.trim_start_matches('-').is_empty())
+        {
+            return Err(Error::InvalidPattern(format!(
+                "flag names must start with `-` (got {name})"
+            )));
+        }
+        let bundles_with = bundles_with.unwrap_or_default();
+        if let Some(letter) = bundles_with
+            .chars()
+            .find(|letter| !letter.is_ascii_alphanumeric())
+        {
+            return Err(Error::InvalidPattern(format!(
+                "bundles_with must list single-letter flags without `-` (got {letter:?})"
+            )));
+        }
+        Ok(Self::Flag {
+            names,
+            bundles_with,
+        })
+    }
+
+    pub fn regex(source: &str) -> Result<Self> {
+        TokenRegex::new(source).map(Self::Regex)
+    }
+
+    pub fn glob(source: &str) -> Result<Self> {
+        TokenGlob::new(source).map(Self::Glob)
+    }
+
+    /// Parses a clause as written in a policy file, the inverse of the [`fmt::Display`] impl.
+    pub fn parse(source: &str) -> Result<Self> {
+        crate::parser::parse_arg_matcher_source(source)
+    }
+
+    pub fn matches(&self, token: &str, options: &MatchOptions, bundles: BundleMatching) -> bool {
+        match self {
+            Self::Flag {
+                names,
+                bundles_with,
+            } => {
+                names
+                    .iter()
+                    .any(|name| name == token || long_flag_matches(name, token))
+                    || short_flag_bundle_matches(names, bundles_with, token, bundles)
+            }
+            Self::Regex(regex) => regex.regex.is_match(token),
+            Self::Glob(glob) => glob.matcher.is_match(token),
+            Self::WorkspacePath => path_inside_workspace(token, options) == Some(true),
+            // Without a working directory or workspace roots every path argument is treated as
+            // outside.
+            Self::OutsideWorkspace => path_inside_workspace(token, options) == Some(false),
+        }
+    }
+
+    /// Returns the index of the first token that this matcher accepts. Flags are not looked up
+    /// past a bare `--`, since everything after it is a positional argument.
+    fn find(
+        &self,
+        tokens: &[String],
+        options: &MatchOptions,
+        bundles: BundleMatching,
+    ) -> Option<usize> {
+        let searchable = match self {
+            Self::Flag { .. } => tokens
+                .iter()
+                .position(|token| token == "--")
+                .unwrap_or(tokens.len()),
+            _ => tokens.len(),
+        };
+        tokens[..searchable]
+            .iter()
+            .position(|token| self.matches(token, options, bundles))
+    }
+}
+
+/// Renders the matcher as the Starlark call that builds it.
+impl fmt::Display for ArgMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Flag {
+                names,
+                bundles_with,
+            } => {
+                let mut args = names
+                    .iter()
+                    .map(|name| starlark_string(name))
+                    .collect::<Vec<_>>();
+                if !bundles_with.is_empty() {
+                    args.push(format!("bundles_with = {}", starlark_string(bundles_with)));
+                }
+                write!(f, "flag({})", args.join(", "))
+            }
+            Self::Regex(regex) => write!(f, "regex({})", starlark_string(regex.as_str())),
+            Self::Glob(glob) => write!(f, "glob({})", starlark_string(glob.as_str())),
+            Self::WorkspacePath => f.write_str("workspace_path()"),
+            Self::OutsideWorkspace => f.write_str("outside_workspace()"),
+        }
+    }
+}
+
+fn starlark_string(raw: &str) -> String {
+    serde_json::to_string(raw).unwrap_or_else(|_| format!("{raw:?}"))
+}
+
+/// Whether `token` is the long flag `name` with an attached `=value`.
+fn long_flag_matches(name: &str, token: &str) -> bool {
+    name.starts_with("--")
+        && token
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.starts_with('='))
+}
+
+/// Whether `token` bundles one of the single-letter flags in `names` with other letters. With
+/// [`BundleMatching::KnownFlags`] every other letter must be a boolean flag from `bundles_with`.
+fn short_flag_bundle_matches(
+    names: &[String],
+    bundles_with: &str,
+    token: &str,
+    bundles: BundleMatching,
+) -> bool {
+    let Some(bundle) = token.strip_prefix('-') else {
+        return false;
+    };
+    if bundle.starts_with('-') || bundle.chars().count() < 2 {
+        return false;
+    }
+    let short_letters = names
+        .iter()
+        .filter_map(|name| {
+            let mut letters = name.strip_prefix('-')?.chars();
+            match (letters.next(), letters.next()) {
+                (Some(letter), None) if letter != '-' => Some(letter),
+                _ => None,
+            }
+        })
+        .collect::<Vec<_>>();
+    bundle.chars().any(|letter| short_letters.contains(&letter))
+        && (bundles == BundleMatching::AnyLetters
+            || bundle
+                .chars()
+                .all(|letter| short_letters.contains(&letter) || bundles_with.contains(letter)))
+}
+
+/// Returns whether the path named by `token`, resolved against the command's working directory,
+/// stays inside one of the workspace roots, or `None` when the token is a flag rather than a path.
+/// The check is lexical: symlinks are not resolved.
+fn path_inside_workspace(token: &str, options: &MatchOptions) -> Option<bool> {
+    let candidate = if token.starts_with("--") {
+        token.split_once('=').map(|(_, value)| value)?
+    } else if token.starts_with('-') {
+        return None;
+    } else {
+        token
+    };
+    if candidate.is_empty() {
+        return None;
+    }
+    let Some(cwd) = options.cwd.as_ref() else {
+        return Some(false);
+    };
+    let Ok(resolved) = AbsolutePathBuf::resolve_path_against_base(candidate, cwd.as_path()) else {
+        return Some(false);
+    };
+    let inside = options
+        .workspace_roots
+        .iter()
+        .any(|root| resolved.as_path().starts_with(root.as_path()));
+    Some(inside)
+}
+
+/// A regular expression anchored to match whole tokens.
+#[derive(Clone, Debug)]
+pub struct TokenRegex {
+    source: String,
+    regex: Regex,
+}
+
+impl TokenRegex {
+    pub fn new(source: &str) -> Result<Self> {
+        let regex = Regex::new(&format!("^(?:{source})$"))
+            .map_err(|error| Error::InvalidPattern(format!("invalid regex `{source}`: {error}")))?;
+        Ok(Self {
+            source: source.to_string(),
+            regex,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
+
+impl PartialEq for TokenRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for TokenRegex {}
+
+/// A glob matched against whole tokens; `*` also matches `/`.
+#[derive(Clone, Debug)]
+pub struct TokenGlob {
+    source: String,
+    matcher: GlobMatcher,
+}
+
+impl TokenGlob {
+    pub fn new(source: &str) -> Result<Self> {
+        let glob = Glob::new(source)
+            .map_err(|error| Error::InvalidPattern(format!("invalid glob `{source}`: {error}")))?;
+        Ok(Self {
+            source: source.to_string(),
+            matcher: glob.compile_matcher(),
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
+
+impl PartialEq for TokenGlob {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for TokenGlob {}
+
 /// Prefix matcher for commands with support for alternative match tokens.
 /// First token is fixed since we key by the first token in policy.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -43,14 +323,19 @@ pub struct PrefixPattern {
 }
 
 impl PrefixPattern {
-    pub fn matches_prefix(&self, cmd: &[String]) -> Option<Vec<String>> {
+    pub fn matches_prefix(
+        &self,
+        cmd: &[String],
+        options: &MatchOptions,
+        bundles: BundleMatching,
+    ) -> Option<Vec<String>> {
         let pattern_length = self.rest.len() + 1;
         if cmd.len() < pattern_length || cmd[0] != self.first.as_ref() {
             return None;
         }
 
         for (pattern_token, cmd_token) in self.rest.iter().zip(&cmd[1..pattern_length]) {
-            if !pattern_token.matches(cmd_token) {
+            if !pattern_token.matches(cmd_token, options, bundles) {
                 return None;
             }
         }
@@ -59,6 +344,17 @@ impl PrefixPattern {
     }
 }
 
+/// Records which argument-level clause of a rule matched which command token.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClauseMatch {
+    /// The clause as written in the policy, e.g. `flag("--force", "-f")`.
+    pub clause: String,
+    /// Index of the matched token in the command.
+    pub index: usize,
+    pub token: String,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum RuleMatch {
@@ -74,6 +370,13 @@ pub enum RuleMatch {
         /// (e.g., prompt reasons or rejection messages).
         #[serde(skip_serializing_if = "Option::is_none")]
         justification: Option<String>,
+        /// Argument matchers in `pattern` or `args` that contributed to the match.
+        #[serde(
+            rename = "matchedClauses",
+            default,
+            skip_serializing_if = "Vec::is_empty"
+        )]
+        matched_clauses: Vec<ClauseMatch>,
     },
     HeuristicsRuleMatch {
         command: Vec<String>,
@@ -95,12 +398,14 @@ impl RuleMatch {
                 matched_prefix,
                 decision,
                 justification,
+                matched_clauses,
                 ..
             } => Self::PrefixRuleMatch {
                 matched_prefix,
                 decision,
                 resolved_program: Some(resolved_program.clone()),
                 justification,
+                matched_clauses,
             },
             other => other,
         }
@@ -110,10 +415,33 @@ impl RuleMatch {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PrefixRule {
     pub pattern: PrefixPattern,
+    /// Matchers that must each accept some token after the prefix.
+    pub args: Vec<ArgMatcher>,
+    /// Matchers that exclude a command from this rule when they accept any token after the prefix.
+    pub not_matching: Vec<ArgMatcher>,
     pub decision: Decision,
     pub justification: Option<String>,
 }
 
+impl PrefixRule {
+    /// Bundle matching for the pattern and `args`. A match against an `allow` rule relaxes the
+    /// decision, while a match against a `prompt` or `forbidden` rule restricts it.
+    fn bundle_matching(&self) -> BundleMatching {
+        match self.decision {
+            Decision::Allow => BundleMatching::KnownFlags,
+            Decision::Prompt | Decision::Forbidden => BundleMatching::AnyLetters,
+        }
+    }
+
+    /// Bundle matching for `not_matching`, whose match has the opposite effect of `args`.
+    fn not_matching_bundle_matching(&self) -> BundleMatching {
+        match self.bundle_matching() {
+            BundleMatching::KnownFlags => BundleMatching::AnyLetters,
+            BundleMatching::AnyLetters => BundleMatching::KnownFlags,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum NetworkRuleProtocol {
     Http,
@@ -214,7 +542,7 @@ pub(crate) fn normalize_network_rule_host(raw: &str) -> Result<String> {
 pub trait Rule: Any + Debug + Send + Sync {
     fn program(&self) -> &str;
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch>;
+    fn matches(&self, cmd: &[String], options: &MatchOptions) -> Option<RuleMatch>;
 
     fn as_any(&self) -> &dyn Any;
 }
@@ -226,15 +554,51 @@ impl Rule for PrefixRule {
         self.pattern.first.as_ref()
     }
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch> {
-        self.pattern
-            .matches_prefix(cmd)
-            .map(|matched_prefix| RuleMatch::PrefixRuleMatch {
-                matched_prefix,
-                decision: self.decision,
-                resolved_program: None,
-                justification: self.justification.clone(),
+    fn matches(&self, cmd: &[String], options: &MatchOptions) -> Option<RuleMatch> {
+        let bundles = self.bundle_matching();
+        let matched_prefix = self.pattern.matches_prefix(cmd, options, bundles)?;
+        let args_start = matched_prefix.len();
+        let remaining = &cmd[args_start..];
+        let not_matching_bundles = self.not_matching_bundle_matching();
+        if self.not_matching.iter().any(|matcher| {
+            matcher
+                .find(remaining, options, not_matching_bundles)
+                .is_some()
+        }) {
+            return None;
+        }
+
+        let mut matched_clauses = self
+            .pattern
+            .rest
+            .iter()
+            .zip(&cmd[1..args_start])
+            .enumerate()
+            .filter_map(|(offset, (pattern_token, token))| match pattern_token {
+                PatternToken::Matcher(matcher) => Some(ClauseMatch {
+                    clause: matcher.to_string(),
+                    index: offset + 1,
+                    token: token.clone(),
+                }),
+                PatternToken::Single(_) | PatternToken::Alts(_) => None,
             })
+            .collect::<Vec<_>>();
+        for matcher in &self.args {
+            let offset = matcher.find(remaining, options, bundles)?;
+            matched_clauses.push(ClauseMatch {
+                clause: matcher.to_string(),
+                index: args_start + offset,
+                token: remaining[offset].clone(),
+            });
+        }
+
+        Some(RuleMatch::PrefixRuleMatch {
+            matched_prefix,
+            decision: self.decision,
+            resolved_program: None,
+            justification: self.justification.clone(),
+            matched_clauses,
+        })
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -242,6 +606,16 @@ impl Rule for PrefixRule {
     }
 }
 
+/// Examples are validated with the process working directory standing in for the workspace.
+fn example_match_options() -> MatchOptions {
+    let cwd = AbsolutePathBuf::current_dir().ok();
+    MatchOptions {
+        resolve_host_executables: true,
+        workspace_roots: cwd.iter().cloned().collect(),
+        cwd,
+    }
+}
+
 /// Count how many rules match each provided example and error if any example is unmatched.
 pub(crate) fn validate_match_examples(
     policy: &Policy,
@@ -249,9 +623,7 @@ pub(crate) fn validate_match_examples(
     matches: &[Vec<String>],
 ) -> Result<()> {
     let mut unmatched_examples = Vec::new();
-    let options = MatchOptions {
-        resolve_host_executables: true,
-    };
+    let options = example_match_options();
 
     for example in matches {
         if !policy
@@ -284,9 +656,7 @@ pub(crate) fn validate_not_match_examples(
     _rules: &[RuleRef],
     not_matches: &[Vec<String>],
 ) -> Result<()> {
-    let options = MatchOptions {
-        resolve_host_executables: true,
-    };
+    let options = example_match_options();
 
     for example in not_matches {
         if let Some(rule) = policy
@@ -5,6 +5,8 @@ use std::sync::Arc;
 
 use anyhow::Context;
 use anyhow::Result;
+use codex_execpolicy::ArgMatcher;
+use codex_execpolicy::ClauseMatch;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error;
 use codex_execpolicy::Evaluation;
@@ -12,6 +14,7 @@ use codex_execpolicy::MatchOptions;
 use codex_execpolicy::NetworkRuleProtocol;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
+use codex_execpolicy::PrefixRuleAmendment;
 use codex_execpolicy::RuleMatch;
 use codex_execpolicy::RuleRef;
 use codex_execpolicy::blocking_append_allow_prefix_rule;
@@ -159,6 +162,7 @@ prefix_rule(
                 decision: Decision::Allow,
                 resolved_program: None,
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         },
         evaluation
@@ -191,6 +195,7 @@ prefix_rule(
                 decision: Decision::Forbidden,
                 resolved_program: None,
                 justification: Some("destructive command".to_string()),
+                matched_clauses: Vec::new(),
             }],
         },
         evaluation
@@ -220,6 +225,7 @@ prefix_rule(
                 decision: Decision::Allow,
                 resolved_program: None,
                 justification: Some("safe and commonly used".to_string()),
+                matched_clauses: Vec::new(),
             }],
         },
         evaluation
@@ -260,6 +266,8 @@ fn add_prefix_rule_extends_policy() -> Result<()> {
             },
             decision: Decision::Prompt,
             justification: None,
+            args: Vec::new(),
+            not_matching: Vec::new(),
         })],
         rules
     );
@@ -273,6 +281,7 @@ fn add_prefix_rule_extends_policy() -> Result<()> {
                 decision: Decision::Prompt,
                 resolved_program: None,
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         },
         evaluation
@@ -321,6 +330,8 @@ prefix_rule(
                 },
                 decision: Decision::Prompt,
                 justification: None,
+                args: Vec::new(),
+                not_matching: Vec::new(),
             }),
             RuleSnapshot::Prefix(PrefixRule {
                 pattern: PrefixPattern {
@@ -329,6 +340,8 @@ prefix_rule(
                 },
                 decision: Decision::Forbidden,
                 justification: None,
+                args: Vec::new(),
+                not_matching: Vec::new(),
             }),
         ],
         git_rules
@@ -343,6 +356,7 @@ prefix_rule(
                 decision: Decision::Prompt,
                 resolved_program: None,
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         },
         status_eval
@@ -358,12 +372,14 @@ prefix_rule(
                     decision: Decision::Prompt,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 },
                 RuleMatch::PrefixRuleMatch {
                     matched_prefix: tokens(&["git", "commit"]),
                     decision: Decision::Forbidden,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 },
             ],
         },
@@ -398,6 +414,8 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            args: Vec::new(),
+            not_matching: Vec::new(),
         })],
         bash_rules
     );
@@ -409,6 +427,8 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            args: Vec::new(),
+            not_matching: Vec::new(),
         })],
         sh_rules
     );
@@ -422,6 +442,7 @@ prefix_rule(
                 decision: Decision::Allow,
                 resolved_program: None,
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         },
         bash_eval
@@ -436,6 +457,7 @@ prefix_rule(
                 decision: Decision::Allow,
                 resolved_program: None,
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         },
         sh_eval
@@ -470,6 +492,8 @@ prefix_rule(
             },
             decision: Decision::Allow,
             justification: None,
+            args: Vec::new(),
+            not_matching: Vec::new(),
         })],
         rules
     );
@@ -483,6 +507,7 @@ prefix_rule(
                 decision: Decision::Allow,
                 resolved_program: None,
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         },
         npm_i
@@ -500,6 +525,7 @@ prefix_rule(
                 decision: Decision::Allow,
                 resolved_program: None,
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         },
         npm_install
@@ -531,6 +557,7 @@ prefix_rule(
                 decision: Decision::Allow,
                 resolved_program: None,
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         },
         match_eval
@@ -579,12 +606,14 @@ prefix_rule(
                     decision: Decision::Prompt,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 },
                 RuleMatch::PrefixRuleMatch {
                     matched_prefix: tokens(&["git", "commit"]),
                     decision: Decision::Forbidden,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 },
             ],
         },
@@ -624,18 +653,21 @@ prefix_rule(
                     decision: Decision::Prompt,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 },
                 RuleMatch::PrefixRuleMatch {
                     matched_prefix: tokens(&["git"]),
                     decision: Decision::Prompt,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 },
                 RuleMatch::PrefixRuleMatch {
                     matched_prefix: tokens(&["git", "commit"]),
                     decision: Decision::Forbidden,
                     resolved_program: None,
                     justification: None,
+                    matched_clauses: Vec::new(),
                 },
             ],
         },
@@ -786,6 +818,8 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            cwd: None,
+            workspace_roots: Vec::new(),
         },
     );
     assert_eq!(
@@ -797,6 +831,7 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
                 decision: Decision::Prompt,
                 resolved_program: Some(absolute_path(&git_path)),
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         }
     );
@@ -843,6 +878,8 @@ host_executable(name = "git", paths = [])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            cwd: None,
+            workspace_roots: Vec::new(),
         },
     );
     assert_eq!(
@@ -878,6 +915,8 @@ host_executable(name = "git", paths = ["{allowed_git_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            cwd: None,
+            workspace_roots: Vec::new(),
         },
     );
     assert_eq!(
@@ -908,6 +947,8 @@ prefix_rule(pattern = ["git"], decision = "prompt")
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            cwd: None,
+            workspace_roots: Vec::new(),
         },
     );
     assert_eq!(
@@ -919,6 +960,7 @@ prefix_rule(pattern = ["git"], decision = "prompt")
                 decision: Decision::Prompt,
                 resolved_program: Some(absolute_path(&git_path)),
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         }
     );
@@ -945,6 +987,8 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
         &allow_all,
         &MatchOptions {
             resolve_host_executables: true,
+            cwd: None,
+            workspace_roots: Vec::new(),
         },
     );
     assert_eq!(
@@ -956,8 +1000,343 @@ host_executable(name = "git", paths = ["{git_path_literal}"])
                 decision: Decision::Allow,
                 resolved_program: None,
                 justification: None,
+                matched_clauses: Vec::new(),
             }],
         }
     );
     Ok(())
 }
+
+#[test]
+fn args_match_flags_anywhere_after_the_prefix() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", "push"],
+    args = [flag("--force", "-f")],
+    decision = "forbidden",
+    justification = "Use --force-with-lease instead.",
+    match = ["git push origin main --force", "git push -f origin main", "git push --force=true"],
+    not_match = ["git push --force-with-lease", "git push origin -- -f", "git status --force"],
+)
+"#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(&tokens(&["git", "push", "origin", "-f"]), &allow_all);
+    assert_eq!(
+        evaluation,
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["git", "push"]),
+                decision: Decision::Forbidden,
+                resolved_program: None,
+                justification: Some("Use --force-with-lease instead.".to_string()),
+                matched_clauses: vec![ClauseMatch {
+                    clause: r#"flag("--force", "-f")"#.to_string(),
+                    index: 3,
+                    token: "-f".to_string(),
+                }],
+            }],
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn allow_rules_only_match_bundles_of_known_boolean_flags() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", "push"],
+    args = [flag("--force", "-f", bundles_with = "uv")],
+    decision = "allow",
+    match = ["git push -fu origin main", "git push -vuf", "git push -f"],
+    not_match = [
+        "git push -ofile",
+        "git push -Wfoo",
+        "git push -fx origin",
+        "git push -uv origin",
+    ],
+)
+prefix_rule(
+    pattern = ["cc"],
+    args = [flag("-f")],
+    decision = "allow",
+    match = ["cc -f main.c"],
+    not_match = ["cc -ffast-math main.c", "cc -ofile main.c", "cc -Wformat main.c"],
+)
+"#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(&tokens(&["git", "push", "-uf", "origin"]), &prompt_all);
+    assert_eq!(
+        evaluation.matched_rules,
+        vec![RuleMatch::PrefixRuleMatch {
+            matched_prefix: tokens(&["git", "push"]),
+            decision: Decision::Allow,
+            resolved_program: None,
+            justification: None,
+            matched_clauses: vec![ClauseMatch {
+                clause: r#"flag("--force", "-f", bundles_with = "uv")"#.to_string(),
+                index: 2,
+                token: "-uf".to_string(),
+            }],
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn restrictive_clauses_match_any_bundle_with_a_listed_flag() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", "push"],
+    args = [flag("--force", "-f")],
+    decision = "forbidden",
+    match = ["git push -qf origin", "git push -fq origin", "git push -f"],
+    not_match = ["git push -q origin", "git push --quiet"],
+)
+prefix_rule(
+    pattern = ["rm"],
+    not_matching = [flag("-r")],
+    match = ["rm file.txt"],
+    not_match = ["rm -qr dir", "rm -rq dir"],
+)
+"#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    for bundle in ["-qf", "-fq"] {
+        assert_eq!(
+            policy
+                .check(&tokens(&["git", "push", bundle, "origin"]), &allow_all)
+                .decision,
+            Decision::Forbidden
+        );
+    }
+    assert_eq!(
+        policy
+            .check(&tokens(&["rm", "-qr", "dir"]), &prompt_all)
+            .decision,
+        Decision::Prompt
+    );
+    Ok(())
+}
+
+#[test]
+fn not_matching_excludes_commands_from_a_rule() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["git", "push"],
+    not_matching = [flag("--force", "-f"), regex("\\+.*")],
+    match = ["git push origin main"],
+    not_match = ["git push --force", "git push origin +main"],
+)
+"#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    assert_eq!(
+        policy
+            .check(&tokens(&["git", "push", "origin", "+main"]), &prompt_all)
+            .decision,
+        Decision::Prompt
+    );
+    assert_eq!(
+        policy
+            .check(&tokens(&["git", "push", "origin", "main"]), &prompt_all)
+            .decision,
+        Decision::Allow
+    );
+    assert_eq!(policy.get_allowed_prefixes(), Vec::<Vec<String>>::new());
+    Ok(())
+}
+
+#[test]
+fn regex_and_glob_pattern_tokens_report_matched_clauses() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["cargo", regex("test|check"), glob("-p*")],
+    match = ["cargo test -pcodex-core", "cargo check -p"],
+    not_match = ["cargo testing -p", "cargo test --package"],
+)
+"#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(&tokens(&["cargo", "check", "-pcodex-state"]), &prompt_all);
+    assert_eq!(
+        evaluation.matched_rules,
+        vec![RuleMatch::PrefixRuleMatch {
+            matched_prefix: tokens(&["cargo", "check", "-pcodex-state"]),
+            decision: Decision::Allow,
+            resolved_program: None,
+            justification: None,
+            matched_clauses: vec![
+                ClauseMatch {
+                    clause: r#"regex("test|check")"#.to_string(),
+                    index: 1,
+                    token: "check".to_string(),
+                },
+                ClauseMatch {
+                    clause: r#"glob("-p*")"#.to_string(),
+                    index: 2,
+                    token: "-pcodex-state".to_string(),
+                },
+            ],
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn workspace_path_matchers_check_workspace_roots() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["rm", "-rf", workspace_path()],
+    not_matching = [outside_workspace()],
+)
+"#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+    let workspace = absolute_path(&host_absolute_path(&["work", "repo"]));
+    let options = MatchOptions {
+        resolve_host_executables: false,
+        cwd: Some(workspace.clone()),
+        workspace_roots: vec![workspace.clone()],
+    };
+    let outside = host_absolute_path(&["etc"]);
+    let decision = |cmd: &[&str]| {
+        policy
+            .check_with_options(&tokens(cmd), &prompt_all, &options)
+            .decision
+    };
+
+    assert_eq!(decision(&["rm", "-rf", "target"]), Decision::Allow);
+    assert_eq!(decision(&["rm", "-rf", "./a/../b"]), Decision::Allow);
+    assert_eq!(decision(&["rm", "-rf", "../other"]), Decision::Prompt);
+    assert_eq!(
+        decision(&["rm", "-rf", "target", &outside]),
+        Decision::Prompt
+    );
+    assert_eq!(
+        decision(&["rm", "-rf", "target", &format!("--out={outside}")]),
+        Decision::Prompt
+    );
+    // A command running outside the workspace does not widen it.
+    let root_cwd = MatchOptions {
+        cwd: Some(absolute_path(&host_absolute_path(&[]))),
+        ..options.clone()
+    };
+    assert_eq!(
+        policy
+            .check_with_options(&tokens(&["rm", "-rf", "etc"]), &prompt_all, &root_cwd)
+            .decision,
+        Decision::Prompt
+    );
+    let subdir_cwd = MatchOptions {
+        cwd: Some(absolute_path(&host_absolute_path(&["work", "repo", "src"]))),
+        ..options.clone()
+    };
+    assert_eq!(
+        policy
+            .check_with_options(
+                &tokens(&["rm", "-rf", "../target"]),
+                &prompt_all,
+                &subdir_cwd
+            )
+            .decision,
+        Decision::Allow
+    );
+    // Without a working directory no path counts as inside the workspace.
+    assert_eq!(
+        policy
+            .check(&tokens(&["rm", "-rf", "target"]), &prompt_all)
+            .decision,
+        Decision::Prompt
+    );
+    Ok(())
+}
+
+#[test]
+fn amended_prefix_rules_keep_their_argument_clauses() -> Result<()> {
+    let clauses = [
+        r#"flag("--force", "-f", bundles_with = "u")"#,
+        r#"regex("\\+.*")"#,
+        "outside_workspace()",
+    ];
+    let not_matching = clauses
+        .iter()
+        .map(|source| ArgMatcher::parse(source))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        not_matching
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        clauses
+    );
+
+    let mut policy = Policy::empty();
+    policy.add_prefix_rule_amendment(&PrefixRuleAmendment {
+        not_matching: not_matching[..2].to_vec(),
+        ..PrefixRuleAmendment::allow(tokens(&["git", "push"]))
+    })?;
+    assert_eq!(
+        policy
+            .check(&tokens(&["git", "push", "origin", "main"]), &prompt_all)
+            .decision,
+        Decision::Allow
+    );
+    assert_eq!(
+        policy
+            .check(&tokens(&["git", "push", "-fu", "origin"]), &prompt_all)
+            .decision,
+        Decision::Prompt
+    );
+    assert!(ArgMatcher::parse("prefix_rule(pattern = [\"git\"])").is_err());
+    Ok(())
+}
+
+#[test]
+fn argument_matchers_are_validated_at_load_time() {
+    let cases = [
+        (
+            r#"prefix_rule(pattern = [regex("git")])"#,
+            "the first pattern element must name the program",
+        ),
+        (
+            r#"prefix_rule(pattern = ["git"], args = [regex("(")])"#,
+            "invalid regex `(`",
+        ),
+        (
+            r#"prefix_rule(pattern = ["git"], args = [flag("force")])"#,
+            "flag names must start with `-` (got force)",
+        ),
+        (
+            r#"prefix_rule(pattern = ["git"], args = [flag("-f", bundles_with = "-u")])"#,
+            "bundles_with must list single-letter flags without `-`",
+        ),
+        (
+            r#"prefix_rule(pattern = ["git"], args = ["--force"])"#,
+            "expected an argument matcher",
+        ),
+    ];
+    for (policy_src, expected) in cases {
+        let mut parser = PolicyParser::new();
+        let err = parser
+            .parse("test.rules", policy_src)
+            .expect_err("expected parse error");
+        assert!(
+            err.to_string().contains(expected),
+            "expected `{expected}` in `{err}`"
+        );
+    }
+}
@@ -0,0 +1,336 @@
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+
+use crate::Hook;
+use crate::HookEvent;
+use crate::HookEventName;
+use crate::HookPayload;
+use crate::HookResult;
+use crate::HookToolInput;
+use crate::command_from_argv;
+
+pub const DEFAULT_COMMAND_HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A hook backed by an external command, as declared in `config.toml`.
+///
+/// The command receives the JSON-serialized [`HookPayload`] on stdin and may print a JSON
+/// decision on stdout. Empty stdout is treated as `{"decision": "allow"}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandHookConfig {
+    pub name: String,
+    pub event: HookEventName,
+    pub argv: Vec<String>,
+    pub timeout: Duration,
+    /// When the command fails (spawn error, timeout, non-zero exit or unparseable output), deny
+    /// the operation instead of continuing. Only `before_tool_use` and `user_prompt_submit`
+    /// hooks can deny; failures of other hooks are always logged and ignored.
+    pub fail_closed: bool,
+}
+
+/// Decision printed by a command hook on stdout.
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+enum CommandHookDecision {
+    Allow,
+    Deny {
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    Rewrite {
+        tool_input: HookToolInput,
+    },
+}
+
+pub fn command_hook(config: CommandHookConfig) -> Hook {
+    let config = Arc::new(config);
+    Hook {
+        name: config.name.clone(),
+        func: Arc::new(move |payload: &HookPayload| {
+            let config = Arc::clone(&config);
+            Box::pin(async move { run_command_hook(&config, payload).await })
+        }),
+    }
+}
+
+async fn run_command_hook(config: &CommandHookConfig, payload: &HookPayload) -> HookResult {
+    match try_run_command_hook(config, payload).await {
+        Ok(result) => result,
+        Err(err) if config.fail_closed && is_blocking_event(config.event) => HookResult::Deny {
+            reason: format!("hook '{}' failed: {err}", config.name),
+        },
+        Err(err) => HookResult::FailedContinue(err),
+    }
+}
+
+/// Events whose hooks may deny the operation, so a failing policy hook must not let it through.
+fn is_blocking_event(event: HookEventName) -> bool {
+    matches!(
+        event,
+        HookEventName::BeforeToolUse | HookEventName::UserPromptSubmit
+    )
+}
+
+async fn try_run_command_hook(
+    config: &CommandHookConfig,
+    payload: &HookPayload,
+) -> Result<HookResult, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let Some(mut command) = command_from_argv(&config.argv) else {
+        return Err(std::io::Error::other("hook command is empty").into());
+    };
+    let input = serde_json::to_vec(payload)?;
+
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    let mut child = command.spawn()?;
+
+    let stdin = child.stdin.take();
+    let write_stdin = async move {
+        if let Some(mut stdin) = stdin {
+            // Hooks are free to ignore their input, so a closed pipe is not an error.
+            let _ = stdin.write_all(&input).await;
+        }
+    };
+    let run = async move {
+        let ((), output) = tokio::join!(write_stdin, child.wait_with_output());
+        output
+    };
+    // Dropping `run` on timeout drops the child, which kills it.
+    let output = match tokio::time::timeout(config.timeout, run).await {
+        Ok(output) => output?,
+        Err(_) => {
+            return Err(std::io::Error::other(format!(
+                "hook timed out after {}ms",
+                config.timeout.as_millis()
+            ))
+            .into());
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(std::io::Error::other(format!(
+            "hook exited with {}: {}",
+            output.status,
+            stderr.trim()
+        ))
+        .into());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stdout = stdout.trim();
+    if stdout.is_empty() {
+        return Ok(HookResult::Success);
+    }
+    let decision = serde_json::from_str::<CommandHookDecision>(stdout)?;
+    Ok(decision_to_result(config, payload, decision))
+}
+
+fn decision_to_result(
+    config: &CommandHookConfig,
+    payload: &HookPayload,
+    decision: CommandHookDecision,
+) -> HookResult {
+    match decision {
+        CommandHookDecision::Allow => HookResult::Success,
+        CommandHookDecision::Deny { reason } => HookResult::Deny {
+            reason: reason.unwrap_or_else(|| format!("denied by hook '{}'", config.name)),
+        },
+        CommandHookDecision::Rewrite { tool_input } => match &payload.hook_event {
+            HookEvent::BeforeToolUse { .. } => HookResult::RewriteToolInput(tool_input),
+            _ => HookResult::FailedContinue(
+                std::io::Error::other(format!(
+                    "rewrite decisions are only supported for before_tool_use hooks, not {}",
+                    payload.hook_event.name()
+                ))
+                .into(),
+            ),
+        },
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(windows))]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use codex_protocol::ThreadId;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::HookEventBeforeToolUse;
+    use crate::HookEventSessionEnd;
+    use crate::HookToolKind;
+
+    fn before_tool_use_payload() -> HookPayload {
+        HookPayload {
+            session_id: ThreadId::new(),
+            cwd: PathBuf::from("/tmp"),
+            client: None,
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: "turn-1".to_string(),
+                    call_id: "call-1".to_string(),
+                    tool_name: "shell".to_string(),
+                    tool_kind: HookToolKind::Function,
+                    tool_input: HookToolInput::Function {
+                        arguments: r#"{"command":["rm","-rf","/"]}"#.to_string(),
+                    },
+                },
+            },
+        }
+    }
+
+    fn sh_hook(event: HookEventName, script: &str, timeout: Duration) -> Hook {
+        command_hook(CommandHookConfig {
+            name: "test-hook".to_string(),
+            event,
+            argv: vec!["/bin/sh".to_string(), "-c".to_string(), script.to_string()],
+            timeout,
+            fail_closed: true,
+        })
+    }
+
+    #[tokio::test]
+    async fn empty_stdout_allows() {
+        let hook = sh_hook(
+            HookEventName::BeforeToolUse,
+            "cat > /dev/null",
+            DEFAULT_COMMAND_HOOK_TIMEOUT,
+        );
+        let outcome = hook.execute(&before_tool_use_payload()).await;
+        assert!(matches!(outcome.result, HookResult::Success));
+    }
+
+    #[tokio::test]
+    async fn deny_decision_reads_payload_from_stdin() {
+        let script = r#"if grep -q '"call_id":"call-1"'; then echo '{"decision":"deny","reason":"no rm"}'; fi"#;
+        let hook = sh_hook(
+            HookEventName::BeforeToolUse,
+            script,
+            DEFAULT_COMMAND_HOOK_TIMEOUT,
+        );
+        let outcome = hook.execute(&before_tool_use_payload()).await;
+        match outcome.result {
+            HookResult::Deny { reason } => assert_eq!(reason, "no rm"),
+            other => panic!("expected deny, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rewrite_decision_replaces_tool_input() {
+        let script = r#"cat > /dev/null; echo '{"decision":"rewrite","tool_input":{"input_type":"function","arguments":"{\"command\":[\"ls\"]}"}}'"#;
+        let hook = sh_hook(
+            HookEventName::BeforeToolUse,
+            script,
+            DEFAULT_COMMAND_HOOK_TIMEOUT,
+        );
+        let outcome = hook.execute(&before_tool_use_payload()).await;
+        match outcome.result {
+            HookResult::RewriteToolInput(tool_input) => assert_eq!(
+                tool_input,
+                HookToolInput::Function {
+                    arguments: r#"{"command":["ls"]}"#.to_string(),
+                }
+            ),
+            other => panic!("expected rewrite, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn rewrite_decision_is_rejected_for_other_events() {
+        let script =
+            r#"echo '{"decision":"rewrite","tool_input":{"input_type":"custom","input":"x"}}'"#;
+        let hook = sh_hook(
+            HookEventName::SessionEnd,
+            script,
+            DEFAULT_COMMAND_HOOK_TIMEOUT,
+        );
+        let payload = HookPayload {
+            hook_event: HookEvent::SessionEnd {
+                event: HookEventSessionEnd { turn_count: 1 },
+            },
+            ..before_tool_use_payload()
+        };
+        let outcome = hook.execute(&payload).await;
+        assert!(matches!(outcome.result, HookResult::FailedContinue(_)));
+    }
+
+    #[tokio::test]
+    async fn failing_or_slow_blocking_hooks_deny() {
+        let failing = sh_hook(
+            HookEventName::BeforeToolUse,
+            "exit 3",
+            DEFAULT_COMMAND_HOOK_TIMEOUT,
+        );
+        let outcome = failing.execute(&before_tool_use_payload()).await;
+        match outcome.result {
+            HookResult::Deny { reason } => {
+                assert_eq!(
+                    reason,
+                    "hook 'test-hook' failed: hook exited with exit status: 3: "
+                );
+            }
+            other => panic!("expected deny, got {other:?}"),
+        }
+
+        let invalid = sh_hook(
+            HookEventName::BeforeToolUse,
+            "echo not-json",
+            DEFAULT_COMMAND_HOOK_TIMEOUT,
+        );
+        let outcome = invalid.execute(&before_tool_use_payload()).await;
+        assert!(matches!(outcome.result, HookResult::Deny { .. }));
+
+        let slow = sh_hook(
+            HookEventName::BeforeToolUse,
+            "sleep 5",
+            Duration::from_millis(100),
+        );
+        let outcome = slow.execute(&before_tool_use_payload()).await;
+        match outcome.result {
+            HookResult::Deny { reason } => {
+                assert_eq!(
+                    reason,
+                    "hook 'test-hook' failed: hook timed out after 100ms"
+                );
+            }
+            other => panic!("expected timeout deny, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn failing_hooks_continue_when_fail_open() {
+        let slow = command_hook(CommandHookConfig {
+            name: "test-hook".to_string(),
+            event: HookEventName::BeforeToolUse,
+            argv: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "sleep 5".to_string(),
+            ],
+            timeout: Duration::from_millis(100),
+            fail_closed: false,
+        });
+        let outcome = slow.execute(&before_tool_use_payload()).await;
+        match outcome.result {
+            HookResult::FailedContinue(err) => {
+                assert_eq!(err.to_string(), "hook timed out after 100ms");
+            }
+            other => panic!("expected timeout failure, got {other:?}"),
+        }
+    }
+}
@@ -1,55 +1,101 @@
 use tokio::process::Command;
 
+use crate::command_hook::CommandHookConfig;
+use crate::command_hook::command_hook;
 use crate::types::Hook;
 use crate::types::HookEvent;
+use crate::types::HookEventName;
 use crate::types::HookPayload;
 use crate::types::HookResponse;
+use crate::types::HookResult;
 
 #[derive(Default, Clone)]
 pub struct HooksConfig {
     pub legacy_notify_argv: Option<Vec<String>>,
+    pub command_hooks: Vec<CommandHookConfig>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct Hooks {
+    session_start: Vec<Hook>,
+    user_prompt_submit: Vec<Hook>,
+    before_tool_use: Vec<Hook>,
+    before_compact: Vec<Hook>,
+    session_end: Vec<Hook>,
     after_agent: Vec<Hook>,
     after_tool_use: Vec<Hook>,
 }
 
-impl Default for Hooks {
-    fn default() -> Self {
-        Self::new(HooksConfig::default())
-    }
-}
-
 // Hooks are arbitrary, user-specified functions that are deterministically
-// executed after specific events in the Codex lifecycle.
+// executed before or after specific events in the Codex lifecycle.
 impl Hooks {
     pub fn new(config: HooksConfig) -> Self {
-        let after_agent = config
+        let mut hooks = Self::default();
+        if let Some(argv) = config
             .legacy_notify_argv
             .filter(|argv| !argv.is_empty() && !argv[0].is_empty())
-            .map(crate::notify_hook)
-            .into_iter()
-            .collect();
-        Self {
-            after_agent,
-            after_tool_use: Vec::new(),
+        {
+            hooks.after_agent.push(crate::notify_hook(argv));
+        }
+        for command_hook_config in config.command_hooks {
+            let event = command_hook_config.event;
+            hooks
+                .hooks_for_event_name_mut(event)
+                .push(command_hook(command_hook_config));
+        }
+        hooks
+    }
+
+    fn hooks_for_event_name_mut(&mut self, event: HookEventName) -> &mut Vec<Hook> {
+        match event {
+            HookEventName::SessionStart => &mut self.session_start,
+            HookEventName::UserPromptSubmit => &mut self.user_prompt_submit,
+            HookEventName::BeforeToolUse => &mut self.before_tool_use,
+            HookEventName::BeforeCompact => &mut self.before_compact,
+            HookEventName::SessionEnd => &mut self.session_end,
+            HookEventName::AfterAgent => &mut self.after_agent,
+            HookEventName::AfterToolUse => &mut self.after_tool_use,
         }
     }
 
     fn hooks_for_event(&self, hook_event: &HookEvent) -> &[Hook] {
         match hook_event {
+            HookEvent::SessionStart { .. } => &self.session_start,
+            HookEvent::UserPromptSubmit { .. } => &self.user_prompt_submit,
+            HookEvent::BeforeToolUse { .. } => &self.before_tool_use,
+            HookEvent::BeforeCompact { .. } => &self.before_compact,
+            HookEvent::SessionEnd { .. } => &self.session_end,
             HookEvent::AfterAgent { .. } => &self.after_agent,
             HookEvent::AfterToolUse { .. } => &self.after_tool_use,
         }
     }
 
-    pub async fn dispatch(&self, hook_payload: HookPayload) -> Vec<HookResponse> {
+    /// Returns `true` when at least one hook is registered for `event`, so callers can skip
+    /// building payloads nobody will observe.
+    pub fn has_hooks_for(&self, event: HookEventName) -> bool {
+        match event {
+            HookEventName::SessionStart => !self.session_start.is_empty(),
+            HookEventName::UserPromptSubmit => !self.user_prompt_submit.is_empty(),
+            HookEventName::BeforeToolUse => !self.before_tool_use.is_empty(),
+            HookEventName::BeforeCompact => !self.before_compact.is_empty(),
+            HookEventName::SessionEnd => !self.session_end.is_empty(),
+            HookEventName::AfterAgent => !self.after_agent.is_empty(),
+            HookEventName::AfterToolUse => !self.after_tool_use.is_empty(),
+        }
+    }
+
+    pub async fn dispatch(&self, mut hook_payload: HookPayload) -> Vec<HookResponse> {
         let hooks = self.hooks_for_event(&hook_payload.hook_event);
         let mut outcomes = Vec::with_capacity(hooks.len());
         for hook in hooks {
             let outcome = hook.execute(&hook_payload).await;
+            // Later `before_tool_use` hooks see the input as rewritten by earlier ones.
+            if let (HookResult::RewriteToolInput(tool_input), HookEvent::BeforeToolUse { event }) =
+                (&outcome.result, &mut hook_payload.hook_event)
+            {
+                event.tool_kind = tool_input.kind();
+                event.tool_input = tool_input.clone();
+            }
             let should_abort_operation = outcome.result.should_abort_operation();
             outcomes.push(outcome);
             if should_abort_operation {
@@ -93,7 +139,7 @@ mod tests {
     use super::*;
     use crate::types::HookEventAfterAgent;
     use crate::types::HookEventAfterToolUse;
-    use crate::types::HookResult;
+    use crate::types::HookEventBeforeToolUse;
     use crate::types::HookToolInput;
     use crate::types::HookToolKind;
 
@@ -199,6 +245,23 @@ mod tests {
         }
     }
 
+    fn before_tool_use_payload(label: &str) -> HookPayload {
+        HookPayload {
+            hook_event: HookEvent::BeforeToolUse {
+                event: HookEventBeforeToolUse {
+                    turn_id: format!("turn-{label}"),
+                    call_id: format!("call-{label}"),
+                    tool_name: "apply_patch".to_string(),
+                    tool_kind: HookToolKind::Custom,
+                    tool_input: HookToolInput::Custom {
+                        input: "*** Begin Patch".to_string(),
+                    },
+                },
+            },
+            ..hook_payload(label)
+        }
+    }
+
     #[test]
     fn command_from_argv_returns_none_for_empty_args() {
         assert!(command_from_argv(&[]).is_none());
@@ -231,6 +294,7 @@ mod tests {
         assert!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec![]),
+                ..HooksConfig::default()
             })
             .after_agent
             .is_empty()
@@ -238,6 +302,7 @@ mod tests {
         assert!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec!["".to_string()]),
+                ..HooksConfig::default()
             })
             .after_agent
             .is_empty()
@@ -245,6 +310,7 @@ mod tests {
         assert_eq!(
             Hooks::new(HooksConfig {
                 legacy_notify_argv: Some(vec!["notify-send".to_string()]),
+                ..HooksConfig::default()
             })
             .after_agent
             .len(),
@@ -252,6 +318,90 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hooks_new_registers_command_hooks_by_event() {
+        let hooks = Hooks::new(HooksConfig {
+            legacy_notify_argv: None,
+            command_hooks: vec![
+                CommandHookConfig {
+                    name: "guard".to_string(),
+                    event: HookEventName::BeforeToolUse,
+                    argv: vec!["guard".to_string()],
+                    timeout: Duration::from_secs(1),
+                    fail_closed: true,
+                },
+                CommandHookConfig {
+                    name: "greeter".to_string(),
+                    event: HookEventName::SessionStart,
+                    argv: vec!["greeter".to_string()],
+                    timeout: Duration::from_secs(1),
+                    fail_closed: true,
+                },
+            ],
+        });
+        assert_eq!(hooks.before_tool_use.len(), 1);
+        assert_eq!(hooks.before_tool_use[0].name, "guard");
+        assert_eq!(hooks.session_start.len(), 1);
+        assert!(hooks.has_hooks_for(HookEventName::BeforeToolUse));
+        assert!(!hooks.has_hooks_for(HookEventName::SessionEnd));
+    }
+
+    #[tokio::test]
+    async fn dispatch_passes_rewritten_tool_input_to_later_hooks() {
+        let rewrite = Hook {
+            name: "rewrite".to_string(),
+            func: Arc::new(|_| {
+                Box::pin(async {
+                    HookResult::RewriteToolInput(HookToolInput::Custom {
+                        input: "rewritten".to_string(),
+                    })
+                })
+            }),
+        };
+        let deny_unless_rewritten = Hook {
+            name: "deny".to_string(),
+            func: Arc::new(|payload: &HookPayload| {
+                let rewritten = matches!(
+                    &payload.hook_event,
+                    HookEvent::BeforeToolUse { event }
+                        if event.tool_input == HookToolInput::Custom {
+                            input: "rewritten".to_string(),
+                        }
+                );
+                Box::pin(async move {
+                    if rewritten {
+                        HookResult::Deny {
+                            reason: "saw rewritten input".to_string(),
+                        }
+                    } else {
+                        HookResult::Success
+                    }
+                })
+            }),
+        };
+        let calls = Arc::new(AtomicUsize::new(0));
+        let hooks = Hooks {
+            before_tool_use: vec![
+                rewrite,
+                deny_unless_rewritten,
+                counting_success_hook(&calls, "counting"),
+            ],
+            ..Hooks::default()
+        };
+
+        let outcomes = hooks.dispatch(before_tool_use_payload("rw")).await;
+        assert_eq!(outcomes.len(), 2);
+        assert!(matches!(
+            outcomes[0].result,
+            HookResult::RewriteToolInput(_)
+        ));
+        assert!(matches!(
+            &outcomes[1].result,
+            HookResult::Deny { reason } if reason == "saw rewritten input"
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
     #[tokio::test]
     async fn dispatch_executes_hook() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -7,6 +7,7 @@ use chrono::Utc;
 use codex_protocol::ThreadId;
 use codex_protocol::models::SandboxPermissions;
 use futures::future::BoxFuture;
+use serde::Deserialize;
 use serde::Serialize;
 use serde::Serializer;
 
@@ -16,6 +17,12 @@ pub type HookFn = Arc<dyn for<'a> Fn(&'a HookPayload) -> BoxFuture<'a, HookResul
 pub enum HookResult {
     /// Success: hook completed successfully.
     Success,
+    /// Deny: hook blocked the operation. Subsequent hooks do not execute and `reason` is
+    /// reported back to whoever requested the operation (the model for tool calls).
+    Deny { reason: String },
+    /// RewriteToolInput: hook allowed a `before_tool_use` operation but replaced its input.
+    /// Subsequent hooks observe the rewritten input.
+    RewriteToolInput(HookToolInput),
     /// FailedContinue: hook failed, but other subsequent hooks should still execute and the
     /// operation should continue.
     FailedContinue(Box<dyn std::error::Error + Send + Sync + 'static>),
@@ -26,7 +33,7 @@ pub enum HookResult {
 
 impl HookResult {
     pub fn should_abort_operation(&self) -> bool {
-        matches!(self, Self::FailedAbort(_))
+        matches!(self, Self::Deny { .. } | Self::FailedAbort(_))
     }
 }
 
@@ -90,7 +97,7 @@ pub enum HookToolKind {
     Mcp,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct HookToolInputLocalShell {
     pub command: Vec<String>,
@@ -101,7 +108,7 @@ pub struct HookToolInputLocalShell {
     pub justification: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "input_type", rename_all = "snake_case")]
 pub enum HookToolInput {
     Function {
@@ -120,6 +127,27 @@ pub enum HookToolInput {
     },
 }
 
+impl HookToolInput {
+    pub fn kind(&self) -> HookToolKind {
+        match self {
+            HookToolInput::Function { .. } => HookToolKind::Function,
+            HookToolInput::Custom { .. } => HookToolKind::Custom,
+            HookToolInput::LocalShell { .. } => HookToolKind::LocalShell,
+            HookToolInput::Mcp { .. } => HookToolKind::Mcp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventBeforeToolUse {
+    pub turn_id: String,
+    pub call_id: String,
+    pub tool_name: String,
+    pub tool_kind: HookToolKind,
+    pub tool_input: HookToolInput,
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub struct HookEventAfterToolUse {
@@ -137,6 +165,48 @@ pub struct HookEventAfterToolUse {
     pub output_preview: String,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookSessionStartSource {
+    Startup,
+    Resume,
+    Fork,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventSessionStart {
+    pub source: HookSessionStartSource,
+    pub model: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventUserPromptSubmit {
+    pub turn_id: String,
+    pub input_messages: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookCompactTrigger {
+    Manual,
+    Auto,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventBeforeCompact {
+    pub turn_id: String,
+    pub trigger: HookCompactTrigger,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct HookEventSessionEnd {
+    pub turn_count: u64,
+}
+
 fn serialize_triggered_at<S>(value: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -147,6 +217,26 @@ where
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "event_type", rename_all = "snake_case")]
 pub enum HookEvent {
+    SessionStart {
+        #[serde(flatten)]
+        event: HookEventSessionStart,
+    },
+    UserPromptSubmit {
+        #[serde(flatten)]
+        event: HookEventUserPromptSubmit,
+    },
+    BeforeToolUse {
+        #[serde(flatten)]
+        event: HookEventBeforeToolUse,
+    },
+    BeforeCompact {
+        #[serde(flatten)]
+        event: HookEventBeforeCompact,
+    },
+    SessionEnd {
+        #[serde(flatten)]
+        event: HookEventSessionEnd,
+    },
     AfterAgent {
         #[serde(flatten)]
         event: HookEventAfterAgent,
@@ -157,6 +247,48 @@ pub enum HookEvent {
     },
 }
 
+impl HookEvent {
+    pub fn name(&self) -> HookEventName {
+        match self {
+            HookEvent::SessionStart { .. } => HookEventName::SessionStart,
+            HookEvent::UserPromptSubmit { .. } => HookEventName::UserPromptSubmit,
+            HookEvent::BeforeToolUse { .. } => HookEventName::BeforeToolUse,
+            HookEvent::BeforeCompact { .. } => HookEventName::BeforeCompact,
+            HookEvent::SessionEnd { .. } => HookEventName::SessionEnd,
+            HookEvent::AfterAgent { .. } => HookEventName::AfterAgent,
+            HookEvent::AfterToolUse { .. } => HookEventName::AfterToolUse,
+        }
+    }
+}
+
+/// Identifies a [`HookEvent`] variant without its payload, e.g. when registering hooks.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEventName {
+    SessionStart,
+    UserPromptSubmit,
+    BeforeToolUse,
+    BeforeCompact,
+    SessionEnd,
+    AfterAgent,
+    AfterToolUse,
+}
+
+impl std::fmt::Display for HookEventName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            HookEventName::SessionStart => "session_start",
+            HookEventName::UserPromptSubmit => "user_prompt_submit",
+            HookEventName::BeforeToolUse => "before_tool_use",
+            HookEventName::BeforeCompact => "before_compact",
+            HookEventName::SessionEnd => "session_end",
+            HookEventName::AfterAgent => "after_agent",
+            HookEventName::AfterToolUse => "after_tool_use",
+        };
+        f.write_str(name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
@@ -171,7 +303,9 @@ mod tests {
     use super::HookEvent;
     use super::HookEventAfterAgent;
     use super::HookEventAfterToolUse;
+    use super::HookEventSessionStart;
     use super::HookPayload;
+    use super::HookSessionStartSource;
     use super::HookToolInput;
     use super::HookToolInputLocalShell;
     use super::HookToolKind;
@@ -287,4 +421,52 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn session_start_payload_serializes_stable_wire_shape() {
+        let session_id = ThreadId::new();
+        let payload = HookPayload {
+            session_id,
+            cwd: PathBuf::from("tmp"),
+            client: Some("codex-tui".to_string()),
+            triggered_at: Utc
+                .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+                .single()
+                .expect("valid timestamp"),
+            hook_event: HookEvent::SessionStart {
+                event: HookEventSessionStart {
+                    source: HookSessionStartSource::Resume,
+                    model: "gpt-5".to_string(),
+                },
+            },
+        };
+
+        let actual = serde_json::to_value(payload).expect("serialize hook payload");
+        let expected = json!({
+            "session_id": session_id.to_string(),
+            "cwd": "tmp",
+            "client": "codex-tui",
+            "triggered_at": "2025-01-01T00:00:00Z",
+            "hook_event": {
+                "event_type": "session_start",
+                "source": "resume",
+                "model": "gpt-5",
+            },
+        });
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn tool_input_round_trips_through_hook_json() {
+        let tool_input = HookToolInput::Mcp {
+            server: "docs".to_string(),
+            tool: "search".to_string(),
+            arguments: "{}".to_string(),
+        };
+        let json = serde_json::to_value(&tool_input).expect("serialize tool input");
+        let parsed: HookToolInput = serde_json::from_value(json).expect("parse tool input");
+        assert_eq!(parsed, tool_input);
+        assert_eq!(parsed.kind(), HookToolKind::Mcp);
+    }
 }
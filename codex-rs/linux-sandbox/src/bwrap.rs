@@ -2,9 +2,10 @@
 //!
 //! This module mirrors the semantics used by the macOS Seatbelt sandbox:
 //! - the filesystem is read-only by default,
-//! - explicit writable roots are layered on top, and
+//! - explicit writable roots are layered on top,
 //! - sensitive subpaths such as `.git` and `.codex` remain read-only even when
-//!   their parent root is writable.
+//!   their parent root is writable, and
+//! - paths matched by `deny_read` are masked so they cannot be read at all.
 //!
 //! The overall Linux sandbox is composed of:
 //! - seccomp + `PR_SET_NO_NEW_PRIVS` applied in-process, and
@@ -77,7 +78,8 @@ impl BwrapNetworkMode {
 }
 
 /// Wrap a command with bubblewrap so the filesystem is read-only by default,
-/// with explicit writable roots and read-only subpaths layered afterward.
+/// with explicit writable roots and read-only subpaths layered afterward, and
+/// `denied_paths` masked last.
 ///
 /// When the policy grants full disk write access and full network access, this
 /// returns `command` unchanged so we avoid unnecessary sandboxing overhead.
@@ -87,6 +89,7 @@ pub(crate) fn create_bwrap_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
+    denied_paths: &[PathBuf],
     options: BwrapOptions,
 ) -> Result<Vec<String>> {
     if sandbox_policy.has_full_disk_write_access() {
@@ -97,7 +100,7 @@ pub(crate) fn create_bwrap_command_args(
         };
     }
 
-    create_bwrap_flags(command, sandbox_policy, cwd, options)
+    create_bwrap_flags(command, sandbox_policy, cwd, denied_paths, options)
 }
 
 fn create_bwrap_flags_full_filesystem(command: Vec<String>, options: BwrapOptions) -> Vec<String> {
@@ -129,12 +132,13 @@ fn create_bwrap_flags(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
+    denied_paths: &[PathBuf],
     options: BwrapOptions,
 ) -> Result<Vec<String>> {
     let mut args = Vec::new();
     args.push("--new-session".to_string());
     args.push("--die-with-parent".to_string());
-    args.extend(create_filesystem_args(sandbox_policy, cwd)?);
+    args.extend(create_filesystem_args(sandbox_policy, cwd, denied_paths)?);
     // Request a user namespace explicitly rather than relying on bubblewrap's
     // auto-enable behavior, which is skipped when the caller runs as uid 0.
     args.push("--unshare-user".to_string());
@@ -164,7 +168,13 @@ fn create_bwrap_flags(
 ///    writable subpaths under `/dev` (for example, `/dev/shm`).
 /// 4. `--ro-bind <subpath> <subpath>` re-applies read-only protections under
 ///    those writable roots so protected subpaths win.
-fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<Vec<String>> {
+/// 5. Denied paths are masked last: directories with an empty read-only
+///    `--tmpfs`, everything else with `--ro-bind /dev/null`.
+fn create_filesystem_args(
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+    denied_paths: &[PathBuf],
+) -> Result<Vec<String>> {
     let writable_roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
     ensure_mount_targets_exist(&writable_roots)?;
 
@@ -271,9 +281,43 @@ fn create_filesystem_args(sandbox_policy: &SandboxPolicy, cwd: &Path) -> Result<
         }
     }
 
+    for denied_path in denied_paths {
+        if !is_visible_in_sandbox(denied_path, sandbox_policy, cwd) {
+            continue;
+        }
+        let denied_path = path_to_string(denied_path);
+        if Path::new(&denied_path).is_dir() {
+            args.push("--tmpfs".to_string());
+            args.push(denied_path.clone());
+            args.push("--remount-ro".to_string());
+            args.push(denied_path);
+        } else {
+            args.push("--ro-bind".to_string());
+            args.push("/dev/null".to_string());
+            args.push(denied_path);
+        }
+    }
+
     Ok(args)
 }
 
+/// Returns true when `path` is mounted into the sandbox at all. Paths outside
+/// every readable root are already absent and need no mask.
+fn is_visible_in_sandbox(path: &Path, sandbox_policy: &SandboxPolicy, cwd: &Path) -> bool {
+    if sandbox_policy.has_full_disk_read_access() {
+        return true;
+    }
+    let under_platform_default = sandbox_policy.include_platform_defaults()
+        && LINUX_PLATFORM_DEFAULT_READ_ROOTS
+            .iter()
+            .any(|root| path.starts_with(root));
+    under_platform_default
+        || sandbox_policy
+            .get_readable_roots_with_cwd(cwd)
+            .iter()
+            .any(|root| path.starts_with(root.as_path()))
+}
+
 /// Collect unique read-only subpaths across all writable roots.
 fn collect_read_only_subpaths(writable_roots: &[WritableRoot]) -> Vec<PathBuf> {
     let mut subpaths: BTreeSet<PathBuf> = BTreeSet::new();
@@ -399,6 +443,7 @@ mod tests {
             command.clone(),
             &SandboxPolicy::DangerFullAccess,
             Path::new("/"),
+            &[],
             BwrapOptions {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::FullAccess,
@@ -416,6 +461,7 @@ mod tests {
             command,
             &SandboxPolicy::DangerFullAccess,
             Path::new("/"),
+            &[],
             BwrapOptions {
                 mount_proc: true,
                 network_mode: BwrapNetworkMode::ProxyOnly,
@@ -452,7 +498,8 @@ mod tests {
             exclude_slash_tmp: true,
         };
 
-        let args = create_filesystem_args(&sandbox_policy, Path::new("/")).expect("bwrap fs args");
+        let args =
+            create_filesystem_args(&sandbox_policy, Path::new("/"), &[]).expect("bwrap fs args");
         assert_eq!(
             args,
             vec![
@@ -488,7 +535,7 @@ mod tests {
             network_access: false,
         };
 
-        let args = create_filesystem_args(&policy, temp_dir.path()).expect("filesystem args");
+        let args = create_filesystem_args(&policy, temp_dir.path(), &[]).expect("filesystem args");
 
         assert_eq!(args[0..4], ["--tmpfs", "/", "--dev", "/dev"]);
 
@@ -517,7 +564,7 @@ mod tests {
         // `ReadOnlyAccess::Restricted` always includes `cwd` as a readable
         // root. Using `"/"` here would intentionally collapse to broad read
         // access, so use a non-root cwd to exercise the restricted path.
-        let args = create_filesystem_args(&policy, temp_dir.path()).expect("filesystem args");
+        let args = create_filesystem_args(&policy, temp_dir.path(), &[]).expect("filesystem args");
 
         assert!(args.starts_with(&["--tmpfs".to_string(), "/".to_string()]));
 
@@ -528,4 +575,68 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn denied_paths_are_masked_after_writable_binds() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let workspace = temp_dir.path().join("workspace");
+        let env_file = workspace.join(".env");
+        let keys_dir = workspace.join("keys");
+        std::fs::create_dir_all(&keys_dir).expect("create keys dir");
+        std::fs::write(&env_file, "TOKEN=1").expect("write .env");
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            read_only_access: Default::default(),
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+
+        let args =
+            create_filesystem_args(&policy, &workspace, &[env_file.clone(), keys_dir.clone()])
+                .expect("filesystem args");
+
+        let workspace_str = path_to_string(&workspace);
+        let env_file_str = path_to_string(&env_file);
+        let keys_dir_str = path_to_string(&keys_dir);
+        let bind_index = args
+            .windows(3)
+            .position(|window| window == ["--bind", workspace_str.as_str(), workspace_str.as_str()])
+            .expect("workspace bind");
+        let mask_index = args
+            .windows(3)
+            .position(|window| window == ["--ro-bind", "/dev/null", env_file_str.as_str()])
+            .expect("file mask");
+        assert!(bind_index < mask_index);
+        assert_eq!(
+            args[args.len() - 4..],
+            [
+                "--tmpfs",
+                keys_dir_str.as_str(),
+                "--remount-ro",
+                keys_dir_str.as_str(),
+            ]
+        );
+    }
+
+    #[test]
+    fn denied_paths_outside_restricted_read_roots_are_not_mounted() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let readable_root = temp_dir.path().join("readable");
+        let outside = temp_dir.path().join("outside.txt");
+        std::fs::create_dir(&readable_root).expect("create readable root");
+        std::fs::write(&outside, "secret").expect("write outside file");
+        let policy = SandboxPolicy::ReadOnly {
+            access: ReadOnlyAccess::Restricted {
+                include_platform_defaults: false,
+                readable_roots: Vec::new(),
+            },
+            network_access: false,
+        };
+
+        let args =
+            create_filesystem_args(&policy, &readable_root, &[outside]).expect("filesystem args");
+
+        assert!(!args.iter().any(|arg| arg == "/dev/null"));
+    }
 }
@@ -29,6 +29,11 @@ pub struct LandlockCommand {
     #[arg(long = "sandbox-policy")]
     pub sandbox_policy: codex_protocol::protocol::SandboxPolicy,
 
+    /// Absolute path that must not be readable inside the sandbox. The caller
+    /// expands `deny_read` patterns into these paths. May be repeated.
+    #[arg(long = "deny-read-path", value_name = "PATH")]
+    pub deny_read_paths: Vec<PathBuf>,
+
     /// Opt-in: use the bubblewrap-based Linux sandbox pipeline.
     ///
     /// When not set, we fall back to the legacy Landlock + mount pipeline.
@@ -77,6 +82,7 @@ pub fn run_main() -> ! {
     let LandlockCommand {
         sandbox_policy_cwd,
         sandbox_policy,
+        deny_read_paths,
         use_bwrap_sandbox,
         apply_seccomp_then_exec,
         allow_network_for_proxy,
@@ -88,6 +94,9 @@ pub fn run_main() -> ! {
     if command.is_empty() {
         panic!("No command specified to execute.");
     }
+    // Landlock rules can only grant access, so they cannot hide a path inside
+    // a readable root. Masking denied paths requires the bubblewrap pipeline.
+    let use_bwrap_sandbox = use_bwrap_sandbox || !deny_read_paths.is_empty();
     ensure_inner_stage_mode_is_valid(apply_seccomp_then_exec, use_bwrap_sandbox);
 
     // Inner stage: apply seccomp/no_new_privs after bubblewrap has already
@@ -150,6 +159,7 @@ pub fn run_main() -> ! {
         run_bwrap_with_proc_fallback(
             &sandbox_policy_cwd,
             &sandbox_policy,
+            &deny_read_paths,
             inner,
             !no_proc,
             allow_network_for_proxy,
@@ -178,6 +188,7 @@ fn ensure_inner_stage_mode_is_valid(apply_seccomp_then_exec: bool, use_bwrap_san
 fn run_bwrap_with_proc_fallback(
     sandbox_policy_cwd: &Path,
     sandbox_policy: &codex_protocol::protocol::SandboxPolicy,
+    denied_paths: &[PathBuf],
     inner: Vec<String>,
     mount_proc: bool,
     allow_network_for_proxy: bool,
@@ -195,7 +206,13 @@ fn run_bwrap_with_proc_fallback(
         mount_proc,
         network_mode,
     };
-    let argv = build_bwrap_argv(inner, sandbox_policy, sandbox_policy_cwd, options);
+    let argv = build_bwrap_argv(
+        inner,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        denied_paths,
+        options,
+    );
     exec_vendored_bwrap(argv);
 }
 
@@ -216,10 +233,17 @@ fn build_bwrap_argv(
     inner: Vec<String>,
     sandbox_policy: &codex_protocol::protocol::SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    denied_paths: &[PathBuf],
     options: BwrapOptions,
 ) -> Vec<String> {
-    let mut args = create_bwrap_command_args(inner, sandbox_policy, sandbox_policy_cwd, options)
-        .unwrap_or_else(|err| panic!("error building bubblewrap command: {err:?}"));
+    let mut args = create_bwrap_command_args(
+        inner,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        denied_paths,
+        options,
+    )
+    .unwrap_or_else(|err| panic!("error building bubblewrap command: {err:?}"));
 
     let command_separator_index = args
         .iter()
@@ -256,6 +280,7 @@ fn build_preflight_bwrap_argv(
         preflight_command,
         sandbox_policy,
         sandbox_policy_cwd,
+        &[],
         BwrapOptions {
             mount_proc: true,
             network_mode,
@@ -33,6 +33,7 @@ fn inserts_bwrap_argv0_before_command_separator() {
         vec!["/bin/true".to_string()],
         &SandboxPolicy::new_read_only_policy(),
         Path::new("/"),
+        &[],
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::FullAccess,
@@ -67,6 +68,7 @@ fn inserts_unshare_net_when_network_isolation_requested() {
         vec!["/bin/true".to_string()],
         &SandboxPolicy::new_read_only_policy(),
         Path::new("/"),
+        &[],
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::Isolated,
@@ -81,6 +83,7 @@ fn inserts_unshare_net_when_proxy_only_network_mode_requested() {
         vec!["/bin/true".to_string()],
         &SandboxPolicy::new_read_only_policy(),
         Path::new("/"),
+        &[],
         BwrapOptions {
             mount_proc: true,
             network_mode: BwrapNetworkMode::ProxyOnly,
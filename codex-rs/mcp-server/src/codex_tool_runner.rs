@@ -332,6 +332,9 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::McpToolCallEnd(_)
                     | EventMsg::McpListToolsResponse(_)
                     | EventMsg::ListCustomPromptsResponse(_)
+                    | EventMsg::ListMcpPromptsResponse(_)
+                    | EventMsg::McpPromptListChanged(_)
+                    | EventMsg::GetMcpPromptResponse(_)
                     | EventMsg::PromptTraceResponse(_)
                     | EventMsg::ListSkillsResponse(_)
                     | EventMsg::ListRemoteSkillsResponse(_)
@@ -11,6 +11,8 @@ use crate::protocol::FileChange;
 use crate::protocol::ReviewDecision;
 use crate::protocol::SandboxPolicy;
 use schemars::JsonSchema;
+use schemars::r#gen::SchemaGenerator;
+use schemars::schema::Schema;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
@@ -35,17 +37,30 @@ pub enum EscalationPermissions {
 ///
 /// The `command` tokens form the prefix that would be added as an execpolicy
 /// `prefix_rule(..., decision="allow")`, letting the agent bypass approval for
-/// commands that start with this token sequence.
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
-#[serde(transparent)]
-#[ts(type = "Array<string>")]
+/// commands that start with this token sequence. `args` and `not_matching` hold
+/// argument clauses in policy syntax, such as `flag("--force", "-f")`, that are
+/// persisted with the rule. Without clauses the amendment is serialized as the
+/// bare `command` array.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, TS)]
+#[serde(from = "ExecPolicyAmendmentRepr", into = "ExecPolicyAmendmentRepr")]
+#[ts(
+    type = "Array<string> | { command: Array<string>, args?: Array<string>, not_matching?: Array<string>, }"
+)]
 pub struct ExecPolicyAmendment {
     pub command: Vec<String>,
+    /// Clauses that must each match an argument after the prefix.
+    pub args: Vec<String>,
+    /// Clauses that exclude a command from the rule when they match any argument after the prefix.
+    pub not_matching: Vec<String>,
 }
 
 impl ExecPolicyAmendment {
     pub fn new(command: Vec<String>) -> Self {
-        Self { command }
+        Self {
+            command,
+            args: Vec::new(),
+            not_matching: Vec::new(),
+        }
     }
 
     pub fn command(&self) -> &[String] {
@@ -55,7 +70,70 @@ impl ExecPolicyAmendment {
 
 impl From<Vec<String>> for ExecPolicyAmendment {
     fn from(command: Vec<String>) -> Self {
-        Self { command }
+        Self::new(command)
+    }
+}
+
+#[derive(Deserialize, Serialize, JsonSchema)]
+#[serde(untagged)]
+enum ExecPolicyAmendmentRepr {
+    Prefix(Vec<String>),
+    WithClauses {
+        command: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        not_matching: Vec<String>,
+    },
+}
+
+impl From<ExecPolicyAmendmentRepr> for ExecPolicyAmendment {
+    fn from(repr: ExecPolicyAmendmentRepr) -> Self {
+        match repr {
+            ExecPolicyAmendmentRepr::Prefix(command) => Self::new(command),
+            ExecPolicyAmendmentRepr::WithClauses {
+                command,
+                args,
+                not_matching,
+            } => Self {
+                command,
+                args,
+                not_matching,
+            },
+        }
+    }
+}
+
+impl From<ExecPolicyAmendment> for ExecPolicyAmendmentRepr {
+    fn from(amendment: ExecPolicyAmendment) -> Self {
+        let ExecPolicyAmendment {
+            command,
+            args,
+            not_matching,
+        } = amendment;
+        if args.is_empty() && not_matching.is_empty() {
+            Self::Prefix(command)
+        } else {
+            Self::WithClauses {
+                command,
+                args,
+                not_matching,
+            }
+        }
+    }
+}
+
+impl JsonSchema for ExecPolicyAmendment {
+    fn schema_name() -> String {
+        "ExecPolicyAmendment".to_string()
+    }
+
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        ExecPolicyAmendmentRepr::json_schema(generator)
     }
 }
 
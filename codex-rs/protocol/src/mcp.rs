@@ -102,6 +102,36 @@ pub struct ResourceTemplate {
     pub mime_type: Option<String>,
 }
 
+/// A prompt or prompt template that the server offers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct Prompt {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<PromptArgument>,
+}
+
+/// Describes an argument that a prompt can accept.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptArgument {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
 /// The server's response to a tool call.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
@@ -272,6 +302,54 @@ impl From<ResourceTemplateSerde> for ResourceTemplate {
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct PromptSerde {
+    name: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    arguments: Option<Vec<PromptArgumentSerde>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptArgumentSerde {
+    name: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    required: Option<bool>,
+}
+
+impl From<PromptSerde> for Prompt {
+    fn from(value: PromptSerde) -> Self {
+        let PromptSerde {
+            name,
+            title,
+            description,
+            arguments,
+        } = value;
+        Self {
+            name,
+            title,
+            description,
+            arguments: arguments
+                .unwrap_or_default()
+                .into_iter()
+                .map(|argument| PromptArgument {
+                    name: argument.name,
+                    title: argument.title,
+                    description: argument.description,
+                    required: argument.required.unwrap_or(false),
+                })
+                .collect(),
+        }
+    }
+}
+
 impl Tool {
     pub fn from_mcp_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
         Ok(serde_json::from_value::<ToolSerde>(value)?.into())
@@ -290,6 +368,12 @@ impl ResourceTemplate {
     }
 }
 
+impl Prompt {
+    pub fn from_mcp_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        Ok(serde_json::from_value::<PromptSerde>(value)?.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -325,4 +409,45 @@ mod tests {
         let parsed = Resource::from_mcp_value(resource).expect("should deserialize");
         assert_eq!(parsed.size, None);
     }
+
+    #[test]
+    fn prompt_arguments_default_to_optional() {
+        let prompt = serde_json::json!({
+            "name": "review",
+            "description": "Review a file.",
+            "arguments": [
+                { "name": "path", "required": true },
+                { "name": "focus" },
+            ],
+            "_meta": { "ignored": true },
+        });
+
+        let parsed = Prompt::from_mcp_value(prompt).expect("should deserialize");
+        assert_eq!(
+            parsed,
+            Prompt {
+                name: "review".to_string(),
+                title: None,
+                description: Some("Review a file.".to_string()),
+                arguments: vec![
+                    PromptArgument {
+                        name: "path".to_string(),
+                        title: None,
+                        description: None,
+                        required: true,
+                    },
+                    PromptArgument {
+                        name: "focus".to_string(),
+                        title: None,
+                        description: None,
+                        required: false,
+                    },
+                ],
+            }
+        );
+
+        let parsed = Prompt::from_mcp_value(serde_json::json!({ "name": "bare" }))
+            .expect("should deserialize");
+        assert_eq!(parsed.arguments, Vec::new());
+    }
 }
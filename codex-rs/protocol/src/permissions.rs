@@ -1,3 +1,4 @@
+use std::collections::BTreeSet;
 use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::io;
@@ -5,6 +6,9 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use codex_utils_absolute_path::AbsolutePathBuf;
+use globset::GlobBuilder;
+use globset::GlobSet;
+use globset::GlobSetBuilder;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -98,6 +102,15 @@ pub struct FileSystemSandboxPolicy {
     pub kind: FileSystemSandboxKind,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub entries: Vec<FileSystemSandboxEntry>,
+    /// Glob patterns for paths that must stay unreadable even when they live
+    /// under a readable root. Relative patterns are resolved against the cwd.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny_read: Vec<String>,
+    /// Maximum number of directory entries visited while expanding
+    /// `deny_read` globs. Defaults to [`DEFAULT_DENY_READ_SCAN_LIMIT`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub deny_read_scan_limit: Option<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
@@ -118,6 +131,8 @@ impl Default for FileSystemSandboxPolicy {
                 },
                 access: FileSystemAccessMode::Read,
             }],
+            deny_read: Vec::new(),
+            deny_read_scan_limit: None,
         }
     }
 }
@@ -127,6 +142,8 @@ impl FileSystemSandboxPolicy {
         Self {
             kind: FileSystemSandboxKind::Unrestricted,
             entries: Vec::new(),
+            deny_read: Vec::new(),
+            deny_read_scan_limit: None,
         }
     }
 
@@ -134,6 +151,8 @@ impl FileSystemSandboxPolicy {
         Self {
             kind: FileSystemSandboxKind::ExternalSandbox,
             entries: Vec::new(),
+            deny_read: Vec::new(),
+            deny_read_scan_limit: None,
         }
     }
 
@@ -141,9 +160,34 @@ impl FileSystemSandboxPolicy {
         Self {
             kind: FileSystemSandboxKind::Restricted,
             entries,
+            deny_read: Vec::new(),
+            deny_read_scan_limit: None,
         }
     }
 
+    /// Returns this policy with `deny_read` replaced by `patterns`.
+    pub fn with_deny_read(mut self, patterns: Vec<String>) -> Self {
+        self.deny_read = patterns;
+        self
+    }
+
+    /// Returns this policy with `deny_read_scan_limit` replaced by `limit`.
+    pub fn with_deny_read_scan_limit(mut self, limit: Option<usize>) -> Self {
+        self.deny_read_scan_limit = limit;
+        self
+    }
+
+    /// The number of entries a `deny_read` expansion may visit.
+    pub fn effective_deny_read_scan_limit(&self) -> usize {
+        self.deny_read_scan_limit
+            .unwrap_or(DEFAULT_DENY_READ_SCAN_LIMIT)
+    }
+
+    /// Compiles the `deny_read` patterns against the provided cwd.
+    pub fn read_deny_list(&self, cwd: &Path) -> io::Result<ReadDenyList> {
+        ReadDenyList::new(&self.deny_read, cwd)
+    }
+
     /// Returns true when filesystem reads are unrestricted.
     pub fn has_full_disk_read_access(&self) -> bool {
         match self.kind {
@@ -533,6 +577,138 @@ impl From<&SandboxPolicy> for FileSystemSandboxPolicy {
     }
 }
 
+/// Default upper bound on directory entries visited while expanding
+/// `deny_read` patterns into concrete paths. Exceeding it is an error rather
+/// than a partial result, so a sandbox never starts with denied files left
+/// unmasked.
+pub const DEFAULT_DENY_READ_SCAN_LIMIT: usize = 100_000;
+
+/// Compiled `deny_read` patterns.
+///
+/// Each pattern is resolved against a base directory (`~` expands to the home
+/// directory) and matched with `*` confined to a single path component. A
+/// path is denied when it, or any of its ancestors, matches a pattern, so
+/// denying `~/.ssh` also denies everything below it.
+#[derive(Debug, Clone, Default)]
+pub struct ReadDenyList {
+    patterns: Vec<PathBuf>,
+    globs: GlobSet,
+}
+
+impl ReadDenyList {
+    pub fn new(patterns: &[String], cwd: &Path) -> io::Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        let mut resolved = Vec::with_capacity(patterns.len());
+        for pattern in patterns {
+            let path = AbsolutePathBuf::resolve_path_against_base(pattern, cwd)?.into_path_buf();
+            let glob = GlobBuilder::new(&path.to_string_lossy())
+                .literal_separator(true)
+                .build()
+                .map_err(|err| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("invalid deny_read pattern `{pattern}`: {err}"),
+                    )
+                })?;
+            builder.add(glob);
+            resolved.push(path);
+        }
+        let globs = builder.build().map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid deny_read patterns: {err}"),
+            )
+        })?;
+        Ok(Self {
+            patterns: resolved,
+            globs,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// The patterns after resolution against the base directory.
+    pub fn patterns(&self) -> &[PathBuf] {
+        &self.patterns
+    }
+
+    /// Returns true when `path` or one of its ancestors matches a pattern.
+    pub fn is_denied(&self, path: &Path) -> bool {
+        !self.is_empty()
+            && path
+                .ancestors()
+                .any(|ancestor| self.globs.is_match(ancestor))
+    }
+
+    /// Expands the patterns into the paths that currently exist on disk.
+    ///
+    /// Literal patterns are checked directly. Glob patterns are expanded by
+    /// walking the directory named by their literal prefix without following
+    /// symlinks; matched directories are not descended into.
+    ///
+    /// The result is a snapshot: paths created after the expansion are not
+    /// included. Fails when the walk visits more than `max_entries` entries,
+    /// since the expansion would be incomplete.
+    pub fn find_denied_paths(&self, max_entries: usize) -> io::Result<Vec<PathBuf>> {
+        let mut denied = BTreeSet::new();
+        let mut remaining_entries = max_entries;
+        for pattern in &self.patterns {
+            let prefix = literal_prefix(pattern);
+            if prefix == *pattern {
+                if std::fs::symlink_metadata(pattern).is_ok() {
+                    denied.insert(pattern.clone());
+                }
+                continue;
+            }
+            if self.globs.is_match(&prefix) {
+                denied.insert(prefix);
+                continue;
+            }
+
+            let mut stack = vec![prefix];
+            while let Some(dir) = stack.pop() {
+                let Ok(read_dir) = std::fs::read_dir(&dir) else {
+                    continue;
+                };
+                for entry in read_dir.flatten() {
+                    if remaining_entries == 0 {
+                        return Err(io::Error::other(format!(
+                            "deny_read pattern `{}` matches too many paths to expand: stopped after {max_entries} entries; use a more specific pattern or raise `deny_read_scan_limit`",
+                            pattern.display()
+                        )));
+                    }
+                    remaining_entries -= 1;
+                    let path = entry.path();
+                    if self.globs.is_match(&path) {
+                        denied.insert(path);
+                    } else if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                        stack.push(path);
+                    }
+                }
+            }
+        }
+        Ok(denied.into_iter().collect())
+    }
+}
+
+/// Returns the leading components of `pattern` that contain no glob syntax.
+fn literal_prefix(pattern: &Path) -> PathBuf {
+    let mut prefix = PathBuf::new();
+    for component in pattern.components() {
+        if component
+            .as_os_str()
+            .to_string_lossy()
+            .contains(['*', '?', '[', '{'])
+        {
+            break;
+        }
+        prefix.push(component);
+    }
+    prefix
+}
+
 fn resolve_file_system_path(
     path: &FileSystemPath,
     cwd: Option<&AbsolutePathBuf>,
@@ -693,3 +869,110 @@ fn resolve_gitdir_from_file(dot_git: &AbsolutePathBuf) -> Option<AbsolutePathBuf
     }
     Some(gitdir_path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn read_deny_list_matches_relative_globs_and_descendants() {
+        let cwd = Path::new("/repo");
+        let deny_list =
+            ReadDenyList::new(&["**/.env".to_string(), "/home/user/.ssh".to_string()], cwd)
+                .expect("compile deny list");
+
+        assert!(deny_list.is_denied(Path::new("/repo/.env")));
+        assert!(deny_list.is_denied(Path::new("/repo/service/.env")));
+        assert!(deny_list.is_denied(Path::new("/home/user/.ssh/id_ed25519")));
+        assert!(!deny_list.is_denied(Path::new("/repo/.env.example")));
+        assert!(!deny_list.is_denied(Path::new("/other/.env")));
+        assert!(!deny_list.is_denied(Path::new("/home/user/.sshd")));
+    }
+
+    #[test]
+    fn read_deny_list_star_does_not_cross_directories() {
+        let deny_list = ReadDenyList::new(&["secrets/*.pem".to_string()], Path::new("/repo"))
+            .expect("compile deny list");
+
+        assert!(deny_list.is_denied(Path::new("/repo/secrets/server.pem")));
+        assert!(!deny_list.is_denied(Path::new("/repo/secrets/nested/server.pem")));
+    }
+
+    #[test]
+    fn read_deny_list_rejects_invalid_patterns() {
+        let err = ReadDenyList::new(&["src/[".to_string()], Path::new("/repo"))
+            .expect_err("unterminated class should fail");
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(err.to_string().contains("src/["));
+    }
+
+    #[test]
+    fn find_denied_paths_expands_globs_against_the_filesystem() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let root = temp_dir.path();
+        std::fs::write(root.join(".env"), "TOKEN=1").expect("write .env");
+        std::fs::create_dir_all(root.join("service")).expect("create service dir");
+        std::fs::write(root.join("service/.env"), "TOKEN=2").expect("write nested .env");
+        std::fs::write(root.join("service/main.rs"), "fn main() {}").expect("write main.rs");
+        std::fs::create_dir_all(root.join("keys")).expect("create keys dir");
+
+        let deny_list = ReadDenyList::new(
+            &[
+                "**/.env".to_string(),
+                "keys".to_string(),
+                "missing.txt".to_string(),
+            ],
+            root,
+        )
+        .expect("compile deny list");
+
+        assert_eq!(
+            deny_list
+                .find_denied_paths(DEFAULT_DENY_READ_SCAN_LIMIT)
+                .expect("expand deny list"),
+            vec![
+                root.join(".env"),
+                root.join("keys"),
+                root.join("service/.env"),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_denied_paths_fails_instead_of_returning_a_partial_list() {
+        let temp_dir = TempDir::new().expect("temp dir");
+        let root = temp_dir.path();
+        for name in ["a", "b", "c"] {
+            std::fs::create_dir_all(root.join(name)).expect("create dir");
+            std::fs::write(root.join(name).join(".env"), "TOKEN=1").expect("write .env");
+        }
+        let deny_list =
+            ReadDenyList::new(&["**/.env".to_string()], root).expect("compile deny list");
+
+        let err = deny_list
+            .find_denied_paths(4)
+            .expect_err("scan should exceed the limit");
+        assert!(err.to_string().contains("too many paths"), "{err}");
+        assert_eq!(
+            deny_list
+                .find_denied_paths(6)
+                .expect("scan fits the limit")
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn deny_read_is_omitted_from_serialized_policy_when_empty() {
+        let policy = FileSystemSandboxPolicy::from(&SandboxPolicy::new_read_only_policy());
+        let json = serde_json::to_value(&policy).expect("serialize policy");
+        assert!(json.get("deny_read").is_none());
+
+        let json = serde_json::to_value(policy.with_deny_read(vec!["**/.env".to_string()]))
+            .expect("serialize policy");
+        assert_eq!(json["deny_read"], serde_json::json!(["**/.env"]));
+    }
+}
@@ -27,6 +27,7 @@ use crate::dynamic_tools::DynamicToolResponse;
 use crate::dynamic_tools::DynamicToolSpec;
 use crate::items::TurnItem;
 use crate::mcp::CallToolResult;
+use crate::mcp::Prompt as McpPrompt;
 use crate::mcp::RequestId;
 use crate::mcp::Resource as McpResource;
 use crate::mcp::ResourceTemplate as McpResourceTemplate;
@@ -379,6 +380,22 @@ pub enum Op {
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
 
+    /// Request the list of prompts offered by all configured MCP servers.
+    /// Reply is delivered via `EventMsg::ListMcpPromptsResponse`.
+    ListMcpPrompts,
+
+    /// Render a prompt offered by an MCP server with the given arguments.
+    /// Reply is delivered via `EventMsg::GetMcpPromptResponse`.
+    GetMcpPrompt {
+        /// Name of the MCP server offering the prompt.
+        server: String,
+        /// Name of the prompt on that server.
+        name: String,
+        /// Argument values keyed by argument name.
+        #[serde(default)]
+        arguments: HashMap<String, String>,
+    },
+
     /// Request MCP servers to reinitialize and refresh cached tool lists.
     RefreshMcpServers { config: McpServerRefreshConfig },
 
@@ -1172,6 +1189,15 @@ pub enum EventMsg {
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
+    /// List of prompts offered by MCP servers.
+    ListMcpPromptsResponse(ListMcpPromptsResponseEvent),
+
+    /// An MCP server reported that its prompts changed; carries the new list.
+    McpPromptListChanged(McpPromptListChangedEvent),
+
+    /// Rendered text of an MCP prompt requested via `Op::GetMcpPrompt`.
+    GetMcpPromptResponse(GetMcpPromptResponseEvent),
+
     /// Prompt stack snapshot for the current session.
     PromptTraceResponse(PromptTraceResponseEvent),
 
@@ -1541,6 +1567,9 @@ pub struct WarningEvent {
 #[ts(rename_all = "snake_case")]
 pub enum ModelRerouteReason {
     HighRiskCyberActivity,
+    RateLimited,
+    ProviderUnavailable,
+    ContextWindowExceeded,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -2333,6 +2362,18 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the changes introduced by the commits in `from..to`, i.e. the
+    /// commits reachable from `to` but not from `from`.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { from: String, to: String },
+
+    /// Review the changes in a unified diff file, such as a patch downloaded
+    /// from a code-review system.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PatchFile { path: PathBuf },
+
     /// Arbitrary instructions provided by the user.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -2346,6 +2387,10 @@ pub struct ReviewRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[ts(optional)]
     pub user_facing_hint: Option<String>,
+    /// Only review changes to files matching these git pathspecs (for example
+    /// `src/api/**`). Empty means every changed file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
 }
 
 /// Structured review result produced by a child review session.
@@ -2632,6 +2677,9 @@ pub struct McpListToolsResponseEvent {
     pub resources: std::collections::HashMap<String, Vec<McpResource>>,
     /// Known resource templates grouped by server name.
     pub resource_templates: std::collections::HashMap<String, Vec<McpResourceTemplate>>,
+    /// Known prompts grouped by server name.
+    #[serde(default)]
+    pub prompts: std::collections::HashMap<String, Vec<McpPrompt>>,
     /// Authentication status for each configured MCP server.
     pub auth_statuses: std::collections::HashMap<String, McpAuthStatus>,
 }
@@ -2695,6 +2743,30 @@ pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
 }
 
+/// Response payload for `Op::ListMcpPrompts`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListMcpPromptsResponseEvent {
+    /// Prompts grouped by server name.
+    pub prompts: HashMap<String, Vec<McpPrompt>>,
+}
+
+/// Sent when an MCP server emits `notifications/prompts/list_changed`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpPromptListChangedEvent {
+    pub server: String,
+    /// The server's prompts after the change.
+    pub prompts: Vec<McpPrompt>,
+}
+
+/// Response payload for `Op::GetMcpPrompt`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct GetMcpPromptResponseEvent {
+    pub server: String,
+    pub name: String,
+    /// The prompt's messages joined into a single block of text, or an error.
+    pub result: Result<String, String>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "kebab-case")]
 #[ts(rename_all = "kebab-case")]
@@ -2947,6 +3019,11 @@ pub enum ReviewDecision {
         network_policy_amendment: NetworkPolicyAmendment,
     },
 
+    /// User reviewed a proposed patch hunk by hunk. Only accepted and edited
+    /// hunks are applied; hunks without a decision are rejected. Only valid in
+    /// response to an `ApplyPatchApprovalRequest`.
+    ApprovedHunks { hunks: Vec<PatchHunkDecision> },
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
@@ -2971,12 +3048,38 @@ impl ReviewDecision {
                 NetworkPolicyRuleAction::Allow => "approved_with_network_policy_allow",
                 NetworkPolicyRuleAction::Deny => "denied_with_network_policy_deny",
             },
+            ReviewDecision::ApprovedHunks { .. } => "approved_hunks",
             ReviewDecision::Denied => "denied",
             ReviewDecision::Abort => "abort",
         }
     }
 }
 
+/// The reviewer's decision on one hunk of a proposed patch.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct PatchHunkDecision {
+    /// Path of the file, as keyed in the approval request's `changes`.
+    pub path: PathBuf,
+    /// Zero-based index into [`FileChange::hunks`] for the file.
+    pub hunk_index: usize,
+    pub action: PatchHunkAction,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum PatchHunkAction {
+    Accept,
+    Reject,
+    /// Apply `replacement` instead of the proposed hunk. For updated files it
+    /// holds the hunk's diff lines (prefixed with ` `, `-` or `+`); for added
+    /// files it is the new file contents. Deleted files cannot be edited and
+    /// treat this like `accept`.
+    Edit {
+        replacement: String,
+    },
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type")]
@@ -2993,6 +3096,34 @@ pub enum FileChange {
     },
 }
 
+impl FileChange {
+    /// Splits the change into the hunks a reviewer can accept or reject one at
+    /// a time: each `@@` section of an update's unified diff, or a single hunk
+    /// covering the whole file for additions, deletions and pure renames.
+    pub fn hunks(&self) -> Vec<String> {
+        let unified_diff = match self {
+            FileChange::Add { content } | FileChange::Delete { content } => {
+                return vec![content.clone()];
+            }
+            FileChange::Update { unified_diff, .. } => unified_diff,
+        };
+        let mut hunks: Vec<String> = Vec::new();
+        for line in unified_diff.split_inclusive('\n') {
+            if line.starts_with("@@") {
+                hunks.push(String::new());
+            }
+            // Skip the `---`/`+++` file headers that precede the first hunk.
+            if let Some(hunk) = hunks.last_mut() {
+                hunk.push_str(line);
+            }
+        }
+        if hunks.is_empty() {
+            hunks.push(String::new());
+        }
+        hunks
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct Chunk {
     /// 1-based line index of the first line in the original file
@@ -3797,6 +3928,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn execpolicy_amendment_serializes_as_array_unless_it_has_clauses() -> Result<()> {
+        let prefix = ExecPolicyAmendment::new(vec!["git".to_string(), "push".to_string()]);
+        assert_eq!(serde_json::to_value(&prefix)?, json!(["git", "push"]));
+        assert_eq!(
+            serde_json::from_value::<ExecPolicyAmendment>(json!(["git", "push"]))?,
+            prefix
+        );
+
+        let with_clauses = ExecPolicyAmendment {
+            not_matching: vec![r#"flag("--force", "-f")"#.to_string()],
+            ..prefix
+        };
+        let value = json!({
+            "command": ["git", "push"],
+            "not_matching": [r#"flag("--force", "-f")"#],
+        });
+        assert_eq!(serde_json::to_value(&with_clauses)?, value);
+        assert_eq!(
+            serde_json::from_value::<ExecPolicyAmendment>(value)?,
+            with_clauses
+        );
+        Ok(())
+    }
+
     #[test]
     fn turn_context_item_deserializes_without_network() -> Result<()> {
         let item: TurnContextItem = serde_json::from_value(json!({
@@ -4000,4 +4156,31 @@ mod tests {
 
         assert_eq!(info.model_context_window, Some(258_400));
     }
+
+    #[test]
+    fn file_change_hunks_split_unified_diff_sections() {
+        let update = FileChange::Update {
+            unified_diff:
+                "--- a/f\n+++ b/f\n@@ -1,2 +1,2 @@\n a\n-b\n+B\n@@ -9,2 +9,2 @@\n y\n-z\n+Z\n"
+                    .to_string(),
+            move_path: None,
+        };
+        let rename = FileChange::Update {
+            unified_diff: String::new(),
+            move_path: Some(PathBuf::from("g")),
+        };
+        let add = FileChange::Add {
+            content: "new\n".to_string(),
+        };
+
+        assert_eq!(
+            update.hunks(),
+            vec![
+                "@@ -1,2 +1,2 @@\n a\n-b\n+B\n".to_string(),
+                "@@ -9,2 +9,2 @@\n y\n-z\n+Z\n".to_string(),
+            ]
+        );
+        assert_eq!(rename.hunks(), vec![String::new()]);
+        assert_eq!(add.hunks(), vec!["new\n".to_string()]);
+    }
 }
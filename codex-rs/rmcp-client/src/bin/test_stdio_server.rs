@@ -7,11 +7,19 @@ use rmcp::ServiceExt;
 use rmcp::handler::server::ServerHandler;
 use rmcp::model::CallToolRequestParams;
 use rmcp::model::CallToolResult;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::JsonObject;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
 use rmcp::model::PaginatedRequestParams;
+use rmcp::model::Prompt;
+use rmcp::model::PromptArgument;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
 use rmcp::model::RawResource;
 use rmcp::model::RawResourceTemplate;
 use rmcp::model::ReadResourceRequestParams;
@@ -19,6 +27,7 @@ use rmcp::model::ReadResourceResult;
 use rmcp::model::Resource;
 use rmcp::model::ResourceContents;
 use rmcp::model::ResourceTemplate;
+use rmcp::model::SamplingMessage;
 use rmcp::model::ServerCapabilities;
 use rmcp::model::ServerInfo;
 use rmcp::model::Tool;
@@ -31,6 +40,7 @@ struct TestToolServer {
     tools: Arc<Vec<Tool>>,
     resources: Arc<Vec<Resource>>,
     resource_templates: Arc<Vec<ResourceTemplate>>,
+    prompts: Arc<Vec<Prompt>>,
 }
 
 const MEMO_URI: &str = "memo://codex/example-note";
@@ -47,13 +57,17 @@ impl TestToolServer {
             Self::echo_tool(),
             Self::image_tool(),
             Self::image_scenario_tool(),
+            Self::sample_tool(),
+            Self::roots_tool(),
         ];
         let resources = vec![Self::memo_resource()];
         let resource_templates = vec![Self::memo_template()];
+        let prompts = vec![Self::review_prompt()];
         Self {
             tools: Arc::new(tools),
             resources: Arc::new(resources),
             resource_templates: Arc::new(resource_templates),
+            prompts: Arc::new(prompts),
         }
     }
 
@@ -77,6 +91,40 @@ impl TestToolServer {
         )
     }
 
+    fn sample_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "prompt": { "type": "string" }
+            },
+            "required": ["prompt"],
+            "additionalProperties": false
+        }))
+        .expect("sample tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("sample"),
+            Cow::Borrowed("Ask the client for a completion via sampling/createMessage."),
+            Arc::new(schema),
+        )
+    }
+
+    fn roots_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "additionalProperties": false
+        }))
+        .expect("roots tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("roots"),
+            Cow::Borrowed("Return the roots reported by the client via roots/list."),
+            Arc::new(schema),
+        )
+    }
+
     fn image_tool() -> Tool {
         #[expect(clippy::expect_used)]
         let schema: JsonObject = serde_json::from_value(serde_json::json!({
@@ -179,6 +227,30 @@ impl TestToolServer {
     fn memo_text() -> &'static str {
         MEMO_CONTENT
     }
+
+    fn review_prompt() -> Prompt {
+        Prompt {
+            name: "review".to_string(),
+            title: Some("Review".to_string()),
+            description: Some("Review a file for bugs.".to_string()),
+            arguments: Some(vec![
+                PromptArgument {
+                    name: "path".to_string(),
+                    title: None,
+                    description: Some("File to review.".to_string()),
+                    required: Some(true),
+                },
+                PromptArgument {
+                    name: "focus".to_string(),
+                    title: None,
+                    description: None,
+                    required: Some(false),
+                },
+            ]),
+            icons: None,
+            meta: None,
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -205,6 +277,11 @@ enum ImageScenario {
     TextOnly,
 }
 
+#[derive(Deserialize)]
+struct SampleArgs {
+    prompt: String,
+}
+
 #[derive(Deserialize, Debug)]
 struct ImageScenarioArgs {
     scenario: ImageScenario,
@@ -221,6 +298,7 @@ impl ServerHandler for TestToolServer {
                 .enable_tools()
                 .enable_tool_list_changed()
                 .enable_resources()
+                .enable_prompts()
                 .build(),
             ..ServerInfo::default()
         }
@@ -290,10 +368,55 @@ impl ServerHandler for TestToolServer {
         }
     }
 
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParams>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult {
+            prompts: (*self.prompts).clone(),
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParams {
+            name, arguments, ..
+        }: GetPromptRequestParams,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        if name != "review" {
+            return Err(McpError::invalid_params(
+                format!("unknown prompt: {name}"),
+                None,
+            ));
+        }
+        let argument = |key: &str| {
+            arguments
+                .as_ref()
+                .and_then(|arguments| arguments.get(key))
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string)
+        };
+        let Some(path) = argument("path") else {
+            return Err(McpError::invalid_params("missing argument: path", None));
+        };
+        let mut text = format!("Review {path} for bugs.");
+        if let Some(focus) = argument("focus") {
+            text.push_str(&format!(" Focus on {focus}."));
+        }
+        Ok(GetPromptResult {
+            description: Some("Review a file for bugs.".to_string()),
+            messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+        })
+    }
+
     async fn call_tool(
         &self,
         request: CallToolRequestParams,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         match request.name.as_ref() {
             "echo" => {
@@ -348,6 +471,50 @@ impl ServerHandler for TestToolServer {
                 let args = Self::parse_call_args::<ImageScenarioArgs>(&request, "image_scenario")?;
                 Self::image_scenario_result(args)
             }
+            "sample" => {
+                let args = Self::parse_call_args::<SampleArgs>(&request, "sample")?;
+                let result = context
+                    .peer
+                    .create_message(CreateMessageRequestParams {
+                        meta: None,
+                        task: None,
+                        messages: vec![SamplingMessage::user_text(args.prompt)],
+                        model_preferences: None,
+                        system_prompt: Some("Answer briefly.".to_string()),
+                        include_context: None,
+                        temperature: None,
+                        max_tokens: 64,
+                        stop_sequences: None,
+                        metadata: None,
+                        tools: None,
+                        tool_choice: None,
+                    })
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let text = result
+                    .message
+                    .content
+                    .into_vec()
+                    .into_iter()
+                    .filter_map(|content| content.as_text().map(|text| text.text.clone()))
+                    .collect::<String>();
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    text,
+                )]))
+            }
+            "roots" => {
+                let result = context
+                    .peer
+                    .list_roots()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                Ok(CallToolResult {
+                    content: Vec::new(),
+                    structured_content: Some(json!({ "roots": result.roots })),
+                    is_error: Some(false),
+                    meta: None,
+                })
+            }
             other => Err(McpError::invalid_params(
                 format!("unknown tool: {other}"),
                 None,
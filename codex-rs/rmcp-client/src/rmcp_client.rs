@@ -9,6 +9,7 @@ use std::time::Duration;
 
 use anyhow::Result;
 use anyhow::anyhow;
+use codex_protocol::protocol::W3cTraceContext;
 use futures::FutureExt;
 use futures::StreamExt;
 use futures::future::BoxFuture;
@@ -19,18 +20,24 @@ use reqwest::header::AUTHORIZATION;
 use reqwest::header::CONTENT_TYPE;
 use reqwest::header::HeaderMap;
 use reqwest::header::WWW_AUTHENTICATE;
+use rmcp::model::CallToolRequest;
 use rmcp::model::CallToolRequestParams;
 use rmcp::model::CallToolResult;
 use rmcp::model::ClientNotification;
 use rmcp::model::ClientRequest;
 use rmcp::model::CreateElicitationRequestParams;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestParams;
+use rmcp::model::CreateMessageResult;
 use rmcp::model::CustomNotification;
 use rmcp::model::CustomRequest;
 use rmcp::model::ElicitationAction;
 use rmcp::model::Extensions;
+use rmcp::model::GetPromptRequestParams;
+use rmcp::model::GetPromptResult;
 use rmcp::model::InitializeRequestParams;
 use rmcp::model::InitializeResult;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
@@ -38,6 +45,7 @@ use rmcp::model::PaginatedRequestParams;
 use rmcp::model::ReadResourceRequestParams;
 use rmcp::model::ReadResourceResult;
 use rmcp::model::RequestId;
+use rmcp::model::Root;
 use rmcp::model::ServerResult;
 use rmcp::model::Tool;
 use rmcp::service::RoleClient;
@@ -62,6 +70,7 @@ use tokio::io::AsyncBufReadExt;
 use tokio::io::BufReader;
 use tokio::process::Command;
 use tokio::sync::Mutex;
+use tokio::sync::watch;
 use tokio::time;
 use tracing::info;
 use tracing::warn;
@@ -75,6 +84,8 @@ use crate::program_resolver;
 use crate::utils::apply_default_headers;
 use crate::utils::build_default_headers;
 use crate::utils::create_env_for_mcp_server;
+use crate::utils::trace_context_meta;
+use crate::utils::trace_headers_from_message;
 
 const EVENT_STREAM_MIME_TYPE: &str = "text/event-stream";
 const JSON_MIME_TYPE: &str = "application/json";
@@ -127,6 +138,7 @@ impl StreamableHttpClient for StreamableHttpResponseClient {
         if let Some(session_id_value) = session_id.as_ref() {
             request = request.header(HEADER_SESSION_ID, session_id_value.as_ref());
         }
+        request = request.headers(trace_headers_from_message(&message));
 
         let response = request
             .json(&message)
@@ -447,6 +459,13 @@ pub type SendElicitation = Box<
     dyn Fn(RequestId, Elicitation) -> BoxFuture<'static, Result<ElicitationResponse>> + Send + Sync,
 >;
 
+/// Interface for answering `sampling/createMessage` requests from the server.
+pub type SendSampling = Box<
+    dyn Fn(RequestId, CreateMessageRequestParams) -> BoxFuture<'static, Result<CreateMessageResult>>
+        + Send
+        + Sync,
+>;
+
 pub struct ToolWithConnectorId {
     pub tool: Tool,
     pub connector_id: Option<String>,
@@ -465,6 +484,8 @@ pub struct RmcpClient {
     transport_recipe: TransportRecipe,
     initialize_context: Mutex<Option<InitializeContext>>,
     session_recovery_lock: Mutex<()>,
+    prompt_list_changed: Arc<watch::Sender<()>>,
+    roots: Arc<watch::Sender<Vec<Root>>>,
 }
 
 impl RmcpClient {
@@ -493,6 +514,8 @@ impl RmcpClient {
             transport_recipe,
             initialize_context: Mutex::new(None),
             session_recovery_lock: Mutex::new(()),
+            prompt_list_changed: Arc::new(watch::Sender::new(())),
+            roots: Arc::new(watch::Sender::new(Vec::new())),
         })
     }
 
@@ -521,18 +544,30 @@ impl RmcpClient {
             transport_recipe,
             initialize_context: Mutex::new(None),
             session_recovery_lock: Mutex::new(()),
+            prompt_list_changed: Arc::new(watch::Sender::new(())),
+            roots: Arc::new(watch::Sender::new(Vec::new())),
         })
     }
 
     /// Perform the initialization handshake with the MCP server.
     /// https://modelcontextprotocol.io/specification/2025-06-18/basic/lifecycle#initialization
+    ///
+    /// `sampling/createMessage` requests are answered with `send_sampling`, or
+    /// rejected as unsupported when it is `None`.
     pub async fn initialize(
         &self,
         params: InitializeRequestParams,
         timeout: Option<Duration>,
         send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
     ) -> Result<InitializeResult> {
-        let client_handler = LoggingClientHandler::new(params.clone(), send_elicitation);
+        let client_handler = LoggingClientHandler::new(
+            params.clone(),
+            send_elicitation,
+            send_sampling,
+            Arc::clone(&self.prompt_list_changed),
+            Arc::clone(&self.roots),
+        );
         let pending_transport = {
             let mut guard = self.state.lock().await;
             match &mut *guard {
@@ -686,11 +721,87 @@ impl RmcpClient {
         Ok(result)
     }
 
+    pub async fn list_prompts(
+        &self,
+        params: Option<PaginatedRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        self.refresh_oauth_if_needed().await;
+        let result = self
+            .run_service_operation("prompts/list", timeout, move |service| {
+                let params = params.clone();
+                async move { service.list_prompts(params).await }.boxed()
+            })
+            .await?;
+        self.persist_oauth_tokens().await;
+        Ok(result)
+    }
+
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        self.refresh_oauth_if_needed().await;
+        let result = self
+            .run_service_operation("prompts/get", timeout, move |service| {
+                let params = params.clone();
+                async move { service.get_prompt(params).await }.boxed()
+            })
+            .await?;
+        self.persist_oauth_tokens().await;
+        Ok(result)
+    }
+
+    /// Returns a receiver that is marked changed whenever the server sends
+    /// `notifications/prompts/list_changed`. The receiver errors once the
+    /// client has been dropped.
+    pub fn subscribe_prompt_list_changed(&self) -> watch::Receiver<()> {
+        self.prompt_list_changed.subscribe()
+    }
+
+    /// Replaces the roots returned for `roots/list` and, when they changed on
+    /// an initialized client, sends `notifications/roots/list_changed`.
+    pub async fn set_roots(&self, roots: Vec<Root>) -> Result<()> {
+        let changed = self.roots.send_if_modified(|current| {
+            if *current == roots {
+                return false;
+            }
+            *current = roots;
+            true
+        });
+        if !changed {
+            return Ok(());
+        }
+        let service = {
+            let guard = self.state.lock().await;
+            match &*guard {
+                ClientState::Ready { service, .. } => Arc::clone(service),
+                ClientState::Connecting { .. } => return Ok(()),
+            }
+        };
+        service.peer().notify_roots_list_changed().await?;
+        Ok(())
+    }
+
     pub async fn call_tool(
         &self,
         name: String,
         arguments: Option<serde_json::Value>,
         timeout: Option<Duration>,
+    ) -> Result<CallToolResult> {
+        self.call_tool_with_trace(name, arguments, timeout, None)
+            .await
+    }
+
+    /// Calls a tool, attaching `trace` to the request `_meta` and, for
+    /// streamable HTTP servers, as `traceparent`/`tracestate` headers.
+    pub async fn call_tool_with_trace(
+        &self,
+        name: String,
+        arguments: Option<serde_json::Value>,
+        timeout: Option<Duration>,
+        trace: Option<W3cTraceContext>,
     ) -> Result<CallToolResult> {
         self.refresh_oauth_if_needed().await;
         let arguments = match arguments {
@@ -702,16 +813,27 @@ impl RmcpClient {
             }
             None => None,
         };
-        let rmcp_params = CallToolRequestParams {
-            meta: None,
+        let mut request = CallToolRequest::new(CallToolRequestParams {
+            meta: trace.as_ref().and_then(trace_context_meta),
             name: name.into(),
             arguments,
             task: None,
-        };
+        });
+        // The HTTP transport reads the trace from the extensions to set the
+        // trace headers, so it does not have to parse `_meta` back out.
+        if let Some(trace) = trace {
+            request.extensions.insert(trace);
+        }
         let result = self
             .run_service_operation("tools/call", timeout, move |service| {
-                let rmcp_params = rmcp_params.clone();
-                async move { service.call_tool(rmcp_params).await }.boxed()
+                let request = ClientRequest::CallToolRequest(request.clone());
+                async move {
+                    match service.send_request(request).await? {
+                        ServerResult::CallToolResult(result) => Ok(result),
+                        _ => Err(rmcp::service::ServiceError::UnexpectedResponse),
+                    }
+                }
+                .boxed()
             })
             .await?;
         self.persist_oauth_tokens().await;
@@ -1,11 +1,19 @@
 use anyhow::Result;
+use codex_protocol::protocol::W3cTraceContext;
 use reqwest::ClientBuilder;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
+use rmcp::model::ClientJsonRpcMessage;
+use rmcp::model::GetExtensions;
+use rmcp::model::Meta;
+use serde_json::Value;
 use std::collections::HashMap;
 use std::env;
 
+/// W3C trace context keys, used both in request `_meta` and as HTTP headers.
+const TRACE_CONTEXT_KEYS: [&str; 2] = ["traceparent", "tracestate"];
+
 pub(crate) fn create_env_for_mcp_server(
     extra_env: Option<HashMap<String, String>>,
     env_vars: &[String],
@@ -88,6 +96,44 @@ pub(crate) fn apply_default_headers(
     }
 }
 
+/// Builds the request `_meta` that carries `trace` to the MCP server.
+pub(crate) fn trace_context_meta(trace: &W3cTraceContext) -> Option<Meta> {
+    let mut meta = Meta::new();
+    for (key, value) in TRACE_CONTEXT_KEYS
+        .into_iter()
+        .zip([&trace.traceparent, &trace.tracestate])
+    {
+        if let Some(value) = value {
+            meta.0.insert(key.to_string(), Value::String(value.clone()));
+        }
+    }
+    (!meta.0.is_empty()).then_some(meta)
+}
+
+/// Returns the W3C trace headers for a request sent with a trace context in
+/// its extensions, so HTTP servers can continue the caller's trace.
+pub(crate) fn trace_headers_from_message(message: &ClientJsonRpcMessage) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let ClientJsonRpcMessage::Request(request) = message else {
+        return headers;
+    };
+    let Some(trace) = request.request.extensions().get::<W3cTraceContext>() else {
+        return headers;
+    };
+    for (key, value) in TRACE_CONTEXT_KEYS
+        .into_iter()
+        .zip([&trace.traceparent, &trace.tracestate])
+    {
+        if let Some(value) = value
+            .as_deref()
+            .and_then(|value| HeaderValue::from_str(value).ok())
+        {
+            headers.insert(HeaderName::from_static(key), value);
+        }
+    }
+    headers
+}
+
 #[cfg(unix)]
 pub(crate) const DEFAULT_ENV_VARS: &[&str] = &[
     "HOME",
@@ -138,6 +184,10 @@ pub(crate) const DEFAULT_ENV_VARS: &[&str] = &[
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
+    use rmcp::model::CallToolRequest;
+    use rmcp::model::CallToolRequestParams;
+    use rmcp::model::ClientRequest;
+    use rmcp::model::RequestId;
 
     use serial_test::serial;
     use std::ffi::OsString;
@@ -174,6 +224,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn trace_context_is_sent_in_meta_and_headers() {
+        let trace = W3cTraceContext {
+            traceparent: Some(
+                "00-00000000000000000000000000000011-0000000000000022-01".to_string(),
+            ),
+            tracestate: None,
+        };
+        let meta = trace_context_meta(&trace).expect("meta");
+        assert_eq!(
+            meta.0.get("traceparent").and_then(Value::as_str),
+            trace.traceparent.as_deref()
+        );
+        let mut request = CallToolRequest::new(CallToolRequestParams {
+            meta: Some(meta),
+            name: "echo".into(),
+            arguments: None,
+            task: None,
+        });
+        assert_eq!(
+            trace_headers_from_message(&ClientJsonRpcMessage::request(
+                ClientRequest::CallToolRequest(request.clone()),
+                RequestId::Number(1),
+            )),
+            HeaderMap::new()
+        );
+        request.extensions.insert(trace.clone());
+
+        let headers = trace_headers_from_message(&ClientJsonRpcMessage::request(
+            ClientRequest::CallToolRequest(request),
+            RequestId::Number(1),
+        ));
+        assert_eq!(headers.len(), 1);
+        assert_eq!(
+            headers
+                .get("traceparent")
+                .and_then(|value| value.to_str().ok()),
+            trace.traceparent.as_deref()
+        );
+        assert!(
+            trace_context_meta(&W3cTraceContext {
+                traceparent: None,
+                tracestate: None,
+            })
+            .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn create_env_honors_overrides() {
         let value = "custom".to_string();
@@ -0,0 +1,195 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::SendElicitation;
+use codex_utils_cargo_bin::CargoBinError;
+use futures::FutureExt as _;
+use rmcp::model::ClientCapabilities;
+use rmcp::model::CreateMessageResult;
+use rmcp::model::Implementation;
+use rmcp::model::InitializeRequestParams;
+use rmcp::model::ProtocolVersion;
+use rmcp::model::Root;
+use rmcp::model::RootsCapabilities;
+use rmcp::model::SamplingCapability;
+use rmcp::model::SamplingMessage;
+use serde_json::json;
+
+fn stdio_server_bin() -> Result<PathBuf, CargoBinError> {
+    codex_utils_cargo_bin::cargo_bin("test_stdio_server")
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        meta: None,
+        capabilities: ClientCapabilities {
+            experimental: None,
+            extensions: None,
+            roots: Some(RootsCapabilities {
+                list_changed: Some(true),
+            }),
+            sampling: Some(SamplingCapability::default()),
+            elicitation: None,
+            tasks: None,
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp sampling test".into()),
+            description: None,
+            icons: None,
+            website_url: None,
+        },
+        protocol_version: ProtocolVersion::V_2025_06_18,
+    }
+}
+
+fn decline_elicitation() -> SendElicitation {
+    Box::new(|_, _| {
+        async {
+            Ok(ElicitationResponse {
+                action: ElicitationAction::Decline,
+                content: None,
+                meta: None,
+            })
+        }
+        .boxed()
+    })
+}
+
+async fn stdio_client() -> anyhow::Result<RmcpClient> {
+    Ok(RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?)
+}
+
+fn text_content(result: &rmcp::model::CallToolResult) -> String {
+    result
+        .content
+        .iter()
+        .filter_map(|content| content.as_text().map(|text| text.text.clone()))
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn sampling_requests_are_answered_by_the_client() -> anyhow::Result<()> {
+    let client = stdio_client().await?;
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            decline_elicitation(),
+            Some(Box::new(|_, params| {
+                async move {
+                    let prompt = params
+                        .messages
+                        .iter()
+                        .flat_map(|message| message.content.clone().into_vec())
+                        .filter_map(|content| content.as_text().map(|text| text.text.clone()))
+                        .collect::<String>();
+                    Ok(CreateMessageResult {
+                        model: "test-model".to_string(),
+                        stop_reason: Some(CreateMessageResult::STOP_REASON_END_TURN.to_string()),
+                        message: SamplingMessage::assistant_text(format!(
+                            "{} / {prompt} / {}",
+                            params.system_prompt.unwrap_or_default(),
+                            params.max_tokens
+                        )),
+                    })
+                }
+                .boxed()
+            })),
+        )
+        .await?;
+
+    let result = client
+        .call_tool(
+            "sample".to_string(),
+            Some(json!({ "prompt": "summarize the repo" })),
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    assert_eq!(result.is_error, Some(false));
+    assert_eq!(
+        text_content(&result),
+        "Answer briefly. / summarize the repo / 64"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn sampling_is_rejected_without_a_handler() -> anyhow::Result<()> {
+    let client = stdio_client().await?;
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            decline_elicitation(),
+            None,
+        )
+        .await?;
+
+    let result = client
+        .call_tool(
+            "sample".to_string(),
+            Some(json!({ "prompt": "summarize the repo" })),
+            Some(Duration::from_secs(5)),
+        )
+        .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn roots_list_reflects_latest_roots() -> anyhow::Result<()> {
+    let client = stdio_client().await?;
+    let workspace = Root {
+        uri: "file:///workspace/".to_string(),
+        name: Some("workspace".to_string()),
+    };
+    client.set_roots(vec![workspace.clone()]).await?;
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            decline_elicitation(),
+            None,
+        )
+        .await?;
+
+    let result = client
+        .call_tool("roots".to_string(), None, Some(Duration::from_secs(5)))
+        .await?;
+    assert_eq!(
+        result.structured_content,
+        Some(json!({ "roots": [workspace] }))
+    );
+
+    let extra = Root {
+        uri: "file:///extra/".to_string(),
+        name: Some("extra".to_string()),
+    };
+    client
+        .set_roots(vec![workspace.clone(), extra.clone()])
+        .await?;
+    let result = client
+        .call_tool("roots".to_string(), None, Some(Duration::from_secs(5)))
+        .await?;
+    assert_eq!(
+        result.structured_content,
+        Some(json!({ "roots": [workspace, extra] }))
+    );
+
+    Ok(())
+}
@@ -16,10 +16,15 @@ mod local;
 mod sanitizer;
 
 pub use local::LocalSecretsBackend;
+pub use sanitizer::redact_secret_values;
 pub use sanitizer::redact_secrets;
 
 const KEYRING_SERVICE: &str = "codex";
 
+/// Prefix that marks a config value as a reference to a stored secret, as in
+/// `NPM_TOKEN = "secret:npm_token"`.
+pub const SECRET_REFERENCE_PREFIX: &str = "secret:";
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SecretName(String);
 
@@ -36,6 +41,13 @@ impl SecretName {
         Ok(Self(trimmed.to_string()))
     }
 
+    /// Parses a `secret:<name>` reference. Returns `None` when `value` is not a
+    /// reference. Names in references are case-insensitive.
+    pub fn from_reference(value: &str) -> Option<Result<Self>> {
+        let name = value.strip_prefix(SECRET_REFERENCE_PREFIX)?;
+        Some(Self::new(&name.to_ascii_uppercase()))
+    }
+
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
@@ -136,6 +148,16 @@ impl SecretsManager {
     pub fn list(&self, scope_filter: Option<&SecretScope>) -> Result<Vec<SecretListEntry>> {
         self.backend.list(scope_filter)
     }
+
+    /// Looks up `name` for commands running in `cwd`: the secret scoped to the
+    /// environment of `cwd` wins over the global one.
+    pub fn resolve(&self, name: &SecretName, cwd: &Path) -> Result<Option<String>> {
+        let environment = SecretScope::environment(environment_id_from_cwd(cwd))?;
+        if let Some(value) = self.get(&environment, name)? {
+            return Ok(Some(value));
+        }
+        self.get(&SecretScope::Global, name)
+    }
 }
 
 pub fn environment_id_from_cwd(cwd: &Path) -> String {
@@ -242,4 +264,33 @@ mod tests {
         assert_eq!(manager.get(&scope, &name)?, None);
         Ok(())
     }
+
+    #[test]
+    fn resolve_prefers_environment_scope() -> Result<()> {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let cwd = tempfile::tempdir().expect("tempdir");
+        let keyring = Arc::new(MockKeyringStore::default());
+        let manager = SecretsManager::new_with_keyring_store(
+            codex_home.path().to_path_buf(),
+            SecretsBackendKind::Local,
+            keyring,
+        );
+        let name = SecretName::from_reference("secret:npm_token").expect("reference")?;
+        assert_eq!(name.as_str(), "NPM_TOKEN");
+        assert!(SecretName::from_reference("npm_token").is_none());
+
+        assert_eq!(manager.resolve(&name, cwd.path())?, None);
+        manager.set(&SecretScope::Global, &name, "global-token")?;
+        assert_eq!(
+            manager.resolve(&name, cwd.path())?,
+            Some("global-token".to_string())
+        );
+        let environment = SecretScope::environment(environment_id_from_cwd(cwd.path()))?;
+        manager.set(&environment, &name, "env-token")?;
+        assert_eq!(
+            manager.resolve(&name, cwd.path())?,
+            Some("env-token".to_string())
+        );
+        Ok(())
+    }
 }
@@ -21,6 +21,24 @@ pub fn redact_secrets(input: String) -> String {
     redacted.to_string()
 }
 
+/// Secret values shorter than this are not redacted: replacing them would
+/// mangle unrelated output.
+const MIN_REDACTED_VALUE_LEN: usize = 4;
+
+/// Replace every occurrence of the given secret values in `input`. Longer values
+/// are replaced first so that a value containing another one is fully redacted.
+pub fn redact_secret_values<S: AsRef<str>>(input: &str, values: &[S]) -> String {
+    let mut values: Vec<&str> = values
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|value| value.len() >= MIN_REDACTED_VALUE_LEN)
+        .collect();
+    values.sort_by_key(|value| std::cmp::Reverse(value.len()));
+    values.into_iter().fold(input.to_string(), |output, value| {
+        output.replace(value, "[REDACTED_SECRET]")
+    })
+}
+
 fn compile_regex(pattern: &str) -> Regex {
     match Regex::new(pattern) {
         Ok(regex) => regex,
@@ -38,4 +56,13 @@ mod tests {
         // The goal of this test is just to compile all the regex to prevent the panic
         let _ = redact_secrets("secret".to_string());
     }
+
+    #[test]
+    fn redacts_secret_values() {
+        let values = ["npm-token-1", "npm-token-1-long", "abc"];
+        assert_eq!(
+            redact_secret_values("auth npm-token-1-long and npm-token-1, abc", &values),
+            "auth [REDACTED_SECRET] and [REDACTED_SECRET], abc"
+        );
+    }
 }
@@ -1,4 +1,6 @@
 use crate::bash::parse_shell_lc_plain_commands;
+use crate::fish::parse_fish_lc_plain_commands;
+use crate::nushell::parse_nushell_plain_commands;
 use std::path::Path;
 #[cfg(windows)]
 #[path = "windows_dangerous_commands.rs"]
@@ -17,7 +19,10 @@ pub fn command_might_be_dangerous(command: &[String]) -> bool {
     }
 
     // Support `bash -lc "<script>"` where the any part of the script might contain a dangerous command.
+    // fish and Nushell scripts are split with their own, equally conservative, parsers.
     if let Some(all_commands) = parse_shell_lc_plain_commands(command)
+        .or_else(|| parse_fish_lc_plain_commands(command))
+        .or_else(|| parse_nushell_plain_commands(command))
         && all_commands
             .iter()
             .any(|cmd| is_dangerous_to_call_with_exec(cmd))
@@ -158,4 +163,19 @@ mod tests {
     fn rm_f_is_dangerous() {
         assert!(command_might_be_dangerous(&vec_str(&["rm", "-f", "/"])));
     }
+
+    #[test]
+    fn fish_and_nushell_scripts_are_inspected() {
+        assert!(command_might_be_dangerous(&vec_str(&[
+            "fish",
+            "-lc",
+            "ls; and rm -rf /"
+        ])));
+        assert!(command_might_be_dangerous(&vec_str(&[
+            "nu",
+            "-l",
+            "-c",
+            "pwd; rm -rf /"
+        ])));
+    }
 }
@@ -5,6 +5,8 @@ use crate::command_safety::is_dangerous_command::executable_name_lookup_key;
 // Implemented in `is_dangerous_command` and shared here.
 use crate::command_safety::is_dangerous_command::find_git_subcommand;
 use crate::command_safety::windows_safe_commands::is_safe_command_windows;
+use crate::fish::parse_fish_lc_plain_commands;
+use crate::nushell::parse_nushell_plain_commands;
 
 pub fn is_known_safe_command(command: &[String]) -> bool {
     let command: Vec<String> = command
@@ -31,8 +33,11 @@ pub fn is_known_safe_command(command: &[String]) -> bool {
     // a conservative allow‑list of shell operators that themselves do not
     // introduce side effects ( "&&", "||", ";", and "|" ). If every
     // individual command in the script is itself a known‑safe command, then
-    // the composite expression is considered safe.
+    // the composite expression is considered safe. The same applies to
+    // `fish -lc "..."` and `nu -c "..."` scripts written in their own syntax.
     if let Some(all_commands) = parse_shell_lc_plain_commands(&command)
+        .or_else(|| parse_fish_lc_plain_commands(&command))
+        .or_else(|| parse_nushell_plain_commands(&command))
         && !all_commands.is_empty()
         && all_commands
             .iter()
@@ -561,6 +566,42 @@ mod tests {
         ])));
     }
 
+    #[test]
+    fn fish_and_nushell_safe_examples() {
+        assert!(is_known_safe_command(&vec_str(&[
+            "/usr/bin/fish",
+            "-lc",
+            "ls -la; and git status"
+        ])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "fish",
+            "-c",
+            "ls | wc -l"
+        ])));
+        assert!(is_known_safe_command(&vec_str(&[
+            "nu",
+            "-l",
+            "-c",
+            "git status; pwd"
+        ])));
+
+        assert!(!is_known_safe_command(&vec_str(&[
+            "fish",
+            "-lc",
+            "ls (rm -rf foo)"
+        ])));
+        assert!(!is_known_safe_command(&vec_str(&[
+            "fish",
+            "-lc",
+            "echo hi > out.txt"
+        ])));
+        assert!(!is_known_safe_command(&vec_str(&[
+            "nu",
+            "-c",
+            "ls | each { |it| rm $it.name }"
+        ])));
+    }
+
     #[test]
     fn bash_lc_unsafe_examples() {
         assert!(
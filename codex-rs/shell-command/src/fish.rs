@@ -0,0 +1,218 @@
+use std::path::PathBuf;
+
+use crate::shell_detect::ShellType;
+use crate::shell_detect::detect_shell_type;
+
+/// fish keywords that only chain the command that follows them.
+const CHAINING_KEYWORDS: &[&str] = &["and", "or", "not"];
+
+/// fish keywords that open or continue a block; scripts using them are not
+/// plain command sequences.
+const BLOCK_KEYWORDS: &[&str] = &[
+    "begin", "end", "if", "else", "while", "for", "switch", "case", "function", "return", "break",
+    "continue",
+];
+
+/// Extracts the shell and script from a `fish -c "..."` or `fish -lc "..."`
+/// invocation.
+pub fn extract_fish_command(command: &[String]) -> Option<(&str, &str)> {
+    let [shell, flag, script] = command else {
+        return None;
+    };
+    if !matches!(flag.as_str(), "-lc" | "-c")
+        || detect_shell_type(&PathBuf::from(shell)) != Some(ShellType::Fish)
+    {
+        return None;
+    }
+    Some((shell, script))
+}
+
+/// Returns the sequence of plain commands within a `fish -lc "..."`
+/// invocation when the script only contains word-only commands joined by
+/// `;`, `&&`, `||`, `|` or the `and`/`or`/`not` keywords.
+pub fn parse_fish_lc_plain_commands(command: &[String]) -> Option<Vec<Vec<String>>> {
+    let (_, script) = extract_fish_command(command)?;
+    try_parse_fish_word_only_commands_sequence(script)
+}
+
+/// Splits a fish script into plain commands.
+///
+/// This is deliberately conservative: variable expansion, command
+/// substitution, globs, brace expansion, redirections, backgrounding and
+/// block constructs all cause `None` to be returned.
+pub fn try_parse_fish_word_only_commands_sequence(src: &str) -> Option<Vec<Vec<String>>> {
+    let mut commands = Vec::new();
+    let mut command: Vec<String> = Vec::new();
+    let mut word: Option<String> = None;
+    let mut pending_operator = false;
+    let mut chars = src.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' => finish_word(&mut word, &mut command),
+            '\n' | ';' | '|' | '&' => {
+                finish_word(&mut word, &mut command);
+                let is_sequence = matches!(c, '\n' | ';');
+                match c {
+                    '|' if chars.peek() == Some(&'|') => {
+                        chars.next();
+                    }
+                    '&' if chars.next() != Some('&') => return None,
+                    _ => {}
+                }
+                if command.is_empty() {
+                    // Blank lines and trailing `;` are fine; empty operands
+                    // of `&&`, `||` and `|` are not.
+                    if !is_sequence || pending_operator {
+                        return None;
+                    }
+                    continue;
+                }
+                commands.push(finish_command(std::mem::take(&mut command))?);
+                pending_operator = !is_sequence;
+            }
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        '\\' if matches!(chars.peek(), Some('\'' | '\\')) => {
+                            word.push(chars.next()?);
+                        }
+                        other => word.push(other),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '$' | '(' => return None,
+                        '\\' if matches!(chars.peek(), Some('"' | '\\' | '$')) => {
+                            word.push(chars.next()?);
+                        }
+                        other => word.push(other),
+                    }
+                }
+            }
+            c if is_plain_word_char(c) => word.get_or_insert_with(String::new).push(c),
+            _ => return None,
+        }
+    }
+
+    finish_word(&mut word, &mut command);
+    if command.is_empty() {
+        if pending_operator {
+            return None;
+        }
+    } else {
+        commands.push(finish_command(command)?);
+    }
+    Some(commands)
+}
+
+fn is_plain_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c)
+}
+
+fn finish_word(word: &mut Option<String>, command: &mut Vec<String>) {
+    if let Some(word) = word.take() {
+        command.push(word);
+    }
+}
+
+fn finish_command(mut command: Vec<String>) -> Option<Vec<String>> {
+    let keywords = command
+        .iter()
+        .take_while(|word| CHAINING_KEYWORDS.contains(&word.as_str()))
+        .count();
+    command.drain(..keywords);
+    match command.first() {
+        Some(program) if !BLOCK_KEYWORDS.contains(&program.as_str()) => Some(command),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn parse_seq(src: &str) -> Option<Vec<Vec<String>>> {
+        try_parse_fish_word_only_commands_sequence(src)
+    }
+
+    fn vec_str(items: &[&str]) -> Vec<String> {
+        items.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn accepts_commands_joined_by_operators_and_keywords() {
+        assert_eq!(
+            parse_seq("ls -la; and git status || pwd | wc -l\nnot false").unwrap(),
+            vec![
+                vec_str(&["ls", "-la"]),
+                vec_str(&["git", "status"]),
+                vec_str(&["pwd"]),
+                vec_str(&["wc", "-l"]),
+                vec_str(&["false"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn unescapes_fish_quoting() {
+        assert_eq!(
+            parse_seq(r#"echo 'it\'s' "a \"b\"" 'c\d'"#).unwrap(),
+            vec![vec_str(&["echo", "it's", r#"a "b""#, r"c\d"])]
+        );
+    }
+
+    #[test]
+    fn rejects_expansions_and_substitutions() {
+        for src in [
+            "echo $HOME",
+            "echo \"$HOME\"",
+            "echo (whoami)",
+            "echo \"$(whoami)\"",
+            "ls *.rs",
+            "echo {a,b}",
+            "echo ~",
+        ] {
+            assert!(parse_seq(src).is_none(), "{src}");
+        }
+    }
+
+    #[test]
+    fn rejects_redirections_background_and_blocks() {
+        for src in [
+            "echo hi > out.txt",
+            "cat < in.txt",
+            "sleep 10 &",
+            "ls &> out",
+            "begin; ls; end",
+        ] {
+            assert!(parse_seq(src).is_none(), "{src}");
+        }
+    }
+
+    #[test]
+    fn rejects_empty_operands() {
+        for src in ["&& ls", "ls &&", "ls | | wc", "ls; and", "'unterminated"] {
+            assert!(parse_seq(src).is_none(), "{src}");
+        }
+    }
+
+    #[test]
+    fn parses_fish_lc_invocations_only() {
+        assert_eq!(
+            parse_fish_lc_plain_commands(&vec_str(&["/usr/bin/fish", "-lc", "ls"])),
+            Some(vec![vec_str(&["ls"])])
+        );
+        assert_eq!(
+            parse_fish_lc_plain_commands(&vec_str(&["bash", "-lc", "ls"])),
+            None
+        );
+    }
+}
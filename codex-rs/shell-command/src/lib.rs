@@ -4,6 +4,8 @@ mod shell_detect;
 
 pub mod bash;
 pub mod command_safety;
+pub mod fish;
+pub mod nushell;
 pub mod parse_command;
 pub mod powershell;
 
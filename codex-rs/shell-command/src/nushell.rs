@@ -0,0 +1,210 @@
+use std::path::PathBuf;
+
+use crate::shell_detect::ShellType;
+use crate::shell_detect::detect_shell_type;
+
+/// Nushell keywords that define or control blocks; scripts using them are
+/// not plain command sequences.
+const BLOCK_KEYWORDS: &[&str] = &[
+    "def", "export", "if", "else", "for", "while", "loop", "match", "try", "let", "mut", "const",
+    "alias", "use", "source", "overlay", "module", "do", "return", "break", "continue",
+];
+
+/// Extracts the shell and script from a Nushell invocation of the form
+/// `nu -c "..."` or `nu -l -c "..."` (long flag spellings are accepted too).
+pub fn extract_nushell_command(command: &[String]) -> Option<(&str, &str)> {
+    let (shell, flags, script) = match command {
+        [shell, _, script] => (shell, &command[1..2], script),
+        [shell, _, _, script] => (shell, &command[1..3], script),
+        _ => return None,
+    };
+    let (command_flag, login_flags) = flags.split_last()?;
+    if !matches!(command_flag.as_str(), "-c" | "--commands")
+        || !login_flags
+            .iter()
+            .all(|flag| matches!(flag.as_str(), "-l" | "--login"))
+        || detect_shell_type(&PathBuf::from(shell)) != Some(ShellType::Nushell)
+    {
+        return None;
+    }
+    Some((shell, script))
+}
+
+/// Returns the sequence of plain commands within a `nu -c "..."` invocation
+/// when the script only contains word-only commands joined by `;` or `|`.
+pub fn parse_nushell_plain_commands(command: &[String]) -> Option<Vec<Vec<String>>> {
+    let (_, script) = extract_nushell_command(command)?;
+    try_parse_nushell_word_only_commands_sequence(script)
+}
+
+/// Splits a Nushell script into plain commands.
+///
+/// This is deliberately conservative: variables, subexpressions, closures,
+/// records, lists, string interpolation, redirections and `^` external
+/// calls all cause `None` to be returned. Nushell has no `&&`/`||`
+/// operators, so any `&` is rejected as well.
+pub fn try_parse_nushell_word_only_commands_sequence(src: &str) -> Option<Vec<Vec<String>>> {
+    let mut commands = Vec::new();
+    let mut command: Vec<String> = Vec::new();
+    let mut word: Option<String> = None;
+    let mut pending_pipe = false;
+    let mut chars = src.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' | '\r' => finish_word(&mut word, &mut command),
+            '\n' | ';' | '|' => {
+                finish_word(&mut word, &mut command);
+                let is_pipe = c == '|';
+                if command.is_empty() {
+                    if is_pipe || pending_pipe {
+                        return None;
+                    }
+                    continue;
+                }
+                commands.push(finish_command(std::mem::take(&mut command))?);
+                pending_pipe = is_pipe;
+            }
+            '\'' | '`' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        quote if quote == c => break,
+                        other => word.push(other),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            escaped @ ('"' | '\\' | '\'' | '/') => word.push(escaped),
+                            'n' => word.push('\n'),
+                            't' => word.push('\t'),
+                            _ => return None,
+                        },
+                        other => word.push(other),
+                    }
+                }
+            }
+            c if is_plain_word_char(c) => word.get_or_insert_with(String::new).push(c),
+            _ => return None,
+        }
+    }
+
+    finish_word(&mut word, &mut command);
+    if command.is_empty() {
+        if pending_pipe {
+            return None;
+        }
+    } else {
+        commands.push(finish_command(command)?);
+    }
+    Some(commands)
+}
+
+fn is_plain_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c)
+}
+
+fn finish_word(word: &mut Option<String>, command: &mut Vec<String>) {
+    if let Some(word) = word.take() {
+        command.push(word);
+    }
+}
+
+fn finish_command(command: Vec<String>) -> Option<Vec<String>> {
+    match command.first() {
+        Some(program) if !BLOCK_KEYWORDS.contains(&program.as_str()) => Some(command),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn parse_seq(src: &str) -> Option<Vec<Vec<String>>> {
+        try_parse_nushell_word_only_commands_sequence(src)
+    }
+
+    fn vec_str(items: &[&str]) -> Vec<String> {
+        items.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn accepts_commands_joined_by_semicolons_and_pipes() {
+        assert_eq!(
+            parse_seq("ls -a; git status | lines\npwd").unwrap(),
+            vec![
+                vec_str(&["ls", "-a"]),
+                vec_str(&["git", "status"]),
+                vec_str(&["lines"]),
+                vec_str(&["pwd"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn unescapes_nushell_quoting() {
+        assert_eq!(
+            parse_seq(r#"echo 'a\b' "c \"d\"" `e f`"#).unwrap(),
+            vec![vec_str(&["echo", r"a\b", r#"c "d""#, "e f"])]
+        );
+    }
+
+    #[test]
+    fn rejects_variables_subexpressions_and_blocks() {
+        for src in [
+            "echo $env.HOME",
+            "echo $\"(whoami)\"",
+            "echo (whoami)",
+            "ls | where size > 10",
+            "ls | each { |it| rm $it.name }",
+            "echo [a b]",
+            "^ls",
+            "let x = 1",
+            "def foo [] { ls }",
+            "ls out> file.txt",
+        ] {
+            assert!(parse_seq(src).is_none(), "{src}");
+        }
+    }
+
+    #[test]
+    fn rejects_empty_operands_and_unknown_operators() {
+        for src in [
+            "| ls",
+            "ls |",
+            "ls | ; pwd",
+            "ls && pwd",
+            "ls || pwd",
+            "'open",
+        ] {
+            assert!(parse_seq(src).is_none(), "{src}");
+        }
+    }
+
+    #[test]
+    fn parses_nushell_invocations_only() {
+        assert_eq!(
+            parse_nushell_plain_commands(&vec_str(&["/usr/bin/nu", "-l", "-c", "ls"])),
+            Some(vec![vec_str(&["ls"])])
+        );
+        assert_eq!(
+            parse_nushell_plain_commands(&vec_str(&["nu", "--commands", "ls"])),
+            Some(vec![vec_str(&["ls"])])
+        );
+        assert_eq!(
+            parse_nushell_plain_commands(&vec_str(&["nu", "-lc", "ls"])),
+            None
+        );
+        assert_eq!(
+            parse_nushell_plain_commands(&vec_str(&["bash", "-c", "ls"])),
+            None
+        );
+    }
+}
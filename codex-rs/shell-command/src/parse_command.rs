@@ -1,6 +1,10 @@
 use crate::bash::extract_bash_command;
 use crate::bash::try_parse_shell;
 use crate::bash::try_parse_word_only_commands_sequence;
+use crate::fish::extract_fish_command;
+use crate::fish::try_parse_fish_word_only_commands_sequence;
+use crate::nushell::extract_nushell_command;
+use crate::nushell::try_parse_nushell_word_only_commands_sequence;
 use crate::powershell::extract_powershell_command;
 use codex_protocol::parse_command::ParsedCommand;
 use shlex::split as shlex_split;
@@ -14,7 +18,10 @@ pub fn shlex_join(tokens: &[String]) -> String {
 
 /// Extracts the shell and script from a command, regardless of platform
 pub fn extract_shell_command(command: &[String]) -> Option<(&str, &str)> {
-    extract_bash_command(command).or_else(|| extract_powershell_command(command))
+    extract_bash_command(command)
+        .or_else(|| extract_fish_command(command))
+        .or_else(|| extract_nushell_command(command))
+        .or_else(|| extract_powershell_command(command))
 }
 
 /// DO NOT REVIEW THIS CODE BY HAND
@@ -322,6 +329,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fish_lc_supports_cat() {
+        let inner = "cat README.md";
+        assert_parsed(
+            &vec_str(&["fish", "-lc", inner]),
+            vec![ParsedCommand::Read {
+                cmd: inner.to_string(),
+                name: "README.md".to_string(),
+                path: PathBuf::from("README.md"),
+            }],
+        );
+    }
+
+    #[test]
+    fn nushell_login_supports_cat() {
+        let inner = "cat README.md";
+        assert_parsed(
+            &vec_str(&["nu", "-l", "-c", inner]),
+            vec![ParsedCommand::Read {
+                cmd: inner.to_string(),
+                name: "README.md".to_string(),
+                path: PathBuf::from("README.md"),
+            }],
+        );
+    }
+
+    #[test]
+    fn nushell_pipeline_with_closure_is_unknown() {
+        let inner = "ls | each { |it| $it.name }";
+        assert_parsed(
+            &vec_str(&["nu", "-c", inner]),
+            vec![ParsedCommand::Unknown {
+                cmd: inner.to_string(),
+            }],
+        );
+    }
+
     #[test]
     fn supports_bat() {
         let inner = "bat --theme TwoDark README.md";
@@ -1816,11 +1860,22 @@ fn parse_find_query_and_path(tail: &[String]) -> (Option<String>, Option<String>
 }
 
 fn parse_shell_lc_commands(original: &[String]) -> Option<Vec<ParsedCommand>> {
-    // Only handle bash/zsh here; PowerShell is stripped separately without bash parsing.
-    let (_, script) = extract_bash_command(original)?;
+    // Handle bash/zsh/sh plus fish and Nushell here; PowerShell is stripped separately.
+    let (script, all_commands) = if let Some((_, script)) = extract_bash_command(original) {
+        let all_commands = try_parse_shell(script)
+            .and_then(|tree| try_parse_word_only_commands_sequence(&tree, script));
+        (script, all_commands)
+    } else if let Some((_, script)) = extract_fish_command(original) {
+        (script, try_parse_fish_word_only_commands_sequence(script))
+    } else {
+        let (_, script) = extract_nushell_command(original)?;
+        (
+            script,
+            try_parse_nushell_word_only_commands_sequence(script),
+        )
+    };
 
-    if let Some(tree) = try_parse_shell(script)
-        && let Some(all_commands) = try_parse_word_only_commands_sequence(&tree, script)
+    if let Some(all_commands) = all_commands
         && !all_commands.is_empty()
     {
         let script_tokens = shlex_split(script).unwrap_or_else(|| vec![script.to_string()]);
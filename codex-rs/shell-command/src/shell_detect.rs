@@ -8,6 +8,8 @@ pub(crate) enum ShellType {
     PowerShell,
     Sh,
     Cmd,
+    Fish,
+    Nushell,
 }
 
 pub(crate) fn detect_shell_type(shell_path: &PathBuf) -> Option<ShellType> {
@@ -18,6 +20,9 @@ pub(crate) fn detect_shell_type(shell_path: &PathBuf) -> Option<ShellType> {
         Some("bash") => Some(ShellType::Bash),
         Some("pwsh") => Some(ShellType::PowerShell),
         Some("powershell") => Some(ShellType::PowerShell),
+        Some("fish") => Some(ShellType::Fish),
+        Some("nu") => Some(ShellType::Nushell),
+        Some("nushell") => Some(ShellType::Nushell),
         _ => {
             let shell_name = shell_path.file_stem();
             if let Some(shell_name) = shell_name {
@@ -0,0 +1,34 @@
+CREATE TABLE thread_messages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    thread_id TEXT NOT NULL,
+    turn_id TEXT,
+    role TEXT NOT NULL,
+    content TEXT NOT NULL,
+    FOREIGN KEY(thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_thread_messages_thread ON thread_messages(thread_id);
+
+CREATE VIRTUAL TABLE thread_messages_fts USING fts5(
+    content,
+    content = 'thread_messages',
+    content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER thread_messages_ai AFTER INSERT ON thread_messages BEGIN
+    INSERT INTO thread_messages_fts(rowid, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER thread_messages_ad AFTER DELETE ON thread_messages BEGIN
+    INSERT INTO thread_messages_fts(thread_messages_fts, rowid, content)
+    VALUES ('delete', old.id, old.content);
+END;
+
+-- Rollout items reach the index in incremental batches, so remember the turn
+-- that was open at the end of the previous batch for each thread.
+CREATE TABLE thread_search_cursor (
+    thread_id TEXT PRIMARY KEY,
+    turn_id TEXT,
+    FOREIGN KEY(thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
@@ -0,0 +1,29 @@
+-- Token usage per turn and model. `turn_id` and `model` are empty strings when
+-- the rollout did not record them.
+CREATE TABLE thread_token_usage (
+    thread_id TEXT NOT NULL,
+    turn_id TEXT NOT NULL DEFAULT '',
+    model TEXT NOT NULL DEFAULT '',
+    model_provider TEXT NOT NULL,
+    parent_thread_id TEXT,
+    recorded_at INTEGER NOT NULL,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    cached_input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    reasoning_output_tokens INTEGER NOT NULL DEFAULT 0,
+    total_tokens INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (thread_id, turn_id, model),
+    FOREIGN KEY(thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_thread_token_usage_recorded_at ON thread_token_usage(recorded_at);
+
+-- Rollout items reach the ledger in incremental batches, so remember the open
+-- turn, the active model and the cumulative total already recorded per thread.
+CREATE TABLE thread_token_usage_cursor (
+    thread_id TEXT PRIMARY KEY,
+    turn_id TEXT NOT NULL DEFAULT '',
+    model TEXT NOT NULL DEFAULT '',
+    total_tokens INTEGER NOT NULL DEFAULT 0,
+    FOREIGN KEY(thread_id) REFERENCES threads(id) ON DELETE CASCADE
+);
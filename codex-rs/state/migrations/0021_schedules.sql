@@ -0,0 +1,31 @@
+CREATE TABLE schedules (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    cron TEXT NOT NULL,
+    prompt TEXT NOT NULL,
+    cwd TEXT NOT NULL,
+    profile TEXT,
+    sandbox_mode TEXT,
+    thread_mode TEXT NOT NULL,
+    thread_id TEXT,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    next_run_at INTEGER,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+CREATE TABLE schedule_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    schedule_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    thread_id TEXT,
+    exit_code INTEGER,
+    error TEXT,
+    scheduled_for INTEGER NOT NULL,
+    started_at INTEGER NOT NULL,
+    finished_at INTEGER,
+    FOREIGN KEY(schedule_id) REFERENCES schedules(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_schedules_next_run_at ON schedules(enabled, next_run_at);
+CREATE INDEX idx_schedule_runs_schedule ON schedule_runs(schedule_id, id DESC);
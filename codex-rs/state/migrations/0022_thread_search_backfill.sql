@@ -0,0 +1,9 @@
+-- Threads recorded before 0019_thread_search are not in the search index yet.
+-- Re-run the rollout backfill from the start so they get indexed; indexing is
+-- idempotent, so threads that were already indexed are left unchanged.
+UPDATE backfill_state
+SET
+    status = 'pending',
+    last_watermark = NULL,
+    updated_at = CAST(strftime('%s', 'now') AS INTEGER)
+WHERE id = 1;
@@ -0,0 +1,16 @@
+-- Fork detection looks up usage rows by turn across threads.
+CREATE INDEX idx_thread_token_usage_turn_id ON thread_token_usage(turn_id);
+
+-- Threads recorded before 0020_token_usage have no usage yet, and rows written
+-- by earlier backfills were dated with the thread's last update rather than
+-- with their turn. Rebuild the ledger from the rollouts: clear it and re-run
+-- the rollout backfill from the start.
+DELETE FROM thread_token_usage;
+DELETE FROM thread_token_usage_cursor;
+
+UPDATE backfill_state
+SET
+    status = 'pending',
+    last_watermark = NULL,
+    updated_at = CAST(strftime('%s', 'now') AS INTEGER)
+WHERE id = 1;
@@ -0,0 +1,303 @@
+//! Minimal cron expression support for scheduled runs.
+//!
+//! Expressions use the classic five fields (`minute hour day-of-month month
+//! day-of-week`) with `*`, lists (`1,15`), ranges (`1-5`) and steps (`*/10`,
+//! `0-30/5`). Month and weekday names (`jan`, `mon`) and the `@hourly`,
+//! `@daily`/`@midnight`, `@weekly`, `@monthly` and `@yearly`/`@annually`
+//! shorthands are accepted as well. As in Vixie cron, when both the
+//! day-of-month and day-of-week fields are restricted a day matches if either
+//! field does.
+
+use chrono::DateTime;
+use chrono::Datelike;
+use chrono::Duration;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::TimeZone;
+use chrono::Timelike;
+use std::fmt;
+use std::str::FromStr;
+
+/// Upper bound on the candidate times examined by [`CronSchedule::next_after`].
+/// Enough for any satisfiable expression (e.g. `0 0 29 2 *` in a leap-year gap).
+const MAX_SEARCH_STEPS: usize = 100_000;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_restricted: bool,
+    day_of_week_restricted: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronParseError(String);
+
+impl fmt::Display for CronParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid cron expression: {}", self.0)
+    }
+}
+
+impl std::error::Error for CronParseError {}
+
+impl CronSchedule {
+    pub fn parse(expression: &str) -> Result<Self, CronParseError> {
+        let trimmed = expression.trim();
+        let expanded = match trimmed.to_ascii_lowercase().as_str() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other if other.starts_with('@') => {
+                return Err(CronParseError(format!("unknown shorthand `{trimmed}`")));
+            }
+            _ => trimmed,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            return Err(CronParseError(format!(
+                "expected 5 fields (minute hour day-of-month month day-of-week), got {}",
+                fields.len()
+            )));
+        };
+        let mut days_of_week = parse_field(day_of_week, 0, 7, &WEEKDAY_NAMES, "day-of-week")?;
+        // Both 0 and 7 mean Sunday.
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+        Ok(Self {
+            expression: trimmed.to_string(),
+            minutes: parse_field(minute, 0, 59, &[], "minute")?,
+            hours: parse_field(hour, 0, 23, &[], "hour")?,
+            days_of_month: parse_field(day_of_month, 1, 31, &[], "day-of-month")?,
+            months: parse_field(month, 1, 12, &MONTH_NAMES, "month")?,
+            days_of_week,
+            day_of_month_restricted: !day_of_month.starts_with('*'),
+            day_of_week_restricted: !day_of_week.starts_with('*'),
+        })
+    }
+
+    /// The expression as originally written.
+    pub fn as_str(&self) -> &str {
+        &self.expression
+    }
+
+    /// Returns the first time strictly after `after` that matches the
+    /// expression, evaluated in `after`'s time zone. Local times skipped by a
+    /// DST transition never match; repeated ones match on their first
+    /// occurrence.
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let timezone = after.timezone();
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let mut candidate = start;
+        for _ in 0..MAX_SEARCH_STEPS {
+            if !bit_set(self.months, candidate.month()) {
+                candidate = first_of_next_month(candidate)?;
+                continue;
+            }
+            if !self.day_matches(candidate.date()) {
+                candidate = candidate.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !bit_set(self.hours, candidate.hour()) {
+                candidate = candidate.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !bit_set(self.minutes, candidate.minute()) {
+                candidate += Duration::minutes(1);
+                continue;
+            }
+            if let Some(resolved) = timezone.from_local_datetime(&candidate).earliest()
+                && resolved > *after
+            {
+                return Some(resolved);
+            }
+            candidate += Duration::minutes(1);
+        }
+        None
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day_of_month = bit_set(self.days_of_month, date.day());
+        let day_of_week = bit_set(self.days_of_week, date.weekday().num_days_from_sunday());
+        match (self.day_of_month_restricted, self.day_of_week_restricted) {
+            (true, true) => day_of_month || day_of_week,
+            (true, false) => day_of_month,
+            (false, true) => day_of_week,
+            (false, false) => true,
+        }
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = CronParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+fn bit_set(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+fn first_of_next_month(value: NaiveDateTime) -> Option<NaiveDateTime> {
+    let (year, month) = if value.month() == 12 {
+        (value.year() + 1, 1)
+    } else {
+        (value.year(), value.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)
+}
+
+/// Parse one field into a bitmask of allowed values.
+fn parse_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    names: &[&str],
+    label: &str,
+) -> Result<u64, CronParseError> {
+    let invalid = |detail: &str| CronParseError(format!("{label} field `{field}`: {detail}"));
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| invalid("step must be a positive number"))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_value(start, min, names).ok_or_else(|| invalid("bad range start"))?,
+                parse_value(end, min, names).ok_or_else(|| invalid("bad range end"))?,
+            )
+        } else {
+            let value = parse_value(range, min, names).ok_or_else(|| invalid("bad value"))?;
+            // `5/15` means "from 5 to the end in steps of 15".
+            let end = if step > 1 { max } else { value };
+            (value, end)
+        };
+        if start < min || end > max || start > end {
+            return Err(invalid(&format!("values must be within {min}-{max}")));
+        }
+        let mut value = start;
+        while value <= end {
+            mask |= 1 << value;
+            value += step;
+        }
+    }
+    Ok(mask)
+}
+
+fn parse_value(value: &str, min: u32, names: &[&str]) -> Option<u32> {
+    if let Ok(number) = value.parse::<u32>() {
+        return Some(number);
+    }
+    let lower = value.to_ascii_lowercase();
+    names
+        .iter()
+        .position(|name| *name == lower)
+        .and_then(|index| u32::try_from(index).ok())
+        .map(|index| index + min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use pretty_assertions::assert_eq;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("valid timestamp")
+            .with_timezone(&Utc)
+    }
+
+    fn next(expression: &str, after: &str) -> String {
+        CronSchedule::parse(expression)
+            .expect("valid expression")
+            .next_after(&utc(after))
+            .expect("next occurrence")
+            .to_rfc3339()
+    }
+
+    #[test]
+    fn computes_next_occurrence() {
+        assert_eq!(
+            next("0 2 * * *", "2026-03-10T01:59:30Z"),
+            "2026-03-10T02:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 2 * * *", "2026-03-10T02:00:00Z"),
+            "2026-03-11T02:00:00+00:00"
+        );
+        assert_eq!(
+            next("*/15 9-17 * * mon-fri", "2026-03-13T17:50:00Z"),
+            "2026-03-16T09:00:00+00:00"
+        );
+        assert_eq!(
+            next("@weekly", "2026-03-10T12:00:00Z"),
+            "2026-03-15T00:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 0 29 feb *", "2026-03-01T00:00:00Z"),
+            "2028-02-29T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn restricted_day_fields_match_either() {
+        // The 1st of the month or any Monday.
+        assert_eq!(
+            next("0 0 1 * 1", "2026-03-10T00:00:00Z"),
+            "2026-03-16T00:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 0 1 * 7", "2026-03-23T00:00:00Z"),
+            "2026-03-29T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expression in [
+            "",
+            "* * * *",
+            "60 * * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "@often",
+        ] {
+            assert!(
+                CronSchedule::parse(expression).is_err(),
+                "{expression:?} should be rejected"
+            );
+        }
+    }
+}
@@ -40,6 +40,30 @@ pub fn rollout_item_affects_thread_metadata(item: &RolloutItem) -> bool {
     }
 }
 
+/// Return whether this rollout item feeds the thread full-text search index.
+pub fn rollout_item_affects_thread_search(item: &RolloutItem) -> bool {
+    match item {
+        RolloutItem::TurnContext(turn_ctx) => turn_ctx.turn_id.is_some(),
+        RolloutItem::EventMsg(
+            EventMsg::TurnStarted(_) | EventMsg::UserMessage(_) | EventMsg::AgentMessage(_),
+        ) => true,
+        RolloutItem::SessionMeta(_)
+        | RolloutItem::EventMsg(_)
+        | RolloutItem::ResponseItem(_)
+        | RolloutItem::Compacted(_) => false,
+    }
+}
+
+/// Return the `(role, text)` pair to index for a user or agent message event.
+pub(crate) fn searchable_message(event: &EventMsg) -> Option<(&'static str, &str)> {
+    let (role, text) = match event {
+        EventMsg::UserMessage(user) => ("user", strip_user_message_prefix(user.message.as_str())),
+        EventMsg::AgentMessage(agent) => ("assistant", agent.message.trim()),
+        _ => return None,
+    };
+    (!text.is_empty()).then_some((role, text))
+}
+
 fn apply_session_meta_from_item(metadata: &mut ThreadMetadata, meta_line: &SessionMetaLine) {
     if metadata.id != meta_line.meta.id {
         // Ignore session_meta lines that don't match the canonical thread ID,
@@ -4,6 +4,7 @@
 //! from JSONL rollouts and mirrors it into a local SQLite database. Backfill
 //! orchestration and rollout scanning live in `codex-core`.
 
+mod cron;
 mod extract;
 pub mod log_db;
 mod migrations;
@@ -11,6 +12,8 @@ mod model;
 mod paths;
 mod runtime;
 
+pub use cron::CronParseError;
+pub use cron::CronSchedule;
 pub use model::LogEntry;
 pub use model::LogQuery;
 pub use model::LogRow;
@@ -24,6 +27,7 @@ pub use runtime::StateRuntime;
 /// Most consumers should prefer [`StateRuntime`].
 pub use extract::apply_rollout_item;
 pub use extract::rollout_item_affects_thread_metadata;
+pub use extract::rollout_item_affects_thread_search;
 pub use model::AgentJob;
 pub use model::AgentJobCreateParams;
 pub use model::AgentJobItem;
@@ -36,6 +40,12 @@ pub use model::BackfillState;
 pub use model::BackfillStats;
 pub use model::BackfillStatus;
 pub use model::ExtractionOutcome;
+pub use model::Schedule;
+pub use model::ScheduleCreateParams;
+pub use model::ScheduleRun;
+pub use model::ScheduleRunOutcome;
+pub use model::ScheduleRunStatus;
+pub use model::ScheduleThreadMode;
 pub use model::SortKey;
 pub use model::Stage1JobClaim;
 pub use model::Stage1JobClaimOutcome;
@@ -44,7 +54,11 @@ pub use model::Stage1OutputRef;
 pub use model::Stage1StartupClaimParams;
 pub use model::ThreadMetadata;
 pub use model::ThreadMetadataBuilder;
+pub use model::ThreadSearchHit;
 pub use model::ThreadsPage;
+pub use model::TokenUsageGroupBy;
+pub use model::TokenUsageSummary;
+pub use model::TokenUsageSummaryQuery;
 pub use runtime::logs_db_filename;
 pub use runtime::logs_db_path;
 pub use runtime::state_db_filename;
@@ -2,7 +2,10 @@ mod agent_job;
 mod backfill_state;
 mod log;
 mod memories;
+mod schedule;
 mod thread_metadata;
+mod thread_search;
+mod token_usage;
 
 pub use agent_job::AgentJob;
 pub use agent_job::AgentJobCreateParams;
@@ -23,6 +26,12 @@ pub use memories::Stage1JobClaimOutcome;
 pub use memories::Stage1Output;
 pub use memories::Stage1OutputRef;
 pub use memories::Stage1StartupClaimParams;
+pub use schedule::Schedule;
+pub use schedule::ScheduleCreateParams;
+pub use schedule::ScheduleRun;
+pub use schedule::ScheduleRunOutcome;
+pub use schedule::ScheduleRunStatus;
+pub use schedule::ScheduleThreadMode;
 pub use thread_metadata::Anchor;
 pub use thread_metadata::BackfillStats;
 pub use thread_metadata::ExtractionOutcome;
@@ -30,11 +39,17 @@ pub use thread_metadata::SortKey;
 pub use thread_metadata::ThreadMetadata;
 pub use thread_metadata::ThreadMetadataBuilder;
 pub use thread_metadata::ThreadsPage;
+pub use thread_search::ThreadSearchHit;
+pub use token_usage::TokenUsageGroupBy;
+pub use token_usage::TokenUsageSummary;
+pub use token_usage::TokenUsageSummaryQuery;
 
 pub(crate) use agent_job::AgentJobItemRow;
 pub(crate) use agent_job::AgentJobRow;
 pub(crate) use memories::Stage1OutputRow;
 pub(crate) use memories::stage1_output_ref_from_parts;
+pub(crate) use schedule::ScheduleRow;
+pub(crate) use schedule::ScheduleRunRow;
 pub(crate) use thread_metadata::ThreadRow;
 pub(crate) use thread_metadata::anchor_from_item;
 pub(crate) use thread_metadata::datetime_to_epoch_seconds;
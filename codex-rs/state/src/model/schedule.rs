@@ -0,0 +1,216 @@
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+
+/// Which thread a scheduled run continues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleThreadMode {
+    /// Every run starts a fresh thread.
+    New,
+    /// Every run resumes the thread of the previous run (or the thread the
+    /// schedule was created with), starting a new one only the first time.
+    Resume,
+}
+
+impl ScheduleThreadMode {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ScheduleThreadMode::New => "new",
+            ScheduleThreadMode::Resume => "resume",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "new" => Ok(Self::New),
+            "resume" => Ok(Self::Resume),
+            _ => Err(anyhow::anyhow!("invalid schedule thread mode: {value}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl ScheduleRunStatus {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ScheduleRunStatus::Running => "running",
+            ScheduleRunStatus::Succeeded => "succeeded",
+            ScheduleRunStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "running" => Ok(Self::Running),
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            _ => Err(anyhow::anyhow!("invalid schedule run status: {value}")),
+        }
+    }
+}
+
+/// A recurring agent run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    pub id: String,
+    pub name: String,
+    /// Cron expression, evaluated in the local time zone.
+    pub cron: String,
+    pub prompt: String,
+    pub cwd: String,
+    /// Config profile the run is started with.
+    pub profile: Option<String>,
+    /// Sandbox mode override (`read-only`, `workspace-write`, ...).
+    pub sandbox_mode: Option<String>,
+    pub thread_mode: ScheduleThreadMode,
+    /// Thread the next `resume` run continues.
+    pub thread_id: Option<String>,
+    pub enabled: bool,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Schedule {
+    /// Name used when a schedule is created without one: the first line of
+    /// the prompt, shortened to keep listings readable.
+    pub fn default_name(prompt: &str) -> String {
+        const MAX_CHARS: usize = 60;
+        let first_line = prompt.trim().lines().next().unwrap_or_default();
+        if first_line.chars().count() <= MAX_CHARS {
+            first_line.to_string()
+        } else {
+            let truncated: String = first_line.chars().take(MAX_CHARS - 3).collect();
+            format!("{truncated}...")
+        }
+    }
+}
+
+/// One execution of a [`Schedule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleRun {
+    pub id: i64,
+    pub schedule_id: String,
+    pub status: ScheduleRunStatus,
+    pub thread_id: Option<String>,
+    /// Exit code of the `codex exec` process, when it exited normally.
+    pub exit_code: Option<i32>,
+    pub error: Option<String>,
+    /// The cron slot this run was started for.
+    pub scheduled_for: DateTime<Utc>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ScheduleCreateParams {
+    pub id: String,
+    pub name: String,
+    pub cron: String,
+    pub prompt: String,
+    pub cwd: String,
+    pub profile: Option<String>,
+    pub sandbox_mode: Option<String>,
+    pub thread_mode: ScheduleThreadMode,
+    pub thread_id: Option<String>,
+}
+
+/// Result of a finished [`ScheduleRun`].
+#[derive(Debug, Clone)]
+pub struct ScheduleRunOutcome {
+    pub status: ScheduleRunStatus,
+    pub thread_id: Option<String>,
+    pub exit_code: Option<i32>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct ScheduleRow {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) cron: String,
+    pub(crate) prompt: String,
+    pub(crate) cwd: String,
+    pub(crate) profile: Option<String>,
+    pub(crate) sandbox_mode: Option<String>,
+    pub(crate) thread_mode: String,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) enabled: i64,
+    pub(crate) next_run_at: Option<i64>,
+    pub(crate) created_at: i64,
+    pub(crate) updated_at: i64,
+}
+
+impl TryFrom<ScheduleRow> for Schedule {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ScheduleRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id,
+            name: value.name,
+            cron: value.cron,
+            prompt: value.prompt,
+            cwd: value.cwd,
+            profile: value.profile,
+            sandbox_mode: value.sandbox_mode,
+            thread_mode: ScheduleThreadMode::parse(value.thread_mode.as_str())?,
+            thread_id: value.thread_id,
+            enabled: value.enabled != 0,
+            next_run_at: value
+                .next_run_at
+                .map(epoch_seconds_to_datetime)
+                .transpose()?,
+            created_at: epoch_seconds_to_datetime(value.created_at)?,
+            updated_at: epoch_seconds_to_datetime(value.updated_at)?,
+        })
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct ScheduleRunRow {
+    pub(crate) id: i64,
+    pub(crate) schedule_id: String,
+    pub(crate) status: String,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) exit_code: Option<i64>,
+    pub(crate) error: Option<String>,
+    pub(crate) scheduled_for: i64,
+    pub(crate) started_at: i64,
+    pub(crate) finished_at: Option<i64>,
+}
+
+impl TryFrom<ScheduleRunRow> for ScheduleRun {
+    type Error = anyhow::Error;
+
+    fn try_from(value: ScheduleRunRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: value.id,
+            schedule_id: value.schedule_id,
+            status: ScheduleRunStatus::parse(value.status.as_str())?,
+            thread_id: value.thread_id,
+            exit_code: value
+                .exit_code
+                .map(i32::try_from)
+                .transpose()
+                .map_err(|_| anyhow::anyhow!("invalid exit_code value"))?,
+            error: value.error,
+            scheduled_for: epoch_seconds_to_datetime(value.scheduled_for)?,
+            started_at: epoch_seconds_to_datetime(value.started_at)?,
+            finished_at: value
+                .finished_at
+                .map(epoch_seconds_to_datetime)
+                .transpose()?,
+        })
+    }
+}
+
+fn epoch_seconds_to_datetime(secs: i64) -> Result<DateTime<Utc>> {
+    DateTime::<Utc>::from_timestamp(secs, 0)
+        .ok_or_else(|| anyhow::anyhow!("invalid unix timestamp: {secs}"))
+}
@@ -0,0 +1,16 @@
+use codex_protocol::ThreadId;
+
+/// A single full-text search match within a stored thread.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadSearchHit {
+    /// The thread containing the matching message.
+    pub thread_id: ThreadId,
+    /// The turn the matching message belongs to, when known.
+    pub turn_id: Option<String>,
+    /// Either `user` or `assistant`.
+    pub role: String,
+    /// Excerpt of the matching message with matched terms wrapped in `[` and `]`.
+    pub snippet: String,
+    /// BM25 rank of the match; lower is more relevant.
+    pub rank: f64,
+}
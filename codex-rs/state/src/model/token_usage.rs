@@ -0,0 +1,43 @@
+/// Dimension used to group rows of a [`TokenUsageSummaryQuery`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenUsageGroupBy {
+    /// UTC calendar day the usage was recorded on.
+    Day,
+    /// Git origin URL of the thread, when known.
+    Repository,
+    /// Model that produced the usage.
+    Model,
+}
+
+/// Filters and grouping for [`crate::StateRuntime::token_usage_summary`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenUsageSummaryQuery {
+    /// Grouping dimensions, in output order. Empty means a single total row.
+    pub group_by: Vec<TokenUsageGroupBy>,
+    /// Inclusive lower bound on the recording time, in Unix seconds.
+    pub since: Option<i64>,
+    /// Exclusive upper bound on the recording time, in Unix seconds.
+    pub until: Option<i64>,
+}
+
+/// Aggregated token usage for one group of a [`TokenUsageSummaryQuery`].
+///
+/// Grouping fields are `None` when the dimension was not requested or is unknown.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenUsageSummary {
+    /// UTC day formatted as `YYYY-MM-DD`.
+    pub day: Option<String>,
+    /// Git origin URL of the threads in this group.
+    pub repository: Option<String>,
+    /// Model slug.
+    pub model: Option<String>,
+    /// Number of distinct turns with recorded usage.
+    pub turns: i64,
+    /// Number of distinct threads with recorded usage.
+    pub threads: i64,
+    pub input_tokens: i64,
+    pub cached_input_tokens: i64,
+    pub output_tokens: i64,
+    pub reasoning_output_tokens: i64,
+    pub total_tokens: i64,
+}
@@ -15,7 +15,11 @@ use crate::STATE_DB_VERSION;
 use crate::SortKey;
 use crate::ThreadMetadata;
 use crate::ThreadMetadataBuilder;
+use crate::ThreadSearchHit;
 use crate::ThreadsPage;
+use crate::TokenUsageGroupBy;
+use crate::TokenUsageSummary;
+use crate::TokenUsageSummaryQuery;
 use crate::apply_rollout_item;
 use crate::migrations::LOGS_MIGRATOR;
 use crate::migrations::STATE_MIGRATOR;
@@ -53,9 +57,12 @@ mod agent_jobs;
 mod backfill;
 mod logs;
 mod memories;
+mod schedules;
+mod search;
 #[cfg(test)]
 mod test_support;
 mod threads;
+mod usage;
 
 // "Partition" is the retention bucket we cap at 10 MiB:
 // - one bucket per non-null thread_id
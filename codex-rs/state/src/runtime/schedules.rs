@@ -0,0 +1,422 @@
+use super::*;
+use crate::CronSchedule;
+use crate::Schedule;
+use crate::ScheduleCreateParams;
+use crate::ScheduleRun;
+use crate::ScheduleRunOutcome;
+use crate::ScheduleRunStatus;
+use crate::ScheduleThreadMode;
+use crate::model::ScheduleRow;
+use crate::model::ScheduleRunRow;
+use chrono::Local;
+
+const SCHEDULE_COLUMNS: &str = r#"
+    id,
+    name,
+    cron,
+    prompt,
+    cwd,
+    profile,
+    sandbox_mode,
+    thread_mode,
+    thread_id,
+    enabled,
+    next_run_at,
+    created_at,
+    updated_at
+"#;
+
+const SCHEDULE_RUN_COLUMNS: &str = r#"
+    id,
+    schedule_id,
+    status,
+    thread_id,
+    exit_code,
+    error,
+    scheduled_for,
+    started_at,
+    finished_at
+"#;
+
+impl StateRuntime {
+    /// Store a new schedule and compute its first run time.
+    pub async fn create_schedule(&self, params: &ScheduleCreateParams) -> anyhow::Result<Schedule> {
+        let cron = CronSchedule::parse(params.cron.as_str())?;
+        let now = Utc::now();
+        let next_run_at = next_run_at(&cron, now);
+        sqlx::query(
+            r#"
+INSERT INTO schedules (
+    id,
+    name,
+    cron,
+    prompt,
+    cwd,
+    profile,
+    sandbox_mode,
+    thread_mode,
+    thread_id,
+    enabled,
+    next_run_at,
+    created_at,
+    updated_at
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?, ?)
+            "#,
+        )
+        .bind(params.id.as_str())
+        .bind(params.name.as_str())
+        .bind(cron.as_str())
+        .bind(params.prompt.as_str())
+        .bind(params.cwd.as_str())
+        .bind(params.profile.as_deref())
+        .bind(params.sandbox_mode.as_deref())
+        .bind(params.thread_mode.as_str())
+        .bind(params.thread_id.as_deref())
+        .bind(next_run_at)
+        .bind(now.timestamp())
+        .bind(now.timestamp())
+        .execute(self.pool.as_ref())
+        .await?;
+
+        let schedule_id = params.id.as_str();
+        self.get_schedule(schedule_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("failed to load created schedule {schedule_id}"))
+    }
+
+    pub async fn get_schedule(&self, schedule_id: &str) -> anyhow::Result<Option<Schedule>> {
+        let row = sqlx::query_as::<_, ScheduleRow>(&format!(
+            "SELECT {SCHEDULE_COLUMNS} FROM schedules WHERE id = ?"
+        ))
+        .bind(schedule_id)
+        .fetch_optional(self.pool.as_ref())
+        .await?;
+        row.map(Schedule::try_from).transpose()
+    }
+
+    /// List all schedules, oldest first.
+    pub async fn list_schedules(&self) -> anyhow::Result<Vec<Schedule>> {
+        let rows = sqlx::query_as::<_, ScheduleRow>(&format!(
+            "SELECT {SCHEDULE_COLUMNS} FROM schedules ORDER BY created_at ASC, id ASC"
+        ))
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter().map(Schedule::try_from).collect()
+    }
+
+    /// Delete a schedule and its run history. Returns `false` if it did not exist.
+    pub async fn delete_schedule(&self, schedule_id: &str) -> anyhow::Result<bool> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM schedule_runs WHERE schedule_id = ?")
+            .bind(schedule_id)
+            .execute(&mut *tx)
+            .await?;
+        let result = sqlx::query("DELETE FROM schedules WHERE id = ?")
+            .bind(schedule_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Claim the enabled schedules whose next run time is at or before `now`.
+    ///
+    /// Each claimed schedule has its next run time advanced past `now`, so
+    /// slots missed while no scheduler was running collapse into a single run.
+    /// Claims are conditional on the stored run time, which keeps several
+    /// processes sharing one state DB from starting the same slot twice. The
+    /// returned schedules carry the claimed slot in `next_run_at`.
+    pub async fn claim_due_schedules(&self, now: DateTime<Utc>) -> anyhow::Result<Vec<Schedule>> {
+        let rows = sqlx::query_as::<_, ScheduleRow>(&format!(
+            "SELECT {SCHEDULE_COLUMNS} FROM schedules WHERE enabled = 1 AND next_run_at <= ? ORDER BY next_run_at ASC"
+        ))
+        .bind(now.timestamp())
+        .fetch_all(self.pool.as_ref())
+        .await?;
+
+        let mut claimed = Vec::new();
+        for row in rows {
+            let schedule = Schedule::try_from(row)?;
+            let next = match CronSchedule::parse(schedule.cron.as_str()) {
+                Ok(cron) => next_run_at(&cron, now),
+                Err(err) => {
+                    warn!(
+                        "disabling schedule {} with invalid cron: {err}",
+                        schedule.id
+                    );
+                    None
+                }
+            };
+            let result = sqlx::query(
+                "UPDATE schedules SET next_run_at = ?, updated_at = ? WHERE id = ? AND next_run_at = ?",
+            )
+            .bind(next)
+            .bind(now.timestamp())
+            .bind(schedule.id.as_str())
+            .bind(schedule.next_run_at.map(|at| at.timestamp()))
+            .execute(self.pool.as_ref())
+            .await?;
+            if result.rows_affected() > 0 {
+                claimed.push(schedule);
+            }
+        }
+        Ok(claimed)
+    }
+
+    /// Record the start of a run for the slot `scheduled_for`.
+    pub async fn start_schedule_run(
+        &self,
+        schedule_id: &str,
+        scheduled_for: DateTime<Utc>,
+    ) -> anyhow::Result<ScheduleRun> {
+        let now = Utc::now().timestamp();
+        let row = sqlx::query_as::<_, ScheduleRunRow>(&format!(
+            r#"
+INSERT INTO schedule_runs (schedule_id, status, scheduled_for, started_at)
+VALUES (?, ?, ?, ?)
+RETURNING {SCHEDULE_RUN_COLUMNS}
+            "#
+        ))
+        .bind(schedule_id)
+        .bind(ScheduleRunStatus::Running.as_str())
+        .bind(scheduled_for.timestamp())
+        .bind(now)
+        .fetch_one(self.pool.as_ref())
+        .await?;
+        ScheduleRun::try_from(row)
+    }
+
+    /// Record the result of a run. For `resume` schedules the run's thread
+    /// becomes the one the next run continues.
+    pub async fn finish_schedule_run(
+        &self,
+        run_id: i64,
+        outcome: &ScheduleRunOutcome,
+    ) -> anyhow::Result<ScheduleRun> {
+        let now = Utc::now().timestamp();
+        let mut tx = self.pool.begin().await?;
+        let row = sqlx::query_as::<_, ScheduleRunRow>(&format!(
+            r#"
+UPDATE schedule_runs
+SET status = ?, thread_id = ?, exit_code = ?, error = ?, finished_at = ?
+WHERE id = ?
+RETURNING {SCHEDULE_RUN_COLUMNS}
+            "#
+        ))
+        .bind(outcome.status.as_str())
+        .bind(outcome.thread_id.as_deref())
+        .bind(outcome.exit_code)
+        .bind(outcome.error.as_deref())
+        .bind(now)
+        .bind(run_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if let Some(thread_id) = outcome.thread_id.as_deref() {
+            sqlx::query(
+                "UPDATE schedules SET thread_id = ?, updated_at = ? WHERE id = ? AND thread_mode = ?",
+            )
+            .bind(thread_id)
+            .bind(now)
+            .bind(row.schedule_id.as_str())
+            .bind(ScheduleThreadMode::Resume.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        ScheduleRun::try_from(row)
+    }
+
+    /// List the runs of a schedule, newest first.
+    pub async fn list_schedule_runs(
+        &self,
+        schedule_id: &str,
+        limit: Option<usize>,
+    ) -> anyhow::Result<Vec<ScheduleRun>> {
+        let mut builder = QueryBuilder::<Sqlite>::new(format!(
+            "SELECT {SCHEDULE_RUN_COLUMNS} FROM schedule_runs WHERE schedule_id = "
+        ));
+        builder.push_bind(schedule_id);
+        builder.push(" ORDER BY id DESC");
+        if let Some(limit) = limit {
+            builder.push(" LIMIT ");
+            builder.push_bind(i64::try_from(limit).unwrap_or(i64::MAX));
+        }
+        let rows = builder
+            .build_query_as::<ScheduleRunRow>()
+            .fetch_all(self.pool.as_ref())
+            .await?;
+        rows.into_iter().map(ScheduleRun::try_from).collect()
+    }
+}
+
+/// Next run time after `after`, with the cron expression evaluated in local time.
+fn next_run_at(cron: &CronSchedule, after: DateTime<Utc>) -> Option<i64> {
+    cron.next_after(&after.with_timezone(&Local))
+        .map(|next| next.timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::unique_temp_dir;
+    use pretty_assertions::assert_eq;
+
+    fn create_params(id: &str, thread_mode: ScheduleThreadMode) -> ScheduleCreateParams {
+        ScheduleCreateParams {
+            id: id.to_string(),
+            name: format!("{id} name"),
+            cron: "0 2 * * *".to_string(),
+            prompt: "triage new failing tests".to_string(),
+            cwd: "/tmp/repo".to_string(),
+            profile: Some("nightly".to_string()),
+            sandbox_mode: Some("workspace-write".to_string()),
+            thread_mode,
+            thread_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn claims_due_schedules_once_and_advances_next_run() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home, "test-provider".to_string())
+            .await
+            .expect("initialize runtime");
+
+        let schedule = runtime
+            .create_schedule(&create_params("nightly", ScheduleThreadMode::New))
+            .await
+            .expect("create schedule");
+        let next_run_at = schedule.next_run_at.expect("next run computed");
+        assert!(next_run_at > schedule.created_at);
+        assert!(
+            runtime
+                .create_schedule(&ScheduleCreateParams {
+                    cron: "not a cron".to_string(),
+                    ..create_params("broken", ScheduleThreadMode::New)
+                })
+                .await
+                .is_err()
+        );
+
+        let before = next_run_at - chrono::Duration::seconds(1);
+        assert_eq!(
+            runtime
+                .claim_due_schedules(before)
+                .await
+                .expect("claim before due"),
+            Vec::new()
+        );
+
+        let claimed = runtime
+            .claim_due_schedules(next_run_at)
+            .await
+            .expect("claim due");
+        assert_eq!(claimed.len(), 1);
+        assert_eq!(claimed[0].next_run_at, Some(next_run_at));
+        assert_eq!(
+            runtime
+                .claim_due_schedules(next_run_at)
+                .await
+                .expect("claim again"),
+            Vec::new()
+        );
+        let reloaded = runtime
+            .get_schedule("nightly")
+            .await
+            .expect("get schedule")
+            .expect("schedule exists");
+        assert!(reloaded.next_run_at.expect("next run") > next_run_at);
+    }
+
+    #[tokio::test]
+    async fn records_runs_and_tracks_resumed_thread() {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home, "test-provider".to_string())
+            .await
+            .expect("initialize runtime");
+        runtime
+            .create_schedule(&create_params("audit", ScheduleThreadMode::Resume))
+            .await
+            .expect("create schedule");
+        let scheduled_for = Utc::now();
+
+        let first = runtime
+            .start_schedule_run("audit", scheduled_for)
+            .await
+            .expect("start run");
+        assert_eq!(first.status, ScheduleRunStatus::Running);
+        runtime
+            .finish_schedule_run(
+                first.id,
+                &ScheduleRunOutcome {
+                    status: ScheduleRunStatus::Succeeded,
+                    thread_id: Some("thread-1".to_string()),
+                    exit_code: Some(0),
+                    error: None,
+                },
+            )
+            .await
+            .expect("finish run");
+        let second = runtime
+            .start_schedule_run("audit", scheduled_for)
+            .await
+            .expect("start second run");
+        let second = runtime
+            .finish_schedule_run(
+                second.id,
+                &ScheduleRunOutcome {
+                    status: ScheduleRunStatus::Failed,
+                    thread_id: None,
+                    exit_code: Some(1),
+                    error: Some("boom".to_string()),
+                },
+            )
+            .await
+            .expect("finish second run");
+        assert_eq!(second.exit_code, Some(1));
+        assert!(second.finished_at.is_some());
+
+        let runs = runtime
+            .list_schedule_runs("audit", None)
+            .await
+            .expect("list runs");
+        assert_eq!(
+            runs.iter()
+                .map(|run| (run.status, run.thread_id.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (ScheduleRunStatus::Failed, None),
+                (ScheduleRunStatus::Succeeded, Some("thread-1".to_string())),
+            ]
+        );
+        assert_eq!(
+            runtime
+                .list_schedule_runs("audit", Some(1))
+                .await
+                .expect("list limited runs")
+                .len(),
+            1
+        );
+        let schedule = runtime
+            .get_schedule("audit")
+            .await
+            .expect("get schedule")
+            .expect("schedule exists");
+        assert_eq!(schedule.thread_id.as_deref(), Some("thread-1"));
+
+        assert!(runtime.delete_schedule("audit").await.expect("delete"));
+        assert!(
+            !runtime
+                .delete_schedule("audit")
+                .await
+                .expect("delete again")
+        );
+        assert_eq!(
+            runtime
+                .list_schedule_runs("audit", None)
+                .await
+                .expect("list runs after delete"),
+            Vec::new()
+        );
+    }
+}
@@ -0,0 +1,421 @@
+use super::*;
+use crate::extract::searchable_message;
+use crate::rollout_item_affects_thread_search;
+use codex_protocol::protocol::EventMsg;
+
+impl StateRuntime {
+    /// Index user and assistant messages from rollout items for full-text search.
+    ///
+    /// Items usually arrive in small incremental batches, so the turn that is open at the
+    /// end of a batch is remembered and used for messages at the start of the next one.
+    /// Messages already indexed for the same turn are skipped, which makes replaying a
+    /// whole rollout (e.g. during backfill) safe.
+    pub async fn index_thread_messages(
+        &self,
+        thread_id: ThreadId,
+        items: &[RolloutItem],
+    ) -> anyhow::Result<()> {
+        if !items.iter().any(rollout_item_affects_thread_search) {
+            return Ok(());
+        }
+        let thread_id = thread_id.to_string();
+        let mut tx = self.pool.begin().await?;
+        let mut turn_id: Option<String> =
+            sqlx::query_scalar("SELECT turn_id FROM thread_search_cursor WHERE thread_id = ?")
+                .bind(thread_id.as_str())
+                .fetch_optional(&mut *tx)
+                .await?
+                .flatten();
+        for item in items {
+            let event = match item {
+                RolloutItem::TurnContext(turn_ctx) => {
+                    if let Some(id) = turn_ctx.turn_id.as_ref() {
+                        turn_id = Some(id.clone());
+                    }
+                    continue;
+                }
+                RolloutItem::EventMsg(event) => event,
+                RolloutItem::SessionMeta(_)
+                | RolloutItem::ResponseItem(_)
+                | RolloutItem::Compacted(_) => continue,
+            };
+            if let EventMsg::TurnStarted(started) = event {
+                turn_id = Some(started.turn_id.clone());
+                continue;
+            }
+            let Some((role, content)) = searchable_message(event) else {
+                continue;
+            };
+            sqlx::query(
+                r#"
+INSERT INTO thread_messages (thread_id, turn_id, role, content)
+SELECT ?, ?, ?, ?
+WHERE NOT EXISTS (
+    SELECT 1
+    FROM thread_messages
+    WHERE thread_id = ? AND turn_id IS ? AND role = ? AND content = ?
+)
+                "#,
+            )
+            .bind(thread_id.as_str())
+            .bind(turn_id.as_deref())
+            .bind(role)
+            .bind(content)
+            .bind(thread_id.as_str())
+            .bind(turn_id.as_deref())
+            .bind(role)
+            .bind(content)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query(
+            r#"
+INSERT INTO thread_search_cursor (thread_id, turn_id) VALUES (?, ?)
+ON CONFLICT(thread_id) DO UPDATE SET turn_id = excluded.turn_id
+            "#,
+        )
+        .bind(thread_id.as_str())
+        .bind(turn_id.as_deref())
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Search indexed user and assistant messages, best matches first.
+    ///
+    /// Every whitespace-separated term in `query` must match, either as a whole word or as
+    /// a word prefix. Archived threads are searched only when `archived_only` is set.
+    ///
+    /// Hits are grouped per thread before limiting: the `thread_limit` threads with the best
+    /// matching message are returned, each with at most `matches_per_thread` hits. Threads are
+    /// ordered by their best hit and the hits of a thread are contiguous and best-first.
+    pub async fn search_threads(
+        &self,
+        query: &str,
+        thread_limit: usize,
+        matches_per_thread: usize,
+        archived_only: bool,
+    ) -> anyhow::Result<Vec<ThreadSearchHit>> {
+        let Some(match_expression) = fts_match_expression(query) else {
+            return Ok(Vec::new());
+        };
+        let rows = sqlx::query(
+            r#"
+WITH hits AS (
+    SELECT
+        thread_messages.id AS message_id,
+        thread_messages.thread_id AS thread_id,
+        thread_messages.turn_id AS turn_id,
+        thread_messages.role AS role,
+        snippet(thread_messages_fts, 0, '[', ']', '...', 16) AS snippet,
+        bm25(thread_messages_fts) AS rank
+    FROM thread_messages_fts
+    JOIN thread_messages ON thread_messages.id = thread_messages_fts.rowid
+    JOIN threads ON threads.id = thread_messages.thread_id
+    WHERE thread_messages_fts MATCH ? AND threads.archived = ?
+),
+ranked_hits AS (
+    SELECT
+        hits.*,
+        ROW_NUMBER() OVER (
+            PARTITION BY thread_id ORDER BY rank ASC, message_id DESC
+        ) AS thread_hit_number
+    FROM hits
+),
+top_threads AS (
+    SELECT thread_id, rank AS best_rank, message_id AS best_message_id
+    FROM ranked_hits
+    WHERE thread_hit_number = 1
+    ORDER BY best_rank ASC, best_message_id DESC
+    LIMIT ?
+)
+SELECT
+    ranked_hits.thread_id AS thread_id,
+    ranked_hits.turn_id AS turn_id,
+    ranked_hits.role AS role,
+    ranked_hits.snippet AS snippet,
+    ranked_hits.rank AS rank
+FROM ranked_hits
+JOIN top_threads ON top_threads.thread_id = ranked_hits.thread_id
+WHERE ranked_hits.thread_hit_number <= ?
+ORDER BY
+    top_threads.best_rank ASC,
+    top_threads.best_message_id DESC,
+    ranked_hits.thread_hit_number ASC
+            "#,
+        )
+        .bind(match_expression)
+        .bind(archived_only)
+        .bind(i64::try_from(thread_limit).unwrap_or(i64::MAX))
+        .bind(i64::try_from(matches_per_thread).unwrap_or(i64::MAX))
+        .fetch_all(self.pool.as_ref())
+        .await?;
+        rows.into_iter()
+            .map(|row| {
+                let thread_id: String = row.try_get("thread_id")?;
+                Ok(ThreadSearchHit {
+                    thread_id: ThreadId::try_from(thread_id)?,
+                    turn_id: row.try_get("turn_id")?,
+                    role: row.try_get("role")?,
+                    snippet: row.try_get("snippet")?,
+                    rank: row.try_get("rank")?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Build an FTS5 query that matches every term of free-form user input as a prefix.
+///
+/// Terms are quoted so FTS5 operators and punctuation in the input are taken literally.
+fn fts_match_expression(query: &str) -> Option<String> {
+    let terms = query
+        .split_whitespace()
+        .filter(|term| term.chars().any(char::is_alphanumeric))
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::test_thread_metadata;
+    use crate::runtime::test_support::unique_temp_dir;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::TurnStartedEvent;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    fn turn_started(turn_id: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::TurnStarted(TurnStartedEvent {
+            turn_id: turn_id.to_string(),
+            model_context_window: None,
+            collaboration_mode_kind: Default::default(),
+        }))
+    }
+
+    fn user_message(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        }))
+    }
+
+    fn agent_message(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+            message: message.to_string(),
+            phase: None,
+        }))
+    }
+
+    async fn runtime_with_thread(thread_id: ThreadId) -> Arc<StateRuntime> {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string())
+            .await
+            .expect("state db should initialize");
+        runtime
+            .upsert_thread(&test_thread_metadata(
+                &codex_home,
+                thread_id,
+                codex_home.clone(),
+            ))
+            .await
+            .expect("thread upsert should succeed");
+        runtime
+    }
+
+    #[tokio::test]
+    async fn search_returns_ranked_snippets_with_turn_ids_across_batches() {
+        let thread_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000901").expect("valid thread id");
+        let runtime = runtime_with_thread(thread_id).await;
+
+        runtime
+            .index_thread_messages(
+                thread_id,
+                &[turn_started("turn-1"), user_message("refactor the parser")],
+            )
+            .await
+            .expect("first batch should index");
+        // The second batch has no turn marker and belongs to the turn left open above.
+        runtime
+            .index_thread_messages(
+                thread_id,
+                &[agent_message("The parser now handles nested tables.")],
+            )
+            .await
+            .expect("second batch should index");
+        runtime
+            .index_thread_messages(
+                thread_id,
+                &[turn_started("turn-2"), user_message("add docs for tables")],
+            )
+            .await
+            .expect("third batch should index");
+
+        let hits = runtime
+            .search_threads("pars", 10, 10, false)
+            .await
+            .expect("search should succeed");
+        let summary = hits
+            .iter()
+            .map(|hit| {
+                (
+                    hit.thread_id,
+                    hit.turn_id.as_deref(),
+                    hit.role.as_str(),
+                    hit.snippet.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (thread_id, Some("turn-1"), "user", "refactor the [parser]"),
+                (
+                    thread_id,
+                    Some("turn-1"),
+                    "assistant",
+                    "The [parser] now handles nested tables."
+                ),
+            ]
+        );
+        assert!(hits[0].rank <= hits[1].rank);
+
+        let hits = runtime
+            .search_threads("docs TABLES", 10, 10, false)
+            .await
+            .expect("search should succeed");
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].turn_id.as_deref(), Some("turn-2"));
+    }
+
+    #[tokio::test]
+    async fn replaying_items_does_not_duplicate_hits() {
+        let thread_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000902").expect("valid thread id");
+        let runtime = runtime_with_thread(thread_id).await;
+        let items = vec![turn_started("turn-1"), user_message("find the flaky test")];
+
+        for _ in 0..2 {
+            runtime
+                .index_thread_messages(thread_id, &items)
+                .await
+                .expect("indexing should succeed");
+        }
+
+        let hits = runtime
+            .search_threads("flaky", 10, 10, false)
+            .await
+            .expect("search should succeed");
+        assert_eq!(hits.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn search_skips_archived_and_deleted_threads() {
+        let thread_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000903").expect("valid thread id");
+        let runtime = runtime_with_thread(thread_id).await;
+        runtime
+            .index_thread_messages(thread_id, &[user_message("migrate the database")])
+            .await
+            .expect("indexing should succeed");
+
+        let mut metadata = runtime
+            .get_thread(thread_id)
+            .await
+            .expect("thread should load")
+            .expect("thread should exist");
+        metadata.archived_at = Some(metadata.updated_at);
+        runtime
+            .upsert_thread(&metadata)
+            .await
+            .expect("archive should succeed");
+        assert_eq!(
+            runtime
+                .search_threads("database", 10, 10, false)
+                .await
+                .expect("search should succeed"),
+            Vec::new()
+        );
+        assert_eq!(
+            runtime
+                .search_threads("database", 10, 10, true)
+                .await
+                .expect("search should succeed")
+                .len(),
+            1
+        );
+
+        runtime
+            .delete_thread(thread_id)
+            .await
+            .expect("delete should succeed");
+        assert_eq!(
+            runtime
+                .search_threads("database", 10, 10, true)
+                .await
+                .expect("search should succeed"),
+            Vec::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn search_limits_threads_not_hits() {
+        let busy_thread =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000904").expect("valid thread id");
+        let quiet_thread =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000905").expect("valid thread id");
+        let runtime = runtime_with_thread(busy_thread).await;
+        runtime
+            .upsert_thread(&test_thread_metadata(
+                runtime.codex_home(),
+                quiet_thread,
+                runtime.codex_home().to_path_buf(),
+            ))
+            .await
+            .expect("thread upsert should succeed");
+
+        // The busy thread has more matching messages than the limit, all ranked above the
+        // single match in the quiet thread.
+        let busy_items = (0..5)
+            .map(|idx| user_message(&format!("rollback rollback rollback {idx}")))
+            .collect::<Vec<_>>();
+        runtime
+            .index_thread_messages(busy_thread, &busy_items)
+            .await
+            .expect("indexing should succeed");
+        runtime
+            .index_thread_messages(
+                quiet_thread,
+                &[user_message(
+                    "plan the rollback of the release and everything else",
+                )],
+            )
+            .await
+            .expect("indexing should succeed");
+
+        let hits = runtime
+            .search_threads("rollback", 2, 3, false)
+            .await
+            .expect("search should succeed");
+        let threads = hits.iter().map(|hit| hit.thread_id).collect::<Vec<_>>();
+        assert_eq!(
+            threads,
+            vec![busy_thread, busy_thread, busy_thread, quiet_thread]
+        );
+    }
+
+    #[test]
+    fn fts_match_expression_quotes_terms() {
+        assert_eq!(
+            fts_match_expression(r#"foo "bar" OR -"#),
+            Some(r#""foo"* """bar"""* "OR"*"#.to_string())
+        );
+        assert_eq!(fts_match_expression("  - "), None);
+    }
+}
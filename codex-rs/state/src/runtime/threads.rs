@@ -495,7 +495,8 @@ ON CONFLICT(thread_id, position) DO NOTHING
         {
             return Err(err);
         }
-        Ok(())
+        self.record_token_usage(&metadata, items).await?;
+        self.index_thread_messages(builder.id, items).await
     }
 
     /// Mark a thread as archived using the underlying database.
@@ -0,0 +1,521 @@
+use super::*;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::SubAgentSource;
+
+impl StateRuntime {
+    /// Record per-turn token usage from rollout items in the usage ledger.
+    ///
+    /// `TokenCount` events carry both the usage of the last request and the cumulative usage
+    /// of the thread. The last usage is added to the row of the turn and model that are open
+    /// when the event is seen; events whose cumulative total is not above the total already
+    /// recorded are skipped, which makes replaying a whole rollout (e.g. during backfill)
+    /// safe. Turns that are already recorded under another thread (e.g. history copied into a
+    /// fork) are skipped as well so usage is only counted once.
+    ///
+    /// Turns are dated with the thread's `updated_at`, which is accurate for items that were
+    /// just appended; use [`Self::record_token_usage_at`] for items read back from a rollout.
+    pub async fn record_token_usage(
+        &self,
+        metadata: &ThreadMetadata,
+        items: &[RolloutItem],
+    ) -> anyhow::Result<()> {
+        self.record_token_usage_at(metadata, items, &[]).await
+    }
+
+    /// Like [`Self::record_token_usage`], but each turn is dated with the time its first
+    /// usage was written, taken from `written_at` (the time `items[i]` was written at
+    /// `written_at[i]`). Items without a known time fall back to the thread's `updated_at`.
+    pub async fn record_token_usage_at(
+        &self,
+        metadata: &ThreadMetadata,
+        items: &[RolloutItem],
+        written_at: &[Option<DateTime<Utc>>],
+    ) -> anyhow::Result<()> {
+        if !items.iter().any(rollout_item_affects_token_usage) {
+            return Ok(());
+        }
+        let thread_id = metadata.id.to_string();
+        let parent_thread_id = parent_thread_id(metadata.source.as_str());
+        let mut tx = self.pool.begin().await?;
+        let cursor = sqlx::query(
+            "SELECT turn_id, model, total_tokens FROM thread_token_usage_cursor WHERE thread_id = ?",
+        )
+        .bind(thread_id.as_str())
+        .fetch_optional(&mut *tx)
+        .await?;
+        let (mut turn_id, mut model, mut recorded_total) = match cursor {
+            Some(row) => (
+                row.try_get::<String, _>("turn_id")?,
+                row.try_get::<String, _>("model")?,
+                row.try_get::<i64, _>("total_tokens")?,
+            ),
+            None => (String::new(), String::new(), 0),
+        };
+        for (index, item) in items.iter().enumerate() {
+            let event = match item {
+                RolloutItem::TurnContext(turn_ctx) => {
+                    if let Some(id) = turn_ctx.turn_id.as_ref() {
+                        turn_id = id.clone();
+                    }
+                    model = turn_ctx.model.clone();
+                    continue;
+                }
+                RolloutItem::EventMsg(event) => event,
+                RolloutItem::SessionMeta(_)
+                | RolloutItem::ResponseItem(_)
+                | RolloutItem::Compacted(_) => continue,
+            };
+            let info = match event {
+                EventMsg::TurnStarted(started) => {
+                    turn_id = started.turn_id.clone();
+                    continue;
+                }
+                EventMsg::TokenCount(token_count) => match token_count.info.as_ref() {
+                    Some(info) => info,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            if info.total_token_usage.total_tokens <= recorded_total {
+                continue;
+            }
+            recorded_total = info.total_token_usage.total_tokens;
+            if !turn_id.is_empty() {
+                let recorded_elsewhere: Option<i64> = sqlx::query_scalar(
+                    "SELECT 1 FROM thread_token_usage WHERE turn_id = ? AND thread_id != ? LIMIT 1",
+                )
+                .bind(turn_id.as_str())
+                .bind(thread_id.as_str())
+                .fetch_optional(&mut *tx)
+                .await?;
+                if recorded_elsewhere.is_some() {
+                    continue;
+                }
+            }
+            let recorded_at = written_at
+                .get(index)
+                .copied()
+                .flatten()
+                .unwrap_or(metadata.updated_at);
+            let usage = &info.last_token_usage;
+            sqlx::query(
+                r#"
+INSERT INTO thread_token_usage (
+    thread_id,
+    turn_id,
+    model,
+    model_provider,
+    parent_thread_id,
+    recorded_at,
+    input_tokens,
+    cached_input_tokens,
+    output_tokens,
+    reasoning_output_tokens,
+    total_tokens
+) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+ON CONFLICT(thread_id, turn_id, model) DO UPDATE SET
+    input_tokens = input_tokens + excluded.input_tokens,
+    cached_input_tokens = cached_input_tokens + excluded.cached_input_tokens,
+    output_tokens = output_tokens + excluded.output_tokens,
+    reasoning_output_tokens = reasoning_output_tokens + excluded.reasoning_output_tokens,
+    total_tokens = total_tokens + excluded.total_tokens
+                "#,
+            )
+            .bind(thread_id.as_str())
+            .bind(turn_id.as_str())
+            .bind(model.as_str())
+            .bind(metadata.model_provider.as_str())
+            .bind(parent_thread_id.as_deref())
+            .bind(datetime_to_epoch_seconds(recorded_at))
+            .bind(usage.input_tokens)
+            .bind(usage.cached_input_tokens)
+            .bind(usage.output_tokens)
+            .bind(usage.reasoning_output_tokens)
+            .bind(usage.total_tokens)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query(
+            r#"
+INSERT INTO thread_token_usage_cursor (thread_id, turn_id, model, total_tokens)
+VALUES (?, ?, ?, ?)
+ON CONFLICT(thread_id) DO UPDATE SET
+    turn_id = excluded.turn_id,
+    model = excluded.model,
+    total_tokens = excluded.total_tokens
+            "#,
+        )
+        .bind(thread_id.as_str())
+        .bind(turn_id.as_str())
+        .bind(model.as_str())
+        .bind(recorded_total)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Aggregate recorded token usage, grouped by the requested dimensions.
+    ///
+    /// Rows are ordered by the grouping columns, then by total tokens descending.
+    pub async fn token_usage_summary(
+        &self,
+        query: &TokenUsageSummaryQuery,
+    ) -> anyhow::Result<Vec<TokenUsageSummary>> {
+        let mut group_by = Vec::new();
+        for dimension in &query.group_by {
+            if !group_by.contains(dimension) {
+                group_by.push(*dimension);
+            }
+        }
+        let mut builder = QueryBuilder::<Sqlite>::new("SELECT ");
+        for dimension in &group_by {
+            builder.push(match dimension {
+                TokenUsageGroupBy::Day => {
+                    "strftime('%Y-%m-%d', thread_token_usage.recorded_at, 'unixepoch') AS day, "
+                }
+                TokenUsageGroupBy::Repository => "threads.git_origin_url AS repository, ",
+                TokenUsageGroupBy::Model => "NULLIF(thread_token_usage.model, '') AS model, ",
+            });
+        }
+        builder.push(
+            r#"
+    COUNT(DISTINCT thread_token_usage.thread_id || ' ' || thread_token_usage.turn_id) AS turns,
+    COUNT(DISTINCT thread_token_usage.thread_id) AS threads,
+    COALESCE(SUM(thread_token_usage.input_tokens), 0) AS input_tokens,
+    COALESCE(SUM(thread_token_usage.cached_input_tokens), 0) AS cached_input_tokens,
+    COALESCE(SUM(thread_token_usage.output_tokens), 0) AS output_tokens,
+    COALESCE(SUM(thread_token_usage.reasoning_output_tokens), 0) AS reasoning_output_tokens,
+    COALESCE(SUM(thread_token_usage.total_tokens), 0) AS total_tokens
+FROM thread_token_usage
+JOIN threads ON threads.id = thread_token_usage.thread_id
+WHERE 1 = 1
+            "#,
+        );
+        if let Some(since) = query.since {
+            builder
+                .push(" AND thread_token_usage.recorded_at >= ")
+                .push_bind(since);
+        }
+        if let Some(until) = query.until {
+            builder
+                .push(" AND thread_token_usage.recorded_at < ")
+                .push_bind(until);
+        }
+        let columns = group_by
+            .iter()
+            .map(|dimension| match dimension {
+                TokenUsageGroupBy::Day => "day",
+                TokenUsageGroupBy::Repository => "repository",
+                TokenUsageGroupBy::Model => "model",
+            })
+            .collect::<Vec<_>>();
+        if !columns.is_empty() {
+            builder.push(" GROUP BY ").push(columns.join(", "));
+        }
+        // Without grouping an empty ledger still yields one aggregate row; drop it.
+        builder.push(" HAVING COUNT(*) > 0 ORDER BY ");
+        for column in &columns {
+            builder.push(column).push(" ASC, ");
+        }
+        builder.push("total_tokens DESC");
+
+        let rows = builder.build().fetch_all(self.pool.as_ref()).await?;
+        rows.into_iter()
+            .map(|row| {
+                let dimension = |dimension, column| -> anyhow::Result<Option<String>> {
+                    if group_by.contains(&dimension) {
+                        Ok(row.try_get(column)?)
+                    } else {
+                        Ok(None)
+                    }
+                };
+                Ok(TokenUsageSummary {
+                    day: dimension(TokenUsageGroupBy::Day, "day")?,
+                    repository: dimension(TokenUsageGroupBy::Repository, "repository")?,
+                    model: dimension(TokenUsageGroupBy::Model, "model")?,
+                    turns: row.try_get("turns")?,
+                    threads: row.try_get("threads")?,
+                    input_tokens: row.try_get("input_tokens")?,
+                    cached_input_tokens: row.try_get("cached_input_tokens")?,
+                    output_tokens: row.try_get("output_tokens")?,
+                    reasoning_output_tokens: row.try_get("reasoning_output_tokens")?,
+                    total_tokens: row.try_get("total_tokens")?,
+                })
+            })
+            .collect()
+    }
+}
+
+fn rollout_item_affects_token_usage(item: &RolloutItem) -> bool {
+    match item {
+        RolloutItem::TurnContext(_) => true,
+        RolloutItem::EventMsg(EventMsg::TurnStarted(_) | EventMsg::TokenCount(_)) => true,
+        RolloutItem::EventMsg(_)
+        | RolloutItem::SessionMeta(_)
+        | RolloutItem::ResponseItem(_)
+        | RolloutItem::Compacted(_) => false,
+    }
+}
+
+/// Parent thread of a spawned sub-agent, read from the stringified session source.
+fn parent_thread_id(source: &str) -> Option<String> {
+    match serde_json::from_str::<SessionSource>(source).ok()? {
+        SessionSource::SubAgent(SubAgentSource::ThreadSpawn {
+            parent_thread_id, ..
+        }) => Some(parent_thread_id.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::test_thread_metadata;
+    use crate::runtime::test_support::unique_temp_dir;
+    use codex_protocol::protocol::TokenCountEvent;
+    use codex_protocol::protocol::TokenUsage;
+    use codex_protocol::protocol::TokenUsageInfo;
+    use codex_protocol::protocol::TurnStartedEvent;
+    use pretty_assertions::assert_eq;
+
+    fn turn_started(turn_id: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::TurnStarted(TurnStartedEvent {
+            turn_id: turn_id.to_string(),
+            model_context_window: None,
+            collaboration_mode_kind: Default::default(),
+        }))
+    }
+
+    fn usage(input: i64, cached: i64, output: i64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            total_tokens: input + output,
+        }
+    }
+
+    fn token_count(last: TokenUsage, total: TokenUsage) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: total,
+                last_token_usage: last,
+                model_context_window: None,
+            }),
+            rate_limits: None,
+        }))
+    }
+
+    async fn runtime_with_threads(
+        threads: &[(ThreadId, Option<&str>)],
+    ) -> (Arc<StateRuntime>, Vec<ThreadMetadata>) {
+        let codex_home = unique_temp_dir();
+        let runtime = StateRuntime::init(codex_home.clone(), "test-provider".to_string())
+            .await
+            .expect("state db should initialize");
+        let mut metadata = Vec::new();
+        for (thread_id, origin) in threads {
+            let mut thread = test_thread_metadata(&codex_home, *thread_id, codex_home.clone());
+            thread.git_origin_url = origin.map(str::to_string);
+            runtime
+                .upsert_thread(&thread)
+                .await
+                .expect("thread upsert should succeed");
+            metadata.push(thread);
+        }
+        (runtime, metadata)
+    }
+
+    #[tokio::test]
+    async fn records_usage_per_turn_and_skips_replayed_events() {
+        let thread_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000a01").expect("valid thread id");
+        let (runtime, metadata) =
+            runtime_with_threads(&[(thread_id, Some("git@example.com:org/repo.git"))]).await;
+        let items = vec![
+            turn_started("turn-1"),
+            token_count(usage(100, 40, 10), usage(100, 40, 10)),
+            token_count(usage(50, 0, 5), usage(150, 40, 15)),
+            // Rate-limit only updates repeat the last cumulative total.
+            token_count(usage(50, 0, 5), usage(150, 40, 15)),
+            turn_started("turn-2"),
+            token_count(usage(20, 10, 2), usage(170, 50, 17)),
+        ];
+
+        for _ in 0..2 {
+            runtime
+                .record_token_usage(&metadata[0], &items)
+                .await
+                .expect("recording should succeed");
+        }
+
+        let summary = runtime
+            .token_usage_summary(&TokenUsageSummaryQuery {
+                group_by: vec![TokenUsageGroupBy::Repository],
+                since: None,
+                until: None,
+            })
+            .await
+            .expect("summary should succeed");
+        assert_eq!(
+            summary,
+            vec![TokenUsageSummary {
+                repository: Some("git@example.com:org/repo.git".to_string()),
+                turns: 2,
+                threads: 1,
+                input_tokens: 170,
+                cached_input_tokens: 50,
+                output_tokens: 17,
+                total_tokens: 187,
+                ..Default::default()
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn summary_groups_by_day_and_model_and_filters_by_time() {
+        let parent_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000a02").expect("valid thread id");
+        let child_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000a03").expect("valid thread id");
+        let (runtime, mut metadata) =
+            runtime_with_threads(&[(parent_id, None), (child_id, None)]).await;
+        metadata[1].source =
+            crate::extract::enum_to_string(&SessionSource::SubAgent(SubAgentSource::ThreadSpawn {
+                parent_thread_id: parent_id,
+                depth: 1,
+                agent_nickname: None,
+                agent_role: None,
+            }));
+        metadata[1].updated_at += chrono::Duration::days(1);
+
+        runtime
+            .record_token_usage(
+                &metadata[0],
+                &[
+                    turn_started("turn-1"),
+                    token_count(usage(10, 0, 1), usage(10, 0, 1)),
+                ],
+            )
+            .await
+            .expect("recording should succeed");
+        runtime
+            .record_token_usage(
+                &metadata[1],
+                &[
+                    turn_started("turn-2"),
+                    token_count(usage(30, 0, 3), usage(30, 0, 3)),
+                ],
+            )
+            .await
+            .expect("recording should succeed");
+
+        let parent: Option<String> = sqlx::query_scalar(
+            "SELECT parent_thread_id FROM thread_token_usage WHERE thread_id = ?",
+        )
+        .bind(child_id.to_string())
+        .fetch_one(runtime.pool.as_ref())
+        .await
+        .expect("usage row should exist");
+        assert_eq!(parent, Some(parent_id.to_string()));
+
+        let summary = runtime
+            .token_usage_summary(&TokenUsageSummaryQuery {
+                group_by: vec![TokenUsageGroupBy::Day, TokenUsageGroupBy::Model],
+                since: None,
+                until: None,
+            })
+            .await
+            .expect("summary should succeed");
+        let rows = summary
+            .iter()
+            .map(|row| (row.day.as_deref(), row.model.as_deref(), row.total_tokens))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("2023-11-14"), None, 11),
+                (Some("2023-11-15"), None, 33)
+            ]
+        );
+
+        let since = datetime_to_epoch_seconds(metadata[1].updated_at);
+        let summary = runtime
+            .token_usage_summary(&TokenUsageSummaryQuery {
+                group_by: Vec::new(),
+                since: Some(since),
+                until: None,
+            })
+            .await
+            .expect("summary should succeed");
+        assert_eq!(summary.len(), 1);
+        assert_eq!(summary[0].total_tokens, 33);
+        assert_eq!(summary[0].threads, 1);
+
+        let summary = runtime
+            .token_usage_summary(&TokenUsageSummaryQuery {
+                group_by: Vec::new(),
+                since: Some(since + 86_400),
+                until: None,
+            })
+            .await
+            .expect("summary should succeed");
+        assert_eq!(summary, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn replayed_turns_are_dated_when_they_were_written() {
+        let thread_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000a04").expect("valid thread id");
+        let (runtime, metadata) = runtime_with_threads(&[(thread_id, None)]).await;
+        let first_day = metadata[0].updated_at - chrono::Duration::days(2);
+        let second_day = metadata[0].updated_at - chrono::Duration::days(1);
+
+        runtime
+            .record_token_usage_at(
+                &metadata[0],
+                &[
+                    turn_started("turn-1"),
+                    token_count(usage(10, 0, 1), usage(10, 0, 1)),
+                    turn_started("turn-2"),
+                    token_count(usage(30, 0, 3), usage(40, 0, 4)),
+                    turn_started("turn-3"),
+                    token_count(usage(50, 0, 5), usage(90, 0, 9)),
+                ],
+                &[
+                    Some(first_day),
+                    Some(first_day),
+                    Some(second_day),
+                    Some(second_day),
+                    None,
+                    None,
+                ],
+            )
+            .await
+            .expect("recording should succeed");
+
+        let summary = runtime
+            .token_usage_summary(&TokenUsageSummaryQuery {
+                group_by: vec![TokenUsageGroupBy::Day],
+                since: None,
+                until: None,
+            })
+            .await
+            .expect("summary should succeed");
+        let rows = summary
+            .iter()
+            .map(|row| (row.day.as_deref(), row.total_tokens))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                (Some("2023-11-12"), 11),
+                (Some("2023-11-13"), 33),
+                (Some("2023-11-14"), 55)
+            ]
+        );
+    }
+}
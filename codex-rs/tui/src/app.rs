@@ -2413,6 +2413,9 @@ impl App {
                     self.launch_external_editor(tui).await;
                 }
             }
+            AppEvent::EditPatchHunk { seed } => {
+                self.edit_patch_hunk(tui, seed).await;
+            }
             AppEvent::OpenWindowsSandboxEnablePrompt { preset } => {
                 self.chat_widget.open_windows_sandbox_enable_prompt(preset);
             }
@@ -3169,6 +3172,12 @@ impl App {
             AppEvent::OpenReviewCommitPicker(cwd) => {
                 self.chat_widget.show_review_commit_picker(&cwd).await;
             }
+            AppEvent::OpenReviewCommitRangePrompt => {
+                self.chat_widget.show_review_commit_range_prompt();
+            }
+            AppEvent::OpenReviewPatchFilePrompt => {
+                self.chat_widget.show_review_patch_file_prompt();
+            }
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
@@ -3564,6 +3573,43 @@ impl App {
         tui.frame_requester().schedule_frame();
     }
 
+    async fn edit_patch_hunk(&mut self, tui: &mut tui::Tui, seed: String) {
+        let editor_cmd = match external_editor::resolve_editor_command() {
+            Ok(cmd) => cmd,
+            Err(external_editor::EditorError::MissingEditor) => {
+                self.chat_widget
+                    .add_to_history(history_cell::new_error_event(
+                        "Cannot edit the change: set $VISUAL or $EDITOR before starting Codex."
+                            .to_string(),
+                    ));
+                return;
+            }
+            Err(err) => {
+                self.chat_widget
+                    .add_to_history(history_cell::new_error_event(format!(
+                        "Failed to open editor: {err}",
+                    )));
+                return;
+            }
+        };
+
+        let editor_result = tui
+            .with_restored(tui::RestoreMode::KeepRaw, || async {
+                external_editor::run_editor(&seed, &editor_cmd).await
+            })
+            .await;
+        match editor_result {
+            Ok(text) => self.chat_widget.apply_patch_hunk_edit(text),
+            Err(err) => {
+                self.chat_widget
+                    .add_to_history(history_cell::new_error_event(format!(
+                        "Failed to open editor: {err}",
+                    )));
+            }
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
     fn request_external_editor_launch(&mut self, tui: &mut tui::Tui) {
         self.chat_widget
             .set_external_editor_state(ExternalEditorState::Requested);
@@ -410,6 +410,12 @@ pub(crate) enum AppEvent {
     /// Open the commit picker option from the review popup.
     OpenReviewCommitPicker(PathBuf),
 
+    /// Open the commit range prompt from the review popup.
+    OpenReviewCommitRangePrompt,
+
+    /// Open the patch file prompt from the review popup.
+    OpenReviewPatchFilePrompt,
+
     /// Open the custom prompt option from the review popup.
     OpenReviewCustomPrompt,
 
@@ -436,6 +442,11 @@ pub(crate) enum AppEvent {
     /// Launch the external editor after a normal draw has completed.
     LaunchExternalEditor,
 
+    /// Edit one hunk of a patch under review in the external editor.
+    EditPatchHunk {
+        seed: String,
+    },
+
     /// Async update of the current git branch for status line rendering.
     StatusLineBranchUpdated {
         cwd: PathBuf,
@@ -8,6 +8,8 @@ use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
+use crate::bottom_pane::patch_hunk_review::HunkReviewEvent;
+use crate::bottom_pane::patch_hunk_review::PatchHunkReview;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
@@ -23,6 +25,7 @@ use codex_protocol::models::MacOsAutomationPermission;
 use codex_protocol::models::MacOsPreferencesPermission;
 use codex_protocol::models::PermissionProfile;
 use codex_protocol::protocol::ElicitationAction;
+use codex_protocol::protocol::ExecPolicyAmendment;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::NetworkApprovalContext;
 use codex_protocol::protocol::NetworkPolicyRuleAction;
@@ -98,6 +101,8 @@ pub(crate) struct ApprovalOverlay {
     current_complete: bool,
     done: bool,
     features: Features,
+    /// Set while the user reviews the current patch hunk by hunk.
+    hunk_review: Option<PatchHunkReview>,
 }
 
 impl ApprovalOverlay {
@@ -111,6 +116,7 @@ impl ApprovalOverlay {
             current_complete: false,
             done: false,
             features,
+            hunk_review: None,
         };
         view.set_current(request);
         view
@@ -122,6 +128,7 @@ impl ApprovalOverlay {
 
     fn set_current(&mut self, request: ApprovalRequest) {
         self.current_complete = false;
+        self.hunk_review = None;
         let header = build_header(&request);
         let (options, params) = Self::build_options(&request, header, &self.features);
         self.current_request = Some(request);
@@ -209,6 +216,13 @@ impl ApprovalOverlay {
                 (ApprovalRequest::ApplyPatch { id, .. }, ApprovalDecision::Review(decision)) => {
                     self.handle_patch_decision(id, decision.clone());
                 }
+                (
+                    ApprovalRequest::ApplyPatch { cwd, changes, .. },
+                    ApprovalDecision::ReviewHunks,
+                ) => {
+                    self.hunk_review = Some(PatchHunkReview::new(cwd, changes));
+                    return;
+                }
                 (
                     ApprovalRequest::McpElicitation {
                         server_name,
@@ -288,6 +302,27 @@ impl ApprovalOverlay {
         });
     }
 
+    fn handle_hunk_review_event(&mut self, event: HunkReviewEvent) {
+        match event {
+            HunkReviewEvent::None => {}
+            HunkReviewEvent::Cancel => {
+                self.hunk_review = None;
+            }
+            HunkReviewEvent::Edit(seed) => {
+                self.app_event_tx.send(AppEvent::EditPatchHunk { seed });
+            }
+            HunkReviewEvent::Submit(hunks) => {
+                self.hunk_review = None;
+                if let Some(ApprovalRequest::ApplyPatch { id, .. }) = self.current_request.as_ref()
+                {
+                    self.handle_patch_decision(id, ReviewDecision::ApprovedHunks { hunks });
+                }
+                self.current_complete = true;
+                self.advance_queue();
+            }
+        }
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -347,6 +382,11 @@ impl ApprovalOverlay {
 
 impl BottomPaneView for ApprovalOverlay {
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if let Some(review) = self.hunk_review.as_mut() {
+            let event = review.handle_key_event(key_event);
+            self.handle_hunk_review_event(event);
+            return;
+        }
         if self.try_handle_shortcut(&key_event) {
             return;
         }
@@ -392,6 +432,18 @@ impl BottomPaneView for ApprovalOverlay {
         self.done
     }
 
+    fn prefer_esc_to_handle_key_event(&self) -> bool {
+        // Esc leaves the hunk review instead of rejecting the patch.
+        self.hunk_review.is_some()
+    }
+
+    fn apply_patch_hunk_edit(&mut self, text: String) {
+        if let Some(review) = self.hunk_review.as_mut() {
+            let event = review.apply_edit(&text);
+            self.handle_hunk_review_event(event);
+        }
+    }
+
     fn try_consume_approval_request(
         &mut self,
         request: ApprovalRequest,
@@ -403,15 +455,24 @@ impl BottomPaneView for ApprovalOverlay {
 
 impl Renderable for ApprovalOverlay {
     fn desired_height(&self, width: u16) -> u16 {
-        self.list.desired_height(width)
+        match self.hunk_review.as_ref() {
+            Some(review) => review.desired_height(width),
+            None => self.list.desired_height(width),
+        }
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        self.list.render(area, buf);
+        match self.hunk_review.as_ref() {
+            Some(review) => review.render(area, buf),
+            None => self.list.render(area, buf),
+        }
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
-        self.list.cursor_pos(area)
+        match self.hunk_review.as_ref() {
+            Some(_) => None,
+            None => self.list.cursor_pos(area),
+        }
     }
 }
 
@@ -532,6 +593,8 @@ fn build_header(request: &ApprovalRequest) -> Box<dyn Renderable> {
 #[derive(Clone)]
 enum ApprovalDecision {
     Review(ReviewDecision),
+    /// Step through the patch hunk by hunk before deciding.
+    ReviewHunks,
     McpElicitation(ElicitationAction),
 }
 
@@ -578,9 +641,10 @@ fn exec_options(
                     return None;
                 }
 
+                let clauses = amendment_clauses_suffix(proposed_execpolicy_amendment);
                 Some(ApprovalOption {
                     label: format!(
-                        "Yes, and don't ask again for commands that start with `{rendered_prefix}`"
+                        "Yes, and don't ask again for commands that start with `{rendered_prefix}`{clauses}"
                     ),
                     decision: ApprovalDecision::Review(
                         ReviewDecision::ApprovedExecpolicyAmendment {
@@ -637,10 +701,27 @@ fn exec_options(
                 display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
                 additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
             }),
+            ReviewDecision::ApprovedHunks { .. } => None,
         })
         .collect()
 }
 
+/// Describes the argument clauses saved along with an execpolicy amendment's prefix.
+fn amendment_clauses_suffix(amendment: &ExecPolicyAmendment) -> String {
+    let mut clauses = Vec::new();
+    if !amendment.args.is_empty() {
+        clauses.push(format!("with {}", amendment.args.join(", ")));
+    }
+    if !amendment.not_matching.is_empty() {
+        clauses.push(format!("unless {}", amendment.not_matching.join(", ")));
+    }
+    if clauses.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", clauses.join("; "))
+    }
+}
+
 fn format_additional_permissions_rule(
     additional_permissions: &PermissionProfile,
 ) -> Option<String> {
@@ -723,6 +804,12 @@ fn patch_options() -> Vec<ApprovalOption> {
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
         },
+        ApprovalOption {
+            label: "Review each change before applying".to_string(),
+            decision: ApprovalDecision::ReviewHunks,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('r'))],
+        },
         ApprovalOption {
             label: "No, and tell Codex what to do differently".to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Abort),
@@ -764,9 +851,10 @@ mod tests {
     use codex_protocol::models::MacOsPreferencesPermission;
     use codex_protocol::models::MacOsSeatbeltProfileExtensions;
     use codex_protocol::models::NetworkPermissions;
-    use codex_protocol::protocol::ExecPolicyAmendment;
     use codex_protocol::protocol::NetworkApprovalProtocol;
     use codex_protocol::protocol::NetworkPolicyAmendment;
+    use codex_protocol::protocol::PatchHunkAction;
+    use codex_protocol::protocol::PatchHunkDecision;
     use codex_utils_absolute_path::AbsolutePathBuf;
     use insta::assert_snapshot;
     use pretty_assertions::assert_eq;
@@ -845,6 +933,51 @@ mod tests {
         assert!(saw_op, "expected approval decision to emit an op");
     }
 
+    #[test]
+    fn hunk_review_submits_per_hunk_decisions() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let request = ApprovalRequest::ApplyPatch {
+            thread_id: ThreadId::new(),
+            thread_label: None,
+            id: "patch".to_string(),
+            reason: None,
+            cwd: PathBuf::from("/tmp"),
+            changes: HashMap::from([(
+                PathBuf::from("/tmp/readme.txt"),
+                FileChange::Add {
+                    content: "hello\n".to_string(),
+                },
+            )]),
+        };
+        let mut view = ApprovalOverlay::new(request, tx, Features::with_defaults());
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE));
+        assert!(view.hunk_review.is_some());
+        assert!(rx.try_recv().is_err());
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        let Ok(AppEvent::SubmitThreadOp {
+            op: Op::PatchApproval { id, decision },
+            ..
+        }) = rx.try_recv()
+        else {
+            panic!("expected a patch approval op");
+        };
+        assert_eq!(id, "patch");
+        assert_eq!(
+            decision,
+            ReviewDecision::ApprovedHunks {
+                hunks: vec![PatchHunkDecision {
+                    path: PathBuf::from("/tmp/readme.txt"),
+                    hunk_index: 0,
+                    action: PatchHunkAction::Accept,
+                }],
+            }
+        );
+        assert!(view.is_complete());
+    }
+
     #[test]
     fn o_opens_source_thread_for_cross_thread_approval() {
         let (tx, mut rx) = unbounded_channel::<AppEvent>();
@@ -1025,6 +1158,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn execpolicy_amendment_option_lists_argument_clauses() {
+        let amendment = ExecPolicyAmendment {
+            not_matching: vec![r#"flag("--force", "-f")"#.to_string()],
+            ..ExecPolicyAmendment::new(vec!["git".to_string(), "push".to_string()])
+        };
+        let options = exec_options(
+            &[ReviewDecision::ApprovedExecpolicyAmendment {
+                proposed_execpolicy_amendment: amendment.clone(),
+            }],
+            None,
+            None,
+        );
+
+        assert_eq!(options.len(), 1);
+        assert_eq!(
+            options[0].label,
+            r#"Yes, and don't ask again for commands that start with `git push` (unless flag("--force", "-f"))"#
+        );
+        assert!(matches!(
+            &options[0].decision,
+            ApprovalDecision::Review(ReviewDecision::ApprovedExecpolicyAmendment {
+                proposed_execpolicy_amendment,
+            }) if *proposed_execpolicy_amendment == amendment
+        ));
+    }
+
     #[test]
     fn network_exec_options_use_expected_labels_and_hide_execpolicy_amendment() {
         let network_context = NetworkApprovalContext {
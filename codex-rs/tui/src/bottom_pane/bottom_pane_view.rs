@@ -55,6 +55,10 @@ pub(crate) trait BottomPaneView: Renderable {
         false
     }
 
+    /// Receive the edited text of a patch hunk the view opened in the external
+    /// editor.
+    fn apply_patch_hunk_edit(&mut self, _text: String) {}
+
     /// Try to handle approval request; return the original value if not
     /// consumed.
     fn try_consume_approval_request(
@@ -180,12 +180,15 @@ use super::skill_popup::SkillPopup;
 use super::slash_commands;
 use super::slash_commands::BuiltinCommandFlags;
 use crate::bottom_pane::paste_burst::FlushResult;
+use crate::bottom_pane::prompt_args::McpPromptCommand;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
+use crate::bottom_pane::prompt_args::parse_mcp_prompt_invocation;
 use crate::bottom_pane::prompt_args::parse_slash_name;
 use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
 use crate::bottom_pane::prompt_args::prompt_has_numeric_placeholders;
+use crate::bottom_pane::prompt_args::slash_command_with_arg_placeholders;
 use crate::render::Insets;
 use crate::render::RectExt;
 use crate::render::renderable::Renderable;
@@ -193,6 +196,7 @@ use crate::slash_command::SlashCommand;
 use crate::style::user_message_style;
 use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use codex_protocol::mcp::Prompt as McpPrompt;
 use codex_protocol::models::local_image_label_text;
 use codex_protocol::user_input::ByteRange;
 use codex_protocol::user_input::MAX_USER_INPUT_TEXT_CHARS;
@@ -256,6 +260,12 @@ pub enum InputResult {
     },
     Command(SlashCommand),
     CommandWithArgs(SlashCommand, String, Vec<TextElement>),
+    /// `/mcp:<server>:<prompt>`: fetch the prompt from its MCP server.
+    McpPrompt {
+        server: String,
+        name: String,
+        arguments: HashMap<String, String>,
+    },
     None,
 }
 
@@ -379,6 +389,7 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     remote_image_urls: Vec<String>,
@@ -502,6 +513,7 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            mcp_prompts: Vec::new(),
             footer_mode: FooterMode::ComposerEmpty,
             footer_hint_override: None,
             remote_image_urls: Vec::new(),
@@ -1435,6 +1447,13 @@ impl ChatComposer {
                                 }
                             }
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(command) = popup.mcp_prompt(idx) {
+                                let (text, cursor) = mcp_prompt_command_text(command);
+                                self.textarea.set_text_clearing_elements(&text);
+                                cursor_target = Some(cursor);
+                            }
+                        }
                     }
                     if let Some(pos) = cursor_target {
                         self.textarea.set_cursor(pos);
@@ -1522,6 +1541,23 @@ impl ChatComposer {
                             }
                             return (InputResult::None, true);
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(command) = popup.mcp_prompt(idx) {
+                                if command.prompt.arguments.is_empty() {
+                                    let result = InputResult::McpPrompt {
+                                        server: command.server.clone(),
+                                        name: command.prompt.name.clone(),
+                                        arguments: HashMap::new(),
+                                    };
+                                    self.textarea.set_text_clearing_elements("");
+                                    return (result, true);
+                                }
+                                let (text, cursor) = mcp_prompt_command_text(command);
+                                self.textarea.set_text_clearing_elements(&text);
+                                self.textarea.set_cursor(cursor);
+                            }
+                            return (InputResult::None, true);
+                        }
                     }
                 }
                 // Fallback to default newline handling if no command selected.
@@ -2475,6 +2511,9 @@ impl ChatComposer {
         if let Some(result) = self.try_dispatch_slash_command_with_args() {
             return (result, true);
         }
+        if let Some(result) = self.try_dispatch_mcp_prompt() {
+            return (result, true);
+        }
 
         if let Some((text, text_elements)) = self.prepare_submission_text(true) {
             if should_queue {
@@ -2566,6 +2605,47 @@ impl ChatComposer {
         ))
     }
 
+    /// Check if the input invokes a known MCP prompt (`/mcp:<server>:<prompt> key=value …`).
+    /// Returns Some(InputResult) if the input was handled, None otherwise.
+    fn try_dispatch_mcp_prompt(&mut self) -> Option<InputResult> {
+        if !self.slash_commands_enabled() || self.mcp_prompts.is_empty() {
+            return None;
+        }
+        let mut text = self.textarea.text().to_string();
+        if text.starts_with(' ') {
+            return None;
+        }
+        if !self.pending_pastes.is_empty() {
+            let (expanded, _) = Self::expand_pending_pastes(
+                &text,
+                self.textarea.text_elements(),
+                &self.pending_pastes,
+            );
+            text = expanded;
+        }
+        let text = text.trim();
+        match parse_mcp_prompt_invocation(text, &self.mcp_prompts) {
+            Ok(Some(invocation)) => {
+                self.history
+                    .record_local_submission(HistoryEntry::new(text.to_string()));
+                self.pending_pastes.clear();
+                self.textarea.set_text_clearing_elements("");
+                Some(InputResult::McpPrompt {
+                    server: invocation.server,
+                    name: invocation.name,
+                    arguments: invocation.arguments,
+                })
+            }
+            Ok(None) => None,
+            Err(err) => {
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    history_cell::new_error_event(err.user_message()),
+                )));
+                Some(InputResult::None)
+            }
+        }
+    }
+
     /// Expand pending placeholders and extract normalized inline-command args.
     ///
     /// Inline-arg commands are initially dispatched using the raw draft so command rejection does
@@ -3371,7 +3451,9 @@ impl ChatComposer {
                 .iter()
                 .any(|prompt| prompt.name == prompt_name);
         }
-        false
+        self.mcp_prompts
+            .iter()
+            .any(|command| command.command_name() == name)
     }
 
     /// If the cursor is currently within a slash command on the first line,
@@ -3419,7 +3501,10 @@ impl ChatComposer {
 
         self.custom_prompts.iter().any(|prompt| {
             fuzzy_match(&format!("{PROMPTS_CMD_PREFIX}:{}", prompt.name), name).is_some()
-        })
+        }) || self
+            .mcp_prompts
+            .iter()
+            .any(|command| fuzzy_match(&command.command_name(), name).is_some())
     }
 
     /// Synchronize `self.command_popup` with the current text in the
@@ -3480,6 +3565,7 @@ impl ChatComposer {
                             windows_degraded_sandbox_active: self.windows_degraded_sandbox_active,
                         },
                     );
+                    command_popup.set_mcp_prompts(self.mcp_prompts.clone());
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                 }
@@ -3493,6 +3579,27 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.mcp_prompts = prompts.clone();
+        if let ActivePopup::Command(popup) = &mut self.active_popup {
+            popup.set_mcp_prompts(prompts);
+        }
+    }
+
+    pub(crate) fn set_mcp_server_prompts(&mut self, server: &str, prompts: Vec<McpPrompt>) {
+        let mut all: Vec<McpPromptCommand> = self
+            .mcp_prompts
+            .iter()
+            .filter(|command| command.server != server)
+            .cloned()
+            .collect();
+        all.extend(prompts.into_iter().map(|prompt| McpPromptCommand {
+            server: server.to_string(),
+            prompt,
+        }));
+        self.set_mcp_prompts(all);
+    }
+
     /// Synchronize `self.file_search_popup` with the current text in the textarea.
     /// Note this is only called when self.active_popup is NOT Command.
     fn sync_file_search_popup(&mut self, query: String) {
@@ -4423,6 +4530,19 @@ impl ChatComposer {
     }
 }
 
+/// Command text for an MCP prompt with `key=""` placeholders for its arguments, and the cursor
+/// position to start typing at.
+fn mcp_prompt_command_text(command: &McpPromptCommand) -> (String, usize) {
+    let (text, cursor) =
+        slash_command_with_arg_placeholders(&command.command_name(), &command.argument_names());
+    if command.prompt.arguments.is_empty() {
+        let text = format!("{text} ");
+        let cursor = text.len();
+        return (text, cursor);
+    }
+    (text, cursor)
+}
+
 fn prompt_selection_action(
     prompt: &CustomPrompt,
     first_line: &str,
@@ -6376,7 +6496,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "model")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                     panic!("unexpected prompt selected for '/mo'")
                 }
                 None => panic!("no selected command for '/mo'"),
@@ -6432,7 +6552,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "resume")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                     panic!("unexpected prompt selected for '/res'")
                 }
                 None => panic!("no selected command for '/res'"),
@@ -8189,6 +8309,79 @@ mod tests {
         assert!(composer.textarea.is_empty());
     }
 
+    #[test]
+    fn mcp_prompt_completion_inserts_args_and_submits_invocation() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_mcp_prompts(vec![McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: McpPrompt {
+                name: "triage".to_string(),
+                title: None,
+                description: Some("Triage an issue.".to_string()),
+                arguments: vec![codex_protocol::mcp::PromptArgument {
+                    name: "issue".to_string(),
+                    title: None,
+                    description: None,
+                    required: true,
+                }],
+            },
+        }]);
+
+        // Submitting without a value reports the missing argument and keeps the draft.
+        composer
+            .textarea
+            .set_text_clearing_elements("/mcp:docs:triage");
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(result, InputResult::None);
+        assert_eq!(composer.textarea.text(), "/mcp:docs:triage");
+        let mut saw_error = false;
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::InsertHistoryCell(cell) = event {
+                let text = cell
+                    .display_lines(80)
+                    .into_iter()
+                    .map(|line| line.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                saw_error |= text.contains("Missing required args for /mcp:docs:triage: issue");
+            }
+        }
+        assert!(saw_error, "expected missing-args error");
+
+        composer
+            .textarea
+            .set_text_clearing_elements("/mcp:docs:triage issue=42");
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(
+            result,
+            InputResult::McpPrompt {
+                server: "docs".to_string(),
+                name: "triage".to_string(),
+                arguments: HashMap::from([("issue".to_string(), "42".to_string())]),
+            }
+        );
+        assert!(composer.textarea.is_empty());
+
+        // Completing from the popup inserts placeholders for the prompt's arguments.
+        type_chars_humanlike(&mut composer, &['/', 't', 'r', 'i', 'a']);
+        composer.handle_key_event(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+        assert_eq!(composer.textarea.text(), "/mcp:docs:triage issue=\"\"");
+        assert_eq!(
+            composer.textarea.cursor(),
+            "/mcp:docs:triage issue=\"".len()
+        );
+    }
+
     #[test]
     fn custom_prompt_submission_expands_arguments() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -53,6 +53,7 @@ const APPROVAL_DECLINE_VALUE: &str = "decline";
 const APPROVAL_CANCEL_VALUE: &str = "cancel";
 const APPROVAL_META_KIND_KEY: &str = "codex_approval_kind";
 const APPROVAL_META_KIND_MCP_TOOL_CALL: &str = "mcp_tool_call";
+const APPROVAL_META_KIND_MCP_SAMPLING: &str = "mcp_sampling";
 const APPROVAL_PERSIST_KEY: &str = "persist";
 const APPROVAL_PERSIST_SESSION_VALUE: &str = "session";
 
@@ -168,12 +169,14 @@ impl McpServerElicitationFormRequest {
             return None;
         };
 
-        let is_tool_approval = meta
+        let approval_kind = meta
             .as_ref()
             .and_then(Value::as_object)
             .and_then(|meta| meta.get(APPROVAL_META_KIND_KEY))
-            .and_then(Value::as_str)
-            == Some(APPROVAL_META_KIND_MCP_TOOL_CALL);
+            .and_then(Value::as_str);
+        let is_sampling_approval = approval_kind == Some(APPROVAL_META_KIND_MCP_SAMPLING);
+        let is_tool_approval =
+            approval_kind == Some(APPROVAL_META_KIND_MCP_TOOL_CALL) || is_sampling_approval;
         let is_empty_object_schema = requested_schema.as_object().is_some_and(|schema| {
             schema.get("type").and_then(Value::as_str) == Some("object")
                 && schema
@@ -184,9 +187,23 @@ impl McpServerElicitationFormRequest {
 
         let (response_mode, fields) =
             if requested_schema.is_null() || (is_tool_approval && is_empty_object_schema) {
+                let (accept_description, decline_description, cancel_description) =
+                    if is_sampling_approval {
+                        (
+                            "Let the server query the model and continue.",
+                            "Decline this model request and continue.",
+                            "Cancel this model request",
+                        )
+                    } else {
+                        (
+                            "Run the tool and continue.",
+                            "Decline this tool call and continue.",
+                            "Cancel this tool call",
+                        )
+                    };
                 let mut options = vec![McpServerElicitationOption {
                     label: "Approve Once".to_string(),
-                    description: Some("Run the tool and continue.".to_string()),
+                    description: Some(accept_description.to_string()),
                     value: Value::String(APPROVAL_ACCEPT_ONCE_VALUE.to_string()),
                 }];
                 if meta
@@ -207,12 +224,12 @@ impl McpServerElicitationFormRequest {
                 options.extend([
                     McpServerElicitationOption {
                         label: "Deny".to_string(),
-                        description: Some("Decline this tool call and continue.".to_string()),
+                        description: Some(decline_description.to_string()),
                         value: Value::String(APPROVAL_DECLINE_VALUE.to_string()),
                     },
                     McpServerElicitationOption {
                         label: "Cancel".to_string(),
-                        description: Some("Cancel this tool call".to_string()),
+                        description: Some(cancel_description.to_string()),
                         value: Value::String(APPROVAL_CANCEL_VALUE.to_string()),
                     },
                 ]);
@@ -1687,6 +1704,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sampling_approval_uses_model_request_descriptions() {
+        let request = McpServerElicitationFormRequest::from_event(
+            ThreadId::default(),
+            form_request(
+                "MCP server `indexer` wants to query the model",
+                empty_object_schema(),
+                Some(serde_json::json!({
+                    APPROVAL_META_KIND_KEY: APPROVAL_META_KIND_MCP_SAMPLING,
+                })),
+            ),
+        )
+        .expect("expected approval fallback");
+
+        assert_eq!(
+            request.response_mode,
+            McpServerElicitationResponseMode::ApprovalAction
+        );
+        let McpServerElicitationFieldInput::Select { options, .. } = &request.fields[0].input
+        else {
+            panic!("expected approval options");
+        };
+        assert_eq!(
+            options
+                .iter()
+                .map(|option| option.description.as_deref())
+                .collect::<Vec<_>>(),
+            vec![
+                Some("Let the server query the model and continue."),
+                Some("Decline this model request and continue."),
+                Some("Cancel this model request"),
+            ]
+        );
+    }
+
     #[test]
     fn empty_tool_approval_schema_session_choice_sets_persist_meta() {
         let (tx, mut rx) = test_sender();
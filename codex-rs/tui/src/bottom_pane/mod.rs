@@ -45,6 +45,7 @@ mod app_link_view;
 mod approval_overlay;
 mod mcp_server_elicitation;
 mod multi_select_picker;
+mod patch_hunk_review;
 mod request_user_input;
 mod status_line_setup;
 pub(crate) use app_link_view::AppLinkView;
@@ -139,6 +140,8 @@ pub(crate) use chat_composer::ChatComposer;
 pub(crate) use chat_composer::ChatComposerConfig;
 pub(crate) use chat_composer::InputResult;
 use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::mcp::Prompt as McpPrompt;
+pub(crate) use prompt_args::McpPromptCommand;
 
 use crate::status_indicator_widget::StatusDetailsCapitalization;
 use crate::status_indicator_widget::StatusIndicatorWidget;
@@ -602,6 +605,19 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Hands the text saved in the external editor to the hunk review of the
+    /// active approval overlay.
+    pub(crate) fn apply_patch_hunk_edit(&mut self, text: String) {
+        if let Some(view) = self.view_stack.last_mut() {
+            view.apply_patch_hunk_edit(text);
+            if view.is_complete() {
+                self.view_stack.clear();
+                self.on_active_view_complete();
+            }
+            self.request_redraw();
+        }
+    }
+
     pub(crate) fn set_footer_hint_override(&mut self, items: Option<Vec<(String, String)>>) {
         self.composer.set_footer_hint_override(items);
         self.request_redraw();
@@ -852,6 +868,18 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Update the MCP server prompts available for the slash popup.
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.composer.set_mcp_prompts(prompts);
+        self.request_redraw();
+    }
+
+    /// Replace the prompts offered by one MCP server, e.g. after it reported a change.
+    pub(crate) fn set_mcp_server_prompts(&mut self, server: &str, prompts: Vec<McpPrompt>) {
+        self.composer.set_mcp_server_prompts(server, prompts);
+        self.request_redraw();
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.composer.is_empty()
     }
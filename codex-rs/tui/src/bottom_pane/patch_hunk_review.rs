@@ -0,0 +1,417 @@
+//! Hunk-by-hunk review of a proposed patch inside the approval overlay.
+//!
+//! Each file change is split with [`FileChange::hunks`], the same split core
+//! uses when it rebuilds the patch from the user's decisions. The user steps
+//! through the hunks, accepting, rejecting or editing each one; undecided
+//! hunks are rejected when the review is submitted.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::PatchHunkAction;
+use codex_protocol::protocol::PatchHunkDecision;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Wrap;
+
+use crate::diff_render::display_path_for;
+use crate::key_hint;
+use crate::render::Insets;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::InsetRenderable;
+use crate::render::renderable::Renderable;
+
+/// Explains the editor buffer for a hunk of an updated file. Diff lines always
+/// start with ` `, `-`, `+` or `@@`, so `#` lines cannot be part of the hunk.
+const EDIT_INSTRUCTIONS: &str = "\
+# Edit the hunk below, then save and close the editor.
+# Keep ' ' and '-' lines unchanged so the hunk still applies; change, add or
+# remove '+' lines. Lines starting with '#' are ignored. Leave the hunk
+# unchanged or empty to keep the proposed version.
+";
+
+/// What the approval overlay should do after the review handled an input.
+#[derive(Debug, PartialEq)]
+pub(crate) enum HunkReviewEvent {
+    None,
+    /// Go back to the approval options without deciding.
+    Cancel,
+    /// Open the current hunk in the external editor, seeded with this text.
+    Edit(String),
+    /// Send these decisions; hunks without one are rejected.
+    Submit(Vec<PatchHunkDecision>),
+}
+
+struct HunkEntry {
+    path: PathBuf,
+    hunk_index: usize,
+    hunk_count: usize,
+    /// Just this hunk, for rendering with the regular diff renderer.
+    change: FileChange,
+    /// The hunk text the editor is seeded with.
+    text: String,
+    action: Option<PatchHunkAction>,
+}
+
+impl HunkEntry {
+    /// Deleted files are accepted or rejected as a whole and pure renames have
+    /// nothing to edit.
+    fn is_editable(&self) -> bool {
+        match &self.change {
+            FileChange::Add { .. } => true,
+            FileChange::Delete { .. } => false,
+            FileChange::Update { .. } => !self.text.is_empty(),
+        }
+    }
+
+    fn editor_seed(&self) -> String {
+        let current = match &self.action {
+            Some(PatchHunkAction::Edit { replacement }) => replacement,
+            _ => &self.text,
+        };
+        match self.change {
+            // File contents may contain `#` lines, so they are edited as is.
+            FileChange::Add { .. } => current.clone(),
+            _ => format!("{EDIT_INSTRUCTIONS}{current}"),
+        }
+    }
+
+    /// Turns the edited editor buffer into the action for this hunk.
+    fn action_for_edit(&self, edited: &str) -> PatchHunkAction {
+        let replacement = match self.change {
+            FileChange::Add { .. } => edited.to_string(),
+            _ => edited
+                .lines()
+                .filter(|line| !line.starts_with('#'))
+                .map(|line| format!("{line}\n"))
+                .collect(),
+        };
+        if replacement.trim().is_empty() || replacement == self.text {
+            PatchHunkAction::Accept
+        } else {
+            PatchHunkAction::Edit { replacement }
+        }
+    }
+}
+
+pub(crate) struct PatchHunkReview {
+    cwd: PathBuf,
+    entries: Vec<HunkEntry>,
+    current: usize,
+}
+
+impl PatchHunkReview {
+    pub(crate) fn new(cwd: &Path, changes: &HashMap<PathBuf, FileChange>) -> Self {
+        let mut paths: Vec<&PathBuf> = changes.keys().collect();
+        paths.sort();
+        let mut entries = Vec::new();
+        for path in paths {
+            let change = &changes[path];
+            let hunks = change.hunks();
+            let hunk_count = hunks.len();
+            for (hunk_index, text) in hunks.into_iter().enumerate() {
+                let change = match change {
+                    FileChange::Add { .. } | FileChange::Delete { .. } => change.clone(),
+                    FileChange::Update { move_path, .. } => FileChange::Update {
+                        unified_diff: text.clone(),
+                        move_path: move_path.clone(),
+                    },
+                };
+                entries.push(HunkEntry {
+                    path: path.clone(),
+                    hunk_index,
+                    hunk_count,
+                    change,
+                    text,
+                    action: None,
+                });
+            }
+        }
+        Self {
+            cwd: cwd.to_path_buf(),
+            entries,
+            current: 0,
+        }
+    }
+
+    pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) -> HunkReviewEvent {
+        if key_event.kind == KeyEventKind::Release {
+            return HunkReviewEvent::None;
+        }
+        match key_event.code {
+            KeyCode::Char('y') => self.decide(PatchHunkAction::Accept),
+            KeyCode::Char('n') => self.decide(PatchHunkAction::Reject),
+            KeyCode::Char('e') => match self.entries.get(self.current) {
+                Some(entry) if entry.is_editable() => HunkReviewEvent::Edit(entry.editor_seed()),
+                _ => HunkReviewEvent::None,
+            },
+            KeyCode::Left | KeyCode::Up | KeyCode::Char('k') => {
+                self.current = self.current.saturating_sub(1);
+                HunkReviewEvent::None
+            }
+            KeyCode::Right | KeyCode::Down | KeyCode::Char('j') => {
+                if self.current + 1 < self.entries.len() {
+                    self.current += 1;
+                }
+                HunkReviewEvent::None
+            }
+            KeyCode::Enter => HunkReviewEvent::Submit(self.decisions()),
+            KeyCode::Esc => HunkReviewEvent::Cancel,
+            _ => HunkReviewEvent::None,
+        }
+    }
+
+    /// Applies the text the user saved in the external editor to the current
+    /// hunk.
+    pub(crate) fn apply_edit(&mut self, edited: &str) -> HunkReviewEvent {
+        let Some(entry) = self.entries.get(self.current) else {
+            return HunkReviewEvent::None;
+        };
+        let action = entry.action_for_edit(edited);
+        self.decide(action)
+    }
+
+    fn decide(&mut self, action: PatchHunkAction) -> HunkReviewEvent {
+        let Some(entry) = self.entries.get_mut(self.current) else {
+            return HunkReviewEvent::None;
+        };
+        entry.action = Some(action);
+
+        // Move on to the next undecided hunk, wrapping around, and submit once
+        // every hunk has a decision.
+        let len = self.entries.len();
+        match (1..len)
+            .map(|offset| (self.current + offset) % len)
+            .find(|&index| self.entries[index].action.is_none())
+        {
+            Some(next) => {
+                self.current = next;
+                HunkReviewEvent::None
+            }
+            None => HunkReviewEvent::Submit(self.decisions()),
+        }
+    }
+
+    fn decisions(&self) -> Vec<PatchHunkDecision> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                entry.action.clone().map(|action| PatchHunkDecision {
+                    path: entry.path.clone(),
+                    hunk_index: entry.hunk_index,
+                    action,
+                })
+            })
+            .collect()
+    }
+
+    fn view(&self) -> ColumnRenderable<'_> {
+        let mut column = ColumnRenderable::new();
+        let Some(entry) = self.entries.get(self.current) else {
+            return column;
+        };
+
+        let mut title = vec![
+            format!("Change {} of {}", self.current + 1, self.entries.len()).bold(),
+            " · ".dim(),
+            display_path_for(&entry.path, &self.cwd).into(),
+        ];
+        if entry.hunk_count > 1 {
+            title.push(format!(" (hunk {} of {})", entry.hunk_index + 1, entry.hunk_count).dim());
+        }
+        column.push(Line::from(title));
+        column.push(Line::from(progress_spans(&self.entries, self.current)));
+        column.push(Line::from(""));
+
+        let body: Box<dyn Renderable> = match &entry.action {
+            Some(PatchHunkAction::Edit { replacement }) => Box::new(
+                Paragraph::new(edited_hunk_lines(&entry.change, replacement))
+                    .wrap(Wrap { trim: false }),
+            ),
+            _ if entry.text.is_empty() => Box::new(Line::from("(renamed without changes)".dim())),
+            _ => Box::new(entry.change.clone()),
+        };
+        column.push(InsetRenderable::new(body, Insets::tlbr(0, 2, 0, 0)));
+        column.push(Line::from(""));
+        column.push(footer_hint(entry.is_editable()));
+        column
+    }
+}
+
+impl Renderable for PatchHunkReview {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.view().render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.view().desired_height(width)
+    }
+}
+
+/// One marker per hunk: accepted, edited, rejected or still undecided, with
+/// the current hunk underlined.
+fn progress_spans(entries: &[HunkEntry], current: usize) -> Vec<Span<'static>> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let marker = match entry.action {
+                Some(PatchHunkAction::Accept) => "✔".green(),
+                Some(PatchHunkAction::Edit { .. }) => "✎".cyan(),
+                Some(PatchHunkAction::Reject) => "✗".red(),
+                None => "·".dim(),
+            };
+            if index == current {
+                marker.underlined()
+            } else {
+                marker
+            }
+        })
+        .flat_map(|marker| [marker, " ".into()])
+        .collect()
+}
+
+fn edited_hunk_lines(change: &FileChange, replacement: &str) -> Vec<Line<'static>> {
+    if let FileChange::Add { .. } = change {
+        return replacement
+            .lines()
+            .map(|line| Line::from(format!("+{line}")).green())
+            .collect();
+    }
+    replacement
+        .lines()
+        .map(|line| {
+            let line = line.to_string();
+            if line.starts_with('+') {
+                Line::from(line).green()
+            } else if line.starts_with('-') {
+                Line::from(line).red()
+            } else {
+                Line::from(line).dim()
+            }
+        })
+        .collect()
+}
+
+fn footer_hint(editable: bool) -> Line<'static> {
+    let mut spans = vec![
+        key_hint::plain(KeyCode::Char('y')).into(),
+        " accept  ".into(),
+        key_hint::plain(KeyCode::Char('n')).into(),
+        " reject  ".into(),
+    ];
+    if editable {
+        spans.extend([key_hint::plain(KeyCode::Char('e')).into(), " edit  ".into()]);
+    }
+    spans.extend([
+        key_hint::plain(KeyCode::Left).into(),
+        "/".into(),
+        key_hint::plain(KeyCode::Right).into(),
+        " move  ".into(),
+        key_hint::plain(KeyCode::Enter).into(),
+        " apply decisions  ".into(),
+        key_hint::plain(KeyCode::Esc).into(),
+        " back".into(),
+    ]);
+    Line::from(spans).dim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+
+    fn press(review: &mut PatchHunkReview, code: KeyCode) -> HunkReviewEvent {
+        review.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn changes() -> HashMap<PathBuf, FileChange> {
+        HashMap::from([
+            (
+                PathBuf::from("/repo/a.rs"),
+                FileChange::Update {
+                    unified_diff: "@@ -1,2 +1,2 @@\n a\n-b\n+B\n@@ -9,2 +9,2 @@\n y\n-z\n+Z\n"
+                        .to_string(),
+                    move_path: None,
+                },
+            ),
+            (
+                PathBuf::from("/repo/b.rs"),
+                FileChange::Add {
+                    content: "fn b() {}\n".to_string(),
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn submits_once_every_hunk_is_decided() {
+        let mut review = PatchHunkReview::new(Path::new("/repo"), &changes());
+
+        assert_eq!(
+            press(&mut review, KeyCode::Char('y')),
+            HunkReviewEvent::None
+        );
+        assert_eq!(
+            press(&mut review, KeyCode::Char('n')),
+            HunkReviewEvent::None
+        );
+        assert_eq!(
+            press(&mut review, KeyCode::Char('y')),
+            HunkReviewEvent::Submit(vec![
+                PatchHunkDecision {
+                    path: PathBuf::from("/repo/a.rs"),
+                    hunk_index: 0,
+                    action: PatchHunkAction::Accept,
+                },
+                PatchHunkDecision {
+                    path: PathBuf::from("/repo/a.rs"),
+                    hunk_index: 1,
+                    action: PatchHunkAction::Reject,
+                },
+                PatchHunkDecision {
+                    path: PathBuf::from("/repo/b.rs"),
+                    hunk_index: 0,
+                    action: PatchHunkAction::Accept,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn edited_hunk_drops_instructions_and_enter_submits_partial_review() {
+        let mut review = PatchHunkReview::new(Path::new("/repo"), &changes());
+        press(&mut review, KeyCode::Right);
+
+        let HunkReviewEvent::Edit(seed) = press(&mut review, KeyCode::Char('e')) else {
+            panic!("expected the hunk to open in the editor");
+        };
+        assert_eq!(
+            seed,
+            format!("{EDIT_INSTRUCTIONS}@@ -9,2 +9,2 @@\n y\n-z\n+Z\n")
+        );
+
+        let edited = seed.replace("+Z", "+zz");
+        assert_eq!(review.apply_edit(&edited), HunkReviewEvent::None);
+        assert_eq!(
+            press(&mut review, KeyCode::Enter),
+            HunkReviewEvent::Submit(vec![PatchHunkDecision {
+                path: PathBuf::from("/repo/a.rs"),
+                hunk_index: 1,
+                action: PatchHunkAction::Edit {
+                    replacement: "@@ -9,2 +9,2 @@\n y\n-z\n+zz\n".to_string(),
+                },
+            }])
+        );
+    }
+}
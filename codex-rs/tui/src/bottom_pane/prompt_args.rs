@@ -1,5 +1,6 @@
 use codex_protocol::custom_prompts::CustomPrompt;
 use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use codex_protocol::mcp::Prompt as McpPrompt;
 use codex_protocol::user_input::ByteRange;
 use codex_protocol::user_input::TextElement;
 use lazy_static::lazy_static;
@@ -8,6 +9,9 @@ use shlex::Shlex;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+/// Prefix for prompts provided by MCP servers, invoked as `/mcp:<server>:<prompt>`.
+pub const MCP_PROMPT_CMD_PREFIX: &str = "mcp";
+
 lazy_static! {
     static ref PROMPT_ARG_REGEX: Regex =
         Regex::new(r"\$[A-Z][A-Z0-9_]*").unwrap_or_else(|_| std::process::abort());
@@ -252,6 +256,84 @@ pub fn expand_custom_prompt(
     )))
 }
 
+/// A prompt offered by an MCP server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpPromptCommand {
+    pub server: String,
+    pub prompt: McpPrompt,
+}
+
+impl McpPromptCommand {
+    /// Slash command name without the leading `/`, e.g. `mcp:docs:review`.
+    pub fn command_name(&self) -> String {
+        format!(
+            "{MCP_PROMPT_CMD_PREFIX}:{}:{}",
+            self.server, self.prompt.name
+        )
+    }
+
+    pub fn argument_names(&self) -> Vec<String> {
+        self.prompt
+            .arguments
+            .iter()
+            .map(|arg| arg.name.clone())
+            .collect()
+    }
+}
+
+/// A parsed `/mcp:<server>:<prompt> [key=value …]` command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpPromptInvocation {
+    pub server: String,
+    pub name: String,
+    pub arguments: HashMap<String, String>,
+}
+
+/// Parses a message of the form `/mcp:<server>:<prompt> [key=value] …` against the known MCP
+/// prompts.
+///
+/// Returns `Ok(None)` if the text is not an MCP prompt command or names an unknown prompt, and
+/// an error if the arguments cannot be parsed or a required argument is missing.
+pub fn parse_mcp_prompt_invocation(
+    text: &str,
+    mcp_prompts: &[McpPromptCommand],
+) -> Result<Option<McpPromptInvocation>, PromptExpansionError> {
+    let Some((name, rest, _rest_offset)) = parse_slash_name(text) else {
+        return Ok(None);
+    };
+    if !name.starts_with(&format!("{MCP_PROMPT_CMD_PREFIX}:")) {
+        return Ok(None);
+    }
+    let Some(command) = mcp_prompts.iter().find(|p| p.command_name() == name) else {
+        return Ok(None);
+    };
+    let inputs = parse_prompt_inputs(rest, &[]).map_err(|error| PromptExpansionError::Args {
+        command: format!("/{name}"),
+        error,
+    })?;
+    let missing: Vec<String> = command
+        .prompt
+        .arguments
+        .iter()
+        .filter(|arg| arg.required && !inputs.contains_key(&arg.name))
+        .map(|arg| arg.name.clone())
+        .collect();
+    if !missing.is_empty() {
+        return Err(PromptExpansionError::MissingArgs {
+            command: format!("/{name}"),
+            missing,
+        });
+    }
+    Ok(Some(McpPromptInvocation {
+        server: command.server.clone(),
+        name: command.prompt.name.clone(),
+        arguments: inputs
+            .into_iter()
+            .map(|(key, value)| (key, value.text))
+            .collect(),
+    }))
+}
+
 /// Detect whether `content` contains numeric placeholders ($1..$9) or `$ARGUMENTS`.
 pub fn prompt_has_numeric_placeholders(content: &str) -> bool {
     if content.contains("$ARGUMENTS") {
@@ -548,7 +630,13 @@ fn append_joined_args_with_elements(
 /// Constructs a command text for a custom prompt with arguments.
 /// Returns the text and the cursor position (inside the first double quote).
 pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (String, usize) {
-    let mut text = format!("/{PROMPTS_CMD_PREFIX}:{name}");
+    slash_command_with_arg_placeholders(&format!("{PROMPTS_CMD_PREFIX}:{name}"), args)
+}
+
+/// Constructs `/<command> arg="" …`, returning the text and the cursor position (inside the
+/// first double quote).
+pub fn slash_command_with_arg_placeholders(command: &str, args: &[String]) -> (String, usize) {
+    let mut text = format!("/{command}");
     let mut cursor: usize = text.len();
     for (i, arg) in args.iter().enumerate() {
         text.push_str(format!(" {arg}=\"\"").as_str());
@@ -851,4 +939,62 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn mcp_prompt_invocation_requires_required_args() {
+        let prompts = vec![McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: McpPrompt {
+                name: "review".to_string(),
+                title: None,
+                description: None,
+                arguments: vec![
+                    codex_protocol::mcp::PromptArgument {
+                        name: "path".to_string(),
+                        title: None,
+                        description: None,
+                        required: true,
+                    },
+                    codex_protocol::mcp::PromptArgument {
+                        name: "focus".to_string(),
+                        title: None,
+                        description: None,
+                        required: false,
+                    },
+                ],
+            },
+        }];
+
+        let invocation =
+            parse_mcp_prompt_invocation("/mcp:docs:review path=\"src/main.rs\"", &prompts)
+                .expect("parse")
+                .expect("known prompt");
+        assert_eq!(
+            invocation,
+            McpPromptInvocation {
+                server: "docs".to_string(),
+                name: "review".to_string(),
+                arguments: HashMap::from([("path".to_string(), "src/main.rs".to_string())]),
+            }
+        );
+
+        let err = parse_mcp_prompt_invocation("/mcp:docs:review focus=perf", &prompts)
+            .unwrap_err()
+            .user_message();
+        assert!(err.contains("path"), "{err}");
+        assert_eq!(
+            parse_mcp_prompt_invocation("/mcp:docs:other", &prompts).expect("parse"),
+            None
+        );
+        assert_eq!(
+            slash_command_with_arg_placeholders(
+                &prompts[0].command_name(),
+                &prompts[0].argument_names()
+            ),
+            (
+                "/mcp:docs:review path=\"\" focus=\"\"".to_string(),
+                "/mcp:docs:review path=\"".len()
+            )
+        );
+    }
 }
@@ -112,11 +112,14 @@ use codex_protocol::protocol::ExecCommandEndEvent;
 use codex_protocol::protocol::ExecCommandOutputDeltaEvent;
 use codex_protocol::protocol::ExecCommandSource;
 use codex_protocol::protocol::ExitedReviewModeEvent;
+use codex_protocol::protocol::GetMcpPromptResponseEvent;
 use codex_protocol::protocol::ImageGenerationBeginEvent;
 use codex_protocol::protocol::ImageGenerationEndEvent;
 use codex_protocol::protocol::ListCustomPromptsResponseEvent;
+use codex_protocol::protocol::ListMcpPromptsResponseEvent;
 use codex_protocol::protocol::ListSkillsResponseEvent;
 use codex_protocol::protocol::McpListToolsResponseEvent;
+use codex_protocol::protocol::McpPromptListChangedEvent;
 use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::McpStartupStatus;
 use codex_protocol::protocol::McpStartupUpdateEvent;
@@ -227,6 +230,7 @@ use crate::bottom_pane::ExperimentalFeaturesView;
 use crate::bottom_pane::FeedbackAudience;
 use crate::bottom_pane::InputResult;
 use crate::bottom_pane::LocalImageAttachment;
+use crate::bottom_pane::McpPromptCommand;
 use crate::bottom_pane::McpServerElicitationFormRequest;
 use crate::bottom_pane::MentionBinding;
 use crate::bottom_pane::QUIT_SHORTCUT_TIMEOUT;
@@ -1248,6 +1252,7 @@ impl ChatWidget {
         }
         // Ask codex-core to enumerate custom prompts for this session.
         self.submit_op(Op::ListCustomPrompts);
+        self.submit_op(Op::ListMcpPrompts);
         self.submit_op(Op::ListSkills {
             cwds: Vec::new(),
             force_reload: true,
@@ -3720,6 +3725,17 @@ impl ChatWidget {
                 InputResult::CommandWithArgs(cmd, args, text_elements) => {
                     self.dispatch_command_with_args(cmd, args, text_elements);
                 }
+                InputResult::McpPrompt {
+                    server,
+                    name,
+                    arguments,
+                } => {
+                    self.submit_op(Op::GetMcpPrompt {
+                        server,
+                        name,
+                        arguments,
+                    });
+                }
                 InputResult::None => {}
             },
         }
@@ -3751,6 +3767,11 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    pub(crate) fn apply_patch_hunk_edit(&mut self, text: String) {
+        self.bottom_pane.apply_patch_hunk_edit(text);
+        self.request_redraw();
+    }
+
     pub(crate) fn external_editor_state(&self) -> ExternalEditorState {
         self.external_editor_state
     }
@@ -4237,6 +4258,7 @@ impl ChatWidget {
                             instructions: prepared_args,
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 });
                 self.bottom_pane.drain_pending_submission_state();
@@ -4834,6 +4856,9 @@ impl ChatWidget {
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
+            EventMsg::ListMcpPromptsResponse(ev) => self.on_list_mcp_prompts(ev),
+            EventMsg::McpPromptListChanged(ev) => self.on_mcp_prompt_list_changed(ev),
+            EventMsg::GetMcpPromptResponse(ev) => self.on_get_mcp_prompt_response(ev),
             EventMsg::PromptTraceResponse(ev) => self.on_prompt_trace_response(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
             EventMsg::ListRemoteSkillsResponse(_) | EventMsg::RemoteSkillDownloaded(_) => {}
@@ -8146,6 +8171,46 @@ impl ChatWidget {
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
     }
 
+    fn on_list_mcp_prompts(&mut self, ev: ListMcpPromptsResponseEvent) {
+        let prompts = ev
+            .prompts
+            .into_iter()
+            .flat_map(|(server, prompts)| {
+                prompts.into_iter().map(move |prompt| McpPromptCommand {
+                    server: server.clone(),
+                    prompt,
+                })
+            })
+            .collect::<Vec<_>>();
+        debug!("received {} MCP prompts", prompts.len());
+        self.bottom_pane.set_mcp_prompts(prompts);
+    }
+
+    fn on_mcp_prompt_list_changed(&mut self, ev: McpPromptListChangedEvent) {
+        self.bottom_pane
+            .set_mcp_server_prompts(&ev.server, ev.prompts);
+    }
+
+    /// Puts a fetched MCP prompt into the composer so it can be edited before sending.
+    fn on_get_mcp_prompt_response(&mut self, ev: GetMcpPromptResponseEvent) {
+        match ev.result {
+            Ok(text) => {
+                if self.bottom_pane.composer_is_empty() {
+                    self.set_composer_text(text, Vec::new(), Vec::new());
+                } else {
+                    self.insert_str(&text);
+                }
+            }
+            Err(err) => {
+                self.add_to_history(history_cell::new_error_event(format!(
+                    "Failed to get prompt '{}' from MCP server '{}': {err}",
+                    ev.name, ev.server
+                )));
+            }
+        }
+        self.request_redraw();
+    }
+
     fn on_prompt_trace_response(&mut self, ev: PromptTraceResponseEvent) {
         self.add_to_history(history_cell::new_prompt_trace_output(ev));
         self.request_redraw();
@@ -8273,6 +8338,7 @@ impl ChatWidget {
                     review_request: ReviewRequest {
                         target: ReviewTarget::UncommittedChanges,
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 }));
             })],
@@ -8293,6 +8359,24 @@ impl ChatWidget {
             ..Default::default()
         });
 
+        items.push(SelectionItem {
+            name: "Review a commit range".to_string(),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenReviewCommitRangePrompt);
+            })],
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
+        items.push(SelectionItem {
+            name: "Review a patch file".to_string(),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenReviewPatchFilePrompt);
+            })],
+            dismiss_on_select: false,
+            ..Default::default()
+        });
+
         items.push(SelectionItem {
             name: "Custom review instructions".to_string(),
             actions: vec![Box::new(move |tx| {
@@ -8328,6 +8412,7 @@ impl ChatWidget {
                                 branch: branch.clone(),
                             },
                             user_facing_hint: None,
+                            paths: Vec::new(),
                         },
                     }));
                 })],
@@ -8366,6 +8451,7 @@ impl ChatWidget {
                                 title: Some(subject.clone()),
                             },
                             user_facing_hint: None,
+                            paths: Vec::new(),
                         },
                     }));
                 })],
@@ -8402,6 +8488,62 @@ impl ChatWidget {
                             instructions: trimmed,
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
+                    },
+                }));
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn show_review_commit_range_prompt(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            "Review a commit range".to_string(),
+            "FROM..TO [pathspec ...], e.g. main..HEAD src/api/**".to_string(),
+            None,
+            Box::new(move |input: String| {
+                let Some((range, paths)) = split_review_pathspecs(&input) else {
+                    return;
+                };
+                let Some((from, to)) = range.split_once("..") else {
+                    return;
+                };
+                if from.is_empty() || to.is_empty() || to.starts_with('.') {
+                    return;
+                }
+                tx.send(AppEvent::CodexOp(Op::Review {
+                    review_request: ReviewRequest {
+                        target: ReviewTarget::CommitRange {
+                            from: from.to_string(),
+                            to: to.to_string(),
+                        },
+                        user_facing_hint: None,
+                        paths,
+                    },
+                }));
+            }),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn show_review_patch_file_prompt(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            "Review a patch file".to_string(),
+            "FILE [pathspec ...], e.g. changes.patch".to_string(),
+            None,
+            Box::new(move |input: String| {
+                let Some((path, paths)) = split_review_pathspecs(&input) else {
+                    return;
+                };
+                tx.send(AppEvent::CodexOp(Op::Review {
+                    review_request: ReviewRequest {
+                        target: ReviewTarget::PatchFile {
+                            path: PathBuf::from(path),
+                        },
+                        user_facing_hint: None,
+                        paths,
                     },
                 }));
             }),
@@ -8727,6 +8869,14 @@ async fn fetch_rate_limits(base_url: String, auth: CodexAuth) -> Vec<RateLimitSn
 }
 
 #[cfg(test)]
+/// Splits review prompt input into its leading target argument and any
+/// trailing git pathspecs that limit the review.
+fn split_review_pathspecs(input: &str) -> Option<(&str, Vec<String>)> {
+    let mut parts = input.split_whitespace();
+    let target = parts.next()?;
+    Some((target, parts.map(str::to_string).collect()))
+}
+
 pub(crate) fn show_review_commit_picker_with_entries(
     chat: &mut ChatWidget,
     entries: Vec<codex_core::git_info::CommitLogEntry>,
@@ -8747,6 +8897,7 @@ pub(crate) fn show_review_commit_picker_with_entries(
                             title: Some(subject.clone()),
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 }));
             })],
@@ -857,7 +857,8 @@ pub fn new_approval_decision_cell(
                 ],
             ),
         },
-        Denied => {
+        // Hunk decisions only apply to patches; commands are not run.
+        Denied | ApprovedHunks { .. } => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
                 "✗ ".red(),
@@ -2821,6 +2822,7 @@ mod tests {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         };
         let mut servers = config.mcp_servers.get().clone();
         servers.insert("docs".to_string(), stdio_config);
@@ -2845,6 +2847,7 @@ mod tests {
             disabled_tools: None,
             scopes: None,
             oauth_resource: None,
+            sampling_token_budget: None,
         };
         servers.insert("http".to_string(), http_config);
         config
@@ -21,7 +21,11 @@ use codex_core::ThreadsPage;
 use codex_core::config::Config;
 use codex_core::find_thread_names_by_ids;
 use codex_core::path_utils;
+use codex_core::state_db::get_state_db;
+use codex_core::state_db::search_threads_db;
 use codex_protocol::ThreadId;
+use codex_state::ThreadMetadata;
+use codex_state::ThreadSearchHit;
 use color_eyre::eyre::Result;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -39,6 +43,9 @@ use unicode_width::UnicodeWidthStr;
 
 const PAGE_SIZE: usize = 25;
 const LOAD_NEAR_THRESHOLD: usize = 5;
+/// Maximum number of threads requested per full-text search query. Only the best match of
+/// each thread is shown.
+const CONTENT_SEARCH_MAX_THREADS: usize = 100;
 
 #[derive(Debug, Clone)]
 pub struct SessionTarget {
@@ -95,12 +102,19 @@ struct PageLoadRequest {
 
 type PageLoader = Arc<dyn Fn(PageLoadRequest) + Send + Sync>;
 
+/// Runs a full-text search over past session messages for the given query.
+type ContentSearchLoader = Arc<dyn Fn(String) + Send + Sync>;
+
 enum BackgroundEvent {
     PageLoaded {
         request_token: usize,
         search_token: Option<usize>,
         page: std::io::Result<ThreadsPage>,
     },
+    ContentSearchLoaded {
+        query: String,
+        rows: Vec<Row>,
+    },
 }
 
 /// Interactive session picker that lists recorded rollout files with simple
@@ -119,6 +133,11 @@ enum BackgroundEvent {
 /// 1. Provider and source filtering at the backend (only interactive CLI sessions
 ///    for the current model provider).
 /// 2. Working-directory filtering at the picker (unless `--all` is passed).
+///
+/// Typing a query filters loaded sessions by preview and thread name, and also
+/// runs a full-text search over past messages in the SQLite state DB. Sessions
+/// matched by message content are shown with the best matching snippet, even if
+/// they have not been paged in yet.
 pub async fn run_resume_picker(
     tui: &mut Tui,
     config: &Config,
@@ -153,6 +172,32 @@ async fn run_session_picker(
     };
 
     let config = config.clone();
+    let search_tx = bg_tx.clone();
+    let search_config = config.clone();
+    let search_provider = default_provider.clone();
+    let content_search_loader: ContentSearchLoader = Arc::new(move |query: String| {
+        let tx = search_tx.clone();
+        let config = search_config.clone();
+        let provider_filter = vec![search_provider.clone()];
+        tokio::spawn(async move {
+            let state_db_ctx = get_state_db(&config).await;
+            let results = search_threads_db(
+                state_db_ctx.as_deref(),
+                &query,
+                CONTENT_SEARCH_MAX_THREADS,
+                1,
+                INTERACTIVE_SESSION_SOURCES,
+                Some(provider_filter.as_slice()),
+            )
+            .await
+            .unwrap_or_default();
+            let rows = results
+                .into_iter()
+                .map(|(metadata, hits)| metadata_to_search_row(metadata, &hits))
+                .collect();
+            let _ = tx.send(BackgroundEvent::ContentSearchLoaded { query, rows });
+        });
+    });
     let loader_tx = bg_tx.clone();
     let page_loader: PageLoader = Arc::new(move |request: PageLoadRequest| {
         let tx = loader_tx.clone();
@@ -187,6 +232,7 @@ async fn run_session_picker(
         filter_cwd,
         action,
     );
+    state.content_search_loader = Some(content_search_loader);
     state.start_initial_load();
     state.request_frame();
 
@@ -264,6 +310,11 @@ struct PickerState {
     scroll_top: usize,
     query: String,
     search_state: SearchState,
+    /// Sessions whose messages match `query`, best match first.
+    content_matches: Vec<Row>,
+    /// Query of the in-flight full-text search, if any.
+    pending_content_query: Option<String>,
+    content_search_loader: Option<ContentSearchLoader>,
     next_request_token: usize,
     next_search_token: usize,
     page_loader: PageLoader,
@@ -336,6 +387,8 @@ struct Row {
     updated_at: Option<DateTime<Utc>>,
     cwd: Option<PathBuf>,
     git_branch: Option<String>,
+    /// Best matching message excerpt when the row matched a full-text search.
+    search_snippet: Option<String>,
 }
 
 impl Row {
@@ -382,6 +435,9 @@ impl PickerState {
             scroll_top: 0,
             query: String::new(),
             search_state: SearchState::Idle,
+            content_matches: Vec::new(),
+            pending_content_query: None,
+            content_search_loader: None,
             next_request_token: 0,
             next_search_token: 0,
             page_loader,
@@ -540,6 +596,14 @@ impl PickerState {
                 let completed_token = pending.search_token.or(search_token);
                 self.continue_search_if_token_matches(completed_token);
             }
+            BackgroundEvent::ContentSearchLoaded { query, rows } => {
+                if self.pending_content_query.as_deref() != Some(query.as_str()) {
+                    return Ok(());
+                }
+                self.pending_content_query = None;
+                self.content_matches = rows;
+                self.apply_filter();
+            }
         }
         Ok(())
     }
@@ -626,7 +690,31 @@ impl PickerState {
             self.filtered_rows = base_iter.cloned().collect();
         } else {
             let q = self.query.to_lowercase();
-            self.filtered_rows = base_iter.filter(|r| r.matches_query(&q)).cloned().collect();
+            let snippets: HashMap<ThreadId, &str> = self
+                .content_matches
+                .iter()
+                .filter_map(|row| Some((row.thread_id?, row.search_snippet.as_deref()?)))
+                .collect();
+            let mut filtered_rows: Vec<Row> = base_iter
+                .filter_map(|row| {
+                    let snippet = row.thread_id.and_then(|id| snippets.get(&id).copied());
+                    if snippet.is_none() && !row.matches_query(&q) {
+                        return None;
+                    }
+                    let mut row = row.clone();
+                    row.search_snippet = snippet.map(str::to_string);
+                    Some(row)
+                })
+                .collect();
+            // Content matches that have not been paged in yet go after the loaded rows.
+            filtered_rows.extend(
+                self.content_matches
+                    .iter()
+                    .filter(|row| !self.seen_paths.contains(&row.path))
+                    .filter(|row| self.row_matches_filter(row))
+                    .cloned(),
+            );
+            self.filtered_rows = filtered_rows;
         }
         if self.selected >= self.filtered_rows.len() {
             self.selected = self.filtered_rows.len().saturating_sub(1);
@@ -657,6 +745,8 @@ impl PickerState {
         }
         self.query = new_query;
         self.selected = 0;
+        self.content_matches.clear();
+        self.request_content_search();
         self.apply_filter();
         if self.query.is_empty() {
             self.search_state = SearchState::Idle;
@@ -675,6 +765,18 @@ impl PickerState {
         self.load_more_if_needed(LoadTrigger::Search { token });
     }
 
+    fn request_content_search(&mut self) {
+        let Some(loader) = self.content_search_loader.as_ref() else {
+            return;
+        };
+        if self.query.trim().is_empty() {
+            self.pending_content_query = None;
+            return;
+        }
+        self.pending_content_query = Some(self.query.clone());
+        loader(self.query.clone());
+    }
+
     fn continue_search_if_needed(&mut self) {
         let Some(token) = self.search_state.active_token() else {
             return;
@@ -844,6 +946,28 @@ fn head_to_row(item: &ThreadItem) -> Row {
         updated_at,
         cwd: item.cwd.clone(),
         git_branch: item.git_branch.clone(),
+        search_snippet: None,
+    }
+}
+
+fn metadata_to_search_row(metadata: ThreadMetadata, hits: &[ThreadSearchHit]) -> Row {
+    let preview = metadata
+        .first_user_message
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| String::from("(no message yet)"));
+    Row {
+        path: metadata.rollout_path,
+        preview,
+        thread_id: Some(metadata.id),
+        thread_name: None,
+        created_at: Some(metadata.created_at),
+        updated_at: Some(metadata.updated_at),
+        cwd: Some(metadata.cwd),
+        git_branch: metadata.git_branch,
+        search_snippet: hits.first().map(|hit| hit.snippet.clone()),
     }
 }
 
@@ -1029,7 +1153,13 @@ fn render_list(
         if add_leading_gap {
             preview_width = preview_width.saturating_sub(2);
         }
-        let preview = truncate_text(row.display_preview(), preview_width);
+        let preview = match row.search_snippet.as_deref() {
+            Some(snippet) => truncate_text(
+                &format!("{} · {snippet}", row.display_preview()),
+                preview_width,
+            ),
+            None => truncate_text(row.display_preview(), preview_width),
+        };
         let mut spans: Vec<Span> = vec![marker];
         if let Some(created) = created_span {
             spans.push(created);
@@ -1068,6 +1198,7 @@ fn render_list(
 fn render_empty_state_line(state: &PickerState) -> Line<'static> {
     if !state.query.is_empty() {
         if state.search_state.is_active()
+            || state.pending_content_query.is_some()
             || (state.pagination.loading.is_pending() && state.pagination.next_cursor.is_some())
         {
             return vec!["Searching…".italic().dim()].into();
@@ -1550,6 +1681,7 @@ mod tests {
             updated_at: None,
             cwd: None,
             git_branch: None,
+            search_snippet: None,
         };
 
         assert_eq!(row.display_preview(), "My session");
@@ -1584,6 +1716,7 @@ mod tests {
                 updated_at: Some(now - Duration::seconds(42)),
                 cwd: None,
                 git_branch: None,
+                search_snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1594,6 +1727,7 @@ mod tests {
                 updated_at: Some(now - Duration::minutes(35)),
                 cwd: None,
                 git_branch: None,
+                search_snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/c.jsonl"),
@@ -1604,6 +1738,7 @@ mod tests {
                 updated_at: Some(now - Duration::hours(2)),
                 cwd: None,
                 git_branch: None,
+                search_snippet: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -1898,6 +2033,7 @@ mod tests {
                 updated_at: Some(now - Duration::days(2)),
                 cwd: None,
                 git_branch: None,
+                search_snippet: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
@@ -1908,6 +2044,7 @@ mod tests {
                 updated_at: Some(now - Duration::days(3)),
                 cwd: None,
                 git_branch: None,
+                search_snippet: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -2121,6 +2258,94 @@ mod tests {
         assert_eq!(guard[1].sort_key, ThreadSortKey::CreatedAt);
     }
 
+    #[tokio::test]
+    async fn content_search_matches_are_merged_into_filtered_rows() {
+        let recorded_queries: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let query_sink = recorded_queries.clone();
+        let loader: PageLoader = Arc::new(|_| {});
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+            SessionPickerAction::Resume,
+        );
+        state.content_search_loader = Some(Arc::new(move |query: String| {
+            query_sink.lock().unwrap().push(query);
+        }));
+        let loaded_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000001").expect("thread id");
+        let mut loaded = make_item("/tmp/a.jsonl", "2025-01-01T00:00:00Z", "fix the build");
+        loaded.thread_id = Some(loaded_id);
+        state.reset_pagination();
+        state.ingest_page(page(vec![loaded], None, 1, false));
+
+        state
+            .handle_key(KeyEvent::new(KeyCode::Char('w'), KeyModifiers::NONE))
+            .await
+            .unwrap();
+        assert_eq!(*recorded_queries.lock().unwrap(), vec!["w".to_string()]);
+        assert!(state.filtered_rows.is_empty());
+        assert_eq!(
+            render_empty_state_line(&state).to_string(),
+            "Searching…".to_string()
+        );
+
+        let unloaded_id =
+            ThreadId::from_string("00000000-0000-0000-0000-000000000002").expect("thread id");
+        let search_row = |path: &str, thread_id: ThreadId, snippet: &str| Row {
+            path: PathBuf::from(path),
+            preview: String::from("preview"),
+            thread_id: Some(thread_id),
+            thread_name: None,
+            created_at: None,
+            updated_at: None,
+            cwd: None,
+            git_branch: None,
+            search_snippet: Some(snippet.to_string()),
+        };
+        // A stale result for an older query is ignored.
+        state
+            .handle_background_event(BackgroundEvent::ContentSearchLoaded {
+                query: String::from("x"),
+                rows: vec![search_row("/tmp/x.jsonl", unloaded_id, "[x]")],
+            })
+            .await
+            .unwrap();
+        assert!(state.filtered_rows.is_empty());
+
+        state
+            .handle_background_event(BackgroundEvent::ContentSearchLoaded {
+                query: String::from("w"),
+                rows: vec![
+                    search_row("/tmp/b.jsonl", unloaded_id, "the [websocket] test"),
+                    search_row("/tmp/a.jsonl", loaded_id, "[websocket] reconnect"),
+                ],
+            })
+            .await
+            .unwrap();
+        let rows = state
+            .filtered_rows
+            .iter()
+            .map(|row| (row.path.clone(), row.search_snippet.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    PathBuf::from("/tmp/a.jsonl"),
+                    Some(String::from("[websocket] reconnect"))
+                ),
+                (
+                    PathBuf::from("/tmp/b.jsonl"),
+                    Some(String::from("the [websocket] test"))
+                ),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn page_navigation_uses_view_rows() {
         let loader: PageLoader = Arc::new(|_| {});
@@ -2188,6 +2413,7 @@ mod tests {
             updated_at: None,
             cwd: None,
             git_branch: None,
+            search_snippet: None,
         };
         state.all_rows = vec![row.clone()];
         state.filtered_rows = vec![row];
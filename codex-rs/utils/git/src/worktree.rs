@@ -0,0 +1,269 @@
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::GitToolingError;
+use crate::operations::ensure_git_repository;
+use crate::operations::repo_subdir;
+use crate::operations::resolve_head;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_status;
+use crate::operations::run_git_for_stdout;
+use crate::operations::run_git_for_stdout_all;
+
+/// A linked git worktree checked out on a dedicated branch.
+///
+/// The worktree starts from the repository's `HEAD` commit; uncommitted changes
+/// in the original checkout are not carried over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Worktree {
+    /// Root of the repository the worktree was created from.
+    pub repo_root: PathBuf,
+    /// Root of the linked worktree.
+    pub path: PathBuf,
+    /// Branch checked out in the worktree.
+    pub branch: String,
+    /// Commit the branch was created from.
+    pub base_commit: String,
+    /// Location of the original cwd relative to the repository root, if any.
+    pub subdir: Option<PathBuf>,
+}
+
+/// Creates a worktree at `worktree_path` on a new `branch` that starts at the
+/// `HEAD` of the repository containing `repo_path`.
+pub fn create_worktree(
+    repo_path: &Path,
+    worktree_path: &Path,
+    branch: &str,
+) -> Result<Worktree, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let base_commit =
+        resolve_head(repo_root.as_path())?.ok_or_else(|| GitToolingError::MissingHead {
+            path: repo_root.clone(),
+        })?;
+    let subdir = repo_subdir(repo_root.as_path(), repo_path);
+    if let Some(parent) = worktree_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    run_git_for_status(
+        repo_root.as_path(),
+        vec![
+            OsString::from("worktree"),
+            OsString::from("add"),
+            OsString::from("-b"),
+            OsString::from(branch),
+            worktree_path.as_os_str().to_os_string(),
+            OsString::from(&base_commit),
+        ],
+        None,
+    )?;
+    Ok(Worktree {
+        repo_root,
+        path: worktree_path.to_path_buf(),
+        branch: branch.to_string(),
+        base_commit,
+        subdir,
+    })
+}
+
+impl Worktree {
+    /// Directory inside the worktree that corresponds to the original cwd.
+    pub fn cwd(&self) -> PathBuf {
+        match &self.subdir {
+            Some(subdir) => self.path.join(subdir),
+            None => self.path.clone(),
+        }
+    }
+
+    /// Returns a binary-safe diff of everything staged in the worktree since
+    /// [`Worktree::base_commit`], including commits made on the branch. Call
+    /// [`Worktree::stage_all`] first to include unstaged and untracked files.
+    /// Paths are relative to the repository root.
+    pub fn diff(&self) -> Result<String, GitToolingError> {
+        run_git_for_stdout_all(
+            self.path.as_path(),
+            vec![
+                OsString::from("diff"),
+                OsString::from("--cached"),
+                OsString::from("--binary"),
+                OsString::from(&self.base_commit),
+            ],
+            None,
+        )
+    }
+
+    /// Commits all pending changes in the worktree to its branch. Returns
+    /// `false` when there was nothing to commit.
+    pub fn commit_all(&self, message: &str) -> Result<bool, GitToolingError> {
+        self.stage_all()?;
+        let status = run_git_for_stdout(
+            self.path.as_path(),
+            vec![OsString::from("status"), OsString::from("--porcelain")],
+            None,
+        )?;
+        if status.is_empty() {
+            return Ok(false);
+        }
+        run_git_for_status(
+            self.path.as_path(),
+            vec![
+                OsString::from("commit"),
+                OsString::from("--no-verify"),
+                OsString::from("-m"),
+                OsString::from(message),
+            ],
+            Some(&commit_identity()),
+        )?;
+        Ok(true)
+    }
+
+    /// Whether the branch has any commits on top of [`Worktree::base_commit`].
+    pub fn has_commits(&self) -> Result<bool, GitToolingError> {
+        let count = run_git_for_stdout(
+            self.repo_root.as_path(),
+            vec![
+                OsString::from("rev-list"),
+                OsString::from("--count"),
+                OsString::from(format!("{}..{}", self.base_commit, self.branch)),
+            ],
+            None,
+        )?;
+        Ok(count != "0")
+    }
+
+    /// Removes the worktree directory, discarding uncommitted changes, and
+    /// optionally deletes its branch.
+    pub fn remove(&self, delete_branch: bool) -> Result<(), GitToolingError> {
+        run_git_for_status(
+            self.repo_root.as_path(),
+            vec![
+                OsString::from("worktree"),
+                OsString::from("remove"),
+                OsString::from("--force"),
+                self.path.as_os_str().to_os_string(),
+            ],
+            None,
+        )?;
+        if delete_branch {
+            run_git_for_status(
+                self.repo_root.as_path(),
+                vec![
+                    OsString::from("branch"),
+                    OsString::from("-D"),
+                    OsString::from(&self.branch),
+                ],
+                None,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Stages every change in the worktree, including untracked files.
+    pub fn stage_all(&self) -> Result<(), GitToolingError> {
+        run_git_for_status(
+            self.path.as_path(),
+            vec![OsString::from("add"), OsString::from("--all")],
+            None,
+        )
+    }
+}
+
+/// Identity used for commits made on behalf of an agent, so that committing
+/// works even when the user has not configured one.
+fn commit_identity() -> Vec<(OsString, OsString)> {
+    vec![
+        (
+            OsString::from("GIT_AUTHOR_NAME"),
+            OsString::from("Codex Agent"),
+        ),
+        (
+            OsString::from("GIT_AUTHOR_EMAIL"),
+            OsString::from("agent@codex.local"),
+        ),
+        (
+            OsString::from("GIT_COMMITTER_NAME"),
+            OsString::from("Codex Agent"),
+        ),
+        (
+            OsString::from("GIT_COMMITTER_EMAIL"),
+            OsString::from("agent@codex.local"),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+
+    fn run_git_in(repo_path: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(repo_path)
+            .args(args)
+            .status()
+            .expect("git command");
+        assert!(status.success(), "git command failed: {args:?}");
+    }
+
+    fn init_test_repo(repo: &Path) {
+        run_git_in(repo, &["init", "--initial-branch=main"]);
+        run_git_in(repo, &["config", "core.autocrlf", "false"]);
+        run_git_in(repo, &["config", "user.name", "Tester"]);
+        run_git_in(repo, &["config", "user.email", "test@example.com"]);
+    }
+
+    #[test]
+    fn worktree_diff_commit_and_remove() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(repo.join("src"))?;
+        init_test_repo(&repo);
+        std::fs::write(repo.join("src/lib.rs"), "fn a() {}\n")?;
+        run_git_in(&repo, &["add", "."]);
+        run_git_in(&repo, &["commit", "-m", "init"]);
+
+        let worktree_path = temp.path().join("worktrees").join("agent");
+        let worktree = create_worktree(&repo.join("src"), &worktree_path, "codex/agent-test")?;
+        assert_eq!(worktree.cwd(), worktree_path.join("src"));
+        assert_eq!(worktree.diff()?, "");
+        assert!(!worktree.commit_all("nothing")?);
+
+        std::fs::write(worktree_path.join("src/lib.rs"), "fn b() {}\n")?;
+        std::fs::write(worktree_path.join("new.txt"), "hello\n")?;
+        assert_eq!(worktree.diff()?, "");
+        worktree.stage_all()?;
+        let diff = worktree.diff()?;
+        assert!(diff.contains("-fn a() {}\n+fn b() {}"), "{diff}");
+        assert!(diff.contains("+++ b/new.txt"), "{diff}");
+        // The original checkout is untouched.
+        assert_eq!(
+            std::fs::read_to_string(repo.join("src/lib.rs"))?,
+            "fn a() {}\n"
+        );
+
+        assert!(worktree.commit_all("agent changes")?);
+        assert!(worktree.has_commits()?);
+        assert_eq!(worktree.diff()?, diff);
+
+        worktree.remove(false)?;
+        assert!(!worktree_path.exists());
+        run_git_in(&repo, &["rev-parse", "--verify", "codex/agent-test"]);
+        Ok(())
+    }
+
+    #[test]
+    fn create_worktree_requires_a_commit() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        init_test_repo(temp.path());
+        let result = create_worktree(
+            temp.path(),
+            &temp.path().join(".worktrees/agent"),
+            "codex/agent-test",
+        );
+        assert_matches!(result, Err(GitToolingError::MissingHead { .. }));
+        Ok(())
+    }
+}